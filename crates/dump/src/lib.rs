@@ -6,7 +6,9 @@ use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::Unchecked;
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task, TaskId};
+use meilisearch_types::tasks::{
+    Details, IndexSwap, KindWithContent, Priority, Status, Task, TaskId,
+};
 use meilisearch_types::InstanceUid;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
@@ -61,6 +63,9 @@ pub struct TaskDump {
     // The batch ID were introduced in v1.12, everything prior to this version will be `None`.
     #[serde(default)]
     pub batch_uid: Option<BatchId>,
+    // The task groups were introduced in v1.15, everything prior to this version will be `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_uid: Option<TaskId>,
     #[serde(default)]
     pub index_uid: Option<String>,
     pub status: Status,
@@ -69,6 +74,9 @@ pub struct TaskDump {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub canceled_by: Option<TaskId>,
+    // The priorities were introduced in v1.15, everything prior to this version is `Normal`.
+    #[serde(default, skip_serializing_if = "Priority::is_normal")]
+    pub priority: Priority,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<Details>,
@@ -82,6 +90,12 @@ pub struct TaskDump {
         skip_serializing_if = "Option::is_none",
         default
     )]
+    pub run_at: Option<OffsetDateTime>,
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
     pub started_at: Option<OffsetDateTime>,
     #[serde(
         with = "time::serde::rfc3339::option",
@@ -151,13 +165,16 @@ impl From<Task> for TaskDump {
         TaskDump {
             uid: task.uid,
             batch_uid: task.batch_uid,
+            group_uid: task.group_uid,
             index_uid: task.index_uid().map(|uid| uid.to_string()),
             status: task.status,
             kind: task.kind.into(),
             canceled_by: task.canceled_by,
+            priority: task.priority,
             details: task.details,
             error: task.error,
             enqueued_at: task.enqueued_at,
+            run_at: task.run_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
         }
@@ -237,7 +254,7 @@ pub(crate) mod test {
     use meilisearch_types::milli::{self, FilterableAttributesRule};
     use meilisearch_types::settings::{Checked, FacetingSettings, Settings};
     use meilisearch_types::task_view::DetailsView;
-    use meilisearch_types::tasks::{BatchStopReason, Details, Kind, Status};
+    use meilisearch_types::tasks::{BatchStopReason, Details, Kind, Priority, Status};
     use serde_json::{json, Map, Value};
     use time::macros::datetime;
     use uuid::Uuid;
@@ -304,7 +321,10 @@ pub(crate) mod test {
             search_cutoff_ms: Setting::NotSet,
             localized_attributes: Setting::NotSet,
             facet_search: Setting::NotSet,
+            keyword_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -344,6 +364,7 @@ pub(crate) mod test {
                 TaskDump {
                     uid: 0,
                     batch_uid: Some(0),
+                    group_uid: None,
                     index_uid: Some(S("doggo")),
                     status: Status::Succeeded,
                     kind: KindDump::DocumentImport {
@@ -353,12 +374,14 @@ pub(crate) mod test {
                         documents_count: 12,
                     },
                     canceled_by: None,
+                    priority: Priority::Normal,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
+                    run_at: None,
                     started_at: Some(datetime!(2022-11-20 0:00 UTC)),
                     finished_at: Some(datetime!(2022-11-21 0:00 UTC)),
                 },
@@ -368,6 +391,7 @@ pub(crate) mod test {
                 TaskDump {
                     uid: 1,
                     batch_uid: None,
+                    group_uid: None,
                     index_uid: Some(S("doggo")),
                     status: Status::Enqueued,
                     kind: KindDump::DocumentImport {
@@ -377,12 +401,14 @@ pub(crate) mod test {
                         documents_count: 2,
                     },
                     canceled_by: None,
+                    priority: Priority::Normal,
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
+                    run_at: None,
                     started_at: None,
                     finished_at: None,
                 },
@@ -395,13 +421,16 @@ pub(crate) mod test {
                 TaskDump {
                     uid: 5,
                     batch_uid: None,
+                    group_uid: None,
                     index_uid: Some(S("catto")),
                     status: Status::Enqueued,
                     kind: KindDump::IndexDeletion,
                     canceled_by: None,
+                    priority: Priority::Normal,
                     details: None,
                     error: None,
                     enqueued_at: datetime!(2022-11-15 0:00 UTC),
+                    run_at: None,
                     started_at: None,
                     finished_at: None,
                 },
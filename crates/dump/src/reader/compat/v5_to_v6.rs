@@ -71,6 +71,7 @@ impl CompatV5ToV6 {
                 let task = v6::Task {
                     uid: task_view.uid,
                     batch_uid: None,
+                    group_uid: None,
                     index_uid: task_view.index_uid,
                     status: match task_view.status {
                         v5::Status::Enqueued => v6::Status::Enqueued,
@@ -128,6 +129,7 @@ impl CompatV5ToV6 {
                         }
                     },
                     canceled_by: None,
+                    priority: v6::Priority::Normal,
                     details: task_view.details.map(|details| match details {
                         v5::Details::DocumentAddition { received_documents, indexed_documents } => {
                             v6::Details::DocumentAdditionOrUpdate {
@@ -157,6 +159,7 @@ impl CompatV5ToV6 {
                     }),
                     error: task_view.error.map(|e| e.into()),
                     enqueued_at: task_view.enqueued_at,
+                    run_at: None,
                     started_at: task_view.started_at,
                     finished_at: task_view.finished_at,
                 };
@@ -396,7 +399,10 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             localized_attributes: v6::Setting::NotSet,
             search_cutoff_ms: v6::Setting::NotSet,
             facet_search: v6::Setting::NotSet,
+            keyword_search: v6::Setting::NotSet,
             prefix_search: v6::Setting::NotSet,
+            rules: v6::Setting::NotSet,
+            rerankers: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
         }
     }
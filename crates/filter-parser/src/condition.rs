@@ -7,12 +7,14 @@
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::multispace1;
-use nom::combinator::cut;
-use nom::sequence::{terminated, tuple};
+use nom::character::complete::{char, multispace0, multispace1};
+use nom::combinator::{cut, opt};
+use nom::sequence::{delimited, terminated, tuple};
 use Condition::*;
 
-use crate::{parse_value, FilterCondition, IResult, Span, Token};
+use crate::error::{ErrorKind, NomErrorExt};
+use crate::value::quoted_by;
+use crate::{parse_value, Error, FilterCondition, IResult, Span, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Condition<'a> {
@@ -26,8 +28,10 @@ pub enum Condition<'a> {
     LowerThan(Token<'a>),
     LowerThanOrEqual(Token<'a>),
     Between { from: Token<'a>, to: Token<'a> },
-    Contains { keyword: Token<'a>, word: Token<'a> },
-    StartsWith { keyword: Token<'a>, word: Token<'a> },
+    Contains { keyword: Token<'a>, word: Token<'a>, case_sensitive: bool },
+    StartsWith { keyword: Token<'a>, word: Token<'a>, case_sensitive: bool },
+    EndsWith { keyword: Token<'a>, word: Token<'a>, case_sensitive: bool },
+    Matches { keyword: Token<'a>, regex: Token<'a>, case_sensitive: bool },
 }
 
 impl Condition<'_> {
@@ -45,6 +49,8 @@ impl Condition<'_> {
             Condition::Between { .. } => "TO",
             Condition::Contains { .. } => "CONTAINS",
             Condition::StartsWith { .. } => "STARTS WITH",
+            Condition::EndsWith { .. } => "ENDS WITH",
+            Condition::Matches { .. } => "MATCHES",
         }
     }
 }
@@ -113,58 +119,175 @@ pub fn parse_not_exists(input: Span) -> IResult<FilterCondition> {
     Ok((input, FilterCondition::Not(Box::new(FilterCondition::Condition { fid: key, op: Exists }))))
 }
 
-/// contains        = value "CONTAINS" value
+/// case           = "CASE" WS+ ("SENSITIVE" | "INSENSITIVE") WS*
+///
+/// Returns `true` when the string operator must be evaluated case sensitively.
+/// String operators are case insensitive when this suffix is omitted.
+fn parse_case_sensitivity(input: Span) -> IResult<bool> {
+    let sensitivity = alt((tag("SENSITIVE"), tag("INSENSITIVE")));
+    let (input, case) = opt(tuple((tag("CASE"), multispace1, sensitivity, multispace0)))(input)?;
+    Ok((input, case.is_some_and(|(_, _, sensitivity, _)| *sensitivity.fragment() == "SENSITIVE")))
+}
+
+/// regex          = WS* "/" .* all but unescaped slashes "/" WS*
+fn parse_regex(input: Span) -> IResult<Token> {
+    let (input, _) = multispace0(input)?;
+    terminated(
+        delimited(char('/'), cut(|input| quoted_by('/', input)), cut(char('/'))),
+        multispace0,
+    )(input)
+    .map_err(|e| {
+        e.map_fail(|failure| {
+            // if we encountered a char failure it means the user forgot to close the regex
+            if matches!(failure.kind(), ErrorKind::Char(_)) {
+                Error::new_from_kind(input, ErrorKind::MissingClosingDelimiter(failure.char()))
+            } else {
+                failure
+            }
+        })
+    })
+}
+
+/// contains        = value "CONTAINS" value case?
 pub fn parse_contains(input: Span) -> IResult<FilterCondition> {
-    let (input, (fid, contains, value)) =
-        tuple((parse_value, tag("CONTAINS"), cut(parse_value)))(input)?;
+    let (input, (fid, contains, value, case_sensitive)) =
+        tuple((parse_value, tag("CONTAINS"), cut(parse_value), parse_case_sensitivity))(input)?;
     Ok((
         input,
         FilterCondition::Condition {
             fid,
-            op: Contains { keyword: Token { span: contains, value: None }, word: value },
+            op: Contains {
+                keyword: Token { span: contains, value: None },
+                word: value,
+                case_sensitive,
+            },
         },
     ))
 }
 
-/// contains        = value "NOT" WS+ "CONTAINS" value
+/// contains        = value "NOT" WS+ "CONTAINS" value case?
 pub fn parse_not_contains(input: Span) -> IResult<FilterCondition> {
     let keyword = tuple((tag("NOT"), multispace1, tag("CONTAINS")));
-    let (input, (fid, (_not, _spaces, contains), value)) =
-        tuple((parse_value, keyword, cut(parse_value)))(input)?;
+    let (input, (fid, (_not, _spaces, contains), value, case_sensitive)) =
+        tuple((parse_value, keyword, cut(parse_value), parse_case_sensitivity))(input)?;
 
     Ok((
         input,
         FilterCondition::Not(Box::new(FilterCondition::Condition {
             fid,
-            op: Contains { keyword: Token { span: contains, value: None }, word: value },
+            op: Contains {
+                keyword: Token { span: contains, value: None },
+                word: value,
+                case_sensitive,
+            },
         })),
     ))
 }
 
-/// starts with        = value "CONTAINS" value
+/// starts with        = value "STARTS WITH" value case?
 pub fn parse_starts_with(input: Span) -> IResult<FilterCondition> {
-    let (input, (fid, starts_with, value)) =
-        tuple((parse_value, tag("STARTS WITH"), cut(parse_value)))(input)?;
+    let (input, (fid, starts_with, value, case_sensitive)) =
+        tuple((parse_value, tag("STARTS WITH"), cut(parse_value), parse_case_sensitivity))(input)?;
     Ok((
         input,
         FilterCondition::Condition {
             fid,
-            op: StartsWith { keyword: Token { span: starts_with, value: None }, word: value },
+            op: StartsWith {
+                keyword: Token { span: starts_with, value: None },
+                word: value,
+                case_sensitive,
+            },
         },
     ))
 }
 
-/// starts with        = value "NOT" WS+ "CONTAINS" value
+/// starts with        = value "NOT" WS+ "STARTS WITH" value case?
 pub fn parse_not_starts_with(input: Span) -> IResult<FilterCondition> {
     let keyword = tuple((tag("NOT"), multispace1, tag("STARTS WITH")));
-    let (input, (fid, (_not, _spaces, starts_with), value)) =
-        tuple((parse_value, keyword, cut(parse_value)))(input)?;
+    let (input, (fid, (_not, _spaces, starts_with), value, case_sensitive)) =
+        tuple((parse_value, keyword, cut(parse_value), parse_case_sensitivity))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition {
+            fid,
+            op: StartsWith {
+                keyword: Token { span: starts_with, value: None },
+                word: value,
+                case_sensitive,
+            },
+        })),
+    ))
+}
+
+/// ends with        = value "ENDS WITH" value case?
+pub fn parse_ends_with(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, ends_with, value, case_sensitive)) =
+        tuple((parse_value, tag("ENDS WITH"), cut(parse_value), parse_case_sensitivity))(input)?;
+    Ok((
+        input,
+        FilterCondition::Condition {
+            fid,
+            op: EndsWith {
+                keyword: Token { span: ends_with, value: None },
+                word: value,
+                case_sensitive,
+            },
+        },
+    ))
+}
+
+/// ends with        = value "NOT" WS+ "ENDS WITH" value case?
+pub fn parse_not_ends_with(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("NOT"), multispace1, tag("ENDS WITH")));
+    let (input, (fid, (_not, _spaces, ends_with), value, case_sensitive)) =
+        tuple((parse_value, keyword, cut(parse_value), parse_case_sensitivity))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition {
+            fid,
+            op: EndsWith {
+                keyword: Token { span: ends_with, value: None },
+                word: value,
+                case_sensitive,
+            },
+        })),
+    ))
+}
+
+/// matches        = value "MATCHES" (regex | value) case?
+pub fn parse_matches(input: Span) -> IResult<FilterCondition> {
+    let (input, (fid, matches, regex, case_sensitive)) = tuple((
+        parse_value,
+        tag("MATCHES"),
+        cut(alt((parse_regex, parse_value))),
+        parse_case_sensitivity,
+    ))(input)?;
+    Ok((
+        input,
+        FilterCondition::Condition {
+            fid,
+            op: Matches { keyword: Token { span: matches, value: None }, regex, case_sensitive },
+        },
+    ))
+}
+
+/// matches        = value "NOT" WS+ "MATCHES" (regex | value) case?
+pub fn parse_not_matches(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("NOT"), multispace1, tag("MATCHES")));
+    let (input, (fid, (_not, _spaces, matches), regex, case_sensitive)) = tuple((
+        parse_value,
+        keyword,
+        cut(alt((parse_regex, parse_value))),
+        parse_case_sensitivity,
+    ))(input)?;
 
     Ok((
         input,
         FilterCondition::Not(Box::new(FilterCondition::Condition {
             fid,
-            op: StartsWith { keyword: Token { span: starts_with, value: None }, word: value },
+            op: Matches { keyword: Token { span: matches, value: None }, regex, case_sensitive },
         })),
     ))
 }
@@ -6,12 +6,18 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | geoBoundingBox | geoPolygon | geoIntersects | in | condition | exists | not_exists | to | contains | starts_with | ends_with | matches
 //! in             = value "IN" WS* "[" value_list "]"
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
 //! exists         = value "EXISTS"
 //! not_exists     = value "NOT" WS+ "EXISTS"
 //! to             = value value "TO" WS+ value
+//! contains       = value ("NOT" WS+)? "CONTAINS" value case?
+//! starts_with    = value ("NOT" WS+)? "STARTS WITH" value case?
+//! ends_with      = value ("NOT" WS+)? "ENDS WITH" value case?
+//! matches        = value ("NOT" WS+)? "MATCHES" (regex | value) case?
+//! case           = "CASE" WS+ ("SENSITIVE" | "INSENSITIVE") WS*
+//! regex          = WS* "/" .* all but unescaped slashes "/" WS*
 //! value          = WS* ( word | singleQuoted | doubleQuoted) WS+
 //! value_list     = (value ("," value)* ","?)?
 //! singleQuoted   = "'" .* all but quotes "'"
@@ -19,6 +25,8 @@
 //! word           = (alphanumeric | _ | - | .)+
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! geoPolygon     = "_geoPolygon([" WS* "[" WS* float WS* "," WS* float WS* "]" (WS* "," WS* "[" WS* float WS* "," WS* float WS* "]")* WS* "])"
+//! geoIntersects  = "_geoIntersects([" WS* "[" WS* float WS* "," WS* float WS* "]" (WS* "," WS* "[" WS* float WS* "," WS* float WS* "]")* WS* "])"
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -48,8 +56,9 @@ use std::fmt::Debug;
 
 pub use condition::{parse_condition, parse_to, Condition};
 use condition::{
-    parse_contains, parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null,
-    parse_is_null, parse_not_contains, parse_not_exists, parse_not_starts_with, parse_starts_with,
+    parse_contains, parse_ends_with, parse_exists, parse_is_empty, parse_is_not_empty,
+    parse_is_not_null, parse_is_null, parse_matches, parse_not_contains, parse_not_ends_with,
+    parse_not_exists, parse_not_matches, parse_not_starts_with, parse_starts_with,
 };
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
@@ -145,6 +154,8 @@ pub enum FilterCondition<'a> {
     And(Vec<Self>),
     GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
     GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    GeoPolygon { points: Vec<[Token<'a>; 2]> },
+    GeoIntersects { points: Vec<[Token<'a>; 2]> },
 }
 
 pub enum TraversedElement<'a> {
@@ -166,8 +177,10 @@ impl<'a> FilterCondition<'a> {
                 | Condition::LowerThan(_)
                 | Condition::LowerThanOrEqual(_)
                 | Condition::Between { .. } => None,
-                Condition::Contains { keyword, word: _ }
-                | Condition::StartsWith { keyword, word: _ } => Some(keyword),
+                Condition::Contains { keyword, .. }
+                | Condition::StartsWith { keyword, .. }
+                | Condition::EndsWith { keyword, .. }
+                | Condition::Matches { keyword, .. } => Some(keyword),
             },
             FilterCondition::Not(this) => this.use_contains_operator(),
             FilterCondition::Or(seq) | FilterCondition::And(seq) => {
@@ -175,6 +188,8 @@ impl<'a> FilterCondition<'a> {
             }
             FilterCondition::GeoLowerThan { .. }
             | FilterCondition::GeoBoundingBox { .. }
+            | FilterCondition::GeoPolygon { .. }
+            | FilterCondition::GeoIntersects { .. }
             | FilterCondition::In { .. } => None,
         }
     }
@@ -222,6 +237,11 @@ impl<'a> FilterCondition<'a> {
                 None
             }
             FilterCondition::GeoLowerThan { point: [point, _], .. } if depth == 0 => Some(point),
+            FilterCondition::GeoPolygon { points } | FilterCondition::GeoIntersects { points }
+                if depth == 0 =>
+            {
+                points.first().map(|[lat, _]| lat)
+            }
             _ => None,
         }
     }
@@ -422,6 +442,58 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// Parses the list of `[latitude, longitude]` pairs given to the `keyword` geo operation.
+/// If we parse the `keyword` we MUST parse the rest of the expression.
+fn parse_geo_points<'a>(
+    input: Span<'a>,
+    keyword: &'static str,
+    error_kind: impl Fn() -> ErrorKind<'a>,
+) -> IResult<'a, Vec<[Token<'a>; 2]>> {
+    // we want to allow space BEFORE the keyword but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact(keyword))),
+        // if we were able to parse the keyword and can't parse the rest of the input we return a failure
+        cut(delimited(
+            char('('),
+            ws(delimited(
+                char('['),
+                separated_list1(
+                    tag(","),
+                    ws(delimited(
+                        char('['),
+                        separated_list1(tag(","), ws(recognize_float)),
+                        char(']'),
+                    )),
+                ),
+                char(']'),
+            )),
+            char(')'),
+        )),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, error_kind())));
+
+    let (rest, args) = parsed?;
+
+    if args.iter().any(|point| point.len() != 2) {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, error_kind())));
+    }
+
+    let points = args.into_iter().map(|point| [point[0].into(), point[1].into()]).collect();
+    Ok((rest, points))
+}
+
+/// geoPolygon      = WS* "_geoPolygon([[float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "], ...])"
+fn parse_geo_polygon(input: Span) -> IResult<FilterCondition> {
+    let (rest, points) = parse_geo_points(input, "_geoPolygon", || ErrorKind::GeoPolygon)?;
+    Ok((rest, FilterCondition::GeoPolygon { points }))
+}
+
+/// geoIntersects   = WS* "_geoIntersects([[float WS* "," WS* float WS* "], ...])"
+fn parse_geo_intersects(input: Span) -> IResult<FilterCondition> {
+    let (rest, points) = parse_geo_points(input, "_geoIntersects", || ErrorKind::GeoIntersects)?;
+    Ok((rest, FilterCondition::GeoIntersects { points }))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -477,7 +549,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | geoBoundingBox | geoPolygon | geoIntersects | condition | exists | not_exists | to | contains | starts_with | ends_with | matches
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
@@ -491,8 +563,8 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
                 Error::new_from_kind(input, ErrorKind::MissingClosingDelimiter(c.char()))
             }),
         ),
-        parse_geo_radius,
-        parse_geo_bounding_box,
+        // `alt` can only take up to 21 parsers, so the geo operations are grouped together
+        alt((parse_geo_radius, parse_geo_bounding_box, parse_geo_polygon, parse_geo_intersects)),
         parse_in,
         parse_not_in,
         parse_condition,
@@ -503,10 +575,17 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         parse_exists,
         parse_not_exists,
         parse_to,
-        parse_contains,
-        parse_not_contains,
-        parse_starts_with,
-        parse_not_starts_with,
+        // the string operations are grouped together for the same reason
+        alt((
+            parse_contains,
+            parse_not_contains,
+            parse_starts_with,
+            parse_not_starts_with,
+            parse_ends_with,
+            parse_not_ends_with,
+            parse_matches,
+            parse_not_matches,
+        )),
         // the next lines are only for error handling and are written at the end to have the less possible performance impact
         parse_geo,
         parse_geo_distance,
@@ -573,6 +652,20 @@ impl std::fmt::Display for FilterCondition<'_> {
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::GeoPolygon { points } => {
+                write!(f, "_geoPolygon([")?;
+                for [lat, lng] in points {
+                    write!(f, "[{lat}, {lng}], ")?;
+                }
+                write!(f, "])")
+            }
+            FilterCondition::GeoIntersects { points } => {
+                write!(f, "_geoIntersects([")?;
+                for [lat, lng] in points {
+                    write!(f, "[{lat}, {lng}], ")?;
+                }
+                write!(f, "])")
+            }
         }
     }
 }
@@ -590,12 +683,37 @@ impl std::fmt::Display for Condition<'_> {
             Condition::LowerThan(token) => write!(f, "< {token}"),
             Condition::LowerThanOrEqual(token) => write!(f, "<= {token}"),
             Condition::Between { from, to } => write!(f, "{from} TO {to}"),
-            Condition::Contains { word, keyword: _ } => write!(f, "CONTAINS {word}"),
-            Condition::StartsWith { word, keyword: _ } => write!(f, "STARTS WITH {word}"),
+            Condition::Contains { word, keyword: _, case_sensitive } => {
+                write!(f, "CONTAINS {word}")?;
+                write_case_sensitivity(f, *case_sensitive)
+            }
+            Condition::StartsWith { word, keyword: _, case_sensitive } => {
+                write!(f, "STARTS WITH {word}")?;
+                write_case_sensitivity(f, *case_sensitive)
+            }
+            Condition::EndsWith { word, keyword: _, case_sensitive } => {
+                write!(f, "ENDS WITH {word}")?;
+                write_case_sensitivity(f, *case_sensitive)
+            }
+            Condition::Matches { regex, keyword: _, case_sensitive } => {
+                write!(f, "MATCHES {regex}")?;
+                write_case_sensitivity(f, *case_sensitive)
+            }
         }
     }
 }
 
+fn write_case_sensitivity(
+    f: &mut std::fmt::Formatter<'_>,
+    case_sensitive: bool,
+) -> std::fmt::Result {
+    if case_sensitive {
+        write!(f, " CASE SENSITIVE")
+    } else {
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for Token<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{{{}}}", self.value())
@@ -713,6 +831,26 @@ pub mod tests {
         insta::assert_snapshot!(p("NOT subscribers NOT STARTS WITH 'hel'"), @"{subscribers} STARTS WITH {hel}");
         insta::assert_snapshot!(p("subscribers NOT   STARTS WITH 'hel'"), @"NOT ({subscribers} STARTS WITH {hel})");
 
+        // Test ENDS WITH + NOT ENDS WITH
+        insta::assert_snapshot!(p("subscribers ENDS WITH 'llo'"), @"{subscribers} ENDS WITH {llo}");
+        insta::assert_snapshot!(p("NOT subscribers ENDS WITH 'llo'"), @"NOT ({subscribers} ENDS WITH {llo})");
+        insta::assert_snapshot!(p("subscribers NOT ENDS WITH llo"), @"NOT ({subscribers} ENDS WITH {llo})");
+        insta::assert_snapshot!(p("NOT subscribers NOT ENDS WITH 'llo'"), @"{subscribers} ENDS WITH {llo}");
+
+        // Test MATCHES + NOT MATCHES
+        insta::assert_snapshot!(p("title MATCHES /^hel+o$/"), @"{title} MATCHES {^hel+o$}");
+        insta::assert_snapshot!(p(r"title MATCHES /a\/b\d/"), @r#"{title} MATCHES {a/b\d}"#);
+        insta::assert_snapshot!(p("title MATCHES 'hel+o'"), @"{title} MATCHES {hel+o}");
+        insta::assert_snapshot!(p("title NOT MATCHES /hel+o/"), @"NOT ({title} MATCHES {hel+o})");
+        insta::assert_snapshot!(p("NOT title MATCHES /hel+o/ AND price = 10"), @"AND[NOT ({title} MATCHES {hel+o}), {price} = {10}, ]");
+
+        // Test the case sensitivity of the string operators
+        insta::assert_snapshot!(p("title CONTAINS Hello CASE SENSITIVE"), @"{title} CONTAINS {Hello} CASE SENSITIVE");
+        insta::assert_snapshot!(p("title CONTAINS Hello CASE INSENSITIVE"), @"{title} CONTAINS {Hello}");
+        insta::assert_snapshot!(p("title NOT STARTS WITH 'Hel' CASE SENSITIVE"), @"NOT ({title} STARTS WITH {Hel} CASE SENSITIVE)");
+        insta::assert_snapshot!(p("title ENDS WITH llo CASE   SENSITIVE OR price = 10"), @"OR[{title} ENDS WITH {llo} CASE SENSITIVE, {price} = {10}, ]");
+        insta::assert_snapshot!(p("title MATCHES /^Hel+o$/ CASE SENSITIVE"), @"{title} MATCHES {^Hel+o$} CASE SENSITIVE");
+
         // Test nested NOT
         insta::assert_snapshot!(p("NOT NOT NOT NOT x = 5"), @"{x} = {5}");
         insta::assert_snapshot!(p("NOT NOT (NOT NOT x = 5)"), @"{x} = {5}");
@@ -727,6 +865,15 @@ pub mod tests {
         insta::assert_snapshot!(p("NOT _geoBoundingBox([12, 13], [14, 15])"), @"NOT (_geoBoundingBox([{12}, {13}], [{14}, {15}]))");
         insta::assert_snapshot!(p("_geoBoundingBox([12,13],[14,15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
 
+        // Test geo polygon
+        insta::assert_snapshot!(p("_geoPolygon([[12, 13], [14, 15], [16, 17], [12, 13]])"), @"_geoPolygon([[{12}, {13}], [{14}, {15}], [{16}, {17}], [{12}, {13}], ])");
+        insta::assert_snapshot!(p("NOT _geoPolygon([[12, 13], [14, 15], [16, 17], [12, 13]])"), @"NOT (_geoPolygon([[{12}, {13}], [{14}, {15}], [{16}, {17}], [{12}, {13}], ]))");
+        insta::assert_snapshot!(p("_geoPolygon([[12,13],[14,15],[16,17],[12,13]])"), @"_geoPolygon([[{12}, {13}], [{14}, {15}], [{16}, {17}], [{12}, {13}], ])");
+        insta::assert_snapshot!(p("_geoPolygon( [ [12, 13] , [14, 15] , [16, 17] ] )"), @"_geoPolygon([[{12}, {13}], [{14}, {15}], [{16}, {17}], ])");
+        insta::assert_snapshot!(p("_geoIntersects([[12, 13]])"), @"_geoIntersects([[{12}, {13}], ])");
+        insta::assert_snapshot!(p("NOT _geoIntersects([[12, 13], [14, 15]])"), @"NOT (_geoIntersects([[{12}, {13}], [{14}, {15}], ]))");
+        insta::assert_snapshot!(p("_geoIntersects( [ [12, 13] , [14, 15] , [16, 17] , [12, 13] ] )"), @"_geoIntersects([[{12}, {13}], [{14}, {15}], [{16}, {17}], [{12}, {13}], ])");
+
         // Test OR + AND
         insta::assert_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
         insta::assert_snapshot!(p("channel = ponce OR 'dog race' != 'bernese mountain'"), @"OR[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -784,7 +931,7 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("'OR'"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `\'OR\'`.
         1:5 'OR'
         "###);
 
@@ -794,12 +941,12 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("channel Ponce"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `channel Ponce`.
         1:14 channel Ponce
         "###);
 
         insta::assert_snapshot!(p("channel = Ponce OR"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` but instead got nothing.
         19:19 channel = Ponce OR
         "###);
 
@@ -828,33 +975,79 @@ pub mod tests {
         1:26 _geoBoundingBox(1.0, 1.0)
         "###);
 
+        insta::assert_snapshot!(p("_geoPolygon"), @r###"
+        The `_geoPolygon` filter expects a list of pairs of arguments: `_geoPolygon([[latitude, longitude], [latitude, longitude], ...])`.
+        1:12 _geoPolygon
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon = 12"), @r###"
+        The `_geoPolygon` filter expects a list of pairs of arguments: `_geoPolygon([[latitude, longitude], [latitude, longitude], ...])`.
+        1:17 _geoPolygon = 12
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([1.0, 1.0], [2.0, 2.0], [3.0, 1.0])"), @r###"
+        The `_geoPolygon` filter expects a list of pairs of arguments: `_geoPolygon([[latitude, longitude], [latitude, longitude], ...])`.
+        1:48 _geoPolygon([1.0, 1.0], [2.0, 2.0], [3.0, 1.0])
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([[1.0, 1.0], [2.0, 2.0, 3.0], [3.0, 1.0]])"), @r###"
+        The `_geoPolygon` filter expects a list of pairs of arguments: `_geoPolygon([[latitude, longitude], [latitude, longitude], ...])`.
+        1:55 _geoPolygon([[1.0, 1.0], [2.0, 2.0, 3.0], [3.0, 1.0]])
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([])"), @r###"
+        The `_geoPolygon` filter expects a list of pairs of arguments: `_geoPolygon([[latitude, longitude], [latitude, longitude], ...])`.
+        1:16 _geoPolygon([])
+        "###);
+
+        insta::assert_snapshot!(p("_geoIntersects"), @r###"
+        The `_geoIntersects` filter expects a list of pairs of arguments: `_geoIntersects([[latitude, longitude], [latitude, longitude], ...])`.
+        1:15 _geoIntersects
+        "###);
+        insta::assert_snapshot!(p("_geoIntersects([12, 13])"), @r###"
+        The `_geoIntersects` filter expects a list of pairs of arguments: `_geoIntersects([[latitude, longitude], [latitude, longitude], ...])`.
+        1:25 _geoIntersects([12, 13])
+        "###);
+        insta::assert_snapshot!(p("_geoIntersects([[1.0, 1.0, 1.0]])"), @r###"
+        The `_geoIntersects` filter expects a list of pairs of arguments: `_geoIntersects([[latitude, longitude], [latitude, longitude], ...])`.
+        1:34 _geoIntersects([[1.0, 1.0, 1.0]])
+        "###);
+        insta::assert_snapshot!(p("position = _geoIntersects([[1.0, 1.0]])"), @r###"
+        The `_geoIntersects` filter is an operation and can't be used as a value.
+        12:40 position = _geoIntersects([[1.0, 1.0]])
+        "###);
+        insta::assert_snapshot!(p("position = _geoPolygon([[1.0, 1.0], [2.0, 2.0], [3.0, 1.0]])"), @r###"
+        The `_geoPolygon` filter is an operation and can't be used as a value.
+        12:61 position = _geoPolygon([[1.0, 1.0], [2.0, 2.0], [3.0, 1.0]])
+        "###);
+
         insta::assert_snapshot!(p("_geoPoint(12, 13, 14)"), @r###"
-        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` coordinates.
         1:22 _geoPoint(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geoPoint(12, 13, 14)"), @r###"
-        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` coordinates.
         13:34 position <= _geoPoint(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("_geoDistance(12, 13, 14)"), @r###"
-        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` coordinates.
         1:25 _geoDistance(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geoDistance(12, 13, 14)"), @r###"
-        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` coordinates.
         13:37 position <= _geoDistance(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("_geo(12, 13, 14)"), @r###"
-        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` coordinates.
         1:17 _geo(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geo(12, 13, 14)"), @r###"
-        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` coordinates.
         13:29 position <= _geo(12, 13, 14)
         "###);
 
@@ -884,12 +1077,12 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("colour NOT EXIST"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `colour NOT EXIST`.
         1:17 colour NOT EXIST
         "###);
 
         insta::assert_snapshot!(p("subscribers 100 TO1000"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `subscribers 100 TO1000`.
         1:23 subscribers 100 TO1000
         "###);
 
@@ -952,37 +1145,50 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p(r#"value NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `value NULL`.
         1:11 value NULL
         "###);
         insta::assert_snapshot!(p(r#"value NOT NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NOT NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `value NOT NULL`.
         1:15 value NOT NULL
         "###);
         insta::assert_snapshot!(p(r#"value EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `value EMPTY`.
         1:12 value EMPTY
         "###);
         insta::assert_snapshot!(p(r#"value NOT EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value NOT EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `value NOT EMPTY`.
         1:16 value NOT EMPTY
         "###);
         insta::assert_snapshot!(p(r#"value IS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `value IS`.
         1:9 value IS
         "###);
         insta::assert_snapshot!(p(r#"value IS NOT"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `value IS NOT`.
         1:13 value IS NOT
         "###);
         insta::assert_snapshot!(p(r#"value IS EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `value IS EXISTS`.
         1:16 value IS EXISTS
         "###);
         insta::assert_snapshot!(p(r#"value IS NOT EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `MATCHES`, `NOT MATCHES`, `_geoRadius`, `_geoBoundingBox`, `_geoPolygon`, or `_geoIntersects` at `value IS NOT EXISTS`.
         1:20 value IS NOT EXISTS
         "###);
+
+        insta::assert_snapshot!(p("title MATCHES /hello"), @r###"
+        Expression `/hello` is missing the following closing delimiter: `/`.
+        15:21 title MATCHES /hello
+        "###);
+        insta::assert_snapshot!(p("title ENDS WITH"), @r###"
+        Was expecting a value but instead got nothing.
+        16:16 title ENDS WITH
+        "###);
+        insta::assert_snapshot!(p("title CONTAINS hello CASE"), @r###"
+        Found unexpected characters at the end of the filter: `CASE`. You probably forgot an `OR` or an `AND` rule.
+        22:26 title CONTAINS hello CASE
+        "###);
     }
 
     #[test]
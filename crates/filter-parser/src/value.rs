@@ -7,8 +7,8 @@ use nom::{InputIter, InputLength, InputTake, Slice};
 
 use crate::error::{ExpectedValueKind, NomErrorExt};
 use crate::{
-    parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_point, parse_geo_radius,
-    Error, ErrorKind, IResult, Span, Token,
+    parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_intersects, parse_geo_point,
+    parse_geo_polygon, parse_geo_radius, Error, ErrorKind, IResult, Span, Token,
 };
 
 /// This function goes through all characters in the [Span] if it finds any escaped character (`\`).
@@ -19,7 +19,7 @@ fn unescape(buf: Span, char_to_escape: char) -> String {
 }
 
 /// Parse a value in quote. If it encounter an escaped quote it'll unescape it.
-fn quoted_by(quote: char, input: Span) -> IResult<Token> {
+pub(crate) fn quoted_by(quote: char, input: Span) -> IResult<Token> {
     // empty fields / values are valid in json
     if input.is_empty() {
         return Ok((input.slice(input.input_len()..), input.into()));
@@ -128,6 +128,42 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         _ => (),
     }
 
+    match parse_geo_polygon(input) {
+        Ok(_) => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        // if we encountered a failure it means the user badly wrote a _geoPolygon filter.
+        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+        Err(e) if e.is_failure() => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        _ => (),
+    }
+
+    match parse_geo_intersects(input) {
+        Ok(_) => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoIntersects,
+            )))
+        }
+        // if we encountered a failure it means the user badly wrote a _geoIntersects filter.
+        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+        Err(e) if e.is_failure() => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoIntersects,
+            )))
+        }
+        _ => (),
+    }
+
     // this parser is only used when an error is encountered and it parse the
     // largest string possible that do not contain any “language” syntax.
     // If we try to parse `name = 🦀 AND language = rust` we want to return an
@@ -214,8 +250,12 @@ fn is_keyword(s: &str) -> bool {
             | "CONTAINS"
             | "STARTS"
             | "WITH"
+            | "ENDS"
+            | "MATCHES"
             | "_geoRadius"
             | "_geoBoundingBox"
+            | "_geoPolygon"
+            | "_geoIntersects"
     )
 }
 
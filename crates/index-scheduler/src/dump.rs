@@ -5,11 +5,11 @@ use dump::{KindDump, TaskDump, UpdateFile};
 use meilisearch_types::batches::{Batch, BatchId};
 use meilisearch_types::heed::RwTxn;
 use meilisearch_types::milli;
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Kind, KindWithContent, Priority, Status, Task};
 use roaring::RoaringBitmap;
 use uuid::Uuid;
 
-use crate::{utils, Error, IndexScheduler, Result};
+use crate::{utils, Error, IndexScheduler, Result, TaskId};
 
 pub struct Dump<'a> {
     index_scheduler: &'a IndexScheduler,
@@ -20,6 +20,8 @@ pub struct Dump<'a> {
     indexes: HashMap<String, RoaringBitmap>,
     statuses: HashMap<Status, RoaringBitmap>,
     kinds: HashMap<Kind, RoaringBitmap>,
+    priorities: HashMap<Priority, RoaringBitmap>,
+    groups: HashMap<TaskId, RoaringBitmap>,
 
     batch_indexes: HashMap<String, RoaringBitmap>,
     batch_statuses: HashMap<Status, RoaringBitmap>,
@@ -38,6 +40,8 @@ impl<'a> Dump<'a> {
             indexes: HashMap::new(),
             statuses: HashMap::new(),
             kinds: HashMap::new(),
+            priorities: HashMap::new(),
+            groups: HashMap::new(),
             batch_indexes: HashMap::new(),
             batch_statuses: HashMap::new(),
             batch_kinds: HashMap::new(),
@@ -139,13 +143,16 @@ impl<'a> Dump<'a> {
         let task = Task {
             uid: task.uid,
             batch_uid: task.batch_uid,
+            group_uid: task.group_uid,
             enqueued_at: task.enqueued_at,
+            run_at: task.run_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
             error: task.error,
             canceled_by: task.canceled_by,
             details: task.details,
             status: task.status,
+            priority: task.priority,
             kind: match task.kind {
                 KindDump::DocumentImport {
                     primary_key,
@@ -239,6 +246,14 @@ impl<'a> Dump<'a> {
             task.enqueued_at,
             task.uid,
         )?;
+        if let Some(run_at) = task.run_at {
+            utils::insert_task_datetime(
+                &mut self.wtxn,
+                self.index_scheduler.queue.tasks.run_at,
+                run_at,
+                task.uid,
+            )?;
+        }
 
         // we can't override the started_at & finished_at, so we must only set it if the tasks is finished and won't change
         if matches!(task.status, Status::Succeeded | Status::Failed | Status::Canceled) {
@@ -262,6 +277,12 @@ impl<'a> Dump<'a> {
 
         self.statuses.entry(task.status).or_default().insert(task.uid);
         self.kinds.entry(task.kind.as_kind()).or_default().insert(task.uid);
+        if !task.priority.is_normal() {
+            self.priorities.entry(task.priority).or_default().insert(task.uid);
+        }
+        if let Some(group_uid) = task.group_uid {
+            self.groups.entry(group_uid).or_default().insert(task.uid);
+        }
 
         Ok(task)
     }
@@ -285,6 +306,12 @@ impl<'a> Dump<'a> {
         for (kind, bitmap) in self.kinds {
             self.index_scheduler.queue.tasks.put_kind(&mut self.wtxn, kind, &bitmap)?;
         }
+        for (priority, bitmap) in self.priorities {
+            self.index_scheduler.queue.tasks.priority.put(&mut self.wtxn, &priority, &bitmap)?;
+        }
+        for (group_uid, bitmap) in self.groups {
+            self.index_scheduler.queue.tasks.groups.put(&mut self.wtxn, &group_uid, &bitmap)?;
+        }
 
         for (index, bitmap) in self.batch_indexes {
             self.index_scheduler.queue.batches.index_tasks.put(&mut self.wtxn, &index, &bitmap)?;
@@ -5,6 +5,7 @@ use meilisearch_types::error::{Code, ErrorCode};
 use meilisearch_types::tasks::{Kind, Status};
 use meilisearch_types::{heed, milli};
 use thiserror::Error;
+use uuid::Uuid;
 
 use crate::TaskId;
 
@@ -16,6 +17,8 @@ pub enum DateField {
     AfterStartedAt,
     BeforeFinishedAt,
     AfterFinishedAt,
+    BeforeRunAt,
+    AfterRunAt,
 }
 
 impl Display for DateField {
@@ -27,6 +30,8 @@ impl Display for DateField {
             DateField::AfterStartedAt => write!(f, "afterStartedAt"),
             DateField::BeforeFinishedAt => write!(f, "beforeFinishedAt"),
             DateField::AfterFinishedAt => write!(f, "afterFinishedAt"),
+            DateField::BeforeRunAt => write!(f, "beforeRunAt"),
+            DateField::AfterRunAt => write!(f, "afterRunAt"),
         }
     }
 }
@@ -40,6 +45,8 @@ impl From<DateField> for Code {
             DateField::AfterStartedAt => Code::InvalidTaskAfterStartedAt,
             DateField::BeforeFinishedAt => Code::InvalidTaskBeforeFinishedAt,
             DateField::AfterFinishedAt => Code::InvalidTaskAfterFinishedAt,
+            DateField::BeforeRunAt => Code::InvalidTaskBeforeRunAt,
+            DateField::AfterRunAt => Code::InvalidTaskAfterRunAt,
         }
     }
 }
@@ -73,6 +80,14 @@ pub enum Error {
         .0.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
     )]
     SwapIndexesNotFound(Vec<String>),
+    #[error("A task group must contain at least one operation.")]
+    EmptyTaskGroup,
+    #[error("A task group can only contain document and settings operations, but it contains a `{0}` task.")]
+    InvalidTaskGroupKind(Kind),
+    #[error(
+        "Indexes must be declared only once in a task group. `{0}` was specified several times."
+    )]
+    TaskGroupDuplicateIndexFound(String),
     #[error("Corrupted dump.")]
     CorruptedDump,
     #[error(
@@ -83,6 +98,8 @@ pub enum Error {
     InvalidTaskUid { task_uid: String },
     #[error("Batch uid `{batch_uid}` is invalid. It should only contain numeric characters.")]
     InvalidBatchUid { batch_uid: String },
+    #[error("Task group uid `{group_uid}` is invalid. It should only contain numeric characters.")]
+    InvalidTaskGroupUid { group_uid: String },
     #[error(
         "Task status `{status}` is invalid. Available task statuses are {}.",
             enum_iterator::all::<Status>()
@@ -113,9 +130,15 @@ pub enum Error {
     TaskFileNotFound(TaskId),
     #[error("Batch `{0}` not found.")]
     BatchNotFound(BatchId),
-    #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
+    #[error("Task group `{0}` not found.")]
+    TaskGroupNotFound(TaskId),
+    #[error("Webhook `{0}` not found.")]
+    WebhookNotFound(Uuid),
+    #[error("Webhook `{0}` is configured with `--task-webhook-url` and cannot be modified or deleted through the API.")]
+    ImmutableWebhook(Uuid),
+    #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`, `beforeRunAt`, `afterRunAt`.")]
     TaskDeletionWithEmptyQuery,
-    #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
+    #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`, `beforeRunAt`, `afterRunAt`.")]
     TaskCancelationWithEmptyQuery,
     #[error("Aborted task")]
     AbortedTask,
@@ -131,6 +154,11 @@ pub enum Error {
     Milli { error: milli::Error, index_uid: Option<String> },
     #[error("An unexpected crash occurred when processing the task: {0}")]
     ProcessBatchPanicked(String),
+    #[error(
+        "The task group was only partially committed: the changes to {} were committed, but not the following ones. {error}",
+        .committed.iter().map(|s| format!("`{}`", s)).collect::<Vec<_>>().join(", ")
+    )]
+    TaskGroupPartiallyCommitted { committed: Vec<String>, error: Box<Error> },
     #[error(transparent)]
     FileStore(#[from] file_store::Error),
     #[error(transparent)]
@@ -182,9 +210,13 @@ impl Error {
             | Error::SwapIndexNotFound(_)
             | Error::NoSpaceLeftInTaskQueue
             | Error::SwapIndexesNotFound(_)
+            | Error::EmptyTaskGroup
+            | Error::InvalidTaskGroupKind(_)
+            | Error::TaskGroupDuplicateIndexFound(_)
             | Error::CorruptedDump
             | Error::InvalidTaskDate { .. }
             | Error::InvalidTaskUid { .. }
+            | Error::InvalidTaskGroupUid { .. }
             | Error::InvalidBatchUid { .. }
             | Error::InvalidTaskStatuses { .. }
             | Error::InvalidTaskTypes { .. }
@@ -193,6 +225,9 @@ impl Error {
             | Error::TaskNotFound(_)
             | Error::TaskFileNotFound(_)
             | Error::BatchNotFound(_)
+            | Error::TaskGroupNotFound(_)
+            | Error::WebhookNotFound(_)
+            | Error::ImmutableWebhook(_)
             | Error::TaskDeletionWithEmptyQuery
             | Error::TaskCancelationWithEmptyQuery
             | Error::AbortedTask
@@ -200,6 +235,7 @@ impl Error {
             | Error::Heed(_)
             | Error::Milli { .. }
             | Error::ProcessBatchPanicked(_)
+            | Error::TaskGroupPartiallyCommitted { .. }
             | Error::FileStore(_)
             | Error::IoError(_)
             | Error::Persist(_)
@@ -245,8 +281,12 @@ impl ErrorCode for Error {
             Error::SwapDuplicateIndexFound(_) => Code::InvalidSwapDuplicateIndexFound,
             Error::SwapIndexNotFound(_) => Code::IndexNotFound,
             Error::SwapIndexesNotFound(_) => Code::IndexNotFound,
+            Error::EmptyTaskGroup => Code::InvalidTaskGroupOperations,
+            Error::InvalidTaskGroupKind(_) => Code::InvalidTaskGroupOperations,
+            Error::TaskGroupDuplicateIndexFound(_) => Code::InvalidTaskGroupDuplicateIndexFound,
             Error::InvalidTaskDate { field, .. } => (*field).into(),
             Error::InvalidTaskUid { .. } => Code::InvalidTaskUids,
+            Error::InvalidTaskGroupUid { .. } => Code::InvalidTaskGroupUids,
             Error::InvalidBatchUid { .. } => Code::InvalidBatchUids,
             Error::InvalidTaskStatuses { .. } => Code::InvalidTaskStatuses,
             Error::InvalidTaskTypes { .. } => Code::InvalidTaskTypes,
@@ -255,6 +295,9 @@ impl ErrorCode for Error {
             Error::TaskNotFound(_) => Code::TaskNotFound,
             Error::TaskFileNotFound(_) => Code::TaskFileNotFound,
             Error::BatchNotFound(_) => Code::BatchNotFound,
+            Error::TaskGroupNotFound(_) => Code::TaskGroupNotFound,
+            Error::WebhookNotFound(_) => Code::WebhookNotFound,
+            Error::ImmutableWebhook(_) => Code::ImmutableWebhook,
             Error::TaskDeletionWithEmptyQuery => Code::MissingTaskFilters,
             Error::TaskCancelationWithEmptyQuery => Code::MissingTaskFilters,
             // TODO: not sure of the Code to use
@@ -262,6 +305,7 @@ impl ErrorCode for Error {
             Error::Dump(e) => e.error_code(),
             Error::Milli { error, .. } => error.error_code(),
             Error::ProcessBatchPanicked(_) => Code::Internal,
+            Error::TaskGroupPartiallyCommitted { error, .. } => error.error_code(),
             Error::Heed(e) => e.error_code(),
             Error::HeedTransaction(e) => e.error_code(),
             Error::FileStore(e) => e.error_code(),
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
@@ -79,7 +80,9 @@ pub struct IndexMapper {
 
     /// A few types of long running batches of tasks that act on a single index set this field
     /// so that a handle to the index is available from other threads (search) in an optimized manner.
-    currently_updating_index: Arc<RwLock<Option<(String, Index)>>>,
+    ///
+    /// There is one entry per batch processing concurrently.
+    currently_updating_indexes: Arc<RwLock<HashMap<String, Index>>>,
 }
 
 /// Whether the index is available for use or is forbidden to be inserted back in the index map
@@ -102,6 +105,9 @@ pub struct IndexStats {
     /// Stats of the documents database.
     #[serde(default)]
     pub documents_database_stats: DatabaseStats,
+    /// Stats of the keyword search databases when the keyword search was disabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyword_search_saved_stats: Option<DatabaseStats>,
 
     #[serde(default, skip_serializing)]
     pub number_of_documents: Option<u64>,
@@ -147,6 +153,7 @@ impl IndexStats {
             number_of_embeddings: Some(arroy_stats.number_of_embeddings),
             number_of_embedded_documents: Some(arroy_stats.documents.len()),
             documents_database_stats: index.documents_stats(rtxn)?.unwrap_or_default(),
+            keyword_search_saved_stats: index.keyword_search_saved_stats(rtxn)?,
             number_of_documents: None,
             database_size: index.on_disk_size()?,
             used_database_size: index.used_size()?,
@@ -178,7 +185,7 @@ impl IndexMapper {
             index_growth_amount: options.index_growth_amount,
             enable_mdb_writemap: options.enable_mdb_writemap,
             indexer_config: options.indexer_config.clone(),
-            currently_updating_index: Default::default(),
+            currently_updating_indexes: Default::default(),
         })
     }
 
@@ -342,12 +349,8 @@ impl IndexMapper {
 
     /// Return an index, may open it if it wasn't already opened.
     pub fn index(&self, rtxn: &RoTxn, name: &str) -> Result<Index> {
-        if let Some((current_name, current_index)) =
-            self.currently_updating_index.read().unwrap().as_ref()
-        {
-            if current_name == name {
-                return Ok(current_index.clone());
-            }
+        if let Some(current_index) = self.currently_updating_indexes.read().unwrap().get(name) {
+            return Ok(current_index.clone());
         }
 
         let uuid = self
@@ -528,7 +531,11 @@ impl IndexMapper {
         &self.indexer_config
     }
 
-    pub fn set_currently_updating_index(&self, index: Option<(String, Index)>) {
-        *self.currently_updating_index.write().unwrap() = index;
+    pub fn set_currently_updating_index(&self, name: &str, index: Option<Index>) {
+        let mut currently_updating_indexes = self.currently_updating_indexes.write().unwrap();
+        match index {
+            Some(index) => currently_updating_indexes.insert(name.to_string(), index),
+            None => currently_updating_indexes.remove(name),
+        };
     }
 }
@@ -28,12 +28,12 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
 
         index_mapper,
         features: _,
-        webhook_url: _,
-        webhook_authorization_header: _,
+        webhooks: _,
         test_breakpoint_sdr: _,
         planned_failures: _,
         run_loop_iteration: _,
         embedders: _,
+        rerankers: _,
     } = scheduler;
 
     let rtxn = env.read_txn().unwrap();
@@ -52,12 +52,16 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
 
     let processing = processing_tasks.read().unwrap().clone();
     snap.push_str(&format!("### Autobatching Enabled = {}\n", scheduler.autobatching_enabled));
-    snap.push_str(&format!(
-        "### Processing batch {:?}:\n",
-        processing.batch.as_ref().map(|batch| batch.uid)
-    ));
+    let processing_batches: Vec<_> =
+        processing.processing_batches().map(|batch| batch.uid).collect();
+    match processing_batches.as_slice() {
+        [] | [_] => {
+            snap.push_str(&format!("### Processing batch {:?}:\n", processing_batches.first()))
+        }
+        uids => snap.push_str(&format!("### Processing batches {uids:?}:\n")),
+    }
     snap.push_str(&snapshot_bitmap(&processing.processing));
-    if let Some(ref batch) = processing.batch {
+    for batch in processing.processing_batches() {
         snap.push('\n');
         snap.push_str(&snapshot_batch(&batch.to_batch()));
     }
@@ -206,13 +210,16 @@ pub fn snapshot_task(task: &Task) -> String {
     let Task {
         uid,
         batch_uid,
+        group_uid,
         enqueued_at: _,
+        run_at: _,
         started_at: _,
         finished_at: _,
         error,
         canceled_by,
         details,
         status,
+        priority,
         kind,
     } = task;
     snap.push('{');
@@ -220,7 +227,13 @@ pub fn snapshot_task(task: &Task) -> String {
     if let Some(batch_uid) = batch_uid {
         snap.push_str(&format!("batch_uid: {batch_uid}, "));
     }
+    if let Some(group_uid) = group_uid {
+        snap.push_str(&format!("group_uid: {group_uid}, "));
+    }
     snap.push_str(&format!("status: {status}, "));
+    if !priority.is_normal() {
+        snap.push_str(&format!("priority: {priority}, "));
+    }
     if let Some(canceled_by) = canceled_by {
         snap.push_str(&format!("canceled_by: {canceled_by}, "));
     }
@@ -34,12 +34,13 @@ pub mod upgrade;
 mod utils;
 pub mod uuid_codec;
 pub mod versioning;
+mod webhooks;
 
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 pub type TaskId = u32;
 
+use std::collections::btree_map::Entry;
 use std::collections::{BTreeMap, HashMap};
-use std::io::{self, BufReader, Read};
 use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
@@ -48,26 +49,28 @@ use std::time::Duration;
 use dump::Dump;
 pub use error::Error;
 pub use features::RoFeatures;
-use flate2::bufread::GzEncoder;
-use flate2::Compression;
-use meilisearch_types::batches::Batch;
+use meilisearch_types::batches::{Batch, BatchId};
 use meilisearch_types::features::{InstanceTogglableFeatures, Network, RuntimeTogglableFeatures};
 use meilisearch_types::heed::byteorder::BE;
 use meilisearch_types::heed::types::I128;
 use meilisearch_types::heed::{self, Env, RoTxn, WithoutTls};
 use meilisearch_types::milli::index::IndexEmbeddingConfig;
+use meilisearch_types::milli::prompt::Prompt;
 use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::vector::rerank::{Reranker, RerankerOptions, RerankerSettings};
 use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfigs};
 use meilisearch_types::milli::{self, Index};
-use meilisearch_types::task_view::TaskView;
-use meilisearch_types::tasks::{KindWithContent, Task};
+use meilisearch_types::tasks::{KindWithContent, Priority, Status, Task};
+use meilisearch_types::webhooks::{DeliveryId, Webhook, WebhookDelivery};
 use processing::ProcessingTasks;
 pub use queue::Query;
 use queue::Queue;
 use roaring::RoaringBitmap;
 use scheduler::Scheduler;
 use time::OffsetDateTime;
+use uuid::Uuid;
 use versioning::Versioning;
+pub use webhooks::CLI_WEBHOOK_UUID;
 
 use crate::index_mapper::IndexMapper;
 use crate::utils::clamp_to_page_size;
@@ -76,6 +79,15 @@ pub(crate) type BEI128 = I128<BE>;
 
 const TASK_SCHEDULER_SIZE_THRESHOLD_PERCENT_INT: u64 = 40;
 
+/// The options of a task that are given when it is registered.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TaskOptions {
+    /// The enqueued tasks of the indexes with the highest priority tasks are batched first.
+    pub priority: Priority,
+    /// The date before which the task must not be processed, it can be canceled until then.
+    pub run_at: Option<OffsetDateTime>,
+}
+
 #[derive(Debug)]
 pub struct IndexSchedulerOptions {
     /// The path to the version file of Meilisearch.
@@ -123,6 +135,14 @@ pub struct IndexSchedulerOptions {
     /// If the autobatcher is allowed to automatically batch tasks
     /// it will only batch this defined maximum size (in bytes) of tasks at once.
     pub batched_tasks_size_limit: u64,
+    /// The maximum number of batches operating on different indexes that can be processed at the same time.
+    ///
+    /// Index swaps, dumps, snapshots, task cancelations and deletions are always processed alone.
+    pub max_concurrent_batches: usize,
+    /// Whether the enqueued tasks of the indexes are batched in turn instead of by their order of arrival.
+    ///
+    /// The tasks of the highest priority are still batched first.
+    pub round_robin_indexes: bool,
     /// The experimental features enabled for this instance.
     pub instance_features: InstanceTogglableFeatures,
     /// The experimental features enabled for this instance.
@@ -157,10 +177,8 @@ pub struct IndexScheduler {
     /// Whether we should automatically cleanup the task queue or not.
     pub(crate) cleanup_enabled: bool,
 
-    /// The webhook url we should send tasks to after processing every batches.
-    pub(crate) webhook_url: Option<String>,
-    /// The Authorization header to send to the webhook URL.
-    pub(crate) webhook_authorization_header: Option<String>,
+    /// The webhooks notified with the tasks of every processed batch, and their deliveries.
+    pub(crate) webhooks: webhooks::WebhookStore,
 
     /// A map to retrieve the runtime representation of an embedder depending on its configuration.
     ///
@@ -169,6 +187,10 @@ pub struct IndexScheduler {
     /// to the same embeddings for the same input text.
     embedders: Arc<RwLock<HashMap<EmbedderOptions, Arc<Embedder>>>>,
 
+    /// A map to retrieve the runtime representation of a reranker depending on its configuration,
+    /// shared between the indexes like the embedders.
+    rerankers: Arc<RwLock<HashMap<RerankerOptions, Arc<Reranker>>>>,
+
     // ================= test
     // The next entry is dedicated to the tests.
     /// Provide a way to set a breakpoint in multiple part of the scheduler.
@@ -199,9 +221,9 @@ impl IndexScheduler {
 
             index_mapper: self.index_mapper.clone(),
             cleanup_enabled: self.cleanup_enabled,
-            webhook_url: self.webhook_url.clone(),
-            webhook_authorization_header: self.webhook_authorization_header.clone(),
+            webhooks: self.webhooks.clone(),
             embedders: self.embedders.clone(),
+            rerankers: self.rerankers.clone(),
             #[cfg(test)]
             test_breakpoint_sdr: self.test_breakpoint_sdr.clone(),
             #[cfg(test)]
@@ -213,7 +235,11 @@ impl IndexScheduler {
     }
 
     pub(crate) const fn nb_db() -> u32 {
-        Versioning::nb_db() + Queue::nb_db() + IndexMapper::nb_db() + features::FeatureData::nb_db()
+        Versioning::nb_db()
+            + Queue::nb_db()
+            + IndexMapper::nb_db()
+            + features::FeatureData::nb_db()
+            + webhooks::WebhookStore::nb_db()
     }
 
     /// Create an index scheduler and start its run loop.
@@ -248,6 +274,7 @@ impl IndexScheduler {
                 options.index_base_map_size,
                 task_db_size,
                 options.index_count,
+                options.indexer_config.embedding_cache.as_ref().map_or(0, |cache| cache.map_size()),
             )
         };
 
@@ -267,6 +294,7 @@ impl IndexScheduler {
         let features = features::FeatureData::new(&env, &mut wtxn, options.instance_features)?;
         let queue = Queue::new(&env, &mut wtxn, &options)?;
         let index_mapper = IndexMapper::new(&env, &mut wtxn, &options, budget)?;
+        let webhooks = webhooks::WebhookStore::new(&env, &mut wtxn, &options)?;
         wtxn.commit()?;
 
         // allow unreachable_code to get rids of the warning in the case of a test build.
@@ -279,9 +307,9 @@ impl IndexScheduler {
             index_mapper,
             env,
             cleanup_enabled: options.cleanup_enabled,
-            webhook_url: options.webhook_url,
-            webhook_authorization_header: options.webhook_authorization_header,
+            webhooks,
             embedders: Default::default(),
+            rerankers: Default::default(),
 
             #[cfg(test)]
             test_breakpoint_sdr,
@@ -293,6 +321,7 @@ impl IndexScheduler {
         };
 
         this.run();
+        this.run_webhook_deliveries();
         Ok(this)
     }
 
@@ -308,6 +337,7 @@ impl IndexScheduler {
         base_map_size: usize,
         mut task_db_size: usize,
         max_index_count: usize,
+        embedding_cache_size: usize,
     ) -> IndexBudget {
         #[cfg(windows)]
         const DEFAULT_BUDGET: usize = 6 * 1024 * 1024 * 1024 * 1024; // 6 TiB, 1 index
@@ -324,7 +354,8 @@ impl IndexScheduler {
         };
 
         tracing::debug!("memmap budget: {budget}B");
-        let mut budget = budget / 2;
+        // the map of the persistent embedding cache is taken from the budget of the indexes
+        let mut budget = (budget / 2).saturating_sub(embedding_cache_size);
         if task_db_size > (budget / 2) {
             task_db_size = clamp_to_page_size(budget * 2 / 5);
             tracing::debug!(
@@ -394,7 +425,7 @@ impl IndexScheduler {
                     let ret = catch_unwind(AssertUnwindSafe(|| run.tick()));
                     match ret {
                         Ok(Ok(TickOutcome::TickAgain(_))) => (),
-                        Ok(Ok(TickOutcome::WaitForSignal)) => run.scheduler.wake_up.wait(),
+                        Ok(Ok(TickOutcome::WaitForSignal)) => run.wait_for_signal_or_due_task(),
                         Ok(Ok(TickOutcome::StopProcessingForever)) => break,
                         Ok(Err(e)) => {
                             tracing::error!("{e}");
@@ -438,6 +469,25 @@ impl IndexScheduler {
             .saturating_sub(self.used_size()?))
     }
 
+    /// Waits until the scheduler is woken up or the next scheduled task becomes due.
+    fn wait_for_signal_or_due_task(&self) {
+        let next_due_date = self
+            .read_txn()
+            .and_then(|rtxn| self.queue.tasks.next_due_date(&rtxn, OffsetDateTime::now_utc()));
+        match next_due_date {
+            Ok(Some(date)) => {
+                let timeout: Duration =
+                    (date - OffsetDateTime::now_utc()).try_into().unwrap_or_default();
+                self.scheduler.wake_up.wait_timeout(timeout);
+            }
+            Ok(None) => self.scheduler.wake_up.wait(),
+            Err(e) => {
+                tracing::error!("{e}");
+                self.scheduler.wake_up.wait();
+            }
+        }
+    }
+
     /// Return the index corresponding to the name.
     ///
     /// * If the index wasn't opened before, the index will be opened.
@@ -629,6 +679,42 @@ impl IndexScheduler {
         self.queue.get_batch_ids_from_authorized_indexes(&rtxn, query, filters, &processing)
     }
 
+    /// Return the status of each of the given task groups as a whole, the groups that don't
+    /// exist are omitted.
+    pub fn get_task_groups_status(
+        &self,
+        group_uids: impl IntoIterator<Item = TaskId>,
+    ) -> Result<BTreeMap<TaskId, Status>> {
+        let rtxn = self.read_txn()?;
+        let processing = self.processing_tasks.read().unwrap();
+        let mut statuses = BTreeMap::new();
+        for group_uid in group_uids {
+            if let Entry::Vacant(entry) = statuses.entry(group_uid) {
+                if let Some(status) = self.queue.get_group_status(&rtxn, group_uid, &processing)? {
+                    entry.insert(status);
+                }
+            }
+        }
+        Ok(statuses)
+    }
+
+    /// Return the uid of the task group processed by each of the given batches, the batches
+    /// that don't process a task group are omitted.
+    pub fn get_batches_task_group(
+        &self,
+        batch_uids: impl IntoIterator<Item = BatchId>,
+    ) -> Result<BTreeMap<BatchId, TaskId>> {
+        let rtxn = self.read_txn()?;
+        let processing = self.processing_tasks.read().unwrap();
+        let mut groups = BTreeMap::new();
+        for batch_uid in batch_uids {
+            if let Some(group_uid) = self.queue.get_batch_group(&rtxn, batch_uid, &processing)? {
+                groups.insert(batch_uid, group_uid);
+            }
+        }
+        Ok(groups)
+    }
+
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
@@ -637,6 +723,17 @@ impl IndexScheduler {
         kind: KindWithContent,
         task_id: Option<TaskId>,
         dry_run: bool,
+    ) -> Result<Task> {
+        self.register_with_options(kind, task_id, dry_run, TaskOptions::default())
+    }
+
+    /// Register a new task in the scheduler with the given priority and due date.
+    pub fn register_with_options(
+        &self,
+        kind: KindWithContent,
+        task_id: Option<TaskId>,
+        dry_run: bool,
+        options: TaskOptions,
     ) -> Result<Task> {
         // if the task doesn't delete or cancel anything and 40% of the task queue is full, we must refuse to enqueue the incoming task
         if !matches!(&kind, KindWithContent::TaskDeletion { tasks, .. } | KindWithContent::TaskCancelation { tasks, .. } if !tasks.is_empty())
@@ -647,16 +744,13 @@ impl IndexScheduler {
         }
 
         let mut wtxn = self.env.write_txn()?;
-        let task = self.queue.register(&mut wtxn, &kind, task_id, dry_run)?;
+        let task = self.queue.register(&mut wtxn, &kind, task_id, dry_run, options)?;
 
         // If the registered task is a task cancelation
         // we inform the processing tasks to stop (if necessary).
         if let KindWithContent::TaskCancelation { tasks, .. } = kind {
             let tasks_to_cancel = RoaringBitmap::from_iter(tasks);
-            if self.processing_tasks.read().unwrap().must_cancel_processing_tasks(&tasks_to_cancel)
-            {
-                self.scheduler.must_stop_processing.must_stop();
-            }
+            self.processing_tasks.read().unwrap().stop_canceled_batches(&tasks_to_cancel);
         }
 
         if let Err(e) = wtxn.commit() {
@@ -669,6 +763,35 @@ impl IndexScheduler {
         Ok(task)
     }
 
+    /// Register a task group in the scheduler. The tasks of the group are processed in the
+    /// same batch and either all succeed or all fail.
+    pub fn register_group(
+        &self,
+        kinds: Vec<KindWithContent>,
+        dry_run: bool,
+        options: TaskOptions,
+    ) -> Result<Vec<Task>> {
+        if (self.env.non_free_pages_size()? * 100) / self.env.info().map_size as u64
+            > TASK_SCHEDULER_SIZE_THRESHOLD_PERCENT_INT
+        {
+            return Err(Error::NoSpaceLeftInTaskQueue);
+        }
+
+        let mut wtxn = self.env.write_txn()?;
+        let tasks = self.queue.register_group(&mut wtxn, &kinds, dry_run, options)?;
+
+        if let Err(e) = wtxn.commit() {
+            for task in &tasks {
+                self.queue.delete_persisted_task_data(task)?;
+            }
+            return Err(e.into());
+        }
+
+        // notify the scheduler loop to execute a new tick
+        self.scheduler.wake_up.signal();
+        Ok(tasks)
+    }
+
     /// Register a new task coming from a dump in the scheduler.
     /// By taking a mutable ref we're pretty sure no one will ever import a dump while actix is running.
     pub fn register_dumped_task(&mut self) -> Result<Dump> {
@@ -699,88 +822,6 @@ impl IndexScheduler {
         Ok(())
     }
 
-    /// Once the tasks changes have been committed we must send all the tasks that were updated to our webhook if there is one.
-    fn notify_webhook(&self, updated: &RoaringBitmap) -> Result<()> {
-        if let Some(ref url) = self.webhook_url {
-            struct TaskReader<'a, 'b> {
-                rtxn: &'a RoTxn<'a>,
-                index_scheduler: &'a IndexScheduler,
-                tasks: &'b mut roaring::bitmap::Iter<'b>,
-                buffer: Vec<u8>,
-                written: usize,
-            }
-
-            impl Read for TaskReader<'_, '_> {
-                fn read(&mut self, mut buf: &mut [u8]) -> std::io::Result<usize> {
-                    if self.buffer.is_empty() {
-                        match self.tasks.next() {
-                            None => return Ok(0),
-                            Some(task_id) => {
-                                let task = self
-                                    .index_scheduler
-                                    .queue
-                                    .tasks
-                                    .get_task(self.rtxn, task_id)
-                                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?
-                                    .ok_or_else(|| {
-                                        io::Error::new(
-                                            io::ErrorKind::Other,
-                                            Error::CorruptedTaskQueue,
-                                        )
-                                    })?;
-
-                                serde_json::to_writer(
-                                    &mut self.buffer,
-                                    &TaskView::from_task(&task),
-                                )?;
-                                self.buffer.push(b'\n');
-                            }
-                        }
-                    }
-
-                    let mut to_write = &self.buffer[self.written..];
-                    let wrote = io::copy(&mut to_write, &mut buf)?;
-                    self.written += wrote as usize;
-
-                    // we wrote everything and must refresh our buffer on the next call
-                    if self.written == self.buffer.len() {
-                        self.written = 0;
-                        self.buffer.clear();
-                    }
-
-                    Ok(wrote as usize)
-                }
-            }
-
-            let rtxn = self.env.read_txn()?;
-
-            let task_reader = TaskReader {
-                rtxn: &rtxn,
-                index_scheduler: self,
-                tasks: &mut updated.into_iter(),
-                buffer: Vec::with_capacity(50), // on average a task is around ~100 bytes
-                written: 0,
-            };
-
-            // let reader = GzEncoder::new(BufReader::new(task_reader), Compression::default());
-            let reader = GzEncoder::new(BufReader::new(task_reader), Compression::default());
-            let request = ureq::post(url)
-                .timeout(Duration::from_secs(30))
-                .set("Content-Encoding", "gzip")
-                .set("Content-Type", "application/x-ndjson");
-            let request = match &self.webhook_authorization_header {
-                Some(header) => request.set("Authorization", header),
-                None => request,
-            };
-
-            if let Err(e) = request.send(reader) {
-                tracing::error!("While sending data to the webhook: {e}");
-            }
-        }
-
-        Ok(())
-    }
-
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
@@ -809,6 +850,49 @@ impl IndexScheduler {
         self.features.network()
     }
 
+    /// Returns all the webhooks, starting with the one given with `--task-webhook-url`.
+    pub fn webhooks(&self) -> Result<Vec<Webhook>> {
+        let rtxn = self.read_txn()?;
+        self.webhooks.webhooks(&rtxn)
+    }
+
+    pub fn webhook(&self, uuid: Uuid) -> Result<Webhook> {
+        let rtxn = self.read_txn()?;
+        self.webhooks.webhook(&rtxn, uuid)?.ok_or(Error::WebhookNotFound(uuid))
+    }
+
+    /// Creates or replaces a webhook, the deliveries that are not sent yet use its new configuration.
+    pub fn put_webhook(&self, webhook: Webhook) -> Result<()> {
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        self.webhooks.put_webhook(&mut wtxn, &webhook)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Deletes a webhook and cancels its deliveries.
+    pub fn delete_webhook(&self, uuid: Uuid) -> Result<()> {
+        let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
+        if !self.webhooks.delete_webhook(&mut wtxn, uuid)? {
+            return Err(Error::WebhookNotFound(uuid));
+        }
+        wtxn.commit()?;
+        Ok(())
+    }
+
+    /// Returns up to `limit` deliveries of a webhook, from the most recent one and starting at `from`.
+    pub fn webhook_deliveries(
+        &self,
+        uuid: Uuid,
+        from: Option<DeliveryId>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let rtxn = self.read_txn()?;
+        if self.webhooks.webhook(&rtxn, uuid)?.is_none() {
+            return Err(Error::WebhookNotFound(uuid));
+        }
+        self.webhooks.deliveries(&rtxn, uuid, from, limit)
+    }
+
     pub fn embedders(
         &self,
         index_uid: String,
@@ -816,47 +900,84 @@ impl IndexScheduler {
     ) -> Result<EmbeddingConfigs> {
         let res: Result<_> = embedding_configs
             .into_iter()
-            .map(
-                |IndexEmbeddingConfig {
-                     name,
-                     config: milli::vector::EmbeddingConfig { embedder_options, prompt, quantized },
-                     ..
-                 }| {
-                    let prompt = Arc::new(
-                        prompt
-                            .try_into()
-                            .map_err(meilisearch_types::milli::Error::from)
-                            .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?,
-                    );
-                    // optimistically return existing embedder
-                    {
-                        let embedders = self.embedders.read().unwrap();
-                        if let Some(embedder) = embedders.get(&embedder_options) {
-                            return Ok((
-                                name,
-                                (embedder.clone(), prompt, quantized.unwrap_or_default()),
-                            ));
-                        }
+            .map(|IndexEmbeddingConfig { name, config, .. }| {
+                let quantization = config.quantization();
+                let milli::vector::EmbeddingConfig { embedder_options, prompt, .. } = config;
+                let prompt = Arc::new(
+                    prompt
+                        .try_into()
+                        .map_err(meilisearch_types::milli::Error::from)
+                        .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?,
+                );
+                // optimistically return existing embedder
+                {
+                    let embedders = self.embedders.read().unwrap();
+                    if let Some(embedder) = embedders.get(&embedder_options) {
+                        return Ok((name, (embedder.clone(), prompt, quantization)));
                     }
+                }
 
-                    // add missing embedder
-                    let embedder = Arc::new(
-                        Embedder::new(embedder_options.clone(), self.scheduler.embedding_cache_cap)
-                            .map_err(meilisearch_types::milli::vector::Error::from)
-                            .map_err(|err| {
-                                Error::from_milli(err.into(), Some(index_uid.clone()))
-                            })?,
-                    );
-                    {
-                        let mut embedders = self.embedders.write().unwrap();
-                        embedders.insert(embedder_options, embedder.clone());
-                    }
-                    Ok((name, (embedder, prompt, quantized.unwrap_or_default())))
-                },
-            )
+                // add missing embedder
+                let embedder = Arc::new(
+                    Embedder::new(
+                        embedder_options.clone(),
+                        self.scheduler.embedding_cache_cap,
+                        self.index_mapper.indexer_config().embedding_cache.as_ref(),
+                    )
+                    .map_err(meilisearch_types::milli::vector::Error::from)
+                    .map_err(|err| Error::from_milli(err.into(), Some(index_uid.clone())))?,
+                );
+                {
+                    let mut embedders = self.embedders.write().unwrap();
+                    embedders.insert(embedder_options, embedder.clone());
+                }
+                Ok((name, (embedder, prompt, quantization)))
+            })
             .collect();
         res.map(EmbeddingConfigs::new)
     }
+
+    /// Returns the reranker described by the settings, along with the template rendering the documents.
+    pub fn reranker(
+        &self,
+        index_uid: String,
+        reranker_name: &str,
+        settings: &RerankerSettings,
+    ) -> Result<(Arc<Reranker>, Arc<Prompt>)> {
+        let (reranker_options, prompt) = settings
+            .to_config()
+            .map_err(|message| milli::UserError::InvalidSettingsReranker {
+                reranker_name: reranker_name.to_owned(),
+                message,
+            })
+            .map_err(|err| Error::from_milli(err.into(), Some(index_uid.clone())))?;
+        let prompt = Arc::new(
+            prompt
+                .try_into()
+                .map_err(meilisearch_types::milli::Error::from)
+                .map_err(|err| Error::from_milli(err, Some(index_uid.clone())))?,
+        );
+
+        // optimistically return existing reranker
+        {
+            let rerankers = self.rerankers.read().unwrap();
+            if let Some(reranker) = rerankers.get(&reranker_options) {
+                return Ok((reranker.clone(), prompt));
+            }
+        }
+
+        // add missing reranker
+        let reranker = Arc::new(
+            Reranker::new(reranker_options.clone())
+                .map_err(meilisearch_types::milli::vector::Error::from)
+                .map_err(|err| Error::from_milli(err.into(), Some(index_uid)))?,
+        );
+        {
+            let mut rerankers = self.rerankers.write().unwrap();
+            rerankers.insert(reranker_options, reranker.clone());
+        }
+        Ok((reranker, prompt))
+    }
 }
 
 /// The outcome of calling the [`IndexScheduler::tick`] function.
@@ -1,18 +1,37 @@
 use std::sync::Arc;
 
+use meilisearch_types::batches::BatchId;
 use meilisearch_types::milli::progress::{AtomicSubStep, NamedStep, Progress, ProgressView};
 use meilisearch_types::milli::{make_atomic_progress, make_enum_progress};
 use roaring::RoaringBitmap;
 
+use crate::scheduler::MustStopProcessing;
 use crate::utils::ProcessingBatch;
+use crate::TaskId;
 
 #[derive(Clone, Default)]
 pub struct ProcessingTasks {
-    pub batch: Option<Arc<ProcessingBatch>>,
+    /// The batches that are currently running, in the order they were started.
+    pub batches: Vec<RunningBatch>,
     /// The list of tasks ids that are currently running.
     pub processing: Arc<RoaringBitmap>,
-    /// The progress on processing tasks
-    pub progress: Option<Progress>,
+}
+
+/// A batch that is being processed, possibly alongside other batches operating on other indexes.
+#[derive(Clone)]
+pub struct RunningBatch {
+    pub batch: Arc<ProcessingBatch>,
+    /// The tasks of the batch.
+    pub tasks: Arc<RoaringBitmap>,
+    /// The progress of the batch.
+    pub progress: Progress,
+    /// Set to stop the processing of this batch when one of its tasks is canceled.
+    pub must_stop_processing: MustStopProcessing,
+    /// Whether the processing of the batch was aborted.
+    ///
+    /// An aborted batch is kept until the next batch starts so that its tasks keep their
+    /// `started_at` date and are seen as processing until then, but its tasks can be batched again.
+    pub aborted: bool,
 }
 
 impl ProcessingTasks {
@@ -21,39 +40,87 @@ impl ProcessingTasks {
         ProcessingTasks::default()
     }
 
-    pub fn get_progress_view(&self) -> Option<ProgressView> {
-        Some(self.progress.as_ref()?.as_progress_view())
+    /// Returns the batch with the given uid if it is processing.
+    pub fn batch(&self, batch_uid: BatchId) -> Option<&Arc<ProcessingBatch>> {
+        self.batches.iter().map(|running| &running.batch).find(|batch| batch.uid == batch_uid)
+    }
+
+    /// Returns the processing batch containing the given task.
+    pub fn batch_of_task(&self, task_id: TaskId) -> Option<&Arc<ProcessingBatch>> {
+        self.batches
+            .iter()
+            .find(|running| running.tasks.contains(task_id))
+            .map(|running| &running.batch)
+    }
+
+    /// Returns an iterator over the batches that are processing.
+    pub fn processing_batches(&self) -> impl Iterator<Item = &Arc<ProcessingBatch>> {
+        self.batches.iter().map(|running| &running.batch)
+    }
+
+    /// Returns an iterator over the batches that are processing and have not been aborted.
+    pub fn running_batches(&self) -> impl Iterator<Item = &RunningBatch> {
+        self.batches.iter().filter(|running| !running.aborted)
+    }
+
+    pub fn get_progress_view(&self, batch_uid: BatchId) -> Option<ProgressView> {
+        let running = self.batches.iter().find(|running| running.batch.uid == batch_uid)?;
+        Some(running.progress.as_progress_view())
     }
 
     /// Stores the currently processing tasks, and the date time at which it started.
+    ///
+    /// The batches that were aborted are removed.
     pub fn start_processing(
         &mut self,
         processing_batch: ProcessingBatch,
         processing: RoaringBitmap,
+        must_stop_processing: MustStopProcessing,
     ) -> Progress {
-        self.batch = Some(Arc::new(processing_batch));
-        self.processing = Arc::new(processing);
+        self.batches.retain(|running| !running.aborted);
         let progress = Progress::default();
         progress.update_progress(BatchProgress::ProcessingTasks);
-        self.progress = Some(progress.clone());
+        self.batches.push(RunningBatch {
+            batch: Arc::new(processing_batch),
+            tasks: Arc::new(processing),
+            progress: progress.clone(),
+            must_stop_processing,
+            aborted: false,
+        });
+        self.update_processing();
 
         progress
     }
 
-    /// Set the processing tasks to an empty list
-    pub fn stop_processing(&mut self) -> Self {
-        self.progress = None;
+    /// Removes the batch and its tasks from the processing ones.
+    pub fn stop_processing(&mut self, batch_uid: BatchId) {
+        self.batches.retain(|running| running.batch.uid != batch_uid);
+        self.update_processing();
+    }
 
-        Self {
-            batch: std::mem::take(&mut self.batch),
-            processing: std::mem::take(&mut self.processing),
-            progress: None,
+    /// Marks the batch as aborted, its tasks are considered processing until the next batch starts.
+    pub fn abort_processing(&mut self, batch_uid: BatchId) {
+        for running in self.batches.iter_mut().filter(|running| running.batch.uid == batch_uid) {
+            running.aborted = true;
+        }
+    }
+
+    /// Stops the processing of the batches containing at least one of the canceled tasks.
+    pub fn stop_canceled_batches(&self, canceled_tasks: &RoaringBitmap) {
+        for running in &self.batches {
+            if !running.tasks.is_disjoint(canceled_tasks) {
+                running.must_stop_processing.must_stop();
+            }
         }
     }
 
-    /// Returns `true` if there, at least, is one task that is currently processing that we must stop.
-    pub fn must_cancel_processing_tasks(&self, canceled_tasks: &RoaringBitmap) -> bool {
-        !self.processing.is_disjoint(canceled_tasks)
+    fn update_processing(&mut self) {
+        self.processing = Arc::new(
+            self.batches
+                .iter()
+                .map(|running| &*running.tasks)
+                .fold(RoaringBitmap::new(), |acc, tasks| acc | tasks),
+        );
     }
 }
 
@@ -190,8 +257,12 @@ mod test {
     #[test]
     fn one_level() {
         let mut processing = ProcessingTasks::new();
-        processing.start_processing(ProcessingBatch::new(0), RoaringBitmap::new());
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        let progress = processing.start_processing(
+            ProcessingBatch::new(0),
+            RoaringBitmap::new(),
+            MustStopProcessing::default(),
+        );
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -203,8 +274,8 @@ mod test {
           "percentage": 0.0
         }
         "#);
-        processing.progress.as_ref().unwrap().update_progress(BatchProgress::WritingTasksToDisk);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        progress.update_progress(BatchProgress::WritingTasksToDisk);
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -221,10 +292,14 @@ mod test {
     #[test]
     fn task_progress() {
         let mut processing = ProcessingTasks::new();
-        processing.start_processing(ProcessingBatch::new(0), RoaringBitmap::new());
+        let progress = processing.start_processing(
+            ProcessingBatch::new(0),
+            RoaringBitmap::new(),
+            MustStopProcessing::default(),
+        );
         let (atomic, tasks) = AtomicTaskStep::new(10);
-        processing.progress.as_ref().unwrap().update_progress(tasks);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        progress.update_progress(tasks);
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -242,7 +317,7 @@ mod test {
         }
         "#);
         atomic.fetch_add(6, Ordering::Relaxed);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -259,8 +334,8 @@ mod test {
           "percentage": 30.000002
         }
         "#);
-        processing.progress.as_ref().unwrap().update_progress(BatchProgress::WritingTasksToDisk);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        progress.update_progress(BatchProgress::WritingTasksToDisk);
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
@@ -273,9 +348,9 @@ mod test {
         }
         "#);
         let (atomic, tasks) = AtomicTaskStep::new(5);
-        processing.progress.as_ref().unwrap().update_progress(tasks);
+        progress.update_progress(tasks);
         atomic.fetch_add(4, Ordering::Relaxed);
-        snapshot!(json_string!(processing.get_progress_view()), @r#"
+        snapshot!(json_string!(processing.get_progress_view(0)), @r#"
         {
           "steps": [
             {
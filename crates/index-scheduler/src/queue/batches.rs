@@ -280,9 +280,9 @@ impl BatchQueue {
         tasks
             .into_iter()
             .map(|batch_id| {
-                if Some(batch_id) == processing.batch.as_ref().map(|batch| batch.uid) {
-                    let mut batch = processing.batch.as_ref().unwrap().to_batch();
-                    batch.progress = processing.get_progress_view();
+                if let Some(batch) = processing.batch(batch_id) {
+                    let mut batch = batch.to_batch();
+                    batch.progress = processing.get_progress_view(batch_id);
                     Ok(batch)
                 } else {
                     self.get_batch(rtxn, batch_id)
@@ -307,6 +307,7 @@ impl Queue {
             reverse,
             uids,
             batch_uids,
+            group_uids,
             statuses,
             types,
             index_uids,
@@ -317,12 +318,12 @@ impl Queue {
             after_started_at,
             before_finished_at,
             after_finished_at,
+            before_run_at,
+            after_run_at,
         } = query;
 
         let mut batches = self.batches.all_batch_ids(rtxn)?;
-        if let Some(batch_id) = processing.batch.as_ref().map(|batch| batch.uid) {
-            batches.insert(batch_id);
-        }
+        batches.extend(processing.processing_batches().map(|batch| batch.uid));
 
         if let Some(from) = from {
             let range = if reverse.unwrap_or_default() {
@@ -344,9 +345,8 @@ impl Queue {
                 match status {
                     // special case for Processing batches
                     Status::Processing => {
-                        if let Some(batch_id) = processing.batch.as_ref().map(|batch| batch.uid) {
-                            status_batches.insert(batch_id);
-                        }
+                        status_batches
+                            .extend(processing.processing_batches().map(|batch| batch.uid));
                     }
                     // Enqueued tasks are not stored in batches
                     Status::Enqueued => (),
@@ -354,7 +354,7 @@ impl Queue {
                 };
             }
             if !status.contains(&Status::Processing) {
-                if let Some(ref batch) = processing.batch {
+                for batch in processing.processing_batches() {
                     batches.remove(batch.uid);
                 }
             }
@@ -373,6 +373,53 @@ impl Queue {
             batches &= batches_by_task_uids;
         }
 
+        if let Some(group_uids) = &group_uids {
+            let mut batches_by_group_uids = RoaringBitmap::new();
+            for group_uid in group_uids {
+                // all the tasks of a group are processed in the same batch
+                let Some(task_uid) = self.tasks.get_group(rtxn, *group_uid)?.min() else {
+                    continue;
+                };
+                if let Some(running) =
+                    processing.batches.iter().find(|running| running.tasks.contains(task_uid))
+                {
+                    batches_by_group_uids.insert(running.batch.uid);
+                } else if let Some(task) = self.tasks.get_task(rtxn, task_uid)? {
+                    if let Some(batch_uid) = task.batch_uid {
+                        batches_by_group_uids.insert(batch_uid);
+                    }
+                }
+            }
+            batches &= batches_by_group_uids;
+        }
+
+        // A batch matches when one of its tasks was scheduled to run in the given range
+        if after_run_at.is_some() || before_run_at.is_some() {
+            let mut run_at_tasks = self.tasks.all_task_ids(rtxn)?;
+            keep_ids_within_datetimes(
+                rtxn,
+                &mut run_at_tasks,
+                self.tasks.run_at,
+                *after_run_at,
+                *before_run_at,
+            )?;
+
+            let mut batches_by_run_at = RoaringBitmap::new();
+            for running in &processing.batches {
+                if !running.tasks.is_disjoint(&run_at_tasks) {
+                    batches_by_run_at.insert(running.batch.uid);
+                }
+            }
+            for task_uid in run_at_tasks {
+                if let Some(task) = self.tasks.get_task(rtxn, task_uid)? {
+                    if let Some(batch_uid) = task.batch_uid {
+                        batches_by_run_at.insert(batch_uid);
+                    }
+                }
+            }
+            batches &= batches_by_run_at;
+        }
+
         // There is no database for this query, we must retrieve the task queried by the client and ensure it's valid
         if let Some(canceled_by) = &canceled_by {
             let mut all_canceled_batches = RoaringBitmap::new();
@@ -401,13 +448,12 @@ impl Queue {
             let mut kind_batches = RoaringBitmap::new();
             for kind in kind {
                 kind_batches |= self.batches.get_kind(rtxn, *kind)?;
-                if let Some(uid) = processing
-                    .batch
-                    .as_ref()
-                    .and_then(|batch| batch.kinds.contains(kind).then_some(batch.uid))
-                {
-                    kind_batches.insert(uid);
-                }
+                kind_batches.extend(
+                    processing
+                        .processing_batches()
+                        .filter(|batch| batch.kinds.contains(kind))
+                        .map(|batch| batch.uid),
+                );
             }
             batches &= &kind_batches;
         }
@@ -416,13 +462,12 @@ impl Queue {
             let mut index_batches = RoaringBitmap::new();
             for index in index {
                 index_batches |= self.batches.index_batches(rtxn, index)?;
-                if let Some(uid) = processing
-                    .batch
-                    .as_ref()
-                    .and_then(|batch| batch.indexes.contains(index).then_some(batch.uid))
-                {
-                    index_batches.insert(uid);
-                }
+                index_batches.extend(
+                    processing
+                        .processing_batches()
+                        .filter(|batch| batch.indexes.contains(index))
+                        .map(|batch| batch.uid),
+                );
             }
             batches &= &index_batches;
         }
@@ -432,25 +477,25 @@ impl Queue {
         // are entirely removed unless the in-memory startedAt variable falls within the date filter.
         // Once we have filtered the two subsets, we put them back together and assign it back to `batches`.
         batches = {
+            let processing_batches: RoaringBitmap =
+                processing.processing_batches().map(|batch| batch.uid).collect();
             let (mut filtered_non_processing_batches, mut filtered_processing_batches) =
-                (&batches - &*processing.processing, &batches & &*processing.processing);
+                (&batches - &processing_batches, &batches & &processing_batches);
 
             // special case for Processing batches
-            // A closure that clears the filtered_processing_batches if their started_at date falls outside the given bounds
+            // A closure that removes the filtered_processing_batches whose started_at date falls outside the given bounds
             let mut clear_filtered_processing_batches =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &processing
-                            .batch
-                            .as_ref()
-                            .map_or_else(OffsetDateTime::now_utc, |batch| batch.started_at)
-                            .unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_batches.clear();
+                    for batch in processing.processing_batches() {
+                        let is_within_dates = RangeBounds::contains(
+                            &(start, end),
+                            &batch.started_at.unix_timestamp_nanos(),
+                        );
+                        if !is_within_dates {
+                            filtered_processing_batches.remove(batch.uid);
+                        }
                     }
                 };
             match (after_started_at, before_started_at) {
@@ -536,7 +581,7 @@ impl Queue {
         if query.index_uids.is_some() || !filters.all_indexes_authorized() {
             for kind in enum_iterator::all::<Kind>().filter(|kind| !kind.related_to_one_index()) {
                 batches -= self.tasks.get_kind(rtxn, kind)?;
-                if let Some(batch) = processing.batch.as_ref() {
+                for batch in processing.processing_batches() {
                     if batch.kinds.contains(&kind) {
                         batches.remove(batch.uid);
                     }
@@ -559,7 +604,7 @@ impl Queue {
                     forbidden_indexes |= index_tasks;
                 }
             }
-            if let Some(batch) = processing.batch.as_ref() {
+            for batch in processing.processing_batches() {
                 for index in &batch.indexes {
                     if filters.is_index_authorized(index) {
                         valid_indexes.insert(batch.uid);
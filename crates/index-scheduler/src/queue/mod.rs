@@ -25,9 +25,10 @@ pub(crate) use self::batches::BatchQueue;
 pub(crate) use self::tasks::TaskQueue;
 use crate::processing::ProcessingTasks;
 use crate::utils::{
-    check_index_swap_validity, filter_out_references_to_newer_tasks, ProcessingBatch,
+    check_index_swap_validity, check_task_group_validity, filter_out_references_to_newer_tasks,
+    ProcessingBatch,
 };
-use crate::{Error, IndexSchedulerOptions, Result, TaskId};
+use crate::{Error, IndexSchedulerOptions, Result, TaskId, TaskOptions};
 
 /// The number of database used by queue itself
 const NUMBER_OF_DATABASES: u32 = 1;
@@ -52,6 +53,8 @@ pub struct Query {
     pub uids: Option<Vec<TaskId>>,
     /// The [batch ids](`meilisearch_types::batches::Batch::uid`) to be matched
     pub batch_uids: Option<Vec<BatchId>>,
+    /// The [task groups](`meilisearch_types::tasks::Task::group_uid`) to be matched
+    pub group_uids: Option<Vec<TaskId>>,
     /// The allowed [statuses](`meilisearch_types::tasks::Task::status`) of the matched tasls
     pub statuses: Option<Vec<Status>>,
     /// The allowed [kinds](meilisearch_types::tasks::Kind) of the matched tasks.
@@ -81,6 +84,10 @@ pub struct Query {
     pub before_finished_at: Option<OffsetDateTime>,
     /// Exclusive lower bound of the matched tasks' [`finished_at`](meilisearch_types::tasks::Task::finished_at) field.
     pub after_finished_at: Option<OffsetDateTime>,
+    /// Exclusive upper bound of the matched tasks' [`run_at`](meilisearch_types::tasks::Task::run_at) field.
+    pub before_run_at: Option<OffsetDateTime>,
+    /// Exclusive lower bound of the matched tasks' [`run_at`](meilisearch_types::tasks::Task::run_at) field.
+    pub after_run_at: Option<OffsetDateTime>,
 }
 
 impl Query {
@@ -95,6 +102,7 @@ impl Query {
                 reverse: None,
                 uids: None,
                 batch_uids: None,
+                group_uids: None,
                 statuses: None,
                 types: None,
                 index_uids: None,
@@ -105,6 +113,8 @@ impl Query {
                 after_started_at: None,
                 before_finished_at: None,
                 after_finished_at: None,
+                before_run_at: None,
+                after_run_at: None,
             }
         )
     }
@@ -177,6 +187,52 @@ impl Queue {
         Ok(self.batch_to_tasks_mapping.get(rtxn, &batch_id)?.unwrap_or_default())
     }
 
+    /// Returns the status of a task group as a whole: `enqueued` or `processing` until all its tasks
+    /// are finished, then `canceled` or `failed` if one of its tasks was, and `succeeded` otherwise.
+    ///
+    /// Returns `None` if the group doesn't exist.
+    pub(crate) fn get_group_status(
+        &self,
+        rtxn: &RoTxn,
+        group_uid: TaskId,
+        processing: &ProcessingTasks,
+    ) -> Result<Option<Status>> {
+        let group = self.tasks.get_group(rtxn, group_uid)?;
+        if group.is_empty() {
+            return Ok(None);
+        }
+        if !group.is_disjoint(&processing.processing) {
+            return Ok(Some(Status::Processing));
+        }
+        for status in [Status::Enqueued, Status::Canceled, Status::Failed] {
+            if !group.is_disjoint(&self.tasks.get_status(rtxn, status)?) {
+                return Ok(Some(status));
+            }
+        }
+        Ok(Some(Status::Succeeded))
+    }
+
+    /// Returns the uid of the task group processed by the batch, if any.
+    ///
+    /// All the tasks of a group are processed in a batch of their own.
+    pub(crate) fn get_batch_group(
+        &self,
+        rtxn: &RoTxn,
+        batch_id: BatchId,
+        processing: &ProcessingTasks,
+    ) -> Result<Option<TaskId>> {
+        let tasks = match processing.batches.iter().find(|running| running.batch.uid == batch_id) {
+            Some(running) => running.tasks.min(),
+            None => self.tasks_in_batch(rtxn, batch_id)?.min(),
+        };
+        match tasks {
+            Some(task_id) => {
+                Ok(self.tasks.get_task(rtxn, task_id)?.and_then(|task| task.group_uid))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Convert an iterator to a `Vec` of tasks and edit the `ProcessingBatch` to add the given tasks.
     ///
     /// The tasks MUST exist, or a `CorruptedTaskQueue` error will be thrown.
@@ -258,6 +314,40 @@ impl Queue {
         kind: &KindWithContent,
         task_id: Option<TaskId>,
         dry_run: bool,
+        options: TaskOptions,
+    ) -> Result<Task> {
+        self.register_task(wtxn, kind, task_id, None, dry_run, options)
+    }
+
+    /// Register the tasks of a task group. The group is identified by the uid of its first task,
+    /// its tasks are processed in the same batch and their changes are committed together.
+    pub fn register_group(
+        &self,
+        wtxn: &mut RwTxn,
+        kinds: &[KindWithContent],
+        dry_run: bool,
+        options: TaskOptions,
+    ) -> Result<Vec<Task>> {
+        check_task_group_validity(kinds)?;
+
+        let group_uid = self.tasks.next_task_id(wtxn)?;
+        kinds
+            .iter()
+            .zip(group_uid..)
+            .map(|(kind, task_id)| {
+                self.register_task(wtxn, kind, Some(task_id), Some(group_uid), dry_run, options)
+            })
+            .collect()
+    }
+
+    fn register_task(
+        &self,
+        wtxn: &mut RwTxn,
+        kind: &KindWithContent,
+        task_id: Option<TaskId>,
+        group_uid: Option<TaskId>,
+        dry_run: bool,
+        options: TaskOptions,
     ) -> Result<Task> {
         let next_task_id = self.tasks.next_task_id(wtxn)?;
 
@@ -271,13 +361,16 @@ impl Queue {
             uid: task_id.unwrap_or(next_task_id),
             // The batch is defined once we starts processing the task
             batch_uid: None,
+            group_uid,
             enqueued_at: OffsetDateTime::now_utc(),
+            run_at: options.run_at,
             started_at: None,
             finished_at: None,
             error: None,
             canceled_by: None,
             details: kind.default_details(),
             status: Status::Enqueued,
+            priority: options.priority,
             kind: kind.clone(),
         };
         // For deletion and cancelation tasks, we want to make extra sure that they
@@ -343,6 +436,7 @@ impl Queue {
             },
             None,
             false,
+            TaskOptions::default(),
         )?;
 
         Ok(())
@@ -3,7 +3,7 @@ use std::ops::{Bound, RangeBounds};
 use meilisearch_types::heed::types::{DecodeIgnore, SerdeBincode, SerdeJson, Str};
 use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, RoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Kind, Status, Task};
+use meilisearch_types::tasks::{Kind, Priority, Status, Task};
 use roaring::{MultiOps, RoaringBitmap};
 use time::OffsetDateTime;
 
@@ -15,16 +15,19 @@ use crate::utils::{
 use crate::{Error, Result, TaskId, BEI128};
 
 /// The number of database used by the task queue
-const NUMBER_OF_DATABASES: u32 = 8;
+const NUMBER_OF_DATABASES: u32 = 11;
 /// Database const names for the `IndexScheduler`.
 mod db_name {
     pub const ALL_TASKS: &str = "all-tasks";
 
     pub const STATUS: &str = "status";
     pub const KIND: &str = "kind";
+    pub const PRIORITY: &str = "priority";
+    pub const GROUPS: &str = "groups";
     pub const INDEX_TASKS: &str = "index-tasks";
     pub const CANCELED_BY: &str = "canceled_by";
     pub const ENQUEUED_AT: &str = "enqueued-at";
+    pub const RUN_AT: &str = "run-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
 }
@@ -38,12 +41,18 @@ pub struct TaskQueue {
     pub(crate) status: Database<SerdeBincode<Status>, RoaringBitmapCodec>,
     /// All the tasks ids grouped by their kind.
     pub(crate) kind: Database<SerdeBincode<Kind>, RoaringBitmapCodec>,
+    /// The tasks ids with a `low` or `high` priority, the other tasks have a `normal` priority.
+    pub(crate) priority: Database<SerdeBincode<Priority>, RoaringBitmapCodec>,
+    /// All the tasks ids grouped by the task group they belong to, identified by its first task id.
+    pub(crate) groups: Database<BEU32, RoaringBitmapCodec>,
     /// Store the tasks associated to an index.
     pub(crate) index_tasks: Database<Str, RoaringBitmapCodec>,
     /// Store the tasks that were canceled by a task uid
     pub(crate) canceled_by: Database<BEU32, RoaringBitmapCodec>,
     /// Store the task ids of tasks which were enqueued at a specific date
     pub(crate) enqueued_at: Database<BEI128, CboRoaringBitmapCodec>,
+    /// Store the task ids of tasks which must not be processed before a specific date
+    pub(crate) run_at: Database<BEI128, CboRoaringBitmapCodec>,
     /// Store the task ids of finished tasks which started being processed at a specific date
     pub(crate) started_at: Database<BEI128, CboRoaringBitmapCodec>,
     /// Store the task ids of tasks which finished at a specific date
@@ -56,9 +65,12 @@ impl TaskQueue {
             all_tasks: self.all_tasks,
             status: self.status,
             kind: self.kind,
+            priority: self.priority,
+            groups: self.groups,
             index_tasks: self.index_tasks,
             canceled_by: self.canceled_by,
             enqueued_at: self.enqueued_at,
+            run_at: self.run_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
         }
@@ -73,9 +85,12 @@ impl TaskQueue {
             all_tasks: env.create_database(wtxn, Some(db_name::ALL_TASKS))?,
             status: env.create_database(wtxn, Some(db_name::STATUS))?,
             kind: env.create_database(wtxn, Some(db_name::KIND))?,
+            priority: env.create_database(wtxn, Some(db_name::PRIORITY))?,
+            groups: env.create_database(wtxn, Some(db_name::GROUPS))?,
             index_tasks: env.create_database(wtxn, Some(db_name::INDEX_TASKS))?,
             canceled_by: env.create_database(wtxn, Some(db_name::CANCELED_BY))?,
             enqueued_at: env.create_database(wtxn, Some(db_name::ENQUEUED_AT))?,
+            run_at: env.create_database(wtxn, Some(db_name::RUN_AT))?,
             started_at: env.create_database(wtxn, Some(db_name::STARTED_AT))?,
             finished_at: env.create_database(wtxn, Some(db_name::FINISHED_AT))?,
         })
@@ -132,6 +147,9 @@ impl TaskQueue {
             old_task.enqueued_at, task.enqueued_at,
             "Cannot update a task's enqueued_at time"
         );
+        assert_eq!(old_task.priority, task.priority, "Cannot update a task's priority");
+        assert_eq!(old_task.group_uid, task.group_uid, "Cannot update a task's group");
+        assert_eq!(old_task.run_at, task.run_at, "Cannot update a task's run_at time");
         if old_task.started_at != task.started_at {
             assert!(
                 reprocessing || old_task.started_at.is_none(),
@@ -235,6 +253,60 @@ impl TaskQueue {
         Ok(())
     }
 
+    /// Returns the ids of the tasks with the given priority.
+    ///
+    /// Only the `low` and `high` priorities are stored, the `normal` tasks are all the other ones.
+    pub(crate) fn get_priority(&self, rtxn: &RoTxn, priority: Priority) -> Result<RoaringBitmap> {
+        match priority {
+            Priority::Normal => Ok(self.all_task_ids(rtxn)?
+                - self.get_priority(rtxn, Priority::Low)?
+                - self.get_priority(rtxn, Priority::High)?),
+            priority => Ok(self.priority.get(rtxn, &priority)?.unwrap_or_default()),
+        }
+    }
+
+    pub(crate) fn update_priority(
+        &self,
+        wtxn: &mut RwTxn,
+        priority: Priority,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        if priority.is_normal() {
+            return Ok(());
+        }
+        let mut tasks = self.get_priority(wtxn, priority)?;
+        f(&mut tasks);
+        if tasks.is_empty() {
+            self.priority.delete(wtxn, &priority)?;
+        } else {
+            self.priority.put(wtxn, &priority, &tasks)?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the ids of the tasks of the given task group.
+    pub(crate) fn get_group(&self, rtxn: &RoTxn, group_uid: TaskId) -> Result<RoaringBitmap> {
+        Ok(self.groups.get(rtxn, &group_uid)?.unwrap_or_default())
+    }
+
+    pub(crate) fn update_group(
+        &self,
+        wtxn: &mut RwTxn,
+        group_uid: TaskId,
+        f: impl Fn(&mut RoaringBitmap),
+    ) -> Result<()> {
+        let mut tasks = self.get_group(wtxn, group_uid)?;
+        f(&mut tasks);
+        if tasks.is_empty() {
+            self.groups.delete(wtxn, &group_uid)?;
+        } else {
+            self.groups.put(wtxn, &group_uid, &tasks)?;
+        }
+
+        Ok(())
+    }
+
     /// Convert an iterator to a `Vec` of tasks. The tasks MUST exist or a
     /// `CorruptedTaskQueue` error will be thrown.
     pub(crate) fn get_existing_tasks(
@@ -267,10 +339,51 @@ impl TaskQueue {
             bitmap.insert(task.uid);
         })?;
 
+        self.update_priority(wtxn, task.priority, |bitmap| {
+            bitmap.insert(task.uid);
+        })?;
+
+        if let Some(group_uid) = task.group_uid {
+            self.update_group(wtxn, group_uid, |bitmap| {
+                bitmap.insert(task.uid);
+            })?;
+        }
+
         utils::insert_task_datetime(wtxn, self.enqueued_at, task.enqueued_at, task.uid)?;
+        if let Some(run_at) = task.run_at {
+            utils::insert_task_datetime(wtxn, self.run_at, run_at, task.uid)?;
+        }
 
         Ok(())
     }
+
+    /// Returns the ids of the tasks that must not be processed before a date later than `now`.
+    pub(crate) fn not_yet_due(&self, rtxn: &RoTxn, now: OffsetDateTime) -> Result<RoaringBitmap> {
+        let mut tasks = RoaringBitmap::new();
+        let now = now.unix_timestamp_nanos();
+        for result in self.run_at.range(rtxn, &(Bound::Excluded(now), Bound::Unbounded))? {
+            let (_run_at, ids) = result?;
+            tasks |= ids;
+        }
+        Ok(tasks)
+    }
+
+    /// Returns the earliest date, later than `now`, at which an enqueued task becomes due.
+    pub(crate) fn next_due_date(
+        &self,
+        rtxn: &RoTxn,
+        now: OffsetDateTime,
+    ) -> Result<Option<OffsetDateTime>> {
+        let enqueued = self.get_status(rtxn, Status::Enqueued)?;
+        let now = now.unix_timestamp_nanos();
+        for result in self.run_at.range(rtxn, &(Bound::Excluded(now), Bound::Unbounded))? {
+            let (run_at, ids) = result?;
+            if !ids.is_disjoint(&enqueued) {
+                return Ok(OffsetDateTime::from_unix_timestamp_nanos(run_at).ok());
+            }
+        }
+        Ok(None)
+    }
 }
 
 impl Queue {
@@ -279,16 +392,16 @@ impl Queue {
         &self,
         rtxn: &RoTxn,
         query: &Query,
-        processing_tasks: &ProcessingTasks,
+        processing: &ProcessingTasks,
     ) -> Result<RoaringBitmap> {
-        let ProcessingTasks { batch: processing_batch, processing: processing_tasks, progress: _ } =
-            processing_tasks;
+        let processing_tasks = &processing.processing;
         let Query {
             limit,
             from,
             reverse,
             uids,
             batch_uids,
+            group_uids,
             statuses,
             types,
             index_uids,
@@ -299,6 +412,8 @@ impl Queue {
             after_started_at,
             before_finished_at,
             after_finished_at,
+            before_run_at,
+            after_run_at,
         } = query;
 
         let mut tasks = self.tasks.all_task_ids(rtxn)?;
@@ -315,8 +430,10 @@ impl Queue {
         if let Some(batch_uids) = batch_uids {
             let mut batch_tasks = RoaringBitmap::new();
             for batch_uid in batch_uids {
-                if processing_batch.as_ref().is_some_and(|batch| batch.uid == *batch_uid) {
-                    batch_tasks |= &**processing_tasks;
+                if let Some(running) =
+                    processing.batches.iter().find(|running| running.batch.uid == *batch_uid)
+                {
+                    batch_tasks |= &*running.tasks;
                 } else {
                     batch_tasks |= self.tasks_in_batch(rtxn, *batch_uid)?;
                 }
@@ -324,6 +441,14 @@ impl Queue {
             tasks &= batch_tasks;
         }
 
+        if let Some(group_uids) = group_uids {
+            let mut group_tasks = RoaringBitmap::new();
+            for group_uid in group_uids {
+                group_tasks |= self.tasks.get_group(rtxn, *group_uid)?;
+            }
+            tasks &= group_tasks;
+        }
+
         if let Some(status) = statuses {
             let mut status_tasks = RoaringBitmap::new();
             for status in status {
@@ -388,20 +513,19 @@ impl Queue {
                 (&tasks - &**processing_tasks, &tasks & &**processing_tasks);
 
             // special case for Processing tasks
-            // A closure that clears the filtered_processing_tasks if their started_at date falls outside the given bounds
+            // A closure that removes the filtered_processing_tasks whose batch started_at date falls outside the given bounds
             let mut clear_filtered_processing_tasks =
                 |start: Bound<OffsetDateTime>, end: Bound<OffsetDateTime>| {
                     let start = map_bound(start, |b| b.unix_timestamp_nanos());
                     let end = map_bound(end, |b| b.unix_timestamp_nanos());
-                    let is_within_dates = RangeBounds::contains(
-                        &(start, end),
-                        &processing_batch
-                            .as_ref()
-                            .map_or_else(OffsetDateTime::now_utc, |batch| batch.started_at)
-                            .unix_timestamp_nanos(),
-                    );
-                    if !is_within_dates {
-                        filtered_processing_tasks.clear();
+                    for running in &processing.batches {
+                        let is_within_dates = RangeBounds::contains(
+                            &(start, end),
+                            &running.batch.started_at.unix_timestamp_nanos(),
+                        );
+                        if !is_within_dates {
+                            filtered_processing_tasks -= &*running.tasks;
+                        }
                     }
                 };
             match (after_started_at, before_started_at) {
@@ -444,6 +568,14 @@ impl Queue {
             *before_finished_at,
         )?;
 
+        keep_ids_within_datetimes(
+            rtxn,
+            &mut tasks,
+            self.tasks.run_at,
+            *after_run_at,
+            *before_run_at,
+        )?;
+
         if let Some(limit) = limit {
             tasks = if query.reverse.unwrap_or_default() {
                 tasks.into_iter().take(*limit as usize).collect()
@@ -512,26 +644,19 @@ impl Queue {
             .tasks
             .get_existing_tasks(rtxn, tasks.take(query.limit.unwrap_or(u32::MAX) as usize))?;
 
-        let ProcessingTasks { batch, processing, progress: _ } = processing_tasks;
-
         let ret = tasks.into_iter();
-        if processing.is_empty() || batch.is_none() {
+        if processing_tasks.processing.is_empty() {
             Ok((ret.collect(), total))
         } else {
-            // Safe because we ensured there was a batch in the previous branch
-            let batch = batch.as_ref().unwrap();
             Ok((
-                ret.map(|task| {
-                    if processing.contains(task.uid) {
-                        Task {
-                            status: Status::Processing,
-                            batch_uid: Some(batch.uid),
-                            started_at: Some(batch.started_at),
-                            ..task
-                        }
-                    } else {
-                        task
-                    }
+                ret.map(|task| match processing_tasks.batch_of_task(task.uid) {
+                    Some(batch) => Task {
+                        status: Status::Processing,
+                        batch_uid: Some(batch.uid),
+                        started_at: Some(batch.started_at),
+                        ..task
+                    },
+                    None => task,
                 })
                 .collect(),
                 total,
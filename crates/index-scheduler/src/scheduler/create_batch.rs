@@ -1,10 +1,15 @@
+use std::collections::HashSet;
 use std::fmt;
+use std::ops::Bound;
 
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::settings::{Settings, Unchecked};
-use meilisearch_types::tasks::{BatchStopReason, Kind, KindWithContent, Status, Task};
-use roaring::RoaringBitmap;
+use meilisearch_types::tasks::{
+    BatchStopReason, Kind, KindWithContent, Priority, Status, Task, TaskId,
+};
+use roaring::{MultiOps, RoaringBitmap};
+use time::OffsetDateTime;
 use uuid::Uuid;
 
 use super::autobatcher::{self, BatchKind};
@@ -50,6 +55,12 @@ pub(crate) enum Batch {
     UpgradeDatabase {
         tasks: Vec<Task>,
     },
+    /// The operations of a task group, each of them is an [`Batch::IndexOperation`]
+    /// on a different index.
+    TaskGroup {
+        group_uid: TaskId,
+        operations: Vec<Batch>,
+    },
 }
 
 #[derive(Debug)]
@@ -131,6 +142,9 @@ impl Batch {
             Batch::IndexSwap { task } => {
                 RoaringBitmap::from_sorted_iter(std::iter::once(task.uid)).unwrap()
             }
+            Batch::TaskGroup { operations, .. } => {
+                operations.iter().map(|operation| operation.ids()).union()
+            }
         }
     }
 
@@ -143,7 +157,8 @@ impl Batch {
             | SnapshotCreation(_)
             | Dump(_)
             | UpgradeDatabase { .. }
-            | IndexSwap { .. } => None,
+            | IndexSwap { .. }
+            | TaskGroup { .. } => None,
             IndexOperation { op, .. } => Some(op.index_uid()),
             IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
@@ -168,6 +183,7 @@ impl fmt::Display for Batch {
             Batch::IndexDeletion { .. } => f.write_str("IndexDeletion")?,
             Batch::IndexSwap { .. } => f.write_str("IndexSwap")?,
             Batch::UpgradeDatabase { .. } => f.write_str("UpgradeDatabase")?,
+            Batch::TaskGroup { group_uid, .. } => write!(f, "TaskGroup {group_uid}")?,
         };
         match index_uid {
             Some(name) => f.write_fmt(format_args!(" on {name:?} from tasks: {tasks:?}")),
@@ -436,13 +452,44 @@ impl IndexScheduler {
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::InsideCreateBatch)?;
 
-        let batch_id = self.queue.batches.next_batch_id(rtxn)?;
+        // The batches of other indexes may be processing concurrently, we must not batch their tasks again.
+        let mut running_tasks = RoaringBitmap::new();
+        let mut running_indexes = HashSet::new();
+        let mut next_batch_id = None;
+        for running in self.processing_tasks.read().unwrap().running_batches() {
+            running_tasks |= &*running.tasks;
+            running_indexes.extend(running.batch.indexes.iter().cloned());
+            next_batch_id = next_batch_id.max(Some(running.batch.uid + 1));
+        }
+
+        let batch_id =
+            self.queue.batches.next_batch_id(rtxn)?.max(next_batch_id.unwrap_or_default());
         let mut current_batch = ProcessingBatch::new(batch_id);
 
-        let enqueued = &self.queue.tasks.get_status(rtxn, Status::Enqueued)?;
+        // The scheduled tasks are only batched once their date is reached.
+        let enqueued = &(self.queue.tasks.get_status(rtxn, Status::Enqueued)?
+            - self.queue.tasks.not_yet_due(rtxn, OffsetDateTime::now_utc())?);
         let count_total_enqueued = enqueued.len();
         let failed = &self.queue.tasks.get_status(rtxn, Status::Failed)?;
 
+        // The tasks that don't operate on a single index can only be processed once the other batches are done.
+        if next_batch_id.is_some() {
+            let mut exclusive_tasks = RoaringBitmap::new();
+            for kind in [
+                Kind::UpgradeDatabase,
+                Kind::TaskCancelation,
+                Kind::TaskDeletion,
+                Kind::SnapshotCreation,
+                Kind::DumpCreation,
+                Kind::IndexSwap,
+            ] {
+                exclusive_tasks |= self.queue.tasks.get_kind(rtxn, kind)?;
+            }
+            if !exclusive_tasks.is_disjoint(enqueued) {
+                return Ok(None);
+            }
+        }
+
         // 0. The priority over everything is to upgrade the instance
         // There shouldn't be multiple upgrade tasks but just in case we're going to batch all of them at the same time
         let upgrade = self.queue.tasks.get_kind(rtxn, Kind::UpgradeDatabase)? & (enqueued | failed);
@@ -505,26 +552,103 @@ impl IndexScheduler {
             return Ok(Some((Batch::Dump(task), current_batch)));
         }
 
-        // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
-        let task_id = if let Some(task_id) = enqueued.min() { task_id } else { return Ok(None) };
-        let mut task =
-            self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
+        // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task
+        //    that doesn't operate on an index that is already being processed, among the tasks
+        //    of the highest priority. The tasks of the chosen index are then batched in order.
+        let mut available = enqueued - &running_tasks;
+        for index in &running_indexes {
+            available -= self.queue.tasks.index_tasks(rtxn, index)?;
+        }
+        let (index_name, index_tasks) = 'candidates: loop {
+            let high = &available & self.queue.tasks.get_priority(rtxn, Priority::High)?;
+            let low = &available & self.queue.tasks.get_priority(rtxn, Priority::Low)?;
+            let candidates = if !high.is_empty() {
+                high
+            } else if available.len() > low.len() {
+                &available - low
+            } else {
+                low
+            };
+            let task_id = if self.scheduler.round_robin_indexes {
+                self.next_round_robin_task(rtxn, &candidates)?
+            } else {
+                candidates.min()
+            };
+            let task_id = if let Some(task_id) = task_id { task_id } else { return Ok(None) };
+            let mut task =
+                self.queue.tasks.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
 
-        // If the task is not associated with any index, verify that it is an index swap and
-        // create the batch directly. Otherwise, get the index name associated with the task
-        // and use the autobatcher to batch the enqueued tasks associated with it
+            // If the task is not associated with any index, verify that it is an index swap and
+            // create the batch directly. Otherwise, get the index name associated with the task
+            // and use the autobatcher to batch the enqueued tasks associated with it
 
-        let index_name = if let Some(&index_name) = task.indexes().first() {
-            index_name
-        } else {
-            assert!(matches!(&task.kind, KindWithContent::IndexSwap { swaps } if swaps.is_empty()));
-            current_batch.processing(Some(&mut task));
-            current_batch.reason(BatchStopReason::TaskCannotBeBatched {
-                kind: Kind::IndexSwap,
-                id: task.uid,
-            });
-            return Ok(Some((Batch::IndexSwap { task }, current_batch)));
+            let index_name = if let Some(&index_name) = task.indexes().first() {
+                index_name
+            } else {
+                assert!(
+                    matches!(&task.kind, KindWithContent::IndexSwap { swaps } if swaps.is_empty())
+                );
+                current_batch.processing(Some(&mut task));
+                current_batch.reason(BatchStopReason::TaskCannotBeBatched {
+                    kind: Kind::IndexSwap,
+                    id: task.uid,
+                });
+                return Ok(Some((Batch::IndexSwap { task }, current_batch)));
+            };
+
+            if self.scheduler.round_robin_indexes {
+                *self.scheduler.last_batched_index.lock().unwrap() = Some(index_name.to_owned());
+            }
+
+            // A task group can only be batched once each of its tasks is the next enqueued task of
+            // its index. Otherwise, we first batch the index whose earlier tasks block the group.
+            let mut index_name = index_name.to_string();
+            let candidate_index_tasks = self.queue.tasks.index_tasks(rtxn, &index_name)?;
+            let mut index_tasks = &candidate_index_tasks & enqueued;
+            while let Some(first) = index_tasks.min() {
+                let first =
+                    self.queue.tasks.get_task(rtxn, first)?.ok_or(Error::CorruptedTaskQueue)?;
+                let group_uid = match first.group_uid {
+                    Some(group_uid) => group_uid,
+                    None => break,
+                };
+
+                let group = self.queue.tasks.get_group(rtxn, group_uid)? & enqueued;
+                let group = self.queue.tasks.get_existing_tasks(rtxn, group)?;
+                let mut blocking_index = None;
+                for task in &group {
+                    let indexes = task.indexes();
+                    let member_index = *indexes.first().ok_or(Error::CorruptedTaskQueue)?;
+                    if running_indexes.contains(member_index) {
+                        // The group can't be processed until the other batch is done, neither can
+                        // the next tasks of the index, so we look at the other indexes instead.
+                        available -= &candidate_index_tasks;
+                        continue 'candidates;
+                    }
+                    let member_index_tasks =
+                        self.queue.tasks.index_tasks(rtxn, member_index)? & enqueued;
+                    if member_index_tasks.min() != Some(task.uid) {
+                        blocking_index = Some((member_index.to_string(), member_index_tasks));
+                        break;
+                    }
+                }
+
+                match blocking_index {
+                    Some((blocking_index, blocking_index_tasks)) => {
+                        index_name = blocking_index;
+                        index_tasks = blocking_index_tasks;
+                    }
+                    None => {
+                        current_batch.reason(BatchStopReason::TaskGroup { group_uid });
+                        return Ok(self
+                            .create_task_group_batch(rtxn, group_uid, group, &mut current_batch)?
+                            .map(|batch| (batch, current_batch)));
+                    }
+                }
+            }
+            break (index_name, index_tasks);
         };
+        let index_name = index_name.as_str();
 
         let index_already_exists = self.index_mapper.exists(rtxn, index_name)?;
         let mut primary_key = None;
@@ -534,8 +658,6 @@ impl IndexScheduler {
             primary_key = index.primary_key(&rtxn)?.map(|pk| pk.to_string());
         }
 
-        let index_tasks = self.queue.tasks.index_tasks(rtxn, index_name)? & enqueued;
-
         // If autobatching is disabled we only take one task at a time.
         // Otherwise, we take only a maximum of tasks to create batches.
         let tasks_limit = if self.scheduler.autobatching_enabled {
@@ -558,6 +680,12 @@ impl IndexScheduler {
                 .get_task(rtxn, task_id)
                 .and_then(|task| task.ok_or(Error::CorruptedTaskQueue))?;
 
+            // The tasks of a group are batched together, apart from the other tasks of the index.
+            if let Some(group_uid) = task.group_uid {
+                stop_reason = BatchStopReason::TaskInGroup { id: task.uid, group_uid };
+                break;
+            }
+
             if let Some(uuid) = task.content_uuid() {
                 let content_size = self.queue.file_store.compute_size(uuid)?;
                 total_size = total_size.saturating_add(content_size);
@@ -599,4 +727,69 @@ impl IndexScheduler {
         // somehow and there is nothing to do.
         Ok(None)
     }
+
+    /// Create a batch containing one index operation for each task of the group.
+    fn create_task_group_batch(
+        &self,
+        rtxn: &RoTxn,
+        group_uid: TaskId,
+        tasks: Vec<Task>,
+        current_batch: &mut ProcessingBatch,
+    ) -> Result<Option<Batch>> {
+        let mut operations = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let index_name = task.indexes().first().ok_or(Error::CorruptedTaskQueue)?.to_string();
+            let index_already_exists = self.index_mapper.exists(rtxn, &index_name)?;
+            let mut primary_key = None;
+            if index_already_exists {
+                let index = self.index_mapper.index(rtxn, &index_name)?;
+                let rtxn = index.read_txn()?;
+                primary_key = index.primary_key(&rtxn)?.map(|pk| pk.to_string());
+            }
+
+            let Some((batchkind, create_index, _)) = autobatcher::autobatch(
+                vec![(task.uid, task.kind)],
+                index_already_exists,
+                primary_key.as_deref(),
+            ) else {
+                return Ok(None);
+            };
+            match self.create_next_batch_index(
+                rtxn,
+                index_name,
+                batchkind,
+                current_batch,
+                create_index,
+            )? {
+                Some(operation) => operations.push(operation),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(Batch::TaskGroup { group_uid, operations }))
+    }
+
+    /// Returns the first candidate task of the index that follows the last batched index,
+    /// wrapping around, so that each index with enqueued tasks gets its turn.
+    fn next_round_robin_task(
+        &self,
+        rtxn: &RoTxn,
+        candidates: &RoaringBitmap,
+    ) -> Result<Option<TaskId>> {
+        let last_index = self.scheduler.last_batched_index.lock().unwrap().clone();
+        let start = match &last_index {
+            Some(last_index) => Bound::Excluded(last_index.as_str()),
+            None => Bound::Unbounded,
+        };
+        let after_last = self.queue.tasks.index_tasks.range(rtxn, &(start, Bound::Unbounded))?;
+        for result in after_last.chain(self.queue.tasks.index_tasks.iter(rtxn)?) {
+            let (_index, tasks) = result?;
+            if let Some(task_id) = (tasks & candidates).min() {
+                return Ok(Some(task_id));
+            }
+        }
+
+        // the candidates are not associated with any index
+        Ok(candidates.min())
+    }
 }
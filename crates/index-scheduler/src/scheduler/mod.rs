@@ -16,22 +16,28 @@ mod test_embedders;
 #[cfg(test)]
 mod test_failure;
 
+use std::collections::HashSet;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use convert_case::{Case, Casing as _};
+use create_batch::Batch;
 use meilisearch_types::error::ResponseError;
-use meilisearch_types::heed::{Env, WithoutTls};
+use meilisearch_types::heed::{Env, RoTxn, WithoutTls};
 use meilisearch_types::milli;
+use meilisearch_types::milli::progress::Progress;
 use meilisearch_types::tasks::Status;
 use process_batch::ProcessBatchInfo;
 use rayon::current_num_threads;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
+use time::OffsetDateTime;
 
 use crate::processing::{AtomicTaskStep, BatchProgress};
+use crate::utils::ProcessingBatch;
 use crate::{Error, IndexScheduler, IndexSchedulerOptions, Result, TickOutcome};
 
 #[derive(Default, Clone, Debug)]
@@ -51,6 +57,27 @@ impl MustStopProcessing {
     }
 }
 
+/// Signals the scheduler when dropped, including while unwinding.
+struct WakeUpOnDrop(Arc<SignalEvent>);
+
+impl Drop for WakeUpOnDrop {
+    fn drop(&mut self) {
+        self.0.signal();
+    }
+}
+
+/// A share of the indexing memory, given back to the pool when dropped.
+struct IndexingMemory {
+    pool: Arc<Mutex<usize>>,
+    size: usize,
+}
+
+impl Drop for IndexingMemory {
+    fn drop(&mut self) {
+        *self.pool.lock().unwrap() += self.size;
+    }
+}
+
 pub struct Scheduler {
     /// A boolean that can be set to true to stop the currently processing tasks.
     pub must_stop_processing: MustStopProcessing,
@@ -67,6 +94,18 @@ pub struct Scheduler {
     /// The maximum size, in bytes, of tasks in a batch.
     pub(crate) batched_tasks_size_limit: u64,
 
+    /// The maximum number of batches operating on different indexes that are processed at the same time.
+    pub(crate) max_concurrent_batches: usize,
+
+    /// The indexing memory that isn't used by the batches processed concurrently, if it is limited.
+    pub(crate) indexing_memory: Option<Arc<Mutex<usize>>>,
+
+    /// Whether the indexes are batched in turn instead of by the order of their enqueued tasks.
+    pub(crate) round_robin_indexes: bool,
+
+    /// The index of the last batch created, used to pick the next index when batching in turn.
+    pub(crate) last_batched_index: Arc<Mutex<Option<String>>>,
+
     /// The path used to create the dumps.
     pub(crate) dumps_path: PathBuf,
 
@@ -93,6 +132,10 @@ impl Scheduler {
             autobatching_enabled: self.autobatching_enabled,
             max_number_of_batched_tasks: self.max_number_of_batched_tasks,
             batched_tasks_size_limit: self.batched_tasks_size_limit,
+            max_concurrent_batches: self.max_concurrent_batches,
+            indexing_memory: self.indexing_memory.clone(),
+            round_robin_indexes: self.round_robin_indexes,
+            last_batched_index: self.last_batched_index.clone(),
             dumps_path: self.dumps_path.clone(),
             snapshots_path: self.snapshots_path.clone(),
             auth_env: self.auth_env.clone(),
@@ -109,6 +152,10 @@ impl Scheduler {
             autobatching_enabled: options.autobatching_enabled,
             max_number_of_batched_tasks: options.max_number_of_batched_tasks,
             batched_tasks_size_limit: options.batched_tasks_size_limit,
+            max_concurrent_batches: options.max_concurrent_batches.max(1),
+            indexing_memory: options.indexer_config.max_memory.map(|max| Arc::new(Mutex::new(max))),
+            round_robin_indexes: options.round_robin_indexes,
+            last_batched_index: Arc::default(),
             dumps_path: options.dumps_path.clone(),
             snapshots_path: options.snapshots_path.clone(),
             auth_env,
@@ -131,6 +178,13 @@ impl IndexScheduler {
     ///      of their processing.
     /// 6. Reset the in-memory list of processed tasks.
     ///
+    /// When several batches can be processed concurrently, the batches operating on a single index
+    /// are processed by a worker thread and the tick returns right after starting it. The other
+    /// batches are processed once all the workers are done.
+    ///
+    /// The batches processed alone use the whole indexing memory, the workers get a share of the
+    /// memory left by the other workers when they start.
+    ///
     /// Returns the number of processed tasks.
     pub(crate) fn tick(&self) -> Result<TickOutcome> {
         #[cfg(test)]
@@ -145,31 +199,155 @@ impl IndexScheduler {
             wtxn.commit()?;
         }
 
+        // All the workers are busy, one of them will wake us up once its batch is processed.
+        let running_batches = self.processing_tasks.read().unwrap().running_batches().count();
+        if running_batches >= self.scheduler.max_concurrent_batches {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
+        // A worker took all the indexing memory, we wait for it to give it back.
+        if running_batches > 0
+            && self
+                .scheduler
+                .indexing_memory
+                .as_ref()
+                .is_some_and(|pool| *pool.lock().unwrap() == 0)
+        {
+            return Ok(TickOutcome::WaitForSignal);
+        }
+
         let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
-        let (batch, mut processing_batch) =
+        let (batch, processing_batch) =
             match self.create_next_batch(&rtxn).map_err(|e| Error::CreateBatch(Box::new(e)))? {
                 Some(batch) => batch,
                 None => return Ok(TickOutcome::WaitForSignal),
             };
         let index_uid = batch.index_uid().map(ToOwned::to_owned);
+
+        // The batches operating on a single index don't block the processing of the other indexes.
+        let concurrent = self.scheduler.max_concurrent_batches > 1 && index_uid.is_some();
+        let indexing_memory = match &self.scheduler.indexing_memory {
+            Some(pool) if concurrent => {
+                Some(self.reserve_indexing_memory(&rtxn, pool, running_batches)?)
+            }
+            _ => None,
+        };
         drop(rtxn);
 
         // 1. store the starting date with the bitmap of processing tasks.
-        let mut ids = batch.ids();
-        let processed_tasks = ids.len();
-
-        // We reset the must_stop flag to be sure that we don't stop processing tasks
-        self.scheduler.must_stop_processing.reset();
+        let ids = batch.ids();
+        let must_stop_processing = if concurrent {
+            MustStopProcessing::default()
+        } else {
+            // We reset the must_stop flag to be sure that we don't stop processing tasks
+            self.scheduler.must_stop_processing.reset();
+            self.scheduler.must_stop_processing.clone()
+        };
         let progress = self
             .processing_tasks
             .write()
             .unwrap()
             // We can clone the processing batch here because we don't want its modification to affect the view of the processing batches
-            .start_processing(processing_batch.clone(), ids.clone());
+            .start_processing(processing_batch.clone(), ids.clone(), must_stop_processing.clone());
 
         #[cfg(test)]
         self.breakpoint(crate::test_utils::Breakpoint::BatchCreated);
 
+        if concurrent {
+            let mut worker = self.private_clone();
+            worker.scheduler.must_stop_processing = must_stop_processing;
+            if let Some(memory) = &indexing_memory {
+                let mut indexer_config = self.index_mapper.indexer_config().clone();
+                indexer_config.max_memory = Some(memory.size);
+                worker.index_mapper.indexer_config = Arc::new(indexer_config);
+            }
+            std::thread::Builder::new()
+                .name(format!("batch-worker-{}", processing_batch.uid))
+                .spawn(move || {
+                    // The scheduler may be waiting for a worker to be available,
+                    // it must be woken up even if the batch panics.
+                    let _wake_up = WakeUpOnDrop(worker.scheduler.wake_up.clone());
+                    // Dropped before the wake up so that the scheduler can use the memory.
+                    let _indexing_memory = indexing_memory;
+                    if let Err(e) =
+                        worker.run_batch(batch, processing_batch, progress, ids, index_uid)
+                    {
+                        tracing::error!("{e}");
+                    }
+                })
+                .unwrap();
+            return Ok(TickOutcome::TickAgain(0));
+        }
+
+        self.run_batch(batch, processing_batch, progress, ids, index_uid)
+    }
+
+    /// Takes the share of the indexing memory of a batch processed by a worker.
+    ///
+    /// The memory left by the other workers is split between the indexes that could start a batch
+    /// right now, the index of the new batch included. A batch processed alone gets all of it.
+    fn reserve_indexing_memory(
+        &self,
+        rtxn: &RoTxn,
+        pool: &Arc<Mutex<usize>>,
+        running_batches: usize,
+    ) -> Result<IndexingMemory> {
+        let mut running_indexes = HashSet::new();
+        for running in self.processing_tasks.read().unwrap().running_batches() {
+            running_indexes.extend(running.batch.indexes.iter().cloned());
+        }
+        let enqueued = self.queue.tasks.get_status(rtxn, Status::Enqueued)?
+            - self.queue.tasks.not_yet_due(rtxn, OffsetDateTime::now_utc())?;
+        let mut pending_indexes = 0;
+        for ret in self.queue.tasks.index_tasks.iter(rtxn)? {
+            let (index, tasks) = ret?;
+            if !running_indexes.contains(index) && !tasks.is_disjoint(&enqueued) {
+                pending_indexes += 1;
+            }
+        }
+
+        let free_workers = self.scheduler.max_concurrent_batches - running_batches;
+        let mut available = pool.lock().unwrap();
+        let size = *available / pending_indexes.min(free_workers).max(1);
+        *available -= size;
+        Ok(IndexingMemory { pool: pool.clone(), size })
+    }
+
+    /// Processes the batch and writes the outcome of its tasks.
+    ///
+    /// If it fails, the batch is marked as aborted so that its tasks can be batched again.
+    fn run_batch(
+        &self,
+        batch: Batch,
+        processing_batch: ProcessingBatch,
+        progress: Progress,
+        ids: RoaringBitmap,
+        index_uid: Option<String>,
+    ) -> Result<TickOutcome> {
+        let batch_uid = processing_batch.uid;
+        let ret = catch_unwind(AssertUnwindSafe(|| {
+            self.process_and_write_batch(batch, processing_batch, progress, ids, index_uid)
+        }));
+        if !matches!(ret, Ok(Ok(_))) {
+            self.processing_tasks.write().unwrap().abort_processing(batch_uid);
+        }
+        match ret {
+            Ok(ret) => ret,
+            Err(panic) => resume_unwind(panic),
+        }
+    }
+
+    fn process_and_write_batch(
+        &self,
+        batch: Batch,
+        mut processing_batch: ProcessingBatch,
+        progress: Progress,
+        mut ids: RoaringBitmap,
+        index_uid: Option<String>,
+    ) -> Result<TickOutcome> {
+        let batch_uid = processing_batch.uid;
+        let processed_tasks = ids.len();
+
         // 2. Process the tasks
         let res = {
             let cloned_index_scheduler = self.private_clone();
@@ -215,7 +393,9 @@ impl IndexScheduler {
         };
 
         // Reset the currently updating index to relinquish the index handle
-        self.index_mapper.set_currently_updating_index(None);
+        if let Some(index_uid) = &index_uid {
+            self.index_mapper.set_currently_updating_index(index_uid, None);
+        }
 
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::AcquiringWtxn)?;
@@ -286,25 +466,27 @@ impl IndexScheduler {
                 // the `started_at` date times and `processings` of the current processing tasks.
                 // This date time is used by the task cancelation to store the right `started_at`
                 // date in the task on disk.
+                self.processing_tasks.write().unwrap().abort_processing(batch_uid);
                 return Ok(TickOutcome::TickAgain(0));
             }
             // If an index said it was full, we need to:
-            // 1. identify which index is full
+            // 1. identify which index is full, the one in the error or else the one of the batch
+            //    (a task group has no single index)
             // 2. close the associated environment
             // 3. resize it
             // 4. re-schedule tasks
             Err(Error::Milli {
                 error: milli::Error::UserError(milli::UserError::MaxDatabaseSizeReached),
-                ..
-            }) if index_uid.is_some() => {
-                // fixme: add index_uid to match to avoid the unwrap
-                let index_uid = index_uid.unwrap();
+                index_uid: full_index_uid,
+            }) if full_index_uid.is_some() || index_uid.is_some() => {
+                let index_uid = full_index_uid.or(index_uid).unwrap();
                 // fixme: handle error more gracefully? not sure when this could happen
                 self.index_mapper.resize_index(&wtxn, &index_uid)?;
                 wtxn.abort();
 
                 tracing::info!("The max database size was reached. Resizing the index.");
 
+                self.processing_tasks.write().unwrap().abort_processing(batch_uid);
                 return Ok(TickOutcome::TickAgain(0));
             }
             // In case of a failure we must get back and patch all the tasks with the error.
@@ -404,6 +586,7 @@ impl IndexScheduler {
         tracing::debug!("call trace: {:?}", progress.accumulated_durations());
 
         self.queue.write_batch(&mut wtxn, processing_batch, &ids)?;
+        let webhook_deliveries_enqueued = self.enqueue_webhook_deliveries(&mut wtxn, &ids)?;
 
         #[cfg(test)]
         self.maybe_fail(crate::test_utils::FailureLocation::CommittingWtxn)?;
@@ -412,7 +595,7 @@ impl IndexScheduler {
 
         // We should stop processing AFTER everything is processed and written to disk otherwise, a batch (which only lives in RAM) may appear in the processing task
         // and then become « not found » for some time until the commit everything is written and the final commit is made.
-        self.processing_tasks.write().unwrap().stop_processing();
+        self.processing_tasks.write().unwrap().stop_processing(batch_uid);
 
         // Once the tasks are committed, we should delete all the update files associated ASAP to avoid leaking files in case of a restart
         tracing::debug!("Deleting the update files");
@@ -438,8 +621,9 @@ impl IndexScheduler {
             Ok(())
         })?;
 
-        // We shouldn't crash the tick function if we can't send data to the webhook.
-        let _ = self.notify_webhook(&ids);
+        if webhook_deliveries_enqueued {
+            self.webhooks.wake_up.signal();
+        }
 
         #[cfg(test)]
         self.breakpoint(crate::test_utils::Breakpoint::AfterProcessing);
@@ -3,10 +3,11 @@ use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::Ordering;
 
 use meilisearch_types::batches::{BatchEnqueuedAt, BatchId};
+use meilisearch_types::error::ResponseError;
 use meilisearch_types::heed::{RoTxn, RwTxn};
 use meilisearch_types::milli::progress::{Progress, VariableNameStep};
 use meilisearch_types::milli::{self, ChannelCongestion};
-use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Details, IndexSwap, KindWithContent, Priority, Status, Task};
 use milli::update::Settings as MilliSettings;
 use roaring::RoaringBitmap;
 
@@ -22,6 +23,10 @@ use crate::utils::{
 };
 use crate::{Error, IndexScheduler, Result, TaskId};
 
+/// The share of the map size of an index that must be left once the changes of a task group are applied,
+/// so that the commit of its transaction doesn't run out of space.
+const COMMIT_HEADROOM_RATIO: usize = 20;
+
 #[derive(Debug, Default)]
 pub struct ProcessBatchInfo {
     /// The write channel congestion. None when unavailable: settings update.
@@ -145,8 +150,7 @@ impl IndexScheduler {
                 };
 
                 // the index operation can take a long time, so save this handle to make it available to the search for the duration of the tick
-                self.index_mapper
-                    .set_currently_updating_index(Some((index_uid.clone(), index.clone())));
+                self.index_mapper.set_currently_updating_index(&index_uid, Some(index.clone()));
 
                 let mut index_wtxn = index.write_txn()?;
                 let pre_commit_dabases_sizes = index.database_sizes(&index_wtxn)?;
@@ -349,6 +353,28 @@ impl IndexScheduler {
                 task.status = Status::Succeeded;
                 Ok((vec![task], ProcessBatchInfo::default()))
             }
+            Batch::TaskGroup { operations, .. } => {
+                // The indexes created for the group are deleted unless their changes were committed.
+                let mut created_indexes = Vec::new();
+                let ret = self.process_task_group(operations, &mut created_indexes, &progress);
+                // When an index is full, the group is processed again once the index is resized,
+                // so the indexes it created are kept for the next attempt.
+                let index_is_full = matches!(
+                    &ret,
+                    Err(Error::Milli {
+                        error: milli::Error::UserError(milli::UserError::MaxDatabaseSizeReached),
+                        ..
+                    })
+                );
+                if !index_is_full {
+                    for index_uid in created_indexes {
+                        let wtxn = self.env.write_txn()?;
+                        self.index_mapper.delete_index(wtxn, &index_uid)?;
+                    }
+                }
+
+                Ok((ret?, ProcessBatchInfo::default()))
+            }
             Batch::UpgradeDatabase { mut tasks } => {
                 let KindWithContent::UpgradeDatabase { from } = tasks.last().unwrap().kind else {
                     unreachable!();
@@ -382,6 +408,138 @@ impl IndexScheduler {
         }
     }
 
+    /// Apply each operation of a task group in its own index write transaction, and only
+    /// commit the transactions once all the operations succeeded.
+    ///
+    /// The name of the indexes created along the way are pushed in `created_indexes`,
+    /// and removed from it once their changes are committed.
+    fn process_task_group(
+        &self,
+        operations: Vec<Batch>,
+        created_indexes: &mut Vec<String>,
+        progress: &Progress,
+    ) -> Result<Vec<Task>> {
+        let mut indexes = Vec::with_capacity(operations.len());
+        let mut index_operations = Vec::with_capacity(operations.len());
+        for operation in operations {
+            let Batch::IndexOperation { op, must_create_index } = operation else {
+                unreachable!("a task group only contains index operations");
+            };
+            let index_uid = op.index_uid().to_string();
+            let index = if must_create_index {
+                let wtxn = self.env.write_txn()?;
+                if !self.index_mapper.exists(&wtxn, &index_uid)? {
+                    created_indexes.push(index_uid.clone());
+                }
+                self.index_mapper.create_index(wtxn, &index_uid, None)?
+            } else {
+                let rtxn = self.env.read_txn()?;
+                self.index_mapper.index(&rtxn, &index_uid)?
+            };
+            indexes.push((index_uid, index));
+            index_operations.push(op);
+        }
+
+        let mut operations_tasks = Vec::with_capacity(indexes.len());
+        let mut index_wtxns = Vec::with_capacity(indexes.len());
+        for ((_, index), op) in indexes.iter().zip(index_operations) {
+            let mut index_wtxn = index.write_txn()?;
+            let (operation_tasks, _) =
+                self.apply_index_operation(&mut index_wtxn, index, op, progress)?;
+            operations_tasks.push(operation_tasks);
+            index_wtxns.push(index_wtxn);
+        }
+
+        // If one task of the group failed, none of its changes are committed,
+        // the transactions are aborted when dropped.
+        let error = operations_tasks.iter().flatten().find_map(|task| task.error.clone());
+        if let Some(error) = error {
+            let mut tasks: Vec<_> = operations_tasks.into_iter().flatten().collect();
+            for task in tasks.iter_mut().filter(|task| task.status != Status::Failed) {
+                task.status = Status::Failed;
+                task.details = task.kind.default_finished_details();
+                task.error = Some(error.clone());
+            }
+            return Ok(tasks);
+        }
+
+        // The transactions are committed one after the other, so an index running out of space
+        // while committing would leave the group partially committed. We make sure each index has
+        // some room left before committing any of them, a full index is then resized and the group
+        // processed again.
+        for ((index_uid, index), index_wtxn) in indexes.iter().zip(&index_wtxns) {
+            let used_size: usize = index.database_sizes(index_wtxn)?.values().sum();
+            let map_size = index.map_size();
+            if used_size.saturating_add(map_size / COMMIT_HEADROOM_RATIO) > map_size {
+                return Err(Error::from_milli(
+                    milli::UserError::MaxDatabaseSizeReached.into(),
+                    Some(index_uid.clone()),
+                ));
+            }
+        }
+
+        let mut committed = Vec::with_capacity(indexes.len());
+        {
+            progress.update_progress(FinalizingIndexStep::Committing);
+            let span = tracing::trace_span!(target: "indexing::scheduler", "commit");
+            let _entered = span.enter();
+
+            for ((index_uid, _), index_wtxn) in indexes.iter().zip(index_wtxns) {
+                if let Err(error) = index_wtxn.commit() {
+                    let error = Error::from_milli(error.into(), Some(index_uid.clone()));
+                    if committed.is_empty() {
+                        return Err(error);
+                    }
+
+                    // The committed changes can't be rolled back anymore, the tasks of the
+                    // indexes that were not committed fail and the error says which ones were.
+                    tracing::error!(
+                        error = &error as &dyn std::error::Error,
+                        "A task group was only partially committed"
+                    );
+                    let error: ResponseError = Error::TaskGroupPartiallyCommitted {
+                        committed: committed.clone(),
+                        error: Box::new(error),
+                    }
+                    .into();
+                    for task in operations_tasks[committed.len()..].iter_mut().flatten() {
+                        task.status = Status::Failed;
+                        task.details = task.kind.default_finished_details();
+                        task.error = Some(error.clone());
+                    }
+                    break;
+                }
+                created_indexes.retain(|created| created != index_uid);
+                committed.push(index_uid.clone());
+            }
+        }
+
+        // The stats are not critical, we don't fail the group if we can't store them.
+        progress.update_progress(FinalizingIndexStep::ComputingStats);
+        for (index_uid, index) in
+            indexes.iter().filter(|(index_uid, _)| committed.contains(index_uid))
+        {
+            let res = || -> Result<()> {
+                let index_rtxn = index.read_txn()?;
+                let stats = crate::index_mapper::IndexStats::new(index, &index_rtxn)
+                    .map_err(|e| Error::from_milli(e, Some(index_uid.to_string())))?;
+                let mut wtxn = self.env.write_txn()?;
+                self.index_mapper.store_stats_of(&mut wtxn, index_uid, &stats)?;
+                wtxn.commit()?;
+                Ok(())
+            }();
+
+            if let Err(e) = res {
+                tracing::error!(
+                    error = &e as &dyn std::error::Error,
+                    "Could not write the stats of the index"
+                );
+            }
+        }
+
+        Ok(operations_tasks.into_iter().flatten().collect())
+    }
+
     /// Swap the index `lhs` with the index `rhs`.
     fn apply_index_swap(
         &self,
@@ -465,6 +623,7 @@ impl IndexScheduler {
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut affected_groups = HashSet::new();
         // The tasks that have been removed *per batches*.
         let mut affected_batches: HashMap<BatchId, RoaringBitmap> = HashMap::new();
 
@@ -487,6 +646,9 @@ impl IndexScheduler {
                 task.enqueued_at,
                 task.uid,
             )?;
+            if let Some(run_at) = task.run_at {
+                utils::remove_task_datetime(wtxn, self.queue.tasks.run_at, run_at, task.uid)?;
+            }
             if let Some(started_at) = task.started_at {
                 utils::remove_task_datetime(
                     wtxn,
@@ -506,6 +668,9 @@ impl IndexScheduler {
             if let Some(canceled_by) = task.canceled_by {
                 affected_canceled_by.insert(canceled_by);
             }
+            if let Some(group_uid) = task.group_uid {
+                affected_groups.insert(group_uid);
+            }
             if let Some(batch_uid) = task.batch_uid {
                 affected_batches.entry(batch_uid).or_default().insert(task_id);
             }
@@ -532,6 +697,12 @@ impl IndexScheduler {
             atomic_progress.fetch_add(1, Ordering::Relaxed);
         }
 
+        for priority in [Priority::Low, Priority::High] {
+            self.queue
+                .tasks
+                .update_priority(wtxn, priority, |bitmap| *bitmap -= &to_delete_tasks)?;
+        }
+
         progress.update_progress(TaskDeletionProgress::DeletingTasks);
         let (atomic_progress, task_progress) = AtomicTaskStep::new(to_delete_tasks.len() as u32);
         progress.update_progress(task_progress);
@@ -549,6 +720,9 @@ impl IndexScheduler {
                 }
             }
         }
+        for group_uid in affected_groups {
+            self.queue.tasks.update_group(wtxn, group_uid, |bitmap| *bitmap -= &to_delete_tasks)?;
+        }
         progress.update_progress(TaskDeletionProgress::DeletingBatches);
         let (atomic_progress, batch_progress) = AtomicBatchStep::new(affected_batches.len() as u32);
         progress.update_progress(batch_progress);
@@ -658,7 +832,17 @@ impl IndexScheduler {
         //    Notice that only the _enqueued_ ones are cancelable and we should
         //    have already aborted the indexation of the _processing_ ones
         let cancelable_tasks = self.queue.tasks.get_status(rtxn, Status::Enqueued)?;
-        let tasks_to_cancel = cancelable_tasks & matched_tasks;
+        let mut tasks_to_cancel = &cancelable_tasks & matched_tasks;
+
+        //    The tasks of a group are processed together, so canceling one of them cancels the whole group.
+        let mut groups_to_cancel = RoaringBitmap::new();
+        for result in self.queue.tasks.groups.iter(rtxn)? {
+            let (_group_uid, group) = result?;
+            if !group.is_disjoint(&tasks_to_cancel) {
+                groups_to_cancel |= group;
+            }
+        }
+        tasks_to_cancel |= groups_to_cancel & cancelable_tasks;
 
         let (task_progress, progress_obj) = AtomicTaskStep::new(tasks_to_cancel.len() as u32);
         progress.update_progress(progress_obj);
@@ -1,21 +1,24 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use big_s::S;
 use meili_snap::{json_string, snapshot};
 use meilisearch_auth::AuthFilter;
 use meilisearch_types::milli::index::IndexEmbeddingConfig;
 use meilisearch_types::milli::update::IndexDocumentsMethod::*;
+use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::{self};
 use meilisearch_types::settings::SettingEmbeddingSettings;
-use meilisearch_types::tasks::{IndexSwap, KindWithContent};
+use meilisearch_types::tasks::{IndexSwap, KindWithContent, Priority};
 use roaring::RoaringBitmap;
+use time::{Duration, OffsetDateTime};
 
 use crate::insta_snapshot::snapshot_index_scheduler;
 use crate::test_utils::Breakpoint::*;
 use crate::test_utils::{
     index_creation_task, read_json, replace_document_import_task, sample_documents,
 };
-use crate::IndexScheduler;
+use crate::{IndexScheduler, TaskOptions};
 
 #[test]
 fn insert_task_while_another_task_is_processing() {
@@ -343,6 +346,240 @@ fn do_not_batch_task_of_different_indexes() {
     snapshot!(snapshot_index_scheduler(&index_scheduler), name: "all_tasks_processed");
 }
 
+#[test]
+fn concurrent_batches_of_different_indexes() {
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_concurrent_batches = 2;
+        None
+    });
+
+    index_scheduler.register(index_creation_task("doggos", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("cattos", "id"), None, false).unwrap();
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("doggos") }, None, false)
+        .unwrap();
+
+    // The run loop is stuck on its first breakpoint, so we start the batches ourselves.
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "doggos" from tasks: RoaringBitmap<[0]> in batch 0"###);
+    // The tasks of doggos must wait for its batch to be processed, but cattos can be processed right away.
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "cattos" from tasks: RoaringBitmap<[1]> in batch 1"###);
+    assert_eq!(index_scheduler.start_next_batch(), None);
+
+    // A dump is processed alone, once all the processing batches are done.
+    index_scheduler
+        .register(
+            KindWithContent::DumpCreation { keys: Vec::new(), instance_uid: None },
+            None,
+            false,
+        )
+        .unwrap();
+    index_scheduler.processing_tasks.write().unwrap().stop_processing(0);
+    assert_eq!(index_scheduler.start_next_batch(), None);
+    index_scheduler.processing_tasks.write().unwrap().stop_processing(1);
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @"Dump from tasks: RoaringBitmap<[3]> in batch 0");
+}
+
+#[test]
+fn share_indexing_memory_between_concurrent_batches() {
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_concurrent_batches = 2;
+        config.indexer_config = Arc::new(IndexerConfig {
+            max_memory: Some(1000),
+            skip_index_budget: true,
+            ..Default::default()
+        });
+        None
+    });
+    let pool = index_scheduler.scheduler.indexing_memory.clone().unwrap();
+    let reserve = || {
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        index_scheduler.reserve_indexing_memory(&rtxn, &pool, 0).unwrap()
+    };
+
+    // A batch processed alone gets all the memory, and gives it back once done.
+    index_scheduler.register(index_creation_task("doggos", "id"), None, false).unwrap();
+    let memory = reserve();
+    assert_eq!(memory.size, 1000);
+    assert_eq!(*pool.lock().unwrap(), 0);
+    drop(memory);
+    assert_eq!(*pool.lock().unwrap(), 1000);
+
+    // The memory is split between the indexes that can start a batch, up to the number of workers.
+    index_scheduler.register(index_creation_task("cattos", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("whalos", "id"), None, false).unwrap();
+    assert_eq!(reserve().size, 500);
+}
+
+#[test]
+fn batch_tasks_by_priority() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    let register = |kind, priority| {
+        let options = TaskOptions { priority, ..Default::default() };
+        index_scheduler.register_with_options(kind, None, false, options).unwrap();
+        index_scheduler.assert_internally_consistent();
+    };
+    register(index_creation_task("doggos", "id"), Priority::Normal);
+    register(index_creation_task("cattos", "id"), Priority::Low);
+    register(index_creation_task("whalos", "id"), Priority::High);
+    register(KindWithContent::DocumentClear { index_uid: S("doggos") }, Priority::High);
+
+    // The run loop is stuck on its first breakpoint, so we start the batches ourselves.
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "whalos" from tasks: RoaringBitmap<[2]> in batch 0"###);
+    // The tasks of an index are always processed in order, even if they have a lower priority.
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "doggos" from tasks: RoaringBitmap<[0]> in batch 1"###);
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "cattos" from tasks: RoaringBitmap<[1]> in batch 2"###);
+    assert_eq!(index_scheduler.start_next_batch(), None);
+}
+
+#[test]
+fn batch_indexes_in_turn() {
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.round_robin_indexes = true;
+        None
+    });
+
+    index_scheduler.register(index_creation_task("b", "id"), None, false).unwrap();
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("b") }, None, false)
+        .unwrap();
+    index_scheduler.register(index_creation_task("c", "id"), None, false).unwrap();
+    index_scheduler.register(index_creation_task("a", "id"), None, false).unwrap();
+    *index_scheduler.scheduler.last_batched_index.lock().unwrap() = Some(S("b"));
+
+    // The index following the last batched one goes first, and then we wrap around.
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "c" from tasks: RoaringBitmap<[2]> in batch 0"###);
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "a" from tasks: RoaringBitmap<[3]> in batch 1"###);
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "b" from tasks: RoaringBitmap<[0]> in batch 2"###);
+    assert_eq!(index_scheduler.start_next_batch(), None);
+}
+
+#[test]
+fn skip_tasks_that_are_not_due_yet() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    let register = |kind, run_at| {
+        let options = TaskOptions { run_at, ..Default::default() };
+        index_scheduler.register_with_options(kind, None, false, options).unwrap();
+        index_scheduler.assert_internally_consistent();
+    };
+    let now = OffsetDateTime::now_utc();
+    register(index_creation_task("doggos", "id"), Some(now + Duration::hours(1)));
+    register(index_creation_task("cattos", "id"), None);
+    register(index_creation_task("whalos", "id"), Some(now - Duration::hours(1)));
+
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "cattos" from tasks: RoaringBitmap<[1]> in batch 0"###);
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexCreation on "whalos" from tasks: RoaringBitmap<[2]> in batch 1"###);
+    // The task scheduled in an hour stays enqueued, and the scheduler will wake up for it.
+    assert_eq!(index_scheduler.start_next_batch(), None);
+    let rtxn = index_scheduler.read_txn().unwrap();
+    let next_due_date = index_scheduler.queue.tasks.next_due_date(&rtxn, now).unwrap();
+    assert_eq!(next_due_date, Some(now + Duration::hours(1)));
+}
+
+#[test]
+fn batch_task_groups() {
+    let (index_scheduler, _handle) = IndexScheduler::test(true, vec![]);
+
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("whalos") }, None, false)
+        .unwrap();
+    let options = TaskOptions { priority: Priority::High, ..Default::default() };
+    let group = vec![
+        KindWithContent::DocumentClear { index_uid: S("doggos") },
+        KindWithContent::DocumentClear { index_uid: S("whalos") },
+    ];
+    let tasks = index_scheduler.register_group(group, false, options).unwrap();
+    snapshot!(format!("{:?}", tasks.iter().map(|task| (task.uid, task.group_uid)).collect::<Vec<_>>()), @"[(1, Some(1)), (2, Some(1))]");
+    index_scheduler.assert_internally_consistent();
+
+    // The group has the highest priority, but it must wait for the earlier task of "whalos",
+    // which is batched alone.
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexOperation::DocumentClear on "whalos" from tasks: RoaringBitmap<[0]> in batch 0"###);
+    assert_eq!(index_scheduler.start_next_batch(), None);
+
+    // The tasks of a group must operate on different indexes.
+    let group = vec![
+        KindWithContent::DocumentClear { index_uid: S("doggos") },
+        KindWithContent::DocumentClear { index_uid: S("doggos") },
+    ];
+    let err = index_scheduler.register_group(group, false, Default::default()).unwrap_err();
+    snapshot!(err, @"Indexes must be declared only once in a task group. `doggos` was specified several times.");
+    let err = index_scheduler
+        .register_group(vec![index_creation_task("doggos", "id")], false, Default::default())
+        .unwrap_err();
+    snapshot!(err, @"A task group can only contain document and settings operations, but it contains a `indexCreation` task.");
+}
+
+#[test]
+fn skip_task_groups_of_running_indexes() {
+    let (index_scheduler, _handle) = IndexScheduler::test_with_custom_config(vec![], |config| {
+        config.max_concurrent_batches = 2;
+        None
+    });
+
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("whalos") }, None, false)
+        .unwrap();
+    let group = vec![
+        KindWithContent::DocumentClear { index_uid: S("doggos") },
+        KindWithContent::DocumentClear { index_uid: S("whalos") },
+    ];
+    index_scheduler.register_group(group, false, Default::default()).unwrap();
+    index_scheduler
+        .register(KindWithContent::DocumentClear { index_uid: S("cattos") }, None, false)
+        .unwrap();
+    index_scheduler.assert_internally_consistent();
+
+    // The run loop is stuck on its first breakpoint, so we start the batches ourselves.
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexOperation::DocumentClear on "whalos" from tasks: RoaringBitmap<[0]> in batch 0"###);
+    // The group must wait for the batch of "whalos", but it doesn't prevent "cattos" from being processed.
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @r###"IndexOperation::DocumentClear on "cattos" from tasks: RoaringBitmap<[3]> in batch 1"###);
+    assert_eq!(index_scheduler.start_next_batch(), None);
+
+    index_scheduler.processing_tasks.write().unwrap().stop_processing(0);
+    snapshot!(index_scheduler.start_next_batch().unwrap(), @"TaskGroup 1 from tasks: RoaringBitmap<[1, 2]> in batch 2");
+}
+
+#[test]
+fn process_task_groups_atomically() {
+    let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+    let (file, documents_count) = sample_documents(&index_scheduler, 0, 0);
+    file.persist().unwrap();
+    let group = vec![
+        replace_document_import_task("doggos", Some("id"), 0, documents_count),
+        KindWithContent::DocumentDeletionByFilter {
+            index_uid: S("cattos"),
+            filter_expr: serde_json::json!("id = 0"),
+        },
+    ];
+    index_scheduler.register_group(group, false, Default::default()).unwrap();
+
+    // "cattos" doesn't exist, so the index created for "doggos" is deleted as well.
+    handle.advance_one_failed_batch();
+    index_scheduler.assert_internally_consistent();
+    assert!(index_scheduler.index("doggos").is_err());
+
+    let (file, documents_count) = sample_documents(&index_scheduler, 1, 1);
+    file.persist().unwrap();
+    let (other_file, other_documents_count) = sample_documents(&index_scheduler, 2, 2);
+    other_file.persist().unwrap();
+    let group = vec![
+        replace_document_import_task("doggos", Some("id"), 1, documents_count),
+        replace_document_import_task("cattos", Some("id"), 2, other_documents_count),
+    ];
+    index_scheduler.register_group(group, false, Default::default()).unwrap();
+
+    handle.advance_one_successful_batch();
+    index_scheduler.assert_internally_consistent();
+    for index_uid in ["doggos", "cattos"] {
+        let index = index_scheduler.index(index_uid).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&rtxn).unwrap(), 1);
+    }
+}
+
 #[test]
 fn swap_indexes() {
     let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
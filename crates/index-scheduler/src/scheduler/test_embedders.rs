@@ -423,8 +423,11 @@ fn import_vectors_first_and_embedder_later() {
                     max_bytes: Some(
                         400,
                     ),
+                    chunking: None,
                 },
                 quantized: None,
+                quantization: None,
+                rescoring: None,
             },
             user_provided: RoaringBitmap<[1, 2]>,
         },
@@ -620,8 +623,11 @@ fn delete_document_containing_vector() {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
                     quantized: None,
+                    quantization: None,
+                    rescoring: None,
                 },
                 user_provided: RoaringBitmap<[0]>,
             },
@@ -664,8 +670,11 @@ fn delete_document_containing_vector() {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
                     quantized: None,
+                    quantization: None,
+                    rescoring: None,
                 },
                 user_provided: RoaringBitmap<[]>,
             },
@@ -110,6 +110,8 @@ impl IndexScheduler {
             max_number_of_tasks: 1_000_000,
             max_number_of_batched_tasks: usize::MAX,
             batched_tasks_size_limit: u64::MAX,
+            max_concurrent_batches: 1,
+            round_robin_indexes: false,
             instance_features: Default::default(),
             auto_upgrade: true, // Don't cost much and will ensure the happy path works
             embedding_cache_cap: 10,
@@ -161,6 +163,22 @@ impl IndexScheduler {
             Ok(())
         }
     }
+
+    /// Create the next batch and mark its tasks as processing without processing them, as if it
+    /// was started by the run loop. Return the description of the batch, along with its uid.
+    ///
+    /// Used to check the batches created while other batches are processing.
+    pub(crate) fn start_next_batch(&self) -> Option<String> {
+        let rtxn = self.read_txn().unwrap();
+        let (batch, processing_batch) = self.create_next_batch(&rtxn).unwrap()?;
+        let description = format!("{} in batch {}", batch, processing_batch.uid);
+        self.processing_tasks.write().unwrap().start_processing(
+            processing_batch,
+            batch.ids(),
+            Default::default(),
+        );
+        Some(description)
+    }
 }
 
 /// Return a `KindWithContent::IndexCreation` task
@@ -1,6 +1,6 @@
 use anyhow::bail;
 use meilisearch_types::heed::{Env, RwTxn, WithoutTls};
-use meilisearch_types::tasks::{Details, KindWithContent, Status, Task};
+use meilisearch_types::tasks::{Details, KindWithContent, Priority, Status, Task};
 use meilisearch_types::versioning::{VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH};
 use time::OffsetDateTime;
 use tracing::info;
@@ -75,13 +75,16 @@ pub fn upgrade_index_scheduler(
         &Task {
             uid,
             batch_uid: None,
+            group_uid: None,
             enqueued_at: OffsetDateTime::now_utc(),
+            run_at: None,
             started_at: None,
             finished_at: None,
             error: None,
             canceled_by: None,
             details: Some(Details::UpgradeDatabase { from, to }),
             status: Status::Enqueued,
+            priority: Priority::Normal,
             kind: KindWithContent::UpgradeDatabase { from },
         },
     )?;
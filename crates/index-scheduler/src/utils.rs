@@ -341,6 +341,30 @@ pub(crate) fn check_index_swap_validity(task: &Task) -> Result<()> {
     Ok(())
 }
 
+/// Verify that the operations of a task group can be processed together: they must all be
+/// document or settings operations, and each of them must operate on a different index.
+pub(crate) fn check_task_group_validity(kinds: &[KindWithContent]) -> Result<()> {
+    if kinds.is_empty() {
+        return Err(Error::EmptyTaskGroup);
+    }
+    let mut all_indexes = HashSet::new();
+    for kind in kinds {
+        match kind.as_kind() {
+            Kind::DocumentAdditionOrUpdate
+            | Kind::DocumentEdition
+            | Kind::DocumentDeletion
+            | Kind::SettingsUpdate => (),
+            kind => return Err(Error::InvalidTaskGroupKind(kind)),
+        }
+        for index in kind.indexes() {
+            if !all_indexes.insert(index) {
+                return Err(Error::TaskGroupDuplicateIndexFound(index.to_string()));
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Clamp the provided value to be a multiple of system page size.
 pub fn clamp_to_page_size(size: usize) -> usize {
     size / page_size::get() * page_size::get()
@@ -358,13 +382,16 @@ impl crate::IndexScheduler {
             let Task {
                 uid,
                 batch_uid,
+                group_uid,
                 enqueued_at,
+                run_at,
                 started_at,
                 finished_at,
                 error: _,
                 canceled_by,
                 details,
                 status,
+                priority,
                 kind,
             } = task;
             assert_eq!(uid, task.uid);
@@ -408,6 +435,19 @@ impl crate::IndexScheduler {
                 .unwrap()
                 .unwrap();
             assert!(db_enqueued_at.contains(task_id));
+            if let Some(run_at) = run_at {
+                let db_run_at = self
+                    .queue
+                    .tasks
+                    .run_at
+                    .get(&rtxn, &run_at.unix_timestamp_nanos())
+                    .unwrap()
+                    .unwrap();
+                assert!(db_run_at.contains(task_id));
+            }
+            if let Some(group_uid) = group_uid {
+                assert!(self.queue.tasks.get_group(&rtxn, group_uid).unwrap().contains(task_id));
+            }
             if let Some(started_at) = started_at {
                 let db_started_at = self
                     .queue
@@ -428,6 +468,8 @@ impl crate::IndexScheduler {
                     .unwrap();
                 assert!(db_finished_at.contains(task_id));
             }
+            let db_priority = self.queue.tasks.get_priority(&rtxn, priority).unwrap();
+            assert!(db_priority.contains(uid));
             if let Some(canceled_by) = canceled_by {
                 let db_canceled_tasks =
                     self.queue.tasks.get_status(&rtxn, Status::Canceled).unwrap();
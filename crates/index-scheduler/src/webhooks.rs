@@ -0,0 +1,553 @@
+//! The webhooks notified with the tasks of every processed batch.
+//!
+//! Once a batch is processed, a delivery is enqueued for every webhook whose filter matches
+//! some of its tasks, in the transaction that commits the batch. The deliveries are persisted in
+//! the task database and sent by a dedicated thread, which retries the failed ones with an
+//! exponential backoff.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::panic::resume_unwind;
+use std::sync::Arc;
+use std::time::Duration;
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use hmac::{Hmac, Mac};
+use meilisearch_types::heed::types::{SerdeJson, Unit};
+use meilisearch_types::heed::{Database, Env, RoTxn, RwTxn, WithoutTls};
+use meilisearch_types::milli::{CboRoaringBitmapCodec, BEU32};
+use meilisearch_types::task_view::TaskView;
+use meilisearch_types::tasks::Task;
+use meilisearch_types::webhooks::{DeliveryId, DeliveryStatus, Webhook, WebhookDelivery};
+use roaring::RoaringBitmap;
+use sha2::Sha256;
+use synchronoise::SignalEvent;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::utils::{insert_task_datetime, remove_task_datetime};
+use crate::uuid_codec::UuidCodec;
+use crate::{Error, IndexScheduler, IndexSchedulerOptions, Result, BEI128};
+
+/// The number of database used by the webhooks
+const NUMBER_OF_DATABASES: u32 = 4;
+/// Database const names for the `WebhookStore`.
+mod db_name {
+    pub const WEBHOOKS: &str = "webhooks";
+    pub const WEBHOOK_DELIVERIES: &str = "webhook-deliveries";
+    pub const PENDING_WEBHOOK_DELIVERIES: &str = "pending-webhook-deliveries";
+    pub const FINISHED_WEBHOOK_DELIVERIES: &str = "finished-webhook-deliveries";
+}
+
+/// The uuid of the webhook given with `--task-webhook-url`, which is not persisted.
+pub const CLI_WEBHOOK_UUID: Uuid = Uuid::nil();
+
+/// The number of times a delivery is sent before being marked as failed.
+const MAX_DELIVERY_ATTEMPTS: u32 = 10;
+/// The delay before the first retry of a delivery, doubled after every failed attempt.
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(1);
+/// The longest delay between two attempts of a delivery.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 60);
+/// The number of finished deliveries kept in the delivery log, the oldest ones are deleted first.
+const MAX_FINISHED_DELIVERIES: usize = 1000;
+
+/// The header containing the uid of the delivery, to deduplicate the retried deliveries.
+const DELIVERY_HEADER: &str = "X-Meilisearch-Delivery";
+/// The header containing the HMAC-SHA256 of the uncompressed payload, when the webhook has a secret.
+const SIGNATURE_HEADER: &str = "X-Meilisearch-Signature";
+
+/// The outcome of an attempt to send a delivery.
+pub(crate) enum AttemptOutcome {
+    Sent {
+        status_code: u16,
+    },
+    Failed {
+        status_code: Option<u16>,
+        error: String,
+    },
+    /// The delivery cannot be sent anymore and must not be retried.
+    Abandoned {
+        error: String,
+    },
+}
+
+#[derive(Clone)]
+pub(crate) struct WebhookStore {
+    webhooks: Database<UuidCodec, SerdeJson<Webhook>>,
+    deliveries: Database<BEU32, SerdeJson<WebhookDelivery>>,
+    /// The uids of the enqueued deliveries, by the date of their next attempt.
+    pending: Database<BEI128, CboRoaringBitmapCodec>,
+    /// The uids of the succeeded and failed deliveries.
+    finished: Database<BEU32, Unit>,
+    /// The webhook given with `--task-webhook-url`.
+    cli_webhook: Option<Webhook>,
+    /// Wakes up the delivery loop when new deliveries are enqueued.
+    pub(crate) wake_up: Arc<SignalEvent>,
+}
+
+impl WebhookStore {
+    pub(crate) const fn nb_db() -> u32 {
+        NUMBER_OF_DATABASES
+    }
+
+    pub(crate) fn new(
+        env: &Env<WithoutTls>,
+        wtxn: &mut RwTxn,
+        options: &IndexSchedulerOptions,
+    ) -> Result<Self> {
+        let cli_webhook = options.webhook_url.as_ref().map(|url| Webhook {
+            uuid: CLI_WEBHOOK_UUID,
+            url: url.clone(),
+            headers: options
+                .webhook_authorization_header
+                .iter()
+                .map(|header| (String::from("Authorization"), header.clone()))
+                .collect(),
+            filter: Default::default(),
+            secret: None,
+        });
+
+        Ok(Self {
+            webhooks: env.create_database(wtxn, Some(db_name::WEBHOOKS))?,
+            deliveries: env.create_database(wtxn, Some(db_name::WEBHOOK_DELIVERIES))?,
+            pending: env.create_database(wtxn, Some(db_name::PENDING_WEBHOOK_DELIVERIES))?,
+            finished: env.create_database(wtxn, Some(db_name::FINISHED_WEBHOOK_DELIVERIES))?,
+            cli_webhook,
+            wake_up: Arc::new(SignalEvent::auto(true)),
+        })
+    }
+
+    fn is_empty(&self, rtxn: &RoTxn) -> Result<bool> {
+        Ok(self.cli_webhook.is_none() && self.webhooks.is_empty(rtxn)?)
+    }
+
+    /// Returns all the webhooks, starting with the one given with `--task-webhook-url`.
+    pub(crate) fn webhooks(&self, rtxn: &RoTxn) -> Result<Vec<Webhook>> {
+        let mut webhooks: Vec<_> = self.cli_webhook.iter().cloned().collect();
+        for ret in self.webhooks.iter(rtxn)? {
+            let (_uuid, webhook) = ret?;
+            webhooks.push(webhook);
+        }
+        Ok(webhooks)
+    }
+
+    pub(crate) fn webhook(&self, rtxn: &RoTxn, uuid: Uuid) -> Result<Option<Webhook>> {
+        if uuid == CLI_WEBHOOK_UUID {
+            return Ok(self.cli_webhook.clone());
+        }
+        Ok(self.webhooks.get(rtxn, &uuid)?)
+    }
+
+    pub(crate) fn put_webhook(&self, wtxn: &mut RwTxn, webhook: &Webhook) -> Result<()> {
+        if webhook.uuid == CLI_WEBHOOK_UUID {
+            return Err(Error::ImmutableWebhook(webhook.uuid));
+        }
+        self.webhooks.put(wtxn, &webhook.uuid, webhook)?;
+        Ok(())
+    }
+
+    /// Deletes the webhook along with its deliveries, returns `false` if it didn't exist.
+    pub(crate) fn delete_webhook(&self, wtxn: &mut RwTxn, uuid: Uuid) -> Result<bool> {
+        if uuid == CLI_WEBHOOK_UUID {
+            return Err(Error::ImmutableWebhook(uuid));
+        }
+        if !self.webhooks.delete(wtxn, &uuid)? {
+            return Ok(false);
+        }
+
+        let mut deleted = Vec::new();
+        let mut iter = self.deliveries.iter_mut(wtxn)?;
+        while let Some(ret) = iter.next() {
+            let (_uid, delivery) = ret?;
+            if delivery.webhook_uuid == uuid {
+                deleted.push(delivery);
+                // safety: We don't keep references to the database
+                unsafe { iter.del_current()? };
+            }
+        }
+        drop(iter);
+
+        for delivery in &deleted {
+            self.unindex_delivery(wtxn, delivery)?;
+        }
+        Ok(true)
+    }
+
+    /// Writes the delivery and registers it as pending or finished.
+    fn put_delivery(&self, wtxn: &mut RwTxn, delivery: &WebhookDelivery) -> Result<()> {
+        self.deliveries.put(wtxn, &delivery.uid, delivery)?;
+        if let Some(date) = delivery.next_attempt_at {
+            insert_task_datetime(wtxn, self.pending, date, delivery.uid)?;
+        }
+        if delivery.finished_at.is_some() {
+            self.finished.put(wtxn, &delivery.uid, &())?;
+        }
+        Ok(())
+    }
+
+    /// Removes the delivery from the pending and finished deliveries.
+    fn unindex_delivery(&self, wtxn: &mut RwTxn, delivery: &WebhookDelivery) -> Result<()> {
+        if let Some(date) = delivery.next_attempt_at {
+            remove_task_datetime(wtxn, self.pending, date, delivery.uid)?;
+        }
+        if delivery.finished_at.is_some() {
+            self.finished.delete(wtxn, &delivery.uid)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the deliveries of a webhook from the most recent one, starting at `from`.
+    pub(crate) fn deliveries(
+        &self,
+        rtxn: &RoTxn,
+        uuid: Uuid,
+        from: Option<DeliveryId>,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let mut deliveries = Vec::new();
+        if limit == 0 {
+            return Ok(deliveries);
+        }
+        for ret in self.deliveries.rev_range(rtxn, &(..=from.unwrap_or(DeliveryId::MAX)))? {
+            let (_uid, delivery) = ret?;
+            if delivery.webhook_uuid == uuid {
+                deliveries.push(delivery);
+                if deliveries.len() == limit {
+                    break;
+                }
+            }
+        }
+        Ok(deliveries)
+    }
+
+    /// Enqueues a delivery of the matching tasks for every webhook, returns `true` if any was enqueued.
+    pub(crate) fn enqueue_deliveries(&self, wtxn: &mut RwTxn, tasks: &[Task]) -> Result<bool> {
+        let now = OffsetDateTime::now_utc();
+        let mut next_uid = self.deliveries.last(wtxn)?.map_or(0, |(uid, _)| uid + 1);
+        let mut enqueued = false;
+
+        for webhook in self.webhooks(wtxn)? {
+            let task_uids: Vec<_> = tasks
+                .iter()
+                .filter(|task| webhook.filter.matches(task))
+                .map(|task| task.uid)
+                .collect();
+            if task_uids.is_empty() {
+                continue;
+            }
+
+            let delivery = WebhookDelivery {
+                uid: next_uid,
+                webhook_uuid: webhook.uuid,
+                status: DeliveryStatus::Enqueued,
+                task_uids,
+                attempts: 0,
+                last_status_code: None,
+                last_error: None,
+                enqueued_at: now,
+                last_attempt_at: None,
+                next_attempt_at: Some(now),
+                finished_at: None,
+            };
+            self.put_delivery(wtxn, &delivery)?;
+            next_uid += 1;
+            enqueued = true;
+        }
+
+        Ok(enqueued)
+    }
+
+    /// Returns the enqueued deliveries whose next attempt is due, in the order they were enqueued.
+    fn due_deliveries(&self, rtxn: &RoTxn, now: OffsetDateTime) -> Result<Vec<WebhookDelivery>> {
+        let mut uids = RoaringBitmap::new();
+        for ret in self.pending.range(rtxn, &(..=now.unix_timestamp_nanos()))? {
+            let (_date, pending) = ret?;
+            uids |= pending;
+        }
+
+        let mut due = Vec::with_capacity(uids.len() as usize);
+        for uid in uids {
+            due.push(self.deliveries.get(rtxn, &uid)?.ok_or(Error::CorruptedTaskQueue)?);
+        }
+        Ok(due)
+    }
+
+    /// Returns the date of the next attempt among all the enqueued deliveries.
+    fn next_attempt_date(&self, rtxn: &RoTxn) -> Result<Option<OffsetDateTime>> {
+        match self.pending.first(rtxn)? {
+            Some((date, _)) => Ok(Some(
+                OffsetDateTime::from_unix_timestamp_nanos(date)
+                    .map_err(|_| Error::CorruptedTaskQueue)?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// Records the outcome of an attempt and schedules the next one if the delivery must be retried.
+    fn record_attempt(
+        &self,
+        wtxn: &mut RwTxn,
+        uid: DeliveryId,
+        outcome: AttemptOutcome,
+    ) -> Result<()> {
+        // The delivery was deleted along with its webhook while it was being sent.
+        let Some(mut delivery) = self.deliveries.get(wtxn, &uid)? else {
+            return Ok(());
+        };
+
+        self.unindex_delivery(wtxn, &delivery)?;
+
+        let now = OffsetDateTime::now_utc();
+        delivery.attempts += 1;
+        delivery.last_attempt_at = Some(now);
+        let retry = match outcome {
+            AttemptOutcome::Sent { status_code } => {
+                delivery.status = DeliveryStatus::Succeeded;
+                delivery.last_status_code = Some(status_code);
+                delivery.last_error = None;
+                false
+            }
+            AttemptOutcome::Failed { status_code, error } => {
+                delivery.last_status_code = status_code;
+                delivery.last_error = Some(error);
+                delivery.attempts < MAX_DELIVERY_ATTEMPTS
+            }
+            AttemptOutcome::Abandoned { error } => {
+                delivery.last_status_code = None;
+                delivery.last_error = Some(error);
+                false
+            }
+        };
+
+        if retry {
+            delivery.next_attempt_at = Some(now + retry_delay(delivery.attempts));
+        } else {
+            if delivery.status == DeliveryStatus::Enqueued {
+                delivery.status = DeliveryStatus::Failed;
+            }
+            delivery.next_attempt_at = None;
+            delivery.finished_at = Some(now);
+        }
+        self.put_delivery(wtxn, &delivery)?;
+
+        if !retry {
+            self.delete_oldest_finished_deliveries(wtxn)?;
+        }
+        Ok(())
+    }
+
+    fn delete_oldest_finished_deliveries(&self, wtxn: &mut RwTxn) -> Result<()> {
+        let excess = (self.finished.len(wtxn)? as usize).saturating_sub(MAX_FINISHED_DELIVERIES);
+        let mut oldest = Vec::with_capacity(excess);
+        for ret in self.finished.iter(wtxn)?.take(excess) {
+            let (uid, ()) = ret?;
+            oldest.push(uid);
+        }
+
+        for uid in &oldest {
+            self.finished.delete(wtxn, uid)?;
+            self.deliveries.delete(wtxn, uid)?;
+        }
+        Ok(())
+    }
+}
+
+/// The delay before the next attempt of a delivery that failed `attempts` times.
+fn retry_delay(attempts: u32) -> Duration {
+    let factor = 1u32 << attempts.saturating_sub(1).min(31);
+    FIRST_RETRY_DELAY.saturating_mul(factor).min(MAX_RETRY_DELAY)
+}
+
+/// Returns the value of the signature header of a payload.
+fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    // new_from_slice function never fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(payload);
+
+    let result = mac.finalize();
+    format!("sha256={:x}", result.into_bytes())
+}
+
+impl IndexScheduler {
+    /// Enqueues the deliveries of the processed tasks to the webhooks in the transaction
+    /// committing them, returns `true` if the delivery loop must be woken up once committed.
+    pub(crate) fn enqueue_webhook_deliveries(
+        &self,
+        wtxn: &mut RwTxn,
+        processed: &RoaringBitmap,
+    ) -> Result<bool> {
+        if self.webhooks.is_empty(wtxn)? {
+            return Ok(false);
+        }
+
+        let tasks = processed
+            .iter()
+            .map(|id| self.queue.tasks.get_task(wtxn, id)?.ok_or(Error::CorruptedTaskQueue))
+            .collect::<Result<Vec<_>>>()?;
+        self.webhooks.enqueue_deliveries(wtxn, &tasks)
+    }
+
+    /// Start the loop sending the webhook deliveries.
+    ///
+    /// This function will execute in a different thread and must be called
+    /// only once per index scheduler.
+    pub(crate) fn run_webhook_deliveries(&self) {
+        let run = self.private_clone();
+        std::thread::Builder::new()
+            .name(String::from("webhook-deliveries"))
+            .spawn(move || loop {
+                match run.send_due_webhook_deliveries() {
+                    Ok(Some(date)) => {
+                        let timeout: Duration =
+                            (date - OffsetDateTime::now_utc()).try_into().unwrap_or_default();
+                        run.webhooks.wake_up.wait_timeout(timeout);
+                    }
+                    Ok(None) => run.webhooks.wake_up.wait(),
+                    Err(e) => {
+                        tracing::error!("While sending data to the webhooks: {e}");
+                        std::thread::sleep(Duration::from_secs(1));
+                    }
+                }
+            })
+            .unwrap();
+    }
+
+    /// Sends the due deliveries and returns the date of the next attempt.
+    ///
+    /// The deliveries of each webhook are sent from their own thread, so that an endpoint that is slow
+    /// or doesn't answer doesn't delay the deliveries of the other webhooks by more than one attempt.
+    fn send_due_webhook_deliveries(&self) -> Result<Option<OffsetDateTime>> {
+        let due = self.webhooks.due_deliveries(&self.read_txn()?, OffsetDateTime::now_utc())?;
+        let mut due_by_webhook: BTreeMap<Uuid, Vec<WebhookDelivery>> = BTreeMap::new();
+        for delivery in due {
+            due_by_webhook.entry(delivery.webhook_uuid).or_default().push(delivery);
+        }
+
+        std::thread::scope(|s| {
+            let handles: Vec<_> = due_by_webhook
+                .values()
+                .map(|deliveries| {
+                    let run = self.private_clone();
+                    std::thread::Builder::new()
+                        .name(String::from("webhook-delivery"))
+                        .spawn_scoped(s, move || run.send_webhook_deliveries(deliveries))
+                        .unwrap()
+                })
+                .collect();
+            handles
+                .into_iter()
+                .try_for_each(|handle| handle.join().unwrap_or_else(|panic| resume_unwind(panic)))
+        })?;
+
+        self.webhooks.next_attempt_date(&self.read_txn()?)
+    }
+
+    /// Sends the deliveries of a single webhook, in order.
+    ///
+    /// The webhook is given up for this pass after its first failed attempt, its next deliveries are sent
+    /// during the next pass instead of each of them waiting for an endpoint that doesn't answer.
+    fn send_webhook_deliveries(&self, deliveries: &[WebhookDelivery]) -> Result<()> {
+        for delivery in deliveries {
+            let outcome = self.send_webhook_delivery(delivery)?;
+            let failed = matches!(outcome, AttemptOutcome::Failed { .. });
+            if let AttemptOutcome::Failed { error, .. } = &outcome {
+                tracing::warn!(
+                    delivery_uid = delivery.uid,
+                    webhook_uuid = %delivery.webhook_uuid,
+                    "While sending data to the webhook: {error}"
+                );
+            }
+
+            let mut wtxn = self.env.write_txn()?;
+            self.webhooks.record_attempt(&mut wtxn, delivery.uid, outcome)?;
+            wtxn.commit()?;
+
+            if failed {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn send_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<AttemptOutcome> {
+        let (webhook, payload) = {
+            let rtxn = self.read_txn()?;
+            let Some(webhook) = self.webhooks.webhook(&rtxn, delivery.webhook_uuid)? else {
+                return Ok(AttemptOutcome::Abandoned {
+                    error: String::from("The webhook has been deleted."),
+                });
+            };
+
+            let mut payload = Vec::new();
+            for &task_id in &delivery.task_uids {
+                // The task may have been deleted since the delivery was enqueued.
+                if let Some(task) = self.queue.tasks.get_task(&rtxn, task_id)? {
+                    serde_json::to_writer(&mut payload, &TaskView::from_task(&task))
+                        .map_err(io::Error::from)?;
+                    payload.push(b'\n');
+                }
+            }
+            (webhook, payload)
+        };
+
+        if payload.is_empty() {
+            return Ok(AttemptOutcome::Abandoned {
+                error: String::from("All the tasks of the delivery have been deleted."),
+            });
+        }
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&payload)?;
+        let body = encoder.finish()?;
+
+        let mut request = ureq::post(&webhook.url)
+            .timeout(Duration::from_secs(30))
+            .set("Content-Encoding", "gzip")
+            .set("Content-Type", "application/x-ndjson")
+            .set(DELIVERY_HEADER, &delivery.uid.to_string());
+        for (name, value) in &webhook.headers {
+            request = request.set(name, value);
+        }
+        if let Some(secret) = &webhook.secret {
+            request = request.set(SIGNATURE_HEADER, &sign_payload(secret, &payload));
+        }
+
+        Ok(match request.send(body.as_slice()) {
+            Ok(response) => AttemptOutcome::Sent { status_code: response.status() },
+            Err(ureq::Error::Status(status_code, response)) => AttemptOutcome::Failed {
+                status_code: Some(status_code),
+                error: format!(
+                    "The webhook answered with the status code `{status_code} {}`.",
+                    response.status_text()
+                ),
+            },
+            Err(ureq::Error::Transport(transport)) => {
+                AttemptOutcome::Failed { status_code: None, error: transport.to_string() }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn retry_delay_grows_exponentially_up_to_the_max() {
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(2), Duration::from_secs(2));
+        assert_eq!(retry_delay(5), Duration::from_secs(16));
+        assert_eq!(retry_delay(13), Duration::from_secs(60 * 60));
+        assert_eq!(retry_delay(u32::MAX), MAX_RETRY_DELAY);
+    }
+
+    #[test]
+    fn payload_signature() {
+        // The expected value is the HMAC-SHA256 of the RFC 4231, test case 2.
+        assert_eq!(
+            sign_payload("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
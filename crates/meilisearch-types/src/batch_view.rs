@@ -5,13 +5,21 @@ use utoipa::ToSchema;
 
 use crate::batches::{Batch, BatchId, BatchStats};
 use crate::task_view::DetailsView;
-use crate::tasks::serialize_duration;
+use crate::tasks::{serialize_duration, Status, TaskId};
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 #[schema(rename_all = "camelCase")]
 pub struct BatchView {
     pub uid: BatchId,
+    /// The uid of the task group processed by the batch, omitted when it doesn't process a task group.
+    #[schema(value_type = Option<u32>, example = json!(4310))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_uid: Option<TaskId>,
+    /// The status of the task group as a whole, omitted when the batch doesn't process a task group.
+    #[schema(value_type = Option<Status>, example = json!("succeeded"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_status: Option<Status>,
     pub progress: Option<ProgressView>,
     pub details: DetailsView,
     pub stats: BatchStats,
@@ -29,6 +37,8 @@ impl BatchView {
     pub fn from_batch(batch: &Batch) -> Self {
         Self {
             uid: batch.uid,
+            group_uid: None,
+            group_status: None,
             progress: batch.progress.clone(),
             details: batch.details.clone(),
             stats: batch.stats.clone(),
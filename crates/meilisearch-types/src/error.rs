@@ -214,6 +214,7 @@ ImmutableApiKeyUpdatedAt              , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexCreatedAt               , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexUid                     , InvalidRequest       , BAD_REQUEST;
 ImmutableIndexUpdatedAt               , InvalidRequest       , BAD_REQUEST;
+ImmutableWebhook                      , InvalidRequest       , BAD_REQUEST;
 IndexAlreadyExists                    , InvalidRequest       , CONFLICT ;
 IndexCreationFailed                   , Internal             , INTERNAL_SERVER_ERROR;
 IndexNotFound                         , InvalidRequest       , NOT_FOUND;
@@ -238,6 +239,7 @@ MissingDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentEditionFunction        , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentGeoField               , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentGeojsonField           , InvalidRequest       , BAD_REQUEST ;
 InvalidVectorDimensions               , InvalidRequest       , BAD_REQUEST ;
 InvalidVectorsType                    , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentId                     , InvalidRequest       , BAD_REQUEST ;
@@ -252,6 +254,8 @@ InvalidIndexOffset                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexPrimaryKey                , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexUid                       , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFacets              , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchFacetBuckets        , InvalidRequest       , BAD_REQUEST ;
+InvalidMultiSearchHierarchicalFacets  , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFacetsByIndex       , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFacetOrder          , InvalidRequest       , BAD_REQUEST ;
 InvalidMultiSearchFederated           , InvalidRequest       , BAD_REQUEST ;
@@ -280,10 +284,14 @@ InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetBuckets             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDisjunctiveFacets        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchHierarchicalFacets       , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchExhaustiveFacetCount, InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
+InvalidFacetSearchFacetBranch         , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarId                      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFilter                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarFilter                  , InvalidRequest       , BAD_REQUEST ;
@@ -300,6 +308,7 @@ InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
 FacetSearchDisabled                   , InvalidRequest       , BAD_REQUEST ;
+KeywordSearchDisabled                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchVector                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowMatchesPosition      , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
@@ -307,11 +316,17 @@ InvalidSimilarShowRankingScore        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScoreDetails , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchBoost                    , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRerank                   , InvalidRequest       , BAD_REQUEST ;
+InvalidRecallEmbedder                 , InvalidRequest       , BAD_REQUEST ;
+InvalidRecallK                        , InvalidRequest       , BAD_REQUEST ;
+InvalidRecallSampleSize               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchDistinct                 , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFacetSearch            , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsKeywordSearch          , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPrefixSearch           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFaceting               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
@@ -328,24 +343,38 @@ InvalidSettingsDictionary             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSynonyms               , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsTypoTolerance          , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsLocalizedAttributes    , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsRules                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsRerankers              , InvalidRequest       , BAD_REQUEST ;
 InvalidState                          , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidStoreFile                      , Internal             , INTERNAL_SERVER_ERROR ;
 InvalidSwapDuplicateIndexFound        , InvalidRequest       , BAD_REQUEST ;
 InvalidSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterEnqueuedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterFinishedAt            , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskAfterRunAt                 , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskAfterStartedAt             , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeEnqueuedAt           , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeFinishedAt           , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskBeforeRunAt                , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskBeforeStartedAt            , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskCanceledBy                 , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskFrom                       , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskGroupDuplicateIndexFound   , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskGroupOperations            , InvalidRequest       , BAD_REQUEST ;
+InvalidTaskGroupUids                  , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskLimit                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskReverse                    , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskStatuses                   , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskTypes                      , InvalidRequest       , BAD_REQUEST ;
 InvalidTaskUids                       , InvalidRequest       , BAD_REQUEST  ;
 InvalidBatchUids                      , InvalidRequest       , BAD_REQUEST  ;
+InvalidWebhookDeliveriesFrom          , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookDeliveriesLimit         , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookFilter                  , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookHeaders                 , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookSecret                  , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookUrl                     , InvalidRequest       , BAD_REQUEST ;
+InvalidWebhookUuid                    , InvalidRequest       , BAD_REQUEST ;
 IoError                               , System               , UNPROCESSABLE_ENTITY;
 FeatureNotEnabled                     , InvalidRequest       , BAD_REQUEST ;
 MalformedPayload                      , InvalidRequest       , BAD_REQUEST ;
@@ -364,6 +393,7 @@ MissingPayload                        , InvalidRequest       , BAD_REQUEST ;
 MissingSearchHybrid                   , InvalidRequest       , BAD_REQUEST ;
 MissingSwapIndexes                    , InvalidRequest       , BAD_REQUEST ;
 MissingTaskFilters                    , InvalidRequest       , BAD_REQUEST ;
+MissingWebhookUrl                     , InvalidRequest       , BAD_REQUEST ;
 NoSpaceLeftOnDevice                   , System               , UNPROCESSABLE_ENTITY;
 PayloadTooLarge                       , InvalidRequest       , PAYLOAD_TOO_LARGE ;
 RemoteBadResponse                     , System               , BAD_GATEWAY ;
@@ -376,11 +406,13 @@ TooManySearchRequests                 , System               , SERVICE_UNAVAILAB
 TaskNotFound                          , InvalidRequest       , NOT_FOUND ;
 TaskFileNotFound                      , InvalidRequest       , NOT_FOUND ;
 BatchNotFound                         , InvalidRequest       , NOT_FOUND ;
+TaskGroupNotFound                     , InvalidRequest       , NOT_FOUND ;
 TooManyOpenFiles                      , System               , UNPROCESSABLE_ENTITY ;
 TooManyVectors                        , InvalidRequest       , BAD_REQUEST ;
 UnretrievableDocument                 , Internal             , BAD_REQUEST ;
 UnretrievableErrorCode                , InvalidRequest       , BAD_REQUEST ;
 UnsupportedMediaType                  , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
+WebhookNotFound                       , InvalidRequest       , NOT_FOUND ;
 
 // Experimental features
 VectorEmbeddingError                  , InvalidRequest       , BAD_REQUEST ;
@@ -432,11 +464,15 @@ impl ErrorCode for milli::Error {
                     | UserError::InvalidSettingsDocumentTemplateMaxBytes { .. }
                     | UserError::InvalidPrompt(_)
                     | UserError::InvalidDisableBinaryQuantization { .. }
+                    | UserError::InvalidQuantizationChange { .. }
                     | UserError::InvalidSourceForNested { .. }
                     | UserError::MissingSourceForNested { .. }
                     | UserError::InvalidSettingsEmbedder { .. } => Code::InvalidSettingsEmbedders,
                     UserError::TooManyEmbedders(_) => Code::InvalidSettingsEmbedders,
-                    UserError::InvalidPromptForEmbeddings(..) => Code::InvalidSettingsEmbedders,
+                    UserError::InvalidPromptForEmbeddings(..)
+                    | UserError::InvalidFragmentForEmbeddings { .. } => {
+                        Code::InvalidSettingsEmbedders
+                    }
                     UserError::NoPrimaryKeyCandidateFound => Code::IndexPrimaryKeyNoCandidateFound,
                     UserError::MultiplePrimaryKeyCandidatesFound { .. } => {
                         Code::IndexPrimaryKeyMultipleCandidatesFound
@@ -444,6 +480,8 @@ impl ErrorCode for milli::Error {
                     UserError::PrimaryKeyCannotBeChanged(_) => Code::IndexPrimaryKeyAlreadyExists,
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
+                    UserError::BoostRankingRuleMissing => Code::InvalidSearchBoost,
+                    UserError::InvalidBoostAttribute { .. } => Code::InvalidSearchBoost,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
                     UserError::InvalidSearchableAttribute { .. } => {
@@ -453,11 +491,16 @@ impl ErrorCode for milli::Error {
                         Code::InvalidFacetSearchFacetName
                     }
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
+                    UserError::InvalidQueryRuleFilter { .. } => Code::InvalidSettingsRules,
+                    UserError::InvalidSettingsReranker { .. } => Code::InvalidSettingsRerankers,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
+                    UserError::InvalidGeoJsonField { .. } => Code::InvalidDocumentGeojsonField,
                     UserError::InvalidVectorDimensions { .. }
+                    | UserError::InvalidMultiVectorDimensions { .. }
                     | UserError::InvalidIndexingVectorDimensions { .. } => {
                         Code::InvalidVectorDimensions
                     }
+                    UserError::SparseEmbedderSearchVector { .. } => Code::InvalidSearchVector,
                     UserError::InvalidVectorsMapType { .. }
                     | UserError::InvalidVectorsEmbedderConf { .. } => Code::InvalidVectorsType,
                     UserError::TooManyVectors(_, _) => Code::TooManyVectors,
@@ -467,6 +510,7 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::InvalidSearchEmbedder(_) => Code::InvalidSearchEmbedder,
                     UserError::InvalidSimilarEmbedder(_) => Code::InvalidSimilarEmbedder,
+                    UserError::InvalidSearchReranker(_) => Code::InvalidSearchRerank,
                     UserError::VectorEmbeddingError(_) | UserError::DocumentEmbeddingError(_) => {
                         Code::VectorEmbeddingError
                     }
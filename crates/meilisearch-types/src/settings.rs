@@ -11,7 +11,10 @@ use fst::IntoStreamer;
 use milli::index::{IndexEmbeddingConfig, PrefixSearch};
 use milli::proximity::ProximityPrecision;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, FilterableAttributesRule, Index, DEFAULT_VALUES_PER_FACET};
+use milli::vector::rerank::RerankerSettings;
+use milli::{
+    Criterion, CriterionError, FilterableAttributesRule, Index, QueryRule, DEFAULT_VALUES_PER_FACET,
+};
 use serde::{Deserialize, Serialize, Serializer};
 use utoipa::ToSchema;
 
@@ -284,10 +287,27 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsFacetSearch>)]
     #[schema(value_type = Option<bool>, example = json!(true))]
     pub facet_search: Setting<bool>,
+    /// Set to `false` to stop indexing the words of the documents in vector-only indexes.
+    ///
+    /// The search requests must then use `hybrid` to search with `q`.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsKeywordSearch>)]
+    #[schema(value_type = Option<bool>, example = json!(false))]
+    pub keyword_search: Setting<bool>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
     #[schema(value_type = Option<PrefixSearchSettings>, example = json!("Hemlo"))]
     pub prefix_search: Setting<PrefixSearchSettings>,
+    /// Merchandising rules pinning, hiding and filtering documents for the queries matching a pattern.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsRules>)]
+    #[schema(value_type = Option<Vec<QueryRule>>, example = json!([{ "pattern": "phone", "anchoring": "contains", "pinned": ["42"], "hidden": ["12"] }]))]
+    pub rules: Setting<Vec<QueryRule>>,
+    /// Rerankers that can be used to re-score the best documents of a search.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsRerankers>)]
+    #[schema(value_type = Option<BTreeMap<String, RerankerSettings>>)]
+    pub rerankers: Setting<BTreeMap<String, RerankerSettings>>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -296,6 +316,14 @@ pub struct Settings<T> {
 
 impl<T> Settings<T> {
     pub fn hide_secrets(&mut self) {
+        if let Setting::Set(rerankers) = &mut self.rerankers {
+            for reranker in rerankers.values_mut() {
+                if let Some(api_key) = &mut reranker.api_key {
+                    hide_secret(api_key);
+                }
+            }
+        }
+
         let Setting::Set(embedders) = &mut self.embedders else {
             return;
         };
@@ -309,24 +337,25 @@ impl<T> Settings<T> {
                 continue;
             };
 
-            Self::hide_secret(api_key);
+            hide_secret(api_key);
         }
     }
+}
 
-    fn hide_secret(secret: &mut String) {
-        match secret.len() {
-            x if x < 10 => {
-                secret.replace_range(.., "XXX...");
-            }
-            x if x < 20 => {
-                secret.replace_range(2.., "XXXX...");
-            }
-            x if x < 30 => {
-                secret.replace_range(3.., "XXXXX...");
-            }
-            _x => {
-                secret.replace_range(5.., "XXXXXX...");
-            }
+/// Hides most of a secret, only keeping its first characters when it is long enough.
+pub fn hide_secret(secret: &mut String) {
+    match secret.len() {
+        x if x < 10 => {
+            secret.replace_range(.., "XXX...");
+        }
+        x if x < 20 => {
+            secret.replace_range(2.., "XXXX...");
+        }
+        x if x < 30 => {
+            secret.replace_range(3.., "XXXXX...");
+        }
+        _x => {
+            secret.replace_range(5.., "XXXXXX...");
         }
     }
 }
@@ -353,7 +382,10 @@ impl Settings<Checked> {
             search_cutoff_ms: Setting::Reset,
             localized_attributes: Setting::Reset,
             facet_search: Setting::Reset,
+            keyword_search: Setting::Reset,
             prefix_search: Setting::Reset,
+            rules: Setting::Reset,
+            rerankers: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -379,7 +411,10 @@ impl Settings<Checked> {
             search_cutoff_ms,
             localized_attributes: localized_attributes_rules,
             facet_search,
+            keyword_search,
             prefix_search,
+            rules,
+            rerankers,
             _kind,
         } = self;
 
@@ -403,7 +438,10 @@ impl Settings<Checked> {
             search_cutoff_ms,
             localized_attributes: localized_attributes_rules,
             facet_search,
+            keyword_search,
             prefix_search,
+            rules,
+            rerankers,
             _kind: PhantomData,
         }
     }
@@ -453,7 +491,10 @@ impl Settings<Unchecked> {
             search_cutoff_ms: self.search_cutoff_ms,
             localized_attributes: self.localized_attributes,
             facet_search: self.facet_search,
+            keyword_search: self.keyword_search,
             prefix_search: self.prefix_search,
+            rules: self.rules,
+            rerankers: self.rerankers,
             _kind: PhantomData,
         }
     }
@@ -530,6 +571,9 @@ impl Settings<Unchecked> {
             },
             prefix_search: other.prefix_search.or(self.prefix_search),
             facet_search: other.facet_search.or(self.facet_search),
+            keyword_search: other.keyword_search.or(self.keyword_search),
+            rules: other.rules.clone().or(self.rules.clone()),
+            rerankers: other.rerankers.clone().or(self.rerankers.clone()),
             _kind: PhantomData,
         }
     }
@@ -567,7 +611,10 @@ pub fn apply_settings_to_builder(
         search_cutoff_ms,
         localized_attributes: localized_attributes_rules,
         facet_search,
+        keyword_search,
         prefix_search,
+        rules,
+        rerankers,
         _kind,
     } = settings;
 
@@ -759,6 +806,18 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match rules {
+        Setting::Set(rules) => builder.set_query_rules(rules.clone()),
+        Setting::Reset => builder.reset_query_rules(),
+        Setting::NotSet => (),
+    }
+
+    match rerankers {
+        Setting::Set(rerankers) => builder.set_rerankers(rerankers.clone()),
+        Setting::Reset => builder.reset_rerankers(),
+        Setting::NotSet => (),
+    }
+
     match prefix_search {
         Setting::Set(prefix_search) => {
             builder.set_prefix_search(PrefixSearch::from(*prefix_search))
@@ -772,6 +831,12 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_facet_search(),
         Setting::NotSet => (),
     }
+
+    match keyword_search {
+        Setting::Set(keyword_search) => builder.set_keyword_search(*keyword_search),
+        Setting::Reset => builder.reset_keyword_search(),
+        Setting::NotSet => (),
+    }
 }
 
 pub enum SecretPolicy {
@@ -876,6 +941,12 @@ pub fn settings(
 
     let facet_search = index.facet_search(rtxn)?;
 
+    let keyword_search = index.keyword_search(rtxn)?;
+
+    let rules = index.query_rules(rtxn)?;
+
+    let rerankers = index.rerankers(rtxn)?;
+
     let mut settings = Settings {
         displayed_attributes: match displayed_attributes {
             Some(attrs) => Setting::Set(attrs),
@@ -914,6 +985,9 @@ pub fn settings(
         },
         prefix_search: Setting::Set(prefix_search.unwrap_or_default()),
         facet_search: Setting::Set(facet_search),
+        keyword_search: Setting::Set(keyword_search),
+        rules: Setting::Set(rules.unwrap_or_default()),
+        rerankers: Setting::Set(rerankers.unwrap_or_default()),
         _kind: PhantomData,
     };
 
@@ -1140,7 +1214,10 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
+            keyword_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -1171,7 +1248,10 @@ pub(crate) mod test {
             localized_attributes: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
             facet_search: Setting::NotSet,
+            keyword_search: Setting::NotSet,
             prefix_search: Setting::NotSet,
+            rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
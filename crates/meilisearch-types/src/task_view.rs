@@ -6,7 +6,7 @@ use utoipa::ToSchema;
 use crate::batches::BatchId;
 use crate::error::ResponseError;
 use crate::settings::{Settings, Unchecked};
-use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Status, Task, TaskId};
+use crate::tasks::{serialize_duration, Details, IndexSwap, Kind, Priority, Status, Task, TaskId};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -18,6 +18,14 @@ pub struct TaskView {
     /// The unique identifier of the index where this task is operated.
     #[schema(value_type = Option<u32>, example = json!("movies"))]
     pub batch_uid: Option<BatchId>,
+    /// The uid of the task group the task belongs to, omitted when the task was enqueued alone.
+    #[schema(value_type = Option<u32>, example = json!(4310))]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub group_uid: Option<TaskId>,
+    /// The status of the task group as a whole, omitted when the task was enqueued alone.
+    #[schema(value_type = Option<Status>, example = json!("succeeded"))]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub group_status: Option<Status>,
     #[serde(default)]
     pub index_uid: Option<String>,
     pub status: Status,
@@ -27,6 +35,9 @@ pub struct TaskView {
     /// The uid of the task that performed the taskCancelation if the task has been canceled.
     #[schema(value_type = Option<u32>, example = json!(4326))]
     pub canceled_by: Option<TaskId>,
+    /// The priority of the task, omitted when it is `normal`.
+    #[serde(skip_serializing_if = "Priority::is_normal", default)]
+    pub priority: Priority,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<DetailsView>,
     pub error: Option<ResponseError>,
@@ -38,6 +49,14 @@ pub struct TaskView {
     #[schema(value_type = String, example = json!("2024-08-08_14:12:09.393Z"))]
     #[serde(with = "time::serde::rfc3339")]
     pub enqueued_at: OffsetDateTime,
+    /// The date before which the task is not processed, omitted when the task can be processed right away.
+    #[schema(value_type = Option<String>, example = json!("2024-08-09_00:00:00Z"))]
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        skip_serializing_if = "Option::is_none",
+        default
+    )]
+    pub run_at: Option<OffsetDateTime>,
     /// An `RFC 3339` format for date/time/duration.
     #[schema(value_type = String, example = json!("2024-08-08_14:12:09.393Z"))]
     #[serde(with = "time::serde::rfc3339::option", default)]
@@ -53,14 +72,18 @@ impl TaskView {
         TaskView {
             uid: task.uid,
             batch_uid: task.batch_uid,
+            group_uid: task.group_uid,
+            group_status: None,
             index_uid: task.index_uid().map(ToOwned::to_owned),
             status: task.status,
             kind: task.kind.as_kind(),
             canceled_by: task.canceled_by,
+            priority: task.priority,
             details: task.details.clone().map(DetailsView::from),
             error: task.error.clone(),
             duration: task.started_at.zip(task.finished_at).map(|(start, end)| end - start),
             enqueued_at: task.enqueued_at,
+            run_at: task.run_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
         }
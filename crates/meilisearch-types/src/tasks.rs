@@ -25,9 +25,19 @@ pub type TaskId = u32;
 pub struct Task {
     pub uid: TaskId,
     pub batch_uid: Option<BatchId>,
+    /// The uid of the first task of the task group this task belongs to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_uid: Option<TaskId>,
 
     #[serde(with = "time::serde::rfc3339")]
     pub enqueued_at: OffsetDateTime,
+    /// The date before which the task must not be processed.
+    #[serde(
+        with = "time::serde::rfc3339::option",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub run_at: Option<OffsetDateTime>,
     #[serde(with = "time::serde::rfc3339::option")]
     pub started_at: Option<OffsetDateTime>,
     #[serde(with = "time::serde::rfc3339::option")]
@@ -38,6 +48,8 @@ pub struct Task {
     pub details: Option<Details>,
 
     pub status: Status,
+    #[serde(default, skip_serializing_if = "Priority::is_normal")]
+    pub priority: Priority,
     pub kind: KindWithContent,
 }
 
@@ -469,6 +481,83 @@ impl fmt::Display for ParseTaskStatusError {
 }
 impl std::error::Error for ParseTaskStatusError {}
 
+/// The priority of a task.
+///
+/// The scheduler processes the tasks of the indexes with the highest priority enqueued tasks first.
+/// The tasks of a single index are always processed in the order they were enqueued.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Sequence,
+    PartialOrd,
+    Ord,
+    ToSchema,
+)]
+#[schema(example = json!(Priority::High))]
+#[serde(rename_all = "camelCase")]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    pub fn is_normal(&self) -> bool {
+        matches!(self, Priority::Normal)
+    }
+}
+
+impl Display for Priority {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Priority::Low => write!(f, "low"),
+            Priority::Normal => write!(f, "normal"),
+            Priority::High => write!(f, "high"),
+        }
+    }
+}
+
+impl FromStr for Priority {
+    type Err = ParseTaskPriorityError;
+
+    fn from_str(priority: &str) -> Result<Self, Self::Err> {
+        if priority.eq_ignore_ascii_case("low") {
+            Ok(Priority::Low)
+        } else if priority.eq_ignore_ascii_case("normal") {
+            Ok(Priority::Normal)
+        } else if priority.eq_ignore_ascii_case("high") {
+            Ok(Priority::High)
+        } else {
+            Err(ParseTaskPriorityError(priority.to_owned()))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseTaskPriorityError(pub String);
+impl fmt::Display for ParseTaskPriorityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid task priority. Available priorities are {}.",
+            self.0,
+            enum_iterator::all::<Priority>()
+                .map(|p| format!("`{p}`"))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+impl std::error::Error for ParseTaskPriorityError {}
+
 /// The type of the task.
 #[derive(
     Debug,
@@ -724,6 +813,13 @@ pub enum BatchStopReason {
     SettingsWithDocumentOperation {
         id: TaskId,
     },
+    TaskGroup {
+        group_uid: TaskId,
+    },
+    TaskInGroup {
+        id: TaskId,
+        group_uid: TaskId,
+    },
 }
 
 impl BatchStopReason {
@@ -812,6 +908,12 @@ impl Display for BatchStopReason {
                     "task with id {id} is a document operation in a batch of settings changes"
                 )
             }
+            BatchStopReason::TaskGroup { group_uid } => {
+                write!(f, "batched all the tasks of the task group {group_uid}")
+            }
+            BatchStopReason::TaskInGroup { id, group_uid } => {
+                write!(f, "task with id {id} belongs to the task group {group_uid}")
+            }
         }
     }
 }
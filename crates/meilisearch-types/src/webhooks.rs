@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::index_uid_pattern::IndexUidPattern;
+use crate::tasks::{Kind, Status, Task};
+
+pub type DeliveryId = u32;
+
+/// An endpoint notified with the tasks of every processed batch.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Webhook {
+    pub uuid: Uuid,
+    pub url: String,
+    /// The headers sent along with every delivery, such as an `Authorization` header.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub filter: WebhookFilter,
+    /// The key used to sign the payloads with HMAC-SHA256.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// Restricts the tasks sent to a webhook, `None` means that the tasks are not filtered on this criterion.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookFilter {
+    #[serde(default)]
+    pub index_uids: Option<Vec<IndexUidPattern>>,
+    #[serde(default)]
+    pub types: Option<Vec<Kind>>,
+    #[serde(default)]
+    pub statuses: Option<Vec<Status>>,
+}
+
+impl WebhookFilter {
+    /// Returns `true` if the task must be sent to the webhook.
+    ///
+    /// The tasks that are not related to an index only match when the indexes are not filtered.
+    pub fn matches(&self, task: &Task) -> bool {
+        let index_matches = match &self.index_uids {
+            None => true,
+            Some(patterns) => task
+                .indexes()
+                .iter()
+                .any(|index| patterns.iter().any(|pattern| pattern.matches_str(index))),
+        };
+        let type_matches =
+            self.types.as_ref().is_none_or(|types| types.contains(&task.kind.as_kind()));
+        let status_matches =
+            self.statuses.as_ref().is_none_or(|statuses| statuses.contains(&task.status));
+
+        index_matches && type_matches && status_matches
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub enum DeliveryStatus {
+    /// The delivery has not been sent yet, or it failed and will be retried.
+    Enqueued,
+    /// The webhook answered with a success status code.
+    Succeeded,
+    /// The webhook could not be reached after all the attempts.
+    Failed,
+}
+
+/// The tasks of a batch to send to a webhook, and the state of their delivery.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+    pub uid: DeliveryId,
+    #[schema(value_type = String)]
+    pub webhook_uuid: Uuid,
+    pub status: DeliveryStatus,
+    pub task_uids: Vec<u32>,
+    /// The number of times we tried to send the delivery.
+    pub attempts: u32,
+    /// The status code returned by the webhook on the last attempt, if it answered.
+    pub last_status_code: Option<u16>,
+    /// Why the last attempt failed.
+    pub last_error: Option<String>,
+    #[schema(value_type = String)]
+    #[serde(with = "time::serde::rfc3339")]
+    pub enqueued_at: OffsetDateTime,
+    #[schema(value_type = Option<String>)]
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_attempt_at: Option<OffsetDateTime>,
+    /// When the delivery will be sent, `None` once it is finished.
+    #[schema(value_type = Option<String>)]
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub next_attempt_at: Option<OffsetDateTime>,
+    #[schema(value_type = Option<String>)]
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub finished_at: Option<OffsetDateTime>,
+}
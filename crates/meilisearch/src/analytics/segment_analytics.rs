@@ -200,6 +200,8 @@ struct Infos {
     experimental_get_task_documents_route: bool,
     experimental_composite_embedders: bool,
     experimental_embedding_cache_entries: usize,
+    experimental_max_concurrent_batches: usize,
+    experimental_round_robin_indexes: bool,
     gpu_enabled: bool,
     db_path: bool,
     import_dump: bool,
@@ -218,6 +220,7 @@ struct Infos {
     log_level: String,
     max_indexing_memory: MaxMemory,
     max_indexing_threads: MaxThreads,
+    experimental_embedding_cache: bool,
     with_configuration_file: bool,
     ssl_auth_path: bool,
     ssl_cert_path: bool,
@@ -248,6 +251,8 @@ impl Infos {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
             experimental_embedding_cache_entries,
+            experimental_max_concurrent_batches,
+            experimental_round_robin_indexes,
             http_addr,
             master_key: _,
             env,
@@ -283,8 +288,12 @@ impl Infos {
             ScheduleSnapshot::Enabled(interval) => Some(interval),
         };
 
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } =
-            indexer_options;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            experimental_embedding_cache_path,
+            skip_index_budget: _,
+        } = indexer_options;
 
         let RuntimeTogglableFeatures {
             metrics,
@@ -315,6 +324,8 @@ impl Infos {
             experimental_get_task_documents_route: get_task_documents_route,
             experimental_composite_embedders: composite_embedders,
             experimental_embedding_cache_entries,
+            experimental_max_concurrent_batches,
+            experimental_round_robin_indexes,
             gpu_enabled: meilisearch_types::milli::vector::is_cuda_enabled(),
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
@@ -336,6 +347,7 @@ impl Infos {
             log_level: log_level.to_string(),
             max_indexing_memory,
             max_indexing_threads,
+            experimental_embedding_cache: experimental_embedding_cache_path.is_some(),
             with_configuration_file: config_file_path.is_some(),
             ssl_auth_path: ssl_auth_path.is_some(),
             ssl_cert_path: ssl_cert_path.is_some(),
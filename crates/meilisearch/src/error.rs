@@ -35,6 +35,10 @@ pub enum MeilisearchHttpError {
     PaginationInFederatedQuery(usize, &'static str),
     #[error("Inside `.queries[{0}]`: Using facet options is not allowed in federated queries.\n - Hint: remove `facets` from query #{0} or remove `federation` from the request\n - Hint: pass `federation.facetsByIndex.{1}: {2:?}` for facets in federated search")]
     FacetsInFederatedQuery(usize, String, Vec<String>),
+    #[error("Inside `.queries[{0}]`: Using facet buckets is not allowed in federated queries.\n - Hint: remove `facetBuckets` from query #{0} or remove `federation` from the request\n - Hint: pass `federation.facetBuckets` and add index `{1}` to `federation.facetsByIndex` for facet buckets in federated search")]
+    FacetBucketsInFederatedQuery(usize, String),
+    #[error("Inside `.queries[{0}]`: Using hierarchical facets is not allowed in federated queries.\n - Hint: remove `hierarchicalFacets` from query #{0} or remove `federation` from the request")]
+    HierarchicalFacetsInFederatedQuery(usize),
     #[error("Inconsistent order for values in facet `{facet}`: index `{previous_uid}` orders {previous_facet_order}, but index `{current_uid}` orders {index_facet_order}.\n - Hint: Remove `federation.mergeFacets` or change `faceting.sortFacetValuesBy` to be consistent in settings.")]
     InconsistentFacetOrder {
         facet: String,
@@ -55,6 +59,10 @@ pub enum MeilisearchHttpError {
         .0.iter().map(|uid| format!("\"{uid}\"")).collect::<Vec<_>>().join(", "), .0.len()
     )]
     SwapIndexPayloadWrongLength(Vec<IndexUid>),
+    #[error("Each operation of a task group must contain exactly one of `addDocuments`, `updateDocuments`, `deleteDocuments` or `updateSettings`. The operation on `{0}` does not.")]
+    TaskGroupOperationWrongLength(IndexUid),
+    #[error("The documents of a task group must be JSON objects. The operation on `{0}` contains other values.")]
+    TaskGroupDocumentsMustBeObjects(IndexUid),
     #[error(transparent)]
     IndexUid(#[from] IndexUidFormatError),
     #[error(transparent)]
@@ -101,6 +109,10 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::TooManySearchRequests(_) => Code::TooManySearchRequests,
             MeilisearchHttpError::SearchLimiterIsDown => Code::Internal,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
+            MeilisearchHttpError::TaskGroupOperationWrongLength(_)
+            | MeilisearchHttpError::TaskGroupDocumentsMustBeObjects(_) => {
+                Code::InvalidTaskGroupOperations
+            }
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
             MeilisearchHttpError::SerdeJson(_) => Code::Internal,
             MeilisearchHttpError::HeedError(_) => Code::Internal,
@@ -117,7 +129,11 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::PaginationInFederatedQuery(_, _) => {
                 Code::InvalidMultiSearchQueryPagination
             }
-            MeilisearchHttpError::FacetsInFederatedQuery(..) => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::FacetsInFederatedQuery(..)
+            | MeilisearchHttpError::FacetBucketsInFederatedQuery(..)
+            | MeilisearchHttpError::HierarchicalFacetsInFederatedQuery(..) => {
+                Code::InvalidMultiSearchQueryFacets
+            }
             MeilisearchHttpError::InconsistentFacetOrder { .. } => {
                 Code::InvalidMultiSearchFacetOrder
             }
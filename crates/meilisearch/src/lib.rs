@@ -234,6 +234,8 @@ pub fn setup_meilisearch(opt: &Opt) -> anyhow::Result<(Arc<IndexScheduler>, Arc<
         instance_features: opt.to_instance_features(),
         auto_upgrade: opt.experimental_dumpless_upgrade,
         embedding_cache_cap: opt.experimental_embedding_cache_entries,
+        max_concurrent_batches: opt.experimental_max_concurrent_batches,
+        round_robin_indexes: opt.experimental_round_robin_indexes,
     };
     let bin_major: u32 = VERSION_MAJOR.parse().unwrap();
     let bin_minor: u32 = VERSION_MINOR.parse().unwrap();
@@ -13,6 +13,7 @@ use byte_unit::{Byte, ParseError, UnitType};
 use clap::Parser;
 use meilisearch_types::features::InstanceTogglableFeatures;
 use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::vector::PersistentEmbeddingCache;
 use meilisearch_types::milli::ThreadPoolNoAbortBuilder;
 use rustls::server::{ServerSessionMemoryCache, WebPkiClientVerifier};
 use rustls::RootCertStore;
@@ -65,6 +66,8 @@ const MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_TOTAL_SIZE: &str =
     "MEILI_EXPERIMENTAL_LIMIT_BATCHED_TASKS_SIZE";
 const MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES: &str =
     "MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES";
+const MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES: &str = "MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES";
+const MEILI_EXPERIMENTAL_ROUND_ROBIN_INDEXES: &str = "MEILI_EXPERIMENTAL_ROUND_ROBIN_INDEXES";
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
 const DEFAULT_HTTP_ADDR: &str = "localhost:7700";
@@ -77,6 +80,7 @@ const DEFAULT_DUMP_DIR: &str = "dumps/";
 
 const MEILI_MAX_INDEXING_MEMORY: &str = "MEILI_MAX_INDEXING_MEMORY";
 const MEILI_MAX_INDEXING_THREADS: &str = "MEILI_MAX_INDEXING_THREADS";
+const MEILI_EXPERIMENTAL_EMBEDDING_CACHE_PATH: &str = "MEILI_EXPERIMENTAL_EMBEDDING_CACHE_PATH";
 const DEFAULT_LOG_EVERY_N: usize = 100_000;
 
 // Each environment (index and task-db) is taking space in the virtual address space.
@@ -349,7 +353,7 @@ pub struct Opt {
     /// Experimental contains filter feature. For more information,
     /// see: <https://github.com/orgs/meilisearch/discussions/763>
     ///
-    /// Enables the experimental contains filter operator.
+    /// Enables the experimental `CONTAINS`, `STARTS WITH`, `ENDS WITH` and `MATCHES` filter operators.
     #[clap(long, env = MEILI_EXPERIMENTAL_CONTAINS_FILTER)]
     #[serde(default)]
     pub experimental_contains_filter: bool,
@@ -455,6 +459,23 @@ pub struct Opt {
     #[serde(default = "default_embedding_cache_entries")]
     pub experimental_embedding_cache_entries: usize,
 
+    /// Experimentally processes the batches of different indexes at the same time, with at most this number of batches
+    /// processed at once. The indexing memory is handed out to the batches when they start, a batch processed alone
+    /// gets all of it.
+    ///
+    /// Index swaps, dumps, snapshots, task cancelations and deletions are always processed alone.
+    #[clap(long, env = MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES, default_value_t = default_max_concurrent_batches())]
+    #[serde(default = "default_max_concurrent_batches")]
+    pub experimental_max_concurrent_batches: usize,
+
+    /// Experimentally batches the enqueued tasks of the indexes in turn, instead of following the order
+    /// in which the tasks were enqueued, so that no index can monopolize the task queue.
+    ///
+    /// The tasks of a single index are still processed in the order they were enqueued.
+    #[clap(long, env = MEILI_EXPERIMENTAL_ROUND_ROBIN_INDEXES)]
+    #[serde(default)]
+    pub experimental_round_robin_indexes: bool,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
@@ -559,6 +580,8 @@ impl Opt {
             experimental_max_number_of_batched_tasks,
             experimental_limit_batched_tasks_total_size,
             experimental_embedding_cache_entries,
+            experimental_max_concurrent_batches,
+            experimental_round_robin_indexes,
         } = self;
         export_to_env_if_not_present(MEILI_DB_PATH, db_path);
         export_to_env_if_not_present(MEILI_HTTP_ADDR, http_addr);
@@ -655,6 +678,14 @@ impl Opt {
             MEILI_EXPERIMENTAL_EMBEDDING_CACHE_ENTRIES,
             experimental_embedding_cache_entries.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_MAX_CONCURRENT_BATCHES,
+            experimental_max_concurrent_batches.to_string(),
+        );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_ROUND_ROBIN_INDEXES,
+            experimental_round_robin_indexes.to_string(),
+        );
         indexer_options.export_to_env();
     }
 
@@ -726,6 +757,13 @@ pub struct IndexerOpts {
     #[serde(default)]
     pub max_indexing_threads: MaxThreads,
 
+    /// Enables an experimental on-disk cache of the embeddings computed by the embedders, stored in the given directory.
+    ///
+    /// The cache is shared between indexing and search, and is kept across restarts. Keep it outside of the database
+    /// directory so that reindexing the documents after a dump import doesn't request the embeddings from the embedders again.
+    #[clap(long, env = MEILI_EXPERIMENTAL_EMBEDDING_CACHE_PATH)]
+    pub experimental_embedding_cache_path: Option<PathBuf>,
+
     /// Whether or not we want to determine the budget of virtual memory address space we have available dynamically
     /// (the default), or statically.
     ///
@@ -739,7 +777,12 @@ pub struct IndexerOpts {
 impl IndexerOpts {
     /// Exports the values to their corresponding env vars if they are not set.
     pub fn export_to_env(self) {
-        let IndexerOpts { max_indexing_memory, max_indexing_threads, skip_index_budget: _ } = self;
+        let IndexerOpts {
+            max_indexing_memory,
+            max_indexing_threads,
+            experimental_embedding_cache_path,
+            skip_index_budget: _,
+        } = self;
         if let Some(max_indexing_memory) = max_indexing_memory.0 {
             export_to_env_if_not_present(
                 MEILI_MAX_INDEXING_MEMORY,
@@ -750,6 +793,12 @@ impl IndexerOpts {
             MEILI_MAX_INDEXING_THREADS,
             max_indexing_threads.0.to_string(),
         );
+        if let Some(experimental_embedding_cache_path) = experimental_embedding_cache_path {
+            export_to_env_if_not_present(
+                MEILI_EXPERIMENTAL_EMBEDDING_CACHE_PATH,
+                experimental_embedding_cache_path,
+            );
+        }
     }
 }
 
@@ -761,6 +810,11 @@ impl TryFrom<&IndexerOpts> for IndexerConfig {
             .thread_name(|index| format!("indexing-thread:{index}"))
             .num_threads(*other.max_indexing_threads)
             .build()?;
+        let embedding_cache = other
+            .experimental_embedding_cache_path
+            .as_deref()
+            .map(PersistentEmbeddingCache::open)
+            .transpose()?;
 
         Ok(Self {
             log_every_n: Some(DEFAULT_LOG_EVERY_N),
@@ -768,6 +822,7 @@ impl TryFrom<&IndexerOpts> for IndexerConfig {
             thread_pool: Some(thread_pool),
             max_positions_per_attributes: None,
             skip_index_budget: other.skip_index_budget,
+            embedding_cache,
             ..Default::default()
         })
     }
@@ -976,6 +1031,10 @@ fn default_embedding_cache_entries() -> usize {
     0
 }
 
+fn default_max_concurrent_batches() -> usize {
+    1
+}
+
 fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
@@ -98,13 +98,29 @@ async fn get_batch(
     let (batches, _) = index_scheduler.get_batches_from_authorized_indexes(&query, filters)?;
 
     if let Some(batch) = batches.first() {
-        let batch_view = BatchView::from_batch(batch);
+        let mut batch_view = BatchView::from_batch(batch);
+        fill_task_groups(&index_scheduler, std::slice::from_mut(&mut batch_view))?;
         Ok(HttpResponse::Ok().json(batch_view))
     } else {
         Err(index_scheduler::Error::BatchNotFound(batch_uid).into())
     }
 }
 
+/// Fills the uid and the status of the task group processed by the batches that process one.
+fn fill_task_groups(
+    index_scheduler: &IndexScheduler,
+    batches: &mut [BatchView],
+) -> Result<(), ResponseError> {
+    let groups = index_scheduler.get_batches_task_group(batches.iter().map(|batch| batch.uid))?;
+    let statuses = index_scheduler.get_task_groups_status(groups.values().copied())?;
+    for batch in batches {
+        batch.group_uid = groups.get(&batch.uid).copied();
+        batch.group_status =
+            batch.group_uid.and_then(|group_uid| statuses.get(&group_uid).copied());
+    }
+    Ok(())
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct AllBatches {
     results: Vec<BatchView>,
@@ -184,6 +200,7 @@ async fn get_batches(
     let filters = index_scheduler.filters();
     let (tasks, total) = index_scheduler.get_batches_from_authorized_indexes(&query, filters)?;
     let mut results: Vec<_> = tasks.iter().map(BatchView::from_batch).collect();
+    fill_task_groups(&index_scheduler, &mut results)?;
 
     // If we were able to fetch the number +1 tasks we asked
     // it means that there is more to come.
@@ -6,7 +6,7 @@ use deserr::actix_web::AwebJson;
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use serde_json::Value;
@@ -20,8 +20,8 @@ use crate::routes::indexes::search::search_kind;
 use crate::search::{
     add_search_rules, perform_facet_search, FacetSearchResult, HybridQuery, MatchingStrategy,
     RankingScoreThreshold, SearchQuery, SearchResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
-    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
-    DEFAULT_SEARCH_OFFSET,
+    DEFAULT_HIERARCHY_SEPARATOR, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 use crate::search_queue::SearchQueue;
 
@@ -52,6 +52,12 @@ pub struct FacetSearchQuery {
     pub facet_query: Option<String>,
     #[deserr(error = DeserrJsonError<InvalidFacetSearchFacetName>, missing_field_error = DeserrJsonError::missing_facet_search_facet_name)]
     pub facet_name: String,
+    /// Only search within the children of this path of a hierarchical facet.
+    #[deserr(default, error = DeserrJsonError<InvalidFacetSearchFacetBranch>)]
+    pub facet_branch: Option<String>,
+    #[deserr(default = DEFAULT_HIERARCHY_SEPARATOR(), error = DeserrJsonError<InvalidFacetSearchFacetBranch>)]
+    #[schema(default = DEFAULT_HIERARCHY_SEPARATOR)]
+    pub facet_branch_separator: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
@@ -92,6 +98,8 @@ impl FacetSearchAggregator {
         let FacetSearchQuery {
             facet_query: _,
             facet_name,
+            facet_branch,
+            facet_branch_separator: _,
             vector,
             q,
             filter,
@@ -114,7 +122,8 @@ impl FacetSearchAggregator {
                 || hybrid.is_some()
                 || ranking_score_threshold.is_some()
                 || locales.is_some()
-                || exhaustive_facet_count.is_some(),
+                || exhaustive_facet_count.is_some()
+                || facet_branch.is_some(),
             ..Default::default()
         }
     }
@@ -247,6 +256,16 @@ pub async fn search(
 
     let facet_query = query.facet_query.clone();
     let facet_name = query.facet_name.clone();
+    let facet_prefix = match &query.facet_branch {
+        Some(_) if query.facet_branch_separator.is_empty() => {
+            return Err(ResponseError::from_msg(
+                "The `facetBranchSeparator` must not be empty.".to_string(),
+                Code::InvalidFacetSearchFacetBranch,
+            ))
+        }
+        Some(branch) => Some(format!("{branch}{}", query.facet_branch_separator)),
+        None => None,
+    };
     let locales = query.locales.clone().map(|l| l.into_iter().map(Into::into).collect());
     let mut search_query = SearchQuery::from(query);
 
@@ -264,6 +283,7 @@ pub async fn search(
             search_query,
             facet_query,
             facet_name,
+            facet_prefix,
             search_kind,
             index_scheduler.features(),
             locales,
@@ -289,6 +309,8 @@ impl From<FacetSearchQuery> for SearchQuery {
         let FacetSearchQuery {
             facet_query: _,
             facet_name: _,
+            facet_branch: _,
+            facet_branch_separator: _,
             q,
             vector,
             filter,
@@ -326,8 +348,12 @@ impl From<FacetSearchQuery> for SearchQuery {
             show_ranking_score_details: false,
             filter,
             sort: None,
+            boost: None,
             distinct: None,
             facets: None,
+            disjunctive_facets: false,
+            facet_buckets: None,
+            hierarchical_facets: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
@@ -335,6 +361,7 @@ impl From<FacetSearchQuery> for SearchQuery {
             vector,
             attributes_to_search_on,
             hybrid,
+            rerank: None,
             ranking_score_threshold,
             locales,
         }
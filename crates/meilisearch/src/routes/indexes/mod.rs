@@ -19,7 +19,8 @@ use tracing::debug;
 use utoipa::{IntoParams, OpenApi, ToSchema};
 
 use super::{
-    get_task_id, Pagination, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT,
+    get_task_id, get_task_options, Pagination, PaginationView, SummarizedTaskView,
+    PAGINATION_DEFAULT_LIMIT,
 };
 use crate::analytics::{Aggregate, Analytics};
 use crate::extractors::authentication::policies::*;
@@ -30,6 +31,7 @@ use crate::Opt;
 
 pub mod documents;
 pub mod facet_search;
+pub mod recall;
 pub mod search;
 mod search_analytics;
 #[cfg(test)]
@@ -44,6 +46,7 @@ mod similar_analytics;
     nest(
         (path = "/", api = documents::DocumentsApi),
         (path = "/", api = facet_search::FacetSearchApi),
+        (path = "/", api = recall::RecallApi),
         (path = "/", api = similar::SimilarApi),
         (path = "/", api = settings::SettingsApi),
     ),
@@ -77,6 +80,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
             .service(web::scope("/similar").configure(similar::configure))
+            .service(web::scope("/recall").configure(recall::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
 }
@@ -270,10 +274,12 @@ pub async fn create_index(
         let task = KindWithContent::IndexCreation { index_uid: uid.to_string(), primary_key };
         let uid = get_task_id(&req, &opt)?;
         let dry_run = is_dry_run(&req, &opt)?;
-        let task: SummarizedTaskView =
-            tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                .await??
-                .into();
+        let options = get_task_options(&req)?;
+        let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+            index_scheduler.register_with_options(task, uid, dry_run, options)
+        })
+        .await??
+        .into();
         debug!(returns = ?task, "Create index");
 
         Ok(HttpResponse::Accepted().json(task))
@@ -431,10 +437,12 @@ pub async fn update_index(
 
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let options = get_task_options(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task, uid, dry_run, options)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Update index");
     Ok(HttpResponse::Accepted().json(task))
@@ -479,10 +487,12 @@ pub async fn delete_index(
     let task = KindWithContent::IndexDeletion { index_uid: index_uid.into_inner() };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let options = get_task_options(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task, uid, dry_run, options)
+    })
+    .await??
+    .into();
     debug!(returns = ?task, "Delete index");
 
     Ok(HttpResponse::Accepted().json(task))
@@ -498,6 +508,9 @@ pub struct IndexStats {
     pub raw_document_db_size: u64,
     /// Average size of a document in the documents database.
     pub avg_document_size: u64,
+    /// Size of the keyword search databases that were dropped when the keyword search was disabled, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keyword_search_saved_size: Option<u64>,
     /// Whether or not the index is currently ingesting document
     pub is_indexing: bool,
     /// Number of embeddings in the index
@@ -520,6 +533,10 @@ impl From<index_scheduler::IndexStats> for IndexStats {
                 .unwrap_or(stats.inner_stats.documents_database_stats.number_of_entries()),
             raw_document_db_size: stats.inner_stats.documents_database_stats.total_size(),
             avg_document_size: stats.inner_stats.documents_database_stats.average_value_size(),
+            keyword_search_saved_size: stats
+                .inner_stats
+                .keyword_search_saved_stats
+                .map(|stats| stats.total_size()),
             is_indexing: stats.is_indexing,
             number_of_embeddings: stats.inner_stats.number_of_embeddings,
             number_of_embedded_documents: stats.inner_stats.number_of_embedded_documents,
@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+
+use actix_web::web::{self, Data};
+use actix_web::HttpResponse;
+use deserr::actix_web::AwebQueryParameter;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli::vector::recall::{measure_recall, Recall};
+use serde::Serialize;
+use tracing::debug;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+
+use super::ActionPolicy;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(get_recall),
+    tags(
+        (
+            name = "Recall",
+            description = "The /recall route measures how many of the exact nearest neighbors of a document the approximate vector search of each embedder finds.",
+        ),
+    ),
+)]
+pub struct RecallApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_recall))));
+}
+
+pub const DEFAULT_RECALL_K: fn() -> usize = || 10;
+pub const DEFAULT_RECALL_SAMPLE_SIZE: fn() -> usize = || 100;
+// each sampled query is an exact search over all the documents, the caps bound the duration of a measure
+pub const MAX_RECALL_K: usize = 100;
+pub const MAX_RECALL_SAMPLE_SIZE: usize = 1000;
+
+#[derive(Debug, deserr::Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct RecallQuery {
+    /// The embedder to measure, all the embedders of the index are measured when it is missing
+    #[deserr(default, error = DeserrQueryParamError<InvalidRecallEmbedder>)]
+    #[param(value_type = Option<String>, example = "default")]
+    embedder: Option<String>,
+    /// The number of nearest neighbors searched for each query, at most 100
+    #[deserr(default = Param(DEFAULT_RECALL_K()), error = DeserrQueryParamError<InvalidRecallK>)]
+    #[param(value_type = usize, default = DEFAULT_RECALL_K)]
+    k: Param<usize>,
+    /// The number of documents whose embeddings are used as queries, at most 1000
+    #[deserr(default = Param(DEFAULT_RECALL_SAMPLE_SIZE()), error = DeserrQueryParamError<InvalidRecallSampleSize>)]
+    #[param(value_type = usize, default = DEFAULT_RECALL_SAMPLE_SIZE)]
+    sample_size: Param<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecallView {
+    /// The number of nearest neighbors searched for each query
+    k: usize,
+    /// The number of documents whose embeddings were used as queries
+    sampled_queries: usize,
+    /// The ratio of the exact nearest neighbors found by the approximate search, between 0 and 1
+    ///
+    /// `null` when less than two documents have embeddings, or for sparse embedders that are always searched exactly
+    recall: Option<f64>,
+}
+
+impl From<Recall> for RecallView {
+    fn from(Recall { k, sampled_queries, recall }: Recall) -> Self {
+        Self { k, sampled_queries, recall }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecallResult {
+    /// The recall of each measured embedder, by name
+    embedders: BTreeMap<String, RecallView>,
+}
+
+/// Measure the recall of the vector search
+///
+/// Samples documents and uses their embeddings as queries, to compare the nearest neighbors found by the
+/// approximate vector search of each embedder with the ones found by an exact search over all the documents.
+///
+/// The measure takes as long as `sampleSize` exact searches, it is meant to tune the embedders of an index
+/// and requires the right to update its settings.
+#[utoipa::path(
+    get,
+    path = "{indexUid}/recall",
+    tag = "Recall",
+    security(("Bearer" = ["settings.update", "settings.*", "*"])),
+    params(
+        ("indexUid" = String, Path, example = "movies", description = "Index Unique Identifier", nullable = false),
+        RecallQuery
+    ),
+    responses(
+        (status = 200, description = "The recall of the embedders", body = RecallResult, content_type = "application/json", example = json!(
+            {
+                "embedders": {
+                    "default": {
+                        "k": 10,
+                        "sampledQueries": 100,
+                        "recall": 0.974
+                    }
+                }
+            }
+        )),
+        (status = 404, description = "Index not found", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Index `movies` not found.",
+                "code": "index_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#index_not_found"
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn get_recall(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebQueryParameter<RecallQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let RecallQuery { embedder, k: Param(k), sample_size: Param(sample_size) } =
+        params.into_inner();
+    debug!(parameters = ?(&embedder, k, sample_size), "Get recall");
+
+    if k == 0 || k > MAX_RECALL_K {
+        return Err(ResponseError::from_msg(
+            format!("Invalid value in parameter `k`: the number of neighbors must be between 1 and {MAX_RECALL_K}"),
+            Code::InvalidRecallK,
+        ));
+    }
+    if sample_size > MAX_RECALL_SAMPLE_SIZE {
+        return Err(ResponseError::from_msg(
+            format!("Invalid value in parameter `sampleSize`: the number of sampled documents must be at most {MAX_RECALL_SAMPLE_SIZE}"),
+            Code::InvalidRecallSampleSize,
+        ));
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let embedding_configs = {
+        let rtxn = index.read_txn()?;
+        index.embedding_configs(&rtxn)?
+    };
+    let embedders = index_scheduler.embedders(index_uid.to_string(), embedding_configs)?;
+    let mut embedders: BTreeMap<_, _> = embedders.into_inner().into_iter().collect();
+    if let Some(name) = embedder {
+        let Some(embedder) = embedders.remove(&name) else {
+            return Err(ResponseError::from_msg(
+                format!("Cannot find embedder with name `{name}`."),
+                Code::InvalidRecallEmbedder,
+            ));
+        };
+        embedders = BTreeMap::from([(name, embedder)]);
+    }
+
+    let result = tokio::task::spawn_blocking(move || -> Result<_, ResponseError> {
+        let rtxn = index.read_txn()?;
+        let mut recalls = BTreeMap::new();
+        for (name, (embedder, _, quantization)) in embedders {
+            let recall =
+                measure_recall(&index, &rtxn, &name, &embedder, quantization, sample_size, k)?;
+            recalls.insert(name, RecallView::from(recall));
+        }
+        Ok(RecallResult { embedders: recalls })
+    })
+    .await??;
+
+    debug!(returns = ?result, "Get recall");
+    Ok(HttpResponse::Ok().json(result))
+}
@@ -23,9 +23,10 @@ use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::routes::indexes::search_analytics::{SearchAggregator, SearchGET, SearchPOST};
 use crate::search::{
     add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SearchResult, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    RerankQuery, RetrieveVectors, SearchKind, SearchQuery, SearchReranker, SearchResult,
+    SemanticRatio, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_RERANK_TOP_N, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    DEFAULT_SEMANTIC_RATIO,
 };
 use crate::search_queue::SearchQueue;
 
@@ -93,6 +94,8 @@ pub struct SearchQueryGet {
     filter: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSort>)]
     sort: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchBoost>)]
+    boost: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchDistinct>)]
     distinct: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
@@ -107,6 +110,9 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     #[param(value_type = Vec<String>, explode = false)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDisjunctiveFacets>)]
+    #[param(value_type = bool)]
+    disjunctive_facets: Param<bool>,
     #[deserr(default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     #[param(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     highlight_pre_tag: String,
@@ -126,6 +132,14 @@ pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSemanticRatio>)]
     #[param(value_type = f32)]
     pub hybrid_semantic_ratio: Option<SemanticRatioGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchHybridQuery>)]
+    #[param(value_type = Option<bool>)]
+    pub hybrid_exact: Option<Param<bool>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRerank>)]
+    pub rerank_reranker: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRerank>)]
+    #[param(value_type = Option<usize>)]
+    pub rerank_top_n: Option<Param<usize>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
     #[param(value_type = f32)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
@@ -180,6 +194,14 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             None => None,
         };
 
+        if other.hybrid_embedder.is_none() && other.hybrid_exact.is_some() {
+            return Err(ResponseError::from_msg(
+                "`hybridEmbedder` is mandatory when `hybridExact` is present".into(),
+                meilisearch_types::error::Code::InvalidSearchHybridQuery,
+            ));
+        }
+        let exact = other.hybrid_exact.map(|exact| exact.0).unwrap_or_default();
+
         let hybrid = match (other.hybrid_embedder, other.hybrid_semantic_ratio) {
             (None, None) => None,
             (None, Some(_)) => {
@@ -189,13 +211,27 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
                 ));
             }
             (Some(embedder), None) => {
-                Some(HybridQuery { semantic_ratio: DEFAULT_SEMANTIC_RATIO(), embedder })
+                Some(HybridQuery { semantic_ratio: DEFAULT_SEMANTIC_RATIO(), embedder, exact })
             }
             (Some(embedder), Some(semantic_ratio)) => {
-                Some(HybridQuery { semantic_ratio: *semantic_ratio, embedder })
+                Some(HybridQuery { semantic_ratio: *semantic_ratio, embedder, exact })
             }
         };
 
+        let rerank = match (other.rerank_reranker, other.rerank_top_n) {
+            (None, None) => None,
+            (None, Some(_)) => {
+                return Err(ResponseError::from_msg(
+                    "`rerankReranker` is mandatory when `rerankTopN` is present".into(),
+                    meilisearch_types::error::Code::InvalidSearchRerank,
+                ));
+            }
+            (Some(reranker), top_n) => Some(RerankQuery {
+                reranker,
+                top_n: top_n.map_or_else(DEFAULT_RERANK_TOP_N, |top_n| top_n.0),
+            }),
+        };
+
         if other.vector.is_some() && hybrid.is_none() {
             return Err(ResponseError::from_msg(
                 "`hybridEmbedder` is mandatory when `vector` is present".into(),
@@ -217,17 +253,22 @@ impl TryFrom<SearchQueryGet> for SearchQuery {
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
+            boost: other.boost,
             distinct: other.distinct,
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            disjunctive_facets: other.disjunctive_facets.0,
+            facet_buckets: None,
+            hierarchical_facets: None,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
             matching_strategy: other.matching_strategy,
             attributes_to_search_on: other.attributes_to_search_on.map(|o| o.into_iter().collect()),
             hybrid,
+            rerank,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
             locales: other.locales.map(|o| o.into_iter().collect()),
         })
@@ -339,6 +380,8 @@ pub async fn search_with_url_query(
 
     let search_kind =
         search_kind(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
+    let reranker =
+        search_reranker(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors);
     let permit = search_queue.try_get_search_permit().await?;
     let search_result = tokio::task::spawn_blocking(move || {
@@ -347,6 +390,7 @@ pub async fn search_with_url_query(
             &index,
             query,
             search_kind,
+            reranker,
             retrieve_vector,
             index_scheduler.features(),
         )
@@ -445,6 +489,8 @@ pub async fn search_with_post(
 
     let search_kind =
         search_kind(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
+    let reranker =
+        search_reranker(&query, index_scheduler.get_ref(), index_uid.to_string(), &index)?;
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors);
 
     let permit = search_queue.try_get_search_permit().await?;
@@ -454,6 +500,7 @@ pub async fn search_with_post(
             &index,
             query,
             search_kind,
+            reranker,
             retrieve_vectors,
             index_scheduler.features(),
         )
@@ -481,28 +528,40 @@ pub fn search_kind(
     index_uid: String,
     index: &milli::Index,
 ) -> Result<SearchKind, ResponseError> {
+    let keyword_search = index.keyword_search(&index.read_txn()?)?;
+
     // handle with care, the order of cases matters, the semantics is subtle
     match (query.q.as_deref(), &query.hybrid, query.vector.as_deref()) {
         // empty query, no vector => placeholder search
         (Some(q), _, None) if q.trim().is_empty() => Ok(SearchKind::KeywordOnly),
         // no query, no vector => placeholder search
         (None, _, None) => Ok(SearchKind::KeywordOnly),
+        // no keyword search, hybrid => vector
+        (_, Some(HybridQuery { embedder, .. }), v) if !keyword_search => {
+            SearchKind::semantic(index_scheduler, index_uid, index, embedder, v.map(|v| v.len()))
+        }
+        // no keyword search, query, no hybrid => error
+        (Some(_), None, None) if !keyword_search => Err(ResponseError::from_msg(
+            "The keyword search is disabled for this index, `hybrid` must be specified to search with `q`"
+                .to_string(),
+            meilisearch_types::error::Code::KeywordSearchDisabled,
+        )),
         // hybrid.semantic_ratio == 1.0 => vector
-        (_, Some(HybridQuery { semantic_ratio, embedder }), v) if **semantic_ratio == 1.0 => {
+        (_, Some(HybridQuery { semantic_ratio, embedder, .. }), v) if **semantic_ratio == 1.0 => {
             SearchKind::semantic(index_scheduler, index_uid, index, embedder, v.map(|v| v.len()))
         }
         // hybrid.semantic_ratio == 0.0 => keyword
-        (_, Some(HybridQuery { semantic_ratio, embedder: _ }), _) if **semantic_ratio == 0.0 => {
+        (_, Some(HybridQuery { semantic_ratio, .. }), _) if **semantic_ratio == 0.0 => {
             Ok(SearchKind::KeywordOnly)
         }
         // no query, hybrid, vector => semantic
-        (None, Some(HybridQuery { semantic_ratio: _, embedder }), Some(v)) => {
+        (None, Some(HybridQuery { embedder, .. }), Some(v)) => {
             SearchKind::semantic(index_scheduler, index_uid, index, embedder, Some(v.len()))
         }
         // query, no hybrid, no vector => keyword
         (Some(_), None, None) => Ok(SearchKind::KeywordOnly),
         // query, hybrid, maybe vector => hybrid
-        (Some(_), Some(HybridQuery { semantic_ratio, embedder }), v) => SearchKind::hybrid(
+        (Some(_), Some(HybridQuery { semantic_ratio, embedder, .. }), v) => SearchKind::hybrid(
             index_scheduler,
             index_uid,
             index,
@@ -514,3 +573,16 @@ pub fn search_kind(
         (_, None, Some(_)) => Err(MeilisearchHttpError::MissingSearchHybrid.into()),
     }
 }
+
+pub fn search_reranker(
+    query: &SearchQuery,
+    index_scheduler: &IndexScheduler,
+    index_uid: String,
+    index: &milli::Index,
+) -> Result<Option<SearchReranker>, ResponseError> {
+    query
+        .rerank
+        .as_ref()
+        .map(|rerank| SearchReranker::new(index_scheduler, index_uid, index, rerank))
+        .transpose()
+}
@@ -40,6 +40,8 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
+    filter_with_geo_polygon: bool,
+    filter_with_geo_intersects: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     filter_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -60,6 +62,8 @@ pub struct SearchAggregator<Method: AggregateMethod> {
     // Whether the semantic ratio passed to a hybrid search equals the default ratio.
     semantic_ratio: bool,
     hybrid: bool,
+    // Whether an exact vector search was requested.
+    exact_vector_search: bool,
     retrieve_vectors: bool,
 
     // every time a search is done, we increment the counter linked to the used settings
@@ -115,14 +119,19 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             show_ranking_score_details,
             filter,
             sort,
+            boost: _,
             distinct,
             facets: _,
+            disjunctive_facets: _,
+            facet_buckets: _,
+            hierarchical_facets: _,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
             hybrid,
+            rerank: _,
             ranking_score_threshold,
             locales,
         } = query;
@@ -160,6 +169,8 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             let stringified_filters = filter.to_string();
             ret.filter_with_geo_radius = stringified_filters.contains("_geoRadius(");
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
+            ret.filter_with_geo_polygon = stringified_filters.contains("_geoPolygon(");
+            ret.filter_with_geo_intersects = stringified_filters.contains("_geoIntersects(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
@@ -207,6 +218,7 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
         if let Some(hybrid) = hybrid {
             ret.semantic_ratio = hybrid.semantic_ratio != DEFAULT_SEMANTIC_RATIO();
             ret.hybrid = true;
+            ret.exact_vector_search = hybrid.exact;
         }
 
         ret
@@ -221,6 +233,8 @@ impl<Method: AggregateMethod> SearchAggregator<Method> {
             semantic_hit_count: _,
             facet_distribution: _,
             facet_stats: _,
+            facet_buckets: _,
+            hierarchical_facet_distribution: _,
             degraded,
             used_negative_operator,
         } = result;
@@ -252,6 +266,8 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             distinct,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
+            filter_with_geo_intersects,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -277,6 +293,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             show_ranking_score_details,
             semantic_ratio,
             hybrid,
+            exact_vector_search,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
@@ -305,6 +322,8 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
+        self.filter_with_geo_polygon |= filter_with_geo_polygon;
+        self.filter_with_geo_intersects |= filter_with_geo_intersects;
         self.filter_sum_of_criteria_terms =
             self.filter_sum_of_criteria_terms.saturating_add(filter_sum_of_criteria_terms);
         self.filter_total_number_of_criteria =
@@ -327,6 +346,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
         self.retrieve_vectors |= retrieve_vectors;
         self.semantic_ratio |= semantic_ratio;
         self.hybrid |= hybrid;
+        self.exact_vector_search |= exact_vector_search;
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
@@ -378,6 +398,8 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             distinct,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
+            filter_with_geo_intersects,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -403,6 +425,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             show_ranking_score_details,
             semantic_ratio,
             hybrid,
+            exact_vector_search,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
@@ -434,6 +457,8 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             "filter": {
                "with_geoRadius": filter_with_geo_radius,
                "with_geoBoundingBox": filter_with_geo_bounding_box,
+               "with_geoPolygon": filter_with_geo_polygon,
+               "with_geoIntersects": filter_with_geo_intersects,
                "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
             },
@@ -450,6 +475,7 @@ impl<Method: AggregateMethod> Aggregate for SearchAggregator<Method> {
             "hybrid": {
                 "enabled": hybrid,
                 "semantic_ratio": semantic_ratio,
+                "exact": exact_vector_search,
             },
             "pagination": {
                "max_limit": max_limit,
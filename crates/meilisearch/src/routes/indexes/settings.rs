@@ -16,7 +16,7 @@ use super::settings_analytics::*;
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
-use crate::routes::{get_task_id, is_dry_run, SummarizedTaskView};
+use crate::routes::{get_task_id, get_task_options, is_dry_run, SummarizedTaskView};
 use crate::Opt;
 
 /// This macro generates the routes for the settings.
@@ -82,7 +82,7 @@ macro_rules! make_setting_route {
             use $crate::extractors::authentication::GuardedData;
             use $crate::extractors::sequential_extractor::SeqHandler;
             use $crate::Opt;
-            use $crate::routes::{is_dry_run, get_task_id, SummarizedTaskView};
+            use $crate::routes::{is_dry_run, get_task_id, get_task_options, SummarizedTaskView};
             #[allow(unused_imports)]
             use super::*;
 
@@ -140,10 +140,12 @@ macro_rules! make_setting_route {
                 };
                 let uid = get_task_id(&req, &opt)?;
                 let dry_run = is_dry_run(&req, &opt)?;
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                        .await??
-                        .into();
+                let options = get_task_options(&req)?;
+                let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+                    index_scheduler.register_with_options(task, uid, dry_run, options)
+                })
+                .await??
+                .into();
 
                 debug!(returns = ?task, "Delete settings");
                 Ok(HttpResponse::Accepted().json(task))
@@ -226,10 +228,12 @@ macro_rules! make_setting_route {
                 };
                 let uid = get_task_id(&req, &opt)?;
                 let dry_run = is_dry_run(&req, &opt)?;
-                let task: SummarizedTaskView =
-                    tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-                        .await??
-                        .into();
+                let options = get_task_options(&req)?;
+                let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+                    index_scheduler.register_with_options(task, uid, dry_run, options)
+                })
+                .await??
+                .into();
 
                 debug!(returns = ?task, "Update settings");
                 Ok(HttpResponse::Accepted().json(task))
@@ -497,6 +501,17 @@ make_setting_routes!(
         camelcase_attr: "facetSearch",
         analytics: FacetSearchAnalytics
     },
+    {
+        route: "/keyword-search",
+        update_verb: put,
+        value_type: bool,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsKeywordSearch,
+        >,
+        attr: keyword_search,
+        camelcase_attr: "keywordSearch",
+        analytics: KeywordSearchAnalytics
+    },
     {
         route: "/prefix-search",
         update_verb: put,
@@ -508,6 +523,28 @@ make_setting_routes!(
         camelcase_attr: "prefixSearch",
         analytics: PrefixSearchAnalytics
     },
+    {
+        route: "/rules",
+        update_verb: put,
+        value_type: Vec<meilisearch_types::milli::QueryRule>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsRules,
+        >,
+        attr: rules,
+        camelcase_attr: "rules",
+        analytics: RulesAnalytics
+    },
+    {
+        route: "/rerankers",
+        update_verb: put,
+        value_type: std::collections::BTreeMap<String, meilisearch_types::milli::vector::rerank::RerankerSettings>,
+        err_type: meilisearch_types::deserr::DeserrJsonError<
+            meilisearch_types::error::deserr_codes::InvalidSettingsRerankers,
+        >,
+        attr: rerankers,
+        camelcase_attr: "rerankers",
+        analytics: RerankersAnalytics
+    },
 );
 
 #[utoipa::path(
@@ -596,7 +633,10 @@ pub async fn update_all(
                 new_settings.non_separator_tokens.as_ref().set(),
             ),
             facet_search: FacetSearchAnalytics::new(new_settings.facet_search.as_ref().set()),
+            keyword_search: KeywordSearchAnalytics::new(new_settings.keyword_search.as_ref().set()),
             prefix_search: PrefixSearchAnalytics::new(new_settings.prefix_search.as_ref().set()),
+            rules: RulesAnalytics::new(new_settings.rules.as_ref().set()),
+            rerankers: RerankersAnalytics::new(new_settings.rerankers.as_ref().set()),
         },
         &req,
     );
@@ -611,10 +651,12 @@ pub async fn update_all(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let options = get_task_options(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task, uid, dry_run, options)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Update all settings");
     Ok(HttpResponse::Accepted().json(task))
@@ -705,16 +747,18 @@ pub async fn delete_all(
     };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let options = get_task_options(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task, uid, dry_run, options)
+    })
+    .await??
+    .into();
 
     debug!(returns = ?task, "Delete all settings");
     Ok(HttpResponse::Accepted().json(task))
 }
 
-fn validate_settings(
+pub(crate) fn validate_settings(
     settings: Settings<Unchecked>,
     index_scheduler: &IndexScheduler,
 ) -> Result<Settings<Unchecked>, ResponseError> {
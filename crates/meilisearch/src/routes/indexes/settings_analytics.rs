@@ -8,7 +8,8 @@ use std::collections::{BTreeMap, BTreeSet, HashSet};
 use meilisearch_types::facet_values_sort::FacetValuesSort;
 use meilisearch_types::locales::{Locale, LocalizedAttributesRuleView};
 use meilisearch_types::milli::update::Setting;
-use meilisearch_types::milli::FilterableAttributesRule;
+use meilisearch_types::milli::vector::rerank::RerankerSettings;
+use meilisearch_types::milli::{FilterableAttributesRule, QueryRule};
 use meilisearch_types::settings::{
     FacetingSettings, PaginationSettings, PrefixSearchSettings, ProximityPrecisionView,
     RankingRuleView, SettingEmbeddingSettings, TypoSettings,
@@ -38,7 +39,10 @@ pub struct SettingsAnalytics {
     pub separator_tokens: SeparatorTokensAnalytics,
     pub non_separator_tokens: NonSeparatorTokensAnalytics,
     pub facet_search: FacetSearchAnalytics,
+    pub keyword_search: KeywordSearchAnalytics,
     pub prefix_search: PrefixSearchAnalytics,
+    pub rules: RulesAnalytics,
+    pub rerankers: RerankersAnalytics,
 }
 
 impl Aggregate for SettingsAnalytics {
@@ -173,6 +177,19 @@ impl Aggregate for SettingsAnalytics {
                     (Some(bq), None) | (None, Some(bq)) => Some(bq),
                     (Some(this), Some(other)) => Some(this | other),
                 },
+                chunking_used: match (self.embedders.chunking_used, new.embedders.chunking_used) {
+                    (None, None) => None,
+                    (Some(used), None) | (None, Some(used)) => Some(used),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
+                quantization_used: match (
+                    self.embedders.quantization_used,
+                    new.embedders.quantization_used,
+                ) {
+                    (None, None) => None,
+                    (Some(used), None) | (None, Some(used)) => Some(used),
+                    (Some(this), Some(other)) => Some(this | other),
+                },
             },
             search_cutoff_ms: SearchCutoffMsAnalytics {
                 search_cutoff_ms: new
@@ -194,10 +211,28 @@ impl Aggregate for SettingsAnalytics {
                 set: new.facet_search.set | self.facet_search.set,
                 value: new.facet_search.value.or(self.facet_search.value),
             },
+            keyword_search: KeywordSearchAnalytics {
+                set: new.keyword_search.set | self.keyword_search.set,
+                value: new.keyword_search.value.or(self.keyword_search.value),
+            },
             prefix_search: PrefixSearchAnalytics {
                 set: new.prefix_search.set | self.prefix_search.set,
                 value: new.prefix_search.value.or(self.prefix_search.value),
             },
+            rules: RulesAnalytics {
+                total: new.rules.total.or(self.rules.total),
+                total_pinned: new.rules.total_pinned.or(self.rules.total_pinned),
+                total_hidden: new.rules.total_hidden.or(self.rules.total_hidden),
+                with_filter: new.rules.with_filter.or(self.rules.with_filter),
+            },
+            rerankers: RerankersAnalytics {
+                total: new.rerankers.total.or(self.rerankers.total),
+                sources: match (self.rerankers.sources, new.rerankers.sources) {
+                    (None, None) => None,
+                    (Some(sources), None) | (None, Some(sources)) => Some(sources),
+                    (Some(this), Some(other)) => Some(this.union(&other).cloned().collect()),
+                },
+            },
         })
     }
 
@@ -504,6 +539,10 @@ pub struct EmbeddersAnalytics {
     pub document_template_max_bytes: Option<usize>,
     // |=
     pub binary_quantization_used: Option<bool>,
+    // |=
+    pub chunking_used: Option<bool>,
+    // |=
+    pub quantization_used: Option<bool>,
 }
 
 impl EmbeddersAnalytics {
@@ -524,6 +563,7 @@ impl EmbeddersAnalytics {
                     EmbedderSource::Ollama => sources.insert("ollama".to_string()),
                     EmbedderSource::Rest => sources.insert("rest".to_string()),
                     EmbedderSource::Composite => sources.insert("composite".to_string()),
+                    EmbedderSource::Onnx => sources.insert("onnx".to_string()),
                 };
             }
         };
@@ -547,6 +587,16 @@ impl EmbeddersAnalytics {
                     .filter_map(|config| config.inner.clone().set())
                     .any(|config| config.binary_quantized.set().is_some())
             }),
+            chunking_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| config.chunking.set().is_some())
+            }),
+            quantization_used: setting.as_ref().map(|map| {
+                map.values()
+                    .filter_map(|config| config.inner.clone().set())
+                    .any(|config| config.quantization.set().is_some())
+            }),
         }
     }
 
@@ -659,6 +709,22 @@ impl FacetSearchAnalytics {
     }
 }
 
+#[derive(Serialize, Default)]
+pub struct KeywordSearchAnalytics {
+    pub set: bool,
+    pub value: Option<bool>,
+}
+
+impl KeywordSearchAnalytics {
+    pub fn new(settings: Option<&bool>) -> Self {
+        Self { set: settings.is_some(), value: settings.copied() }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { keyword_search: self, ..Default::default() }
+    }
+}
+
 #[derive(Serialize, Default)]
 pub struct PrefixSearchAnalytics {
     pub set: bool,
@@ -674,3 +740,48 @@ impl PrefixSearchAnalytics {
         SettingsAnalytics { prefix_search: self, ..Default::default() }
     }
 }
+
+#[derive(Serialize, Default)]
+pub struct RulesAnalytics {
+    pub total: Option<usize>,
+    pub total_pinned: Option<usize>,
+    pub total_hidden: Option<usize>,
+    pub with_filter: Option<usize>,
+}
+
+impl RulesAnalytics {
+    pub fn new(rules: Option<&Vec<QueryRule>>) -> Self {
+        Self {
+            total: rules.map(|rules| rules.len()),
+            total_pinned: rules.map(|rules| rules.iter().map(|rule| rule.pinned.len()).sum()),
+            total_hidden: rules.map(|rules| rules.iter().map(|rule| rule.hidden.len()).sum()),
+            with_filter: rules
+                .map(|rules| rules.iter().filter(|rule| rule.filter.is_some()).count()),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { rules: self, ..Default::default() }
+    }
+}
+
+#[derive(Serialize, Default)]
+pub struct RerankersAnalytics {
+    pub total: Option<usize>,
+    pub sources: Option<HashSet<String>>,
+}
+
+impl RerankersAnalytics {
+    pub fn new(rerankers: Option<&BTreeMap<String, RerankerSettings>>) -> Self {
+        Self {
+            total: rerankers.map(|rerankers| rerankers.len()),
+            sources: rerankers.map(|rerankers| {
+                rerankers.values().map(|reranker| reranker.source.to_string()).collect()
+            }),
+        }
+    }
+
+    pub fn into_settings(self) -> SettingsAnalytics {
+        SettingsAnalytics { rerankers: self, ..Default::default() }
+    }
+}
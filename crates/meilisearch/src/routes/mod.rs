@@ -2,24 +2,27 @@ use std::collections::BTreeMap;
 
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
-use index_scheduler::IndexScheduler;
+use index_scheduler::{IndexScheduler, TaskOptions};
 use meilisearch_auth::AuthController;
 use meilisearch_types::batch_view::BatchView;
 use meilisearch_types::batches::BatchStats;
 use meilisearch_types::error::{Code, ErrorType, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::keys::CreateApiKey;
+use meilisearch_types::milli::vector::rerank::{RerankerSettings, RerankerSource};
 use meilisearch_types::milli::{
     AttributePatterns, FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
-    FilterableAttributesRule,
+    FilterableAttributesRule, QueryRule, QueryRuleAnchoring,
 };
 use meilisearch_types::settings::{
     Checked, FacetingSettings, MinWordSizeTyposSetting, PaginationSettings, Settings, TypoSettings,
     Unchecked,
 };
 use meilisearch_types::task_view::{DetailsView, TaskView};
-use meilisearch_types::tasks::{Kind, Status, Task, TaskId};
+use meilisearch_types::tasks::{Kind, Priority, Status, Task, TaskId};
+use meilisearch_types::webhooks::{DeliveryStatus, WebhookDelivery};
 use serde::{Deserialize, Serialize};
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 use tracing::debug;
 use utoipa::{OpenApi, ToSchema};
@@ -40,6 +43,11 @@ use crate::routes::indexes::IndexView;
 use crate::routes::multi_search::SearchResults;
 use crate::routes::network::{Network, Remote};
 use crate::routes::swap_indexes::SwapIndexesPayload;
+use crate::routes::task_groups::{TaskGroupOperation, TaskGroupStatusView, TaskGroupView};
+use crate::routes::webhooks::{
+    AllWebhookDeliveries, WebhookFilterSettings, WebhookFilterView, WebhookList, WebhookSettings,
+    WebhookView,
+};
 use crate::search::{
     FederatedSearch, FederatedSearchResult, Federation, FederationOptions, MergeFacets,
     SearchQueryWithIndex, SearchResultWithIndex, SimilarQuery, SimilarResult,
@@ -63,9 +71,11 @@ pub mod network;
 mod open_api_utils;
 mod snapshot;
 mod swap_indexes;
+mod task_groups;
 pub mod tasks;
 #[cfg(test)]
 mod tasks_test;
+mod webhooks;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -82,8 +92,10 @@ mod tasks_test;
         (path = "/logs", api = logs::LogsApi),
         (path = "/multi-search", api = multi_search::MultiSearchApi),
         (path = "/swap-indexes", api = swap_indexes::SwapIndexesApi),
+        (path = "/task-groups", api = task_groups::TaskGroupsApi),
         (path = "/experimental-features", api = features::ExperimentalFeaturesApi),
         (path = "/network", api = network::NetworkApi),
+        (path = "/webhooks", api = webhooks::WebhooksApi),
     ),
     paths(get_health, get_version, get_stats),
     tags(
@@ -94,7 +106,7 @@ mod tasks_test;
         url = "/",
         description = "Local server",
     )),
-    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, DocumentEditionByFunction, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, KeyView, Action, CreateApiKey, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures))
+    components(schemas(PaginationView<KeyView>, PaginationView<IndexView>, IndexView, DocumentDeletionByFilter, AllBatches, BatchStats, ProgressStepView, ProgressView, BatchView, RuntimeTogglableFeatures, SwapIndexesPayload, TaskGroupOperation, TaskGroupView, TaskGroupStatusView, DocumentEditionByFunction, MergeFacets, FederationOptions, SearchQueryWithIndex, Federation, FederatedSearch, FederatedSearchResult, SearchResults, SearchResultWithIndex, SimilarQuery, SimilarResult, PaginationView<serde_json::Value>, BrowseQuery, UpdateIndexRequest, IndexUid, IndexCreateRequest, KeyView, Action, CreateApiKey, UpdateStderrLogs, LogMode, GetLogs, IndexStats, Stats, HealthStatus, HealthResponse, VersionResponse, Code, ErrorType, AllTasks, TaskView, Status, Priority, DetailsView, ResponseError, Settings<Unchecked>, Settings<Checked>, TypoSettings, MinWordSizeTyposSetting, FacetingSettings, PaginationSettings, SummarizedTaskView, Kind, Network, Remote, FilterableAttributesRule, FilterableAttributesPatterns, AttributePatterns, FilterableAttributesFeatures, FilterFeatures, QueryRule, QueryRuleAnchoring, RerankerSettings, RerankerSource, WebhookSettings, WebhookFilterSettings, WebhookView, WebhookFilterView, WebhookList, AllWebhookDeliveries, WebhookDelivery, DeliveryStatus))
 )]
 pub struct MeilisearchApi;
 
@@ -111,9 +123,11 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
         .service(web::scope("/indexes").configure(indexes::configure))
         .service(web::scope("/multi-search").configure(multi_search::configure))
         .service(web::scope("/swap-indexes").configure(swap_indexes::configure))
+        .service(web::scope("/task-groups").configure(task_groups::configure))
         .service(web::scope("/metrics").configure(metrics::configure))
         .service(web::scope("/experimental-features").configure(features::configure))
-        .service(web::scope("/network").configure(network::configure));
+        .service(web::scope("/network").configure(network::configure))
+        .service(web::scope("/webhooks").configure(webhooks::configure));
 
     #[cfg(feature = "swagger")]
     {
@@ -154,6 +168,42 @@ pub fn get_task_id(req: &HttpRequest, opt: &Opt) -> Result<Option<TaskId>, Respo
     Ok(task_id)
 }
 
+/// Reads the options of the task to enqueue from the `TaskPriority` and `TaskRunAt` headers.
+pub fn get_task_options(req: &HttpRequest) -> Result<TaskOptions, ResponseError> {
+    let priority = get_header(req, "TaskPriority")?
+        .map(|s| {
+            s.parse::<Priority>()
+                .map_err(|e| ResponseError::from_msg(e.to_string(), Code::BadRequest))
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let run_at = get_header(req, "TaskRunAt")?
+        .map(|s| {
+            OffsetDateTime::parse(s, &Rfc3339).map_err(|e| {
+                ResponseError::from_msg(
+                    format!("TaskRunAt `{s}` is not a valid RFC 3339 date: {e}"),
+                    Code::BadRequest,
+                )
+            })
+        })
+        .transpose()?;
+    Ok(TaskOptions { priority, run_at })
+}
+
+fn get_header<'a>(req: &'a HttpRequest, name: &str) -> Result<Option<&'a str>, ResponseError> {
+    req.headers()
+        .get(name)
+        .map(|header| {
+            header.to_str().map_err(|e| {
+                ResponseError::from_msg(
+                    format!("{name} is not a valid utf-8 string: {e}"),
+                    Code::BadRequest,
+                )
+            })
+        })
+        .transpose()
+}
+
 pub fn is_dry_run(req: &HttpRequest, opt: &Opt) -> Result<bool, ResponseError> {
     if !opt.experimental_replication_parameters {
         return Ok(false);
@@ -186,9 +236,18 @@ pub struct SummarizedTaskView {
     /// The type of the task.
     #[serde(rename = "type")]
     kind: Kind,
+    /// The priority of the task, omitted when it is `normal`.
+    #[serde(skip_serializing_if = "Priority::is_normal")]
+    priority: Priority,
     /// The date on which the task was enqueued.
     #[serde(serialize_with = "time::serde::rfc3339::serialize")]
     enqueued_at: OffsetDateTime,
+    /// The date before which the task is not processed, omitted when the task can be processed right away.
+    #[serde(
+        serialize_with = "time::serde::rfc3339::option::serialize",
+        skip_serializing_if = "Option::is_none"
+    )]
+    run_at: Option<OffsetDateTime>,
 }
 
 impl From<Task> for SummarizedTaskView {
@@ -198,7 +257,9 @@ impl From<Task> for SummarizedTaskView {
             index_uid: task.index_uid().map(|s| s.to_string()),
             status: task.status,
             kind: task.kind.as_kind(),
+            priority: task.priority,
             enqueued_at: task.enqueued_at,
+            run_at: task.run_at,
         }
     }
 }
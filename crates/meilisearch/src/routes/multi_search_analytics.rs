@@ -56,14 +56,19 @@ impl MultiSearchAggregator {
             show_matches_position: _,
             filter: _,
             sort: _,
+            boost: _,
             distinct: _,
             facets: _,
+            disjunctive_facets: _,
+            facet_buckets: _,
+            hierarchical_facets: _,
             highlight_pre_tag: _,
             highlight_post_tag: _,
             crop_marker: _,
             matching_strategy: _,
             attributes_to_search_on: _,
             hybrid: _,
+            rerank: _,
             ranking_score_threshold: _,
             locales: _,
         } in &federated_search.queries
@@ -11,7 +11,7 @@ use meilisearch_types::tasks::{IndexSwap, KindWithContent};
 use serde::Serialize;
 use utoipa::{OpenApi, ToSchema};
 
-use super::{get_task_id, is_dry_run, SummarizedTaskView};
+use super::{get_task_id, get_task_options, is_dry_run, SummarizedTaskView};
 use crate::analytics::{Aggregate, Analytics};
 use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::*;
@@ -116,9 +116,11 @@ pub async fn swap_indexes(
     let task = KindWithContent::IndexSwap { swaps };
     let uid = get_task_id(&req, &opt)?;
     let dry_run = is_dry_run(&req, &opt)?;
-    let task: SummarizedTaskView =
-        tokio::task::spawn_blocking(move || index_scheduler.register(task, uid, dry_run))
-            .await??
-            .into();
+    let options = get_task_options(&req)?;
+    let task: SummarizedTaskView = tokio::task::spawn_blocking(move || {
+        index_scheduler.register_with_options(task, uid, dry_run, options)
+    })
+    .await??
+    .into();
     Ok(HttpResponse::Accepted().json(task))
 }
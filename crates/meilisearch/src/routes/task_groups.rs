@@ -0,0 +1,421 @@
+use std::collections::BTreeMap;
+use std::io::{BufWriter, ErrorKind, Write};
+
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use deserr::Deserr;
+use index_scheduler::{IndexScheduler, Query};
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::{InvalidIndexUid, InvalidTaskGroupOperations};
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::settings::{Settings, Unchecked};
+use meilisearch_types::task_view::TaskView;
+use meilisearch_types::tasks::{KindWithContent, Status, TaskId};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::debug;
+use utoipa::{OpenApi, ToSchema};
+use uuid::Uuid;
+
+use super::indexes::settings::validate_settings;
+use super::{get_task_options, is_dry_run, SummarizedTaskView};
+use crate::analytics::{Aggregate, Analytics};
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::Opt;
+
+#[derive(OpenApi)]
+#[openapi(paths(create_task_group, get_task_group))]
+pub struct TaskGroupsApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(create_task_group))))
+        .service(web::resource("/{group_uid}").route(web::get().to(SeqHandler(get_task_group))));
+}
+
+#[derive(Deserr, Debug, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct TaskGroupOperation {
+    /// The index on which the operation is applied
+    #[schema(value_type = String, example = "movies")]
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    index_uid: IndexUid,
+    /// Documents to add or replace in the index
+    #[schema(value_type = Option<Vec<BTreeMap<String, Value>>>)]
+    #[deserr(default, error = DeserrJsonError<InvalidTaskGroupOperations>)]
+    add_documents: Option<Vec<Value>>,
+    /// Documents to add or update in the index
+    #[schema(value_type = Option<Vec<BTreeMap<String, Value>>>)]
+    #[deserr(default, error = DeserrJsonError<InvalidTaskGroupOperations>)]
+    update_documents: Option<Vec<Value>>,
+    /// Ids of the documents to delete from the index
+    #[deserr(default, error = DeserrJsonError<InvalidTaskGroupOperations>)]
+    delete_documents: Option<Vec<Value>>,
+    /// Settings to update on the index
+    #[deserr(default)]
+    update_settings: Option<Settings<Unchecked>>,
+    /// The primary key of the added or updated documents
+    #[deserr(default, error = DeserrJsonError<InvalidTaskGroupOperations>)]
+    primary_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskGroupView {
+    /// The uid of the group, which is the uid of its first task.
+    #[schema(value_type = u32)]
+    group_uid: TaskId,
+    /// The tasks of the group, in the order of the operations.
+    tasks: Vec<SummarizedTaskView>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskGroupStatusView {
+    /// The uid of the group, which is the uid of its first task.
+    #[schema(value_type = u32)]
+    group_uid: TaskId,
+    /// The status of the group as a whole: `enqueued` or `processing` until all its tasks are finished,
+    /// then `canceled` or `failed` if one of its tasks was, and `succeeded` otherwise.
+    status: Status,
+    /// Whether the changes of some operations were applied while the other operations failed.
+    ///
+    /// The indexes of a group are committed one after the other: when the commit of an index fails,
+    /// the indexes committed before it keep their changes and their tasks succeed, while the tasks of
+    /// the following indexes fail with the error of the commit.
+    partially_committed: bool,
+    /// The tasks of the group, in the order of the operations.
+    tasks: Vec<TaskView>,
+}
+
+#[derive(Serialize)]
+struct TaskGroupAnalytics {
+    group_operation_number: usize,
+}
+
+impl Aggregate for TaskGroupAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Task Group Created"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self {
+            group_operation_number: self.group_operation_number.max(new.group_operation_number),
+        })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+/// Create a task group
+///
+/// Enqueue several operations on different indexes as a single unit. The tasks of a group are processed in the same batch, and either all of them succeed, or all of them fail and none of their changes are applied.
+/// The indexes are committed one after the other though: if the commit of an index fails, the indexes committed before it keep their changes, and the group is reported as `partiallyCommitted` by `GET /task-groups/{groupUid}`.
+/// Each operation must operate on a different index and contain exactly one of `addDocuments`, `updateDocuments`, `deleteDocuments` or `updateSettings`.
+#[utoipa::path(
+    post,
+    path = "",
+    tag = "Tasks",
+    security(("Bearer" = ["documents.*", "settings.update", "*"])),
+    request_body = Vec<TaskGroupOperation>,
+    responses(
+        (status = ACCEPTED, description = "Task group successfully enqueued", body = TaskGroupView, content_type = "application/json", example = json!(
+            {
+                "groupUid": 12,
+                "tasks": [
+                    {
+                        "taskUid": 12,
+                        "indexUid": "products",
+                        "status": "enqueued",
+                        "type": "documentAdditionOrUpdate",
+                        "enqueuedAt": "2021-08-12T10:00:00.000000Z"
+                    },
+                    {
+                        "taskUid": 13,
+                        "indexUid": "categories",
+                        "status": "enqueued",
+                        "type": "settingsUpdate",
+                        "enqueuedAt": "2021-08-12T10:00:00.000000Z"
+                    }
+                ]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+pub async fn create_task_group(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_ALL }>, Data<IndexScheduler>>,
+    _settings_policy: GuardedData<ActionPolicy<{ actions::SETTINGS_UPDATE }>, Data<IndexScheduler>>,
+    params: AwebJson<Vec<TaskGroupOperation>, DeserrJsonError>,
+    req: HttpRequest,
+    opt: web::Data<Opt>,
+    analytics: web::Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let params = params.into_inner();
+    debug!(parameters = ?params, "Create task group");
+    analytics.publish(TaskGroupAnalytics { group_operation_number: params.len() }, &req);
+    let dry_run = is_dry_run(&req, &opt)?;
+    let options = get_task_options(&req)?;
+
+    // The update files of the documents must be deleted if the group cannot be registered.
+    let mut update_files = Vec::new();
+    let kinds =
+        match operations_to_kinds(&index_scheduler, params, dry_run, &mut update_files).await {
+            Ok(kinds) => kinds,
+            Err(e) => {
+                delete_update_files(&index_scheduler, update_files);
+                return Err(e);
+            }
+        };
+
+    let scheduler = index_scheduler.clone();
+    let tasks = match tokio::task::spawn_blocking(move || {
+        scheduler.register_group(kinds, dry_run, options)
+    })
+    .await?
+    {
+        Ok(tasks) => tasks,
+        Err(e) => {
+            delete_update_files(&index_scheduler, update_files);
+            return Err(e.into());
+        }
+    };
+
+    let group = TaskGroupView {
+        group_uid: tasks.first().and_then(|task| task.group_uid).unwrap_or_default(),
+        tasks: tasks.into_iter().map(SummarizedTaskView::from).collect(),
+    };
+    debug!(returns = ?group, "Create task group");
+    Ok(HttpResponse::Accepted().json(group))
+}
+
+/// Get a task group
+///
+/// Get the tasks of a task group and the status of the group as a whole.
+#[utoipa::path(
+    get,
+    path = "/{groupUid}",
+    tag = "Tasks",
+    security(("Bearer" = ["tasks.get", "tasks.*", "*"])),
+    params(("groupUid", format = UInt32, example = 12, description = "The task group identifier", nullable = false)),
+    responses(
+        (status = 200, description = "Task group successfully retrieved", body = TaskGroupStatusView, content_type = "application/json", example = json!(
+            {
+                "groupUid": 12,
+                "status": "succeeded",
+                "partiallyCommitted": false,
+                "tasks": [
+                    {
+                        "uid": 12,
+                        "batchUid": 7,
+                        "groupUid": 12,
+                        "groupStatus": "succeeded",
+                        "indexUid": "products",
+                        "status": "succeeded",
+                        "type": "documentAdditionOrUpdate",
+                        "canceledBy": null,
+                        "details": {
+                            "receivedDocuments": 10,
+                            "indexedDocuments": 10
+                        },
+                        "error": null,
+                        "duration": "PT1S",
+                        "enqueuedAt": "2021-08-12T10:00:00.000000Z",
+                        "startedAt": "2021-08-12T10:00:01.000000Z",
+                        "finishedAt": "2021-08-12T10:00:02.000000Z"
+                    }
+                ]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+        (status = 404, description = "The task group uid does not exists", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Task group :groupUid not found.",
+                "code": "task_group_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors/#task_group_not_found"
+            }
+        ))
+    )
+)]
+async fn get_task_group(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    group_uid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let group_uid_string = group_uid.into_inner();
+
+    let group_uid: TaskId = match group_uid_string.parse() {
+        Ok(id) => id,
+        Err(_e) => {
+            return Err(
+                index_scheduler::Error::InvalidTaskGroupUid { group_uid: group_uid_string }.into()
+            )
+        }
+    };
+
+    let query =
+        Query { group_uids: Some(vec![group_uid]), reverse: Some(true), ..Query::default() };
+    let filters = index_scheduler.filters();
+    let (tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(&query, filters)?;
+
+    if tasks.is_empty() {
+        return Err(index_scheduler::Error::TaskGroupNotFound(group_uid).into());
+    }
+    let status = index_scheduler
+        .get_task_groups_status([group_uid])?
+        .remove(&group_uid)
+        .ok_or(index_scheduler::Error::TaskGroupNotFound(group_uid))?;
+
+    let has_status = |status| tasks.iter().any(|task| task.status == status);
+    let partially_committed = has_status(Status::Succeeded) && has_status(Status::Failed);
+
+    let group = TaskGroupStatusView {
+        group_uid,
+        status,
+        partially_committed,
+        tasks: tasks
+            .iter()
+            .map(|task| TaskView { group_status: Some(status), ..TaskView::from_task(task) })
+            .collect(),
+    };
+    debug!(returns = ?group, "Get task group");
+    Ok(HttpResponse::Ok().json(group))
+}
+
+/// Convert the operations of the payload into the tasks of the group, writing the documents
+/// into update files. The uuids of the update files are pushed in `update_files`.
+async fn operations_to_kinds(
+    index_scheduler: &GuardedData<ActionPolicy<{ actions::DOCUMENTS_ALL }>, Data<IndexScheduler>>,
+    operations: Vec<TaskGroupOperation>,
+    dry_run: bool,
+    update_files: &mut Vec<Uuid>,
+) -> Result<Vec<KindWithContent>, ResponseError> {
+    let filters = index_scheduler.filters();
+    let mut kinds = Vec::with_capacity(operations.len());
+    for operation in operations {
+        let TaskGroupOperation {
+            index_uid,
+            add_documents,
+            update_documents,
+            delete_documents,
+            update_settings,
+            primary_key,
+        } = operation;
+        if !filters.is_index_authorized(&index_uid) {
+            return Err(AuthenticationError::InvalidToken.into());
+        }
+        let allow_index_creation = filters.allow_index_creation(&index_uid);
+
+        let kind = match (add_documents, update_documents, delete_documents, update_settings) {
+            (Some(documents), None, None, None) | (None, Some(documents), None, None)
+                if documents.iter().any(|document| !document.is_object()) =>
+            {
+                return Err(MeilisearchHttpError::TaskGroupDocumentsMustBeObjects(index_uid).into());
+            }
+            (Some(documents), None, None, None) => {
+                let documents_count = documents.len() as u64;
+                let content_file =
+                    write_update_file(index_scheduler, documents, dry_run, update_files).await?;
+                KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: index_uid.to_string(),
+                    primary_key,
+                    method: IndexDocumentsMethod::ReplaceDocuments,
+                    content_file,
+                    documents_count,
+                    allow_index_creation,
+                }
+            }
+            (None, Some(documents), None, None) => {
+                let documents_count = documents.len() as u64;
+                let content_file =
+                    write_update_file(index_scheduler, documents, dry_run, update_files).await?;
+                KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: index_uid.to_string(),
+                    primary_key,
+                    method: IndexDocumentsMethod::UpdateDocuments,
+                    content_file,
+                    documents_count,
+                    allow_index_creation,
+                }
+            }
+            (None, None, Some(ids), None) => KindWithContent::DocumentDeletion {
+                index_uid: index_uid.to_string(),
+                documents_ids: ids
+                    .iter()
+                    .map(|v| v.as_str().map(String::from).unwrap_or_else(|| v.to_string()))
+                    .collect(),
+            },
+            (None, None, None, Some(settings)) => KindWithContent::SettingsUpdate {
+                index_uid: index_uid.to_string(),
+                new_settings: Box::new(validate_settings(settings, index_scheduler)?),
+                is_deletion: false,
+                allow_index_creation,
+            },
+            _ => return Err(MeilisearchHttpError::TaskGroupOperationWrongLength(index_uid).into()),
+        };
+        kinds.push(kind);
+    }
+    Ok(kinds)
+}
+
+/// Write the documents in a new update file, in the format the scheduler expects.
+async fn write_update_file(
+    index_scheduler: &Data<IndexScheduler>,
+    documents: Vec<Value>,
+    dry_run: bool,
+    update_files: &mut Vec<Uuid>,
+) -> Result<Uuid, MeilisearchHttpError> {
+    let (uuid, mut update_file) = index_scheduler.queue.create_update_file(dry_run)?;
+    update_files.push(uuid);
+    tokio::task::spawn_blocking(move || -> Result<(), MeilisearchHttpError> {
+        let mut writer = BufWriter::new(&mut update_file);
+        for document in documents {
+            serde_json::to_writer(&mut writer, &document)?;
+        }
+        writer.flush().map_err(file_store::Error::IoError)?;
+        drop(writer);
+        update_file.persist()?;
+        Ok(())
+    })
+    .await??;
+    Ok(uuid)
+}
+
+fn delete_update_files(index_scheduler: &IndexScheduler, update_files: Vec<Uuid>) {
+    for uuid in update_files {
+        // The file may not have been persisted, we ignore the file not found error.
+        match index_scheduler.queue.delete_update_file(uuid) {
+            Ok(()) => (),
+            Err(index_scheduler::Error::FileStore(file_store::Error::IoError(e)))
+                if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => {
+                tracing::warn!(
+                    index_uuid = %uuid,
+                    "Unknown error happened while deleting the update file of a task group: {e}"
+                );
+            }
+        }
+    }
+}
@@ -75,6 +75,10 @@ pub struct TasksFilterQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidBatchUids>)]
     #[param(required = false, value_type = Option<u32>, example = 12421)]
     pub batch_uids: OptionStarOrList<BatchId>,
+    /// Permits to filter tasks by their task group uid, which is the uid of the first task of the group. It's possible to specify several group uids by separating them with the `,` character.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskGroupUids>)]
+    #[param(required = false, value_type = Option<Vec<u32>>, example = json!([12, "*"]))]
+    pub group_uids: OptionStarOrList<TaskId>,
 
     /// Permits to filter tasks by their uid. By default, when the uids query parameter is not set, all task uids are returned. It's possible to specify several uids by separating them with the `,` character.
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskUids>)]
@@ -121,6 +125,14 @@ pub struct TasksFilterQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeFinishedAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
     #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
     pub before_finished_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter tasks based on their runAt time. Matches tasks scheduled to run after the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterRunAt>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub after_run_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter tasks based on their runAt time. Matches tasks scheduled to run before the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeRunAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub before_run_at: OptionStarOr<OffsetDateTime>,
 }
 
 impl TasksFilterQuery {
@@ -130,6 +142,7 @@ impl TasksFilterQuery {
             from: self.from.as_deref().copied(),
             reverse: self.reverse.as_deref().copied(),
             batch_uids: self.batch_uids.merge_star_and_none(),
+            group_uids: self.group_uids.merge_star_and_none(),
             statuses: self.statuses.merge_star_and_none(),
             types: self.types.merge_star_and_none(),
             index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
@@ -141,6 +154,8 @@ impl TasksFilterQuery {
             after_started_at: self.after_started_at.merge_star_and_none(),
             before_finished_at: self.before_finished_at.merge_star_and_none(),
             after_finished_at: self.after_finished_at.merge_star_and_none(),
+            before_run_at: self.before_run_at.merge_star_and_none(),
+            after_run_at: self.after_run_at.merge_star_and_none(),
         }
     }
 }
@@ -161,7 +176,9 @@ impl TaskDeletionOrCancelationQuery {
                 after_started_at: OptionStarOr::None,
                 before_started_at: OptionStarOr::None,
                 after_finished_at: OptionStarOr::None,
-                before_finished_at: OptionStarOr::None
+                before_finished_at: OptionStarOr::None,
+                after_run_at: OptionStarOr::None,
+                before_run_at: OptionStarOr::None
             }
         )
     }
@@ -220,6 +237,14 @@ pub struct TaskDeletionOrCancelationQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeFinishedAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
     #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
     pub before_finished_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter tasks based on their runAt time. Matches tasks scheduled to run after the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterRunAt>, try_from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub after_run_at: OptionStarOr<OffsetDateTime>,
+    /// Permits to filter tasks based on their runAt time. Matches tasks scheduled to run before the given date. Supports RFC 3339 date format.
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeRunAt>, try_from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    #[param(required = false, value_type = Option<String>, example = json!(["2024-08-08T16:37:09.971Z", "*"]))]
+    pub before_run_at: OptionStarOr<OffsetDateTime>,
 }
 
 impl TaskDeletionOrCancelationQuery {
@@ -229,6 +254,7 @@ impl TaskDeletionOrCancelationQuery {
             from: None,
             reverse: None,
             batch_uids: self.batch_uids.merge_star_and_none(),
+            group_uids: None,
             statuses: self.statuses.merge_star_and_none(),
             types: self.types.merge_star_and_none(),
             index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
@@ -240,6 +266,8 @@ impl TaskDeletionOrCancelationQuery {
             after_started_at: self.after_started_at.merge_star_and_none(),
             before_finished_at: self.before_finished_at.merge_star_and_none(),
             after_finished_at: self.after_finished_at.merge_star_and_none(),
+            before_run_at: self.before_run_at.merge_star_and_none(),
+            after_run_at: self.after_run_at.merge_star_and_none(),
         }
     }
 }
@@ -262,6 +290,8 @@ struct TaskFilterAnalytics<Method: AggregateMethod> {
     filtered_by_after_started_at: bool,
     filtered_by_before_finished_at: bool,
     filtered_by_after_finished_at: bool,
+    filtered_by_before_run_at: bool,
+    filtered_by_after_run_at: bool,
 
     #[serde(skip)]
     marker: std::marker::PhantomData<Method>,
@@ -291,6 +321,9 @@ impl<Method: AggregateMethod + 'static> Aggregate for TaskFilterAnalytics<Method
                 | new.filtered_by_before_finished_at,
             filtered_by_after_finished_at: self.filtered_by_after_finished_at
                 | new.filtered_by_after_finished_at,
+            filtered_by_before_run_at: self.filtered_by_before_run_at
+                | new.filtered_by_before_run_at,
+            filtered_by_after_run_at: self.filtered_by_after_run_at | new.filtered_by_after_run_at,
 
             marker: std::marker::PhantomData,
         })
@@ -322,7 +355,7 @@ impl<Method: AggregateMethod + 'static> Aggregate for TaskFilterAnalytics<Method
         )),
         (status = 400, description = "A filter is missing", body = ResponseError, content_type = "application/json", example = json!(
             {
-                "message": "Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.",
+                "message": "Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`, `beforeRunAt`, `afterRunAt`.",
                 "code": "missing_task_filters",
                 "type": "invalid_request",
                 "link": "https://docs.meilisearch.com/errors#missing_task_filters"
@@ -372,6 +405,8 @@ async fn cancel_tasks(
             filtered_by_after_started_at: params.after_started_at.is_some(),
             filtered_by_before_finished_at: params.before_finished_at.is_some(),
             filtered_by_after_finished_at: params.after_finished_at.is_some(),
+            filtered_by_before_run_at: params.before_run_at.is_some(),
+            filtered_by_after_run_at: params.after_run_at.is_some(),
 
             marker: std::marker::PhantomData,
         },
@@ -416,7 +451,7 @@ async fn cancel_tasks(
         )),
         (status = 400, description = "A filter is missing", body = ResponseError, content_type = "application/json", example = json!(
             {
-                "message": "Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.",
+                "message": "Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`, `beforeRunAt`, `afterRunAt`.",
                 "code": "missing_task_filters",
                 "type": "invalid_request",
                 "link": "https://docs.meilisearch.com/errors#missing_task_filters"
@@ -466,6 +501,8 @@ async fn delete_tasks(
             filtered_by_after_started_at: params.after_started_at.is_some(),
             filtered_by_before_finished_at: params.before_finished_at.is_some(),
             filtered_by_after_finished_at: params.after_finished_at.is_some(),
+            filtered_by_before_run_at: params.before_run_at.is_some(),
+            filtered_by_after_run_at: params.after_run_at.is_some(),
 
             marker: std::marker::PhantomData,
         },
@@ -564,6 +601,7 @@ async fn get_tasks(
     let filters = index_scheduler.filters();
     let (tasks, total) = index_scheduler.get_tasks_from_authorized_indexes(&query, filters)?;
     let mut results: Vec<_> = tasks.iter().map(TaskView::from_task).collect();
+    fill_group_statuses(&index_scheduler, &mut results)?;
 
     // If we were able to fetch the number +1 tasks we asked
     // it means that there is more to come.
@@ -575,6 +613,19 @@ async fn get_tasks(
     Ok(HttpResponse::Ok().json(tasks))
 }
 
+/// Fills the status of the task group of the tasks that belong to one.
+pub(crate) fn fill_group_statuses(
+    index_scheduler: &IndexScheduler,
+    tasks: &mut [TaskView],
+) -> Result<(), ResponseError> {
+    let statuses =
+        index_scheduler.get_task_groups_status(tasks.iter().filter_map(|task| task.group_uid))?;
+    for task in tasks {
+        task.group_status = task.group_uid.and_then(|group_uid| statuses.get(&group_uid).copied());
+    }
+    Ok(())
+}
+
 /// Get a task
 ///
 /// Get a [task](https://www.meilisearch.com/docs/learn/async/asynchronous_operations)
@@ -639,7 +690,8 @@ async fn get_task(
     let (tasks, _) = index_scheduler.get_tasks_from_authorized_indexes(&query, filters)?;
 
     if let Some(task) = tasks.first() {
-        let task_view = TaskView::from_task(task);
+        let mut task_view = TaskView::from_task(task);
+        fill_group_statuses(&index_scheduler, std::slice::from_mut(&mut task_view))?;
         Ok(HttpResponse::Ok().json(task_view))
     } else {
         Err(index_scheduler::Error::TaskNotFound(task_uid).into())
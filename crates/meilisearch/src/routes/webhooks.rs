@@ -0,0 +1,571 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::Deserr;
+use index_scheduler::{IndexScheduler, CLI_WEBHOOK_UUID};
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
+use meilisearch_types::error::deserr_codes::{
+    InvalidWebhookDeliveriesFrom, InvalidWebhookDeliveriesLimit, InvalidWebhookFilter,
+    InvalidWebhookHeaders, InvalidWebhookSecret, InvalidWebhookUrl,
+};
+use meilisearch_types::error::{Code, ResponseError};
+use meilisearch_types::index_uid_pattern::IndexUidPattern;
+use meilisearch_types::keys::actions;
+use meilisearch_types::milli::update::Setting;
+use meilisearch_types::settings::hide_secret;
+use meilisearch_types::tasks::{Kind, Status};
+use meilisearch_types::webhooks::{DeliveryId, Webhook, WebhookDelivery, WebhookFilter};
+use serde::Serialize;
+use tracing::debug;
+use utoipa::{IntoParams, OpenApi, ToSchema};
+use uuid::Uuid;
+
+use super::PAGINATION_DEFAULT_LIMIT;
+use crate::analytics::{Aggregate, Analytics};
+use crate::extractors::authentication::policies::ActionPolicy;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(list_webhooks, create_webhook, get_webhook, patch_webhook, delete_webhook, get_webhook_deliveries),
+    tags((
+        name = "Webhooks",
+        description = "The `/webhooks` route allows you to register the endpoints notified with the tasks of every processed batch.
+
+Each endpoint can filter the tasks it receives by index, type and status. The tasks are sent as gzipped ndjson and the deliveries that fail are retried with an exponential backoff. When the webhook has a secret, the `X-Meilisearch-Signature` header contains the HMAC-SHA256 of the uncompressed payload, formatted as `sha256=<hex>`.",
+    )),
+)]
+pub struct WebhooksApi;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("")
+            .route(web::get().to(list_webhooks))
+            .route(web::post().to(SeqHandler(create_webhook))),
+    )
+    .service(
+        web::resource("/{uuid}")
+            .route(web::get().to(get_webhook))
+            .route(web::patch().to(SeqHandler(patch_webhook)))
+            .route(web::delete().to(SeqHandler(delete_webhook))),
+    )
+    .service(web::resource("/{uuid}/deliveries").route(web::get().to(get_webhook_deliveries)));
+}
+
+#[derive(Debug, Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError<InvalidWebhookFilter>, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct WebhookFilterSettings {
+    /// Only send the tasks of the matching indexes, the patterns can end with a `*`.
+    #[schema(value_type = Option<Vec<String>>, example = json!(["movies", "products_*"]))]
+    #[deserr(default)]
+    pub index_uids: Option<Vec<IndexUidPattern>>,
+    /// Only send the tasks of these types.
+    #[schema(value_type = Option<Vec<Kind>>, example = json!(["documentAdditionOrUpdate"]))]
+    #[deserr(default)]
+    pub types: Option<Vec<String>>,
+    /// Only send the tasks with these statuses.
+    #[schema(value_type = Option<Vec<Status>>, example = json!(["failed"]))]
+    #[deserr(default)]
+    pub statuses: Option<Vec<String>>,
+}
+
+#[derive(Deserr, ToSchema)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[schema(rename_all = "camelCase")]
+pub struct WebhookSettings {
+    /// The URL receiving the tasks.
+    #[schema(value_type = Option<String>, example = "https://example.com/meilisearch-tasks")]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookUrl>)]
+    pub url: Setting<String>,
+    /// The headers sent along with every delivery.
+    #[schema(value_type = Option<BTreeMap<String, String>>, example = json!({ "Authorization": "Bearer a-token" }))]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookHeaders>)]
+    pub headers: Setting<BTreeMap<String, String>>,
+    /// Restricts the tasks sent to the webhook, all the tasks are sent by default.
+    #[schema(value_type = Option<WebhookFilterSettings>)]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookFilter>)]
+    pub filter: Setting<WebhookFilterSettings>,
+    /// The key used to sign the payloads, it is never returned.
+    #[schema(value_type = Option<String>, example = "a-very-secret-key")]
+    #[deserr(default, error = DeserrJsonError<InvalidWebhookSecret>)]
+    pub secret: Setting<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct WebhookFilterView {
+    #[schema(value_type = Option<Vec<String>>)]
+    index_uids: Option<Vec<IndexUidPattern>>,
+    types: Option<Vec<Kind>>,
+    statuses: Option<Vec<Status>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct WebhookView {
+    #[schema(value_type = String, example = "0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a")]
+    uuid: Uuid,
+    /// `false` for the webhook given with `--task-webhook-url`, which cannot be modified through the API.
+    is_editable: bool,
+    url: String,
+    /// The headers sent along with every delivery, their values are hidden as they often contain credentials.
+    headers: BTreeMap<String, String>,
+    filter: WebhookFilterView,
+    /// Whether the payloads are signed, the secret itself is never returned.
+    signed: bool,
+}
+
+impl From<Webhook> for WebhookView {
+    fn from(webhook: Webhook) -> Self {
+        let Webhook { uuid, url, mut headers, filter, secret } = webhook;
+        let WebhookFilter { index_uids, types, statuses } = filter;
+        headers.values_mut().for_each(hide_secret);
+        WebhookView {
+            uuid,
+            is_editable: uuid != CLI_WEBHOOK_UUID,
+            url,
+            headers,
+            filter: WebhookFilterView { index_uids, types, statuses },
+            signed: secret.is_some(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookList {
+    results: Vec<WebhookView>,
+}
+
+#[derive(Debug, Deserr, IntoParams)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[into_params(rename_all = "camelCase", parameter_in = Query)]
+pub struct WebhookDeliveriesQuery {
+    /// Maximum number of results to return.
+    #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT as u32), error = DeserrQueryParamError<InvalidWebhookDeliveriesLimit>)]
+    #[param(required = false, value_type = u32, example = 12, default = json!(PAGINATION_DEFAULT_LIMIT))]
+    pub limit: Param<u32>,
+    /// Fetch the next set of results from the given uid.
+    #[deserr(default, error = DeserrQueryParamError<InvalidWebhookDeliveriesFrom>)]
+    #[param(required = false, value_type = Option<u32>, example = 42)]
+    pub from: Option<Param<DeliveryId>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AllWebhookDeliveries {
+    /// The deliveries of the webhook, from the most recent one.
+    results: Vec<WebhookDelivery>,
+    /// Limit given for the query. If limit is not provided as a query parameter, this parameter displays the default limit value.
+    limit: u32,
+    /// The first delivery uid returned.
+    from: Option<u32>,
+    /// Represents the value to send in from to fetch the next slice of the results. When the returned value is null, it means that all the deliveries have been browsed.
+    next: Option<u32>,
+}
+
+#[derive(Serialize)]
+pub struct WebhooksAnalytics {
+    filtered: bool,
+    signed: bool,
+}
+
+impl Aggregate for WebhooksAnalytics {
+    fn event_name(&self) -> &'static str {
+        "Webhooks Updated"
+    }
+
+    fn aggregate(self: Box<Self>, new: Box<Self>) -> Box<Self> {
+        Box::new(Self { filtered: self.filtered | new.filtered, signed: self.signed | new.signed })
+    }
+
+    fn into_event(self: Box<Self>) -> serde_json::Value {
+        serde_json::to_value(*self).unwrap_or_default()
+    }
+}
+
+fn parse_webhook_uuid(uuid: &str) -> Result<Uuid, ResponseError> {
+    Uuid::from_str(uuid).map_err(|error| {
+        ResponseError::from_msg(
+            format!("Invalid webhook uuid `{uuid}`: {error}"),
+            Code::InvalidWebhookUuid,
+        )
+    })
+}
+
+fn validate_url(url: String) -> Result<String, ResponseError> {
+    match url::Url::parse(&url) {
+        Ok(parsed) if matches!(parsed.scheme(), "http" | "https") => Ok(url),
+        Ok(parsed) => Err(ResponseError::from_msg(
+            format!(
+                "Invalid `.url` (`{url}`): the scheme must be `http` or `https`, not `{}`.",
+                parsed.scheme()
+            ),
+            Code::InvalidWebhookUrl,
+        )),
+        Err(error) => Err(ResponseError::from_msg(
+            format!("Invalid `.url` (`{url}`): {error}"),
+            Code::InvalidWebhookUrl,
+        )),
+    }
+}
+
+fn validate_headers(
+    headers: BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, ResponseError> {
+    for (name, value) in &headers {
+        if let Err(error) = HeaderName::from_str(name) {
+            return Err(ResponseError::from_msg(
+                format!("Invalid header name `{name}` in `.headers`: {error}"),
+                Code::InvalidWebhookHeaders,
+            ));
+        }
+        if let Err(error) = HeaderValue::from_str(value) {
+            return Err(ResponseError::from_msg(
+                format!("Invalid value for the header `{name}` in `.headers`: {error}"),
+                Code::InvalidWebhookHeaders,
+            ));
+        }
+    }
+    Ok(headers)
+}
+
+fn validate_filter(filter: WebhookFilterSettings) -> Result<WebhookFilter, ResponseError> {
+    let WebhookFilterSettings { index_uids, types, statuses } = filter;
+    let invalid_filter = |error: String| {
+        ResponseError::from_msg(format!("Invalid `.filter`: {error}"), Code::InvalidWebhookFilter)
+    };
+
+    let types = types
+        .map(|types| types.iter().map(|kind| Kind::from_str(kind)).collect::<Result<_, _>>())
+        .transpose()
+        .map_err(|error| invalid_filter(error.to_string()))?;
+    let statuses = statuses
+        .map(|statuses| statuses.iter().map(|s| Status::from_str(s)).collect::<Result<_, _>>())
+        .transpose()
+        .map_err(|error| invalid_filter(error.to_string()))?;
+
+    Ok(WebhookFilter { index_uids, types, statuses })
+}
+
+/// List webhooks
+///
+/// List all the webhooks, including the one given with `--task-webhook-url`.
+#[utoipa::path(
+    get,
+    path = "",
+    tag = "Webhooks",
+    security(("Bearer" = ["webhooks.get", "*"])),
+    responses(
+        (status = OK, description = "The webhooks are returned", body = WebhookList, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    {
+                        "uuid": "0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a",
+                        "isEditable": true,
+                        "url": "https://example.com/meilisearch-tasks",
+                        "headers": { "Authorization": "BeXXXX..." },
+                        "filter": { "indexUids": ["movies"], "types": null, "statuses": ["failed"] },
+                        "signed": true
+                    }
+                ]
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn list_webhooks(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_GET }>, Data<IndexScheduler>>,
+) -> Result<HttpResponse, ResponseError> {
+    let results = index_scheduler.webhooks()?.into_iter().map(WebhookView::from).collect();
+    let webhooks = WebhookList { results };
+    debug!(returns = ?webhooks, "List webhooks");
+    Ok(HttpResponse::Ok().json(webhooks))
+}
+
+/// Create a webhook
+///
+/// Register a new endpoint notified with the tasks of every processed batch.
+#[utoipa::path(
+    post,
+    path = "",
+    tag = "Webhooks",
+    request_body = WebhookSettings,
+    security(("Bearer" = ["webhooks.update", "*"])),
+    responses(
+        (status = CREATED, description = "The webhook has been created", body = WebhookView, content_type = "application/json", example = json!(
+            {
+                "uuid": "0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a",
+                "isEditable": true,
+                "url": "https://example.com/meilisearch-tasks",
+                "headers": {},
+                "filter": { "indexUids": null, "types": null, "statuses": null },
+                "signed": false
+            }
+        )),
+        (status = 401, description = "The authorization header is missing", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "The Authorization header is missing. It must use the bearer authorization method.",
+                "code": "missing_authorization_header",
+                "type": "auth",
+                "link": "https://docs.meilisearch.com/errors#missing_authorization_header"
+            }
+        )),
+    )
+)]
+async fn create_webhook(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_UPDATE }>, Data<IndexScheduler>>,
+    settings: AwebJson<WebhookSettings, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let WebhookSettings { url, headers, filter, secret } = settings.0;
+
+    let url = match url {
+        Setting::Set(url) => validate_url(url)?,
+        Setting::Reset | Setting::NotSet => {
+            return Err(ResponseError::from_msg(
+                String::from("Missing field `.url`"),
+                Code::MissingWebhookUrl,
+            ))
+        }
+    };
+    let webhook = Webhook {
+        uuid: Uuid::new_v4(),
+        url,
+        headers: validate_headers(headers.set().unwrap_or_default())?,
+        filter: filter.set().map(validate_filter).transpose()?.unwrap_or_default(),
+        secret: secret.set(),
+    };
+
+    analytics.publish(
+        WebhooksAnalytics {
+            filtered: webhook.filter != WebhookFilter::default(),
+            signed: webhook.secret.is_some(),
+        },
+        &req,
+    );
+
+    index_scheduler.put_webhook(webhook.clone())?;
+    let webhook = WebhookView::from(webhook);
+    debug!(returns = ?webhook, "Create webhook");
+    Ok(HttpResponse::Created().json(webhook))
+}
+
+/// Get a webhook
+#[utoipa::path(
+    get,
+    path = "/{uuid}",
+    tag = "Webhooks",
+    security(("Bearer" = ["webhooks.get", "*"])),
+    params(("uuid" = String, Path, example = "0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a", description = "The uuid of the webhook", nullable = false)),
+    responses(
+        (status = OK, description = "The webhook is returned", body = WebhookView, content_type = "application/json"),
+        (status = 404, description = "The webhook does not exist", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Webhook `0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a` not found.",
+                "code": "webhook_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+            }
+        )),
+    )
+)]
+async fn get_webhook(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_GET }>, Data<IndexScheduler>>,
+    uuid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = parse_webhook_uuid(&uuid)?;
+    let webhook = WebhookView::from(index_scheduler.webhook(uuid)?);
+    debug!(returns = ?webhook, "Get webhook");
+    Ok(HttpResponse::Ok().json(webhook))
+}
+
+/// Update a webhook
+///
+/// Update the fields given in the payload, `null` resets a field to its default value.
+#[utoipa::path(
+    patch,
+    path = "/{uuid}",
+    tag = "Webhooks",
+    request_body = WebhookSettings,
+    security(("Bearer" = ["webhooks.update", "*"])),
+    params(("uuid" = String, Path, example = "0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a", description = "The uuid of the webhook", nullable = false)),
+    responses(
+        (status = OK, description = "The updated webhook is returned", body = WebhookView, content_type = "application/json"),
+        (status = 404, description = "The webhook does not exist", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Webhook `0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a` not found.",
+                "code": "webhook_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+            }
+        )),
+    )
+)]
+async fn patch_webhook(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_UPDATE }>, Data<IndexScheduler>>,
+    uuid: web::Path<String>,
+    settings: AwebJson<WebhookSettings, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: Data<Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = parse_webhook_uuid(&uuid)?;
+    let WebhookSettings { url, headers, filter, secret } = settings.0;
+    let old = index_scheduler.webhook(uuid)?;
+
+    let webhook = Webhook {
+        uuid,
+        url: match url {
+            Setting::Set(url) => validate_url(url)?,
+            Setting::Reset => {
+                return Err(ResponseError::from_msg(
+                    String::from("Field `.url` cannot be set to `null`"),
+                    Code::InvalidWebhookUrl,
+                ))
+            }
+            Setting::NotSet => old.url,
+        },
+        headers: match headers {
+            Setting::Set(headers) => validate_headers(headers)?,
+            Setting::Reset => BTreeMap::new(),
+            Setting::NotSet => old.headers,
+        },
+        filter: match filter {
+            Setting::Set(filter) => validate_filter(filter)?,
+            Setting::Reset => WebhookFilter::default(),
+            Setting::NotSet => old.filter,
+        },
+        secret: match secret {
+            Setting::Set(secret) => Some(secret),
+            Setting::Reset => None,
+            Setting::NotSet => old.secret,
+        },
+    };
+
+    analytics.publish(
+        WebhooksAnalytics {
+            filtered: webhook.filter != WebhookFilter::default(),
+            signed: webhook.secret.is_some(),
+        },
+        &req,
+    );
+
+    index_scheduler.put_webhook(webhook.clone())?;
+    let webhook = WebhookView::from(webhook);
+    debug!(returns = ?webhook, "Patch webhook");
+    Ok(HttpResponse::Ok().json(webhook))
+}
+
+/// Delete a webhook
+///
+/// Delete a webhook along with its deliveries, the deliveries that are not sent yet are dropped.
+#[utoipa::path(
+    delete,
+    path = "/{uuid}",
+    tag = "Webhooks",
+    security(("Bearer" = ["webhooks.update", "*"])),
+    params(("uuid" = String, Path, example = "0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a", description = "The uuid of the webhook", nullable = false)),
+    responses(
+        (status = NO_CONTENT, description = "The webhook has been deleted"),
+        (status = 404, description = "The webhook does not exist", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Webhook `0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a` not found.",
+                "code": "webhook_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+            }
+        )),
+    )
+)]
+async fn delete_webhook(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_UPDATE }>, Data<IndexScheduler>>,
+    uuid: web::Path<String>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = parse_webhook_uuid(&uuid)?;
+    index_scheduler.delete_webhook(uuid)?;
+    debug!(uuid = %uuid, "Delete webhook");
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Get the deliveries of a webhook
+///
+/// List the deliveries of a webhook from the most recent one, with the outcome of their last attempt.
+#[utoipa::path(
+    get,
+    path = "/{uuid}/deliveries",
+    tag = "Webhooks",
+    security(("Bearer" = ["webhooks.get", "*"])),
+    params(
+        ("uuid" = String, Path, example = "0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a", description = "The uuid of the webhook", nullable = false),
+        WebhookDeliveriesQuery
+    ),
+    responses(
+        (status = OK, description = "The deliveries are returned", body = AllWebhookDeliveries, content_type = "application/json", example = json!(
+            {
+                "results": [
+                    {
+                        "uid": 12,
+                        "webhookUuid": "0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a",
+                        "status": "enqueued",
+                        "taskUids": [41, 42],
+                        "attempts": 2,
+                        "lastStatusCode": 503,
+                        "lastError": "The webhook answered with the status code `503 Service Unavailable`.",
+                        "enqueuedAt": "2024-08-12T10:00:00Z",
+                        "lastAttemptAt": "2024-08-12T10:00:01Z",
+                        "nextAttemptAt": "2024-08-12T10:00:03Z",
+                        "finishedAt": null
+                    }
+                ],
+                "limit": 20,
+                "from": 12,
+                "next": null
+            }
+        )),
+        (status = 404, description = "The webhook does not exist", body = ResponseError, content_type = "application/json", example = json!(
+            {
+                "message": "Webhook `0b4a9bb8-6a79-4f1e-9f36-6d6e4c7c2b1a` not found.",
+                "code": "webhook_not_found",
+                "type": "invalid_request",
+                "link": "https://docs.meilisearch.com/errors#webhook_not_found"
+            }
+        )),
+    )
+)]
+async fn get_webhook_deliveries(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::WEBHOOKS_GET }>, Data<IndexScheduler>>,
+    uuid: web::Path<String>,
+    params: AwebQueryParameter<WebhookDeliveriesQuery, DeserrQueryParamError>,
+) -> Result<HttpResponse, ResponseError> {
+    let uuid = parse_webhook_uuid(&uuid)?;
+    let WebhookDeliveriesQuery { limit, from } = params.into_inner();
+    let limit = limit.0;
+
+    // We fetch one more delivery to know if there is a next page.
+    let mut results = index_scheduler.webhook_deliveries(
+        uuid,
+        from.map(|from| from.0),
+        limit.saturating_add(1) as usize,
+    )?;
+    let next = if results.len() > limit as usize { results.pop().map(|d| d.uid) } else { None };
+    let from = results.first().map(|d| d.uid);
+
+    let deliveries = AllWebhookDeliveries { results, limit, from, next };
+    debug!(returns = ?deliveries, "Get webhook deliveries");
+    Ok(HttpResponse::Ok().json(deliveries))
+}
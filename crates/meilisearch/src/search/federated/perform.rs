@@ -19,8 +19,9 @@ use tokio::task::JoinHandle;
 
 use super::super::ranking_rules::{self, RankingRules};
 use super::super::{
-    compute_facet_distribution_stats, prepare_search, AttributesFormat, ComputedFacets, HitMaker,
-    HitsInfo, RetrieveVectors, SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
+    apply_query_rules, compute_facet_buckets, compute_facet_distribution_stats, prepare_search,
+    AttributesFormat, ComputedFacets, FacetBucketsQuery, HitMaker, HitsInfo, RetrieveVectors,
+    SearchHit, SearchKind, SearchQuery, SearchQueryWithIndex,
 };
 use super::proxy::{proxy_search, ProxySearchError, ProxySearchParams};
 use super::types::{
@@ -29,7 +30,7 @@ use super::types::{
 };
 use super::weighted_scores;
 use crate::error::MeilisearchHttpError;
-use crate::routes::indexes::search::search_kind;
+use crate::routes::indexes::search::{search_kind, search_reranker};
 use crate::search::federated::types::{INDEX_UID, QUERIES_POSITION, WEIGHTED_RANKING_SCORE};
 
 pub async fn perform_federated_search(
@@ -123,8 +124,12 @@ pub async fn perform_federated_search(
         .collect();
 
     // 3.3. merge facets
-    let (facet_distribution, facet_stats, facets_by_index) =
-        facet_order.merge(federation.merge_facets, remote_results, facets);
+    let (facet_distribution, facet_stats, facet_buckets, facets_by_index) = facet_order.merge(
+        federation.merge_facets,
+        &federation.facet_buckets,
+        remote_results,
+        facets,
+    );
 
     let after_merge = std::time::Instant::now();
 
@@ -145,6 +150,7 @@ pub async fn perform_federated_search(
         used_negative_operator,
         facet_distribution,
         facet_stats,
+        facet_buckets,
         facets_by_index,
         remote_errors: partitioned_queries.has_remote.then_some(remote_errors),
     })
@@ -411,6 +417,7 @@ fn merge_metadata(
         semantic_hit_count: _,
         facet_distribution: _,
         facet_stats: _,
+        facet_buckets: _,
         facets_by_index: _,
         degraded: degraded_for_host,
         used_negative_operator: host_used_negative_operator,
@@ -474,6 +481,20 @@ impl PartitionedQueries {
             .into());
         }
 
+        if federated_query.has_facet_buckets().is_some() {
+            return Err(MeilisearchHttpError::FacetBucketsInFederatedQuery(
+                query_index,
+                federated_query.index_uid.into_inner(),
+            )
+            .into());
+        }
+
+        if federated_query.has_hierarchical_facets().is_some() {
+            return Err(
+                MeilisearchHttpError::HierarchicalFacetsInFederatedQuery(query_index).into()
+            );
+        }
+
         let (index_uid, query, federation_options) = federated_query.into_index_query_federation();
 
         let federation_options = federation_options.unwrap_or_default();
@@ -731,6 +752,8 @@ impl SearchByIndex {
             let res: Result<(), ResponseError> = (|| {
                 let search_kind =
                     search_kind(&query, params.index_scheduler, index_uid.to_string(), &index)?;
+                let reranker =
+                    search_reranker(&query, params.index_scheduler, index_uid.to_string(), &index)?;
 
                 let canonicalization_kind = match (&search_kind, &query.q) {
                     (SearchKind::SemanticOnly { .. }, _) => {
@@ -800,18 +823,30 @@ impl SearchByIndex {
                     None => TimeBudget::default(),
                 };
 
-                let (mut search, _is_finite_pagination, _max_total_hits, _offset) = prepare_search(
-                    &index,
-                    &rtxn,
-                    &query,
-                    &search_kind,
-                    time_budget,
-                    params.features,
-                )?;
+                let mut query = query;
+                let rules = apply_query_rules(&index, &rtxn, &mut query, params.features)?;
+
+                let (mut search, _is_finite_pagination, _max_total_hits, _offset, _limit) =
+                    prepare_search(
+                        &index,
+                        &rtxn,
+                        &query,
+                        &search_kind,
+                        time_budget,
+                        params.features,
+                    )?;
 
                 search.scoring_strategy(milli::score_details::ScoringStrategy::Detailed);
+                // The hits are merged by ranking score, which the pinned documents don't have,
+                // so only the hidden documents and the filters of the rules apply.
+                if !rules.hidden.is_empty() {
+                    search.excluded_documents(rules.hidden);
+                }
                 search.offset(0);
                 search.limit(params.required_hit_count);
+                if let Some(reranker) = reranker {
+                    reranker.apply(&mut search);
+                }
 
                 let (result, _semantic_hit_count) =
                     super::super::search_from_kind(index_uid.to_string(), search_kind, search)?;
@@ -916,15 +951,24 @@ impl SearchByIndex {
                 .collect();
         let merged_result = merged_result?;
         let estimated_total_hits = candidates.len() as usize;
+        let facet_buckets = &self.federation.facet_buckets;
         let facets = facets_by_index
             .map(|facets_by_index| {
-                compute_facet_distribution_stats(
+                let buckets = compute_facet_buckets(
+                    facet_buckets,
+                    &index,
+                    &rtxn,
+                    candidates.clone(),
+                    super::super::Route::MultiSearch,
+                )?;
+                let facets = compute_facet_distribution_stats(
                     &facets_by_index,
                     &index,
                     &rtxn,
                     candidates,
                     super::super::Route::MultiSearch,
-                )
+                )?;
+                Ok(ComputedFacets { buckets, ..facets })
             })
             .transpose()
             .map_err(|mut error| {
@@ -983,13 +1027,22 @@ impl SearchByIndex {
             }
 
             if let Some(facets) = facets {
-                if let Err(mut error) = compute_facet_distribution_stats(
-                    &facets,
+                if let Err(mut error) = compute_facet_buckets(
+                    &self.federation.facet_buckets,
                     &index,
                     &rtxn,
                     Default::default(),
                     super::super::Route::MultiSearch,
-                ) {
+                )
+                .and_then(|_| {
+                    compute_facet_distribution_stats(
+                        &facets,
+                        &index,
+                        &rtxn,
+                        Default::default(),
+                        super::super::Route::MultiSearch,
+                    )
+                }) {
                     error.message =
                 format!("Inside `.federation.facetsByIndex.{index_uid}`: {}\n - Note: index `{index_uid}` is not used in queries", error.message);
                     return Err(error);
@@ -1029,6 +1082,7 @@ enum FacetOrder {
 
 type FacetDistributions = BTreeMap<String, indexmap::IndexMap<String, u64>>;
 type FacetStats = BTreeMap<String, crate::search::FacetStats>;
+type FacetBuckets = BTreeMap<String, Vec<crate::search::FacetBucket>>;
 
 impl FacetOrder {
     fn check_facet_order(
@@ -1078,35 +1132,43 @@ impl FacetOrder {
     fn merge(
         self,
         merge_facets: Option<MergeFacets>,
+        facet_buckets: &BTreeMap<String, FacetBucketsQuery>,
         remote_results: Vec<FederatedSearchResult>,
         mut facets: FederatedFacets,
-    ) -> (Option<FacetDistributions>, Option<FacetStats>, FederatedFacets) {
-        let (facet_distribution, facet_stats, facets_by_index) = match (self, merge_facets) {
-            (FacetOrder::ByFacet(facet_order), Some(merge_facets)) => {
-                for remote_facets_by_index in
-                    remote_results.into_iter().map(|result| result.facets_by_index)
-                {
-                    facets.append(remote_facets_by_index);
-                }
-                let facets = facets.merge(merge_facets, facet_order);
-
-                let (facet_distribution, facet_stats) = facets
-                    .map(|ComputedFacets { distribution, stats }| (distribution, stats))
-                    .unzip();
+    ) -> (Option<FacetDistributions>, Option<FacetStats>, Option<FacetBuckets>, FederatedFacets)
+    {
+        let (facet_distribution, facet_stats, facet_buckets, facets_by_index) =
+            match (self, merge_facets) {
+                (FacetOrder::ByFacet(facet_order), Some(merge_facets)) => {
+                    for remote_facets_by_index in
+                        remote_results.into_iter().map(|result| result.facets_by_index)
+                    {
+                        facets.append(remote_facets_by_index);
+                    }
+                    let facets = facets.merge(merge_facets, facet_order, facet_buckets);
+
+                    let (facet_distribution, facet_stats, facet_buckets) = match facets {
+                        Some(ComputedFacets { distribution, stats, buckets }) => (
+                            Some(distribution),
+                            Some(stats),
+                            (!buckets.is_empty()).then_some(buckets),
+                        ),
+                        None => (None, None, None),
+                    };
 
-                (facet_distribution, facet_stats, FederatedFacets::default())
-            }
-            (FacetOrder::ByIndex(facet_order), _) => {
-                for remote_facets_by_index in
-                    remote_results.into_iter().map(|result| result.facets_by_index)
-                {
-                    facets.append(remote_facets_by_index);
+                    (facet_distribution, facet_stats, facet_buckets, FederatedFacets::default())
                 }
-                facets.sort_and_truncate(facet_order);
-                (None, None, facets)
-            }
-            _ => (None, None, facets),
-        };
-        (facet_distribution, facet_stats, facets_by_index)
+                (FacetOrder::ByIndex(facet_order), _) => {
+                    for remote_facets_by_index in
+                        remote_results.into_iter().map(|result| result.facets_by_index)
+                    {
+                        facets.append(remote_facets_by_index);
+                    }
+                    facets.sort_and_truncate(facet_order, facet_buckets);
+                    (None, None, None, facets)
+                }
+                _ => (None, None, None, facets),
+            };
+        (facet_distribution, facet_stats, facet_buckets, facets_by_index)
     }
 }
@@ -6,9 +6,10 @@ use std::vec::Vec;
 use indexmap::IndexMap;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::{
-    InvalidMultiSearchFacetsByIndex, InvalidMultiSearchMaxValuesPerFacet,
-    InvalidMultiSearchMergeFacets, InvalidMultiSearchQueryPosition, InvalidMultiSearchRemote,
-    InvalidMultiSearchWeight, InvalidSearchLimit, InvalidSearchOffset,
+    InvalidMultiSearchFacetBuckets, InvalidMultiSearchFacetsByIndex,
+    InvalidMultiSearchMaxValuesPerFacet, InvalidMultiSearchMergeFacets,
+    InvalidMultiSearchQueryPosition, InvalidMultiSearchRemote, InvalidMultiSearchWeight,
+    InvalidSearchLimit, InvalidSearchOffset,
 };
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
@@ -17,7 +18,10 @@ use meilisearch_types::milli::OrderBy;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use super::super::{ComputedFacets, FacetStats, HitsInfo, SearchHit, SearchQueryWithIndex};
+use super::super::{
+    ComputedFacets, FacetBucket, FacetBucketsQuery, FacetStats, HitsInfo, SearchHit,
+    SearchQueryWithIndex,
+};
 
 pub const DEFAULT_FEDERATED_WEIGHT: f64 = 1.0;
 
@@ -87,6 +91,9 @@ pub struct Federation {
     pub facets_by_index: BTreeMap<IndexUid, Option<Vec<String>>>,
     #[deserr(default, error = DeserrJsonError<InvalidMultiSearchMergeFacets>)]
     pub merge_facets: Option<MergeFacets>,
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchFacetBuckets>)]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub facet_buckets: BTreeMap<String, FacetBucketsQuery>,
 }
 
 #[derive(Copy, Clone, Debug, deserr::Deserr, Serialize, Default, ToSchema)]
@@ -125,6 +132,8 @@ pub struct FederatedSearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facet_buckets: Option<BTreeMap<String, Vec<FacetBucket>>>,
     #[serde(default, skip_serializing_if = "FederatedFacets::is_empty")]
     pub facets_by_index: FederatedFacets,
 
@@ -149,6 +158,7 @@ impl fmt::Debug for FederatedSearchResult {
             used_negative_operator,
             facet_distribution,
             facet_stats,
+            facet_buckets,
             facets_by_index,
             remote_errors,
         } = self;
@@ -170,6 +180,9 @@ impl fmt::Debug for FederatedSearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(facet_buckets) = facet_buckets {
+            debug.field("facet_buckets", &facet_buckets);
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
@@ -202,6 +215,7 @@ impl FederatedFacets {
         self,
         MergeFacets { max_values_per_facet }: MergeFacets,
         facet_order: BTreeMap<String, (String, OrderBy)>,
+        facet_buckets: &BTreeMap<String, FacetBucketsQuery>,
     ) -> Option<ComputedFacets> {
         if self.is_empty() {
             return None;
@@ -209,6 +223,7 @@ impl FederatedFacets {
 
         let mut distribution: BTreeMap<String, _> = Default::default();
         let mut stats: BTreeMap<String, FacetStats> = Default::default();
+        let mut buckets: BTreeMap<String, Vec<FacetBucket>> = Default::default();
 
         for facets_by_index in self.0.into_values() {
             for (facet, index_distribution) in facets_by_index.distribution {
@@ -242,8 +257,14 @@ impl FederatedFacets {
                     }
                 }
             }
+
+            for (facet, index_buckets) in facets_by_index.buckets {
+                merge_buckets(buckets.entry(facet).or_default(), index_buckets);
+            }
         }
 
+        sort_and_truncate_buckets(&mut buckets, facet_buckets, max_values_per_facet);
+
         // fixup order
         for (facet, values) in &mut distribution {
             let order_by = facet_order.get(facet).map(|(_, order)| *order).unwrap_or_default();
@@ -266,7 +287,7 @@ impl FederatedFacets {
             };
         }
 
-        Some(ComputedFacets { distribution, stats })
+        Some(ComputedFacets { distribution, stats, buckets })
     }
 
     pub(crate) fn append(&mut self, FederatedFacets(remote_facets_by_index): FederatedFacets) {
@@ -293,10 +314,21 @@ impl FederatedFacets {
                     *count += remote_count;
                 }
             }
+
+            for (remote_facet, remote_buckets) in remote_facets.buckets {
+                merge_buckets(
+                    merged_facets.buckets.entry(remote_facet).or_default(),
+                    remote_buckets,
+                );
+            }
         }
     }
 
-    pub fn sort_and_truncate(&mut self, facet_order: BTreeMap<String, (OrderByMap, usize)>) {
+    pub fn sort_and_truncate(
+        &mut self,
+        facet_order: BTreeMap<String, (OrderByMap, usize)>,
+        facet_buckets: &BTreeMap<String, FacetBucketsQuery>,
+    ) {
         for (index, facets) in &mut self.0 {
             let Some((order_by, max_values_per_facet)) = facet_order.get(index) else {
                 continue;
@@ -316,6 +348,44 @@ impl FederatedFacets {
                 }
                 values.truncate(*max_values_per_facet);
             }
+            sort_and_truncate_buckets(
+                &mut facets.buckets,
+                facet_buckets,
+                Some(*max_values_per_facet),
+            );
+        }
+    }
+}
+
+/// Adds the counts of `buckets` to the buckets with the same bounds in `merged`.
+fn merge_buckets(merged: &mut Vec<FacetBucket>, buckets: Vec<FacetBucket>) {
+    for bucket in buckets {
+        match merged.iter_mut().find(|merged| merged.from == bucket.from && merged.to == bucket.to)
+        {
+            Some(merged) => merged.count += bucket.count,
+            None => merged.push(bucket),
+        }
+    }
+}
+
+/// The ranges are returned in the order of the query, but histograms merged from several indexes
+/// must be sorted again and truncated to the maximum number of values per facet.
+fn sort_and_truncate_buckets(
+    buckets: &mut BTreeMap<String, Vec<FacetBucket>>,
+    facet_buckets: &BTreeMap<String, FacetBucketsQuery>,
+    max_values_per_facet: Option<usize>,
+) {
+    for (facet, buckets) in buckets {
+        let Some(FacetBucketsQuery { interval: Some(_), .. }) = facet_buckets.get(facet) else {
+            continue;
+        };
+        buckets.sort_by(|left, right| {
+            let left = left.from.unwrap_or(f64::NEG_INFINITY);
+            let right = right.from.unwrap_or(f64::NEG_INFINITY);
+            left.total_cmp(&right)
+        });
+        if let Some(max_values_per_facet) = max_values_per_facet {
+            buckets.truncate(max_values_per_facet);
         }
     }
 }
@@ -63,13 +63,28 @@ pub fn compare(
                     }
                 }
             }
+            (Some(WeightedScoreValue::Boost(left)), Some(WeightedScoreValue::Boost(right))) => {
+                match (left, right) {
+                    (None, None) => continue,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(left), Some(right)) => {
+                        if (left - right).abs() <= f64::EPSILON {
+                            continue;
+                        }
+                        return left.partial_cmp(&right).unwrap();
+                    }
+                }
+            }
             // not comparable details, use global
             (Some(WeightedScoreValue::WeightedScore(_)), Some(_))
             | (Some(_), Some(WeightedScoreValue::WeightedScore(_)))
             | (Some(WeightedScoreValue::VectorSort(_)), Some(_))
             | (Some(_), Some(WeightedScoreValue::VectorSort(_)))
             | (Some(WeightedScoreValue::GeoSort { .. }), Some(WeightedScoreValue::Sort { .. }))
-            | (Some(WeightedScoreValue::Sort { .. }), Some(WeightedScoreValue::GeoSort { .. })) => {
+            | (Some(WeightedScoreValue::Sort { .. }), Some(WeightedScoreValue::GeoSort { .. }))
+            | (Some(WeightedScoreValue::Boost(_)), Some(_))
+            | (Some(_), Some(WeightedScoreValue::Boost(_))) => {
                 let left_count = left_it.count();
                 let right_count = right_it.count();
                 // compare how many remaining groups of rules each side has.
@@ -16,9 +16,11 @@ use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::prompt::Prompt;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
-use meilisearch_types::milli::vector::Embedder;
+use meilisearch_types::milli::vector::rerank::Reranker;
+use meilisearch_types::milli::vector::{Embedder, VectorQuantization};
 use meilisearch_types::milli::{
     FacetValueHit, InternalError, OrderBy, PatternMatch, SearchForFacetValues, TimeBudget,
 };
@@ -26,8 +28,9 @@ use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, LocalizedAttributesRule,
-    MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    AscDesc, BoostError, DocumentId, FieldId, FieldsIdsMap, Filter, FormatOptions, Index,
+    LocalizedAttributesRule, MatchBounds, MatcherBuilder, SortError, TermsMatchingStrategy,
+    DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -55,6 +58,8 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_HIERARCHY_SEPARATOR: fn() -> String = || " > ".to_string();
+pub const DEFAULT_RERANK_TOP_N: fn() -> usize = || 20;
 
 #[derive(Clone, Default, PartialEq, Deserr, ToSchema)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -65,6 +70,8 @@ pub struct SearchQuery {
     pub vector: Option<Vec<f32>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRerank>)]
+    pub rerank: Option<RerankQuery>,
     #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
     #[schema(default = DEFAULT_SEARCH_OFFSET)]
     pub offset: usize,
@@ -96,10 +103,18 @@ pub struct SearchQuery {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoost>)]
+    pub boost: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetBuckets>)]
+    pub facet_buckets: Option<BTreeMap<String, FacetBucketsQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHierarchicalFacets>)]
+    pub hierarchical_facets: Option<BTreeMap<String, HierarchicalFacetQuery>>,
     #[deserr(error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     #[schema(default = DEFAULT_HIGHLIGHT_PRE_TAG)]
     pub highlight_pre_tag: String,
@@ -163,6 +178,7 @@ impl fmt::Debug for SearchQuery {
             q,
             vector,
             hybrid,
+            rerank,
             offset,
             limit,
             page,
@@ -177,8 +193,12 @@ impl fmt::Debug for SearchQuery {
             show_ranking_score_details,
             filter,
             sort,
+            boost,
             distinct,
             facets,
+            disjunctive_facets,
+            facet_buckets,
+            hierarchical_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -219,6 +239,9 @@ impl fmt::Debug for SearchQuery {
         if let Some(hybrid) = hybrid {
             debug.field("hybrid", &hybrid);
         }
+        if let Some(rerank) = rerank {
+            debug.field("rerank", &rerank);
+        }
         if let Some(attributes_to_search_on) = attributes_to_search_on {
             debug.field("attributes_to_search_on", &attributes_to_search_on);
         }
@@ -228,12 +251,24 @@ impl fmt::Debug for SearchQuery {
         if let Some(sort) = sort {
             debug.field("sort", &sort);
         }
+        if let Some(boost) = boost {
+            debug.field("boost", &boost);
+        }
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if *disjunctive_facets {
+            debug.field("disjunctive_facets", &disjunctive_facets);
+        }
+        if let Some(facet_buckets) = facet_buckets {
+            debug.field("facet_buckets", &facet_buckets);
+        }
+        if let Some(hierarchical_facets) = hierarchical_facets {
+            debug.field("hierarchical_facets", &hierarchical_facets);
+        }
         debug.field("matching_strategy", &matching_strategy);
 
         // Then everything related to the formatting
@@ -285,13 +320,99 @@ pub struct HybridQuery {
     pub semantic_ratio: SemanticRatio,
     #[deserr(error = DeserrJsonError<InvalidSearchEmbedder>)]
     pub embedder: String,
+    /// Compute the distance to the embeddings of all the matching documents instead of
+    /// searching for the approximate nearest neighbors.
+    ///
+    /// The results are exact, but the search is slower on large sets of documents,
+    /// so it is best suited to searches whose filter matches few documents.
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
+    #[schema(default)]
+    #[serde(default)]
+    pub exact: bool,
+}
+
+/// Re-scores the first documents of the search with a reranker of the index.
+#[derive(Debug, Clone, PartialEq, Deserr, ToSchema, Serialize)]
+#[deserr(error = DeserrJsonError<InvalidSearchRerank>, rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct RerankQuery {
+    /// Name of the reranker, as declared in the `rerankers` setting.
+    #[deserr(error = DeserrJsonError<InvalidSearchRerank>)]
+    pub reranker: String,
+    /// Number of documents to re-score and return, starting from the first one of the search.
+    ///
+    /// Must be between 1 and the `maxTotalHits` of the pagination settings of the index.
+    #[deserr(default = DEFAULT_RERANK_TOP_N(), error = DeserrJsonError<InvalidSearchRerank>)]
+    #[schema(default = DEFAULT_RERANK_TOP_N)]
+    pub top_n: usize,
+}
+
+/// The reranker of a search, resolved from the settings of the index.
+#[derive(Clone)]
+pub struct SearchReranker {
+    pub reranker_name: String,
+    pub reranker: Arc<Reranker>,
+    pub document_template: Arc<Prompt>,
+    pub top_n: usize,
+}
+
+impl SearchReranker {
+    pub(crate) fn new(
+        index_scheduler: &index_scheduler::IndexScheduler,
+        index_uid: String,
+        index: &Index,
+        rerank: &RerankQuery,
+    ) -> Result<Self, ResponseError> {
+        let rtxn = index.read_txn()?;
+        let max_total_hits = index
+            .pagination_max_total_hits(&rtxn)
+            .map_err(milli::Error::from)?
+            .map(|x| x as usize)
+            .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+        if !(1..=max_total_hits).contains(&rerank.top_n) {
+            return Err(ResponseError::from_msg(
+                format!(
+                    "Invalid value for `rerank.topN`: expected a number between 1 and the `maxTotalHits` of the index ({max_total_hits}), but got `{}`.",
+                    rerank.top_n
+                ),
+                Code::InvalidSearchRerank,
+            ));
+        }
+        let settings = index
+            .rerankers(&rtxn)
+            .map_err(milli::Error::from)?
+            .and_then(|mut rerankers| rerankers.remove(&rerank.reranker))
+            .ok_or_else(|| milli::UserError::InvalidSearchReranker(rerank.reranker.clone()))
+            .map_err(milli::Error::from)?;
+        let (reranker, document_template) =
+            index_scheduler.reranker(index_uid, &rerank.reranker, &settings)?;
+        Ok(Self {
+            reranker_name: rerank.reranker.clone(),
+            reranker,
+            document_template,
+            top_n: rerank.top_n,
+        })
+    }
+
+    fn apply(self, search: &mut milli::Search<'_>) {
+        search.rerank(self.reranker_name, self.reranker, self.document_template, self.top_n);
+    }
 }
 
 #[derive(Clone)]
 pub enum SearchKind {
     KeywordOnly,
-    SemanticOnly { embedder_name: String, embedder: Arc<Embedder>, quantized: bool },
-    Hybrid { embedder_name: String, embedder: Arc<Embedder>, quantized: bool, semantic_ratio: f32 },
+    SemanticOnly {
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+        quantization: VectorQuantization,
+    },
+    Hybrid {
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+        quantization: VectorQuantization,
+        semantic_ratio: f32,
+    },
 }
 
 impl SearchKind {
@@ -302,7 +423,7 @@ impl SearchKind {
         embedder_name: &str,
         vector_len: Option<usize>,
     ) -> Result<Self, ResponseError> {
-        let (embedder_name, embedder, quantized) = Self::embedder(
+        let (embedder_name, embedder, quantization) = Self::embedder(
             index_scheduler,
             index_uid,
             index,
@@ -310,7 +431,7 @@ impl SearchKind {
             vector_len,
             Route::Search,
         )?;
-        Ok(Self::SemanticOnly { embedder_name, embedder, quantized })
+        Ok(Self::SemanticOnly { embedder_name, embedder, quantization })
     }
 
     pub(crate) fn hybrid(
@@ -321,7 +442,7 @@ impl SearchKind {
         semantic_ratio: f32,
         vector_len: Option<usize>,
     ) -> Result<Self, ResponseError> {
-        let (embedder_name, embedder, quantized) = Self::embedder(
+        let (embedder_name, embedder, quantization) = Self::embedder(
             index_scheduler,
             index_uid,
             index,
@@ -329,7 +450,7 @@ impl SearchKind {
             vector_len,
             Route::Search,
         )?;
-        Ok(Self::Hybrid { embedder_name, embedder, quantized, semantic_ratio })
+        Ok(Self::Hybrid { embedder_name, embedder, quantization, semantic_ratio })
     }
 
     pub(crate) fn embedder(
@@ -339,12 +460,12 @@ impl SearchKind {
         embedder_name: &str,
         vector_len: Option<usize>,
         route: Route,
-    ) -> Result<(String, Arc<Embedder>, bool), ResponseError> {
+    ) -> Result<(String, Arc<Embedder>, VectorQuantization), ResponseError> {
         let rtxn = index.read_txn()?;
         let embedder_configs = index.embedding_configs(&rtxn)?;
         let embedders = index_scheduler.embedders(index_uid, embedder_configs)?;
 
-        let (embedder, _, quantized) = embedders
+        let (embedder, _, quantization) = embedders
             .get(embedder_name)
             .ok_or(match route {
                 Route::Search | Route::MultiSearch => {
@@ -357,7 +478,25 @@ impl SearchKind {
             .map_err(milli::Error::from)?;
 
         if let Some(vector_len) = vector_len {
-            if vector_len != embedder.dimensions() {
+            if embedder.is_sparse() {
+                return Err(meilisearch_types::milli::Error::UserError(
+                    meilisearch_types::milli::UserError::SparseEmbedderSearchVector {
+                        embedder_name: embedder_name.to_owned(),
+                    },
+                )
+                .into());
+            }
+            if embedder.is_multi_vector() {
+                if vector_len == 0 || vector_len % embedder.dimensions() != 0 {
+                    return Err(meilisearch_types::milli::Error::UserError(
+                        meilisearch_types::milli::UserError::InvalidMultiVectorDimensions {
+                            expected: embedder.dimensions(),
+                            found: vector_len,
+                        },
+                    )
+                    .into());
+                }
+            } else if vector_len != embedder.dimensions() {
                 return Err(meilisearch_types::milli::Error::UserError(
                     meilisearch_types::milli::UserError::InvalidVectorDimensions {
                         expected: embedder.dimensions(),
@@ -368,7 +507,7 @@ impl SearchKind {
             }
         }
 
-        Ok((embedder_name.to_owned(), embedder, quantized))
+        Ok((embedder_name.to_owned(), embedder, quantization))
     }
 }
 
@@ -427,6 +566,8 @@ pub struct SearchQueryWithIndex {
     pub vector: Option<Vec<f32>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHybridQuery>)]
     pub hybrid: Option<HybridQuery>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRerank>)]
+    pub rerank: Option<RerankQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchOffset>)]
     pub offset: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchLimit>)]
@@ -455,10 +596,18 @@ pub struct SearchQueryWithIndex {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchBoost>)]
+    pub boost: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDisjunctiveFacets>)]
+    pub disjunctive_facets: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetBuckets>)]
+    pub facet_buckets: Option<BTreeMap<String, FacetBucketsQuery>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHierarchicalFacets>)]
+    pub hierarchical_facets: Option<BTreeMap<String, HierarchicalFacetQuery>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -497,6 +646,14 @@ impl SearchQueryWithIndex {
         self.facets.as_deref().filter(|v| !v.is_empty())
     }
 
+    pub fn has_facet_buckets(&self) -> Option<&BTreeMap<String, FacetBucketsQuery>> {
+        self.facet_buckets.as_ref().filter(|buckets| !buckets.is_empty())
+    }
+
+    pub fn has_hierarchical_facets(&self) -> Option<&BTreeMap<String, HierarchicalFacetQuery>> {
+        self.hierarchical_facets.as_ref().filter(|hierarchies| !hierarchies.is_empty())
+    }
+
     pub fn from_index_query_federation(
         index_uid: IndexUid,
         query: SearchQuery,
@@ -506,6 +663,7 @@ impl SearchQueryWithIndex {
             q,
             vector,
             hybrid,
+            rerank,
             offset,
             limit,
             page,
@@ -520,8 +678,12 @@ impl SearchQueryWithIndex {
             show_ranking_score_details,
             filter,
             sort,
+            boost,
             distinct,
             facets,
+            disjunctive_facets,
+            facet_buckets,
+            hierarchical_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -536,6 +698,7 @@ impl SearchQueryWithIndex {
             q,
             vector,
             hybrid,
+            rerank,
             offset: if offset == DEFAULT_SEARCH_OFFSET() { None } else { Some(offset) },
             limit: if limit == DEFAULT_SEARCH_LIMIT() { None } else { Some(limit) },
             page,
@@ -550,8 +713,12 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            boost,
             distinct,
             facets,
+            disjunctive_facets,
+            facet_buckets,
+            hierarchical_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -583,14 +750,19 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            boost,
             distinct,
             facets,
+            disjunctive_facets,
+            facet_buckets,
+            hierarchical_facets,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
             matching_strategy,
             attributes_to_search_on,
             hybrid,
+            rerank,
             ranking_score_threshold,
             locales,
         } = self;
@@ -613,14 +785,19 @@ impl SearchQueryWithIndex {
                 show_matches_position,
                 filter,
                 sort,
+                boost,
                 distinct,
                 facets,
+                disjunctive_facets,
+                facet_buckets,
+                hierarchical_facets,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
                 matching_strategy,
                 attributes_to_search_on,
                 hybrid,
+                rerank,
                 ranking_score_threshold,
                 locales,
                 // do not use ..Default::default() here,
@@ -751,6 +928,23 @@ pub struct SearchHit {
     pub ranking_score: Option<f64>,
     #[serde(default, rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
     pub ranking_score_details: Option<serde_json::Map<String, serde_json::Value>>,
+    #[serde(default, rename = "_semanticChunk", skip_serializing_if = "Option::is_none")]
+    pub semantic_chunk: Option<SemanticChunk>,
+}
+
+/// The chunk of a document that is the most similar to the query, for embedders that split documents into chunks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SemanticChunk {
+    /// Index of the chunk in the document.
+    pub index: usize,
+    /// Byte offset of the start of the chunk.
+    pub start: usize,
+    /// Byte offset of the end of the chunk, excluded.
+    pub end: usize,
+    /// Field containing the chunk, or `None` if the chunk is part of the rendered document template.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
 }
 
 #[derive(Serialize, Clone, PartialEq, ToSchema)]
@@ -767,6 +961,10 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_buckets: Option<BTreeMap<String, Vec<FacetBucket>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hierarchical_facet_distribution: Option<BTreeMap<String, Vec<FacetHierarchyLevel>>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
@@ -787,6 +985,8 @@ impl fmt::Debug for SearchResult {
             hits_info,
             facet_distribution,
             facet_stats,
+            facet_buckets,
+            hierarchical_facet_distribution,
             semantic_hit_count,
             degraded,
             used_negative_operator,
@@ -810,6 +1010,12 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(facet_buckets) = facet_buckets {
+            debug.field("facet_buckets", &facet_buckets);
+        }
+        if let Some(hierarchical_facet_distribution) = hierarchical_facet_distribution {
+            debug.field("hierarchical_facet_distribution", &hierarchical_facet_distribution);
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
@@ -854,6 +1060,123 @@ pub struct FacetStats {
     pub max: f64,
 }
 
+/// How the values of a number facet are grouped into buckets.
+///
+/// Either a histogram of buckets of `interval` width, starting at the multiples of `interval`,
+/// or the user-defined `ranges`.
+#[derive(Debug, Clone, PartialEq, Deserr, Serialize, ToSchema)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct FacetBucketsQuery {
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<f64>,
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranges: Option<Vec<FacetRangeQuery>>,
+}
+
+/// A range of facet values, `from` is included and `to` is excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Deserr, Serialize, ToSchema)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetRangeQuery {
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+}
+
+impl FacetBucketsQuery {
+    fn to_milli(&self, facet: &str) -> Result<milli::FacetBuckets, String> {
+        match (self.interval, &self.ranges) {
+            (Some(interval), None) => {
+                if !interval.is_finite() || interval <= 0.0 {
+                    return Err(format!(
+                        "The `interval` of the buckets of facet `{facet}` must be a positive number, but found `{interval}`."
+                    ));
+                }
+                Ok(milli::FacetBuckets::Histogram { interval })
+            }
+            (None, Some(ranges)) => {
+                let ranges = ranges
+                    .iter()
+                    .enumerate()
+                    .map(|(i, &FacetRangeQuery { from, to })| match (from, to) {
+                        (Some(from), Some(to)) if from >= to => Err(format!(
+                            "The range `{i}` of the buckets of facet `{facet}` is empty: `from` must be lower than `to`, but found `{from}` and `{to}`."
+                        )),
+                        _ => Ok(milli::FacetRange { from, to }),
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(milli::FacetBuckets::Ranges(ranges))
+            }
+            _ => Err(format!(
+                "The buckets of facet `{facet}` must be defined with either an `interval` or a list of `ranges`."
+            )),
+        }
+    }
+}
+
+/// A tree of facet values where each level is stored in its own attribute,
+/// like `lvl0: "Electronics"` and `lvl1: "Electronics > Phones"`.
+#[derive(Debug, Clone, PartialEq, Deserr, Serialize, ToSchema)]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+#[schema(rename_all = "camelCase")]
+pub struct HierarchicalFacetQuery {
+    /// The attributes of the levels, from the root to the leaves.
+    pub attributes: Vec<String>,
+    #[deserr(default = DEFAULT_HIERARCHY_SEPARATOR())]
+    #[schema(default = DEFAULT_HIERARCHY_SEPARATOR)]
+    pub separator: String,
+    /// The path of the selected branch, whose children are counted at each level.
+    #[deserr(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected_path: Option<String>,
+}
+
+impl HierarchicalFacetQuery {
+    fn to_milli(&self, facet: &str) -> Result<milli::FacetHierarchy, String> {
+        if self.attributes.is_empty() {
+            return Err(format!(
+                "The hierarchical facet `{facet}` must have at least one attribute in `attributes`."
+            ));
+        }
+        if self.separator.is_empty() {
+            return Err(format!(
+                "The `separator` of the hierarchical facet `{facet}` must not be empty."
+            ));
+        }
+        Ok(milli::FacetHierarchy {
+            levels: self.attributes.clone(),
+            separator: self.separator.clone(),
+            selected_path: self.selected_path.clone(),
+        })
+    }
+}
+
+/// The distribution of the values of a level of a hierarchical facet.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct FacetHierarchyLevel {
+    pub attribute: String,
+    #[schema(value_type = BTreeMap<String, u64>)]
+    pub values: IndexMap<String, u64>,
+}
+
+/// The number of hits with a facet value in a range.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, ToSchema)]
+pub struct FacetBucket {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+    pub count: u64,
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FacetSearchResult {
@@ -882,6 +1205,93 @@ pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
     }
 }
 
+/// The documents pinned and hidden by the rules of the index matching the query of a search.
+#[derive(Default)]
+struct AppliedQueryRules {
+    /// The pinned documents matching the filter of the search, in the order they must be returned.
+    pinned: Vec<DocumentId>,
+    /// The documents that must never be returned.
+    hidden: roaring::RoaringBitmap,
+}
+
+impl AppliedQueryRules {
+    /// The documents the search must not return by itself, they are either hidden or pinned.
+    fn excluded_documents(&self) -> Option<roaring::RoaringBitmap> {
+        if self.pinned.is_empty() && self.hidden.is_empty() {
+            return None;
+        }
+        let mut excluded = self.hidden.clone();
+        excluded.extend(self.pinned.iter().copied());
+        Some(excluded)
+    }
+
+    /// Returns the pinned documents that belong to the page starting at `offset` with `limit` hits,
+    /// along with the offset and limit of the search that fills the rest of the page.
+    fn paginate(&self, offset: usize, limit: usize) -> (&[DocumentId], usize, usize) {
+        let pinned_len = self.pinned.len();
+        let pinned_page =
+            &self.pinned[min(offset, pinned_len)..min(offset.saturating_add(limit), pinned_len)];
+        (pinned_page, offset.saturating_sub(pinned_len), limit - pinned_page.len())
+    }
+}
+
+/// Adds the filters of the rules matching the query to the filter of the query,
+/// and returns the documents pinned and hidden by these rules.
+fn apply_query_rules(
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &mut SearchQuery,
+    features: RoFeatures,
+) -> Result<AppliedQueryRules, ResponseError> {
+    let Some(rules) = index.query_rules(rtxn)? else {
+        return Ok(Default::default());
+    };
+    let q = query.q.as_deref().unwrap_or_default();
+    let rules: Vec<_> = rules.into_iter().filter(|rule| rule.matches(q)).collect();
+    if rules.is_empty() {
+        return Ok(Default::default());
+    }
+
+    let external_documents_ids = index.external_documents_ids();
+    let mut pinned = Vec::new();
+    let mut hidden = roaring::RoaringBitmap::new();
+    for rule in rules {
+        if let Some(filter) = rule.filter {
+            add_search_rules(
+                &mut query.filter,
+                IndexSearchRules { filter: Some(Value::String(filter)) },
+            );
+        }
+        for external_id in rule.hidden {
+            if let Some(docid) = external_documents_ids.get(rtxn, &external_id)? {
+                hidden.insert(docid);
+            }
+        }
+        for external_id in rule.pinned {
+            if let Some(docid) = external_documents_ids.get(rtxn, &external_id)? {
+                if !pinned.contains(&docid) {
+                    pinned.push(docid);
+                }
+            }
+        }
+    }
+
+    // a pinned document must not escape the hidden documents nor the filter of the search
+    pinned.retain(|docid| !hidden.contains(*docid));
+    let filter = match &query.filter {
+        Some(filter) if !pinned.is_empty() => {
+            parse_filter(filter, Code::InvalidSearchFilter, features)?
+        }
+        _ => None,
+    };
+    if let Some(filter) = filter {
+        let filtered = filter.evaluate(rtxn, index)?;
+        pinned.retain(|docid| filtered.contains(*docid));
+    }
+
+    Ok(AppliedQueryRules { pinned, hidden })
+}
+
 fn prepare_search<'t>(
     index: &'t Index,
     rtxn: &'t RoTxn,
@@ -889,7 +1299,7 @@ fn prepare_search<'t>(
     search_kind: &SearchKind,
     time_budget: TimeBudget,
     features: RoFeatures,
-) -> Result<(milli::Search<'t>, bool, usize, usize), ResponseError> {
+) -> Result<(milli::Search<'t>, bool, usize, usize, usize), ResponseError> {
     let mut search = index.search(rtxn);
     search.time_budget(time_budget);
     if let Some(ranking_score_threshold) = query.ranking_score_threshold {
@@ -906,7 +1316,22 @@ fn prepare_search<'t>(
                 search.query(q);
             }
         }
-        SearchKind::SemanticOnly { embedder_name, embedder, quantized } => {
+        SearchKind::SemanticOnly { embedder_name, embedder, quantization: _ }
+            if embedder.is_sparse() =>
+        {
+            let span = tracing::trace_span!(target: "search::vector", "embed_one");
+            let _entered = span.enter();
+
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+
+            let sparse_vector = embedder
+                .embed_search_sparse(query.q.as_ref().unwrap(), Some(deadline))
+                .map_err(milli::vector::Error::from)
+                .map_err(milli::Error::from)?;
+
+            search.sparse_semantic(embedder_name.clone(), embedder.clone(), Some(sparse_vector));
+        }
+        SearchKind::SemanticOnly { embedder_name, embedder, quantization } => {
             let vector = match query.vector.clone() {
                 Some(vector) => vector,
                 None => {
@@ -915,26 +1340,36 @@ fn prepare_search<'t>(
 
                     let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
 
-                    embedder
-                        .embed_search(query.q.as_ref().unwrap(), Some(deadline))
-                        .map_err(milli::vector::Error::from)
-                        .map_err(milli::Error::from)?
+                    let q = query.q.as_ref().unwrap();
+                    // the embeddings of a multi-vector embedder are concatenated in the vector
+                    let embedding = if embedder.is_multi_vector() {
+                        embedder
+                            .embed_search_multi(q, Some(deadline))
+                            .map(|embeddings| embeddings.concat())
+                    } else {
+                        embedder.embed_search(q, Some(deadline))
+                    };
+                    embedding.map_err(milli::vector::Error::from).map_err(milli::Error::from)?
                 }
             };
 
-            search.semantic(embedder_name.clone(), embedder.clone(), *quantized, Some(vector));
+            search.semantic(embedder_name.clone(), embedder.clone(), *quantization, Some(vector));
         }
-        SearchKind::Hybrid { embedder_name, embedder, quantized, semantic_ratio: _ } => {
+        SearchKind::Hybrid { embedder_name, embedder, quantization, semantic_ratio: _ } => {
             if let Some(q) = &query.q {
                 search.query(q);
             }
             // will be embedded in hybrid search if necessary
-            search.semantic(
-                embedder_name.clone(),
-                embedder.clone(),
-                *quantized,
-                query.vector.clone(),
-            );
+            if embedder.is_sparse() {
+                search.sparse_semantic(embedder_name.clone(), embedder.clone(), None);
+            } else {
+                search.semantic(
+                    embedder_name.clone(),
+                    embedder.clone(),
+                    *quantization,
+                    query.vector.clone(),
+                );
+            }
         }
     }
 
@@ -952,6 +1387,7 @@ fn prepare_search<'t>(
         .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
 
     search.exhaustive_number_hits(is_finite_pagination);
+    search.exact_vector_search(query.hybrid.as_ref().is_some_and(|hybrid| hybrid.exact));
     search.scoring_strategy(
         if query.show_ranking_score
             || query.show_ranking_score_details
@@ -999,18 +1435,26 @@ fn prepare_search<'t>(
         search.sort_criteria(sort);
     }
 
+    if let Some(ref boost) = query.boost {
+        let boost = boost.parse().map_err(|error: BoostError| {
+            ResponseError::from_msg(error.to_string(), Code::InvalidSearchBoost)
+        })?;
+        search.boost(boost);
+    }
+
     if let Some(ref locales) = query.locales {
         search.locales(locales.iter().copied().map(Into::into).collect());
     }
 
-    Ok((search, is_finite_pagination, max_total_hits, offset))
+    Ok((search, is_finite_pagination, max_total_hits, offset, limit))
 }
 
 pub fn perform_search(
     index_uid: String,
     index: &Index,
-    query: SearchQuery,
+    mut query: SearchQuery,
     search_kind: SearchKind,
+    reranker: Option<SearchReranker>,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
 ) -> Result<SearchResult, ResponseError> {
@@ -1021,21 +1465,60 @@ pub fn perform_search(
         None => TimeBudget::default(),
     };
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
+    let rules = apply_query_rules(index, &rtxn, &mut query, features)?;
+
+    let disjunctive_candidates = match &query.facets {
+        Some(facets) if query.disjunctive_facets => disjunctive_facets_candidates(
+            index,
+            &rtxn,
+            &query,
+            facets,
+            &rules.hidden,
+            &search_kind,
+            time_budget.clone(),
+            features,
+        )?,
+        _ => Default::default(),
+    };
+
+    let (mut search, is_finite_pagination, max_total_hits, offset, limit) =
         prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
 
+    if let Some(reranker) = reranker {
+        reranker.apply(&mut search);
+    }
+
+    let semantic_embedder_name = match &search_kind {
+        SearchKind::KeywordOnly => None,
+        SearchKind::SemanticOnly { embedder_name, .. }
+        | SearchKind::Hybrid { embedder_name, .. } => Some(embedder_name.clone()),
+    };
+
+    // The pinned documents come before all the others: the ones that belong to the requested
+    // page are inserted at its start, and the window of the search is shifted accordingly.
+    let (pinned_page, search_offset, search_limit) = rules.paginate(offset, limit);
+    if let Some(excluded) = rules.excluded_documents() {
+        search.excluded_documents(excluded);
+        search.offset(search_offset);
+        search.limit(search_limit);
+    }
+
     let (
         milli::SearchResult {
-            documents_ids,
+            mut documents_ids,
             matching_words,
-            candidates,
-            document_scores,
+            mut candidates,
+            mut document_scores,
             degraded,
             used_negative_operator,
         },
         semantic_hit_count,
     ) = search_from_kind(index_uid, search_kind, search)?;
 
+    documents_ids.splice(0..0, pinned_page.iter().copied());
+    document_scores.splice(0..0, pinned_page.iter().map(|_| Vec::new()));
+    candidates.extend(rules.pinned.iter().copied());
+
     let SearchQuery {
         q,
         limit,
@@ -1052,6 +1535,10 @@ pub fn perform_search(
         show_ranking_score_details,
         sort,
         facets,
+        // already used to compute the candidates of the disjunctive facets
+        disjunctive_facets: _,
+        facet_buckets,
+        hierarchical_facets,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
@@ -1059,11 +1546,13 @@ pub fn perform_search(
         // already used in prepare_search
         vector: _,
         hybrid: _,
+        rerank: _,
         offset: _,
         ranking_score_threshold: _,
         matching_strategy: _,
         attributes_to_search_on: _,
         filter: _,
+        boost: _,
         distinct: _,
     } = query;
 
@@ -1083,7 +1572,7 @@ pub fn perform_search(
         locales: locales.map(|l| l.iter().copied().map(Into::into).collect()),
     };
 
-    let documents = make_hits(
+    let mut documents = make_hits(
         index,
         &rtxn,
         format,
@@ -1091,6 +1580,16 @@ pub fn perform_search(
         documents_ids.iter().copied().zip(document_scores.iter()),
     )?;
 
+    if let Some(embedder_name) = semantic_embedder_name {
+        insert_semantic_chunks(
+            index,
+            &rtxn,
+            &embedder_name,
+            &mut documents,
+            documents_ids.iter().copied().zip(document_scores.iter()),
+        )?;
+    }
+
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
         let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -1109,12 +1608,48 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
+    let facet_buckets = facet_buckets
+        .map(|facet_buckets| {
+            compute_facet_buckets(&facet_buckets, index, &rtxn, candidates.clone(), Route::Search)
+        })
+        .transpose()?;
+
+    let hierarchical_facet_distribution = hierarchical_facets
+        .map(|hierarchical_facets| {
+            compute_facet_hierarchies(
+                &hierarchical_facets,
+                index,
+                &rtxn,
+                candidates.clone(),
+                Route::Search,
+            )
+        })
+        .transpose()?;
+
     let (facet_distribution, facet_stats) = facets
         .map(move |facets| {
-            compute_facet_distribution_stats(&facets, index, &rtxn, candidates, Route::Search)
+            let facets: Vec<_> = facets
+                .into_iter()
+                .filter(|facet| !disjunctive_candidates.contains_key(facet))
+                .collect();
+            let mut computed =
+                compute_facet_distribution_stats(&facets, index, &rtxn, candidates, Route::Search)?;
+            for (facet, candidates) in disjunctive_candidates {
+                let ComputedFacets { distribution, stats, buckets: _ } =
+                    compute_facet_distribution_stats(
+                        &[facet],
+                        index,
+                        &rtxn,
+                        candidates,
+                        Route::Search,
+                    )?;
+                computed.distribution.extend(distribution);
+                computed.stats.extend(stats);
+            }
+            Ok::<_, ResponseError>(computed)
         })
         .transpose()?
-        .map(|ComputedFacets { distribution, stats }| (distribution, stats))
+        .map(|ComputedFacets { distribution, stats, buckets: _ }| (distribution, stats))
         .unzip();
 
     let result = SearchResult {
@@ -1124,6 +1659,8 @@ pub fn perform_search(
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        facet_buckets,
+        hierarchical_facet_distribution,
         degraded,
         used_negative_operator,
         semantic_hit_count,
@@ -1136,6 +1673,8 @@ pub struct ComputedFacets {
     #[schema(value_type = BTreeMap<String, BTreeMap<String, u64>>)]
     pub distribution: BTreeMap<String, IndexMap<String, u64>>,
     pub stats: BTreeMap<String, FacetStats>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub buckets: BTreeMap<String, Vec<FacetBucket>>,
 }
 
 pub enum Route {
@@ -1185,7 +1724,191 @@ fn compute_facet_distribution_stats<S: AsRef<str>>(
         })?;
     let stats = facet_distribution.compute_stats()?;
     let stats = stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect();
-    Ok(ComputedFacets { distribution, stats })
+    Ok(ComputedFacets { distribution, stats, buckets: Default::default() })
+}
+
+/// Returns the candidates on which to compute the distribution of the disjunctive facets.
+///
+/// The distribution of a disjunctive facet ignores the conjuncts of the filter that are only
+/// about this facet, so that selecting a value of a facet doesn't hide the other values of the
+/// same facet. The facets that don't have such conjuncts are not returned, their distribution is
+/// computed on the candidates of the search.
+fn disjunctive_facets_candidates(
+    index: &Index,
+    rtxn: &RoTxn,
+    query: &SearchQuery,
+    facets: &[String],
+    hidden: &roaring::RoaringBitmap,
+    search_kind: &SearchKind,
+    time_budget: TimeBudget,
+    features: RoFeatures,
+) -> Result<BTreeMap<String, roaring::RoaringBitmap>, ResponseError> {
+    let filter = match &query.filter {
+        Some(filter) => parse_filter(filter, Code::InvalidSearchFilter, features)?,
+        None => None,
+    };
+    let Some(filter) = filter else {
+        return Ok(Default::default());
+    };
+
+    let conjuncts = filter.conjuncts();
+    let conjuncts_by_facet: Vec<(&String, Vec<usize>)> = facets
+        .iter()
+        .filter_map(|facet| {
+            let facet_conjuncts: Vec<_> = conjuncts
+                .iter()
+                .enumerate()
+                .filter(|(_, conjunct)| conjunct.only_applies_to(facet))
+                .map(|(i, _)| i)
+                .collect();
+            (!facet_conjuncts.is_empty()).then_some((facet, facet_conjuncts))
+        })
+        .collect();
+    if conjuncts_by_facet.is_empty() {
+        return Ok(Default::default());
+    }
+
+    // The documents matching the query regardless of the filter, and the documents matching each
+    // conjunct are computed only once and shared by all the facets.
+    let universe = match search_kind {
+        // All the documents are candidates of a semantic or hybrid search, there is no need to
+        // embed the query again to know it.
+        SearchKind::SemanticOnly { .. } | SearchKind::Hybrid { .. } => {
+            index.documents_ids(rtxn).map_err(milli::Error::from)? - hidden
+        }
+        SearchKind::KeywordOnly => {
+            let mut search = index.search(rtxn);
+            search.time_budget(time_budget);
+            if let Some(q) = &query.q {
+                search.query(q);
+            }
+            if let Some(ref searchable) = query.attributes_to_search_on {
+                search.searchable_attributes(searchable);
+            }
+            search.terms_matching_strategy(query.matching_strategy.into());
+            if let Some(ref locales) = query.locales {
+                search.locales(locales.iter().copied().map(Into::into).collect());
+            }
+            search.limit(0);
+            search.excluded_documents(hidden.clone());
+            search.execute_for_candidates(false)?
+        }
+    };
+    let conjuncts_docids = conjuncts
+        .iter()
+        .map(|conjunct| conjunct.evaluate(rtxn, index))
+        .collect::<milli::Result<Vec<_>>>()?;
+
+    Ok(conjuncts_by_facet
+        .into_iter()
+        .map(|(facet, facet_conjuncts)| {
+            let mut candidates = universe.clone();
+            for (i, docids) in conjuncts_docids.iter().enumerate() {
+                if !facet_conjuncts.contains(&i) {
+                    candidates &= docids;
+                }
+            }
+            (facet.clone(), candidates)
+        })
+        .collect())
+}
+
+fn compute_facet_buckets(
+    facet_buckets: &BTreeMap<String, FacetBucketsQuery>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: roaring::RoaringBitmap,
+    route: Route,
+) -> Result<BTreeMap<String, Vec<FacetBucket>>, ResponseError> {
+    let code = match route {
+        Route::MultiSearch => Code::InvalidMultiSearchFacetBuckets,
+        Route::Search | Route::Similar => Code::InvalidSearchFacetBuckets,
+    };
+
+    let buckets = facet_buckets
+        .iter()
+        .map(|(facet, buckets)| Ok((facet, buckets.to_milli(facet)?)))
+        .collect::<Result<Vec<_>, String>>()
+        .map_err(|message| ResponseError::from_msg(message, code))?;
+
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    let buckets = index
+        .facets_distribution(rtxn)
+        .max_values_per_facet(max_values_by_facet)
+        .buckets(buckets)
+        .candidates(candidates)
+        .compute_buckets()
+        .map_err(|error| match error {
+            error @ milli::Error::UserError(milli::UserError::InvalidFacetsDistribution {
+                ..
+            }) => ResponseError::from_msg(error.to_string(), code),
+            error => error.into(),
+        })?;
+
+    Ok(buckets
+        .into_iter()
+        .map(|(facet, buckets)| {
+            let buckets = buckets
+                .into_iter()
+                .map(|(milli::FacetRange { from, to }, count)| FacetBucket { from, to, count })
+                .collect();
+            (facet, buckets)
+        })
+        .collect())
+}
+
+fn compute_facet_hierarchies(
+    hierarchical_facets: &BTreeMap<String, HierarchicalFacetQuery>,
+    index: &Index,
+    rtxn: &RoTxn,
+    candidates: roaring::RoaringBitmap,
+    route: Route,
+) -> Result<BTreeMap<String, Vec<FacetHierarchyLevel>>, ResponseError> {
+    let code = match route {
+        Route::MultiSearch => Code::InvalidMultiSearchHierarchicalFacets,
+        Route::Search | Route::Similar => Code::InvalidSearchHierarchicalFacets,
+    };
+
+    let hierarchies = hierarchical_facets
+        .iter()
+        .map(|(facet, hierarchy)| Ok((facet, hierarchy.to_milli(facet)?)))
+        .collect::<Result<Vec<_>, String>>()
+        .map_err(|message| ResponseError::from_msg(message, code))?;
+
+    let max_values_by_facet = index
+        .max_values_per_facet(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+
+    let hierarchies = index
+        .facets_distribution(rtxn)
+        .max_values_per_facet(max_values_by_facet)
+        .hierarchies(hierarchies)
+        .candidates(candidates)
+        .compute_hierarchies()
+        .map_err(|error| match error {
+            error @ milli::Error::UserError(milli::UserError::InvalidFacetsDistribution {
+                ..
+            }) => ResponseError::from_msg(error.to_string(), code),
+            error => error.into(),
+        })?;
+
+    Ok(hierarchies
+        .into_iter()
+        .map(|(facet, levels)| {
+            let levels = levels
+                .into_iter()
+                .map(|(attribute, values)| FacetHierarchyLevel { attribute, values })
+                .collect();
+            (facet, levels)
+        })
+        .collect())
 }
 
 pub fn search_from_kind(
@@ -1476,12 +2199,61 @@ impl<'a> HitMaker<'a> {
             matches_position,
             ranking_score_details,
             ranking_score,
+            semantic_chunk: None,
         };
 
         Ok(hit)
     }
 }
 
+/// Reports the best matching chunk of the hits, for embedders that split documents into chunks.
+fn insert_semantic_chunks<'a>(
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+    embedder_name: &str,
+    hits: &mut [SearchHit],
+    documents_ids_scores: impl Iterator<Item = (u32, &'a Vec<ScoreDetails>)>,
+) -> milli::Result<()> {
+    let Some(config) =
+        index.embedding_configs(rtxn)?.into_iter().find(|config| config.name == embedder_name)
+    else {
+        return Ok(());
+    };
+    let Some(chunking) = config.config.prompt.chunking else {
+        return Ok(());
+    };
+    let Some(embedder_id) = index.embedder_category_id.get(rtxn, embedder_name)? else {
+        return Ok(());
+    };
+    // the chunks are stored when the documents are embedded, there is no need to render them again
+    let chunk_store = milli::vector::chunking::ChunkStore::new(index, embedder_id);
+
+    for (hit, (id, score)) in hits.iter_mut().zip(documents_ids_scores) {
+        // the vectors provided by the user are not chunks of the document
+        if config.user_provided.contains(id) {
+            continue;
+        }
+        let Some(vector_index) = score.iter().find_map(|details| match details {
+            ScoreDetails::Vector(vector) => vector.vector_index,
+            _ => None,
+        }) else {
+            continue;
+        };
+        let Some(chunks) = chunk_store.chunks(rtxn, id)? else {
+            continue;
+        };
+        if let Some(range) = chunks.into_iter().nth(vector_index.into()) {
+            hit.semantic_chunk = Some(SemanticChunk {
+                index: vector_index.into(),
+                start: range.start,
+                end: range.end,
+                field: chunking.field.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
 fn make_hits<'a>(
     index: &Index,
     rtxn: &RoTxn<'_>,
@@ -1512,9 +2284,10 @@ fn make_hits<'a>(
 
 pub fn perform_facet_search(
     index: &Index,
-    search_query: SearchQuery,
+    mut search_query: SearchQuery,
     facet_query: Option<String>,
     facet_name: String,
+    facet_prefix: Option<String>,
     search_kind: SearchKind,
     features: RoFeatures,
     locales: Option<Vec<Language>>,
@@ -1548,8 +2321,15 @@ pub fn perform_facet_search(
             .collect()
     });
 
-    let (search, _, _, _) =
+    // The facet values are counted on the documents the search would return, so the pinned
+    // documents count even when they don't match the query, and the hidden ones never count.
+    let rules = apply_query_rules(index, &rtxn, &mut search_query, features)?;
+
+    let (mut search, _, _, _, _) =
         prepare_search(index, &rtxn, &search_query, &search_kind, time_budget, features)?;
+    if !rules.hidden.is_empty() {
+        search.excluded_documents(rules.hidden);
+    }
     let mut facet_search = SearchForFacetValues::new(
         facet_name,
         search,
@@ -1558,9 +2338,15 @@ pub fn perform_facet_search(
     if let Some(facet_query) = &facet_query {
         facet_search.query(facet_query);
     }
+    if let Some(facet_prefix) = facet_prefix {
+        facet_search.prefix(facet_prefix);
+    }
     if let Some(max_facets) = index.max_values_per_facet(&rtxn)? {
         facet_search.max_values(max_facets as usize);
     }
+    if !rules.pinned.is_empty() {
+        facet_search.pinned_documents(rules.pinned.into_iter().collect());
+    }
 
     if let Some(locales) = locales {
         facet_search.locales(locales);
@@ -1578,7 +2364,7 @@ pub fn perform_similar(
     query: SimilarQuery,
     embedder_name: String,
     embedder: Arc<Embedder>,
-    quantized: bool,
+    quantization: VectorQuantization,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
 ) -> Result<SimilarResult, ResponseError> {
@@ -1620,7 +2406,7 @@ pub fn perform_similar(
         &rtxn,
         embedder_name,
         embedder,
-        quantized,
+        quantization,
     );
 
     if let Some(ref filter) = query.filter {
@@ -1697,11 +2483,27 @@ pub fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
         // TODO: TAMO: milli encountered an internal error, what do we want to do?
         let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
-        let geo_point = &document.get("_geo").unwrap_or(&json!(null));
-        if let Some((lat, lng)) =
-            extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
-        {
-            let distance = milli::distance_between_two_points(&base, &[lat, lng]);
+        let geo = document.get("_geo").unwrap_or(&Value::Null);
+        // a document with multiple locations is sorted by its nearest point
+        let geo_points = match geo {
+            Value::Array(geo_points) => geo_points.as_slice(),
+            geo_point => std::slice::from_ref(geo_point),
+        };
+        let nearest = geo_points
+            .iter()
+            .filter_map(|geo_point| {
+                let (lat, lng) = extract_geo_value(&geo_point["lat"])
+                    .zip(extract_geo_value(&geo_point["lng"]))?;
+                let distance = milli::distance_between_two_points(&base, &[lat, lng]);
+                Some((distance, geo_point))
+            })
+            .min_by(|(left, _), (right, _)| left.total_cmp(right));
+
+        if let Some((distance, geo_point)) = nearest {
+            // with several locations, the point the distance was computed from is reported as well
+            if geo.is_array() {
+                document.insert("_geoPoint".to_string(), geo_point.clone());
+            }
             document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
         }
     }
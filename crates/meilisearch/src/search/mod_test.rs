@@ -112,3 +112,41 @@ fn test_insert_geo_distance_with_coords_as_string() {
     insert_geo_distance(sorters, &mut document);
     assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
 }
+
+#[test]
+fn test_insert_geo_distance_with_multiple_points() {
+    let value: Document = serde_json::from_str(
+        r#"{
+          "_geo": [
+            { "lat": 50.629973371633746, "lng": 3.0569447399419567 },
+            { "lat": 48.8566, "lng": 2.3522 }
+          ],
+          "id": "1"
+        }"#,
+    )
+    .unwrap();
+
+    // the distance is computed from the nearest point, which is reported in `_geoPoint`
+    let sorters = &["_geoPoint(48.8566,2.3522):asc".to_string()];
+    let mut document = value.clone();
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+    assert_eq!(document.get("_geoPoint"), Some(&json!({ "lat": 48.8566, "lng": 2.3522 })));
+
+    let sorters = &["_geoPoint(50.629973371633746, 3.0569447399419567):desc".to_string()];
+    let mut document = value;
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+    assert_eq!(
+        document.get("_geoPoint"),
+        Some(&json!({ "lat": 50.629973371633746, "lng": 3.0569447399419567 }))
+    );
+
+    // a single point only reports the distance
+    let value: Document = serde_json::from_str(r#"{ "_geo": { "lat": 50, "lng": 3 } }"#).unwrap();
+    let sorters = &["_geoPoint(50,3):asc".to_string()];
+    let mut document = value;
+    insert_geo_distance(sorters, &mut document);
+    assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+    assert_eq!(document.get("_geoPoint"), None);
+}
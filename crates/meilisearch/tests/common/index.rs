@@ -515,6 +515,11 @@ impl<State> Index<'_, State> {
         self.service.get(url).await
     }
 
+    pub async fn recall(&self, query: &str) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/recall{}", urlencode(self.uid.as_ref()), query);
+        self.service.get(url).await
+    }
+
     pub async fn facet_search(&self, query: Value) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/facet-search", urlencode(self.uid.as_ref()));
         self.service.post_encoded(url, query, self.encoder).await
@@ -388,6 +388,14 @@ impl<State> Server<State> {
         self.service.post("/swap-indexes", value).await
     }
 
+    pub async fn create_task_group(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/task-groups", value).await
+    }
+
+    pub async fn get_task_group(&self, group_uid: u64) -> (Value, StatusCode) {
+        self.service.get(format!("/task-groups/{group_uid}")).await
+    }
+
     pub async fn cancel_tasks(&self, value: &str) -> (Value, StatusCode) {
         self.service.post(format!("/tasks/cancel?{}", value), json!(null)).await
     }
@@ -441,6 +449,26 @@ impl<State> Server<State> {
     pub async fn get_network(&self) -> (Value, StatusCode) {
         self.service.get("/network").await
     }
+
+    pub async fn create_webhook(&self, value: Value) -> (Value, StatusCode) {
+        self.service.post("/webhooks", value).await
+    }
+
+    pub async fn get_webhooks(&self) -> (Value, StatusCode) {
+        self.service.get("/webhooks").await
+    }
+
+    pub async fn update_webhook(&self, uuid: &str, value: Value) -> (Value, StatusCode) {
+        self.service.patch(format!("/webhooks/{uuid}"), value).await
+    }
+
+    pub async fn delete_webhook(&self, uuid: &str) -> (Value, StatusCode) {
+        self.service.delete(format!("/webhooks/{uuid}")).await
+    }
+
+    pub async fn get_webhook_deliveries(&self, uuid: &str) -> (Value, StatusCode) {
+        self.service.get(format!("/webhooks/{uuid}/deliveries")).await
+    }
 }
 
 pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
@@ -459,6 +487,7 @@ pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
             skip_index_budget: true,
             // Having 2 threads makes the tests way faster
             max_indexing_threads: MaxThreads::from_str("2").unwrap(),
+            experimental_embedding_cache_path: None,
         },
         experimental_enable_metrics: false,
         ..Parser::parse_from(None as Option<&str>)
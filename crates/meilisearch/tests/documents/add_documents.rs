@@ -2106,7 +2106,7 @@ async fn add_documents_invalid_geo_field() {
         "indexedDocuments": 0
       },
       "error": {
-        "message": "Index `test`: The `_geo` field in the document with the id: `\"11\"` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields but instead got `\"foobar\"`.",
+        "message": "Index `test`: The `_geo` field in the document with the id: `\"11\"` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields or an array of such objects but instead got `\"foobar\"`.",
         "code": "invalid_document_geo_field",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_document_geo_field"
@@ -2986,3 +2986,108 @@ async fn dry_register_file() {
     "###);
     snapshot!(code, @"404 Not Found");
 }
+
+#[actix_rt::test]
+async fn add_documents_with_priority() {
+    let server = Server::new().await;
+    let index = server.index("tamo");
+
+    let documents = r#"{ "id": "12", "doggo": "kefir" }"#;
+
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json"), ("TaskPriority", "high")],
+            "",
+        )
+        .await;
+    snapshot!(response, @r###"
+    {
+      "taskUid": 0,
+      "indexUid": "tamo",
+      "status": "enqueued",
+      "type": "documentAdditionOrUpdate",
+      "priority": "high",
+      "enqueuedAt": "[date]"
+    }
+    "###);
+    snapshot!(code, @"202 Accepted");
+
+    let response = index.wait_task(response.uid()).await.succeeded();
+    snapshot!(response["priority"], @r###""high""###);
+
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json"), ("TaskPriority", "urgent")],
+            "",
+        )
+        .await;
+    snapshot!(response, @r###"
+    {
+      "message": "`urgent` is not a valid task priority. Available priorities are `low`, `normal`, `high`.",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+    snapshot!(code, @"400 Bad Request");
+}
+
+#[actix_rt::test]
+async fn add_documents_with_run_at() {
+    let server = Server::new().await;
+    let index = server.index("tamo");
+
+    let documents = r#"{ "id": "12", "doggo": "kefir" }"#;
+
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json"), ("TaskRunAt", "2100-01-01T00:00:00Z")],
+            "",
+        )
+        .await;
+    snapshot!(response, @r###"
+    {
+      "taskUid": 0,
+      "indexUid": "tamo",
+      "status": "enqueued",
+      "type": "documentAdditionOrUpdate",
+      "enqueuedAt": "[date]",
+      "runAt": "2100-01-01T00:00:00Z"
+    }
+    "###);
+    snapshot!(code, @"202 Accepted");
+
+    let (response, code) = server.tasks_filter("afterRunAt=2099-12-31&statuses=enqueued").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"][0]["uid"], @"0");
+    snapshot!(response["results"][0]["runAt"], @r###""2100-01-01T00:00:00Z""###);
+    let (response, _code) = server.tasks_filter("beforeRunAt=2099-12-31").await;
+    snapshot!(response["results"], @"[]");
+
+    // The task can be canceled until it is due.
+    let (response, _code) = server.cancel_tasks("afterRunAt=2099-12-31").await;
+    server.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = server.get_task(0).await;
+    snapshot!(response["status"], @r###""canceled""###);
+
+    // The batch canceling the task matches its run date.
+    let (response, code) = server.batches_filter("afterRunAt=2099-12-31").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["results"][0]["uid"], @"0");
+    snapshot!(response["total"], @"1");
+    let (response, _code) = server.batches_filter("beforeRunAt=2099-12-31").await;
+    snapshot!(response["results"], @"[]");
+
+    let (response, code) = index
+        .raw_add_documents(
+            documents,
+            vec![("Content-Type", "application/json"), ("TaskRunAt", "tomorrow")],
+            "",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""bad_request""###);
+}
@@ -102,7 +102,10 @@ async fn import_dump_v1_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -275,7 +278,10 @@ async fn import_dump_v1_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -447,7 +453,10 @@ async fn import_dump_v1_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -605,7 +614,10 @@ async fn import_dump_v2_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -775,7 +787,10 @@ async fn import_dump_v2_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -944,7 +959,10 @@ async fn import_dump_v2_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1102,7 +1120,10 @@ async fn import_dump_v3_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1272,7 +1293,10 @@ async fn import_dump_v3_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1441,7 +1465,10 @@ async fn import_dump_v3_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1599,7 +1626,10 @@ async fn import_dump_v4_movie_raw() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1769,7 +1799,10 @@ async fn import_dump_v4_movie_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -1938,7 +1971,10 @@ async fn import_dump_v4_rubygems_with_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###
     );
@@ -2227,7 +2263,10 @@ async fn import_dump_v6_containing_experimental_features() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###);
 
@@ -2468,7 +2507,10 @@ async fn generate_and_import_dump_containing_vectors() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "###);
 
@@ -948,3 +948,57 @@ async fn distinct_facet_search_on_movies() {
     assert_eq!(code, 200, "{}", response);
     snapshot!(response["facetHits"], @r###"[{"value":"Blob","count":23}]"###);
 }
+
+#[actix_rt::test]
+async fn facet_search_within_branch() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "lvl1": "Electronics > Phones" },
+        { "id": 2, "lvl1": "Electronics > Phones" },
+        { "id": 3, "lvl1": "Electronics > Laptops" },
+        { "id": 4, "lvl1": "Books > Phone directories" },
+    ]);
+    let (response, code) = index.update_settings_filterable_attributes(json!(["lvl1"])).await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "lvl1", "facetBranch": "Electronics"})).await;
+    assert_eq!(code, 200, "{}", response);
+    snapshot!(response["facetHits"], @r###"[{"value":"Electronics > Laptops","count":1},{"value":"Electronics > Phones","count":2}]"###);
+
+    let (response, code) = index
+        .facet_search(
+            json!({"facetName": "lvl1", "facetBranch": "Electronics", "facetQuery": "pho"}),
+        )
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    snapshot!(response["facetHits"], @r###"[{"value":"Electronics > Phones","count":2}]"###);
+
+    // The typos are only allowed on the query, not on the branch.
+    let (response, code) = index
+        .facet_search(json!({"facetName": "lvl1", "facetBranch": "Electronics", "facetQuery": "l"}))
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    snapshot!(response["facetHits"], @r###"[{"value":"Electronics > Laptops","count":1}]"###);
+
+    let (response, code) = index
+        .facet_search(
+            json!({"facetName": "lvl1", "facetBranch": "Electronics", "facetQuery": "phnoes"}),
+        )
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    snapshot!(response["facetHits"], @r###"[{"value":"Electronics > Phones","count":2}]"###);
+
+    let (response, code) = index
+        .facet_search(
+            json!({"facetName": "lvl1", "facetBranch": "Electronic", "facetQuery": "phones"}),
+        )
+        .await;
+    assert_eq!(code, 200, "{}", response);
+    snapshot!(response["facetHits"], @"[]");
+}
@@ -120,6 +120,97 @@ async fn search_with_contains_filter() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 2);
 }
 
+#[actix_rt::test]
+async fn search_with_string_operators_filter() {
+    let temp = TempDir::new().unwrap();
+    let server = Server::new_with_options(Opt {
+        experimental_contains_filter: true,
+        ..default_settings(temp.path())
+    })
+    .await
+    .unwrap();
+    let index = server.index("movies");
+
+    index.update_settings(json!({"filterableAttributes": ["title"]})).await;
+
+    let documents = DOCUMENTS.clone();
+    let (request, _code) = index.add_documents(documents, None).await;
+    index.wait_task(request.uid()).await.succeeded();
+
+    for (filter, expected_hits) in [
+        ("title ENDS WITH world", 1),
+        ("title NOT ENDS WITH world", 4),
+        ("title MATCHES /^(shazam|escape)/", 2),
+        ("title MATCHES 'marvel$'", 1),
+        ("title STARTS WITH captain CASE SENSITIVE", 0),
+        ("title STARTS WITH Captain CASE SENSITIVE", 1),
+        ("title MATCHES /^[A-Z][a-z]+ [A-Z]/ CASE SENSITIVE", 2),
+    ] {
+        let (response, code) = index.search_post(json!({ "filter": filter })).await;
+        assert_eq!(code, 200, "{}", response);
+        assert_eq!(response["hits"].as_array().unwrap().len(), expected_hits, "{filter}");
+    }
+}
+
+#[actix_rt::test]
+async fn search_with_datetime_filter() {
+    test_settings_documents_indexing_swapping_and_search(
+        &json!([
+            { "id": 1, "publishedAt": "2024-01-01" },
+            { "id": 2, "publishedAt": "2024-01-15T12:00:00Z" },
+            { "id": 3, "publishedAt": "2024-02-01T00:00:00+01:00" },
+            { "id": 4, "publishedAt": "not a date" },
+        ]),
+        &json!({
+            "filterableAttributes": [{
+                "attributePatterns": ["publishedAt"],
+                "features": { "datetime": true, "filter": { "comparison": true } }
+            }],
+            "sortableAttributes": ["publishedAt"]
+        }),
+        &json!({
+            "filter": "publishedAt 2024-01-01 TO NOW",
+            "sort": ["publishedAt:desc"],
+            "facets": ["publishedAt"],
+            "attributesToRetrieve": ["id"]
+        }),
+        |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 3
+              },
+              {
+                "id": 2
+              },
+              {
+                "id": 1
+              }
+            ]
+            "###);
+            snapshot!(json_string!(response["facetDistribution"]), @r###"
+            {
+              "publishedAt": {
+                "2024-01-01T00:00:00Z": 1,
+                "2024-01-15T12:00:00Z": 1,
+                "2024-01-31T23:00:00Z": 1
+              }
+            }
+            "###);
+            snapshot!(json_string!(response["facetStats"]), @r###"
+            {
+              "publishedAt": {
+                "min": 1704067200.0,
+                "max": 1706742000.0
+              }
+            }
+            "###);
+        },
+    )
+    .await;
+}
+
 #[actix_rt::test]
 async fn search_with_pattern_filter_settings() {
     // Check if the Equality filter works with patterns
@@ -625,3 +625,55 @@ async fn retrieve_vectors() {
     ]
     "###);
 }
+
+#[actix_rt::test]
+async fn keyword_search_disabled() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    let (response, code) = index.update_settings(json!({ "keywordSearch": false })).await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({"q": "Captain"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "The keyword search is disabled for this index, `hybrid` must be specified to search with `q`",
+      "code": "keyword_search_disabled",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#keyword_search_disabled"
+    }
+    "###);
+
+    // the placeholder search is still available
+    let (response, code) = index.search_post(json!({"q": ""})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["estimatedTotalHits"], @"3");
+
+    // any hybrid search is a semantic search
+    let (response, code) = index
+        .search_post(json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.2, "embedder": "default"}, "attributesToRetrieve": ["id"]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"3"},{"id":"2"},{"id":"1"}]"###);
+    snapshot!(response["semanticHitCount"], @"3");
+
+    let (stats, code) = index.stats().await;
+    snapshot!(code, @"200 OK");
+    assert!(stats["keywordSearchSavedSize"].as_u64().unwrap() > 0, "{stats}");
+
+    // enabling the keyword search again indexes the words of the documents
+    let (response, code) = index.update_settings(json!({ "keywordSearch": null })).await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({"q": "Planet", "attributesToRetrieve": ["id"]})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"2"}]"###);
+
+    let (stats, code) = index.stats().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(stats["keywordSearchSavedSize"], @"null");
+}
@@ -571,6 +571,422 @@ async fn search_facet_distribution() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_disjunctive_facets() {
+    let index = shared_index_with_nested_documents().await;
+
+    index
+        .search(
+            json!({
+                "filter": "father = jean AND doggos.age > 3",
+                "facets": ["father", "doggos.age"],
+                "attributesToRetrieve": ["id"]
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 852
+                  }
+                ]
+                "###);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "doggos.age": {
+                    "2": 1,
+                    "4": 1
+                  },
+                  "father": {
+                    "jean": 1
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "father = jean AND doggos.age > 3",
+                "facets": ["father", "doggos.age"],
+                "disjunctiveFacets": true,
+                "attributesToRetrieve": ["id"]
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 852
+                  }
+                ]
+                "###);
+                snapshot!(json_string!(response["facetDistribution"]), @r###"
+                {
+                  "doggos.age": {
+                    "2": 1,
+                    "4": 1
+                  },
+                  "father": {
+                    "jean": 1,
+                    "jean-baptiste": 1,
+                    "pierre": 1
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_facet_buckets() {
+    let index = shared_index_with_nested_documents().await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetBuckets": {
+                "doggos.age": { "ranges": [{ "to": 4 }, { "from": 4, "to": 6 }, { "from": 6 }] }
+            }
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetBuckets"]), @r###"
+    {
+      "doggos.age": [
+        {
+          "to": 4.0,
+          "count": 1
+        },
+        {
+          "from": 4.0,
+          "to": 6.0,
+          "count": 2
+        },
+        {
+          "from": 6.0,
+          "count": 2
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "father != jean",
+            "facetBuckets": { "doggos.age": { "interval": 5 } }
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetBuckets"]), @r###"
+    {
+      "doggos.age": [
+        {
+          "from": 5.0,
+          "to": 10.0,
+          "count": 2
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetBuckets": { "doggos.age": { "interval": 0 } }
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `interval` of the buckets of facet `doggos.age` must be a positive number, but found `0`.",
+      "code": "invalid_search_facet_buckets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_buckets"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetBuckets": { "doggos.age": { "interval": 5, "ranges": [] } }
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["message"]), @r###""The buckets of facet `doggos.age` must be defined with either an `interval` or a list of `ranges`.""###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "facetBuckets": { "mother": { "interval": 5 } }
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_search_facet_buckets""###);
+}
+
+#[actix_rt::test]
+async fn search_hierarchical_facets() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "lvl0": "Electronics", "lvl1": "Electronics > Phones", "lvl2": "Electronics > Phones > Android" },
+        { "id": 2, "lvl0": "Electronics", "lvl1": "Electronics > Phones", "lvl2": "Electronics > Phones > iOS" },
+        { "id": 3, "lvl0": "Electronics", "lvl1": "Electronics > Laptops" },
+        { "id": 4, "lvl0": "Books", "lvl1": "Books > Novels" },
+    ]);
+    let (response, code) =
+        index.update_settings_filterable_attributes(json!(["lvl0", "lvl1", "lvl2"])).await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+            "hierarchicalFacets": {
+                "categories": {
+                    "attributes": ["lvl0", "lvl1", "lvl2"],
+                    "selectedPath": "Electronics > Phones"
+                }
+            }
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hierarchicalFacetDistribution"]), @r###"
+    {
+      "categories": [
+        {
+          "attribute": "lvl0",
+          "values": {
+            "Books": 1,
+            "Electronics": 3
+          }
+        },
+        {
+          "attribute": "lvl1",
+          "values": {
+            "Electronics > Laptops": 1,
+            "Electronics > Phones": 2
+          }
+        },
+        {
+          "attribute": "lvl2",
+          "values": {
+            "Electronics > Phones > Android": 1,
+            "Electronics > Phones > iOS": 1
+          }
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "hierarchicalFacets": { "categories": { "attributes": ["lvl0", "lvl1"], "separator": "" } }
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `separator` of the hierarchical facet `categories` must not be empty.",
+      "code": "invalid_search_hierarchical_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hierarchical_facets"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_boost() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "Dune", "popularity": 3 },
+        { "id": 2, "title": "Dune Messiah", "popularity": 10 },
+        { "id": 3, "title": "Children of Dune" },
+    ]);
+    let (response, code) = index.update_settings_sortable_attributes(json!(["popularity"])).await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, _code) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "q": "dune",
+                "boost": "log1p(popularity) * 2",
+                "attributesToRetrieve": ["id"],
+                "showRankingScoreDetails": true,
+            }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                let ids: Vec<_> =
+                    response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+                snapshot!(json_string!(ids), @"[2, 1, 3]");
+                snapshot!(json_string!(response["hits"][2]["_rankingScoreDetails"]["boost"]), @r###"
+                {
+                  "order": 4,
+                  "expression": "log1p(popularity) * 2",
+                  "value": null
+                }
+                "###);
+            },
+        )
+        .await;
+
+    let (response, code) = index.search_post(json!({ "boost": "log1p(popularity" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid boost expression `log1p(popularity`: expected `)` but reached the end of the expression.",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({ "boost": "title * 2" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `title` is not sortable and thus, cannot be used in a boost expression. Available sortable attributes are: `popularity`.",
+      "code": "invalid_search_boost",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_boost"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "red shoes", "color": "red" },
+        { "id": 2, "title": "blue shoes", "color": "blue" },
+        { "id": 3, "title": "green shoes", "color": "green" },
+        { "id": 4, "title": "running shoes", "color": "blue" },
+        { "id": 5, "title": "leather shoes", "color": "red" },
+    ]);
+    let (response, _code) = index.add_documents(documents, None).await;
+    index.wait_task(response.uid()).await.succeeded();
+    let (response, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["color"],
+            "rules": [
+                { "pattern": "shoes", "anchoring": "endsWith", "pinned": ["4", "5"], "hidden": ["2"] },
+                { "pattern": "red shoes", "pinned": ["3"], "filter": "color = red" },
+            ],
+        }))
+        .await;
+    assert_eq!(202, code, "{:?}", response);
+    index.wait_task(response.uid()).await.succeeded();
+
+    index
+        .search(json!({ "q": "shoes", "attributesToRetrieve": ["id"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 4
+              },
+              {
+                "id": 5
+              },
+              {
+                "id": 1
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+            snapshot!(response["estimatedTotalHits"], @"4");
+        })
+        .await;
+
+    // the pinned documents are paginated along with the other documents
+    index
+        .search(
+            json!({ "q": "shoes", "offset": 1, "limit": 2, "attributesToRetrieve": ["id"] }),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 5
+                  },
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+                snapshot!(response["estimatedTotalHits"], @"4");
+            },
+        )
+        .await;
+
+    // the pinned documents must match the filters injected by the rules
+    index
+        .search(json!({ "q": "red shoes", "attributesToRetrieve": ["id"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 5
+              },
+              {
+                "id": 1
+              }
+            ]
+            "###);
+            snapshot!(response["estimatedTotalHits"], @"2");
+        })
+        .await;
+
+    // the query doesn't match any rule
+    index
+        .search(json!({ "q": "blue", "attributesToRetrieve": ["id"] }), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // the facet values are counted on the pinned documents but not on the hidden ones
+    let (response, code) = index.facet_search(json!({ "q": "shoes", "facetName": "color" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["facetHits"], @r###"[{"value":"blue","count":1},{"value":"green","count":1},{"value":"red","count":2}]"###);
+
+    // the federated searches don't return the hidden documents, but don't pin any document
+    // since their hits are merged by ranking score
+    let (response, code) = server
+        .multi_search(json!({
+            "federation": {},
+            "queries": [{ "indexUid": "test", "q": "shoes", "attributesToRetrieve": ["id"] }],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let ids: Vec<_> = response["hits"].as_array().unwrap().iter().map(|hit| &hit["id"]).collect();
+    snapshot!(json!(ids), @"[1,3,4,5]");
+    snapshot!(response["estimatedTotalHits"], @"4");
+
+    let (response, _code) = index
+        .update_settings(json!({ "rules": [{ "pattern": "shoes", "filter": "color =" }] }))
+        .await;
+    let response = index.wait_task(response.uid()).await.failed();
+    snapshot!(response["error"]["code"], @r###""invalid_settings_rules""###);
+}
+
 #[actix_rt::test]
 async fn displayed_attributes() {
     let server = Server::new().await;
@@ -5307,6 +5307,102 @@ async fn federation_facets_same_indexes() {
     "###);
 }
 
+#[actix_rt::test]
+async fn federation_facet_buckets() {
+    let server = Server::new().await;
+
+    for index_uid in ["doggos", "doggos-2"] {
+        let index = server.index(index_uid);
+
+        let documents = NESTED_DOCUMENTS.clone();
+        let (value, _) = index.add_documents(documents, None).await;
+        index.wait_task(value.uid()).await.succeeded();
+
+        let (value, _) =
+            index.update_settings(json!({ "filterableAttributes": ["doggos.age"] })).await;
+        index.wait_task(value.uid()).await.succeeded();
+    }
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {
+          "facetsByIndex": {
+            "doggos": [],
+            "doggos-2": []
+          },
+          "mergeFacets": {},
+          "facetBuckets": {
+            "doggos.age": { "interval": 5 }
+          }
+        }, "queries": [
+          {"indexUid" : "doggos", "q": "", "attributesToRetrieve": ["id"] },
+          {"indexUid" : "doggos-2", "q": "", "attributesToRetrieve": ["id"] },
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetBuckets"]), @r###"
+    {
+      "doggos.age": [
+        {
+          "from": 0.0,
+          "to": 5.0,
+          "count": 2
+        },
+        {
+          "from": 5.0,
+          "to": 10.0,
+          "count": 4
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {
+          "facetsByIndex": {
+            "doggos": []
+          },
+          "facetBuckets": {
+            "doggos.age": { "ranges": [{ "from": 5 }] }
+          }
+        }, "queries": [
+          {"indexUid" : "doggos", "q": "", "attributesToRetrieve": ["id"] },
+          {"indexUid" : "doggos-2", "q": "", "attributesToRetrieve": ["id"] },
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetsByIndex"]), @r###"
+    {
+      "doggos": {
+        "distribution": {},
+        "stats": {},
+        "buckets": {
+          "doggos.age": [
+            {
+              "from": 5.0,
+              "count": 2
+            }
+          ]
+        }
+      }
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+          {"indexUid" : "doggos", "q": "", "facetBuckets": { "doggos.age": { "interval": 5 } } },
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: Using facet buckets is not allowed in federated queries.\n - Hint: remove `facetBuckets` from query #0 or remove `federation` from the request\n - Hint: pass `federation.facetBuckets` and add index `doggos` to `federation.facetsByIndex` for facet buckets in federated search",
+      "code": "invalid_multi_search_query_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_facets"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn federation_inconsistent_merge_order() {
     let server = Server::new().await;
@@ -161,11 +161,26 @@ test_setting_routes!(
         update_verb: put,
         default_value: true
     },
+    {
+        setting: keyword_search,
+        update_verb: put,
+        default_value: true
+    },
     {
         setting: prefix_search,
         update_verb: put,
         default_value: "indexingTime"
     },
+    {
+        setting: rules,
+        update_verb: put,
+        default_value: []
+    },
+    {
+        setting: rerankers,
+        update_verb: put,
+        default_value: {}
+    },
     {
         setting: proximity_precision,
         update_verb: put,
@@ -199,7 +214,7 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 20);
+    assert_eq!(settings.keys().len(), 23);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
@@ -232,7 +247,10 @@ async fn get_settings() {
     assert_eq!(settings["searchCutoffMs"], json!(null));
     assert_eq!(settings["prefixSearch"], json!("indexingTime"));
     assert_eq!(settings["facetSearch"], json!(true));
+    assert_eq!(settings["keywordSearch"], json!(true));
     assert_eq!(settings["embedders"], json!({}));
+    assert_eq!(settings["rules"], json!([]));
+    assert_eq!(settings["rerankers"], json!({}));
 }
 
 #[actix_rt::test]
@@ -335,7 +353,10 @@ async fn secrets_are_hidden_in_settings() {
       "searchCutoffMs": null,
       "localizedAttributes": null,
       "facetSearch": true,
-      "prefixSearch": "indexingTime"
+      "keywordSearch": true,
+      "prefixSearch": "indexingTime",
+      "rules": [],
+      "rerankers": {}
     }
     "#);
 
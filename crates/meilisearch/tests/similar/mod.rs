@@ -702,3 +702,53 @@ async fn limit_and_offset() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn rules_do_not_apply() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .update_settings(json!({
+        "embedders": {
+            "manual": {
+                "source": "userProvided",
+                "dimensions": 3,
+            }
+        },
+        "rules": [{ "pattern": "", "pinned": ["287947"], "hidden": ["522681"] }]}))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = DOCUMENTS.clone();
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // the rules match the query of a search, and a search for similar documents has none
+    index
+        .similar(
+            json!({"id": 143, "attributesToRetrieve": ["id"], "embedder": "manual"}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": "522681"
+                  },
+                  {
+                    "id": "299537"
+                  },
+                  {
+                    "id": "166428"
+                  },
+                  {
+                    "id": "287947"
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
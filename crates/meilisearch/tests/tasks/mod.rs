@@ -2,7 +2,7 @@ mod errors;
 mod webhook;
 
 use meili_snap::insta::assert_json_snapshot;
-use meili_snap::snapshot;
+use meili_snap::{json_string, snapshot};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
@@ -145,6 +145,99 @@ async fn list_tasks_with_star_filters() {
     assert_eq!(response["results"].as_array().unwrap().len(), 2);
 }
 
+#[actix_rt::test]
+async fn task_group_is_committed_atomically() {
+    let server = Server::new().await;
+
+    let (response, code) = server
+        .create_task_group(json!([
+            { "indexUid": "products", "addDocuments": [{ "id": 1, "name": "shirt" }], "primaryKey": "id" },
+            { "indexUid": "categories", "updateSettings": { "filterableAttributes": ["name"] } },
+        ]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(json_string!(response, { ".tasks[].enqueuedAt" => "[date]" }), @r###"
+    {
+      "groupUid": 0,
+      "tasks": [
+        {
+          "taskUid": 0,
+          "indexUid": "products",
+          "status": "enqueued",
+          "type": "documentAdditionOrUpdate",
+          "enqueuedAt": "[date]"
+        },
+        {
+          "taskUid": 1,
+          "indexUid": "categories",
+          "status": "enqueued",
+          "type": "settingsUpdate",
+          "enqueuedAt": "[date]"
+        }
+      ]
+    }
+    "###);
+    server.wait_task(1).await.succeeded();
+
+    let (response, code) = server.tasks_filter("groupUids=0").await;
+    snapshot!(code, @"200 OK");
+    let tasks = response["results"].as_array().unwrap();
+    assert_eq!(tasks.len(), 2);
+    for task in tasks {
+        snapshot!(task["groupUid"], @"0");
+        snapshot!(task["status"], @r###""succeeded""###);
+        snapshot!(task["groupStatus"], @r###""succeeded""###);
+    }
+    // The tasks of the group are processed in a single batch.
+    assert_eq!(tasks[0]["batchUid"], tasks[1]["batchUid"]);
+    let (batch, _code) = server.get_batch(tasks[0]["batchUid"].as_u64().unwrap() as u32).await;
+    snapshot!(batch["groupUid"], @"0");
+    snapshot!(batch["groupStatus"], @r###""succeeded""###);
+
+    let (response, code) = server.get_task_group(0).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["partiallyCommitted"], @"false");
+    let tasks = response["tasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 2);
+    snapshot!(tasks[0]["indexUid"], @r###""products""###);
+    snapshot!(tasks[1]["indexUid"], @r###""categories""###);
+
+    // The second operation fails because the index doesn't exist, so the document is not added.
+    let (response, code) = server
+        .create_task_group(json!([
+            { "indexUid": "products", "addDocuments": [{ "id": 2, "name": "hat" }] },
+            { "indexUid": "unknown", "deleteDocuments": [1] },
+        ]))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    snapshot!(response["groupUid"], @"2");
+    let task = server.wait_task(3).await.failed();
+    snapshot!(task["error"]["code"], @r###""index_not_found""###);
+    let (task, _code) = server.get_task(2).await;
+    snapshot!(task["status"], @r###""failed""###);
+    snapshot!(task["groupStatus"], @r###""failed""###);
+    let (_response, code) = server.index("products").get_document(2, None).await;
+    snapshot!(code, @"404 Not Found");
+    let (response, code) = server.get_task_group(2).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["status"], @r###""failed""###);
+
+    let (response, code) = server.get_task_group(42).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""task_group_not_found""###);
+
+    // The indexes of a group must all be different.
+    let (response, code) = server
+        .create_task_group(json!([
+            { "indexUid": "products", "deleteDocuments": [1] },
+            { "indexUid": "products", "deleteDocuments": [2] },
+        ]))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_task_group_duplicate_index_found""###);
+}
+
 #[actix_rt::test]
 async fn list_tasks_status_filtered() {
     let server = Server::new().await;
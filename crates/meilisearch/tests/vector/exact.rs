@@ -0,0 +1,175 @@
+use meili_snap::{json_string, snapshot};
+
+use crate::common::index::Index;
+use crate::common::Server;
+use crate::json;
+
+async fn index_with_embeddings(server: &Server) -> Index {
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "filterableAttributes": ["id"],
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 3,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [1.0, 0.0, 0.0] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [0.9, 0.1, 0.0] }},
+      {"id": 2, "name": "billou", "_vectors": { "manual": [0.5, 0.5, 0.0] }},
+      {"id": 3, "name": "intel", "_vectors": { "manual": [0.0, 0.0, 1.0] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    index
+}
+
+#[actix_rt::test]
+async fn exact_search() {
+    let server = Server::new().await;
+    let index = index_with_embeddings(&server).await;
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0, 0.0],
+            "hybrid": { "semanticRatio": 1.0, "embedder": "manual", "exact": true },
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r#"[{"id":0},{"id":1},{"id":2},{"id":3}]"#);
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1.0, 0.0, 0.0],
+            "hybrid": { "semanticRatio": 1.0, "embedder": "manual", "exact": true },
+            "filter": "id != 0",
+            "limit": 2,
+            "attributesToRetrieve": ["id"],
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r#"[{"id":1},{"id":2}]"#);
+
+    let (response, code) = index
+        .search_get("?vector=1.0,0.0,0.0&hybridEmbedder=manual&hybridSemanticRatio=1.0&hybridExact=true&attributesToRetrieve=id&limit=1")
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r#"[{"id":0}]"#);
+
+    let (response, code) = index.search_get("?q=kefir&hybridExact=true").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r#"
+    {
+      "message": "`hybridEmbedder` is mandatory when `hybridExact` is present",
+      "code": "invalid_search_hybrid_query",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hybrid_query"
+    }
+    "#);
+}
+
+#[actix_rt::test]
+async fn recall() {
+    let server = Server::new().await;
+    let index = index_with_embeddings(&server).await;
+
+    let (response, code) = index.recall("?k=2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r#"
+    {
+      "embedders": {
+        "manual": {
+          "k": 2,
+          "sampledQueries": 4,
+          "recall": 1.0
+        }
+      }
+    }
+    "#);
+
+    let (response, code) = index.recall("?embedder=manual&sampleSize=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r#"
+    {
+      "embedders": {
+        "manual": {
+          "k": 10,
+          "sampledQueries": 1,
+          "recall": 1.0
+        }
+      }
+    }
+    "#);
+}
+
+#[actix_rt::test]
+async fn recall_errors() {
+    let server = Server::new().await;
+    let index = index_with_embeddings(&server).await;
+
+    let (response, code) = index.recall("?embedder=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r#"
+    {
+      "message": "Cannot find embedder with name `doggo`.",
+      "code": "invalid_recall_embedder",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_recall_embedder"
+    }
+    "#);
+
+    let (response, code) = index.recall("?k=0").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r#"
+    {
+      "message": "Invalid value in parameter `k`: the number of neighbors must be between 1 and 100",
+      "code": "invalid_recall_k",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_recall_k"
+    }
+    "#);
+
+    let (response, code) = index.recall("?k=101").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r#"
+    {
+      "message": "Invalid value in parameter `k`: the number of neighbors must be between 1 and 100",
+      "code": "invalid_recall_k",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_recall_k"
+    }
+    "#);
+
+    let (response, code) = index.recall("?sampleSize=1001").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r#"
+    {
+      "message": "Invalid value in parameter `sampleSize`: the number of sampled documents must be at most 1000",
+      "code": "invalid_recall_sample_size",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_recall_sample_size"
+    }
+    "#);
+
+    let (response, code) = index.recall("?sampleSize=many").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r#"
+    {
+      "message": "Invalid value in parameter `sampleSize`: could not parse `many` as a positive integer",
+      "code": "invalid_recall_sample_size",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_recall_sample_size"
+    }
+    "#);
+}
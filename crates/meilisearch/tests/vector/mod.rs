@@ -1,7 +1,9 @@
 mod binary_quantized;
+mod exact;
 #[cfg(feature = "test-ollama")]
 mod ollama;
 mod openai;
+mod quantization;
 mod rest;
 mod settings;
 
@@ -779,3 +781,85 @@ async fn add_remove_one_vector_4588() {
     }
     "#);
 }
+
+#[actix_rt::test]
+async fn multi_vector_user_provided() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "manual": {
+                  "source": "userProvided",
+                  "dimensions": 2,
+                  "multiVector": true,
+              }
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "_vectors": { "manual": [[1, 0]] }},
+      {"id": 1, "name": "echo", "_vectors": { "manual": [[1, 0], [0, 1]] }},
+      {"id": 2, "name": "intel", "_vectors": { "manual": [[-1, 0]] }},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // the query has two vectors, only `echo` has a matching vector for both of them,
+    // and `intel` has a negative cosine with the first one
+    let (documents, _code) = index
+        .search_post(json!({
+            "vector": [1, 0, 0, 1],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+            "showRankingScore": true,
+        }))
+        .await;
+    snapshot!(documents, @r###"
+    {
+      "hits": [
+        {
+          "id": 1,
+          "name": "echo",
+          "_rankingScore": 1.0
+        },
+        {
+          "id": 0,
+          "name": "kefir",
+          "_rankingScore": 0.75
+        },
+        {
+          "id": 2,
+          "name": "intel",
+          "_rankingScore": 0.25
+        }
+      ],
+      "query": "",
+      "processingTimeMs": "[duration]",
+      "limit": 20,
+      "offset": 0,
+      "estimatedTotalHits": 3,
+      "semanticHitCount": 3
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "vector": [1, 0, 0],
+            "hybrid": {"semanticRatio": 1.0, "embedder": "manual"},
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid vector dimensions: expected a concatenation of vectors of dimension `2` for a multi-vector embedder, found: `3`.",
+      "code": "invalid_vector_dimensions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_vector_dimensions"
+    }
+    "###);
+}
@@ -5,7 +5,7 @@ use reqwest::IntoUrl;
 use wiremock::matchers::{method, path};
 use wiremock::{Mock, MockServer, Request, ResponseTemplate};
 
-use crate::common::Value;
+use crate::common::{default_settings, Server, Value};
 use crate::json;
 use crate::vector::{get_server_vector, GetAllDocumentsOptions};
 
@@ -2111,3 +2111,590 @@ async fn searchable_reindex() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn chunking() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let text: String = req.body_json().unwrap();
+            let embedding = if text.trim() == "kefir" { [1.0, 0.0, 0.0] } else { [0.0, 1.0, 0.0] };
+            ResponseTemplate::new(200).set_body_json(json!({ "data": embedding }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let setting = json!({
+        "source": "rest",
+        "url": mock_server.uri(),
+        "dimensions": 3,
+        "request": "{{text}}",
+        "response": {
+          "data": "{{embedding}}"
+        },
+        "documentTemplate": "{{doc.name}}",
+        "chunking": { "size": 6 },
+    });
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let documents = json!([
+      {"id": 0, "name": "kefir intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (documents, _code) = index
+        .get_all_documents(GetAllDocumentsOptions { retrieve_vectors: true, ..Default::default() })
+        .await;
+    snapshot!(json_string!(documents["results"][0]["_vectors"]), @r###"
+    {
+      "rest": {
+        "embeddings": [
+          [
+            1.0,
+            0.0,
+            0.0
+          ],
+          [
+            0.0,
+            1.0,
+            0.0
+          ]
+        ],
+        "regenerate": true
+      }
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+          "vector": [0.0, 1.0, 0.0],
+          "hybrid": { "semanticRatio": 1.0, "embedder": "rest" },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["_semanticChunk"]), @r###"
+    {
+      "index": 1,
+      "start": 6,
+      "end": 11
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": { "chunking": { "size": 6, "overlap": 6 } },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["error"]["message"], @r###""`.embedders.rest`: `chunking.overlap` must be smaller than `chunking.size`, got overlap 6 for size 6""###);
+}
+
+#[actix_rt::test]
+async fn sparse() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let text: String = req.body_json().unwrap();
+            // each word of the text gets an equal share of the weight
+            let words: Vec<&str> = text.split_whitespace().collect();
+            let embedding: BTreeMap<&str, f32> =
+                words.iter().map(|word| (*word, 1.0 / words.len() as f32)).collect();
+            ResponseTemplate::new(200).set_body_json(json!({ "data": embedding }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let setting = json!({
+        "source": "rest",
+        "url": mock_server.uri(),
+        "sparse": true,
+        "request": "{{text}}",
+        "response": {
+          "data": "{{embedding}}"
+        },
+        "documentTemplate": "{{doc.name}}",
+    });
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "intel"},
+      {"id": 2, "name": "kefir intel"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+          "q": "kefir",
+          "hybrid": { "semanticRatio": 1.0, "embedder": "rest" },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["id"]), @"0");
+    snapshot!(json_string!(response["hits"][1]["id"]), @"2");
+
+    let (response, code) = index
+        .search_post(json!({
+          "vector": [1.0],
+          "hybrid": { "semanticRatio": 1.0, "embedder": "rest" },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "The embedder `rest` generates sparse embeddings and cannot search with a `vector`.\n  - Hint: search with `q` instead.",
+      "code": "invalid_search_vector",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn rerank() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            #[derive(serde::Deserialize)]
+            struct RerankRequest {
+                query: String,
+                documents: Vec<String>,
+            }
+            let request: RerankRequest = req.body_json().unwrap();
+            assert_eq!(request.query, "kefir");
+            // the longer the document, the more relevant, and the results are sorted by relevance
+            let mut results: Vec<_> = request
+                .documents
+                .iter()
+                .enumerate()
+                .map(|(index, document)| (index, 0.25 * document.split_whitespace().count() as f32))
+                .collect();
+            results.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+            let results: Vec<_> = results
+                .into_iter()
+                .map(|(index, score)| json!({ "index": index, "relevance_score": score }))
+                .collect();
+            ResponseTemplate::new(200).set_body_json(json!({ "results": results }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "rerankers": {
+              "bad": {
+                  "source": "rest",
+                  "url": mock_server.uri(),
+                  "response": { "results": [{ "index": "{{index}}", "relevance_score": "{{score}}" }, "{{..}}"] },
+              },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""failed""###);
+    snapshot!(task["error"]["message"], @r###""`.rerankers.bad`: `request` is mandatory for source `rest`""###);
+    snapshot!(task["error"]["code"], @r###""invalid_settings_rerankers""###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "rerankers": {
+              "rest": {
+                  "source": "rest",
+                  "url": mock_server.uri(),
+                  "apiKey": "My super secret key",
+                  "request": { "query": "{{query}}", "documents": ["{{text}}", "{{..}}"] },
+                  "response": { "results": [{ "index": "{{index}}", "relevance_score": "{{score}}" }, "{{..}}"] },
+                  "documentTemplate": "{{doc.name}}",
+              },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (settings, _code) = index.settings().await;
+    snapshot!(settings["rerankers"]["rest"]["apiKey"], @r###""MyXXXX...""###);
+
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "intel"},
+      {"id": 2, "name": "kefir the dog"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({ "q": "kefir" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["id"]), @"0");
+    snapshot!(json_string!(response["hits"][1]["id"]), @"2");
+
+    let (response, code) = index
+        .search_post(json!({
+          "q": "kefir",
+          "rerank": { "reranker": "rest" },
+          "showRankingScore": true,
+          "showRankingScoreDetails": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["id"]), @"2");
+    snapshot!(json_string!(response["hits"][0]["_rankingScore"]), @"0.75");
+    snapshot!(json_string!(response["hits"][0]["_rankingScoreDetails"]["rerank"]), @r###"
+    {
+      "order": 0,
+      "reranker": "rest",
+      "score": 0.75
+    }
+    "###);
+    snapshot!(json_string!(response["hits"][1]["id"]), @"0");
+    snapshot!(json_string!(response["hits"][1]["_rankingScore"]), @"0.25");
+
+    // only the first document is reranked and returned
+    let (response, code) = index
+        .search_post(json!({
+          "q": "kefir",
+          "rerank": { "reranker": "rest", "topN": 1 },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 0,
+        "name": "kefir"
+      }
+    ]
+    "###);
+    snapshot!(response["estimatedTotalHits"], @"1");
+
+    let (response, code) = index
+        .search_post(json!({
+          "q": "kefir",
+          "rerank": { "reranker": "rest", "topN": 1 },
+          "offset": 1,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @"[]");
+
+    // the number of reranked documents is bounded by the `maxTotalHits`
+    let (response, code) = index
+        .search_post(json!({
+          "q": "kefir",
+          "rerank": { "reranker": "rest", "topN": 0 },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value for `rerank.topN`: expected a number between 1 and the `maxTotalHits` of the index (1000), but got `0`.",
+      "code": "invalid_search_rerank",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_rerank"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({
+          "q": "kefir",
+          "rerank": { "reranker": "rest", "topN": 1001 },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value for `rerank.topN`: expected a number between 1 and the `maxTotalHits` of the index (1000), but got `1001`.",
+      "code": "invalid_search_rerank",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_rerank"
+    }
+    "###);
+
+    // the threshold applies to the scores of the reranker
+    let (response, code) = index
+        .search_post(json!({
+          "q": "kefir",
+          "rerank": { "reranker": "rest" },
+          "rankingScoreThreshold": 0.5,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "name": "kefir the dog"
+      }
+    ]
+    "###);
+    snapshot!(response["estimatedTotalHits"], @"1");
+
+    let (response, code) = index
+        .search_post(json!({
+          "q": "kefir",
+          "rerank": { "reranker": "nope" },
+        }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Cannot find reranker with name `nope`.",
+      "code": "invalid_search_rerank",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_rerank"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn persistent_embedding_cache() {
+    let (mock, setting) = create_mock().await;
+    let temp = tempfile::tempdir().unwrap();
+    let cache = tempfile::tempdir().unwrap();
+    let mut options = default_settings(temp.path());
+    options.indexer_options.experimental_embedding_cache_path = Some(cache.path().to_owned());
+    let server = Server::new_with_options(options).await.unwrap();
+
+    let index = server.index("doggo");
+    let documents = json!([
+      {"id": 0, "name": "kefir"},
+      {"id": 1, "name": "intel"},
+    ]);
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+    let (value, code) = index.add_documents(documents.clone(), None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+    let requests = mock.received_requests().await.unwrap().len();
+
+    // the documents are embedded again in a new index, from the cache
+    let (value, code) = index.delete().await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    // the query is the text of a document, so its embedding is in the cache
+    let (response, code) = index
+        .search_post(json!({
+            "q": "intel",
+            "hybrid": {"semanticRatio": 1.0, "embedder": "rest"},
+            "retrieveVectors": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]), @r###"
+    {
+      "id": 1,
+      "name": "intel",
+      "_vectors": {
+        "rest": {
+          "embeddings": [
+            [
+              1.0,
+              1.0,
+              1.0
+            ]
+          ],
+          "regenerate": true
+        }
+      }
+    }
+    "###);
+
+    assert_eq!(mock.received_requests().await.unwrap().len(), requests);
+}
+
+#[actix_rt::test]
+async fn multimodal_fragments() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let body: serde_json::Value = req.body_json().unwrap();
+            // a CLIP-style model embeds the images of the documents and the texts of the queries in the same space
+            let input = &body["input"];
+            let input = input.as_str().or(input["image"].as_str()).or(input["text"].as_str());
+            let embedding = match input.unwrap_or_default() {
+                "https://example.com/kefir.jpg" | "a photo of kefir" => [1.0, 0.0, 0.0],
+                "https://example.com/intel.jpg" | "a photo of intel" => [0.0, 1.0, 0.0],
+                _ => [0.0, 0.0, 1.0],
+            };
+            ResponseTemplate::new(200).set_body_json(json!({ "data": embedding }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let setting = json!({
+        "source": "rest",
+        "url": mock_server.uri(),
+        "dimensions": 3,
+        "request": { "input": "{{text}}" },
+        "response": {
+          "data": "{{embedding}}"
+        },
+        "indexingFragment": { "image": "{{doc.picture}}" },
+        "searchFragment": { "text": "a photo of {{q}}" },
+    });
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": setting,
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (settings, _code) = index.settings().await;
+    snapshot!(json_string!(settings["embedders"]["rest"]["indexingFragment"]), @r###"
+    {
+      "image": "{{doc.picture}}"
+    }
+    "###);
+    snapshot!(json_string!(settings["embedders"]["rest"]["searchFragment"]), @r###"
+    {
+      "text": "a photo of {{q}}"
+    }
+    "###);
+    snapshot!(settings["embedders"]["rest"]["documentTemplate"], @"null");
+
+    let documents = json!([
+      {"id": 0, "name": "kefir", "picture": "https://example.com/kefir.jpg"},
+      {"id": 1, "name": "intel", "picture": "https://example.com/intel.jpg"},
+    ]);
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await.succeeded();
+
+    let (response, code) = index
+        .search_post(json!({
+          "q": "intel",
+          "hybrid": { "semanticRatio": 1.0, "embedder": "rest" },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"][0]["id"]), @"1");
+
+    let mut bodies: Vec<serde_json::Value> = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| request.body_json().unwrap())
+        .collect();
+    bodies.sort_by_key(|body| body.to_string());
+    snapshot!(json_string!(bodies), @r###"
+    [
+      {
+        "input": {
+          "image": "https://example.com/intel.jpg"
+        }
+      },
+      {
+        "input": {
+          "image": "https://example.com/kefir.jpg"
+        }
+      },
+      {
+        "input": {
+          "text": "a photo of intel"
+        }
+      }
+    ]
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": { "documentTemplate": "{{doc.name}}" },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["error"]["message"], @r###""`.embedders.rest`: `documentTemplate` cannot be set along with `indexingFragment`, as documents are rendered with the fragment.\n  - Hint: reset `indexingFragment` to `null` to render documents with `documentTemplate` again.""###);
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": { "searchFragment": { "text": "{{doc.name}}" } },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["error"]["code"], @r###""invalid_settings_embedders""###);
+
+    // documents are rendered with the template again once the fragment is reset
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": { "indexingFragment": null, "documentTemplate": "{{doc.name}}" },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await.succeeded();
+}
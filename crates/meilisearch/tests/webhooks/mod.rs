@@ -0,0 +1,285 @@
+//! The tests of the webhooks registered through the `/webhooks` route, the webhook given with
+//! `--task-webhook-url` is tested in `tasks::webhook`.
+
+use std::io::Read;
+use std::time::Duration;
+
+use flate2::read::GzDecoder;
+use hmac::{Hmac, Mac};
+use meili_snap::{json_string, snapshot};
+use meilisearch::Opt;
+use sha2::Sha256;
+use tokio::time::sleep;
+use url::Url;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+use crate::common::{default_settings, Server, Value};
+use crate::json;
+
+/// Waits until the most recent delivery of the webhook is finished and returns all its deliveries.
+async fn wait_for_finished_delivery<State>(server: &Server<State>, uuid: &str) -> Value {
+    // the first retry happens one second after the first attempt
+    for _ in 0..100 {
+        let (response, code) = server.get_webhook_deliveries(uuid).await;
+        assert_eq!(code, 200, "{response}");
+        if response["results"][0]["finishedAt"].is_string() {
+            return response;
+        }
+        sleep(Duration::from_millis(100)).await;
+    }
+    panic!("The delivery of the webhook `{uuid}` didn't finish in time");
+}
+
+#[actix_rt::test]
+async fn filtered_and_signed_delivery() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST")).respond_with(ResponseTemplate::new(200)).mount(&mock_server).await;
+
+    let server = Server::new().await;
+    let (response, code) = server
+        .create_webhook(json!({
+            "url": mock_server.uri(),
+            "headers": { "Authorization": "Bearer a-token" },
+            "filter": { "indexUids": ["tamo*"], "statuses": ["succeeded"] },
+            "secret": "a secret",
+        }))
+        .await;
+    snapshot!(code, @"201 Created");
+    snapshot!(json_string!(response, { ".uuid" => "[uuid]", ".url" => "[url]" }), @r###"
+    {
+      "uuid": "[uuid]",
+      "isEditable": true,
+      "url": "[url]",
+      "headers": {
+        "Authorization": "BeXXXX..."
+      },
+      "filter": {
+        "indexUids": [
+          "tamo*"
+        ],
+        "types": null,
+        "statuses": [
+          "succeeded"
+        ]
+      },
+      "signed": true
+    }
+    "###);
+    let uuid = response["uuid"].as_str().unwrap().to_string();
+
+    // this task doesn't match the filter of the webhook
+    let (response, _) = server.index("doggo").add_documents(json!({ "id": 1 }), None).await;
+    server.wait_task(response.uid()).await.succeeded();
+    let (response, _) = server.index("tamo").add_documents(json!({ "id": 1 }), None).await;
+    server.wait_task(response.uid()).await.succeeded();
+
+    let deliveries = wait_for_finished_delivery(&server, &uuid).await;
+    snapshot!(json_string!(deliveries, {
+        ".results[].webhookUuid" => "[uuid]",
+        ".results[].enqueuedAt" => "[date]",
+        ".results[].lastAttemptAt" => "[date]",
+        ".results[].finishedAt" => "[date]",
+    }), @r###"
+    {
+      "results": [
+        {
+          "uid": 0,
+          "webhookUuid": "[uuid]",
+          "status": "succeeded",
+          "taskUids": [
+            1
+          ],
+          "attempts": 1,
+          "lastStatusCode": 200,
+          "lastError": null,
+          "enqueuedAt": "[date]",
+          "lastAttemptAt": "[date]",
+          "nextAttemptAt": null,
+          "finishedAt": "[date]"
+        }
+      ],
+      "limit": 20,
+      "from": 0,
+      "next": null
+    }
+    "###);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    let request = &requests[0];
+    assert_eq!(request.headers.get("authorization").unwrap(), "Bearer a-token");
+    assert_eq!(request.headers.get("x-meilisearch-delivery").unwrap(), "0");
+
+    let mut payload = String::new();
+    GzDecoder::new(request.body.as_slice()).read_to_string(&mut payload).unwrap();
+    let tasks: Vec<serde_json::Value> =
+        payload.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0]["uid"], 1);
+    assert_eq!(tasks[0]["indexUid"], "tamo");
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"a secret").unwrap();
+    mac.update(payload.as_bytes());
+    let signature = format!("sha256={:x}", mac.finalize().into_bytes());
+    assert_eq!(request.headers.get("x-meilisearch-signature").unwrap(), signature.as_str());
+}
+
+#[actix_rt::test]
+async fn failed_delivery_is_retried() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .respond_with(ResponseTemplate::new(200))
+        .with_priority(2)
+        .mount(&mock_server)
+        .await;
+
+    let server = Server::new().await;
+    let (response, code) = server.create_webhook(json!({ "url": mock_server.uri() })).await;
+    snapshot!(code, @"201 Created");
+    let uuid = response["uuid"].as_str().unwrap().to_string();
+
+    let (response, _) = server.index("doggo").add_documents(json!({ "id": 1 }), None).await;
+    server.wait_task(response.uid()).await.succeeded();
+
+    let deliveries = wait_for_finished_delivery(&server, &uuid).await;
+    snapshot!(json_string!(deliveries["results"][0], {
+        ".webhookUuid" => "[uuid]",
+        ".enqueuedAt" => "[date]",
+        ".lastAttemptAt" => "[date]",
+        ".finishedAt" => "[date]",
+    }), @r###"
+    {
+      "uid": 0,
+      "webhookUuid": "[uuid]",
+      "status": "succeeded",
+      "taskUids": [
+        0
+      ],
+      "attempts": 2,
+      "lastStatusCode": 200,
+      "lastError": null,
+      "enqueuedAt": "[date]",
+      "lastAttemptAt": "[date]",
+      "nextAttemptAt": null,
+      "finishedAt": "[date]"
+    }
+    "###);
+    assert_eq!(mock_server.received_requests().await.unwrap().len(), 2);
+}
+
+#[actix_rt::test]
+async fn update_and_delete_webhook() {
+    let server = Server::new().await;
+    let (response, code) = server
+        .create_webhook(json!({ "url": "http://localhost:7777/", "secret": "a secret" }))
+        .await;
+    snapshot!(code, @"201 Created");
+    let uuid = response["uuid"].as_str().unwrap().to_string();
+
+    let (response, code) = server
+        .update_webhook(&uuid, json!({ "filter": { "types": ["settingsUpdate"] }, "secret": null }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".uuid" => "[uuid]" }), @r###"
+    {
+      "uuid": "[uuid]",
+      "isEditable": true,
+      "url": "http://localhost:7777/",
+      "headers": {},
+      "filter": {
+        "indexUids": null,
+        "types": [
+          "settingsUpdate"
+        ],
+        "statuses": null
+      },
+      "signed": false
+    }
+    "###);
+
+    let (response, code) =
+        server.update_webhook(&uuid, json!({ "filter": { "types": ["doggo"] } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_webhook_filter""###);
+
+    let (response, code) = server.update_webhook(&uuid, json!({ "url": "ftp://kefir" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid `.url` (`ftp://kefir`): the scheme must be `http` or `https`, not `ftp`.",
+      "code": "invalid_webhook_url",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_webhook_url"
+    }
+    "###);
+
+    let (_response, code) = server.delete_webhook(&uuid).await;
+    snapshot!(code, @"204 No Content");
+
+    let (response, code) = server.get_webhook_deliveries(&uuid).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(response["code"], @r###""webhook_not_found""###);
+
+    let (response, code) = server.delete_webhook("kefir").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_webhook_uuid""###);
+}
+
+#[actix_rt::test]
+async fn cli_webhook_cannot_be_modified() {
+    let db_path = tempfile::tempdir().unwrap();
+    let server = Server::new_with_options(Opt {
+        task_webhook_url: Some(Url::parse("http://localhost:7777/").unwrap()),
+        task_webhook_authorization_header: Some(String::from("Bearer a-token")),
+        ..default_settings(db_path.path())
+    })
+    .await
+    .unwrap();
+
+    let (response, code) = server.get_webhooks().await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response, @r###"
+    {
+      "results": [
+        {
+          "uuid": "00000000-0000-0000-0000-000000000000",
+          "isEditable": false,
+          "url": "http://localhost:7777/",
+          "headers": {
+            "Authorization": "BeXXXX..."
+          },
+          "filter": {
+            "indexUids": null,
+            "types": null,
+            "statuses": null
+          },
+          "signed": false
+        }
+      ]
+    }
+    "###);
+
+    let (response, code) = server
+        .update_webhook("00000000-0000-0000-0000-000000000000", json!({ "url": "http://kefir/" }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Webhook `00000000-0000-0000-0000-000000000000` is configured with `--task-webhook-url` and cannot be modified or deleted through the API.",
+      "code": "immutable_webhook",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#immutable_webhook"
+    }
+    "###);
+
+    let (response, code) = server.delete_webhook("00000000-0000-0000-0000-000000000000").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""immutable_webhook""###);
+}
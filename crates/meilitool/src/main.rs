@@ -187,6 +187,29 @@ fn clear_task_queue(db_path: PathBuf) -> anyhow::Result<()> {
     let enqueued_at = try_opening_poly_database(&env, &wtxn, "enqueued-at")?;
     let started_at = try_opening_poly_database(&env, &wtxn, "started-at")?;
     let finished_at = try_opening_poly_database(&env, &wtxn, "finished-at")?;
+    // the priorities and run-at dates were introduced in v1.15 and are missing from older task queues
+    let priority = env
+        .database_options()
+        .name("priority")
+        .open(&wtxn)
+        .context("While opening the \"priority\" poly database")?;
+    let run_at = env
+        .database_options()
+        .name("run-at")
+        .open(&wtxn)
+        .context("While opening the \"run-at\" poly database")?;
+    // the task groups were introduced in v1.15 as well
+    let groups = env
+        .database_options()
+        .name("groups")
+        .open(&wtxn)
+        .context("While opening the \"groups\" poly database")?;
+    // the deliveries refer to the tasks, the webhooks themselves are kept
+    let webhook_deliveries = env
+        .database_options()
+        .name("webhook-deliveries")
+        .open(&wtxn)
+        .context("While opening the \"webhook-deliveries\" poly database")?;
 
     try_clearing_poly_database(&mut wtxn, all_tasks, "all-tasks")?;
     try_clearing_poly_database(&mut wtxn, status, "status")?;
@@ -196,6 +219,18 @@ fn clear_task_queue(db_path: PathBuf) -> anyhow::Result<()> {
     try_clearing_poly_database(&mut wtxn, enqueued_at, "enqueued-at")?;
     try_clearing_poly_database(&mut wtxn, started_at, "started-at")?;
     try_clearing_poly_database(&mut wtxn, finished_at, "finished-at")?;
+    if let Some(priority) = priority {
+        try_clearing_poly_database(&mut wtxn, priority, "priority")?;
+    }
+    if let Some(run_at) = run_at {
+        try_clearing_poly_database(&mut wtxn, run_at, "run-at")?;
+    }
+    if let Some(groups) = groups {
+        try_clearing_poly_database(&mut wtxn, groups, "groups")?;
+    }
+    if let Some(webhook_deliveries) = webhook_deliveries {
+        try_clearing_poly_database(&mut wtxn, webhook_deliveries, "webhook-deliveries")?;
+    }
 
     wtxn.commit().context("While committing the transaction")?;
 
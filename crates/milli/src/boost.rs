@@ -0,0 +1,426 @@
+//! This module provides the `BoostExpression` type used to compute a score for each document
+//! at search time from its numeric facet values, and defines the errors related to this type.
+
+use std::fmt;
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Invalid boost expression `{expression}`: {reason}.")]
+pub struct BoostError {
+    expression: String,
+    reason: String,
+}
+
+/// An arithmetic expression over the numeric values of the fields of a document.
+///
+/// It supports numbers, the `+`, `-`, `*` and `/` operators, parentheses, durations
+/// like `30d` (converted to seconds, the supported units are `s`, `m`, `h`, `d` and `w`),
+/// `NOW` (the current number of seconds since the Unix epoch, to compare with datetime fields,
+/// spelled in uppercase like in the datetime filters) and the following functions:
+/// - `log(x)`, `log1p(x)`, `sqrt(x)`, `abs(x)` and `exp(x)`,
+/// - `min(x, y, ...)`, `max(x, y, ...)` and `pow(x, y)`,
+/// - `gauss(x, origin, scale[, decay])` and `linear(x, origin, scale[, decay])` that are equal
+///   to 1 at `origin` and decrease to `decay` (0.5 by default) at `scale` from `origin`.
+///
+/// Any other name is a field of the document, like `popularity`, `stats.views` or `now`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoostExpression {
+    source: String,
+    node: Node,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Number(f64),
+    Now,
+    Field(String),
+    Neg(Box<Node>),
+    Binary(Operator, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Function {
+    Log,
+    Log1p,
+    Sqrt,
+    Abs,
+    Exp,
+    Min,
+    Max,
+    Pow,
+    Gauss,
+    Linear,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "log" => Some(Function::Log),
+            "log1p" => Some(Function::Log1p),
+            "sqrt" => Some(Function::Sqrt),
+            "abs" => Some(Function::Abs),
+            "exp" => Some(Function::Exp),
+            "min" => Some(Function::Min),
+            "max" => Some(Function::Max),
+            "pow" => Some(Function::Pow),
+            "gauss" => Some(Function::Gauss),
+            "linear" => Some(Function::Linear),
+            _ => None,
+        }
+    }
+
+    /// The minimum and maximum number of arguments of the function.
+    fn arity(&self) -> (usize, usize) {
+        match self {
+            Function::Log | Function::Log1p | Function::Sqrt | Function::Abs | Function::Exp => {
+                (1, 1)
+            }
+            Function::Min | Function::Max => (1, usize::MAX),
+            Function::Pow => (2, 2),
+            Function::Gauss | Function::Linear => (3, 4),
+        }
+    }
+
+    fn apply(&self, args: &[f64]) -> f64 {
+        match self {
+            Function::Log => args[0].ln(),
+            Function::Log1p => args[0].ln_1p(),
+            Function::Sqrt => args[0].sqrt(),
+            Function::Abs => args[0].abs(),
+            Function::Exp => args[0].exp(),
+            Function::Min => args.iter().copied().fold(f64::INFINITY, f64::min),
+            Function::Max => args.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            Function::Pow => args[0].powf(args[1]),
+            Function::Gauss => {
+                let (distance, decay) = decay_arguments(args);
+                decay.powf(distance * distance)
+            }
+            Function::Linear => {
+                let (distance, decay) = decay_arguments(args);
+                (1.0 - (1.0 - decay) * distance).max(0.0)
+            }
+        }
+    }
+}
+
+/// Returns the distance from the origin in number of scales and the decay of a decay function.
+fn decay_arguments(args: &[f64]) -> (f64, f64) {
+    let (value, origin, scale) = (args[0], args[1], args[2]);
+    let decay = args.get(3).copied().unwrap_or(0.5);
+    ((value - origin).abs() / scale, decay)
+}
+
+impl BoostExpression {
+    /// Returns the fields used in the expression.
+    pub fn fields(&self) -> Vec<&str> {
+        fn collect<'a>(node: &'a Node, fields: &mut Vec<&'a str>) {
+            match node {
+                Node::Number(_) | Node::Now => (),
+                Node::Field(field) => {
+                    if !fields.contains(&field.as_str()) {
+                        fields.push(field);
+                    }
+                }
+                Node::Neg(node) => collect(node, fields),
+                Node::Binary(_, left, right) => {
+                    collect(left, fields);
+                    collect(right, fields);
+                }
+                Node::Call(_, args) => args.iter().for_each(|arg| collect(arg, fields)),
+            }
+        }
+
+        let mut fields = Vec::new();
+        collect(&self.node, &mut fields);
+        fields
+    }
+
+    /// Evaluates the expression with the given values of the fields and `now`.
+    ///
+    /// Returns `None` when a field has no value or when the result is not a finite number.
+    pub fn evaluate(&self, now: f64, value: &mut impl FnMut(&str) -> Option<f64>) -> Option<f64> {
+        fn evaluate(
+            node: &Node,
+            now: f64,
+            value: &mut impl FnMut(&str) -> Option<f64>,
+        ) -> Option<f64> {
+            match node {
+                Node::Number(number) => Some(*number),
+                Node::Now => Some(now),
+                Node::Field(field) => value(field),
+                Node::Neg(node) => evaluate(node, now, value).map(|x| -x),
+                Node::Binary(operator, left, right) => {
+                    let left = evaluate(left, now, value)?;
+                    let right = evaluate(right, now, value)?;
+                    Some(match operator {
+                        Operator::Add => left + right,
+                        Operator::Sub => left - right,
+                        Operator::Mul => left * right,
+                        Operator::Div => left / right,
+                    })
+                }
+                Node::Call(function, args) => {
+                    let args = args
+                        .iter()
+                        .map(|arg| evaluate(arg, now, value))
+                        .collect::<Option<Vec<_>>>()?;
+                    Some(function.apply(&args))
+                }
+            }
+        }
+
+        evaluate(&self.node, now, value).filter(|result| result.is_finite())
+    }
+}
+
+impl FromStr for BoostExpression {
+    type Err = BoostError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = |reason: String| BoostError { expression: text.to_string(), reason };
+
+        let mut parser = Parser { chars: text.char_indices().peekable() };
+        let node = parser.expression().map_err(error)?;
+        parser.skip_whitespaces();
+        if let Some((position, c)) = parser.chars.next() {
+            return Err(error(format!("unexpected `{c}` at position {position}")));
+        }
+
+        Ok(BoostExpression { source: text.to_string(), node })
+    }
+}
+
+impl fmt::Display for BoostExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// A recursive descent parser following this grammar:
+///
+/// ```text
+/// expression = term (("+" | "-") term)*
+/// term       = factor (("*" | "/") factor)*
+/// factor     = "-" factor | number unit? | "(" expression ")" | name ("(" arguments ")")?
+/// arguments  = expression ("," expression)*
+/// ```
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespaces(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespaces();
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.chars.next();
+                Ok(())
+            }
+            Some(c) => Err(format!("expected `{expected}` but found `{c}`")),
+            None => Err(format!("expected `{expected}` but reached the end of the expression")),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Node, String> {
+        let mut node = self.term()?;
+        loop {
+            let operator = match self.peek() {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Sub,
+                _ => return Ok(node),
+            };
+            self.chars.next();
+            node = Node::Binary(operator, Box::new(node), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Node, String> {
+        let mut node = self.factor()?;
+        loop {
+            let operator = match self.peek() {
+                Some('*') => Operator::Mul,
+                Some('/') => Operator::Div,
+                _ => return Ok(node),
+            };
+            self.chars.next();
+            node = Node::Binary(operator, Box::new(node), Box::new(self.factor()?));
+        }
+    }
+
+    fn factor(&mut self) -> Result<Node, String> {
+        match self.peek() {
+            None => Err("unexpected end of the expression".to_string()),
+            Some('-') => {
+                self.chars.next();
+                Ok(Node::Neg(Box::new(self.factor()?)))
+            }
+            Some('(') => {
+                self.chars.next();
+                let node = self.expression()?;
+                self.expect(')')?;
+                Ok(node)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.number(),
+            Some(c) if c.is_alphabetic() || c == '_' => self.name(),
+            Some(c) => {
+                let (position, _) = self.chars.next().unwrap();
+                Err(format!("unexpected `{c}` at position {position}"))
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Node, String> {
+        let mut number = String::new();
+        while let Some((_, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit() || *c == '.') {
+            number.push(c);
+        }
+        let value: f64 = number.parse().map_err(|_| format!("`{number}` is not a valid number"))?;
+
+        let mut unit = String::new();
+        while let Some((_, c)) = self.chars.next_if(|(_, c)| c.is_alphanumeric()) {
+            unit.push(c);
+        }
+        let unit_seconds = match unit.as_str() {
+            "" => 1.0,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 60.0 * 60.0,
+            "d" => 24.0 * 60.0 * 60.0,
+            "w" => 7.0 * 24.0 * 60.0 * 60.0,
+            unit => {
+                return Err(format!(
+                    "unknown duration unit `{unit}` after `{number}`, expected one of `s`, `m`, `h`, `d` or `w`"
+                ))
+            }
+        };
+
+        Ok(Node::Number(value * unit_seconds))
+    }
+
+    fn name(&mut self) -> Result<Node, String> {
+        let mut name = String::new();
+        while let Some((_, c)) =
+            self.chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_' || *c == '.')
+        {
+            name.push(c);
+        }
+
+        if self.peek() != Some('(') {
+            return Ok(if name == "NOW" { Node::Now } else { Node::Field(name) });
+        }
+
+        let function = Function::from_name(&name).ok_or_else(|| {
+            format!(
+                "unknown function `{name}`, expected one of `log`, `log1p`, `sqrt`, `abs`, `exp`, `min`, `max`, `pow`, `gauss` or `linear`"
+            )
+        })?;
+        self.chars.next();
+
+        let mut args = vec![self.expression()?];
+        while self.peek() == Some(',') {
+            self.chars.next();
+            args.push(self.expression()?);
+        }
+        self.expect(')')?;
+
+        let (min, max) = function.arity();
+        if args.len() < min || args.len() > max {
+            let expected = match (min, max) {
+                (min, max) if min == max => format!("{min}"),
+                (min, usize::MAX) => format!("at least {min}"),
+                (min, max) => format!("{min} or {max}"),
+            };
+            return Err(format!(
+                "function `{name}` takes {expected} arguments but {} were given",
+                args.len()
+            ));
+        }
+
+        Ok(Node::Call(function, args))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(expression: &str, fields: &[(&str, f64)]) -> Option<f64> {
+        let expression: BoostExpression = expression.parse().unwrap();
+        expression.evaluate(1000.0, &mut |field| {
+            fields.iter().find(|(name, _)| *name == field).map(|(_, value)| *value)
+        })
+    }
+
+    #[test]
+    fn evaluate_expressions() {
+        assert_eq!(evaluate("1 + 2 * 3", &[]), Some(7.0));
+        assert_eq!(evaluate("(1 + 2) * 3", &[]), Some(9.0));
+        assert_eq!(evaluate("-2 - -3", &[]), Some(1.0));
+        assert_eq!(evaluate("8 / 2 / 2", &[]), Some(2.0));
+        assert_eq!(evaluate("1d + 1h", &[]), Some(90000.0));
+        assert_eq!(evaluate("NOW - 1m", &[]), Some(940.0));
+        // only the uppercase spelling is the current time, like in the datetime filters
+        assert_eq!(evaluate("now - 1m", &[("now", 100.0)]), Some(40.0));
+        assert_eq!(evaluate("max(1, pow(2, 3), 4) + min(5)", &[]), Some(13.0));
+        assert_eq!(evaluate("log1p(popularity) * 2", &[("popularity", 0.0)]), Some(0.0));
+        assert_eq!(evaluate("stats.views / 10", &[("stats.views", 50.0)]), Some(5.0));
+        assert_eq!(evaluate("gauss(date, NOW, 10)", &[("date", 1000.0)]), Some(1.0));
+        assert_eq!(evaluate("gauss(date, NOW, 10)", &[("date", 990.0)]), Some(0.5));
+        assert_eq!(evaluate("gauss(date, NOW, 10, 0.25)", &[("date", 1020.0)]), Some(0.00390625));
+        assert_eq!(evaluate("linear(date, NOW, 10)", &[("date", 1010.0)]), Some(0.5));
+        assert_eq!(evaluate("linear(date, NOW, 10)", &[("date", 1100.0)]), Some(0.0));
+
+        // missing fields and infinite results
+        assert_eq!(evaluate("popularity + 1", &[]), None);
+        assert_eq!(evaluate("log(popularity)", &[("popularity", 0.0)]), None);
+        assert_eq!(evaluate("1 / 0", &[]), None);
+    }
+
+    #[test]
+    fn expression_fields() {
+        let expression: BoostExpression =
+            "log1p(popularity) * gauss(publishedAt, NOW, 30d) + popularity".parse().unwrap();
+        assert_eq!(expression.fields(), vec!["popularity", "publishedAt"]);
+        assert_eq!(
+            expression.to_string(),
+            "log1p(popularity) * gauss(publishedAt, NOW, 30d) + popularity"
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error =
+            |expression: &str| expression.parse::<BoostExpression>().unwrap_err().to_string();
+
+        insta::assert_snapshot!(error(""), @"Invalid boost expression ``: unexpected end of the expression.");
+        insta::assert_snapshot!(error("1 +"), @"Invalid boost expression `1 +`: unexpected end of the expression.");
+        insta::assert_snapshot!(error("(1 + 2"), @"Invalid boost expression `(1 + 2`: expected `)` but reached the end of the expression.");
+        insta::assert_snapshot!(error("1 2"), @"Invalid boost expression `1 2`: unexpected `2` at position 2.");
+        insta::assert_snapshot!(error("a % b"), @"Invalid boost expression `a % b`: unexpected `%` at position 2.");
+        insta::assert_snapshot!(error("1.2.3"), @"Invalid boost expression `1.2.3`: `1.2.3` is not a valid number.");
+        insta::assert_snapshot!(error("30y"), @"Invalid boost expression `30y`: unknown duration unit `y` after `30`, expected one of `s`, `m`, `h`, `d` or `w`.");
+        insta::assert_snapshot!(error("pow(2)"), @"Invalid boost expression `pow(2)`: function `pow` takes 2 arguments but 1 were given.");
+        insta::assert_snapshot!(error("gauss(a, 1)"), @"Invalid boost expression `gauss(a, 1)`: function `gauss` takes 3 or 4 arguments but 2 were given.");
+        insta::assert_snapshot!(error("foo(1)"), @"Invalid boost expression `foo(1)`: unknown function `foo`, expected one of `log`, `log1p`, `sqrt`, `abs`, `exp`, `min`, `max`, `pow`, `gauss` or `linear`.");
+    }
+}
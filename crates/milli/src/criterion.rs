@@ -113,7 +113,7 @@ mod tests {
     use CriterionError::*;
 
     use super::*;
-    use crate::constants::RESERVED_GEO_FIELD_NAME;
+    use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 
     #[test]
     fn parse_criterion() {
@@ -155,6 +155,7 @@ mod tests {
             ("price:asc and desc", InvalidName { name: S("price:asc and desc") }),
             ("price:asc:truc", InvalidName { name: S("price:asc:truc") }),
             ("_geo:asc", ReservedName { name: S(RESERVED_GEO_FIELD_NAME) }),
+            ("_geojson:asc", ReservedName { name: S(RESERVED_GEOJSON_FIELD_NAME) }),
             ("_geoDistance:asc", ReservedName { name: S("_geoDistance") }),
             ("_geoPoint:asc", ReservedNameForSort { name: S("_geoPoint") }),
             ("_geoPoint(42, 75):asc", ReservedNameForSort { name: S("_geoPoint") }),
@@ -165,6 +166,16 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            ("_geoPolygon:asc", ReservedNameForFilter { name: S("_geoPolygon") }),
+            (
+                "_geoPolygon([[42, 75], [75, 59], [42, 59], [42, 75]]):asc",
+                ReservedNameForFilter { name: S("_geoPolygon") },
+            ),
+            ("_geoIntersects:asc", ReservedNameForFilter { name: S("_geoIntersects") }),
+            (
+                "_geoIntersects([[42, 75], [75, 59]]):asc",
+                ReservedNameForFilter { name: S("_geoIntersects") },
+            ),
         ];
 
         for (input, expected) in invalid_criteria {
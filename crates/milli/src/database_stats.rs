@@ -1,5 +1,6 @@
 use std::mem;
 
+use heed::types::Bytes;
 use heed::Database;
 use heed::DatabaseStat;
 use heed::RoTxn;
@@ -45,6 +46,26 @@ impl DatabaseStats {
         })
     }
 
+    /// Returns the sum of the stats of the databases.
+    ///
+    /// Contrary to [`Self::new`], the exact sizes of the keys and values are computed
+    /// by iterating over all the entries of the databases, which is even less efficient.
+    pub(crate) fn from_databases(
+        databases: impl IntoIterator<Item = Database<Bytes, Bytes>>,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Self> {
+        let mut stats = Self::default();
+        for database in databases {
+            for result in database.iter(rtxn)? {
+                let (key, value) = result?;
+                stats.number_of_entries += 1;
+                stats.total_key_size += key.len() as u64;
+                stats.total_value_size += value.len() as u64;
+            }
+        }
+        Ok(stats)
+    }
+
     pub fn average_key_size(&self) -> u64 {
         self.total_key_size.checked_div(self.number_of_entries).unwrap_or(0)
     }
@@ -0,0 +1,126 @@
+use time::format_description::well_known::{Iso8601, Rfc3339};
+use time::macros::format_description;
+use time::{Date, Duration, OffsetDateTime, PrimitiveDateTime};
+
+/// Parses the value of a datetime field into the number of seconds since the Unix epoch.
+///
+/// The value must follow RFC 3339 like `2024-01-31T12:00:00+02:00`. The offset can be omitted,
+/// in which case the datetime is considered to be in UTC, and a day like `2024-01-31`
+/// represents its midnight in UTC.
+pub fn parse_datetime(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let datetime = if let Ok(datetime) = OffsetDateTime::parse(value, &Rfc3339) {
+        datetime
+    } else if let Ok(datetime) = PrimitiveDateTime::parse(value, &Iso8601::DEFAULT) {
+        datetime.assume_utc()
+    } else if let Ok(date) = Date::parse(value, format_description!("[year]-[month]-[day]")) {
+        date.midnight().assume_utc()
+    } else {
+        return None;
+    };
+
+    Some(timestamp(datetime))
+}
+
+/// Parses a datetime written in a filter into the number of seconds since the Unix epoch.
+///
+/// On top of the values accepted by [`parse_datetime`], it accepts datetimes relative to `now`
+/// like `NOW`, `NOW-7d` or `NOW+1h`. The supported units are `s`, `m`, `h`, `d` and `w`.
+pub fn parse_datetime_expression(value: &str, now: OffsetDateTime) -> Option<f64> {
+    let value = value.trim();
+    let Some(relative) = value.strip_prefix("NOW") else {
+        return parse_datetime(value);
+    };
+
+    let offset = match relative.chars().next() {
+        None => return Some(timestamp(now)),
+        Some('+') => 1,
+        Some('-') => -1,
+        Some(_) => return None,
+    };
+
+    let relative = &relative[1..];
+    let unit = relative.chars().last()?;
+    let amount = &relative[..relative.len() - unit.len_utf8()];
+    if !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let amount: i64 = amount.parse().ok()?;
+    let unit_seconds = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    let seconds = amount.checked_mul(unit_seconds)?.checked_mul(offset)?;
+
+    now.checked_add(Duration::seconds(seconds)).map(timestamp)
+}
+
+/// Formats a number of seconds since the Unix epoch as an RFC 3339 datetime in UTC.
+pub fn format_datetime(timestamp: f64) -> Option<String> {
+    if !timestamp.is_finite() {
+        return None;
+    }
+    let nanos = (timestamp * 1_000_000_000.0).round() as i128;
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()?.format(&Rfc3339).ok()
+}
+
+fn timestamp(datetime: OffsetDateTime) -> f64 {
+    datetime.unix_timestamp_nanos() as f64 / 1_000_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn parse_datetimes() {
+        assert_eq!(parse_datetime("2024-01-01T00:00:00Z"), Some(1704067200.0));
+        assert_eq!(parse_datetime("2024-01-01T02:00:00+02:00"), Some(1704067200.0));
+        assert_eq!(parse_datetime("2024-01-01T00:00:00.5Z"), Some(1704067200.5));
+        assert_eq!(parse_datetime("2024-01-01T00:00:00"), Some(1704067200.0));
+        assert_eq!(parse_datetime("2024-01-01"), Some(1704067200.0));
+        assert_eq!(parse_datetime(" 2024-01-01 "), Some(1704067200.0));
+        assert_eq!(parse_datetime("1969-12-31T23:59:59Z"), Some(-1.0));
+
+        assert_eq!(parse_datetime("2024-13-01"), None);
+        assert_eq!(parse_datetime("2024-01-01T25:00:00Z"), None);
+        assert_eq!(parse_datetime("1704067200"), None);
+        assert_eq!(parse_datetime("yesterday"), None);
+        assert_eq!(parse_datetime(""), None);
+    }
+
+    #[test]
+    fn parse_relative_datetimes() {
+        let now = datetime!(2024-01-08 00:00:00 UTC);
+
+        assert_eq!(parse_datetime_expression("NOW", now), Some(1704672000.0));
+        assert_eq!(parse_datetime_expression("NOW-7d", now), Some(1704067200.0));
+        assert_eq!(parse_datetime_expression("NOW-1w", now), Some(1704067200.0));
+        assert_eq!(parse_datetime_expression("NOW+1h", now), Some(1704675600.0));
+        assert_eq!(parse_datetime_expression("NOW+30m", now), Some(1704673800.0));
+        assert_eq!(parse_datetime_expression("NOW-10s", now), Some(1704671990.0));
+        assert_eq!(parse_datetime_expression("2024-01-01", now), Some(1704067200.0));
+
+        assert_eq!(parse_datetime_expression("NOW-", now), None);
+        assert_eq!(parse_datetime_expression("NOW-d", now), None);
+        assert_eq!(parse_datetime_expression("NOW-7", now), None);
+        assert_eq!(parse_datetime_expression("NOW-7y", now), None);
+        assert_eq!(parse_datetime_expression("NOW7d", now), None);
+        assert_eq!(parse_datetime_expression("NOW--7d", now), None);
+        assert_eq!(parse_datetime_expression("NOW-99999999999999999d", now), None);
+    }
+
+    #[test]
+    fn format_datetimes() {
+        assert_eq!(format_datetime(1704067200.0).as_deref(), Some("2024-01-01T00:00:00Z"));
+        assert_eq!(format_datetime(1704067200.5).as_deref(), Some("2024-01-01T00:00:00.5Z"));
+        assert_eq!(format_datetime(f64::INFINITY), None);
+        assert_eq!(format_datetime(1e30), None);
+    }
+}
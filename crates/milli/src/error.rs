@@ -11,15 +11,25 @@ use rhai::EvalAltResult;
 use serde_json::Value;
 use thiserror::Error;
 
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::documents::{self, DocumentsBatchCursorError};
 use crate::thread_pool_no_abort::PanicCatched;
 use crate::vector::settings::EmbeddingSettings;
+use crate::vector::VectorQuantization;
 use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
-    [RESERVED_GEO_FIELD_NAME, "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"]
-        .contains(&keyword)
+    [
+        RESERVED_GEO_FIELD_NAME,
+        "_geoDistance",
+        "_geoPoint",
+        "_geoRadius",
+        "_geoBoundingBox",
+        "_geoPolygon",
+        "_geoIntersects",
+        RESERVED_GEOJSON_FIELD_NAME,
+    ]
+    .contains(&keyword)
 }
 
 #[derive(Error, Debug)]
@@ -152,8 +162,14 @@ and can not be more than 511 bytes.", .document_id.to_string()
     },
     #[error(transparent)]
     InvalidGeoField(#[from] Box<GeoError>),
+    #[error(transparent)]
+    InvalidGeoJsonField(#[from] Box<GeoJsonError>),
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
     InvalidVectorDimensions { expected: usize, found: usize },
+    #[error("Invalid vector dimensions: expected a concatenation of vectors of dimension `{}` for a multi-vector embedder, found: `{}`.", .expected, .found)]
+    InvalidMultiVectorDimensions { expected: usize, found: usize },
+    #[error("The embedder `{embedder_name}` generates sparse embeddings and cannot search with a `vector`.\n  - Hint: search with `q` instead.")]
+    SparseEmbedderSearchVector { embedder_name: String },
     #[error("Invalid vector dimensions in document with id `{document_id}` in `._vectors.{embedder_name}`.\n  - note: embedding #{embedding_index} has dimensions {found}\n  - note: embedder `{embedder_name}` requires {expected}")]
     InvalidIndexingVectorDimensions {
         embedder_name: String,
@@ -193,6 +209,17 @@ and can not be more than 511 bytes.", .document_id.to_string()
         }
     )]
     InvalidSortableAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` is not sortable and thus, cannot be used in a boost expression. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured sortable attributes.".to_string(),
+            false => format!("Available sortable attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidBoostAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
     #[error("Attribute `{}` is not filterable and thus, cannot be used as distinct attribute. {}",
         .field,
         match (.valid_patterns.is_empty(), .matching_rule_index) {
@@ -255,6 +282,8 @@ and can not be more than 511 bytes.", .document_id.to_string()
     EnvAlreadyOpened,
     #[error("You must specify where `sort` is listed in the rankingRules setting to use the sort parameter at search time.")]
     SortRankingRuleMissing,
+    #[error("You must specify where `sort` is listed in the rankingRules setting to use the boost parameter at search time.")]
+    BoostRankingRuleMissing,
     #[error("The database file is in an invalid state.")]
     InvalidStoreFile,
     #[error("Maximum database size has been reached.")]
@@ -287,12 +316,20 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidPrompt(#[from] crate::prompt::error::NewPromptError),
     #[error("`.embedders.{0}.documentTemplate`: Invalid template: {1}.")]
     InvalidPromptForEmbeddings(String, crate::prompt::error::NewPromptError),
+    #[error("`.embedders.{embedder_name}.{field}`: Invalid fragment: {inner}.")]
+    InvalidFragmentForEmbeddings {
+        embedder_name: String,
+        field: &'static str,
+        inner: crate::prompt::error::NewPromptError,
+    },
     #[error("Too many embedders in the configuration. Found {0}, but limited to 256.")]
     TooManyEmbedders(usize),
     #[error("Cannot find embedder with name `{0}`.")]
     InvalidSearchEmbedder(String),
     #[error("Cannot find embedder with name `{0}`.")]
     InvalidSimilarEmbedder(String),
+    #[error("Cannot find reranker with name `{0}`.")]
+    InvalidSearchReranker(String),
     #[error("Too many vectors for document with id {0}: found {1}, but limited to 256.")]
     TooManyVectors(String, usize),
     #[error("`.embedders.{embedder_name}`: Field `{field}` unavailable for source `{source_}`{for_context}.{available_sources}{available_fields}{available_contexts}",
@@ -371,10 +408,22 @@ and can not be more than 511 bytes.", .document_id.to_string()
     InvalidSettingsEmbedder { embedder_name: String, message: String },
     #[error("`.embedders.{embedder_name}.dimensions`: `dimensions` cannot be zero")]
     InvalidSettingsDimensions { embedder_name: String },
+    #[error("`.rules[{index}].filter`: {error}")]
+    InvalidQueryRuleFilter { index: usize, error: String },
+    #[error("`.rerankers.{reranker_name}`: {message}")]
+    InvalidSettingsReranker { reranker_name: String, message: String },
     #[error(
         "`.embedders.{embedder_name}.binaryQuantized`: Cannot disable the binary quantization.\n - Note: Binary quantization is a lossy operation that cannot be reverted.\n - Hint: Add a new embedder that is non-quantized and regenerate the vectors."
     )]
     InvalidDisableBinaryQuantization { embedder_name: String },
+    #[error(
+        "`.embedders.{embedder_name}.quantization`: Cannot change the quantization from {old} to {new}.\n - Note: Quantization is a lossy operation, the quantized embeddings cannot be made more precise again.\n - Hint: Add a new embedder with the desired quantization and regenerate the vectors."
+    )]
+    InvalidQuantizationChange {
+        embedder_name: String,
+        old: VectorQuantization,
+        new: VectorQuantization,
+    },
     #[error("`.embedders.{embedder_name}.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero")]
     InvalidSettingsDocumentTemplateMaxBytes { embedder_name: String },
     #[error("`.embedders.{embedder_name}.url`: could not parse `{url}`: {inner_error}")]
@@ -426,7 +475,7 @@ impl From<arroy::Error> for Error {
 
 #[derive(Error, Debug)]
 pub enum GeoError {
-    #[error("The `_geo` field in the document with the id: `{document_id}` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields but instead got `{value}`.")]
+    #[error("The `_geo` field in the document with the id: `{document_id}` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields or an array of such objects but instead got `{value}`.")]
     NotAnObject { document_id: Value, value: Value },
     #[error("The `_geo` field in the document with the id: `{document_id}` contains the following unexpected fields: `{value}`.")]
     UnexpectedExtraFields { document_id: Value, value: Value },
@@ -444,6 +493,29 @@ pub enum GeoError {
     BadLongitude { document_id: Value, value: Value },
 }
 
+#[derive(Error, Debug)]
+pub enum GeoJsonError {
+    #[error("The `_geojson` field in the document with the id: `{document_id}` is not an object. Was expecting a GeoJSON geometry object but instead got `{value}`.")]
+    NotAnObject { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` has an unsupported type `{value}`. Was expecting one of `Point`, `LineString`, `Polygon` or `MultiPolygon`.")]
+    UnsupportedType { document_id: Value, value: Value },
+    #[error("Could not find the coordinates in the `_geojson` field of the document with the id: `{document_id}`. Was expecting a `_geojson.coordinates` field.")]
+    MissingCoordinates { document_id: Value },
+    #[error("Could not parse the coordinates of the `{geometry_type}` in the `_geojson` field of the document with the id: `{document_id}`. Was expecting {expected} but instead got `{value}`.")]
+    BadCoordinates {
+        document_id: Value,
+        geometry_type: &'static str,
+        expected: &'static str,
+        value: Value,
+    },
+    #[error("Could not parse a position in the `_geojson` field of the document with the id: `{document_id}`. Was expecting a finite longitude between -180 and 180 followed by a finite latitude between -90 and 90 but instead got `{value}`.")]
+    BadPosition { document_id: Value, value: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` contains a polygon ring that is not closed. The first position `{first}` must be equal to the last position `{last}`.")]
+    UnclosedRing { document_id: Value, first: Value, last: Value },
+    #[error("The `_geojson` field in the document with the id: `{document_id}` contains a polygon ring that is self-intersecting. The edge going from `{}` to `{}` intersects the edge going from `{}` to `{}`.", .first_edge[0], .first_edge[1], .second_edge[0], .second_edge[1])]
+    SelfIntersectingRing { document_id: Value, first_edge: [Value; 2], second_edge: [Value; 2] },
+}
+
 #[allow(dead_code)]
 fn format_invalid_filter_distribution(
     invalid_facets_name: &BTreeSet<String>,
@@ -528,6 +600,7 @@ error_from_sub_error! {
     ThreadPoolBuildError => InternalError,
     SerializationError => InternalError,
     Box<GeoError> => UserError,
+    Box<GeoJsonError> => UserError,
     CriterionError => UserError,
 }
 
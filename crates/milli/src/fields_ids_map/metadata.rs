@@ -6,7 +6,9 @@ use heed::RoTxn;
 
 use super::FieldsIdsMap;
 use crate::attribute_patterns::{match_field_legacy, PatternMatch};
-use crate::constants::{RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
+use crate::constants::{
+    RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+};
 use crate::{
     is_faceted_by, FieldId, FilterableAttributesFeatures, FilterableAttributesRule, Index,
     LocalizedAttributesRule, Result, Weight,
@@ -22,7 +24,7 @@ pub struct Metadata {
     pub distinct: bool,
     /// The field has been defined as asc/desc in the ranking rules.
     pub asc_desc: bool,
-    /// The field is a geo field (`_geo`, `_geo.lat`, `_geo.lng`).
+    /// The field is a geo field (`_geo`, `_geo.lat`, `_geo.lng`, `_geojson`).
     pub geo: bool,
     /// The id of the localized attributes rule if the field is localized.
     pub localized_attributes_rule_id: Option<NonZeroU16>,
@@ -287,7 +289,9 @@ impl MetadataBuilder {
             // saturating_add(1): make `id` `NonZero`
             .map(|id| NonZeroU16::new(id.saturating_add(1).try_into().unwrap()).unwrap());
 
-        if match_field_legacy(RESERVED_GEO_FIELD_NAME, field) == PatternMatch::Match {
+        if match_field_legacy(RESERVED_GEO_FIELD_NAME, field) == PatternMatch::Match
+            || match_field_legacy(RESERVED_GEOJSON_FIELD_NAME, field) == PatternMatch::Match
+        {
             // Geo fields are not searchable, distinct or asc_desc
             return Metadata {
                 searchable: None,
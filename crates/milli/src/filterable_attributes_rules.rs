@@ -5,7 +5,7 @@ use utoipa::ToSchema;
 
 use crate::{
     attribute_patterns::{match_distinct_field, match_field_legacy, PatternMatch},
-    constants::RESERVED_GEO_FIELD_NAME,
+    constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME},
     AttributePatterns,
 };
 
@@ -35,6 +35,13 @@ impl FilterableAttributesRule {
         matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEO_FIELD_NAME)
     }
 
+    /// Check if the rule is a geojson field.
+    ///
+    /// prefer using `index.is_geojson_filtering_enabled` to check if the geojson feature is enabled.
+    pub fn has_geojson(&self) -> bool {
+        matches!(self, FilterableAttributesRule::Field(field_name) if field_name == RESERVED_GEOJSON_FIELD_NAME)
+    }
+
     /// Get the features of the rule.
     pub fn features(&self) -> FilterableAttributesFeatures {
         match self {
@@ -77,6 +84,9 @@ pub struct FilterableAttributesFeatures {
     #[serde(default)]
     #[deserr(default)]
     filter: FilterFeatures,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[deserr(default)]
+    datetime: bool,
 }
 
 impl FilterableAttributesFeatures {
@@ -85,12 +95,12 @@ impl FilterableAttributesFeatures {
     /// This is the default behavior for `FilterableAttributesRule::Field`.
     /// This will set the facet search to true and activate all the filter operators.
     pub fn legacy_default() -> Self {
-        Self { facet_search: true, filter: FilterFeatures::legacy_default() }
+        Self { facet_search: true, filter: FilterFeatures::legacy_default(), datetime: false }
     }
 
     /// Create a new `FilterableAttributesFeatures` with no features.
     pub fn no_features() -> Self {
-        Self { facet_search: false, filter: FilterFeatures::no_features() }
+        Self { facet_search: false, filter: FilterFeatures::no_features(), datetime: false }
     }
 
     pub fn is_filterable(&self) -> bool {
@@ -127,6 +137,11 @@ impl FilterableAttributesFeatures {
         self.facet_search
     }
 
+    /// Check if the string values must be parsed as dates and indexed as numbers
+    pub fn is_datetime(&self) -> bool {
+        self.datetime
+    }
+
     pub fn allowed_filter_operators(&self) -> Vec<String> {
         self.filter.allowed_operators()
     }
@@ -0,0 +1,563 @@
+use rstar::primitives::Rectangle;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::GeoJsonError;
+use crate::search::facet::geo_polygon::{segments_intersect, GeoPolygon};
+use crate::{distance_between_two_points, DocumentId, GeoJsonEnvelope, Result};
+
+/// A shape stored in the `_geojson` field of a document.
+///
+/// The positions are expressed as `[latitude, longitude]` like everywhere else in the engine,
+/// even though GeoJSON describes them as `[longitude, latitude]`. The edges of the shapes are
+/// straight lines in the latitude/longitude plane, like the ones of the `_geoPolygon` filter.
+/// The polygons crossing the antimeridian are split in two, see [`split_at_antimeridian`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GeoJsonShape {
+    Point([f64; 2]),
+    LineString(Vec<[f64; 2]>),
+    /// The first ring is the exterior of the polygon, the following ones are its holes.
+    Polygon(Vec<GeoPolygon>),
+    MultiPolygon(Vec<Vec<GeoPolygon>>),
+}
+
+impl GeoJsonShape {
+    /// Parses and validates the GeoJSON geometry of the `_geojson` field of a document.
+    ///
+    /// It can be of the form `{ "type": "Point", "coordinates": [1.0, 0.0] }`.
+    pub fn from_value(document_id: &str, value: Value) -> Result<Option<Self>> {
+        let mut geometry = match value {
+            Value::Null => return Ok(None),
+            Value::Object(geometry) => geometry,
+            value => {
+                return Err(Box::new(GeoJsonError::NotAnObject {
+                    document_id: Value::from(document_id),
+                    value,
+                })
+                .into())
+            }
+        };
+
+        let geometry_type = match geometry.remove("type") {
+            Some(Value::String(geometry_type)) => geometry_type,
+            value => {
+                return Err(Box::new(GeoJsonError::UnsupportedType {
+                    document_id: Value::from(document_id),
+                    value: value.unwrap_or(Value::Null),
+                })
+                .into())
+            }
+        };
+
+        let parser = CoordinatesParser { document_id };
+        let shape = match geometry_type.as_str() {
+            "Point" => {
+                let coordinates = parser.coordinates(&mut geometry)?;
+                GeoJsonShape::Point(parser.position(&coordinates)?)
+            }
+            "LineString" => {
+                let coordinates = parser.coordinates(&mut geometry)?;
+                GeoJsonShape::LineString(parser.line_string(&coordinates)?)
+            }
+            "Polygon" => {
+                let coordinates = parser.coordinates(&mut geometry)?;
+                GeoJsonShape::from_polygons(split_at_antimeridian(
+                    parser.polygon("Polygon", &coordinates)?,
+                ))
+            }
+            "MultiPolygon" => {
+                let coordinates = parser.coordinates(&mut geometry)?;
+                let polygons =
+                    parser.array("MultiPolygon", "an array of polygons", &coordinates)?;
+                let polygons = polygons
+                    .iter()
+                    .map(|polygon| parser.polygon("MultiPolygon", polygon))
+                    .collect::<Result<Vec<_>>>()?;
+                GeoJsonShape::MultiPolygon(
+                    polygons.into_iter().flat_map(split_at_antimeridian).collect(),
+                )
+            }
+            _ => {
+                return Err(Box::new(GeoJsonError::UnsupportedType {
+                    document_id: Value::from(document_id),
+                    value: Value::from(geometry_type),
+                })
+                .into())
+            }
+        };
+
+        Ok(Some(shape))
+    }
+
+    /// Creates a shape from a list of `[latitude, longitude]` points.
+    ///
+    /// A single point makes a point, a closed ring of at least four points makes a polygon
+    /// and anything else makes a line string.
+    pub fn from_points(mut points: Vec<[f64; 2]>) -> Self {
+        match points.as_slice() {
+            [point] => GeoJsonShape::Point(*point),
+            [first, .., last] if points.len() >= 4 && first == last => {
+                GeoJsonShape::from_polygons(split_at_antimeridian(vec![GeoPolygon::new(points)]))
+            }
+            _ => {
+                points.dedup();
+                GeoJsonShape::LineString(points)
+            }
+        }
+    }
+
+    /// Makes a polygon out of a single polygon and a multi polygon out of several ones.
+    fn from_polygons(mut polygons: Vec<Vec<GeoPolygon>>) -> Self {
+        if polygons.len() == 1 {
+            GeoJsonShape::Polygon(polygons.pop().unwrap())
+        } else {
+            GeoJsonShape::MultiPolygon(polygons)
+        }
+    }
+
+    /// Returns a rectangle covering the given bounding box.
+    ///
+    /// The bounding box may cross the antimeridian, in which case it is split in two rectangles.
+    pub fn from_bounding_box(top_right: [f64; 2], bottom_left: [f64; 2]) -> Self {
+        let rectangle = |[south, west]: [f64; 2], [north, east]: [f64; 2]| {
+            vec![GeoPolygon::new(vec![
+                [south, west],
+                [south, east],
+                [north, east],
+                [north, west],
+                [south, west],
+            ])]
+        };
+
+        let [north, east] = top_right;
+        let [south, west] = bottom_left;
+        if west > east {
+            GeoJsonShape::MultiPolygon(vec![
+                rectangle([south, west], [north, 180.0]),
+                rectangle([south, -180.0], [north, east]),
+            ])
+        } else {
+            GeoJsonShape::Polygon(rectangle(bottom_left, top_right))
+        }
+    }
+
+    /// Returns the minimum and maximum corners of the latitude/longitude bounding box of the shape.
+    pub fn bounding_box(&self) -> [[f64; 2]; 2] {
+        let mut min = [f64::MAX; 2];
+        let mut max = [f64::MIN; 2];
+        for point in self.points() {
+            min = [min[0].min(point[0]), min[1].min(point[1])];
+            max = [max[0].max(point[0]), max[1].max(point[1])];
+        }
+        [min, max]
+    }
+
+    /// Returns the bounding boxes to look for in the geojson rtree to find the shapes that may
+    /// intersect with this one, the polygons of a multi polygon get a bounding box each.
+    pub fn bounding_boxes(&self) -> Vec<[[f64; 2]; 2]> {
+        match self {
+            GeoJsonShape::MultiPolygon(polygons) => polygons
+                .iter()
+                .map(|rings| GeoJsonShape::Polygon(rings.clone()).bounding_box())
+                .collect(),
+            shape => vec![shape.bounding_box()],
+        }
+    }
+
+    /// Returns the envelope of the shape to store in the geojson rtree.
+    pub fn into_envelope(self, docid: DocumentId) -> GeoJsonEnvelope {
+        let [min, max] = self.bounding_box();
+        GeoJsonEnvelope::new(Rectangle::from_corners(min, max), (docid, self))
+    }
+
+    /// Returns `true` if the shape shares at least one point with the other shape.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let edges = self.edges();
+        let other_edges = other.edges();
+        let crossing = edges
+            .iter()
+            .any(|&(a, b)| other_edges.iter().any(|&(c, d)| segments_intersect(a, b, c, d)));
+
+        // when no edges cross, the shapes can only intersect if one of them is inside the other
+        crossing
+            || self.points().next().is_some_and(|point| other.covers(&point))
+            || other.points().next().is_some_and(|point| self.covers(&point))
+    }
+
+    /// Returns `true` if at least one point of the shape is at most `radius` meters from the center.
+    pub fn intersects_circle(&self, center: [f64; 2], radius: f64) -> bool {
+        self.covers(&center)
+            || self.edges().into_iter().any(|(a, b)| {
+                let closest = closest_point_on_segment(center, a, b);
+                distance_between_two_points(&center, &closest) <= radius
+            })
+    }
+
+    fn points(&self) -> impl Iterator<Item = [f64; 2]> + '_ {
+        let points: Box<dyn Iterator<Item = &[f64; 2]>> = match self {
+            GeoJsonShape::Point(point) => Box::new(std::iter::once(point)),
+            GeoJsonShape::LineString(points) => Box::new(points.iter()),
+            GeoJsonShape::Polygon(rings) => Box::new(rings.iter().flat_map(GeoPolygon::points)),
+            GeoJsonShape::MultiPolygon(polygons) => {
+                Box::new(polygons.iter().flatten().flat_map(GeoPolygon::points))
+            }
+        };
+        points.copied()
+    }
+
+    /// Returns every edge of the shape, a point is represented as an edge of length zero.
+    fn edges(&self) -> Vec<([f64; 2], [f64; 2])> {
+        match self {
+            GeoJsonShape::Point(point) => vec![(*point, *point)],
+            GeoJsonShape::LineString(points) => match points.as_slice() {
+                [point] => vec![(*point, *point)],
+                points => points.windows(2).map(|w| (w[0], w[1])).collect(),
+            },
+            GeoJsonShape::Polygon(rings) => rings.iter().flat_map(GeoPolygon::edges).collect(),
+            GeoJsonShape::MultiPolygon(polygons) => {
+                polygons.iter().flatten().flat_map(GeoPolygon::edges).collect()
+            }
+        }
+    }
+
+    /// Returns `true` if the point is inside the area covered by the polygons of the shape.
+    fn covers(&self, point: &[f64; 2]) -> bool {
+        let polygon_covers = |rings: &[GeoPolygon]| match rings {
+            [exterior, holes @ ..] => {
+                exterior.contains(point)
+                    && holes.iter().all(|hole| !hole.contains(point) || hole.on_boundary(point))
+            }
+            [] => false,
+        };
+
+        match self {
+            GeoJsonShape::Point(_) | GeoJsonShape::LineString(_) => false,
+            GeoJsonShape::Polygon(rings) => polygon_covers(rings),
+            GeoJsonShape::MultiPolygon(polygons) => {
+                polygons.iter().any(|rings| polygon_covers(rings))
+            }
+        }
+    }
+}
+
+/// Splits a polygon crossing the antimeridian in two polygons, one on each side of it.
+///
+/// We consider that an edge spanning more than 180° of longitude, like the `[0, 170]`-`[0, -170]`
+/// edge, goes through the antimeridian. The longitudes of such a polygon are made continuous,
+/// which puts some of them outside of the [-180, 180] range, and the polygon is copied on the
+/// other side of the antimeridian to cover the points of both sides.
+fn split_at_antimeridian(rings: Vec<GeoPolygon>) -> Vec<Vec<GeoPolygon>> {
+    let crosses_antimeridian =
+        rings.iter().flat_map(GeoPolygon::edges).any(|(a, b)| (a[1] - b[1]).abs() > 180.0);
+    let Some(exterior_lng) = rings.first().map(|exterior| exterior.points()[0][1]) else {
+        return vec![rings];
+    };
+    if !crosses_antimeridian {
+        return vec![rings];
+    }
+
+    let mut unwrapped = Vec::with_capacity(rings.len());
+    for ring in &rings {
+        let mut previous_lng = ring.points()[0][1];
+        // the holes start on the same side of the antimeridian as the exterior ring
+        let mut offset = match previous_lng - exterior_lng {
+            delta if delta > 180.0 => -360.0,
+            delta if delta < -180.0 => 360.0,
+            _ => 0.0,
+        };
+        let mut points = Vec::with_capacity(ring.points().len());
+        for &[lat, lng] in ring.points() {
+            if lng - previous_lng > 180.0 {
+                offset -= 360.0;
+            } else if lng - previous_lng < -180.0 {
+                offset += 360.0;
+            }
+            previous_lng = lng;
+            points.push([lat, lng + offset]);
+        }
+        if points.first() != points.last() {
+            // the ring goes around a pole and can't be represented in the latitude/longitude plane
+            return vec![rings];
+        }
+        unwrapped.push(GeoPolygon::new(points));
+    }
+
+    let max_lng =
+        unwrapped.iter().flat_map(GeoPolygon::points).fold(f64::MIN, |max, p| max.max(p[1]));
+    let shift = if max_lng > 180.0 { -360.0 } else { 360.0 };
+    let shifted = unwrapped
+        .iter()
+        .map(|ring| {
+            GeoPolygon::new(ring.points().iter().map(|&[lat, lng]| [lat, lng + shift]).collect())
+        })
+        .collect();
+
+    vec![unwrapped, shifted]
+}
+
+/// Returns the latitude/longitude bounding boxes containing every point that is at most
+/// `radius` meters from the center. The circle is split in two bounding boxes when it
+/// crosses the antimeridian.
+pub fn circle_bounding_boxes(center: [f64; 2], radius: f64) -> Vec<[[f64; 2]; 2]> {
+    // We use the smallest radius of the earth to make sure the boxes are never too small.
+    const EARTH_POLAR_RADIUS: f64 = 6_356_752.0;
+
+    let [lat, lng] = center;
+    let angle = radius / EARTH_POLAR_RADIUS;
+    let delta_lat = angle.to_degrees();
+    let (min_lat, max_lat) = (lat - delta_lat, lat + delta_lat);
+    if min_lat <= -90.0 || max_lat >= 90.0 || angle >= std::f64::consts::FRAC_PI_2 {
+        // the circle contains one of the poles and thus every longitude
+        return vec![[[min_lat.max(-90.0), -180.0], [max_lat.min(90.0), 180.0]]];
+    }
+
+    let delta_lng = (angle.sin() / lat.to_radians().cos()).min(1.0).asin().to_degrees();
+    let (min_lng, max_lng) = (lng - delta_lng, lng + delta_lng);
+    if min_lng < -180.0 {
+        vec![
+            [[min_lat, min_lng + 360.0], [max_lat, 180.0]],
+            [[min_lat, -180.0], [max_lat, max_lng]],
+        ]
+    } else if max_lng > 180.0 {
+        vec![
+            [[min_lat, min_lng], [max_lat, 180.0]],
+            [[min_lat, -180.0], [max_lat, max_lng - 360.0]],
+        ]
+    } else {
+        vec![[[min_lat, min_lng], [max_lat, max_lng]]]
+    }
+}
+
+/// Returns the point of the `a`-`b` segment that is the closest to `point`.
+///
+/// The longitudes are scaled by the cosine of the latitude of the point, which
+/// is a good approximation as long as the segment is not too far from the point.
+fn closest_point_on_segment(point: [f64; 2], a: [f64; 2], b: [f64; 2]) -> [f64; 2] {
+    let scale = point[0].to_radians().cos();
+    let ab = [b[0] - a[0], (b[1] - a[1]) * scale];
+    let ap = [point[0] - a[0], (point[1] - a[1]) * scale];
+    let length = ab[0] * ab[0] + ab[1] * ab[1];
+    if length == 0.0 {
+        return a;
+    }
+    let t = ((ap[0] * ab[0] + ap[1] * ab[1]) / length).clamp(0.0, 1.0);
+    [a[0] + t * (b[0] - a[0]), a[1] + t * (b[1] - a[1])]
+}
+
+struct CoordinatesParser<'a> {
+    document_id: &'a str,
+}
+
+impl CoordinatesParser<'_> {
+    fn coordinates(&self, geometry: &mut serde_json::Map<String, Value>) -> Result<Value> {
+        geometry.remove("coordinates").ok_or_else(|| {
+            Box::new(GeoJsonError::MissingCoordinates {
+                document_id: Value::from(self.document_id),
+            })
+            .into()
+        })
+    }
+
+    fn array<'v>(
+        &self,
+        geometry_type: &'static str,
+        expected: &'static str,
+        value: &'v Value,
+    ) -> Result<&'v [Value]> {
+        match value {
+            Value::Array(values) if !values.is_empty() => Ok(values),
+            value => Err(Box::new(GeoJsonError::BadCoordinates {
+                document_id: Value::from(self.document_id),
+                geometry_type,
+                expected,
+                value: value.clone(),
+            })
+            .into()),
+        }
+    }
+
+    /// Parses a `[longitude, latitude]` position, an optional altitude is ignored.
+    fn position(&self, value: &Value) -> Result<[f64; 2]> {
+        let position = match value.as_array().map(Vec::as_slice) {
+            Some([lng, lat] | [lng, lat, _]) => lat.as_f64().zip(lng.as_f64()),
+            _ => None,
+        };
+
+        match position {
+            Some((lat, lng))
+                if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lng) =>
+            {
+                Ok([lat, lng])
+            }
+            _ => Err(Box::new(GeoJsonError::BadPosition {
+                document_id: Value::from(self.document_id),
+                value: value.clone(),
+            })
+            .into()),
+        }
+    }
+
+    fn line_string(&self, value: &Value) -> Result<Vec<[f64; 2]>> {
+        let expected = "an array of at least two positions";
+        let positions = self.array("LineString", expected, value)?;
+        if positions.len() < 2 {
+            return Err(Box::new(GeoJsonError::BadCoordinates {
+                document_id: Value::from(self.document_id),
+                geometry_type: "LineString",
+                expected,
+                value: value.clone(),
+            })
+            .into());
+        }
+        positions.iter().map(|position| self.position(position)).collect()
+    }
+
+    fn polygon(&self, geometry_type: &'static str, value: &Value) -> Result<Vec<GeoPolygon>> {
+        let expected = "an array of linear rings made of at least four positions";
+        let rings = self.array(geometry_type, expected, value)?;
+        rings
+            .iter()
+            .map(|ring| {
+                let positions = self.array(geometry_type, expected, ring)?;
+                let ring: Vec<_> = positions
+                    .iter()
+                    .map(|position| self.position(position))
+                    .collect::<Result<_>>()?;
+                if ring.len() < 4 {
+                    return Err(Box::new(GeoJsonError::BadCoordinates {
+                        document_id: Value::from(self.document_id),
+                        geometry_type,
+                        expected,
+                        value: Value::from(positions.to_vec()),
+                    })
+                    .into());
+                }
+                if ring.first() != ring.last() {
+                    return Err(Box::new(GeoJsonError::UnclosedRing {
+                        document_id: Value::from(self.document_id),
+                        first: positions[0].clone(),
+                        last: positions[positions.len() - 1].clone(),
+                    })
+                    .into());
+                }
+                let polygon = GeoPolygon::new(ring);
+                if let Some((i, j)) = polygon.self_intersection() {
+                    return Err(Box::new(GeoJsonError::SelfIntersectingRing {
+                        document_id: Value::from(self.document_id),
+                        first_edge: [positions[i].clone(), positions[i + 1].clone()],
+                        second_edge: [positions[j].clone(), positions[j + 1].clone()],
+                    })
+                    .into());
+                }
+                Ok(polygon)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn shape(value: Value) -> GeoJsonShape {
+        GeoJsonShape::from_value("1", value).unwrap().unwrap()
+    }
+
+    #[test]
+    fn parse_geometries() {
+        assert!(matches!(
+            shape(json!({ "type": "Point", "coordinates": [2.35, 48.86] })),
+            GeoJsonShape::Point([48.86, 2.35])
+        ));
+        assert!(matches!(
+            shape(json!({ "type": "LineString", "coordinates": [[0, 0], [1, 1], [2, 1, 100]] })),
+            GeoJsonShape::LineString(points) if points == [[0.0, 0.0], [1.0, 1.0], [1.0, 2.0]]
+        ));
+        assert!(matches!(
+            shape(json!({ "type": "Polygon", "coordinates": [
+                [[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+                [[2, 2], [4, 2], [4, 4], [2, 4], [2, 2]],
+            ] })),
+            GeoJsonShape::Polygon(rings) if rings.len() == 2
+        ));
+        assert!(GeoJsonShape::from_value("1", Value::Null).unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |value| GeoJsonShape::from_value("1", value).unwrap_err().to_string();
+
+        insta::assert_snapshot!(error(json!([1, 2])), @r#"The `_geojson` field in the document with the id: `"1"` is not an object. Was expecting a GeoJSON geometry object but instead got `[1,2]`."#);
+        insta::assert_snapshot!(error(json!({ "type": "Circle", "coordinates": [1, 2] })), @r#"The `_geojson` field in the document with the id: `"1"` has an unsupported type `"Circle"`. Was expecting one of `Point`, `LineString`, `Polygon` or `MultiPolygon`."#);
+        insta::assert_snapshot!(error(json!({ "type": "Point" })), @r#"Could not find the coordinates in the `_geojson` field of the document with the id: `"1"`. Was expecting a `_geojson.coordinates` field."#);
+        insta::assert_snapshot!(error(json!({ "type": "Point", "coordinates": [200, 2] })), @r#"Could not parse a position in the `_geojson` field of the document with the id: `"1"`. Was expecting a finite longitude between -180 and 180 followed by a finite latitude between -90 and 90 but instead got `[200,2]`."#);
+        insta::assert_snapshot!(error(json!({ "type": "LineString", "coordinates": [[1, 2]] })), @r#"Could not parse the coordinates of the `LineString` in the `_geojson` field of the document with the id: `"1"`. Was expecting an array of at least two positions but instead got `[[1,2]]`."#);
+        insta::assert_snapshot!(error(json!({ "type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]] })), @r#"The `_geojson` field in the document with the id: `"1"` contains a polygon ring that is not closed. The first position `[0,0]` must be equal to the last position `[0,1]`."#);
+        insta::assert_snapshot!(error(json!({ "type": "MultiPolygon", "coordinates": [[[[0, 0], [10, 10], [10, 0], [0, 10], [0, 0]]]] })), @r#"The `_geojson` field in the document with the id: `"1"` contains a polygon ring that is self-intersecting. The edge going from `[0,0]` to `[10,10]` intersects the edge going from `[10,0]` to `[0,10]`."#);
+    }
+
+    #[test]
+    fn intersections() {
+        let square = shape(json!({ "type": "Polygon", "coordinates": [
+            [[0, 0], [10, 0], [10, 10], [0, 10], [0, 0]],
+            [[2, 2], [8, 2], [8, 8], [2, 8], [2, 2]],
+        ] }));
+
+        // a point in the hole, on the border of the hole and in the polygon
+        assert!(!square.intersects(&GeoJsonShape::Point([5.0, 5.0])));
+        assert!(square.intersects(&GeoJsonShape::Point([2.0, 5.0])));
+        assert!(square.intersects(&GeoJsonShape::Point([1.0, 5.0])));
+
+        // a line crossing the polygon and a line staying in the hole
+        assert!(square.intersects(&GeoJsonShape::LineString(vec![[5.0, -5.0], [5.0, 15.0]])));
+        assert!(!square.intersects(&GeoJsonShape::LineString(vec![[4.0, 4.0], [6.0, 6.0]])));
+
+        // a polygon covering the whole square
+        let big = GeoJsonShape::from_bounding_box([20.0, 20.0], [-20.0, -20.0]);
+        assert!(square.intersects(&big));
+        assert!(big.intersects(&square));
+
+        // a bounding box crossing the antimeridian
+        let bbox = GeoJsonShape::from_bounding_box([10.0, -170.0], [-10.0, 170.0]);
+        assert!(bbox.intersects(&GeoJsonShape::Point([0.0, 175.0])));
+        assert!(bbox.intersects(&GeoJsonShape::Point([0.0, -175.0])));
+        assert!(!bbox.intersects(&GeoJsonShape::Point([0.0, 0.0])));
+    }
+
+    #[test]
+    fn circle_intersections() {
+        // a line going from Paris to Berlin
+        let line = GeoJsonShape::LineString(vec![[48.8566, 2.3522], [52.52, 13.405]]);
+        // Brussels is around 200km from the line and Lyon around 400km from Paris
+        assert!(line.intersects_circle([50.8503, 4.3517], 250_000.0));
+        assert!(!line.intersects_circle([50.8503, 4.3517], 100_000.0));
+        assert!(!line.intersects_circle([45.764, 4.8357], 350_000.0));
+        assert!(line.intersects_circle([45.764, 4.8357], 450_000.0));
+
+        let polygon = GeoJsonShape::from_bounding_box([10.0, 10.0], [-10.0, -10.0]);
+        assert!(polygon.intersects_circle([0.0, 0.0], 1.0));
+    }
+
+    #[test]
+    fn circle_bounding_boxes_contain_the_circle() {
+        for (center, radius) in
+            [([48.8566, 2.3522], 500_000.0), ([-30.0, 179.0], 300_000.0), ([85.0, 10.0], 800_000.0)]
+        {
+            let boxes = circle_bounding_boxes(center, radius);
+            for lat in (-900..=900).map(|lat| lat as f64 / 10.0) {
+                for lng in (-1800..=1800).step_by(5).map(|lng| lng as f64 / 10.0) {
+                    if distance_between_two_points(&center, &[lat, lng]) <= radius {
+                        assert!(
+                            boxes.iter().any(|[min, max]| (min[0]..=max[0]).contains(&lat)
+                                && (min[1]..=max[1]).contains(&lng)),
+                            "{lat} {lng} is not in {boxes:?}"
+                        );
+                    }
+                }
+            }
+        }
+        assert_eq!(circle_bounding_boxes([-30.0, 179.0], 300_000.0).len(), 2);
+    }
+}
@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+use std::convert::TryInto;
+use std::mem::size_of;
+use std::str;
+
+use heed::BoxedError;
+
+use super::SliceTooShortError;
+
+/// Encodes a `(u8, &str, u32)` as the `u8`, the string, a NUL byte and the big-endian `u32`.
+///
+/// The NUL byte ensures that iterating over the prefix of a string never yields
+/// the entries of a longer string starting with the same bytes.
+pub struct U8StrBEU32Codec;
+
+impl<'a> heed::BytesDecode<'a> for U8StrBEU32Codec {
+    type DItem = (u8, &'a str, u32);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let footer_len = size_of::<u32>();
+
+        let (n, bytes) = bytes.split_first().ok_or(SliceTooShortError)?;
+        if bytes.len() < footer_len + 1 {
+            return Err(SliceTooShortError.into());
+        }
+
+        let (word_plus_nul_byte, bytes) = bytes.split_at(bytes.len() - footer_len);
+        // unwrap: we just checked the footer + 1 above.
+        let (_, word) = word_plus_nul_byte.split_last().unwrap();
+        let word = str::from_utf8(word)?;
+        let pos = bytes.try_into().map(u32::from_be_bytes)?;
+
+        Ok((*n, word, pos))
+    }
+}
+
+impl<'a> heed::BytesEncode<'a> for U8StrBEU32Codec {
+    type EItem = (u8, &'a str, u32);
+
+    fn bytes_encode((n, word, pos): &Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let pos = pos.to_be_bytes();
+
+        let mut bytes = Vec::with_capacity(1 + word.len() + 1 + pos.len());
+        bytes.push(*n);
+        bytes.extend_from_slice(word.as_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&pos[..]);
+
+        Ok(Cow::Owned(bytes))
+    }
+}
+
+/// Encodes a `(u8, u32)` as the `u8` followed by the big-endian `u32`.
+pub struct U8BEU32Codec;
+
+impl<'a> heed::BytesDecode<'a> for U8BEU32Codec {
+    type DItem = (u8, u32);
+
+    fn bytes_decode(bytes: &'a [u8]) -> Result<Self::DItem, BoxedError> {
+        let (n, bytes) = bytes.split_first().ok_or(SliceTooShortError)?;
+        let pos = bytes.try_into().map(u32::from_be_bytes)?;
+        Ok((*n, pos))
+    }
+}
+
+impl<'a> heed::BytesEncode<'a> for U8BEU32Codec {
+    type EItem = (u8, u32);
+
+    fn bytes_encode((n, pos): &Self::EItem) -> Result<Cow<'a, [u8]>, BoxedError> {
+        let mut bytes = Vec::with_capacity(1 + size_of::<u32>());
+        bytes.push(*n);
+        bytes.extend_from_slice(&pos.to_be_bytes());
+        Ok(Cow::Owned(bytes))
+    }
+}
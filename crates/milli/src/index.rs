@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::File;
+use std::ops::Range;
 use std::path::Path;
 
 use heed::{types::*, DatabaseStat, WithoutTls};
@@ -21,16 +22,19 @@ use crate::heed_codec::facet::{
     FieldIdCodec, OrderedF64Codec,
 };
 use crate::heed_codec::version::VersionCodec;
-use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
+use crate::heed_codec::{
+    BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec, U8BEU32Codec, U8StrBEU32Codec,
+};
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
-use crate::vector::{ArroyStats, ArroyWrapper, Embedding, EmbeddingConfig};
+use crate::vector::rerank::RerankerSettings;
+use crate::vector::{ArroyStats, ArroyWrapper, Embedding, EmbeddingConfig, SparseEmbedding};
 use crate::{
     default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
     FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
-    FieldidsWeightsMap, FilterableAttributesRule, GeoPoint, LocalizedAttributesRule, ObkvCodec,
-    Result, RoaringBitmapCodec, RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32,
-    BEU64,
+    FieldidsWeightsMap, FilterableAttributesRule, GeoJsonEnvelope, GeoPoint,
+    LocalizedAttributesRule, ObkvCodec, QueryRule, Result, RoaringBitmapCodec,
+    RoaringBitmapLenCodec, Search, U8StrStrCodec, Weight, BEU16, BEU32, BEU64,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -50,6 +54,7 @@ pub mod main_key {
     pub const FIELDIDS_WEIGHTS_MAP_KEY: &str = "fieldids-weights-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
+    pub const GEOJSON_RTREE_KEY: &str = "geojson-rtree";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
     pub const SEARCHABLE_FIELDS_KEY: &str = "searchable-fields";
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
@@ -75,7 +80,11 @@ pub mod main_key {
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
     pub const SEARCH_CUTOFF: &str = "search_cutoff";
     pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
+    pub const QUERY_RULES: &str = "query_rules";
+    pub const RERANKERS: &str = "rerankers";
     pub const FACET_SEARCH: &str = "facet_search";
+    pub const KEYWORD_SEARCH: &str = "keyword_search";
+    pub const KEYWORD_SEARCH_SAVED_STATS: &str = "keyword_search_saved_stats";
     pub const PREFIX_SEARCH: &str = "prefix_search";
     pub const DOCUMENTS_STATS: &str = "documents_stats";
 }
@@ -105,6 +114,10 @@ pub mod db_name {
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
     pub const VECTOR_ARROY: &str = "vector-arroy";
+    pub const VECTOR_SPARSE_POSTINGS: &str = "vector-sparse-postings";
+    pub const VECTOR_SPARSE_DOCUMENTS: &str = "vector-sparse-documents";
+    pub const VECTOR_RESCORING: &str = "vector-rescoring";
+    pub const VECTOR_CHUNKS: &str = "vector-chunks";
     pub const DOCUMENTS: &str = "documents";
 }
 
@@ -171,6 +184,14 @@ pub struct Index {
     pub embedder_category_id: Database<Str, U8>,
     /// Vector store based on arroy™.
     pub vector_arroy: arroy::Database<Unspecified>,
+    /// Maps the embedder id, a token and a document id with the weight of the token in the sparse embedding of the document.
+    pub vector_sparse_postings: Database<U8StrBEU32Codec, BEU32>,
+    /// Maps the embedder id and a document id with the sparse embedding of the document.
+    pub vector_sparse_documents: Database<U8BEU32Codec, SerdeJson<SparseEmbedding>>,
+    /// Maps the embedder id and a document id with the embeddings used to rescore the document when the embedder is quantized.
+    pub vector_rescoring: Database<U8BEU32Codec, Bytes>,
+    /// Maps the embedder id and a document id with the byte ranges of the chunks embedded for the document.
+    pub vector_chunks: Database<U8BEU32Codec, SerdeJson<Vec<Range<usize>>>>,
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
@@ -186,7 +207,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(29);
 
         let env = unsafe { options.open(path) }?;
         let mut wtxn = env.write_txn()?;
@@ -228,6 +249,12 @@ impl Index {
         let embedder_category_id =
             env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_CATEGORY_ID))?;
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
+        let vector_sparse_postings =
+            env.create_database(&mut wtxn, Some(VECTOR_SPARSE_POSTINGS))?;
+        let vector_sparse_documents =
+            env.create_database(&mut wtxn, Some(VECTOR_SPARSE_DOCUMENTS))?;
+        let vector_rescoring = env.create_database(&mut wtxn, Some(VECTOR_RESCORING))?;
+        let vector_chunks = env.create_database(&mut wtxn, Some(VECTOR_CHUNKS))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
 
@@ -255,6 +282,10 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            vector_sparse_postings,
+            vector_sparse_documents,
+            vector_rescoring,
+            vector_chunks,
             embedder_category_id,
             documents,
         };
@@ -599,6 +630,38 @@ impl Index {
         }
     }
 
+    /* geojson rtree */
+
+    /// Writes the provided `rtree` which associates the envelopes of the geojson shapes to documents ids.
+    pub(crate) fn put_geojson_rtree(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        rtree: &RTree<GeoJsonEnvelope>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<RTree<GeoJsonEnvelope>>>().put(
+            wtxn,
+            main_key::GEOJSON_RTREE_KEY,
+            rtree,
+        )
+    }
+
+    /// Delete the `rtree` which associates the envelopes of the geojson shapes to documents ids.
+    pub(crate) fn delete_geojson_rtree(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::GEOJSON_RTREE_KEY)
+    }
+
+    /// Returns the `rtree` which associates the envelopes of the geojson shapes to documents ids.
+    pub fn geojson_rtree(&self, rtxn: &RoTxn<'_>) -> Result<Option<RTree<GeoJsonEnvelope>>> {
+        match self
+            .main
+            .remap_types::<Str, SerdeBincode<RTree<GeoJsonEnvelope>>>()
+            .get(rtxn, main_key::GEOJSON_RTREE_KEY)?
+        {
+            Some(rtree) => Ok(Some(rtree)),
+            None => Ok(None),
+        }
+    }
+
     /* geo faceted */
 
     /// Writes the documents ids that are faceted with a _geo field.
@@ -947,6 +1010,13 @@ impl Index {
         Ok(geo_filter)
     }
 
+    /// Returns true if the geojson filtering feature is enabled.
+    pub fn is_geojson_filtering_enabled(&self, rtxn: &RoTxn<'_>) -> Result<bool> {
+        let geojson_filter =
+            self.filterable_attributes_rules(rtxn)?.iter().any(|field| field.has_geojson());
+        Ok(geojson_filter)
+    }
+
     pub fn asc_desc_fields(&self, rtxn: &RoTxn<'_>) -> Result<HashSet<String>> {
         let asc_desc_fields = self
             .criteria(rtxn)?
@@ -1623,6 +1693,70 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::FACET_SEARCH)
     }
 
+    /// Returns `false` if the words of the documents are not indexed, making the index usable
+    /// for semantic search only.
+    pub fn keyword_search(&self, txn: &RoTxn<'_>) -> heed::Result<bool> {
+        self.main
+            .remap_types::<Str, SerdeBincode<bool>>()
+            .get(txn, main_key::KEYWORD_SEARCH)
+            .map(|v| v.unwrap_or(true))
+    }
+
+    pub(crate) fn put_keyword_search(&self, txn: &mut RwTxn<'_>, val: bool) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeBincode<bool>>().put(txn, main_key::KEYWORD_SEARCH, &val)
+    }
+
+    pub(crate) fn delete_keyword_search(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::KEYWORD_SEARCH)
+    }
+
+    /// The databases that are only used by the keyword search.
+    pub(crate) fn keyword_search_databases(&self) -> [Database<Bytes, Bytes>; 10] {
+        [
+            self.word_docids.remap_types(),
+            self.exact_word_docids.remap_types(),
+            self.word_prefix_docids.remap_types(),
+            self.exact_word_prefix_docids.remap_types(),
+            self.word_pair_proximity_docids.remap_types(),
+            self.word_position_docids.remap_types(),
+            self.word_fid_docids.remap_types(),
+            self.field_id_word_count_docids.remap_types(),
+            self.word_prefix_position_docids.remap_types(),
+            self.word_prefix_fid_docids.remap_types(),
+        ]
+    }
+
+    /// Writes the stats of the keyword search databases, cleared when the keyword search was disabled.
+    pub(crate) fn put_keyword_search_saved_stats(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        stats: DatabaseStats,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<DatabaseStats>>().put(
+            wtxn,
+            main_key::KEYWORD_SEARCH_SAVED_STATS,
+            &stats,
+        )
+    }
+
+    /// Returns the stats of the keyword search databases at the time the keyword search was disabled,
+    /// i.e. the size saved by not indexing the words of the documents.
+    pub fn keyword_search_saved_stats(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Option<DatabaseStats>> {
+        self.main
+            .remap_types::<Str, SerdeJson<DatabaseStats>>()
+            .get(rtxn, main_key::KEYWORD_SEARCH_SAVED_STATS)
+    }
+
+    pub(crate) fn delete_keyword_search_saved_stats(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+    ) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::KEYWORD_SEARCH_SAVED_STATS)
+    }
+
     pub fn localized_attributes_rules(
         &self,
         rtxn: &RoTxn<'_>,
@@ -1651,6 +1785,51 @@ impl Index {
         self.main.remap_key_type::<Str>().delete(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
     }
 
+    pub fn query_rules(&self, rtxn: &RoTxn<'_>) -> heed::Result<Option<Vec<QueryRule>>> {
+        self.main.remap_types::<Str, SerdeJson<Vec<QueryRule>>>().get(rtxn, main_key::QUERY_RULES)
+    }
+
+    pub(crate) fn put_query_rules(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: Vec<QueryRule>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<Vec<QueryRule>>>().put(
+            txn,
+            main_key::QUERY_RULES,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_query_rules(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::QUERY_RULES)
+    }
+
+    pub fn rerankers(
+        &self,
+        rtxn: &RoTxn<'_>,
+    ) -> heed::Result<Option<BTreeMap<String, RerankerSettings>>> {
+        self.main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, RerankerSettings>>>()
+            .get(rtxn, main_key::RERANKERS)
+    }
+
+    pub(crate) fn put_rerankers(
+        &self,
+        txn: &mut RwTxn<'_>,
+        val: BTreeMap<String, RerankerSettings>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BTreeMap<String, RerankerSettings>>>().put(
+            txn,
+            main_key::RERANKERS,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_rerankers(&self, txn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(txn, main_key::RERANKERS)
+    }
+
     /// Put the embedding configs:
     /// 1. The name of the embedder
     /// 2. The configuration option for this embedder
@@ -1700,8 +1879,7 @@ impl Index {
         let embedding_configs = self.embedding_configs(rtxn)?;
         for config in embedding_configs {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
-            let reader =
-                ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
+            let reader = ArroyWrapper::new(self, embedder_id, config.config.quantization());
             let embeddings = reader.item_vectors(rtxn, docid)?;
             res.insert(config.name.to_owned(), embeddings);
         }
@@ -1718,8 +1896,7 @@ impl Index {
         let embedding_configs = self.embedding_configs(rtxn)?;
         for config in embedding_configs {
             let embedder_id = self.embedder_category_id.get(rtxn, &config.name)?.unwrap();
-            let reader =
-                ArroyWrapper::new(self.vector_arroy, embedder_id, config.config.quantized());
+            let reader = ArroyWrapper::new(self, embedder_id, config.config.quantization());
             reader.aggregate_stats(rtxn, &mut stats)?;
         }
         Ok(stats)
@@ -1764,6 +1941,10 @@ impl Index {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            vector_sparse_postings,
+            vector_sparse_documents,
+            vector_rescoring,
+            vector_chunks,
             embedder_category_id,
             documents,
         } = self;
@@ -1835,6 +2016,14 @@ impl Index {
             field_id_docid_facet_strings.stat(rtxn).map(compute_size)?,
         );
         sizes.insert("vector_arroy", vector_arroy.stat(rtxn).map(compute_size)?);
+        sizes
+            .insert("vector_sparse_postings", vector_sparse_postings.stat(rtxn).map(compute_size)?);
+        sizes.insert(
+            "vector_sparse_documents",
+            vector_sparse_documents.stat(rtxn).map(compute_size)?,
+        );
+        sizes.insert("vector_rescoring", vector_rescoring.stat(rtxn).map(compute_size)?);
+        sizes.insert("vector_chunks", vector_chunks.stat(rtxn).map(compute_size)?);
         sizes.insert("embedder_category_id", embedder_category_id.stat(rtxn).map(compute_size)?);
         sizes.insert("documents", documents.stat(rtxn).map(compute_size)?);
 
@@ -2476,6 +2665,151 @@ pub(crate) mod tests {
         insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2, 4, 5]>");
     }
 
+    #[test]
+    fn test_string_operators() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                    "doggo".to_string(),
+                )]);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "doggo": "Kefir" },
+                { "id": 1, "doggo": "kefirounet" },
+                { "id": 2, "doggo": "kefkef" },
+                { "id": 3, "doggo": "fifir" },
+                { "id": 4, "doggo": ["boubou", "KEFIR"] },
+                { "id": 5 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let candidates = |filter: &str| {
+            let mut search = index.search(&rtxn);
+            search.filter(Filter::from_str(filter).unwrap().unwrap()).execute().unwrap().candidates
+        };
+
+        insta::assert_debug_snapshot!(candidates("doggo ENDS WITH fir"), @"RoaringBitmap<[0, 3, 4]>");
+        insta::assert_debug_snapshot!(candidates("doggo NOT ENDS WITH FIR"), @"RoaringBitmap<[1, 2, 5]>");
+        insta::assert_debug_snapshot!(candidates("doggo STARTS WITH kef"), @"RoaringBitmap<[0, 1, 2, 4]>");
+        insta::assert_debug_snapshot!(candidates("doggo MATCHES /^k.f(ir)?$/"), @"RoaringBitmap<[0, 4]>");
+        insta::assert_debug_snapshot!(candidates("doggo MATCHES /(kef){2}|^bou/"), @"RoaringBitmap<[2, 4]>");
+        insta::assert_debug_snapshot!(candidates("doggo NOT MATCHES /fir/"), @"RoaringBitmap<[2, 5]>");
+
+        insta::assert_debug_snapshot!(candidates("doggo CONTAINS kefir CASE SENSITIVE"), @"RoaringBitmap<[1]>");
+        insta::assert_debug_snapshot!(candidates("doggo CONTAINS kefir CASE INSENSITIVE"), @"RoaringBitmap<[0, 1, 4]>");
+        insta::assert_debug_snapshot!(candidates("doggo STARTS WITH K CASE SENSITIVE"), @"RoaringBitmap<[0, 4]>");
+        insta::assert_debug_snapshot!(candidates("doggo ENDS WITH IR CASE SENSITIVE"), @"RoaringBitmap<[4]>");
+        insta::assert_debug_snapshot!(candidates("doggo MATCHES /^[A-Z]/ CASE SENSITIVE"), @"RoaringBitmap<[0, 4]>");
+
+        let filter = Filter::from_str("doggo MATCHES /kef(ir/").unwrap().unwrap();
+        let error = index.search(&rtxn).filter(filter).execute().unwrap_err();
+        insta::assert_snapshot!(error, @r###"
+        Invalid regular expression: unclosed group.
+        16:22 doggo MATCHES /kef(ir/
+        "###);
+    }
+
+    #[test]
+    fn test_datetime_facets() {
+        use crate::{AscDesc, Member, OrderBy};
+
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings
+                    .set_filterable_fields(vec![FilterableAttributesRule::Field(S("publishedAt"))]);
+                settings.set_sortable_fields(HashSet::from([S("publishedAt")]));
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "publishedAt": "2024-01-01" },
+                { "id": 1, "publishedAt": "2024-01-15T12:00:00Z" },
+                { "id": 2, "publishedAt": "2024-02-01T00:00:00+01:00" },
+                { "id": 3, "publishedAt": "not a date" },
+                { "id": 4, "publishedAt": ["2023-12-31T23:59:59Z", "2024-03-01"] },
+                { "id": 5 },
+            ]))
+            .unwrap();
+
+        let candidates = |filter: &str| {
+            let rtxn = index.read_txn().unwrap();
+            let mut search = index.search(&rtxn);
+            search.filter(Filter::from_str(filter).unwrap().unwrap()).execute().unwrap().candidates
+        };
+
+        // The datetimes are compared as strings until the field is declared as a datetime
+        insta::assert_debug_snapshot!(candidates("publishedAt = '2024-01-15T13:00:00+01:00'"), @"RoaringBitmap<[]>");
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![serde_json::from_value(serde_json::json!({
+                    "attributePatterns": ["publishedAt"],
+                    "features": { "datetime": true, "filter": { "comparison": true } },
+                }))
+                .unwrap()]);
+            })
+            .unwrap();
+
+        insta::assert_debug_snapshot!(candidates("publishedAt = '2024-01-15T13:00:00+01:00'"), @"RoaringBitmap<[1]>");
+        insta::assert_debug_snapshot!(candidates("publishedAt > 2024-01-01"), @"RoaringBitmap<[1, 2, 4]>");
+        insta::assert_debug_snapshot!(candidates("publishedAt >= '2024-01-01'"), @"RoaringBitmap<[0, 1, 2, 4]>");
+        insta::assert_debug_snapshot!(candidates("publishedAt '2024-01-01' TO '2024-01-31'"), @"RoaringBitmap<[0, 1]>");
+        insta::assert_debug_snapshot!(candidates("publishedAt < NOW"), @"RoaringBitmap<[0, 1, 2, 4]>");
+        insta::assert_debug_snapshot!(candidates("publishedAt < 'NOW-7d'"), @"RoaringBitmap<[0, 1, 2, 4]>");
+        insta::assert_debug_snapshot!(candidates("publishedAt = 1704067200"), @"RoaringBitmap<[0]>");
+        insta::assert_debug_snapshot!(candidates("publishedAt = 'not a date'"), @"RoaringBitmap<[3]>");
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("publishedAt > yesterday").unwrap().unwrap();
+        let error = index.search(&rtxn).filter(filter).execute().unwrap_err();
+        insta::assert_snapshot!(error, @r###"
+        Invalid datetime. A datetime must follow RFC 3339 like `2024-01-31T12:00:00Z`, be a day like `2024-01-31`, be relative to the current time like `NOW-7d`, or be a number of seconds since the Unix epoch.
+        15:24 publishedAt > yesterday
+        "###);
+
+        let mut search = index.search(&rtxn);
+        search.sort_criteria(vec![AscDesc::Asc(Member::Field(S("publishedAt")))]);
+        let SearchResult { documents_ids, .. } = search.execute().unwrap();
+        insta::assert_debug_snapshot!(documents_ids, @"[4, 0, 1, 2, 3, 5]");
+
+        let distribution = index
+            .facets_distribution(&rtxn)
+            .facets(vec![("publishedAt", OrderBy::Lexicographic)])
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(distribution, @r###"
+        {
+            "publishedAt": {
+                "2023-12-31T23:59:59Z": 1,
+                "2024-01-01T00:00:00Z": 1,
+                "2024-01-15T12:00:00Z": 1,
+                "2024-01-31T23:00:00Z": 1,
+                "2024-03-01T00:00:00Z": 1,
+                "not a date": 1,
+            },
+        }
+        "###);
+        drop(rtxn);
+
+        // The datetimes are indexed as strings again once the field is no longer a datetime
+        index
+            .update_settings(|settings| {
+                settings
+                    .set_filterable_fields(vec![FilterableAttributesRule::Field(S("publishedAt"))]);
+            })
+            .unwrap();
+
+        insta::assert_debug_snapshot!(candidates("publishedAt = '2024-01-15T12:00:00Z'"), @"RoaringBitmap<[1]>");
+        insta::assert_debug_snapshot!(candidates("publishedAt > 1704067200"), @"RoaringBitmap<[]>");
+    }
+
     #[test]
     fn replace_documents_external_ids_and_soft_deletion_check() {
         let index = TempIndex::new();
@@ -3085,6 +3419,37 @@ pub(crate) mod tests {
         db_snap!(index, geo_faceted_documents_ids); // ensure that no documents were inserted
     }
 
+    #[test]
+    fn invalid_point_in_multiple_geo_points() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_primary_key("id".to_string());
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(
+                    RESERVED_GEO_FIELD_NAME.to_string(),
+                )]);
+            })
+            .unwrap();
+
+        let err = index
+            .add_documents(
+                documents!({ "id" : "doggo", RESERVED_GEO_FIELD_NAME: [{ "lat": 1, "lng": 2 }, { "lat": "unparseable", "lng": 2 }] }),
+            )
+            .unwrap_err();
+        insta::assert_snapshot!(err, @r###"Could not parse latitude in the document with the id: `"doggo"`. Was expecting a finite number but instead got `"unparseable"`."###);
+
+        let err = index
+            .add_documents(
+                documents!({ "id" : "doggo", RESERVED_GEO_FIELD_NAME: [{ "lat": 1, "lng": 2 }, [3, 4]] }),
+            )
+            .unwrap_err();
+        insta::assert_snapshot!(err, @r###"The `_geo` field in the document with the id: `"doggo"` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields or an array of such objects but instead got `[3,4]`."###);
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
+    }
+
     #[test]
     fn swapping_searchable_attributes() {
         // See https://github.com/meilisearch/meilisearch/issues/4484
@@ -10,19 +10,23 @@ pub mod documents;
 
 mod asc_desc;
 mod attribute_patterns;
+mod boost;
 mod criterion;
 pub mod database_stats;
+mod datetime;
 mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
 mod filterable_attributes_rules;
+mod geojson;
 pub mod heed_codec;
 pub mod index;
 mod localized_attributes_rules;
 pub mod order_by_map;
 pub mod prompt;
 pub mod proximity;
+mod query_rules;
 pub mod score_details;
 mod search;
 mod thread_pool_no_abort;
@@ -56,6 +60,7 @@ pub use {charabia as tokenizer, heed, rhai};
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
 pub use self::attribute_patterns::AttributePatterns;
 pub use self::attribute_patterns::PatternMatch;
+pub use self::boost::{BoostError, BoostExpression};
 pub use self::criterion::{default_criteria, Criterion, CriterionError};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
@@ -67,6 +72,7 @@ pub use self::filterable_attributes_rules::{
     FilterFeatures, FilterableAttributesFeatures, FilterableAttributesPatterns,
     FilterableAttributesRule,
 };
+pub use self::geojson::GeoJsonShape;
 pub use self::heed_codec::{
     BEU16StrCodec, BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec,
     CboRoaringBitmapCodec, CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec,
@@ -75,11 +81,13 @@ pub use self::heed_codec::{
 };
 pub use self::index::Index;
 pub use self::localized_attributes_rules::LocalizedAttributesRule;
+pub use self::query_rules::{QueryRule, QueryRuleAnchoring};
 pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
 pub use self::search::similar::Similar;
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    FacetBuckets, FacetDistribution, FacetHierarchy, FacetRange, Filter, FormatOptions,
+    MatchBounds, MatcherBuilder, MatchingWords, OrderBy, Search, SearchResult, SemanticSearch,
+    TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
 };
 pub use self::update::ChannelCongestion;
 
@@ -111,6 +119,14 @@ pub type SmallVec8<T> = smallvec::SmallVec<[T; 8]>;
 /// expressed in term of latitude and longitude.
 pub type GeoPoint = rstar::primitives::GeomWithData<[f64; 3], (DocumentId, [f64; 2])>;
 
+/// A GeoJsonEnvelope is the bounding box of the `_geojson` shape of a document, expressed in
+/// terms of latitude and longitude. Its metadata is a tuple composed of 1. the DocumentId of the
+/// associated document and 2. its shape, so that the filters don't need to read the documents.
+pub type GeoJsonEnvelope = rstar::primitives::GeomWithData<
+    rstar::primitives::Rectangle<[f64; 2]>,
+    (DocumentId, GeoJsonShape),
+>;
+
 /// The maximum length a LMDB key can be.
 ///
 /// Note that the actual allowed length is a little bit higher, but
@@ -54,6 +54,11 @@ impl<'a> Document<'a> {
         Self(out_data)
     }
 
+    /// The raw JSON value of a top-level field of the document.
+    pub fn raw_field(&self, name: &str) -> Option<&'a [u8]> {
+        self.0.get(name).map(|(raw, _)| *raw)
+    }
+
     fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
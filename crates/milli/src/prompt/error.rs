@@ -21,6 +21,13 @@ impl NewPromptError {
     pub(crate) fn invalid_fields_in_template(inner: liquid::Error) -> NewPromptError {
         Self { kind: NewPromptErrorKind::InvalidFieldsInTemplate(inner), fault: FaultSource::User }
     }
+
+    pub(crate) fn invalid_fields_in_query_template(inner: liquid::Error) -> NewPromptError {
+        Self {
+            kind: NewPromptErrorKind::InvalidFieldsInQueryTemplate(inner),
+            fault: FaultSource::User,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -29,6 +36,8 @@ pub enum NewPromptErrorKind {
     CannotParseTemplate(liquid::Error),
     #[error("template contains invalid fields: {0}. Only `doc.*`, `fields[i].name`, `fields[i].value` are supported")]
     InvalidFieldsInTemplate(liquid::Error),
+    #[error("template contains invalid fields: {0}. Only `q` is supported")]
+    InvalidFieldsInQueryTemplate(liquid::Error),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -51,6 +60,13 @@ impl RenderPromptError {
     pub(crate) fn missing_context(inner: liquid::Error) -> RenderPromptError {
         Self { kind: RenderPromptErrorKind::MissingContext(inner), fault: FaultSource::User }
     }
+
+    pub(crate) fn cannot_read_field(field: String, inner: crate::Error) -> RenderPromptError {
+        Self {
+            kind: RenderPromptErrorKind::CannotReadField(field, Box::new(inner)),
+            fault: FaultSource::Bug,
+        }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -59,6 +75,8 @@ pub enum RenderPromptErrorKind {
     MissingContext(liquid::Error),
     #[error("missing field in document `{0}`: {1}")]
     MissingContextWithExternalDocid(String, liquid::Error),
+    #[error("could not read the field `{0}` of the document: {1}")]
+    CannotReadField(String, Box<crate::Error>),
 }
 
 impl From<RenderPromptError> for crate::Error {
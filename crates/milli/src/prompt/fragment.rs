@@ -0,0 +1,132 @@
+use liquid::ObjectView;
+use serde_json::Value;
+
+use super::error::NewPromptError;
+use super::new_template;
+use super::template_checker::TemplateChecker;
+
+/// A JSON value whose strings are liquid templates, rendered to a JSON value of the same shape.
+///
+/// Fragments let an embedder receive structured inputs, such as the URL of an image along with
+/// the description of a document, instead of a single rendered text.
+pub struct Fragment {
+    value: Value,
+    /// The templates of the strings of `value`, in the order they are visited.
+    templates: Vec<liquid::Template>,
+}
+
+impl Fragment {
+    /// Parses a fragment rendered for each document, whose templates can use `doc.*` and `fields`.
+    pub fn for_documents(value: Value) -> Result<Self, NewPromptError> {
+        let this = Self::new(value)?;
+        for template in &this.templates {
+            // render template with special object that's OK with `doc.*` and `fields.*`
+            template
+                .render(&TemplateChecker)
+                .map_err(NewPromptError::invalid_fields_in_template)?;
+        }
+        Ok(this)
+    }
+
+    /// Parses a fragment rendered for each search query, whose templates can use `q`.
+    pub fn for_query(value: Value) -> Result<Self, NewPromptError> {
+        let this = Self::new(value)?;
+        this.render_query("").map_err(NewPromptError::invalid_fields_in_query_template)?;
+        Ok(this)
+    }
+
+    fn new(mut value: Value) -> Result<Self, NewPromptError> {
+        let mut strings = Vec::new();
+        strings_mut(&mut value, &mut strings);
+        let templates = strings
+            .into_iter()
+            .map(|text| new_template(text).map_err(NewPromptError::cannot_parse_template))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { value, templates })
+    }
+
+    /// Renders each string of the fragment with the given context.
+    pub fn render(&self, context: &dyn ObjectView) -> Result<Value, liquid::Error> {
+        let mut rendered = self.value.clone();
+        let mut strings = Vec::new();
+        strings_mut(&mut rendered, &mut strings);
+        for (text, template) in strings.into_iter().zip(&self.templates) {
+            *text = template.render(context)?;
+        }
+        Ok(rendered)
+    }
+
+    /// Renders each string of the fragment with the search query as `q`.
+    pub fn render_query(&self, query: &str) -> Result<Value, liquid::Error> {
+        self.render(&liquid::object!({ "q": query }))
+    }
+
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+}
+
+impl std::fmt::Debug for Fragment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Fragment").field(&self.value).finish()
+    }
+}
+
+impl Clone for Fragment {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone()).unwrap()
+    }
+}
+
+fn strings_mut<'a>(value: &'a mut Value, strings: &mut Vec<&'a mut String>) {
+    match value {
+        Value::String(text) => strings.push(text),
+        Value::Array(values) => values.iter_mut().for_each(|value| strings_mut(value, strings)),
+        Value::Object(map) => map.values_mut().for_each(|value| strings_mut(value, strings)),
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::Fragment;
+    use crate::error::FaultSource;
+    use crate::prompt::error::{NewPromptError, NewPromptErrorKind};
+
+    #[test]
+    fn query_fragment() {
+        let fragment =
+            Fragment::for_query(json!({ "text": "a photo of {{q}}", "kind": ["query", 1] }))
+                .unwrap();
+        assert_eq!(
+            fragment.render_query("a \"kefir\" dog").unwrap(),
+            json!({ "text": "a photo of a \"kefir\" dog", "kind": ["query", 1] })
+        );
+    }
+
+    #[test]
+    fn document_fragment() {
+        Fragment::for_documents(json!({ "image": "{{doc.url}}", "text": "{{doc.title}}" }))
+            .unwrap();
+        assert!(matches!(
+            Fragment::for_documents(json!({ "image": "{{url}}" })),
+            Err(NewPromptError {
+                kind: NewPromptErrorKind::InvalidFieldsInTemplate(_),
+                fault: FaultSource::User
+            })
+        ));
+    }
+
+    #[test]
+    fn query_fragment_with_document_fields() {
+        assert!(matches!(
+            Fragment::for_query(json!({ "text": "{{doc.title}}" })),
+            Err(NewPromptError {
+                kind: NewPromptErrorKind::InvalidFieldsInQueryTemplate(_),
+                fault: FaultSource::User
+            })
+        ));
+    }
+}
@@ -2,39 +2,58 @@ mod context;
 mod document;
 pub(crate) mod error;
 mod fields;
+mod fragment;
 mod template_checker;
 
 use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 use std::num::NonZeroUsize;
+use std::ops::Range;
+use std::sync::RwLock;
 
 use bumpalo::Bump;
 use document::ParseableDocument;
 use error::{NewPromptError, RenderPromptError};
 use fields::{BorrowedFields, OwnedFields};
+use heed::RoTxn;
 
 use self::context::Context;
 use self::document::Document;
+pub use self::fragment::Fragment;
 use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
 use crate::update::del_add::DelAdd;
-use crate::GlobalFieldsIdsMap;
+use crate::update::new::document::{Document as _, DocumentFromDb};
+use crate::vector::chunking::Chunking;
+use crate::{DocumentId, GlobalFieldsIdsMap, Index};
 
 pub struct Prompt {
     template: liquid::Template,
     template_text: String,
     max_bytes: Option<NonZeroUsize>,
+    chunking: Option<Chunking>,
+    fragment: Option<Fragment>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PromptData {
     pub template: String,
     pub max_bytes: Option<NonZeroUsize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chunking: Option<Chunking>,
+    /// Renders the documents to JSON values in place of the template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fragment: Option<serde_json::Value>,
 }
 
 impl From<Prompt> for PromptData {
     fn from(value: Prompt) -> Self {
-        Self { template: value.template_text, max_bytes: value.max_bytes }
+        Self {
+            template: value.template_text,
+            max_bytes: value.max_bytes,
+            chunking: value.chunking,
+            fragment: value.fragment.map(Fragment::into_value),
+        }
     }
 }
 
@@ -42,7 +61,10 @@ impl TryFrom<PromptData> for Prompt {
     type Error = NewPromptError;
 
     fn try_from(value: PromptData) -> Result<Self, Self::Error> {
-        Prompt::new(value.template, value.max_bytes)
+        let fragment = value.fragment.map(Fragment::for_documents).transpose()?;
+        Ok(Prompt::new(value.template, value.max_bytes)?
+            .with_chunking(value.chunking)
+            .with_fragment(fragment))
     }
 }
 
@@ -53,6 +75,8 @@ impl Clone for Prompt {
             template: new_template(&template_text).unwrap(),
             template_text,
             max_bytes: self.max_bytes,
+            chunking: self.chunking.clone(),
+            fragment: self.fragment.clone(),
         }
     }
 }
@@ -83,13 +107,20 @@ impl Default for Prompt {
             template: default_template(),
             template_text: default_template_text().into(),
             max_bytes: Some(default_max_bytes()),
+            chunking: None,
+            fragment: None,
         }
     }
 }
 
 impl Default for PromptData {
     fn default() -> Self {
-        Self { template: default_template_text().into(), max_bytes: Some(default_max_bytes()) }
+        Self {
+            template: default_template_text().into(),
+            max_bytes: Some(default_max_bytes()),
+            chunking: None,
+            fragment: None,
+        }
     }
 }
 
@@ -103,6 +134,8 @@ impl Prompt {
                 .map_err(NewPromptError::cannot_parse_template)?,
             template_text: template,
             max_bytes,
+            chunking: None,
+            fragment: None,
         };
 
         // render template with special object that's OK with `doc.*` and `fields.*`
@@ -113,6 +146,39 @@ impl Prompt {
         Ok(this)
     }
 
+    /// Splits the rendered text in several chunks, each of them embedded as a separate vector.
+    ///
+    /// When set, the rendered text is no longer truncated to the max bytes of the prompt.
+    pub fn with_chunking(mut self, chunking: Option<Chunking>) -> Self {
+        self.chunking = chunking;
+        self
+    }
+
+    /// Renders the documents to JSON values with the fragment, in place of the template.
+    ///
+    /// The rendered values are serialized to text, and are neither truncated nor chunked.
+    pub fn with_fragment(mut self, fragment: Option<Fragment>) -> Self {
+        self.fragment = fragment;
+        self
+    }
+
+    pub fn chunking(&self) -> Option<&Chunking> {
+        self.chunking.as_ref()
+    }
+
+    pub fn max_bytes(&self) -> Option<NonZeroUsize> {
+        self.max_bytes
+    }
+
+    /// Splits the rendered text into the byte ranges of the chunks to embed, or returns `None` if
+    /// the rendered text is embedded whole.
+    pub fn chunk_ranges(&self, rendered: &str) -> Option<Vec<Range<usize>>> {
+        match (&self.chunking, &self.fragment) {
+            (Some(chunking), None) => Some(chunking.chunks(rendered)),
+            _ => None,
+        }
+    }
+
     pub fn render_document<
         'a,       // lifetime of the borrow of the document
         'doc: 'a, // lifetime of the allocator, will live for an entire chunk of documents
@@ -123,9 +189,29 @@ impl Prompt {
         field_id_map: &RefCell<GlobalFieldsIdsMap>,
         doc_alloc: &'doc Bump,
     ) -> Result<&'doc str, RenderPromptError> {
+        if let Some(field) = self.chunking.as_ref().and_then(|chunking| chunking.field.as_deref()) {
+            let value = document
+                .top_level_field(field)
+                .map_err(|error| RenderPromptError::cannot_read_field(field.to_owned(), error))?;
+            let text = match value {
+                Some(value) => field_text(value.get()),
+                None => String::new(),
+            };
+            return Ok(doc_alloc.alloc_str(&text));
+        }
+
         let document = ParseableDocument::new(document, doc_alloc);
         let fields = BorrowedFields::new(&document, field_id_map, doc_alloc);
         let context = Context::new(&document, &fields);
+        if let Some(fragment) = &self.fragment {
+            let rendered = fragment.render(&context).map_err(|liquid_error| {
+                RenderPromptError::missing_context_with_external_docid(
+                    external_docid.to_owned(),
+                    liquid_error,
+                )
+            })?;
+            return Ok(doc_alloc.alloc_str(&rendered.to_string()));
+        }
         let mut rendered = bumpalo::collections::Vec::with_capacity_in(
             self.max_bytes.unwrap_or_else(default_max_bytes).get(),
             doc_alloc,
@@ -147,19 +233,59 @@ impl Prompt {
         field_id_map: &FieldIdMapWithMetadata,
     ) -> Result<String, RenderPromptError> {
         let document = Document::new(document, side, field_id_map.as_fields_ids_map());
+        if let Some(field) = self.chunking.as_ref().and_then(|chunking| chunking.field.as_deref()) {
+            return Ok(document
+                .raw_field(field)
+                .map(|raw| field_text(std::str::from_utf8(raw).unwrap_or_default()))
+                .unwrap_or_default());
+        }
+
         let fields = OwnedFields::new(&document, field_id_map);
         let context = Context::new(&document, &fields);
+        if let Some(fragment) = &self.fragment {
+            let rendered = fragment.render(&context).map_err(RenderPromptError::missing_context)?;
+            return Ok(rendered.to_string());
+        }
 
         let mut rendered =
             self.template.render(&context).map_err(RenderPromptError::missing_context)?;
-        if let Some(max_bytes) = self.max_bytes {
+        if let (Some(max_bytes), None) = (self.max_bytes, &self.chunking) {
             truncate(&mut rendered, max_bytes.get());
         }
         Ok(rendered)
     }
+
+    /// Renders a document of the index, as it was rendered to be embedded.
+    pub fn render_stored_document(
+        &self,
+        index: &Index,
+        rtxn: &RoTxn<'_>,
+        docid: DocumentId,
+    ) -> crate::Result<Option<String>> {
+        let fields_ids_map = index.fields_ids_map_with_metadata(rtxn)?;
+        let Some(document) =
+            DocumentFromDb::new(docid, rtxn, index, fields_ids_map.as_fields_ids_map())?
+        else {
+            return Ok(None);
+        };
+        let global_fields_ids_map = RwLock::new(fields_ids_map.clone());
+        let global_fields_ids_map = RefCell::new(GlobalFieldsIdsMap::new(&global_fields_ids_map));
+        let doc_alloc = Bump::new();
+        let rendered = self.render_document("", document, &global_fields_ids_map, &doc_alloc)?;
+        Ok(Some(rendered.to_owned()))
+    }
+}
+
+/// The text of a field value: strings are taken as is, other values as JSON.
+fn field_text(raw_json: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(raw_json) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(serde_json::Value::Null) | Err(_) => String::new(),
+        Ok(_) => raw_json.to_owned(),
+    }
 }
 
-fn truncate(s: &mut String, max_bytes: usize) {
+pub(crate) fn truncate(s: &mut String, max_bytes: usize) {
     if max_bytes >= s.len() {
         return;
     }
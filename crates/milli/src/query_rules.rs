@@ -0,0 +1,110 @@
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// A merchandising rule that applies to the searches whose query matches its pattern.
+///
+/// The documents listed in `pinned` are returned first, in order, and the documents
+/// listed in `hidden` are never returned. The `filter`, if any, is added to the filter of the search.
+///
+/// The rules also apply to the facet searches and to the federated searches. Since the hits
+/// of a federated search are merged by ranking score, the pinned documents are not moved first there.
+/// The searches for similar documents have no query, so no rule applies to them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct QueryRule {
+    /// The pattern matched against the query of the search.
+    pub pattern: String,
+    /// How the pattern must be matched against the query.
+    #[serde(default)]
+    #[deserr(default)]
+    pub anchoring: QueryRuleAnchoring,
+    /// The external ids of the documents to return before the other ones, in order.
+    #[serde(default)]
+    #[deserr(default)]
+    pub pinned: Vec<String>,
+    /// The external ids of the documents to never return.
+    #[serde(default)]
+    #[deserr(default)]
+    pub hidden: Vec<String>,
+    /// A filter expression to add to the filter of the search.
+    #[serde(default)]
+    #[deserr(default)]
+    pub filter: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum QueryRuleAnchoring {
+    /// The query must be equal to the pattern.
+    #[default]
+    Is,
+    /// The query must start with the pattern.
+    StartsWith,
+    /// The query must end with the pattern.
+    EndsWith,
+    /// The query must contain the pattern.
+    Contains,
+}
+
+impl QueryRule {
+    /// Returns `true` if the rule applies to the given query.
+    ///
+    /// The comparison ignores the case and the number of whitespaces between the words.
+    pub fn matches(&self, query: &str) -> bool {
+        let pattern = normalize(&self.pattern);
+        let query = normalize(query);
+        match self.anchoring {
+            QueryRuleAnchoring::Is => query == pattern,
+            QueryRuleAnchoring::StartsWith => query.starts_with(&pattern),
+            QueryRuleAnchoring::EndsWith => query.ends_with(&pattern),
+            QueryRuleAnchoring::Contains => query.contains(&pattern),
+        }
+    }
+}
+
+fn normalize(s: &str) -> String {
+    s.split_whitespace().map(str::to_lowercase).collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(pattern: &str, anchoring: QueryRuleAnchoring) -> QueryRule {
+        QueryRule {
+            pattern: pattern.to_string(),
+            anchoring,
+            pinned: Vec::new(),
+            hidden: Vec::new(),
+            filter: None,
+        }
+    }
+
+    #[test]
+    fn matches() {
+        let is = rule("Blue  Shoes", QueryRuleAnchoring::Is);
+        assert!(is.matches("blue shoes"));
+        assert!(is.matches("  BLUE shoes "));
+        assert!(!is.matches("blue shoes sale"));
+
+        let starts_with = rule("blue", QueryRuleAnchoring::StartsWith);
+        assert!(starts_with.matches("blue shoes"));
+        assert!(!starts_with.matches("dark blue shoes"));
+
+        let ends_with = rule("shoes", QueryRuleAnchoring::EndsWith);
+        assert!(ends_with.matches("blue shoes"));
+        assert!(!ends_with.matches("shoes blue"));
+
+        let contains = rule("blue", QueryRuleAnchoring::Contains);
+        assert!(contains.matches("dark blue shoes"));
+        assert!(!contains.matches("red shoes"));
+
+        let empty = rule("", QueryRuleAnchoring::Is);
+        assert!(empty.matches(""));
+        assert!(empty.matches("   "));
+        assert!(!empty.matches("shoes"));
+    }
+}
@@ -17,6 +17,8 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    Boost(Boost),
+    Rerank(Rerank),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -27,12 +29,14 @@ pub enum ScoreValue<'a> {
     Score(f64),
     Sort(&'a Sort),
     GeoSort(&'a GeoSort),
+    Boost(&'a Boost),
 }
 
 enum RankOrValue<'a> {
     Rank(Rank),
     Sort(&'a Sort),
     GeoSort(&'a GeoSort),
+    Boost(&'a Boost),
     Score(f64),
 }
 
@@ -43,6 +47,7 @@ pub enum WeightedScoreValue {
     Sort { asc: bool, value: serde_json::Value },
     GeoSort { asc: bool, distance: Option<f64> },
     VectorSort(f64),
+    Boost(Option<f64>),
 }
 
 impl ScoreDetails {
@@ -62,6 +67,8 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::Boost(_) => None,
+            ScoreDetails::Rerank(_) => None,
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -92,6 +99,7 @@ impl ScoreDetails {
                 RankOrValue::Rank(r) => ScoreValue::Score(r.local_score()),
                 RankOrValue::Sort(s) => ScoreValue::Sort(s),
                 RankOrValue::GeoSort(g) => ScoreValue::GeoSort(g),
+                RankOrValue::Boost(b) => ScoreValue::Boost(b),
                 RankOrValue::Score(s) => ScoreValue::Score(s),
             })
     }
@@ -116,6 +124,7 @@ impl ScoreDetails {
                 RankOrValue::GeoSort(g) => {
                     WeightedScoreValue::GeoSort { asc: g.ascending, distance: g.distance() }
                 }
+                RankOrValue::Boost(b) => WeightedScoreValue::Boost(b.value),
                 RankOrValue::Score(s) => WeightedScoreValue::VectorSort(s * weight),
             })
     }
@@ -131,9 +140,11 @@ impl ScoreDetails {
             ScoreDetails::ExactWords(e) => RankOrValue::Rank(e.rank()),
             ScoreDetails::Sort(sort) => RankOrValue::Sort(sort),
             ScoreDetails::GeoSort(geosort) => RankOrValue::GeoSort(geosort),
+            ScoreDetails::Boost(boost) => RankOrValue::Boost(boost),
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
+            ScoreDetails::Rerank(rerank) => RankOrValue::Score(rerank.score as f64),
             ScoreDetails::Skipped => RankOrValue::Rank(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -281,6 +292,15 @@ impl ScoreDetails {
                     details_map.insert(sort, sort_details);
                     order += 1;
                 }
+                ScoreDetails::Boost(details) => {
+                    let boost_details = serde_json::json!({
+                        "order": order,
+                        "expression": details.expression,
+                        "value": details.value,
+                    });
+                    details_map.insert("boost".into(), boost_details);
+                    order += 1;
+                }
                 ScoreDetails::Vector(s) => {
                     let similarity = s.similarity.as_ref();
 
@@ -291,6 +311,15 @@ impl ScoreDetails {
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::Rerank(rerank) => {
+                    let details = serde_json::json!({
+                        "order": order,
+                        "reranker": rerank.reranker,
+                        "score": rerank.score,
+                    });
+                    details_map.insert("rerank".into(), details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -540,9 +569,41 @@ impl PartialOrd for GeoSort {
     }
 }
 
+/// The value of a boost expression for a document, `None` if it could not be computed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Boost {
+    pub expression: String,
+    pub value: Option<f64>,
+}
+
+impl PartialOrd for Boost {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.expression != other.expression {
+            return None;
+        }
+        // the greater the boost, the better the document
+        Some(match (self.value, other.value) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(left), Some(right)) => left.partial_cmp(&right)?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Vector {
     pub similarity: Option<f32>,
+    /// Index of the vector of the document that is the most similar to the target,
+    /// which is the index of the best matching chunk for chunked documents.
+    pub vector_index: Option<u8>,
+}
+
+/// The relevance of a document for the query, according to a reranker.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Rerank {
+    pub reranker: String,
+    pub score: f32,
 }
 
 impl GeoSort {
@@ -1,5 +1,6 @@
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Display;
+use std::ops::Bound::{self, Excluded, Included, Unbounded};
 use std::ops::ControlFlow;
 use std::{fmt, mem};
 
@@ -10,15 +11,19 @@ use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
 use crate::attribute_patterns::match_field_legacy;
+use crate::datetime::format_datetime;
 use crate::facet::FacetType;
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
 use crate::heed_codec::facet::{
-    FacetGroupKeyCodec, FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FieldDocIdFacetF64Codec,
+    FieldDocIdFacetStringCodec, OrderedF64Codec,
 };
 use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
+use crate::search::facet::{facet_max_value, facet_min_value};
 use crate::{Error, FieldId, FilterableAttributesRule, Index, PatternMatch, Result, UserError};
 
 /// The default number of values by facets that will
@@ -48,8 +53,72 @@ impl Display for OrderBy {
     }
 }
 
+/// How the values of a number facet are grouped into buckets.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FacetBuckets {
+    /// Buckets of `interval` width, starting at the multiples of `interval`,
+    /// from the one containing the smallest value to the one containing the biggest value.
+    Histogram { interval: f64 },
+    /// Buckets delimited by the given ranges, in the same order.
+    Ranges(Vec<FacetRange>),
+}
+
+/// A range of facet values, `from` is included and `to` is excluded.
+///
+/// A missing bound leaves the range unbounded on this side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
+impl FacetRange {
+    fn bounds(&self) -> (Bound<f64>, Bound<f64>) {
+        let left = self.from.map_or(Unbounded, Included);
+        let right = self.to.map_or(Unbounded, Excluded);
+        (left, right)
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!((self.from, self.to), (Some(from), Some(to)) if from >= to)
+    }
+}
+
+/// A tree of facet values, like categories, where each level is stored in its own field with the
+/// full path of the value, like `lvl0: "Electronics"` and `lvl1: "Electronics > Phones"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FacetHierarchy {
+    /// The fields of the levels, from the root to the leaves.
+    pub levels: Vec<String>,
+    /// The string between the values of a path, like ` > `. It must not be empty.
+    pub separator: String,
+    /// The path of the selected branch, like `Electronics > Phones`.
+    pub selected_path: Option<String>,
+}
+
+impl FacetHierarchy {
+    /// Returns the fields of the levels of the selected branch, from the root, with the prefix of
+    /// the path of the values of this branch at that level.
+    fn branch_levels(&self) -> impl Iterator<Item = (&str, String)> + '_ {
+        let segments: Vec<&str> = match &self.selected_path {
+            Some(path) => path.split(self.separator.as_str()).collect(),
+            None => Vec::new(),
+        };
+
+        self.levels.iter().take(segments.len() + 1).enumerate().map(move |(depth, level)| {
+            let mut prefix = segments[..depth].join(&self.separator);
+            if depth != 0 {
+                prefix.push_str(&self.separator);
+            }
+            (level.as_str(), prefix)
+        })
+    }
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
+    buckets: Option<HashMap<String, FacetBuckets>>,
+    hierarchies: Option<HashMap<String, FacetHierarchy>>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
     default_order_by: OrderBy,
@@ -61,6 +130,8 @@ impl<'a> FacetDistribution<'a> {
     pub fn new(rtxn: &'a heed::RoTxn<'a>, index: &'a Index) -> FacetDistribution<'a> {
         FacetDistribution {
             facets: None,
+            buckets: None,
+            hierarchies: None,
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
             default_order_by: OrderBy::default(),
@@ -82,6 +153,34 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// The number facets to group into buckets when calling [`Self::compute_buckets`].
+    pub fn buckets<I: IntoIterator<Item = (A, FacetBuckets)>, A: AsRef<str>>(
+        &mut self,
+        names_bucketed_by: I,
+    ) -> &mut Self {
+        self.buckets = Some(
+            names_bucketed_by
+                .into_iter()
+                .map(|(name, buckets)| (name.as_ref().to_string(), buckets))
+                .collect(),
+        );
+        self
+    }
+
+    /// The facet hierarchies to compute when calling [`Self::compute_hierarchies`].
+    pub fn hierarchies<I: IntoIterator<Item = (A, FacetHierarchy)>, A: AsRef<str>>(
+        &mut self,
+        names_hierarchies: I,
+    ) -> &mut Self {
+        self.hierarchies = Some(
+            names_hierarchies
+                .into_iter()
+                .map(|(name, hierarchy)| (name.as_ref().to_string(), hierarchy))
+                .collect(),
+        );
+        self
+    }
+
     pub fn max_values_per_facet(&mut self, max: usize) -> &mut Self {
         self.max_values_per_facet = max;
         self
@@ -103,6 +202,7 @@ impl<'a> FacetDistribution<'a> {
         &self,
         field_id: FieldId,
         facet_type: FacetType,
+        datetime: bool,
         candidates: &RoaringBitmap,
         distribution: &mut IndexMap<String, u64>,
     ) -> heed::Result<()> {
@@ -122,7 +222,8 @@ impl<'a> FacetDistribution<'a> {
 
                     for result in iter {
                         let ((_, _, value), ()) = result?;
-                        *lexicographic_distribution.entry(value.to_string()).or_insert(0) += 1;
+                        let value = format_number(value, datetime);
+                        *lexicographic_distribution.entry(value).or_insert(0) += 1;
                     }
                 }
 
@@ -173,6 +274,7 @@ impl<'a> FacetDistribution<'a> {
     fn facet_numbers_distribution_from_facet_levels(
         &self,
         field_id: FieldId,
+        datetime: bool,
         candidates: &RoaringBitmap,
         order_by: OrderBy,
         distribution: &mut IndexMap<String, u64>,
@@ -189,7 +291,7 @@ impl<'a> FacetDistribution<'a> {
             candidates,
             |facet_key, nbr_docids, _| {
                 let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
-                distribution.insert(facet_key.to_string(), nbr_docids);
+                distribution.insert(format_number(facet_key, datetime), nbr_docids);
                 if distribution.len() == self.max_values_per_facet {
                     Ok(ControlFlow::Break(()))
                 } else {
@@ -247,6 +349,7 @@ impl<'a> FacetDistribution<'a> {
     fn facet_values(
         &self,
         field_id: FieldId,
+        datetime: bool,
         order_by: OrderBy,
     ) -> heed::Result<IndexMap<String, u64>> {
         use FacetType::{Number, String};
@@ -256,8 +359,20 @@ impl<'a> FacetDistribution<'a> {
             (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
                 // Classic search, candidates were specified, we must return facet values only related
                 // to those candidates. We also enter here for facet strings for performance reasons.
-                self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
-                self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
+                self.facet_distribution_from_documents(
+                    field_id,
+                    Number,
+                    datetime,
+                    cnd,
+                    &mut distribution,
+                )?;
+                self.facet_distribution_from_documents(
+                    field_id,
+                    String,
+                    datetime,
+                    cnd,
+                    &mut distribution,
+                )?;
             }
             _ => {
                 let universe;
@@ -271,6 +386,7 @@ impl<'a> FacetDistribution<'a> {
 
                 self.facet_numbers_distribution_from_facet_levels(
                     field_id,
+                    datetime,
                     candidates,
                     order_by,
                     &mut distribution,
@@ -329,6 +445,170 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Counts the candidates with a value in each bucket of the bucketed number facets.
+    ///
+    /// A histogram is truncated to the maximum number of values per facet.
+    pub fn compute_buckets(&self) -> Result<BTreeMap<String, Vec<(FacetRange, u64)>>> {
+        let Some(buckets) = &self.buckets else {
+            return Ok(Default::default());
+        };
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+        self.check_faceted_fields(&filterable_attributes_rules)?;
+
+        let candidates = match &self.candidates {
+            Some(candidates) => candidates.clone(),
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, buckets) in buckets {
+            let Some(field_id) = fields_ids_map.id(name) else {
+                distribution.insert(name.to_string(), Vec::new());
+                continue;
+            };
+
+            let ranges = match buckets {
+                FacetBuckets::Histogram { interval } => {
+                    self.histogram_ranges(field_id, *interval, &candidates)?
+                }
+                FacetBuckets::Ranges(ranges) => ranges.clone(),
+            };
+
+            let db = self.index.facet_id_f64_docids;
+            let mut counts = Vec::with_capacity(ranges.len());
+            for range in ranges {
+                let mut docids = RoaringBitmap::new();
+                if !range.is_empty() {
+                    let (left, right) = range.bounds();
+                    find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+                        self.rtxn,
+                        db,
+                        field_id,
+                        &left,
+                        &right,
+                        Some(&candidates),
+                        &mut docids,
+                    )?;
+                }
+                counts.push((range, docids.len()));
+            }
+
+            distribution.insert(name.to_string(), counts);
+        }
+
+        Ok(distribution)
+    }
+
+    /// Returns the ranges of a histogram covering the values of the candidates.
+    fn histogram_ranges(
+        &self,
+        field_id: FieldId,
+        interval: f64,
+        candidates: &RoaringBitmap,
+    ) -> Result<Vec<FacetRange>> {
+        if !interval.is_finite() || interval <= 0.0 {
+            return Ok(Vec::new());
+        }
+
+        let min = facet_min_value(self.index, self.rtxn, field_id, candidates.clone())?;
+        let max = facet_max_value(self.index, self.rtxn, field_id, candidates.clone())?;
+        let (Some(min), Some(max)) = (min, max) else {
+            return Ok(Vec::new());
+        };
+
+        let first = (min / interval).floor();
+        let last = (max / interval).floor();
+        let len = ((last - first) as usize).saturating_add(1).min(self.max_values_per_facet);
+
+        Ok((0..len)
+            .map(|i| {
+                let from = (first + i as f64) * interval;
+                FacetRange { from: Some(from), to: Some(from + interval) }
+            })
+            .collect())
+    }
+
+    /// Computes the distribution of the string values of each level of the selected branch of the
+    /// hierarchies, from the root to the children of the selected path.
+    ///
+    /// Only the values of a level that are in the selected branch are counted.
+    pub fn compute_hierarchies(
+        &self,
+    ) -> Result<BTreeMap<String, Vec<(String, IndexMap<String, u64>)>>> {
+        let Some(hierarchies) = &self.hierarchies else {
+            return Ok(Default::default());
+        };
+
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
+        self.check_faceted_fields(&filterable_attributes_rules)?;
+        let sort_facet_values_by = self.index.sort_facet_values_by(self.rtxn)?;
+
+        let candidates = match &self.candidates {
+            Some(candidates) => candidates.clone(),
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, hierarchy) in hierarchies {
+            let mut levels = Vec::new();
+            for (level, prefix) in hierarchy.branch_levels() {
+                let values = match fields_ids_map.id(level) {
+                    Some(field_id) => self.facet_string_values_with_prefix(
+                        field_id,
+                        &prefix,
+                        sort_facet_values_by.get(level),
+                        &candidates,
+                    )?,
+                    None => IndexMap::new(),
+                };
+                levels.push((level.to_string(), values));
+            }
+            distribution.insert(name.to_string(), levels);
+        }
+
+        Ok(distribution)
+    }
+
+    fn facet_string_values_with_prefix(
+        &self,
+        field_id: FieldId,
+        prefix: &str,
+        order_by: OrderBy,
+        candidates: &RoaringBitmap,
+    ) -> heed::Result<IndexMap<String, u64>> {
+        let normalized_prefix = crate::normalize_facet(prefix);
+        let prefix = FacetGroupKey { field_id, level: 0, left_bound: normalized_prefix.as_str() };
+
+        let mut values = Vec::new();
+        for result in self.index.facet_id_string_docids.prefix_iter(self.rtxn, &prefix)? {
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+            let docids = bitmap & candidates;
+            let Some(any_docid) = docids.min() else { continue };
+
+            let key: (FieldId, _, &str) = (field_id, any_docid, left_bound);
+            let original_string =
+                match self.index.field_id_docid_facet_strings.get(self.rtxn, &key)? {
+                    Some(original_string) => original_string.to_owned(),
+                    None => left_bound.to_string(),
+                };
+            values.push((original_string, docids.len()));
+
+            if order_by == OrderBy::Lexicographic && values.len() == self.max_values_per_facet {
+                break;
+            }
+        }
+
+        if order_by == OrderBy::Count {
+            values.sort_by(|(_, left), (_, right)| right.cmp(left));
+            values.truncate(self.max_values_per_facet);
+        }
+
+        Ok(values.into_iter().collect())
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_attributes_rules = self.index.filterable_attributes_rules(self.rtxn)?;
@@ -342,7 +622,9 @@ impl<'a> FacetDistribution<'a> {
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                let datetime = matching_features(name, &filterable_attributes_rules)
+                    .is_some_and(|(_, features)| features.is_datetime());
+                let values = self.facet_values(fid, datetime, order_by)?;
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -380,19 +662,24 @@ impl<'a> FacetDistribution<'a> {
         let mut invalid_facets = BTreeSet::new();
         let mut matching_rule_indices = HashMap::new();
 
-        if let Some(facets) = &self.facets {
-            for field in facets.keys() {
-                let matched_rule = matching_features(field, filterable_attributes_rules);
-                let is_filterable = matched_rule.is_some_and(|(_, f)| f.is_filterable());
-
-                if !is_filterable {
-                    invalid_facets.insert(field.to_string());
-
-                    // If the field matched a rule but that rule doesn't enable filtering,
-                    // store the rule index for better error messages
-                    if let Some((rule_index, _)) = matched_rule {
-                        matching_rule_indices.insert(field.to_string(), rule_index);
-                    }
+        let facets = self.facets.iter().flat_map(|facets| facets.keys());
+        let buckets = self.buckets.iter().flat_map(|buckets| buckets.keys());
+        let hierarchies = self
+            .hierarchies
+            .iter()
+            .flat_map(|hierarchies| hierarchies.values())
+            .flat_map(|hierarchy| &hierarchy.levels);
+        for field in facets.chain(buckets).chain(hierarchies) {
+            let matched_rule = matching_features(field, filterable_attributes_rules);
+            let is_filterable = matched_rule.is_some_and(|(_, f)| f.is_filterable());
+
+            if !is_filterable {
+                invalid_facets.insert(field.to_string());
+
+                // If the field matched a rule but that rule doesn't enable filtering,
+                // store the rule index for better error messages
+                if let Some((rule_index, _)) = matched_rule {
+                    matching_rule_indices.insert(field.to_string(), rule_index);
                 }
             }
         }
@@ -416,10 +703,17 @@ impl<'a> FacetDistribution<'a> {
     }
 }
 
+/// Formats a number facet value, the ones of datetime fields are formatted as RFC 3339 datetimes.
+fn format_number(value: f64, datetime: bool) -> String {
+    datetime.then(|| format_datetime(value)).flatten().unwrap_or_else(|| value.to_string())
+}
+
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let FacetDistribution {
             facets,
+            buckets,
+            hierarchies,
             candidates,
             max_values_per_facet,
             default_order_by,
@@ -429,6 +723,8 @@ impl fmt::Debug for FacetDistribution<'_> {
 
         f.debug_struct("FacetDistribution")
             .field("facets", facets)
+            .field("buckets", buckets)
+            .field("hierarchies", hierarchies)
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
             .field("default_order_by", default_order_by)
@@ -438,13 +734,17 @@ impl fmt::Debug for FacetDistribution<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::iter;
 
     use big_s::S;
 
     use crate::documents::mmap_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, FilterableAttributesRule, OrderBy};
+    use crate::{
+        milli_snap, FacetBuckets, FacetDistribution, FacetHierarchy, FacetRange,
+        FilterableAttributesRule, OrderBy,
+    };
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -723,6 +1023,127 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_buckets() {
+        let index = TempIndex::new_with_map_size(4096 * 10_000);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S("colour"))])
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..1000 {
+            let document = serde_json::json!({
+                "id": i,
+                "colour": i,
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = mmap_from_objects(documents);
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let buckets = |map: BTreeMap<String, Vec<(FacetRange, u64)>>| {
+            map["colour"]
+                .iter()
+                .map(|(range, count)| (range.from, range.to, *count))
+                .collect::<Vec<_>>()
+        };
+
+        let ranges = FacetBuckets::Ranges(vec![
+            FacetRange { from: None, to: Some(10.0) },
+            FacetRange { from: Some(10.0), to: Some(50.0) },
+            FacetRange { from: Some(50.0), to: None },
+            FacetRange { from: Some(990.0), to: Some(1010.0) },
+            FacetRange { from: Some(5.0), to: Some(5.0) },
+        ]);
+        let map = FacetDistribution::new(&txn, &index)
+            .buckets(iter::once(("colour", ranges)))
+            .compute_buckets()
+            .unwrap();
+
+        milli_snap!(format!("{:?}", buckets(map)), "ranges", @"[(None, Some(10.0), 10), (Some(10.0), Some(50.0), 40), (Some(50.0), None, 950), (Some(990.0), Some(1010.0), 10), (Some(5.0), Some(5.0), 0)]");
+
+        let map = FacetDistribution::new(&txn, &index)
+            .buckets(iter::once(("colour", FacetBuckets::Histogram { interval: 250.0 })))
+            .candidates((217..777).collect())
+            .compute_buckets()
+            .unwrap();
+
+        milli_snap!(format!("{:?}", buckets(map)), "histogram_candidates_217_777", @"[(Some(0.0), Some(250.0), 33), (Some(250.0), Some(500.0), 250), (Some(500.0), Some(750.0), 250), (Some(750.0), Some(1000.0), 27)]");
+
+        let map = FacetDistribution::new(&txn, &index)
+            .buckets(iter::once(("colour", FacetBuckets::Histogram { interval: 1.0 })))
+            .max_values_per_facet(3)
+            .compute_buckets()
+            .unwrap();
+
+        milli_snap!(format!("{:?}", buckets(map)), "histogram_truncated", @"[(Some(0.0), Some(1.0), 1), (Some(1.0), Some(2.0), 1), (Some(2.0), Some(3.0), 1)]");
+    }
+
+    #[test]
+    fn facet_hierarchies() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![
+                    FilterableAttributesRule::Field(S("lvl0")),
+                    FilterableAttributesRule::Field(S("lvl1")),
+                    FilterableAttributesRule::Field(S("lvl2")),
+                ])
+            })
+            .unwrap();
+
+        let documents = documents!([
+            { "id": 0, "lvl0": "Electronics", "lvl1": "Electronics > Phones", "lvl2": "Electronics > Phones > Android" },
+            { "id": 1, "lvl0": "Electronics", "lvl1": "Electronics > Phones", "lvl2": "Electronics > Phones > iOS" },
+            { "id": 2, "lvl0": "Electronics", "lvl1": "Electronics > Laptops" },
+            { "id": 3, "lvl0": "Books", "lvl1": "Books > Fiction" }
+        ]);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let hierarchy = |selected_path: Option<&str>| FacetHierarchy {
+            levels: vec![S("lvl0"), S("lvl1"), S("lvl2")],
+            separator: S(" > "),
+            selected_path: selected_path.map(String::from),
+        };
+
+        let map = FacetDistribution::new(&txn, &index)
+            .hierarchies(iter::once(("categories", hierarchy(None))))
+            .compute_hierarchies()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"categories": [("lvl0", {"Books": 1, "Electronics": 3})]}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .hierarchies(iter::once(("categories", hierarchy(Some("Electronics > Phones")))))
+            .compute_hierarchies()
+            .unwrap();
+
+        let levels = &map["categories"];
+        assert_eq!(levels.len(), 3);
+        milli_snap!(format!("{:?}", levels[0]), @r###"("lvl0", {"Books": 1, "Electronics": 3})"###);
+        milli_snap!(format!("{:?}", levels[1]), @r###"("lvl1", {"Electronics > Laptops": 1, "Electronics > Phones": 2})"###);
+        milli_snap!(format!("{:?}", levels[2]), @r###"("lvl2", {"Electronics > Phones > Android": 1, "Electronics > Phones > iOS": 1})"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .hierarchies(iter::once(("categories", hierarchy(Some("Electronics")))))
+            .candidates([2, 3].into_iter().collect())
+            .compute_hierarchies()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"categories": [("lvl0", {"Books": 1, "Electronics": 1}), ("lvl1", {"Electronics > Laptops": 1})]}"###);
+    }
+
     #[test]
     fn facet_stats_array() {
         let index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -4,23 +4,26 @@ use std::ops::Bound::{self, Excluded, Included};
 
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
-use heed::types::LazyDecode;
-use memchr::memmem::Finder;
+use heed::types::{Bytes, Unit};
 use roaring::{MultiOps, RoaringBitmap};
+use rstar::AABB;
 use serde_json::Value;
+use time::OffsetDateTime;
 
 use super::facet_range_search;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use super::geo_polygon::{bounding_box_xyz_envelope, GeoPolygon};
+use super::string_automaton::{string_filter_docids, BadRegexError, StringFilter};
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
+use crate::datetime::parse_datetime_expression;
 use crate::error::{Error, UserError};
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
+use crate::geojson::circle_bounding_boxes;
 use crate::heed_codec::facet::{
-    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec, OrderedF64Codec,
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
 };
-use crate::index::db_name::FACET_ID_STRING_DOCIDS;
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, FieldId, FieldsIdsMap,
-    FilterableAttributesFeatures, FilterableAttributesRule, Index, InternalError, Result,
-    SerializationError,
+    distance_between_two_points, lat_lng_to_xyz, DocumentId, FieldId, FieldsIdsMap,
+    FilterableAttributesFeatures, FilterableAttributesRule, GeoJsonShape, Index, Result,
 };
 
 /// The maximum number of filters the filter AST can process.
@@ -36,6 +39,9 @@ pub enum BadGeoError {
     Lat(f64),
     Lng(f64),
     BoundingBoxTopIsBelowBottom(f64, f64),
+    PolygonTooFewPoints(usize),
+    PolygonNotClosed([f64; 2], [f64; 2]),
+    PolygonSelfIntersecting([[f64; 2]; 2], [[f64; 2]; 2]),
 }
 
 impl std::error::Error for BadGeoError {}
@@ -46,6 +52,21 @@ impl Display for BadGeoError {
             Self::BoundingBoxTopIsBelowBottom(top, bottom) => {
                 write!(f, "The top latitude `{top}` is below the bottom latitude `{bottom}`.")
             }
+            Self::PolygonTooFewPoints(count) => write!(
+                f,
+                "A polygon must be made of at least four points, the last one being equal to the first one, but only `{count}` points were provided."
+            ),
+            Self::PolygonNotClosed([first_lat, first_lng], [last_lat, last_lng]) => write!(
+                f,
+                "The polygon is not closed. The first point `[{first_lat}, {first_lng}]` must be equal to the last point `[{last_lat}, {last_lng}]`."
+            ),
+            Self::PolygonSelfIntersecting(
+                [[a_lat, a_lng], [b_lat, b_lng]],
+                [[c_lat, c_lng], [d_lat, d_lng]],
+            ) => write!(
+                f,
+                "The polygon is self-intersecting. The edge going from `[{a_lat}, {a_lng}]` to `[{b_lat}, {b_lng}]` intersects the edge going from `[{c_lat}, {c_lng}]` to `[{d_lat}, {d_lng}]`."
+            ),
             Self::Lat(lat) => write!(
                 f,
                 "Bad latitude `{}`. Latitude must be contained between -90 and 90 degrees. ",
@@ -64,6 +85,8 @@ impl Display for BadGeoError {
 enum FilterError<'a> {
     AttributeNotFilterable { attribute: &'a str, filterable_patterns: BTreeSet<&'a str> },
     ParseGeoError(BadGeoError),
+    BadRegex(BadRegexError),
+    BadDatetime,
     TooDeep,
 }
 impl std::error::Error for FilterError<'_> {}
@@ -101,6 +124,11 @@ impl Display for FilterError<'_> {
                 MAX_FILTER_DEPTH
             ),
             Self::ParseGeoError(error) => write!(f, "{}", error),
+            Self::BadRegex(error) => write!(f, "{}", error),
+            Self::BadDatetime => write!(
+                f,
+                "Invalid datetime. A datetime must follow RFC 3339 like `2024-01-31T12:00:00Z`, be a day like `2024-01-31`, be relative to the current time like `NOW-7d`, or be a number of seconds since the Unix epoch."
+            ),
         }
     }
 }
@@ -227,6 +255,48 @@ impl<'a> Filter<'a> {
     pub fn use_contains_operator(&self) -> Option<&Token> {
         self.condition.use_contains_operator()
     }
+
+    /// Splits the filter into the filters a document must all match to match this filter,
+    /// that is the operands of its top-level `AND`s.
+    pub fn conjuncts(&self) -> Vec<Filter<'a>> {
+        fn push_conjuncts<'a>(condition: &FilterCondition<'a>, conjuncts: &mut Vec<Filter<'a>>) {
+            match condition {
+                FilterCondition::And(conditions) => {
+                    conditions.iter().for_each(|condition| push_conjuncts(condition, conjuncts))
+                }
+                condition => conjuncts.push(Filter { condition: condition.clone() }),
+            }
+        }
+
+        let mut conjuncts = Vec::new();
+        push_conjuncts(&self.condition, &mut conjuncts);
+        conjuncts
+    }
+
+    /// Returns `true` if the filter is only about the values of the `facet` field or of its
+    /// nested fields, like `color = red OR color = blue` for the `color` facet.
+    ///
+    /// Geo filters are never about a single facet.
+    pub fn only_applies_to(&self, facet: &str) -> bool {
+        fn only_applies_to(condition: &FilterCondition, facet: &str) -> bool {
+            match condition {
+                FilterCondition::Condition { fid, .. } | FilterCondition::In { fid, .. } => {
+                    crate::is_faceted_by(fid.value(), facet)
+                }
+                FilterCondition::Not(condition) => only_applies_to(condition, facet),
+                FilterCondition::Or(conditions) | FilterCondition::And(conditions) => {
+                    !conditions.is_empty()
+                        && conditions.iter().all(|condition| only_applies_to(condition, facet))
+                }
+                FilterCondition::GeoLowerThan { .. }
+                | FilterCondition::GeoBoundingBox { .. }
+                | FilterCondition::GeoPolygon { .. }
+                | FilterCondition::GeoIntersects { .. } => false,
+            }
+        }
+
+        only_applies_to(&self.condition, facet)
+    }
 }
 
 impl<'a> Filter<'a> {
@@ -305,18 +375,21 @@ impl<'a> Filter<'a> {
                 ));
             }
             Condition::GreaterThan(val) => {
-                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
+                (Excluded(parse_comparison_value(val, features)?), Included(f64::MAX))
             }
             Condition::GreaterThanOrEqual(val) => {
-                (Included(val.parse_finite_float()?), Included(f64::MAX))
+                (Included(parse_comparison_value(val, features)?), Included(f64::MAX))
             }
-            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
-            Condition::LowerThanOrEqual(val) => {
-                (Included(f64::MIN), Included(val.parse_finite_float()?))
+            Condition::LowerThan(val) => {
+                (Included(f64::MIN), Excluded(parse_comparison_value(val, features)?))
             }
-            Condition::Between { from, to } => {
-                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+            Condition::LowerThanOrEqual(val) => {
+                (Included(f64::MIN), Included(parse_comparison_value(val, features)?))
             }
+            Condition::Between { from, to } => (
+                Included(parse_comparison_value(from, features)?),
+                Included(parse_comparison_value(to, features)?),
+            ),
             Condition::Null => {
                 let is_null = index.null_faceted_documents_ids(rtxn, field_id)?;
                 return Ok(is_null);
@@ -341,7 +414,7 @@ impl<'a> Filter<'a> {
                     )?
                     .map(|v| v.bitmap)
                     .unwrap_or_default();
-                let number = val.parse_finite_float().ok();
+                let number = parse_comparison_value(val, features).ok();
                 let number_docids = match number {
                     Some(n) => numbers_db
                         .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: n })?
@@ -359,58 +432,25 @@ impl<'a> Filter<'a> {
                 let all_ids = index.documents_ids(rtxn)?;
                 return Ok(all_ids - docids);
             }
-            Condition::Contains { keyword: _, word } => {
-                let value = crate::normalize_facet(word.value());
-                let finder = Finder::new(&value);
-                let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
-                let docids = strings_db
-                    .prefix_iter(rtxn, &base)?
-                    .remap_data_type::<LazyDecode<FacetGroupValueCodec>>()
-                    .filter_map(|result| -> Option<Result<RoaringBitmap>> {
-                        match result {
-                            Ok((FacetGroupKey { left_bound, .. }, lazy_group_value)) => {
-                                if finder.find(left_bound.as_bytes()).is_some() {
-                                    Some(lazy_group_value.decode().map(|gv| gv.bitmap).map_err(
-                                        |_| {
-                                            InternalError::from(SerializationError::Decoding {
-                                                db_name: Some(FACET_ID_STRING_DOCIDS),
-                                            })
-                                            .into()
-                                        },
-                                    ))
-                                } else {
-                                    None
-                                }
-                            }
-                            Err(_e) => {
-                                Some(Err(InternalError::from(SerializationError::Decoding {
-                                    db_name: Some(FACET_ID_STRING_DOCIDS),
-                                })
-                                .into()))
-                            }
-                        }
-                    })
-                    .union()?;
-
-                return Ok(docids);
+            Condition::Contains { keyword: _, word, case_sensitive } => {
+                let filter = StringFilter::contains(word.value(), *case_sensitive)
+                    .map_err(|error| word.as_external_error(FilterError::BadRegex(error)))?;
+                return string_filter_docids(rtxn, index, field_id, &filter);
             }
-            Condition::StartsWith { keyword: _, word } => {
-                let value = crate::normalize_facet(word.value());
-                let base = FacetGroupKey { field_id, level: 0, left_bound: value.as_str() };
-                let docids = strings_db
-                    .prefix_iter(rtxn, &base)?
-                    .map(|result| -> Result<RoaringBitmap> {
-                        match result {
-                            Ok((_facet_group_key, FacetGroupValue { bitmap, .. })) => Ok(bitmap),
-                            Err(_e) => Err(InternalError::from(SerializationError::Decoding {
-                                db_name: Some(FACET_ID_STRING_DOCIDS),
-                            })
-                            .into()),
-                        }
-                    })
-                    .union()?;
-
-                return Ok(docids);
+            Condition::StartsWith { keyword: _, word, case_sensitive } => {
+                let filter = StringFilter::starts_with(word.value(), *case_sensitive)
+                    .map_err(|error| word.as_external_error(FilterError::BadRegex(error)))?;
+                return string_filter_docids(rtxn, index, field_id, &filter);
+            }
+            Condition::EndsWith { keyword: _, word, case_sensitive } => {
+                let filter = StringFilter::ends_with(word.value(), *case_sensitive)
+                    .map_err(|error| word.as_external_error(FilterError::BadRegex(error)))?;
+                return string_filter_docids(rtxn, index, field_id, &filter);
+            }
+            Condition::Matches { keyword: _, regex, case_sensitive } => {
+                let filter = StringFilter::matches(regex.value(), *case_sensitive)
+                    .map_err(|error| regex.as_external_error(FilterError::BadRegex(error)))?;
+                return string_filter_docids(rtxn, index, field_id, &filter);
             }
         };
 
@@ -561,7 +601,9 @@ impl<'a> Filter<'a> {
                 }
             }
             FilterCondition::GeoLowerThan { point, radius } => {
-                if index.is_geo_filtering_enabled(rtxn)? {
+                let geo_enabled = index.is_geo_filtering_enabled(rtxn)?;
+                let geojson_enabled = index.is_geojson_filtering_enabled(rtxn)?;
+                if geo_enabled || geojson_enabled {
                     let base_point: [f64; 2] =
                         [point[0].parse_finite_float()?, point[1].parse_finite_float()?];
                     if !(-90.0..=90.0).contains(&base_point[0]) {
@@ -571,21 +613,30 @@ impl<'a> Filter<'a> {
                         return Err(point[1].as_external_error(BadGeoError::Lng(base_point[1])))?;
                     }
                     let radius = radius.parse_finite_float()?;
-                    let rtree = match index.geo_rtree(rtxn)? {
-                        Some(rtree) => rtree,
-                        None => return Ok(RoaringBitmap::new()),
-                    };
 
-                    let xyz_base_point = lat_lng_to_xyz(&base_point);
+                    let mut result = RoaringBitmap::new();
+                    let rtree = if geo_enabled { index.geo_rtree(rtxn)? } else { None };
+                    if let Some(rtree) = rtree {
+                        let xyz_base_point = lat_lng_to_xyz(&base_point);
 
-                    let result = rtree
-                        .nearest_neighbor_iter(&xyz_base_point)
-                        .take_while(|point| {
-                            distance_between_two_points(&base_point, &point.data.1)
-                                <= radius + f64::EPSILON
-                        })
-                        .map(|point| point.data.0)
-                        .collect();
+                        result = rtree
+                            .nearest_neighbor_iter(&xyz_base_point)
+                            .take_while(|point| {
+                                distance_between_two_points(&base_point, &point.data.1)
+                                    <= radius + f64::EPSILON
+                            })
+                            .map(|point| point.data.0)
+                            .collect();
+                    }
+
+                    if geojson_enabled {
+                        result |= geojson_filter(
+                            rtxn,
+                            index,
+                            &circle_bounding_boxes(base_point, radius),
+                            |shape| shape.intersects_circle(base_point, radius + f64::EPSILON),
+                        )?;
+                    }
 
                     Ok(result)
                 } else {
@@ -599,7 +650,9 @@ impl<'a> Filter<'a> {
                 }
             }
             FilterCondition::GeoBoundingBox { top_right_point, bottom_left_point } => {
-                if index.is_geo_filtering_enabled(rtxn)? {
+                let geo_enabled = index.is_geo_filtering_enabled(rtxn)?;
+                let geojson_enabled = index.is_geojson_filtering_enabled(rtxn)?;
+                if geo_enabled || geojson_enabled {
                     let top_right: [f64; 2] = [
                         top_right_point[0].parse_finite_float()?,
                         top_right_point[1].parse_finite_float()?,
@@ -632,6 +685,19 @@ impl<'a> Filter<'a> {
                         ))?;
                     }
 
+                    let mut result = RoaringBitmap::new();
+                    if geojson_enabled {
+                        let bounding_box = GeoJsonShape::from_bounding_box(top_right, bottom_left);
+                        result =
+                            geojson_filter(rtxn, index, &bounding_box.bounding_boxes(), |shape| {
+                                shape.intersects(&bounding_box)
+                            })?;
+                    }
+
+                    if !geo_enabled {
+                        return Ok(result);
+                    }
+
                     // Instead of writing a custom `GeoBoundingBox` filter we're simply going to re-use the range
                     // filter to create the following filter;
                     // `_geo.lat {top_right[0]} TO {bottom_left[0]} AND _geo.lng {top_right[1]} TO {bottom_left[1]}`
@@ -726,7 +792,51 @@ impl<'a> Filter<'a> {
                         )?
                     };
 
-                    Ok(selected_lat & selected_lng)
+                    let mut selected = selected_lat & selected_lng;
+
+                    // A document with multiple geo points can match the latitude of one of its
+                    // points and the longitude of another one, we must make sure that one of its
+                    // points is actually in the bounding box. Only the documents with several
+                    // latitudes and several longitudes are checked against the rtree.
+                    let mut several_coordinates = RoaringBitmap::new();
+                    if let Some((lat_fid, lng_fid)) =
+                        field_ids_map.id("_geo.lat").zip(field_ids_map.id("_geo.lng"))
+                    {
+                        for docid in &selected {
+                            if has_several_values(index, rtxn, lat_fid, docid)?
+                                && has_several_values(index, rtxn, lng_fid, docid)?
+                            {
+                                several_coordinates.insert(docid);
+                            }
+                        }
+                    }
+                    if !several_coordinates.is_empty() {
+                        let rtree = index.geo_rtree(rtxn)?.unwrap_or_default();
+                        let [south, west] = bottom_left;
+                        let [north, east] = top_right;
+                        let boxes = if east < west {
+                            vec![([south, west], [north, 180.0]), ([south, -180.0], [north, east])]
+                        } else {
+                            vec![(bottom_left, top_right)]
+                        };
+                        let in_bounding_box = boxes
+                            .into_iter()
+                            .flat_map(|(min, max)| {
+                                let envelope = bounding_box_xyz_envelope(min, max);
+                                rtree.locate_in_envelope(&envelope).filter(move |point| {
+                                    let [lat, lng] = point.data.1;
+                                    (min[0]..=max[0]).contains(&lat)
+                                        && (min[1]..=max[1]).contains(&lng)
+                                })
+                            })
+                            .map(|point| point.data.0)
+                            .filter(|docid| several_coordinates.contains(*docid));
+                        let in_bounding_box: RoaringBitmap = in_bounding_box.collect();
+                        selected -= &several_coordinates;
+                        selected |= in_bounding_box;
+                    }
+
+                    Ok(result | selected)
                 } else {
                     Err(top_right_point[0].as_external_error(
                         FilterError::AttributeNotFilterable {
@@ -739,8 +849,141 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoPolygon { points } => {
+                if index.is_geo_filtering_enabled(rtxn)? {
+                    let ring = points.iter().map(parse_geo_point).collect::<Result<Vec<_>>>()?;
+
+                    // the parser ensures that there is at least one point
+                    let [last_lat, _] = &points[points.len() - 1];
+                    let (first, last) = (ring[0], ring[ring.len() - 1]);
+                    if first != last {
+                        return Err(
+                            last_lat.as_external_error(BadGeoError::PolygonNotClosed(first, last))
+                        )?;
+                    }
+                    if ring.len() < 4 {
+                        return Err(last_lat
+                            .as_external_error(BadGeoError::PolygonTooFewPoints(ring.len())))?;
+                    }
+
+                    let polygon = GeoPolygon::new(ring);
+                    if let Some((i, j)) = polygon.self_intersection() {
+                        return Err(points[j][0].as_external_error(
+                            BadGeoError::PolygonSelfIntersecting(polygon.edge(i), polygon.edge(j)),
+                        ))?;
+                    }
+
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    let result = rtree
+                        .locate_in_envelope(&polygon.xyz_envelope())
+                        .filter(|point| polygon.contains(&point.data.1))
+                        .map(|point| point.data.0)
+                        .collect();
+
+                    Ok(result)
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEO_FIELD_NAME,
+                        filterable_patterns: filtered_matching_patterns(
+                            filterable_attribute_rules,
+                            &|features| features.is_filterable(),
+                        ),
+                    }))?
+                }
+            }
+            FilterCondition::GeoIntersects { points } => {
+                if index.is_geojson_filtering_enabled(rtxn)? {
+                    let points = points.iter().map(parse_geo_point).collect::<Result<Vec<_>>>()?;
+                    let shape = GeoJsonShape::from_points(points);
+
+                    geojson_filter(rtxn, index, &shape.bounding_boxes(), |other| {
+                        other.intersects(&shape)
+                    })
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: RESERVED_GEOJSON_FIELD_NAME,
+                        filterable_patterns: filtered_matching_patterns(
+                            filterable_attribute_rules,
+                            &|features| features.is_filterable(),
+                        ),
+                    }))?
+                }
+            }
+        }
+    }
+}
+
+/// Parses and validates a `[latitude, longitude]` pair of tokens.
+fn parse_geo_point([lat, lng]: &[Token; 2]) -> Result<[f64; 2]> {
+    let point = [lat.parse_finite_float()?, lng.parse_finite_float()?];
+    if !(-90.0..=90.0).contains(&point[0]) {
+        return Err(lat.as_external_error(BadGeoError::Lat(point[0])))?;
+    }
+    if !(-180.0..=180.0).contains(&point[1]) {
+        return Err(lng.as_external_error(BadGeoError::Lng(point[1])))?;
+    }
+    Ok(point)
+}
+
+/// Parses the number a facet value is compared to.
+///
+/// The values of datetime fields can also be datetimes, like `2024-01-31` or `NOW-7d`,
+/// that are converted into the number of seconds since the Unix epoch they are indexed as.
+fn parse_comparison_value(token: &Token, features: &FilterableAttributesFeatures) -> Result<f64> {
+    if !features.is_datetime() {
+        return Ok(token.parse_finite_float()?);
+    }
+    if let Some(timestamp) = parse_datetime_expression(token.value(), OffsetDateTime::now_utc()) {
+        return Ok(timestamp);
+    }
+    match token.parse_finite_float() {
+        Ok(timestamp) => Ok(timestamp),
+        Err(_) => Err(token.as_external_error(FilterError::BadDatetime))?,
+    }
+}
+
+/// Whether the document has more than one number value in the given field.
+fn has_several_values(
+    index: &Index,
+    rtxn: &heed::RoTxn<'_>,
+    field_id: FieldId,
+    docid: DocumentId,
+) -> Result<bool> {
+    let prefix = concat_arrays::concat_arrays!(field_id.to_be_bytes(), docid.to_be_bytes());
+    let mut values =
+        index.field_id_docid_facet_f64s.remap_types::<Bytes, Unit>().prefix_iter(rtxn, &prefix)?;
+    Ok(values.nth(1).transpose()?.is_some())
+}
+
+/// Returns the documents with a `_geojson` shape matching the `predicate`.
+///
+/// Only the shapes whose bounding box intersects one of the `bounding_boxes` are
+/// given to the `predicate`, the shapes are read from the geojson rtree.
+fn geojson_filter(
+    rtxn: &heed::RoTxn<'_>,
+    index: &Index,
+    bounding_boxes: &[[[f64; 2]; 2]],
+    predicate: impl Fn(&GeoJsonShape) -> bool,
+) -> Result<RoaringBitmap> {
+    let Some(rtree) = index.geojson_rtree(rtxn)? else {
+        return Ok(RoaringBitmap::new());
+    };
+
+    let mut result = RoaringBitmap::new();
+    for [min, max] in bounding_boxes {
+        for envelope in rtree.locate_in_envelope_intersecting(&AABB::from_corners(*min, *max)) {
+            let (docid, shape) = &envelope.data;
+            if !result.contains(*docid) && predicate(shape) {
+                result.insert(*docid);
+            }
         }
     }
+
+    Ok(result)
 }
 
 fn generate_filter_error(
@@ -781,7 +1024,7 @@ mod tests {
     use meili_snap::snapshot;
     use roaring::RoaringBitmap;
 
-    use crate::constants::RESERVED_GEO_FIELD_NAME;
+    use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
     use crate::index::tests::TempIndex;
     use crate::{Filter, FilterableAttributesRule};
 
@@ -808,6 +1051,25 @@ mod tests {
         assert!(bitmap.is_empty());
     }
 
+    #[test]
+    fn conjuncts() {
+        let filter =
+            Filter::from_str("(color = red OR color = blue) AND (size > 10 AND color.shade = dark) AND NOT (_geoRadius(12, 13, 14) OR color = green)")
+                .unwrap()
+                .unwrap();
+        let conjuncts = filter.conjuncts();
+        let conjuncts: Vec<_> = conjuncts
+            .iter()
+            .map(|conjunct| (conjunct.only_applies_to("color"), conjunct.only_applies_to("size")))
+            .collect();
+        snapshot!(format!("{conjuncts:?}"), @"[(true, false), (false, true), (true, false), (false, false)]");
+
+        let filter = Filter::from_str("colors = red").unwrap().unwrap();
+        assert_eq!(filter.conjuncts(), vec![filter.clone()]);
+        assert!(!filter.only_applies_to("color"));
+        assert!(filter.only_applies_to("colors"));
+    }
+
     #[test]
     fn from_array() {
         // Simple array with Left
@@ -1191,6 +1453,331 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn geo_polygon() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEO_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 45.4777599, "lng": 9.1967508 } },
+              { "id": 2, RESERVED_GEO_FIELD_NAME: { "lat": 45.4632046, "lng": 9.1719421 } },
+              { "id": 3, RESERVED_GEO_FIELD_NAME: { "lat": 45.4800000, "lng": 9.1700000 } },
+              { "id": 4, RESERVED_GEO_FIELD_NAME: { "lat": 48.8566000, "lng": 2.3522000 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // a triangle around the two first points in Milan
+        let filter = Filter::from_str(
+            "_geoPolygon([[45.46, 9.16], [45.46, 9.21], [45.49, 9.21], [45.46, 9.16]])",
+        )
+        .unwrap()
+        .unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 1]);
+
+        // a square around the three points in Milan
+        let filter = Filter::from_str(
+            "_geoPolygon([[45.46, 9.16], [45.49, 9.16], [45.49, 9.21], [45.46, 9.21], [45.46, 9.16]])",
+        )
+        .unwrap()
+        .unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        let filter = Filter::from_str(
+            "NOT _geoPolygon([[45.46, 9.16], [45.49, 9.16], [45.49, 9.21], [45.46, 9.21], [45.46, 9.16]])",
+        )
+        .unwrap()
+        .unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn geo_polygon_error() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEO_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // geoPolygon have a bad latitude
+        let filter =
+            Filter::from_str("_geoPolygon([[0, 0], [91, 0], [0, 10], [0, 0]])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("Bad latitude `91`. Latitude must be contained between -90 and 90 degrees."));
+
+        // geoPolygon have a bad longitude
+        let filter = Filter::from_str("_geoPolygon([[0, 0], [10, 0], [0, -180.1], [0, 0]])")
+            .unwrap()
+            .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().contains(
+            "Bad longitude `-180.1`. Longitude must be contained between -180 and 180 degrees."
+        ));
+
+        // geoPolygon is not closed
+        let filter = Filter::from_str("_geoPolygon([[0, 0], [10, 0], [0, 10]])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "The polygon is not closed. The first point `[0, 0]` must be equal to the last point `[0, 10]`."
+            ),
+            "{}",
+            error
+        );
+
+        // geoPolygon doesn't have enough points
+        let filter = Filter::from_str("_geoPolygon([[0, 0], [10, 0], [0, 0]])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "A polygon must be made of at least four points, the last one being equal to the first one, but only `3` points were provided."
+            ),
+            "{}",
+            error
+        );
+
+        // geoPolygon is self-intersecting
+        let filter = Filter::from_str("_geoPolygon([[0, 0], [10, 10], [10, 0], [0, 10], [0, 0]])")
+            .unwrap()
+            .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "The polygon is self-intersecting. The edge going from `[0, 0]` to `[10, 10]` intersects the edge going from `[10, 0]` to `[0, 10]`."
+            ),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn geo_filters_with_multiple_points() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEO_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 5, "lng": 20 }, { "lat": 20, "lng": 5 }] },
+              { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 5, "lng": 5 } },
+              { "id": 2 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>()
+        };
+
+        assert_eq!(evaluate("_geoRadius(20, 5, 1000)"), vec![0]);
+        assert_eq!(evaluate("_geoRadius(5, 5, 1000)"), vec![1]);
+        // the document 0 matches the latitude of its first point
+        // and the longitude of its second point but none of its points
+        assert_eq!(evaluate("_geoBoundingBox([6, 6], [4, 4])"), vec![1]);
+        assert_eq!(evaluate("_geoBoundingBox([21, 6], [19, 4])"), vec![0]);
+        assert_eq!(evaluate("_geoBoundingBox([21, 21], [4, 4])"), vec![0, 1]);
+    }
+
+    #[test]
+    fn geojson() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+              { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [
+                  [[9.16, 45.46], [9.21, 45.46], [9.21, 45.49], [9.16, 45.49], [9.16, 45.46]]
+              ] } },
+              { "id": 2, RESERVED_GEOJSON_FIELD_NAME: {
+                  "type": "LineString", "coordinates": [[2.3522, 48.8566], [13.405, 52.52]]
+              } },
+              { "id": 3 },
+            ]))
+            .unwrap();
+
+        // the geojson rtree is built when `_geojson` becomes filterable
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEOJSON_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 4, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [4.8357, 45.764] } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>()
+        };
+
+        // a point in the polygon around Milan
+        assert_eq!(evaluate("_geoIntersects([[45.47, 9.19]])"), vec![0]);
+        // Brussels is around 200km from the line going from Paris to Berlin
+        assert_eq!(evaluate("_geoRadius(50.8503, 4.3517, 250000)"), vec![1]);
+        assert_eq!(evaluate("_geoRadius(50.8503, 4.3517, 100000)"), Vec::<u32>::new());
+        // a bounding box around Lyon
+        assert_eq!(evaluate("_geoBoundingBox([46, 6], [45, 4])"), vec![3]);
+        // a polygon covering Milan, Paris and Lyon
+        let filter = "_geoIntersects([[40, 0], [50, 0], [50, 10], [40, 10], [40, 0]])";
+        assert_eq!(evaluate(filter), vec![0, 1, 3]);
+        assert_eq!(evaluate(&format!("NOT {filter}")), vec![2]);
+        drop(rtxn);
+
+        index.delete_document("2");
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str(filter).unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 3]);
+    }
+
+    #[test]
+    fn geojson_antimeridian() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEOJSON_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [175.0, -18.0] } },
+              { "id": 2, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [-175.0, -18.0] } },
+              { "id": 3, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [0.0, -18.0] } },
+              // a polygon around the Fiji islands crossing the antimeridian
+              { "id": 4, RESERVED_GEOJSON_FIELD_NAME: { "type": "Polygon", "coordinates": [
+                  [[177.0, -16.0], [-179.0, -16.0], [-179.0, -19.0], [177.0, -19.0], [177.0, -16.0]]
+              ] } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().into_iter().collect::<Vec<_>>()
+        };
+
+        // the shapes are read from the rtree, not from the documents
+        let rtree = index.geojson_rtree(&rtxn).unwrap().unwrap();
+        assert_eq!(rtree.size(), 4);
+        assert!(rtree
+            .iter()
+            .any(|envelope| matches!(envelope.data, (3, crate::GeoJsonShape::MultiPolygon(_)))));
+
+        // a polygon going from 170 to -170 through the antimeridian, not through the meridian
+        let filter =
+            "_geoIntersects([[-10, 170], [-10, -170], [-25, -170], [-25, 170], [-10, 170]])";
+        assert_eq!(evaluate(filter), vec![0, 1, 3]);
+        // points on each side of the antimeridian only match the polygon of the document crossing it
+        assert_eq!(evaluate("_geoIntersects([[-17, 178]])"), vec![3]);
+        assert_eq!(evaluate("_geoIntersects([[-17, -179.5]])"), vec![3]);
+        assert_eq!(evaluate("_geoIntersects([[-17, 0]])"), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn geojson_is_reserved() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEOJSON_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 1, "name": "Milan", RESERVED_GEOJSON_FIELD_NAME: {
+                  "type": "LineString", "coordinates": [[9.16, 45.46], [9.21, 45.49]]
+              } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        // the subfields of `_geojson` are neither searchable nor faceted
+        let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+        assert!(fields_ids_map.id(RESERVED_GEOJSON_FIELD_NAME).is_some());
+        assert!(fields_ids_map.id("_geojson.type").is_none());
+        assert!(fields_ids_map.id("_geojson.coordinates").is_none());
+        let mut search = crate::Search::new(&rtxn, &index);
+        search.query("LineString");
+        assert!(search.execute().unwrap().documents_ids.is_empty());
+        search.query("Milan");
+        assert_eq!(search.execute().unwrap().documents_ids, vec![0]);
+    }
+
+    #[test]
+    fn geojson_error() {
+        let index = TempIndex::new();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("_geoIntersects([[0, 0]])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(
+            error.to_string().starts_with("Attribute `_geojson` is not filterable."),
+            "{}",
+            error
+        );
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(vec![FilterableAttributesRule::Field(S(
+                    RESERVED_GEOJSON_FIELD_NAME,
+                ))]);
+            })
+            .unwrap();
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 1, RESERVED_GEOJSON_FIELD_NAME: { "type": "Point", "coordinates": [45.0, 91.0] } },
+            ]))
+            .unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "Could not parse a position in the `_geojson` field of the document with the id: `\"1\"`."
+            ),
+            "{}",
+            error
+        );
+    }
     #[test]
     fn filter_depth() {
         // generates a big (2 MiB) filter with too much of ORs.
@@ -0,0 +1,232 @@
+use rstar::AABB;
+use serde::{Deserialize, Serialize};
+
+use crate::lat_lng_to_xyz;
+
+/// A polygon described by a closed ring of `[latitude, longitude]` points.
+///
+/// The edges of the polygon are straight lines in the latitude/longitude plane,
+/// which means that a polygon can't cross the antimeridian.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeoPolygon {
+    /// The points of the ring, the first point is always equal to the last one.
+    ring: Vec<[f64; 2]>,
+}
+
+impl GeoPolygon {
+    /// Creates a new polygon from a ring of points.
+    ///
+    /// The ring must already be closed, i.e. its first and last points must be equal.
+    pub fn new(ring: Vec<[f64; 2]>) -> Self {
+        debug_assert_eq!(ring.first(), ring.last());
+        Self { ring }
+    }
+
+    /// Returns the points of the ring, the first point being equal to the last one.
+    pub fn points(&self) -> &[[f64; 2]] {
+        &self.ring
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = ([f64; 2], [f64; 2])> + '_ {
+        self.ring.windows(2).map(|w| (w[0], w[1]))
+    }
+
+    /// Returns the start and end points of the edge at the given index.
+    pub fn edge(&self, index: usize) -> [[f64; 2]; 2] {
+        [self.ring[index], self.ring[index + 1]]
+    }
+
+    /// Returns the indexes of the first two edges of the polygon that intersect with each other.
+    pub fn self_intersection(&self) -> Option<(usize, usize)> {
+        let edges: Vec<_> = self.edges().collect();
+        let last = edges.len() - 1;
+        for (i, &(a, b)) in edges.iter().enumerate() {
+            for (j, &(c, d)) in edges.iter().enumerate().skip(i + 1) {
+                let intersects = if j == i + 1 {
+                    consecutive_edges_overlap(a, b, d)
+                } else if i == 0 && j == last {
+                    consecutive_edges_overlap(b, a, c)
+                } else {
+                    segments_intersect(a, b, c, d)
+                };
+                if intersects {
+                    return Some((i, j));
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns `true` if the point is on one of the edges of the polygon.
+    pub fn on_boundary(&self, point: &[f64; 2]) -> bool {
+        self.edges().any(|(a, b)| orientation(a, b, *point) == 0.0 && on_segment(*point, a, b))
+    }
+
+    /// Returns `true` if the point is inside the polygon or on one of its edges.
+    pub fn contains(&self, point: &[f64; 2]) -> bool {
+        if self.on_boundary(point) {
+            return true;
+        }
+
+        let [lat, lng] = *point;
+        let mut inside = false;
+        for (a, b) in self.edges() {
+            // ray casting along the longitude axis
+            if (a[0] > lat) != (b[0] > lat) {
+                let lng_at_lat = a[1] + (lat - a[0]) / (b[0] - a[0]) * (b[1] - a[1]);
+                if lng < lng_at_lat {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+
+    /// Returns the smallest envelope, in the cartesian space, containing every point of
+    /// the latitude/longitude bounding box of the polygon.
+    ///
+    /// It can be used to query the geo rtree for the candidates of this polygon.
+    pub fn xyz_envelope(&self) -> AABB<[f64; 3]> {
+        let (mut min_lat, mut max_lat) = (f64::MAX, f64::MIN);
+        let (mut min_lng, mut max_lng) = (f64::MAX, f64::MIN);
+        for [lat, lng] in &self.ring {
+            min_lat = min_lat.min(*lat);
+            max_lat = max_lat.max(*lat);
+            min_lng = min_lng.min(*lng);
+            max_lng = max_lng.max(*lng);
+        }
+
+        bounding_box_xyz_envelope([min_lat, min_lng], [max_lat, max_lng])
+    }
+}
+
+/// Returns the smallest envelope, in the cartesian space, containing every point of
+/// the latitude/longitude bounding box going from the `min` to the `max` corner.
+///
+/// It can be used to query the geo rtree for the points in the bounding box.
+pub fn bounding_box_xyz_envelope(min: [f64; 2], max: [f64; 2]) -> AABB<[f64; 3]> {
+    let [min_lat, min_lng] = min;
+    let [max_lat, max_lng] = max;
+
+    // The cartesian coordinates are products of sines and cosines of the latitude and longitude,
+    // their extremums are reached either on the bounds of the box or where one of the sines or
+    // cosines reaches its own extremum.
+    let lats = [min_lat, max_lat, 0.0].into_iter().filter(|lat| (min_lat..=max_lat).contains(lat));
+    let lngs: Vec<f64> = [min_lng, max_lng, -180.0, -90.0, 0.0, 90.0, 180.0]
+        .into_iter()
+        .filter(|lng| (min_lng..=max_lng).contains(lng))
+        .collect();
+
+    let mut min = [f64::MAX; 3];
+    let mut max = [f64::MIN; 3];
+    for lat in lats {
+        for lng in &lngs {
+            let xyz = lat_lng_to_xyz(&[lat, *lng]);
+            min = std::array::from_fn(|axis| min[axis].min(xyz[axis]));
+            max = std::array::from_fn(|axis| max[axis].max(xyz[axis]));
+        }
+    }
+
+    // we make the envelope a little bit larger to absorb the rounding errors
+    AABB::from_corners(min.map(|c| c - f64::EPSILON), max.map(|c| c + f64::EPSILON))
+}
+
+/// Returns a positive value if `a`, `b` and `c` are in counterclockwise order,
+/// a negative value if they are in clockwise order and zero if they are collinear.
+fn orientation(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+/// Returns `true` if `p`, which must be collinear with `a` and `b`, lies on the `a`-`b` segment.
+fn on_segment(p: [f64; 2], a: [f64; 2], b: [f64; 2]) -> bool {
+    (a[0].min(b[0])..=a[0].max(b[0])).contains(&p[0])
+        && (a[1].min(b[1])..=a[1].max(b[1])).contains(&p[1])
+}
+
+/// Consecutive edges always share a point, they only intersect when they overlap.
+/// `p` is the other end of the first edge and `q` the other end of the second one.
+fn consecutive_edges_overlap(p: [f64; 2], shared: [f64; 2], q: [f64; 2]) -> bool {
+    orientation(p, shared, q) == 0.0
+        && ((q != shared && on_segment(q, p, shared)) || (p != shared && on_segment(p, shared, q)))
+}
+
+/// Returns `true` if the `a`-`b` segment intersects or touches the `c`-`d` segment.
+pub fn segments_intersect(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> bool {
+    let o1 = orientation(a, b, c);
+    let o2 = orientation(a, b, d);
+    let o3 = orientation(c, d, a);
+    let o4 = orientation(c, d, b);
+
+    if o1 * o2 < 0.0 && o3 * o4 < 0.0 {
+        return true;
+    }
+
+    (o1 == 0.0 && on_segment(c, a, b))
+        || (o2 == 0.0 && on_segment(d, a, b))
+        || (o3 == 0.0 && on_segment(a, c, d))
+        || (o4 == 0.0 && on_segment(b, c, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstar::Envelope;
+
+    use super::*;
+
+    fn square() -> GeoPolygon {
+        GeoPolygon::new(vec![[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0], [0.0, 0.0]])
+    }
+
+    #[test]
+    fn contains() {
+        let polygon = square();
+        assert!(polygon.contains(&[5.0, 5.0]));
+        assert!(polygon.contains(&[0.0, 5.0]));
+        assert!(polygon.contains(&[10.0, 10.0]));
+        assert!(!polygon.contains(&[10.1, 5.0]));
+        assert!(!polygon.contains(&[-5.0, -5.0]));
+
+        // a concave polygon shaped like a `U`
+        let polygon = GeoPolygon::new(vec![
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 3.0],
+            [2.0, 3.0],
+            [2.0, 7.0],
+            [10.0, 7.0],
+            [10.0, 10.0],
+            [0.0, 10.0],
+            [0.0, 0.0],
+        ]);
+        assert!(polygon.contains(&[1.0, 5.0]));
+        assert!(polygon.contains(&[5.0, 1.0]));
+        assert!(!polygon.contains(&[5.0, 5.0]));
+    }
+
+    #[test]
+    fn self_intersection() {
+        assert_eq!(square().self_intersection(), None);
+
+        // a bow tie
+        let polygon =
+            GeoPolygon::new(vec![[0.0, 0.0], [10.0, 10.0], [10.0, 0.0], [0.0, 10.0], [0.0, 0.0]]);
+        assert_eq!(polygon.self_intersection(), Some((0, 2)));
+
+        // the last edge goes back on the first one
+        let polygon = GeoPolygon::new(vec![[0.0, 0.0], [0.0, 10.0], [0.0, 5.0], [0.0, 0.0]]);
+        assert_eq!(polygon.self_intersection(), Some((0, 1)));
+    }
+
+    #[test]
+    fn envelope_contains_every_point() {
+        let polygon =
+            GeoPolygon::new(vec![[-45.0, -170.0], [60.0, -170.0], [60.0, 170.0], [-45.0, -170.0]]);
+        let envelope = polygon.xyz_envelope();
+        for lat in (-45..=60).step_by(5) {
+            for lng in (-170..=170).step_by(10) {
+                let point = lat_lng_to_xyz(&[lat as f64, lng as f64]);
+                assert!(envelope.contains_point(&point), "{lat} {lng}");
+            }
+        }
+    }
+}
@@ -20,11 +20,13 @@ const DEFAULT_MAX_NUMBER_OF_VALUES_PER_FACET: usize = 100;
 
 pub struct SearchForFacetValues<'a> {
     query: Option<String>,
+    prefix: Option<String>,
     facet: String,
     search_query: Search<'a>,
     max_values: usize,
     is_hybrid: bool,
     locales: Option<Vec<Language>>,
+    pinned_documents: Option<RoaringBitmap>,
 }
 
 impl<'a> SearchForFacetValues<'a> {
@@ -35,11 +37,13 @@ impl<'a> SearchForFacetValues<'a> {
     ) -> SearchForFacetValues<'a> {
         SearchForFacetValues {
             query: None,
+            prefix: None,
             facet,
             search_query,
             max_values: DEFAULT_MAX_NUMBER_OF_VALUES_PER_FACET,
             is_hybrid,
             locales: None,
+            pinned_documents: None,
         }
     }
 
@@ -48,6 +52,13 @@ impl<'a> SearchForFacetValues<'a> {
         self
     }
 
+    /// Only returns the facet values starting with `prefix`, like the values in a branch of a
+    /// hierarchy of facets. The query then applies to the rest of the value.
+    pub fn prefix(&mut self, prefix: impl Into<String>) -> &mut Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
     pub fn max_values(&mut self, max: usize) -> &mut Self {
         self.max_values = max;
         self
@@ -58,6 +69,13 @@ impl<'a> SearchForFacetValues<'a> {
         self
     }
 
+    /// Counts the given documents in the facet values even when they don't match the search,
+    /// like the documents that are always returned by the search.
+    pub fn pinned_documents(&mut self, documents: RoaringBitmap) -> &mut Self {
+        self.pinned_documents = Some(documents);
+        self
+    }
+
     fn one_original_value_of(
         &self,
         field_id: FieldId,
@@ -109,7 +127,7 @@ impl<'a> SearchForFacetValues<'a> {
             None => return Ok(Vec::new()),
         };
 
-        let search_candidates = self.search_query.execute_for_candidates(
+        let mut search_candidates = self.search_query.execute_for_candidates(
             self.is_hybrid
                 || self
                     .search_query
@@ -118,16 +136,30 @@ impl<'a> SearchForFacetValues<'a> {
                     .and_then(|semantic| semantic.vector.as_ref())
                     .is_some(),
         )?;
+        if let Some(pinned_documents) = &self.pinned_documents {
+            search_candidates |= pinned_documents;
+        }
 
         let mut results = match index.sort_facet_values_by(rtxn)?.get(&self.facet) {
             OrderBy::Lexicographic => ValuesCollection::by_lexicographic(self.max_values),
             OrderBy::Count => ValuesCollection::by_count(self.max_values),
         };
 
+        let normalized_prefix = self.prefix.as_deref().map(crate::normalize_facet);
+        let normalized_prefix = normalized_prefix.as_deref().unwrap_or_default();
+
         match self.query.as_ref() {
             Some(query) => {
+                // The typos and the prefix search only apply to the query, the values must
+                // start with the branch given in the prefix.
                 let query = normalize_facet_string(query, self.locales.as_deref());
                 let query = query.as_ref();
+                let branch = self
+                    .prefix
+                    .as_deref()
+                    .map(|prefix| normalize_facet_string(prefix, self.locales.as_deref()))
+                    .unwrap_or_default();
+                let branch = branch.as_bytes();
 
                 let authorize_typos = self.search_query.index.authorize_typos(rtxn)?;
                 let field_authorizes_typos =
@@ -136,11 +168,14 @@ impl<'a> SearchForFacetValues<'a> {
                 if authorize_typos && field_authorizes_typos {
                     let exact_words_fst = self.search_query.index.exact_words(rtxn)?;
                     if exact_words_fst.is_some_and(|fst| fst.contains(query)) {
-                        if fst.contains(query) {
+                        let value = [branch, query.as_bytes()].concat();
+                        if fst.contains(&value) {
+                            let value = std::str::from_utf8(&value)?;
                             self.fetch_original_facets_using_normalized(
                                 fid,
-                                query,
-                                query,
+                                value,
+                                value,
+                                normalized_prefix,
                                 &search_candidates,
                                 &mut results,
                             )?;
@@ -157,6 +192,7 @@ impl<'a> SearchForFacetValues<'a> {
                         } else {
                             build_dfa(query, 2, is_prefix)
                         };
+                        let automaton = StartsWithThen { prefix: branch, automaton };
 
                         let mut stream = fst.search(automaton).into_stream();
                         while let Some(facet_value) = stream.next() {
@@ -166,6 +202,7 @@ impl<'a> SearchForFacetValues<'a> {
                                     fid,
                                     value,
                                     query,
+                                    normalized_prefix,
                                     &search_candidates,
                                     &mut results,
                                 )?
@@ -176,7 +213,8 @@ impl<'a> SearchForFacetValues<'a> {
                         }
                     }
                 } else {
-                    let automaton = Str::new(query).starts_with();
+                    let automaton =
+                        StartsWithThen { prefix: branch, automaton: Str::new(query).starts_with() };
                     let mut stream = fst.search(automaton).into_stream();
                     while let Some(facet_value) = stream.next() {
                         let value = std::str::from_utf8(facet_value)?;
@@ -185,6 +223,7 @@ impl<'a> SearchForFacetValues<'a> {
                                 fid,
                                 value,
                                 query,
+                                normalized_prefix,
                                 &search_candidates,
                                 &mut results,
                             )?
@@ -196,7 +235,8 @@ impl<'a> SearchForFacetValues<'a> {
                 }
             }
             None => {
-                let prefix = FacetGroupKey { field_id: fid, level: 0, left_bound: "" };
+                let prefix =
+                    FacetGroupKey { field_id: fid, level: 0, left_bound: normalized_prefix };
                 for result in index.facet_id_string_docids.prefix_iter(rtxn, &prefix)? {
                     let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) =
                         result?;
@@ -221,6 +261,7 @@ impl<'a> SearchForFacetValues<'a> {
         fid: FieldId,
         value: &str,
         query: &str,
+        prefix: &str,
         search_candidates: &RoaringBitmap,
         results: &mut ValuesCollection,
     ) -> Result<ControlFlow<()>> {
@@ -237,6 +278,9 @@ impl<'a> SearchForFacetValues<'a> {
             }
         };
         for original in original_strings {
+            if !original.starts_with(prefix) {
+                continue;
+            }
             let key = FacetGroupKey { field_id: fid, level: 0, left_bound: original.as_str() };
             let docids = match index.facet_id_string_docids.get(rtxn, &key)? {
                 Some(FacetGroupValue { bitmap, .. }) => bitmap,
@@ -260,6 +304,66 @@ impl<'a> SearchForFacetValues<'a> {
     }
 }
 
+/// An automaton matching the strings made of the `prefix` followed by a string
+/// matched by the inner `automaton`.
+struct StartsWithThen<'a, A> {
+    prefix: &'a [u8],
+    automaton: A,
+}
+
+#[derive(Clone)]
+enum StartsWithThenState<S> {
+    /// The number of bytes of the prefix read so far.
+    Prefix(usize),
+    /// The prefix has been read, the state of the inner automaton.
+    Inner(S),
+    /// The string doesn't start with the prefix.
+    Dead,
+}
+
+impl<A: Automaton> Automaton for StartsWithThen<'_, A> {
+    type State = StartsWithThenState<A::State>;
+
+    fn start(&self) -> Self::State {
+        if self.prefix.is_empty() {
+            StartsWithThenState::Inner(self.automaton.start())
+        } else {
+            StartsWithThenState::Prefix(0)
+        }
+    }
+
+    fn is_match(&self, state: &Self::State) -> bool {
+        match state {
+            StartsWithThenState::Inner(inner) => self.automaton.is_match(inner),
+            StartsWithThenState::Prefix(_) | StartsWithThenState::Dead => false,
+        }
+    }
+
+    fn can_match(&self, state: &Self::State) -> bool {
+        match state {
+            StartsWithThenState::Prefix(_) => true,
+            StartsWithThenState::Inner(inner) => self.automaton.can_match(inner),
+            StartsWithThenState::Dead => false,
+        }
+    }
+
+    fn accept(&self, state: &Self::State, byte: u8) -> Self::State {
+        match state {
+            StartsWithThenState::Prefix(read) if self.prefix[*read] == byte => {
+                if read + 1 == self.prefix.len() {
+                    StartsWithThenState::Inner(self.automaton.start())
+                } else {
+                    StartsWithThenState::Prefix(read + 1)
+                }
+            }
+            StartsWithThenState::Inner(inner) => {
+                StartsWithThenState::Inner(self.automaton.accept(inner, byte))
+            }
+            StartsWithThenState::Prefix(_) | StartsWithThenState::Dead => StartsWithThenState::Dead,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, PartialEq)]
 pub struct FacetValueHit {
     /// The original facet value
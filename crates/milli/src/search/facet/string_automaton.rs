@@ -0,0 +1,394 @@
+use std::fmt::Display;
+use std::ops::Bound::{Excluded, Included};
+
+use charabia::normalizer::{CharNormalizer, CompatibilityDecompositionNormalizer};
+use fst::Automaton;
+use heed::types::{Bytes, LazyDecode};
+use regex_automata::dfa::{dense, Automaton as _, StartKind};
+use regex_automata::util::primitives::StateID;
+use regex_automata::util::syntax;
+use regex_automata::{Input, MatchKind};
+use roaring::RoaringBitmap;
+
+use crate::heed_codec::facet::FacetGroupValueCodec;
+use crate::index::db_name::FACET_ID_STRING_DOCIDS;
+use crate::{FieldId, Index, InternalError, Result, SerializationError};
+
+/// The maximum size, in bytes, of the DFA compiled for a string operator.
+const DFA_SIZE_LIMIT: usize = 10 * 1024 * 1024;
+
+#[derive(Debug)]
+pub enum BadRegexError {
+    Syntax(String),
+    Build(String),
+}
+
+impl std::error::Error for BadRegexError {}
+
+impl Display for BadRegexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Syntax(error) => write!(f, "Invalid regular expression: {error}."),
+            Self::Build(error) => write!(f, "The regular expression cannot be compiled: {error}."),
+        }
+    }
+}
+
+/// A DFA searching for a pattern in the strings it is run on.
+///
+/// The DFA runs an unanchored search and every pattern ends with `(?s:.*)`, that way
+/// once a match is found every string starting with the matching prefix matches too.
+/// Patterns starting with `^` are anchored by the DFA itself which lets it reject
+/// a string as soon as its prefix can't match anymore.
+///
+/// It implements the [`fst::Automaton`] trait so that it can be intersected
+/// with an FST or with the sorted keys of a database.
+pub struct StringAutomaton {
+    dfa: dense::DFA<Vec<u32>>,
+    start: StateID,
+}
+
+impl StringAutomaton {
+    fn new(pattern: &str, case_insensitive: bool) -> Result<Self, BadRegexError> {
+        let dfa = dense::Builder::new()
+            .configure(
+                dense::Config::new()
+                    .start_kind(StartKind::Unanchored)
+                    .match_kind(MatchKind::All)
+                    .dfa_size_limit(Some(DFA_SIZE_LIMIT))
+                    .determinize_size_limit(Some(DFA_SIZE_LIMIT)),
+            )
+            .syntax(syntax::Config::new().case_insensitive(case_insensitive))
+            .build(pattern)
+            .map_err(|error| BadRegexError::Build(error.to_string()))?;
+        let start = dfa
+            .start_state_forward(&Input::new(""))
+            .map_err(|error| BadRegexError::Build(error.to_string()))?;
+        Ok(Self { dfa, start })
+    }
+
+    /// Returns `true` if the string is accepted by the automaton.
+    pub fn accepts(&self, s: &str) -> bool {
+        let state = s.bytes().fold(self.start, |state, byte| self.dfa.next_state(state, byte));
+        self.dfa.is_match_state(self.dfa.next_eoi_state(state))
+    }
+}
+
+impl Automaton for StringAutomaton {
+    type State = StateID;
+
+    fn start(&self) -> StateID {
+        self.start
+    }
+
+    fn is_match(&self, state: &StateID) -> bool {
+        // The matches of a DFA are delayed by one byte, we must
+        // feed it the end of input to know if the string matched.
+        self.dfa.is_match_state(self.dfa.next_eoi_state(*state))
+    }
+
+    fn can_match(&self, state: &StateID) -> bool {
+        !self.dfa.is_dead_state(*state) && !self.dfa.is_quit_state(*state)
+    }
+
+    fn accept(&self, state: &StateID, byte: u8) -> StateID {
+        self.dfa.next_state(*state, byte)
+    }
+}
+
+/// The automatons used to evaluate one of the string operators of the filters,
+/// i.e. `CONTAINS`, `STARTS WITH`, `ENDS WITH` and `MATCHES`.
+///
+/// The operators are case insensitive by default and run against the normalized
+/// facet values. When they are case sensitive, the normalized facet values only
+/// select the candidates and the original values of the documents are checked afterward.
+pub struct StringFilter {
+    /// Runs against the normalized facet values.
+    normalized: StringAutomaton,
+    /// Runs against the original, trimmed, facet values when the operator is case sensitive.
+    original: Option<StringAutomaton>,
+}
+
+impl StringFilter {
+    pub fn contains(word: &str, case_sensitive: bool) -> Result<Self, BadRegexError> {
+        Self::literal(word, case_sensitive, |word| format!("{word}(?s:.*)"))
+    }
+
+    pub fn starts_with(word: &str, case_sensitive: bool) -> Result<Self, BadRegexError> {
+        Self::literal(word, case_sensitive, |word| format!("^{word}(?s:.*)"))
+    }
+
+    pub fn ends_with(word: &str, case_sensitive: bool) -> Result<Self, BadRegexError> {
+        Self::literal(word, case_sensitive, |word| format!("{word}$(?s:.*)"))
+    }
+
+    /// Like a regex search, the pattern is not anchored unless it uses `^` or `$`.
+    pub fn matches(regex: &str, case_sensitive: bool) -> Result<Self, BadRegexError> {
+        if let Err(error) = regex_syntax::Parser::new().parse(regex) {
+            let error = match error {
+                regex_syntax::Error::Parse(error) => error.kind().to_string(),
+                regex_syntax::Error::Translate(error) => error.kind().to_string(),
+                error => error.to_string(),
+            };
+            return Err(BadRegexError::Syntax(error));
+        }
+
+        let search = |regex: &str| format!("(?:{regex})(?s:.*)");
+        if case_sensitive {
+            // A case sensitive regex can't be translated to work on the normalized
+            // facet values, every value is a candidate and must be checked.
+            Ok(Self {
+                normalized: StringAutomaton::new("(?s:.*)", false)?,
+                original: Some(StringAutomaton::new(&search(regex), false)?),
+            })
+        } else {
+            let regex = CompatibilityDecompositionNormalizer.normalize_str(regex);
+            Ok(Self { normalized: StringAutomaton::new(&search(&regex), true)?, original: None })
+        }
+    }
+
+    fn literal(
+        word: &str,
+        case_sensitive: bool,
+        pattern: impl Fn(&str) -> String,
+    ) -> Result<Self, BadRegexError> {
+        // The normalized facet values are already lowercased but we still compile
+        // a case insensitive automaton, it makes sure that the candidates selected
+        // for a case sensitive operator are a superset of the final result.
+        let normalized = regex_syntax::escape(&crate::normalize_facet(word));
+        let normalized = StringAutomaton::new(&pattern(&normalized), true)?;
+        let original = if case_sensitive {
+            Some(StringAutomaton::new(&pattern(&regex_syntax::escape(word)), false)?)
+        } else {
+            None
+        };
+        Ok(Self { normalized, original })
+    }
+}
+
+/// Returns the documents ids of the facet strings of the field that are accepted by the filter.
+pub fn string_filter_docids(
+    rtxn: &heed::RoTxn<'_>,
+    index: &Index,
+    field_id: FieldId,
+    filter: &StringFilter,
+) -> Result<RoaringBitmap> {
+    let mut docids = RoaringBitmap::new();
+    for_each_matching_facet_string(rtxn, index, field_id, &filter.normalized, |value, bitmap| {
+        match &filter.original {
+            None => docids |= bitmap,
+            Some(original) => {
+                for docid in bitmap {
+                    let key: (FieldId, _, &str) = (field_id, docid, value);
+                    if let Some(original_value) =
+                        index.field_id_docid_facet_strings.get(rtxn, &key)?
+                    {
+                        if original.accepts(original_value.trim()) {
+                            docids.insert(docid);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    })?;
+    Ok(docids)
+}
+
+/// Calls `f` with every level 0 facet string of the field accepted by the automaton, along with its documents ids.
+///
+/// The facet strings are sorted in the database, which lets us walk them like an FST:
+/// as soon as the automaton rejects a prefix, we jump to the next prefix that can still
+/// match instead of iterating over every facet string sharing the rejected prefix.
+fn for_each_matching_facet_string<A: Automaton>(
+    rtxn: &heed::RoTxn<'_>,
+    index: &Index,
+    field_id: FieldId,
+    automaton: &A,
+    mut f: impl FnMut(&str, RoaringBitmap) -> Result<()>,
+) -> Result<()> {
+    if !automaton.can_match(&automaton.start()) {
+        return Ok(());
+    }
+
+    let db = index.facet_id_string_docids.remap_types::<Bytes, LazyDecode<FacetGroupValueCodec>>();
+    // The facet keys are made of the field id, the level and the facet value.
+    let [fid_high, fid_low] = field_id.to_be_bytes();
+    let level_0_prefix = [fid_high, fid_low, 0];
+    let level_1_prefix = [fid_high, fid_low, 1];
+    let prefix_len = level_0_prefix.len();
+
+    let mut lower_bound = level_0_prefix.to_vec();
+    loop {
+        let range = (Included(lower_bound.as_slice()), Excluded(level_1_prefix.as_slice()));
+        let next_lower_bound = 'keys: {
+            for result in db.range(rtxn, &range)? {
+                let (key, lazy_group_value) = result?;
+                let facet_value = &key[prefix_len..];
+                match walk(automaton, facet_value) {
+                    Walk::Match => {
+                        let facet_value =
+                            std::str::from_utf8(facet_value).map_err(|_| decoding_error())?;
+                        let bitmap =
+                            lazy_group_value.decode().map_err(|_| decoding_error())?.bitmap;
+                        f(facet_value, bitmap)?;
+                    }
+                    Walk::NoMatch => (),
+                    Walk::Jump(Some(next_prefix)) => {
+                        let mut next_lower_bound = level_0_prefix.to_vec();
+                        next_lower_bound.extend_from_slice(&next_prefix);
+                        break 'keys next_lower_bound;
+                    }
+                    Walk::Jump(None) => return Ok(()),
+                }
+            }
+            return Ok(());
+        };
+        lower_bound = next_lower_bound;
+    }
+}
+
+fn decoding_error() -> crate::Error {
+    InternalError::from(SerializationError::Decoding { db_name: Some(FACET_ID_STRING_DOCIDS) })
+        .into()
+}
+
+enum Walk {
+    /// The string is accepted by the automaton.
+    Match,
+    /// The string is rejected but the strings it prefixes may be accepted.
+    NoMatch,
+    /// A prefix of the string is rejected and no string starting with this prefix can match,
+    /// contains the smallest prefix greater than the rejected one that can still match.
+    Jump(Option<Vec<u8>>),
+}
+
+fn walk<A: Automaton>(automaton: &A, bytes: &[u8]) -> Walk {
+    // the states reached before reading each byte
+    let mut states = Vec::with_capacity(bytes.len());
+    let mut state = automaton.start();
+    for &byte in bytes {
+        let next_state = automaton.accept(&state, byte);
+        states.push(state);
+        if !automaton.can_match(&next_state) {
+            return Walk::Jump(next_matching_prefix(automaton, &bytes[..states.len()], &states));
+        }
+        state = next_state;
+    }
+
+    if automaton.is_match(&state) {
+        Walk::Match
+    } else {
+        Walk::NoMatch
+    }
+}
+
+/// Returns the smallest byte string greater than all the strings starting
+/// with the rejected prefix that the automaton doesn't immediately reject.
+fn next_matching_prefix<A: Automaton>(
+    automaton: &A,
+    rejected_prefix: &[u8],
+    states: &[A::State],
+) -> Option<Vec<u8>> {
+    for (i, state) in states.iter().enumerate().rev() {
+        let Some(first_byte) = rejected_prefix[i].checked_add(1) else { continue };
+        for byte in first_byte..=u8::MAX {
+            if automaton.can_match(&automaton.accept(state, byte)) {
+                let mut prefix = rejected_prefix[..i].to_vec();
+                prefix.push(byte);
+                return Some(prefix);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk_all(filter: &StringFilter, values: &[&str]) -> Vec<String> {
+        values.iter().filter(|v| filter.normalized.accepts(v)).map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn string_filters() {
+        let values = ["hello", "hello world", "say hello", "help", "world"];
+
+        let filter = StringFilter::contains("Hello", false).unwrap();
+        insta::assert_debug_snapshot!(walk_all(&filter, &values), @r###"
+        [
+            "hello",
+            "hello world",
+            "say hello",
+        ]
+        "###);
+        let filter = StringFilter::starts_with("hel", false).unwrap();
+        insta::assert_debug_snapshot!(walk_all(&filter, &values), @r###"
+        [
+            "hello",
+            "hello world",
+            "help",
+        ]
+        "###);
+        let filter = StringFilter::ends_with("WORLD", false).unwrap();
+        insta::assert_debug_snapshot!(walk_all(&filter, &values), @r###"
+        [
+            "hello world",
+            "world",
+        ]
+        "###);
+        let filter = StringFilter::matches("^hel+o$|^wor", false).unwrap();
+        insta::assert_debug_snapshot!(walk_all(&filter, &values), @r###"
+        [
+            "hello",
+            "world",
+        ]
+        "###);
+        let filter = StringFilter::matches("l{2}", false).unwrap();
+        insta::assert_debug_snapshot!(walk_all(&filter, &values), @r###"
+        [
+            "hello",
+            "hello world",
+            "say hello",
+        ]
+        "###);
+        // the special characters of the literal operators are escaped
+        let filter = StringFilter::contains("l+", false).unwrap();
+        insta::assert_debug_snapshot!(walk_all(&filter, &values), @"[]");
+    }
+
+    #[test]
+    fn case_sensitive_string_filters() {
+        let filter = StringFilter::contains("Hello", true).unwrap();
+        let original = filter.original.as_ref().unwrap();
+        assert!(filter.normalized.accepts("hello world"));
+        assert!(original.accepts("Hello world"));
+        assert!(!original.accepts("hello world"));
+
+        let filter = StringFilter::matches("^[A-Z]", true).unwrap();
+        let original = filter.original.as_ref().unwrap();
+        assert!(filter.normalized.accepts("hello"));
+        assert!(original.accepts("Hello"));
+        assert!(!original.accepts("hello"));
+    }
+
+    #[test]
+    fn invalid_regex() {
+        let Err(error) = StringFilter::matches("hel(lo", false) else { panic!() };
+        insta::assert_snapshot!(error, @"Invalid regular expression: unclosed group.");
+    }
+
+    #[test]
+    fn walk_jumps_to_next_matching_prefix() {
+        let automaton = StringFilter::starts_with("hel", false).unwrap().normalized;
+        let Walk::Jump(next) = walk(&automaton, b"abc") else { panic!() };
+        assert_eq!(next.as_deref(), Some(&b"h"[..]));
+        let Walk::Jump(next) = walk(&automaton, b"heaven") else { panic!() };
+        assert_eq!(next.as_deref(), Some(&b"hel"[..]));
+        let Walk::Jump(next) = walk(&automaton, b"hi") else { panic!() };
+        assert_eq!(next, None);
+        assert!(matches!(walk(&automaton, b"he"), Walk::NoMatch));
+        assert!(matches!(walk(&automaton, b"help"), Walk::Match));
+    }
+}
@@ -53,6 +53,12 @@ fn compare_scores(
                     order => return order,
                 }
             }
+            (Some(ScoreValue::Boost(left)), Some(ScoreValue::Boost(right))) => {
+                match left.partial_cmp(right).unwrap() {
+                    Ordering::Equal => continue,
+                    order => return order,
+                }
+            }
             (Some(ScoreValue::Score(x)), Some(_)) => {
                 return if x == 0. { Ordering::Less } else { Ordering::Greater }
             }
@@ -64,6 +70,10 @@ fn compare_scores(
             | (Some(ScoreValue::Sort(_)), Some(ScoreValue::GeoSort(_))) => {
                 unreachable!("Unexpected geo and sort comparison")
             }
+            (Some(ScoreValue::Boost(_)), Some(ScoreValue::Sort(_) | ScoreValue::GeoSort(_)))
+            | (Some(ScoreValue::Sort(_) | ScoreValue::GeoSort(_)), Some(ScoreValue::Boost(_))) => {
+                unreachable!("Unexpected boost and sort comparison")
+            }
         }
     }
 }
@@ -154,14 +164,22 @@ impl ScoreWithRatioResult {
 impl Search<'_> {
     #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
     pub fn execute_hybrid(&self, semantic_ratio: f32) -> Result<(SearchResult, Option<u32>)> {
+        if let Some(rerank) = &self.rerank {
+            let (result, semantic_hit_count) =
+                self.rerank_window(rerank).execute_hybrid(semantic_ratio)?;
+            return Ok((self.apply_rerank(rerank, result)?, semantic_hit_count));
+        }
+
         // TODO: find classier way to achieve that than to reset vector and query params
         // create separate keyword and semantic searches
         let mut search = Search {
             query: self.query.clone(),
             filter: self.filter.clone(),
+            excluded_documents: self.excluded_documents.clone(),
             offset: 0,
             limit: self.limit + self.offset,
             sort_criteria: self.sort_criteria.clone(),
+            boost: self.boost.clone(),
             distinct: self.distinct.clone(),
             searchable_attributes: self.searchable_attributes,
             geo_param: self.geo_param,
@@ -169,9 +187,11 @@ impl Search<'_> {
             scoring_strategy: ScoringStrategy::Detailed,
             words_limit: self.words_limit,
             exhaustive_number_hits: self.exhaustive_number_hits,
+            exact_vector_search: self.exact_vector_search,
             rtxn: self.rtxn,
             index: self.index,
             semantic: self.semantic.clone(),
+            rerank: None,
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
             locales: self.locales.clone(),
@@ -190,35 +210,84 @@ impl Search<'_> {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         };
         // no embedder, no semantic search
-        let Some(SemanticSearch { vector, embedder_name, embedder, quantized }) = semantic else {
+        let Some(SemanticSearch { vector, sparse_vector, embedder_name, embedder, quantization }) =
+            semantic
+        else {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         };
 
-        let vector_query = match vector {
-            Some(vector_query) => vector_query,
-            None => {
-                // attempt to embed the vector
-                let span = tracing::trace_span!(target: "search::hybrid", "embed_one");
-                let _entered = span.enter();
-
-                let deadline = std::time::Instant::now() + std::time::Duration::from_secs(3);
-
-                match embedder.embed_search(&query, Some(deadline)) {
-                    Ok(embedding) => embedding,
-                    Err(error) => {
-                        tracing::error!(error=%error, "Embedding failed");
-                        return Ok(return_keyword_results(
-                            self.limit,
-                            self.offset,
-                            keyword_results,
-                        ));
+        if embedder.is_sparse() {
+            let sparse_vector_query = match sparse_vector {
+                Some(sparse_vector_query) => sparse_vector_query,
+                None => {
+                    // attempt to embed the query
+                    let span = tracing::trace_span!(target: "search::hybrid", "embed_one");
+                    let _entered = span.enter();
+
+                    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(3);
+
+                    match embedder.embed_search_sparse(&query, Some(deadline)) {
+                        Ok(embedding) => embedding,
+                        Err(error) => {
+                            tracing::error!(error=%error, "Embedding failed");
+                            return Ok(return_keyword_results(
+                                self.limit,
+                                self.offset,
+                                keyword_results,
+                            ));
+                        }
                     }
                 }
-            }
-        };
+            };
+
+            search.semantic = Some(SemanticSearch {
+                vector: None,
+                sparse_vector: Some(sparse_vector_query),
+                embedder_name,
+                embedder,
+                quantization,
+            });
+        } else {
+            let vector_query = match vector {
+                Some(vector_query) => vector_query,
+                None => {
+                    // attempt to embed the vector
+                    let span = tracing::trace_span!(target: "search::hybrid", "embed_one");
+                    let _entered = span.enter();
+
+                    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(3);
+
+                    // the embeddings of a multi-vector embedder are concatenated in the vector
+                    let embedding = if embedder.is_multi_vector() {
+                        embedder
+                            .embed_search_multi(&query, Some(deadline))
+                            .map(|embeddings| embeddings.concat())
+                    } else {
+                        embedder.embed_search(&query, Some(deadline))
+                    };
 
-        search.semantic =
-            Some(SemanticSearch { vector: Some(vector_query), embedder_name, embedder, quantized });
+                    match embedding {
+                        Ok(embedding) => embedding,
+                        Err(error) => {
+                            tracing::error!(error=%error, "Embedding failed");
+                            return Ok(return_keyword_results(
+                                self.limit,
+                                self.offset,
+                                keyword_results,
+                            ));
+                        }
+                    }
+                }
+            };
+
+            search.semantic = Some(SemanticSearch {
+                vector: Some(vector_query),
+                sparse_vector: None,
+                embedder_name,
+                embedder,
+                quantization,
+            });
+        }
 
         // TODO: would be better to have two distinct functions at this point
         let vector_results = search.execute()?;
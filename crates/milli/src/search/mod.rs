@@ -6,15 +6,21 @@ use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet::{
+    FacetBuckets, FacetDistribution, FacetHierarchy, FacetRange, Filter, OrderBy,
+    DEFAULT_VALUES_PER_FACET,
+};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats};
+use self::new::{execute_vector_search, PartialSearchResult, VectorStoreStats, VectorTarget};
+pub use self::rerank::RerankSearch;
 use crate::filterable_attributes_rules::{filtered_matching_patterns, matching_features};
+use crate::prompt::Prompt;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
-use crate::vector::Embedder;
+use crate::vector::rerank::Reranker;
+use crate::vector::{Embedder, SparseEmbedding, VectorQuantization};
 use crate::{
-    execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
-    Result, SearchContext, TimeBudget, UserError,
+    execute_search, filtered_universe, AscDesc, BoostExpression, DefaultSearchLogger, DocumentId,
+    Error, Index, Result, SearchContext, TimeBudget, UserError,
 };
 
 // Building these factories is not free.
@@ -26,23 +32,28 @@ pub mod facet;
 mod fst_utils;
 pub mod hybrid;
 pub mod new;
+mod rerank;
 pub mod similar;
 
 #[derive(Debug, Clone)]
 pub struct SemanticSearch {
     vector: Option<Vec<f32>>,
+    sparse_vector: Option<SparseEmbedding>,
     embedder_name: String,
     embedder: Arc<Embedder>,
-    quantized: bool,
+    quantization: VectorQuantization,
 }
 
+#[derive(Clone)]
 pub struct Search<'a> {
     query: Option<String>,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
+    excluded_documents: Option<RoaringBitmap>,
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
+    boost: Option<BoostExpression>,
     distinct: Option<String>,
     searchable_attributes: Option<&'a [String]>,
     geo_param: new::GeoSortParameter,
@@ -50,9 +61,11 @@ pub struct Search<'a> {
     scoring_strategy: ScoringStrategy,
     words_limit: usize,
     exhaustive_number_hits: bool,
+    exact_vector_search: bool,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
     semantic: Option<SemanticSearch>,
+    rerank: Option<RerankSearch>,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<Vec<Language>>,
@@ -63,19 +76,23 @@ impl<'a> Search<'a> {
         Search {
             query: None,
             filter: None,
+            excluded_documents: None,
             offset: 0,
             limit: 20,
             sort_criteria: None,
+            boost: None,
             distinct: None,
             searchable_attributes: None,
             geo_param: new::GeoSortParameter::default(),
             terms_matching_strategy: TermsMatchingStrategy::default(),
             scoring_strategy: Default::default(),
             exhaustive_number_hits: false,
+            exact_vector_search: false,
             words_limit: 10,
             rtxn,
             index,
             semantic: None,
+            rerank: None,
             locales: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
@@ -91,10 +108,50 @@ impl<'a> Search<'a> {
         &mut self,
         embedder_name: String,
         embedder: Arc<Embedder>,
-        quantized: bool,
+        quantization: VectorQuantization,
         vector: Option<Vec<f32>>,
     ) -> &mut Search<'a> {
-        self.semantic = Some(SemanticSearch { embedder_name, embedder, quantized, vector });
+        self.semantic = Some(SemanticSearch {
+            embedder_name,
+            embedder,
+            quantization,
+            vector,
+            sparse_vector: None,
+        });
+        self
+    }
+
+    /// Search with an embedder generating sparse embeddings.
+    ///
+    /// When `sparse_vector` is `None`, hybrid search embeds the query with the embedder.
+    pub fn sparse_semantic(
+        &mut self,
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+        sparse_vector: Option<SparseEmbedding>,
+    ) -> &mut Search<'a> {
+        self.semantic = Some(SemanticSearch {
+            embedder_name,
+            embedder,
+            quantization: VectorQuantization::default(),
+            vector: None,
+            sparse_vector,
+        });
+        self
+    }
+
+    /// Reorders the first `top_n` documents of the search by their relevance for the query,
+    /// as scored by the reranker from the documents rendered with the template.
+    ///
+    /// Only these documents are returned, so that all the hits have the score of the reranker.
+    pub fn rerank(
+        &mut self,
+        reranker_name: String,
+        reranker: Arc<Reranker>,
+        document_template: Arc<Prompt>,
+        top_n: usize,
+    ) -> &mut Search<'a> {
+        self.rerank = Some(RerankSearch { reranker_name, reranker, document_template, top_n });
         self
     }
 
@@ -113,6 +170,12 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Sorts the documents by the value of the expression where the `sort` ranking rule is.
+    pub fn boost(&mut self, boost: BoostExpression) -> &mut Search<'a> {
+        self.boost = Some(boost);
+        self
+    }
+
     pub fn distinct(&mut self, distinct: String) -> &mut Search<'a> {
         self.distinct = Some(distinct);
         self
@@ -143,6 +206,12 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Removes the given documents from the documents that can be returned by the search.
+    pub fn excluded_documents(&mut self, documents: RoaringBitmap) -> &mut Search<'a> {
+        self.excluded_documents = Some(documents);
+        self
+    }
+
     #[cfg(test)]
     pub fn geo_sort_strategy(&mut self, strategy: new::GeoSortStrategy) -> &mut Search<'a> {
         self.geo_param.strategy = strategy;
@@ -162,6 +231,13 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Forces the semantic search to compute the distance to the embeddings of all the candidates
+    /// instead of searching the approximate nearest neighbors, this is slow on large sets of candidates.
+    pub fn exact_vector_search(&mut self, exact_vector_search: bool) -> &mut Search<'a> {
+        self.exact_vector_search = exact_vector_search;
+        self
+    }
+
     pub fn time_budget(&mut self, time_budget: TimeBudget) -> &mut Search<'a> {
         self.time_budget = time_budget;
         self
@@ -177,16 +253,29 @@ impl<'a> Search<'a> {
         self
     }
 
+    fn universe(&self, ctx: &SearchContext<'_>) -> Result<RoaringBitmap> {
+        let mut universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        if let Some(excluded_documents) = &self.excluded_documents {
+            universe -= excluded_documents;
+        }
+        Ok(universe)
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
-            filtered_universe(ctx.index, ctx.txn, &self.filter)
+            self.universe(&ctx)
         } else {
             Ok(self.execute()?.candidates)
         }
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
+        if let Some(rerank) = &self.rerank {
+            let result = self.rerank_window(rerank).execute()?;
+            return self.apply_rerank(rerank, result);
+        }
+
         let mut ctx = SearchContext::new(self.index, self.rtxn)?;
 
         if let Some(searchable_attributes) = self.searchable_attributes {
@@ -221,7 +310,7 @@ impl<'a> Search<'a> {
             }
         }
 
-        let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        let universe = self.universe(&ctx)?;
         let PartialSearchResult {
             located_query_terms,
             candidates,
@@ -229,21 +318,40 @@ impl<'a> Search<'a> {
             document_scores,
             degraded,
             used_negative_operator,
-        } = match self.semantic.as_ref() {
-            Some(SemanticSearch { vector: Some(vector), embedder_name, embedder, quantized }) => {
+        } = match self.semantic.as_ref().and_then(|semantic| {
+            let target = match semantic {
+                // the vector of a multi-vector embedder is the concatenation of the query embeddings
+                SemanticSearch { vector: Some(vector), embedder, .. }
+                    if embedder.is_multi_vector() =>
+                {
+                    VectorTarget::MultiVector(
+                        vector.chunks(embedder.dimensions()).map(<[f32]>::to_vec).collect(),
+                    )
+                }
+                SemanticSearch { vector: Some(vector), .. } => VectorTarget::Dense(vector.clone()),
+                SemanticSearch { sparse_vector: Some(sparse_vector), .. } => {
+                    VectorTarget::Sparse(sparse_vector.clone())
+                }
+                _ => return None,
+            };
+            Some((semantic, target))
+        }) {
+            Some((SemanticSearch { embedder_name, embedder, quantization, .. }, target)) => {
                 execute_vector_search(
                     &mut ctx,
-                    vector,
+                    &target,
                     self.scoring_strategy,
                     universe,
                     &self.sort_criteria,
+                    self.boost.as_ref(),
                     &self.distinct,
                     self.geo_param,
                     self.offset,
                     self.limit,
                     embedder_name,
                     embedder,
-                    *quantized,
+                    *quantization,
+                    self.exact_vector_search,
                     self.time_budget.clone(),
                     self.ranking_score_threshold,
                 )?
@@ -256,6 +364,7 @@ impl<'a> Search<'a> {
                 self.exhaustive_number_hits,
                 universe,
                 &self.sort_criteria,
+                self.boost.as_ref(),
                 &self.distinct,
                 self.geo_param,
                 self.offset,
@@ -297,9 +406,11 @@ impl fmt::Debug for Search<'_> {
         let Search {
             query,
             filter,
+            excluded_documents,
             offset,
             limit,
             sort_criteria,
+            boost,
             distinct,
             searchable_attributes,
             geo_param: _,
@@ -307,9 +418,11 @@ impl fmt::Debug for Search<'_> {
             scoring_strategy,
             words_limit,
             exhaustive_number_hits,
+            exact_vector_search,
             rtxn: _,
             index: _,
             semantic,
+            rerank,
             time_budget,
             ranking_score_threshold,
             locales,
@@ -318,19 +431,23 @@ impl fmt::Debug for Search<'_> {
             .field("query", query)
             .field("vector", &"[...]")
             .field("filter", filter)
+            .field("excluded_documents", excluded_documents)
             .field("offset", offset)
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
+            .field("boost", boost)
             .field("distinct", distinct)
             .field("searchable_attributes", searchable_attributes)
             .field("terms_matching_strategy", terms_matching_strategy)
             .field("scoring_strategy", scoring_strategy)
             .field("exhaustive_number_hits", exhaustive_number_hits)
+            .field("exact_vector_search", exact_vector_search)
             .field("words_limit", words_limit)
             .field(
                 "semantic.embedder_name",
                 &semantic.as_ref().map(|semantic| &semantic.embedder_name),
             )
+            .field("rerank.reranker_name", &rerank.as_ref().map(|rerank| &rerank.reranker_name))
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
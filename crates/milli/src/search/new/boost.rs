@@ -0,0 +1,151 @@
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use heed::types::Bytes;
+use heed::RoTxn;
+use roaring::RoaringBitmap;
+
+use super::distinct::facet_values_prefix_key;
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::FieldDocIdFacetF64Codec;
+use crate::score_details::{self, ScoreDetails};
+use crate::{BoostExpression, FieldId, Index, Result};
+
+/// Sorts the documents by the value of a boost expression, from the greatest to the smallest.
+///
+/// The expression is evaluated for every document of the universe given to
+/// [`start_iteration`](RankingRule::start_iteration), the documents for which it cannot be
+/// computed are returned in the last bucket.
+pub struct Boost<Query> {
+    expression: BoostExpression,
+    field_ids: Vec<(String, Option<FieldId>)>,
+    now: f64,
+    original_query: Option<Query>,
+    buckets: VecDeque<(f64, RoaringBitmap)>,
+}
+
+impl<Query> Boost<Query> {
+    pub fn new(index: &Index, rtxn: &RoTxn<'_>, expression: BoostExpression) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_ids = expression
+            .fields()
+            .into_iter()
+            .map(|field| (field.to_string(), fields_ids_map.id(field)))
+            .collect();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |duration| duration.as_secs_f64());
+
+        Ok(Self { expression, field_ids, now, original_query: None, buckets: VecDeque::new() })
+    }
+
+    fn score(&self, value: Option<f64>) -> ScoreDetails {
+        ScoreDetails::Boost(score_details::Boost { expression: self.expression.to_string(), value })
+    }
+}
+
+/// Returns the greatest number value of the given field of the given document.
+fn greatest_number_value(
+    index: &Index,
+    txn: &RoTxn<'_>,
+    field_id: FieldId,
+    docid: u32,
+) -> Result<Option<f64>> {
+    let key = facet_values_prefix_key(field_id, docid);
+    let iter = index
+        .field_id_docid_facet_f64s
+        .remap_key_type::<Bytes>()
+        .prefix_iter(txn, &key)?
+        .remap_key_type::<FieldDocIdFacetF64Codec>();
+
+    let mut greatest = None;
+    for result in iter {
+        let ((_, _, value), ()) = result?;
+        greatest = Some(greatest.map_or(value, |greatest: f64| greatest.max(value)));
+    }
+    Ok(greatest)
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Boost<Query> {
+    fn id(&self) -> String {
+        format!("boost({})", self.expression)
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+        query: &Query,
+    ) -> Result<()> {
+        let mut values = Vec::with_capacity(universe.len() as usize);
+        for docid in universe {
+            let mut error = None;
+            let value = self.expression.evaluate(self.now, &mut |field| {
+                let (_, field_id) = self.field_ids.iter().find(|(name, _)| name == field)?;
+                match greatest_number_value(ctx.index, ctx.txn, (*field_id)?, docid) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        error.get_or_insert(e);
+                        None
+                    }
+                }
+            });
+            if let Some(error) = error {
+                return Err(error);
+            }
+            if let Some(value) = value {
+                values.push((value, docid));
+            }
+        }
+
+        values.sort_unstable_by(|(left, _), (right, _)| right.total_cmp(left));
+        self.buckets.clear();
+        for (value, docid) in values {
+            match self.buckets.back_mut() {
+                Some((last, docids)) if *last == value => {
+                    docids.insert(docid);
+                }
+                _ => self.buckets.push_back((value, RoaringBitmap::from_iter([docid]))),
+            }
+        }
+
+        self.original_query = Some(query.clone());
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((value, docids)) = self.buckets.pop_front() {
+            let candidates = docids & universe;
+            if !candidates.is_empty() {
+                return Ok(Some(RankingRuleOutput {
+                    query,
+                    candidates,
+                    score: self.score(Some(value)),
+                }));
+            }
+        }
+
+        // the documents for which the expression could not be computed
+        Ok(Some(RankingRuleOutput { query, candidates: universe.clone(), score: self.score(None) }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::boost")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+}
@@ -1,16 +1,18 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
 use heed::types::{Bytes, Unit};
 use heed::{RoPrefix, RoTxn};
 use roaring::RoaringBitmap;
 use rstar::RTree;
-use std::collections::VecDeque;
 
 use super::facet_string_values;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use crate::heed_codec::facet::{FieldDocIdFacetCodec, OrderedF64Codec};
 use crate::score_details::{self, ScoreDetails};
 use crate::{
-    distance_between_two_points, lat_lng_to_xyz, GeoPoint, Index, Result, SearchContext,
-    SearchLogger,
+    distance_between_two_points, lat_lng_to_xyz, DocumentId, GeoPoint, Index, Result,
+    SearchContext, SearchLogger,
 };
 
 const FID_SIZE: usize = 2;
@@ -94,6 +96,8 @@ pub struct GeoSort<Q: RankingRuleQueryTrait> {
     point: [f64; 2],
     field_ids: Option<[u16; 2]>,
     rtree: Option<RTree<GeoPoint>>,
+    // The points of the documents with more than one geo point, read from the rtree
+    multiple_points: HashMap<DocumentId, Vec<[f64; 2]>>,
 
     cached_sorted_docids: VecDeque<(u32, [f64; 2])>,
     geo_candidates: RoaringBitmap,
@@ -120,12 +124,49 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             geo_candidates: geo_faceted_docids,
             field_ids: None,
             rtree: None,
+            multiple_points: HashMap::new(),
             cached_sorted_docids: VecDeque::new(),
             max_bucket_size,
             distance_error_margin,
         })
     }
 
+    /// Lazily load the rtree, cache it in `self.rtree` and gather the points of the
+    /// documents with multiple geo points.
+    fn load_rtree(&mut self, ctx: &mut SearchContext<'_>) -> Result<()> {
+        if self.rtree.is_none() {
+            let rtree = ctx.index.geo_rtree(ctx.txn)?.expect("geo candidates but no rtree");
+            if rtree.size() as u64 > self.geo_candidates.len() {
+                for point in rtree.iter() {
+                    let (docid, point) = point.data;
+                    self.multiple_points.entry(docid).or_default().push(point);
+                }
+                self.multiple_points.retain(|_, points| points.len() > 1);
+            }
+            self.rtree = Some(rtree);
+        }
+        Ok(())
+    }
+
+    /// Returns all the geo points of a single document.
+    ///
+    /// The points are rebuilt from the lat and lng facet values when the document has
+    /// a single latitude or a single longitude, otherwise we can't tell which latitude
+    /// goes with which longitude and the points are read from the rtree.
+    fn geo_points(&mut self, ctx: &mut SearchContext<'_>, docid: u32) -> Result<Vec<[f64; 2]>> {
+        let [field_lat, field_lng] = self.field_ids.unwrap();
+        let lats = geo_values(docid, field_lat, ctx.index, ctx.txn)?;
+        let lngs = geo_values(docid, field_lng, ctx.index, ctx.txn)?;
+        match (lats.as_slice(), lngs.as_slice()) {
+            ([lat], lngs) => Ok(lngs.iter().map(|lng| [*lat, *lng]).collect()),
+            (lats, [lng]) => Ok(lats.iter().map(|lat| [*lat, *lng]).collect()),
+            _ => {
+                self.load_rtree(ctx)?;
+                Ok(self.multiple_points.get(&docid).cloned().unwrap_or_default())
+            }
+        }
+    }
+
     /// Refill the internal buffer of cached docids based on the strategy.
     /// Drop the rtree if we don't need it anymore.
     fn fill_buffer(
@@ -136,17 +177,10 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         debug_assert!(self.field_ids.is_some(), "fill_buffer can't be called without the lat&lng");
         debug_assert!(self.cached_sorted_docids.is_empty());
 
-        // lazily initialize the rtree if needed by the strategy, and cache it in `self.rtree`
+        // lazily initialize the rtree if needed by the strategy
         let rtree = if self.strategy.use_rtree(geo_candidates.len() as usize) {
-            if let Some(rtree) = self.rtree.as_ref() {
-                // get rtree from cache
-                Some(rtree)
-            } else {
-                let rtree = ctx.index.geo_rtree(ctx.txn)?.expect("geo candidates but no rtree");
-                // insert rtree in cache and returns it.
-                // Can't use `get_or_insert_with` because getting the rtree from the DB is a fallible operation.
-                Some(&*self.rtree.insert(rtree))
-            }
+            self.load_rtree(ctx)?;
+            self.rtree.as_ref()
         } else {
             None
         };
@@ -154,9 +188,12 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         let cache_size = self.strategy.cache_size();
         if let Some(rtree) = rtree {
             if self.ascending {
+                // A document with multiple geo points is sorted by its nearest point,
+                // the first one we encounter.
+                let mut seen = RoaringBitmap::new();
                 let point = lat_lng_to_xyz(&self.point);
                 for point in rtree.nearest_neighbor_iter(&point) {
-                    if geo_candidates.contains(point.data.0) {
+                    if geo_candidates.contains(point.data.0) && seen.insert(point.data.0) {
                         self.cached_sorted_docids.push_back(point.data);
                         if self.cached_sorted_docids.len() >= cache_size {
                             break;
@@ -165,10 +202,25 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
                 }
             } else {
                 // in the case of the desc geo sort we look for the closest point to the opposite of the queried point
-                // and we insert the points in reverse order they get reversed when emptying the cache later on
+                // and we insert the points in reverse order they get reversed when emptying the cache later on.
+                // A document with multiple geo points is sorted by its nearest point to the queried point, the
+                // farthest one from the opposite, so we only insert it once we encountered all of its points.
+                let mut remaining_points = HashMap::new();
                 let point = lat_lng_to_xyz(&opposite_of(self.point));
                 for point in rtree.nearest_neighbor_iter(&point) {
-                    if geo_candidates.contains(point.data.0) {
+                    let docid = point.data.0;
+                    if geo_candidates.contains(docid) {
+                        if let Some(points) = self.multiple_points.get(&docid) {
+                            let remaining = match remaining_points.entry(docid) {
+                                Entry::Occupied(entry) => entry.into_mut(),
+                                Entry::Vacant(entry) => entry.insert(points.len()),
+                            };
+                            *remaining = remaining.saturating_sub(1);
+                            if *remaining > 0 {
+                                continue;
+                            }
+                        }
+
                         self.cached_sorted_docids.push_front(point.data);
                         if self.cached_sorted_docids.len() >= cache_size {
                             break;
@@ -178,51 +230,49 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             }
         } else {
             // the iterative version
-            let [lat, lng] = self.field_ids.unwrap();
-
-            let mut documents = geo_candidates
-                .iter()
-                .map(|id| -> Result<_> { Ok((id, geo_value(id, lat, lng, ctx.index, ctx.txn)?)) })
-                .collect::<Result<Vec<(u32, [f64; 2])>>>()?;
+            let mut documents = Vec::with_capacity(geo_candidates.len() as usize);
+            for id in geo_candidates {
+                // A document with multiple geo points is sorted by its nearest point
+                let nearest = self
+                    .geo_points(ctx, id)?
+                    .into_iter()
+                    .map(|point| (distance_between_two_points(&self.point, &point), point))
+                    .min_by(|(left, _), (right, _)| left.total_cmp(right))
+                    .expect("A geo faceted document doesn't contain any geo point");
+                documents.push((id, nearest));
+            }
             // computing the distance between two points is expensive thus we cache the result
-            documents
-                .sort_by_cached_key(|(_, p)| distance_between_two_points(&self.point, p) as usize);
-            self.cached_sorted_docids.extend(documents);
+            documents.sort_by_key(|(_, (distance, _))| *distance as usize);
+            self.cached_sorted_docids.extend(documents.into_iter().map(|(id, (_, p))| (id, p)));
         };
 
         Ok(())
     }
 }
 
-/// Extracts the lat and long values from a single document.
+/// Extracts the lat or long values of a single document.
 ///
-/// If it is not able to find it in the facet number index it will extract it
-/// from the facet string index and parse it as f64 (as the geo extraction behaves).
-fn geo_value(
-    docid: u32,
-    field_lat: u16,
-    field_lng: u16,
-    index: &Index,
-    rtxn: &RoTxn<'_>,
-) -> Result<[f64; 2]> {
-    let extract_geo = |geo_field: u16| -> Result<f64> {
-        match facet_number_values(docid, geo_field, index, rtxn)?.next() {
-            Some(Ok(((_, _, geo), ()))) => Ok(geo),
-            Some(Err(e)) => Err(e.into()),
-            None => match facet_string_values(docid, geo_field, index, rtxn)?.next() {
-                Some(Ok((_, geo))) => {
-                    Ok(geo.parse::<f64>().expect("cannot parse geo field as f64"))
-                }
-                Some(Err(e)) => Err(e.into()),
-                None => panic!("A geo faceted document doesn't contain any lat or lng"),
-            },
+/// If it is not able to find them in the facet number index it will extract them
+/// from the facet string index and parse them as f64 (as the geo extraction behaves).
+fn geo_values(docid: u32, geo_field: u16, index: &Index, rtxn: &RoTxn<'_>) -> Result<Vec<f64>> {
+    let mut values = Vec::new();
+    for result in facet_number_values(docid, geo_field, index, rtxn)? {
+        let ((_, _, geo), ()) = result?;
+        values.push(geo);
+    }
+
+    if values.is_empty() {
+        for result in facet_string_values(docid, geo_field, index, rtxn)? {
+            let (_, geo) = result?;
+            values.push(geo.parse::<f64>().expect("cannot parse geo field as f64"));
         }
-    };
+    }
 
-    let lat = extract_geo(field_lat)?;
-    let lng = extract_geo(field_lng)?;
+    if values.is_empty() {
+        panic!("A geo faceted document doesn't contain any lat or lng");
+    }
 
-    Ok([lat, lng])
+    Ok(values)
 }
 
 impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
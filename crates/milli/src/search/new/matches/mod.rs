@@ -512,6 +512,7 @@ mod tests {
                 false,
                 universe,
                 &None,
+                None,
                 &None,
                 crate::search::new::GeoSortParameter::default(),
                 0,
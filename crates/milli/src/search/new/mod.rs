@@ -1,3 +1,4 @@
+mod boost;
 mod bucket_sort;
 mod db_cache;
 mod distinct;
@@ -25,6 +26,7 @@ use std::collections::HashSet;
 use std::ops::AddAssign;
 use std::time::Duration;
 
+use boost::Boost;
 use bucket_sort::{bucket_sort, BucketSortOutput};
 use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
@@ -52,15 +54,16 @@ pub use self::geo_sort::Strategy as GeoSortStrategy;
 use self::graph_based_ranking_rule::Words;
 use self::interner::Interned;
 use self::vector_sort::VectorSort;
+pub use self::vector_sort::VectorTarget;
 use crate::constants::RESERVED_GEO_FIELD_NAME;
 use crate::index::PrefixSearch;
 use crate::localized_attributes_rules::LocalizedFieldIds;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
 use crate::search::new::distinct::apply_distinct_rule;
-use crate::vector::Embedder;
+use crate::vector::{Embedder, VectorQuantization};
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
-    UserError, Weight,
+    AscDesc, BoostExpression, DocumentId, FieldId, Filter, Index, Member, Result,
+    TermsMatchingStrategy, TimeBudget, UserError, Weight,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -298,6 +301,7 @@ fn resolve_negative_phrases(
 fn get_ranking_rules_for_placeholder_search<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boost: Option<&BoostExpression>,
     geo_param: geo_sort::Parameter,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     let mut sort = false;
@@ -319,6 +323,7 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 }
                 resolve_sort_criteria(
                     sort_criteria,
+                    boost,
                     ctx,
                     &mut ranking_rules,
                     &mut sorted_fields,
@@ -350,12 +355,14 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
 fn get_ranking_rules_for_vector<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boost: Option<&BoostExpression>,
     geo_param: geo_sort::Parameter,
     limit_plus_offset: usize,
-    target: &[f32],
+    target: &VectorTarget,
     embedder_name: &str,
     embedder: &Embedder,
-    quantized: bool,
+    quantization: VectorQuantization,
+    exact: bool,
 ) -> Result<Vec<BoxRankingRule<'ctx, PlaceholderQuery>>> {
     // query graph search
 
@@ -378,12 +385,13 @@ fn get_ranking_rules_for_vector<'ctx>(
                     let vector_candidates = ctx.index.documents_ids(ctx.txn)?;
                     let vector_sort = VectorSort::new(
                         ctx,
-                        target.to_vec(),
+                        target.clone(),
                         vector_candidates,
                         limit_plus_offset,
                         embedder_name,
                         embedder,
-                        quantized,
+                        quantization,
+                        exact,
                     )?;
                     ranking_rules.push(Box::new(vector_sort));
                     vector = true;
@@ -395,6 +403,7 @@ fn get_ranking_rules_for_vector<'ctx>(
                 }
                 resolve_sort_criteria(
                     sort_criteria,
+                    boost,
                     ctx,
                     &mut ranking_rules,
                     &mut sorted_fields,
@@ -427,6 +436,7 @@ fn get_ranking_rules_for_vector<'ctx>(
 fn get_ranking_rules_for_query_graph_search<'ctx>(
     ctx: &SearchContext<'ctx>,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boost: Option<&BoostExpression>,
     geo_param: geo_sort::Parameter,
     terms_matching_strategy: TermsMatchingStrategy,
 ) -> Result<Vec<BoxRankingRule<'ctx, QueryGraph>>> {
@@ -497,6 +507,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 }
                 resolve_sort_criteria(
                     sort_criteria,
+                    boost,
                     ctx,
                     &mut ranking_rules,
                     &mut sorted_fields,
@@ -534,12 +545,18 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
 
 fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
     sort_criteria: &Option<Vec<AscDesc>>,
+    boost: Option<&BoostExpression>,
     ctx: &SearchContext<'ctx>,
     ranking_rules: &mut Vec<BoxRankingRule<'ctx, Query>>,
     sorted_fields: &mut HashSet<String>,
     geo_sorted: &mut bool,
     geo_param: geo_sort::Parameter,
 ) -> Result<()> {
+    // The boost expression is applied before the sort criteria of the query
+    if let Some(boost) = boost {
+        ranking_rules.push(Box::new(Boost::new(ctx.index, ctx.txn, boost.clone())?));
+    }
+
     let sort_criteria = sort_criteria.clone().unwrap_or_default();
     ranking_rules.reserve(sort_criteria.len());
     for criterion in sort_criteria {
@@ -603,33 +620,38 @@ pub fn filtered_universe(
 #[allow(clippy::too_many_arguments)]
 pub fn execute_vector_search(
     ctx: &mut SearchContext<'_>,
-    vector: &[f32],
+    vector: &VectorTarget,
     scoring_strategy: ScoringStrategy,
     universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boost: Option<&BoostExpression>,
     distinct: &Option<String>,
     geo_param: geo_sort::Parameter,
     from: usize,
     length: usize,
     embedder_name: &str,
     embedder: &Embedder,
-    quantized: bool,
+    quantization: VectorQuantization,
+    exact: bool,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
+    check_boost(ctx, boost)?;
 
     // FIXME: input universe = universe & documents_with_vectors
     // for now if we're computing embeddings for ALL documents, we can assume that this is just universe
     let ranking_rules = get_ranking_rules_for_vector(
         ctx,
         sort_criteria,
+        boost,
         geo_param,
         from + length,
         vector,
         embedder_name,
         embedder,
-        quantized,
+        quantization,
+        exact,
     )?;
 
     let mut placeholder_search_logger = logger::DefaultSearchLogger;
@@ -670,6 +692,7 @@ pub fn execute_search(
     exhaustive_number_hits: bool,
     mut universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
+    boost: Option<&BoostExpression>,
     distinct: &Option<String>,
     geo_param: geo_sort::Parameter,
     from: usize,
@@ -682,6 +705,7 @@ pub fn execute_search(
     locales: Option<&Vec<Language>>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
+    check_boost(ctx, boost)?;
 
     let mut used_negative_operator = false;
     let mut located_query_terms = None;
@@ -785,6 +809,7 @@ pub fn execute_search(
         let ranking_rules = get_ranking_rules_for_query_graph_search(
             ctx,
             sort_criteria,
+            boost,
             geo_param,
             terms_matching_strategy,
         )?;
@@ -807,7 +832,7 @@ pub fn execute_search(
         )?
     } else {
         let ranking_rules =
-            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, geo_param)?;
+            get_ranking_rules_for_placeholder_search(ctx, sort_criteria, boost, geo_param)?;
         bucket_sort(
             ctx,
             ranking_rules,
@@ -906,6 +931,35 @@ fn check_sort_criteria(
     Ok(())
 }
 
+fn check_boost(ctx: &SearchContext<'_>, boost: Option<&BoostExpression>) -> Result<()> {
+    let Some(boost) = boost else {
+        return Ok(());
+    };
+
+    // The boost ranking rule is applied where the sort ranking rule is.
+    if !ctx.index.criteria(ctx.txn)?.contains(&crate::Criterion::Sort) {
+        return Err(UserError::BoostRankingRuleMissing.into());
+    }
+
+    // We check that the fields of the expression are declared in the sortable fields.
+    let sortable_fields = ctx.index.sortable_fields(ctx.txn)?;
+    for field in boost.fields() {
+        if !crate::is_faceted(field, &sortable_fields) {
+            let (valid_fields, hidden_fields) =
+                ctx.index.remove_hidden_fields(ctx.txn, sortable_fields)?;
+
+            return Err(UserError::InvalidBoostAttribute {
+                field: field.to_string(),
+                valid_fields,
+                hidden_fields,
+            }
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
 pub struct PartialSearchResult {
     pub located_query_terms: Option<Vec<LocatedQueryTerm>>,
     pub candidates: RoaringBitmap,
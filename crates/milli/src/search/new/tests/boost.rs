@@ -0,0 +1,97 @@
+/*!
+This module tests the `boost` parameter of the search:
+
+1. the documents are sorted by the value of the expression, from the greatest to the smallest
+2. the documents for which the expression cannot be computed appear at the end
+3. the boost is applied where the `sort` ranking rule is, before the sort criteria of the query
+4. an error is returned if the `sort` ranking rule is missing or if a field of the expression is not sortable
+*/
+
+use big_s::S;
+use maplit::hashset;
+use meili_snap::insta;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::ScoreDetails;
+use crate::{AscDesc, BoostExpression, Criterion, Member, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(hashset! { S("popularity"), S("rank") });
+            s.set_criteria(vec![Criterion::Sort]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "popularity": 1, "rank": 3 },
+            { "id": 1, "popularity": 10, "rank": 0 },
+            { "id": 2, "popularity": 0, "rank": 1 },
+            { "id": 3, "rank": 10 },
+            { "id": 4, "popularity": [2, 4], "rank": 0 },
+            { "id": 5, "popularity": 2, "rank": 1 },
+        ]))
+        .unwrap();
+    index
+}
+
+fn boost(expression: &str) -> BoostExpression {
+    expression.parse().unwrap()
+}
+
+#[test]
+fn test_boost() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+    s.boost(boost("popularity * 2 + rank"));
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4, 0, 5, 2, 3]");
+
+    let values: Vec<_> = document_scores
+        .iter()
+        .map(|scores| match scores.as_slice() {
+            [ScoreDetails::Boost(boost)] => boost.value,
+            scores => panic!("unexpected scores {scores:?}"),
+        })
+        .collect();
+    insta::assert_snapshot!(format!("{values:?}"), @"[Some(20.0), Some(8.0), Some(5.0), Some(5.0), Some(1.0), None]");
+
+    // the boost is applied before the sort criteria
+    let mut s = Search::new(&txn, &index);
+    s.boost(boost("min(popularity, 2)"));
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("rank")))]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    insta::assert_snapshot!(format!("{documents_ids:?}"), @"[1, 4, 5, 0, 2, 3]");
+}
+
+#[test]
+fn test_boost_errors() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.boost(boost("log1p(views)"));
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"Attribute `views` is not sortable and thus, cannot be used in a boost expression. Available sortable attributes are: `popularity, rank`.");
+
+    drop(txn);
+    index
+        .update_settings(|s| {
+            s.set_criteria(vec![Criterion::Words]);
+        })
+        .unwrap();
+    let txn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    s.boost(boost("popularity"));
+    let error = s.execute().unwrap_err();
+    insta::assert_snapshot!(error, @"You must specify where `sort` is listed in the rankingRules setting to use the boost parameter at search time.");
+}
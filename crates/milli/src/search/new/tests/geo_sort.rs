@@ -415,3 +415,58 @@ fn geo_sort_without_any_geo_faceted_documents() {
     insta::assert_snapshot!(format!("{ids:?}"), @"[0, 2, 3]");
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
+
+#[test]
+fn geo_sort_with_multiple_points() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, RESERVED_GEO_FIELD_NAME: [{ "lat": 10, "lng": 10 }, { "lat": 0, "lng": 1 }] },
+            { "id": 1, RESERVED_GEO_FIELD_NAME: { "lat": 0, "lng": 2 } },
+            { "id": 2, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 3 }, { "lat": -20, "lng": -20 }] },
+            { "id": 3, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 0.5 }] },
+            { "id": 4 },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    // the documents are sorted by their nearest point to the target point
+    let nearest_points = |scores: &[Vec<ScoreDetails>]| {
+        scores
+            .iter()
+            .map(|scores| match scores.as_slice() {
+                [ScoreDetails::GeoSort(geo_sort)] => geo_sort.value,
+                _ => panic!("unexpected scores {scores:?}"),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 0, 1, 2, 4]");
+    insta::assert_snapshot!(format!("{:?}", nearest_points(&scores)), @"[Some([0.0, 0.5]), Some([0.0, 1.0]), Some([0.0, 2.0]), Some([0.0, 3.0]), None]");
+
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.]))]);
+    let (ids, scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[2, 1, 0, 3, 4]");
+    insta::assert_snapshot!(format!("{:?}", nearest_points(&scores)), @"[Some([0.0, 3.0]), Some([0.0, 2.0]), Some([0.0, 1.0]), Some([0.0, 0.5]), None]");
+    drop(rtxn);
+
+    // updating a document replaces all of its points
+    index
+        .add_documents(documents!([
+            { "id": 2, RESERVED_GEO_FIELD_NAME: [{ "lat": 0, "lng": 0.1 }, { "lat": 30, "lng": 30 }] },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    let mut s = Search::new(&rtxn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, _scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[2, 3, 0, 1, 4]");
+}
@@ -1,5 +1,6 @@
 pub mod attribute_fid;
 pub mod attribute_position;
+pub mod boost;
 pub mod cutoff;
 pub mod distinct;
 pub mod exactness;
@@ -6,29 +6,44 @@ use roaring::RoaringBitmap;
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use super::VectorStoreStats;
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::{ArroyWrapper, DistributionShift, Embedder};
+use crate::vector::{
+    sparse, ArroyWrapper, DistributionShift, Embedder, Embedding, SparseEmbedding,
+    SparseVectorStore, VectorQuantization,
+};
 use crate::{DocumentId, Result, SearchContext, SearchLogger};
 
+/// The embedding that documents are sorted against.
+#[derive(Debug, Clone)]
+pub enum VectorTarget {
+    Dense(Vec<f32>),
+    Sparse(SparseEmbedding),
+    /// The embeddings of a query for a multi-vector embedder, compared to documents with MaxSim.
+    MultiVector(Vec<Embedding>),
+}
+
 pub struct VectorSort<Q: RankingRuleQueryTrait> {
     query: Option<Q>,
-    target: Vec<f32>,
+    target: VectorTarget,
     vector_candidates: RoaringBitmap,
-    cached_sorted_docids: std::vec::IntoIter<(DocumentId, f32)>,
+    cached_sorted_docids: std::vec::IntoIter<(DocumentId, u8, f32)>,
     limit: usize,
     distribution_shift: Option<DistributionShift>,
     embedder_index: u8,
-    quantized: bool,
+    quantization: VectorQuantization,
+    /// Whether the distances are computed for all the candidates rather than searched in the arroy trees.
+    exact: bool,
 }
 
 impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
     pub fn new(
         ctx: &SearchContext<'_>,
-        target: Vec<f32>,
+        target: VectorTarget,
         vector_candidates: RoaringBitmap,
         limit: usize,
         embedder_name: &str,
         embedder: &Embedder,
-        quantized: bool,
+        quantization: VectorQuantization,
+        exact: bool,
     ) -> Result<Self> {
         let embedder_index = ctx
             .index
@@ -44,7 +59,8 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
             limit,
             distribution_shift: embedder.distribution(),
             embedder_index,
-            quantized,
+            quantization,
+            exact,
         })
     }
 
@@ -53,11 +69,35 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         ctx: &mut SearchContext<'_>,
         vector_candidates: &RoaringBitmap,
     ) -> Result<()> {
-        let target = &self.target;
-
         let before = Instant::now();
-        let reader = ArroyWrapper::new(ctx.index.vector_arroy, self.embedder_index, self.quantized);
-        let results = reader.nns_by_vector(ctx.txn, target, self.limit, Some(vector_candidates))?;
+        let results = match &self.target {
+            VectorTarget::Dense(target) => {
+                let reader = ArroyWrapper::new(ctx.index, self.embedder_index, self.quantization);
+                if self.exact {
+                    reader.exact_nns_by_vector(ctx.txn, target, self.limit, vector_candidates)?
+                } else {
+                    reader.nns_by_vector(ctx.txn, target, self.limit, Some(vector_candidates))?
+                }
+            }
+            VectorTarget::MultiVector(target) => {
+                let reader = ArroyWrapper::new(ctx.index, self.embedder_index, self.quantization);
+                let results = if self.exact {
+                    reader.exact_nns_by_vectors(ctx.txn, target, self.limit, vector_candidates)?
+                } else {
+                    reader.nns_by_vectors(ctx.txn, target, self.limit, Some(vector_candidates))?
+                };
+                // MaxSim is a mean of cosine similarities, turned into a distance like arroy does
+                results.into_iter().map(|(docid, score)| (docid, 0, (1.0 - score) / 2.0)).collect()
+            }
+            VectorTarget::Sparse(target) => {
+                let store = SparseVectorStore::new(ctx.index, self.embedder_index);
+                store
+                    .nns_by_vector(ctx.txn, target, self.limit, Some(vector_candidates))?
+                    .into_iter()
+                    .map(|(docid, dot_product)| (docid, 0, 1.0 - sparse::similarity(dot_product)))
+                    .collect()
+            }
+        };
         self.cached_sorted_docids = results.into_iter();
         *ctx.vector_store_stats.get_or_insert_default() += VectorStoreStats {
             total_time: before.elapsed(),
@@ -105,11 +145,14 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Vector(score_details::Vector { similarity: None }),
+                score: ScoreDetails::Vector(score_details::Vector {
+                    similarity: None,
+                    vector_index: None,
+                }),
             }));
         }
 
-        for (docid, distance) in self.cached_sorted_docids.by_ref() {
+        for (docid, vector_index, distance) in self.cached_sorted_docids.by_ref() {
             if vector_candidates.contains(docid) {
                 let score = 1.0 - distance;
                 let score = self
@@ -119,7 +162,15 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
                 return Ok(Some(RankingRuleOutput {
                     query,
                     candidates: RoaringBitmap::from_iter([docid]),
-                    score: ScoreDetails::Vector(score_details::Vector { similarity: Some(score) }),
+                    score: ScoreDetails::Vector(score_details::Vector {
+                        similarity: Some(score),
+                        // sparse embeddings of chunks are merged, and multi-vector scores are computed
+                        // from all the vectors of the document, so there is no matching chunk
+                        vector_index: match self.target {
+                            VectorTarget::Dense(_) => Some(vector_index),
+                            VectorTarget::Sparse(_) | VectorTarget::MultiVector(_) => None,
+                        },
+                    }),
                 }));
             }
         }
@@ -135,7 +186,10 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Vector(score_details::Vector { similarity: None }),
+                score: ScoreDetails::Vector(score_details::Vector {
+                    similarity: None,
+                    vector_index: None,
+                }),
             }));
         }
 
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use crate::prompt::{truncate, Prompt};
+use crate::score_details::{self, ScoreDetails};
+use crate::vector::rerank::Reranker;
+use crate::{Result, Search, SearchResult};
+
+#[derive(Clone)]
+pub struct RerankSearch {
+    pub(super) reranker_name: String,
+    pub(super) reranker: Arc<Reranker>,
+    pub(super) document_template: Arc<Prompt>,
+    pub(super) top_n: usize,
+}
+
+impl<'a> Search<'a> {
+    /// The search to rerank: it starts at the first document, so that the reranked documents
+    /// do not depend on the requested page, and contains at least the documents to rerank.
+    ///
+    /// The ranking score threshold applies to the scores of the reranker, after reranking.
+    pub(super) fn rerank_window(&self, rerank: &RerankSearch) -> Search<'a> {
+        Search {
+            offset: 0,
+            limit: self.offset.saturating_add(self.limit).max(rerank.top_n),
+            ranking_score_threshold: None,
+            rerank: None,
+            ..self.clone()
+        }
+    }
+
+    /// Reorders the first documents of the results of the window and only keeps them,
+    /// so that all the documents are scored by the reranker, then returns the requested page.
+    #[tracing::instrument(level = "trace", skip_all, target = "search::rerank")]
+    pub(super) fn apply_rerank(
+        &self,
+        rerank: &RerankSearch,
+        mut result: SearchResult,
+    ) -> Result<SearchResult> {
+        // no reranking for placeholder searches
+        if let Some(query) = self.query.as_deref().filter(|query| !query.trim().is_empty()) {
+            let top_n = rerank.top_n.min(result.documents_ids.len());
+
+            let mut texts = Vec::with_capacity(top_n);
+            for docid in &result.documents_ids[..top_n] {
+                let mut text = rerank
+                    .document_template
+                    .render_stored_document(self.index, self.rtxn, *docid)?
+                    .unwrap_or_default();
+                if let Some(max_bytes) = rerank.document_template.max_bytes() {
+                    truncate(&mut text, max_bytes.get());
+                }
+                texts.push(text);
+            }
+            let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+            let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+            match rerank.reranker.rerank(query, &texts, Some(deadline)) {
+                Ok(scores) => {
+                    let mut reranked: Vec<_> = result
+                        .documents_ids
+                        .drain(..top_n)
+                        .zip(result.document_scores.drain(..top_n))
+                        .zip(scores)
+                        .collect();
+                    // the sort is stable, so documents with the same score keep their order
+                    reranked.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+
+                    let (documents_ids, document_scores): (Vec<_>, Vec<_>) = reranked
+                        .into_iter()
+                        .map(|((docid, mut scores), score)| {
+                            scores.insert(
+                                0,
+                                ScoreDetails::Rerank(score_details::Rerank {
+                                    reranker: rerank.reranker_name.clone(),
+                                    score,
+                                }),
+                            );
+                            (docid, scores)
+                        })
+                        .unzip();
+                    result.candidates = documents_ids.iter().copied().collect();
+                    result.documents_ids = documents_ids;
+                    result.document_scores = document_scores;
+                }
+                Err(error) => {
+                    // keep the order of the search, like hybrid search does when the query cannot be embedded
+                    tracing::error!(error=%error, "Reranking failed");
+                }
+            }
+        }
+
+        if let Some(ranking_score_threshold) = self.ranking_score_threshold {
+            // the documents are sorted by score, the ones after the first one below the threshold
+            // are no longer candidates
+            if let Some(below_threshold) = result.document_scores.iter().position(|scores| {
+                ScoreDetails::global_score(scores.iter()) < ranking_score_threshold
+            }) {
+                result.documents_ids.truncate(below_threshold);
+                result.document_scores.truncate(below_threshold);
+                result.candidates = result.documents_ids.iter().copied().collect();
+            }
+        }
+
+        let offset = self.offset.min(result.documents_ids.len());
+        result.documents_ids.drain(..offset);
+        result.documents_ids.truncate(self.limit);
+        let offset = self.offset.min(result.document_scores.len());
+        result.document_scores.drain(..offset);
+        result.document_scores.truncate(self.limit);
+
+        Ok(result)
+    }
+}
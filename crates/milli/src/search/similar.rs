@@ -3,7 +3,7 @@ use std::sync::Arc;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::{ArroyWrapper, Embedder};
+use crate::vector::{sparse, ArroyWrapper, Embedder, SparseVectorStore, VectorQuantization};
 use crate::{filtered_universe, DocumentId, Filter, Index, Result, SearchResult};
 
 pub struct Similar<'a> {
@@ -17,7 +17,7 @@ pub struct Similar<'a> {
     embedder_name: String,
     embedder: Arc<Embedder>,
     ranking_score_threshold: Option<f64>,
-    quantized: bool,
+    quantization: VectorQuantization,
 }
 
 impl<'a> Similar<'a> {
@@ -30,7 +30,7 @@ impl<'a> Similar<'a> {
         rtxn: &'a heed::RoTxn<'a>,
         embedder_name: String,
         embedder: Arc<Embedder>,
-        quantized: bool,
+        quantization: VectorQuantization,
     ) -> Self {
         Self {
             id,
@@ -42,7 +42,7 @@ impl<'a> Similar<'a> {
             embedder_name,
             embedder,
             ranking_score_threshold: None,
-            quantized,
+            quantization,
         }
     }
 
@@ -69,13 +69,35 @@ impl<'a> Similar<'a> {
                 || crate::UserError::InvalidSimilarEmbedder(self.embedder_name.to_owned()),
             )?;
 
-        let reader = ArroyWrapper::new(self.index.vector_arroy, embedder_index, self.quantized);
-        let results = reader.nns_by_item(
-            self.rtxn,
-            self.id,
-            self.limit + self.offset + 1,
-            Some(&universe),
-        )?;
+        let results = if self.embedder.is_sparse() {
+            let store = SparseVectorStore::new(self.index, embedder_index);
+            match store.item(self.rtxn, self.id)? {
+                Some(embedding) => store
+                    .nns_by_vector(
+                        self.rtxn,
+                        &embedding,
+                        self.limit + self.offset + 1,
+                        Some(&universe),
+                    )?
+                    .into_iter()
+                    .map(|(docid, dot_product)| (docid, 1.0 - sparse::similarity(dot_product)))
+                    .collect(),
+                None => Vec::new(),
+            }
+        } else if self.embedder.is_multi_vector() {
+            // the vectors of the target document are the query
+            let reader = ArroyWrapper::new(self.index, embedder_index, self.quantization);
+            let query = reader.item_vectors(self.rtxn, self.id)?;
+            reader
+                .nns_by_vectors(self.rtxn, &query, self.limit + self.offset + 1, Some(&universe))?
+                .into_iter()
+                // MaxSim is a mean of cosine similarities, turned into a distance like arroy does
+                .map(|(docid, score)| (docid, (1.0 - score) / 2.0))
+                .collect()
+        } else {
+            let reader = ArroyWrapper::new(self.index, embedder_index, self.quantization);
+            reader.nns_by_item(self.rtxn, self.id, self.limit + self.offset + 1, Some(&universe))?
+        };
 
         let mut documents_ids = Vec::with_capacity(self.limit);
         let mut document_scores = Vec::with_capacity(self.limit);
@@ -101,8 +123,10 @@ impl<'a> Similar<'a> {
                 .map(|distribution| distribution.shift(score))
                 .unwrap_or(score);
 
-            let score_details =
-                vec![ScoreDetails::Vector(score_details::Vector { similarity: Some(score) })];
+            let score_details = vec![ScoreDetails::Vector(score_details::Vector {
+                similarity: Some(score),
+                vector_index: None,
+            })];
 
             let score = ScoreDetails::global_score(score_details.iter());
 
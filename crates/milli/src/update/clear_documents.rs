@@ -46,6 +46,10 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
             vector_arroy,
+            vector_sparse_postings,
+            vector_sparse_documents,
+            vector_rescoring,
+            vector_chunks,
             embedder_category_id: _,
             documents,
         } = self.index;
@@ -62,6 +66,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
+        self.index.delete_geojson_rtree(self.wtxn)?;
 
         // Remove all user-provided bits from the configs
         let mut configs = self.index.embedding_configs(self.wtxn)?;
@@ -93,6 +98,10 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         field_id_docid_facet_strings.clear(self.wtxn)?;
         // vector
         vector_arroy.clear(self.wtxn)?;
+        vector_sparse_postings.clear(self.wtxn)?;
+        vector_sparse_documents.clear(self.wtxn)?;
+        vector_rescoring.clear(self.wtxn)?;
+        vector_chunks.clear(self.wtxn)?;
 
         documents.clear(self.wtxn)?;
 
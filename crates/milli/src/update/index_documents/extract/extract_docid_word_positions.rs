@@ -211,8 +211,8 @@ fn tokens_from_document<'a>(
             // "_vectors": { "manual": [1, 2, 3]} -> "_vectors.manual" is not registered.
             continue;
         };
-        // if field is searchable.
-        if metadata.is_searchable() {
+        // if field is searchable and the words are indexed.
+        if settings.keyword_search && metadata.is_searchable() {
             // extract deletion or addition only.
             if let Some(field_bytes) = KvReaderDelAdd::from_slice(field_bytes).get(del_add) {
                 // parse json.
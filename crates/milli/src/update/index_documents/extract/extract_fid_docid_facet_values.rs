@@ -115,6 +115,7 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         // during settings update, recompute the changing settings only unless a global change is detected.
                         if settings_diff.settings_update_only
                             && !settings_diff.global_facet_settings_changed()
+                            && !settings_diff.datetime_changed(field_id)
                         {
                             continue;
                         }
@@ -165,10 +166,12 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         .new
                         .geo_fields_ids
                         .is_some_and(|(lat, lng)| field_id == lat || field_id == lng);
-                    let del_filterable_values =
-                        del_value.map(|value| extract_facet_values(&value, del_geo_support));
-                    let add_filterable_values =
-                        add_value.map(|value| extract_facet_values(&value, add_geo_support));
+                    let del_datetime = settings_diff.old.is_datetime(field_id);
+                    let add_datetime = settings_diff.new.is_datetime(field_id);
+                    let del_filterable_values = del_value
+                        .map(|value| extract_facet_values(&value, del_geo_support, del_datetime));
+                    let add_filterable_values = add_value
+                        .map(|value| extract_facet_values(&value, add_geo_support, add_datetime));
 
                     // Those closures are just here to simplify things a bit.
                     let mut insert_numbers_diff = |del_numbers, add_numbers| {
@@ -513,13 +516,16 @@ enum FilterableValues {
 }
 
 /// Extracts the facet values of a JSON field.
-fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
+///
+/// The strings of a datetime field that are valid datetimes are extracted as numbers.
+fn extract_facet_values(value: &Value, geo_field: bool, datetime_field: bool) -> FilterableValues {
     fn inner_extract_facet_values(
         value: &Value,
         can_recurse: bool,
         output_numbers: &mut Vec<f64>,
         output_strings: &mut Vec<(String, String)>,
         geo_field: bool,
+        datetime_field: bool,
     ) {
         match value {
             Value::Null => (),
@@ -530,6 +536,12 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                 }
             }
             Value::String(original) => {
+                if datetime_field {
+                    if let Some(timestamp) = crate::datetime::parse_datetime(original) {
+                        output_numbers.push(timestamp);
+                        return;
+                    }
+                }
                 // if we're working on a geofield it MUST be something we can parse or else there was an internal error
                 // in the enrich pipeline. But since the enrich pipeline worked, we want to avoid crashing at all costs.
                 if geo_field {
@@ -553,6 +565,7 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                             output_numbers,
                             output_strings,
                             geo_field,
+                            datetime_field,
                         );
                     }
                 }
@@ -569,7 +582,14 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
         otherwise => {
             let mut numbers = Vec::new();
             let mut strings = Vec::new();
-            inner_extract_facet_values(otherwise, true, &mut numbers, &mut strings, geo_field);
+            inner_extract_facet_values(
+                otherwise,
+                true,
+                &mut numbers,
+                &mut strings,
+                geo_field,
+                datetime_field,
+            );
             FilterableValues::Values { numbers, strings }
         }
     }
@@ -46,15 +46,11 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
 
         if del_lat_lng != add_lat_lng {
             let mut obkv = KvWriterDelAdd::memory();
-            if let Some([lat, lng]) = del_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-                obkv.insert(DelAdd::Deletion, bytes)?;
+            if !del_lat_lng.is_empty() {
+                obkv.insert(DelAdd::Deletion, lat_lng_to_bytes(&del_lat_lng))?;
             }
-            if let Some([lat, lng]) = add_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-                obkv.insert(DelAdd::Addition, bytes)?;
+            if !add_lat_lng.is_empty() {
+                obkv.insert(DelAdd::Addition, lat_lng_to_bytes(&add_lat_lng))?;
             }
             let bytes = obkv.into_inner()?;
             writer.insert(docid_bytes, bytes)?;
@@ -64,13 +60,28 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
     writer_into_reader(writer)
 }
 
+/// Concatenates the native endian bytes of the latitude and longitude of each point.
+fn lat_lng_to_bytes(points: &[[f64; 2]]) -> Vec<u8> {
+    points
+        .iter()
+        .flat_map(|[lat, lng]| {
+            #[allow(clippy::drop_non_drop)]
+            let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+            bytes
+        })
+        .collect()
+}
+
 /// Extract the finite floats lat and lng from two bytes slices.
+///
+/// When the document has multiple geo points the flattened `_geo.lat` and `_geo.lng`
+/// fields are arrays of the same length, their values are zipped back together.
 fn extract_lat_lng(
     document: &obkv::KvReader<FieldId>,
     settings: &InnerIndexSettings,
     deladd: DelAdd,
     document_id: impl Fn() -> Value,
-) -> Result<Option<[f64; 2]>> {
+) -> Result<Vec<[f64; 2]>> {
     match settings.geo_fields_ids {
         Some((lat_fid, lng_fid)) => {
             let lat =
@@ -89,21 +100,43 @@ fn extract_lat_lng(
                         Box::new(GeoError::MissingLongitude { document_id: document_id() }).into()
                     )
                 }
-                (None, None) => return Ok(None),
+                (None, None) => return Ok(Vec::new()),
+            };
+            let lat: Value = serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?;
+            let lng: Value = serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?;
+            let (lats, lngs) = match (lat, lng) {
+                (Value::Array(lats), Value::Array(lngs)) => {
+                    if lats.len() < lngs.len() {
+                        return Err(Box::new(GeoError::MissingLatitude {
+                            document_id: document_id(),
+                        })
+                        .into());
+                    }
+                    if lats.len() > lngs.len() {
+                        return Err(Box::new(GeoError::MissingLongitude {
+                            document_id: document_id(),
+                        })
+                        .into());
+                    }
+                    (lats, lngs)
+                }
+                (lat, lng) => (vec![lat], vec![lng]),
             };
-            let lat = extract_finite_float_from_value(
-                serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lat| GeoError::BadLatitude { document_id: document_id(), value: lat })
-            .map_err(Box::new)?;
 
-            let lng = extract_finite_float_from_value(
-                serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lng| GeoError::BadLongitude { document_id: document_id(), value: lng })
-            .map_err(Box::new)?;
-            Ok(Some([lat, lng]))
+            let mut points = Vec::with_capacity(lats.len());
+            for (lat, lng) in lats.into_iter().zip(lngs) {
+                let lat = extract_finite_float_from_value(lat)
+                    .map_err(|value| GeoError::BadLatitude { document_id: document_id(), value })
+                    .map_err(Box::new)?;
+
+                let lng = extract_finite_float_from_value(lng)
+                    .map_err(|value| GeoError::BadLongitude { document_id: document_id(), value })
+                    .map_err(Box::new)?;
+
+                points.push([lat, lng]);
+            }
+            Ok(points)
         }
-        None => Ok(None),
+        None => Ok(Vec::new()),
     }
 }
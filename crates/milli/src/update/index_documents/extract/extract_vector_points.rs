@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use bytemuck::cast_slice;
 use grenad::Writer;
+use itertools::Itertools;
 use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 use serde_json::Value;
@@ -20,11 +21,13 @@ use crate::index::IndexEmbeddingConfig;
 use crate::prompt::Prompt;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::settings::InnerIndexSettingsDiff;
+use crate::vector::chunking::Chunking;
 use crate::vector::error::{EmbedErrorKind, PossibleEmbeddingMistakes, UnusedVectorsDistribution};
 use crate::vector::parsed_vectors::{ParsedVectorsDiff, VectorState};
 use crate::vector::settings::ReindexAction;
-use crate::vector::{Embedder, Embedding};
-use crate::{try_split_array_at, DocumentId, FieldId, Result, ThreadPoolNoAbort};
+use crate::vector::sparse::merge_max;
+use crate::vector::{Embedder, Embedding, SparseEmbedding};
+use crate::{try_split_array_at, DocumentId, FieldId, InternalError, Result, ThreadPoolNoAbort};
 
 /// The length of the elements that are always in the buffer when inserting new values.
 const TRUNCATE_SIZE: usize = size_of::<DocumentId>();
@@ -40,6 +43,7 @@ pub struct ExtractedVectorPoints {
     // embedder
     pub embedder_name: String,
     pub embedder: Arc<Embedder>,
+    pub chunking: Option<Chunking>,
     pub add_to_user_provided: RoaringBitmap,
     pub remove_from_user_provided: RoaringBitmap,
 }
@@ -444,7 +448,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     for EmbedderVectorExtractor {
         embedder_name,
         embedder,
-        prompt: _,
+        prompt,
         prompts_writer,
         remove_vectors_writer,
         action,
@@ -468,6 +472,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
             prompts: writer_into_reader(prompts_writer)?,
             embedder,
             embedder_name,
+            chunking: prompt.chunking().cloned(),
             add_to_user_provided,
             remove_from_user_provided,
         })
@@ -681,10 +686,11 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     indexer: GrenadParameters,
     embedder: Arc<Embedder>,
     embedder_name: &str,
+    chunking: Option<&Chunking>,
     possible_embedding_mistakes: &PossibleEmbeddingMistakes,
     unused_vectors_distribution: &UnusedVectorsDistribution,
     request_threads: &ThreadPoolNoAbort,
-) -> Result<grenad::Reader<BufReader<File>>> {
+) -> Result<(grenad::Reader<BufReader<File>>, grenad::Reader<BufReader<File>>)> {
     let n_chunks = embedder.chunk_count_hint(); // chunk level parallelism
     let n_vectors_per_chunk = embedder.prompt_count_in_chunk_hint(); // number of vectors in a single chunk
 
@@ -694,6 +700,14 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
         indexer.chunk_compression_level,
         tempfile::tempfile()?,
     );
+    // docid, byte ranges of the chunks embedded as the vectors of the document
+    let mut chunks_writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+    // each chunk becomes a vector of the document, unless the embeddings are merged or flattened
+    let store_chunks = !embedder.is_sparse() && !embedder.is_multi_vector();
 
     let mut chunks = Vec::with_capacity(n_chunks);
     let mut current_chunk = Vec::with_capacity(n_vectors_per_chunk);
@@ -705,7 +719,20 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
         let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
         // SAFETY: precondition, the grenad value was saved from a string
         let prompt = unsafe { std::str::from_utf8_unchecked(value) };
-        if current_chunk.len() == current_chunk.capacity() {
+        let texts: Vec<_> = match chunking {
+            Some(chunking) => {
+                let ranges = chunking.chunks(prompt);
+                if store_chunks {
+                    let value = serde_json::to_vec(&ranges).map_err(InternalError::SerdeJson)?;
+                    chunks_writer.insert(key, value)?;
+                }
+                ranges.into_iter().map(|r| &prompt[r]).collect()
+            }
+            None => vec![prompt],
+        };
+        // all the chunks of a document are kept in the same chunk of prompts,
+        // so that its embeddings are written at once.
+        if !current_chunk.is_empty() && current_chunk.len() + texts.len() > n_vectors_per_chunk {
             chunks.push(std::mem::replace(
                 &mut current_chunk,
                 Vec::with_capacity(n_vectors_per_chunk),
@@ -715,8 +742,10 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
                 Vec::with_capacity(n_vectors_per_chunk),
             ));
         };
-        current_chunk.push(prompt.to_owned());
-        current_chunk_ids.push(docid);
+        for text in texts {
+            current_chunk.push(text.to_owned());
+            current_chunk_ids.push(docid);
+        }
 
         if chunks.len() == chunks.capacity() {
             let chunked_embeds = embed_chunks(
@@ -728,13 +757,11 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
                 request_threads,
             )?;
 
-            for (docid, embeddings) in chunks_ids
-                .iter()
-                .flat_map(|docids| docids.iter())
-                .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
-            {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
-            }
+            insert_embeddings(
+                &mut state_writer,
+                chunks_ids.iter().flat_map(|docids| docids.iter()),
+                &chunked_embeds,
+            )?;
             chunks_ids.clear();
         }
     }
@@ -749,13 +776,11 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
             unused_vectors_distribution,
             request_threads,
         )?;
-        for (docid, embeddings) in chunks_ids
-            .iter()
-            .flat_map(|docids| docids.iter())
-            .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
-        {
-            state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
-        }
+        insert_embeddings(
+            &mut state_writer,
+            chunks_ids.iter().flat_map(|docids| docids.iter()),
+            &chunked_embeds,
+        )?;
     }
 
     if !current_chunk.is_empty() {
@@ -768,14 +793,64 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
             request_threads,
         )?;
 
-        if let Some(embeds) = embeds.first() {
-            for (docid, embeddings) in current_chunk_ids.iter().zip(embeds.iter()) {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings))?;
+        insert_embeddings(&mut state_writer, current_chunk_ids.iter(), &embeds)?;
+    }
+
+    Ok((writer_into_reader(state_writer)?, writer_into_reader(chunks_writer)?))
+}
+
+/// The embeddings of chunks of texts, depending on the kind of embedder.
+enum ChunkEmbeddings {
+    Dense(Vec<Vec<Embedding>>),
+    Sparse(Vec<Vec<SparseEmbedding>>),
+    MultiVector(Vec<Vec<Vec<Embedding>>>),
+}
+
+/// Writes the embeddings of each document, the consecutive embeddings of a document being its chunks.
+///
+/// Dense embeddings, including all the embeddings of each text of a multi-vector embedder, are written
+/// as their concatenated floats, while the sparse embeddings of the chunks are merged and written as JSON.
+fn insert_embeddings<'a>(
+    state_writer: &mut Writer<BufWriter<File>>,
+    docids: impl Iterator<Item = &'a DocumentId>,
+    embeddings: &ChunkEmbeddings,
+) -> Result<()> {
+    let mut buffer = Vec::new();
+    match embeddings {
+        ChunkEmbeddings::Dense(chunks) => {
+            for (docid, embeddings) in
+                &docids.zip(chunks.iter().flatten()).chunk_by(|(docid, _)| **docid)
+            {
+                buffer.clear();
+                for (_, embedding) in embeddings {
+                    buffer.extend_from_slice(cast_slice(embedding));
+                }
+                state_writer.insert(docid.to_be_bytes(), &buffer)?;
+            }
+        }
+        ChunkEmbeddings::MultiVector(chunks) => {
+            for (docid, embeddings) in
+                &docids.zip(chunks.iter().flatten()).chunk_by(|(docid, _)| **docid)
+            {
+                buffer.clear();
+                for embedding in embeddings.flat_map(|(_, embeddings)| embeddings) {
+                    buffer.extend_from_slice(cast_slice(embedding));
+                }
+                state_writer.insert(docid.to_be_bytes(), &buffer)?;
+            }
+        }
+        ChunkEmbeddings::Sparse(chunks) => {
+            for (docid, embeddings) in
+                &docids.zip(chunks.iter().flatten()).chunk_by(|(docid, _)| **docid)
+            {
+                let embedding = merge_max(embeddings.map(|(_, embedding)| embedding.clone()));
+                buffer.clear();
+                serde_json::to_writer(&mut buffer, &embedding).map_err(InternalError::SerdeJson)?;
+                state_writer.insert(docid.to_be_bytes(), &buffer)?;
             }
         }
     }
-
-    writer_into_reader(state_writer)
+    Ok(())
 }
 
 fn embed_chunks(
@@ -785,8 +860,15 @@ fn embed_chunks(
     possible_embedding_mistakes: &PossibleEmbeddingMistakes,
     unused_vectors_distribution: &UnusedVectorsDistribution,
     request_threads: &ThreadPoolNoAbort,
-) -> Result<Vec<Vec<Embedding>>> {
-    match embedder.embed_index(text_chunks, request_threads) {
+) -> Result<ChunkEmbeddings> {
+    let embedded = if embedder.is_sparse() {
+        embedder.embed_index_sparse(text_chunks, request_threads).map(ChunkEmbeddings::Sparse)
+    } else if embedder.is_multi_vector() {
+        embedder.embed_index_multi(text_chunks, request_threads).map(ChunkEmbeddings::MultiVector)
+    } else {
+        embedder.embed_index(text_chunks, request_threads).map(ChunkEmbeddings::Dense)
+    };
+    match embedded {
         Ok(chunks) => Ok(chunks),
         Err(error) => {
             if let FaultSource::Bug = error.fault {
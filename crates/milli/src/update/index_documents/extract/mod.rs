@@ -260,23 +260,25 @@ fn send_original_documents_data(
                         prompts,
                         embedder_name,
                         embedder,
+                        chunking,
                         add_to_user_provided,
                         remove_from_user_provided,
                     } in extracted_vectors
                     {
-                        let embeddings = match extract_embeddings(
+                        let (embeddings, chunks) = match extract_embeddings(
                             prompts,
                             indexer,
                             embedder.clone(),
                             &embedder_name,
+                            chunking.as_ref(),
                             &possible_embedding_mistakes,
                             &unused_vectors_distribution,
                             request_threads(),
                         ) {
-                            Ok(results) => Some(results),
+                            Ok((embeddings, chunks)) => (Some(embeddings), Some(chunks)),
                             Err(error) => {
                                 let _ = lmdb_writer_sx.send(Err(error));
-                                None
+                                (None, None)
                             }
                         };
                         if !(remove_vectors.is_empty()
@@ -286,6 +288,7 @@ fn send_original_documents_data(
                             let _ = lmdb_writer_sx.send(Ok(TypedChunk::VectorPoints {
                                 remove_vectors,
                                 embeddings,
+                                chunks,
                                 expected_dimension: embedder.dimensions(),
                                 manual_vectors,
                                 embedder_name,
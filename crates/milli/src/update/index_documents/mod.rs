@@ -443,6 +443,7 @@ where
                                     expected_dimension,
                                     remove_vectors,
                                     embeddings,
+                                    chunks,
                                     manual_vectors,
                                     embedder_name,
                                     add_to_user_provided,
@@ -452,6 +453,7 @@ where
                                     TypedChunk::VectorPoints {
                                         remove_vectors,
                                         embeddings,
+                                        chunks,
                                         expected_dimension,
                                         manual_vectors,
                                         embedder_name,
@@ -488,7 +490,7 @@ where
         let number_of_documents = self.index.number_of_documents(self.wtxn)?;
         let mut rng = rand::rngs::StdRng::seed_from_u64(42);
 
-        // If an embedder wasn't used in the typedchunk but must be binary quantized
+        // If an embedder wasn't used in the typedchunk but its quantization changes
         // we should insert it in `dimension`
         for (name, action) in settings_diff.embedding_config_updates.iter() {
             if action.is_being_quantized && !dimension.contains_key(name.as_str()) {
@@ -498,35 +500,50 @@ where
                         key: None,
                     },
                 )?;
-                let reader =
-                    ArroyWrapper::new(self.index.vector_arroy, index, action.was_quantized);
+                let reader = ArroyWrapper::new(self.index, index, action.was_quantized);
                 let dim = reader.dimensions(self.wtxn)?;
                 dimension.insert(name.to_string(), dim);
             }
         }
 
         for (embedder_name, dimension) in dimension {
+            // sparse embeddings are stored in posting lists and need no arroy build
+            if settings_diff
+                .new
+                .embedding_configs
+                .get(&embedder_name)
+                .is_some_and(|(embedder, _, _)| embedder.is_sparse())
+            {
+                continue;
+            }
             let wtxn = &mut *self.wtxn;
-            let vector_arroy = self.index.vector_arroy;
+            let index = self.index;
             let cancel = &self.should_abort;
 
             let embedder_index = self.index.embedder_category_id.get(wtxn, &embedder_name)?.ok_or(
                 InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
             )?;
             let embedder_config = settings_diff.embedding_config_updates.get(&embedder_name);
-            let was_quantized =
-                settings_diff.old.embedding_configs.get(&embedder_name).is_some_and(|conf| conf.2);
-            let is_quantizing = embedder_config.is_some_and(|action| action.is_being_quantized);
+            let was_quantized = settings_diff
+                .old
+                .embedding_configs
+                .get(&embedder_name)
+                .map(|conf| conf.2)
+                .unwrap_or_default();
+            let quantizing = embedder_config
+                .filter(|action| action.is_being_quantized)
+                .and_then(|_| settings_diff.new.embedding_configs.get(&embedder_name))
+                .map(|conf| conf.2);
 
             pool.install(|| {
-                let mut writer = ArroyWrapper::new(vector_arroy, embedder_index, was_quantized);
+                let mut writer = ArroyWrapper::new(index, embedder_index, was_quantized);
                 writer.build_and_quantize(
                     wtxn,
                     // In the settings we don't have any progress to share
                     &Progress::default(),
                     &mut rng,
                     dimension,
-                    is_quantizing,
+                    quantizing,
                     self.indexer_config.max_memory,
                     cancel,
                 )?;
@@ -2772,6 +2789,7 @@ mod tests {
                         model: Setting::NotSet,
                         revision: Setting::NotSet,
                         pooling: Setting::NotSet,
+                        normalize: Setting::NotSet,
                         api_key: Setting::NotSet,
                         dimensions: Setting::Set(3),
                         document_template: Setting::NotSet,
@@ -2784,6 +2802,13 @@ mod tests {
                         search_embedder: Setting::NotSet,
                         indexing_embedder: Setting::NotSet,
                         binary_quantized: Setting::NotSet,
+                        quantization: Setting::NotSet,
+                        rescoring: Setting::NotSet,
+                        chunking: Setting::NotSet,
+                        sparse: Setting::NotSet,
+                        multi_vector: Setting::NotSet,
+                        indexing_fragment: Setting::NotSet,
+                        search_fragment: Setting::NotSet,
                     }),
                 );
                 settings.set_embedder_settings(embedders);
@@ -2809,7 +2834,7 @@ mod tests {
         insta::assert_snapshot!(embedder_name, @"manual");
         insta::assert_debug_snapshot!(user_provided, @"RoaringBitmap<[0, 1, 2]>");
         let embedder = std::sync::Arc::new(
-            crate::vector::Embedder::new(embedder.embedder_options, 0).unwrap(),
+            crate::vector::Embedder::new(embedder.embedder_options, 0, None).unwrap(),
         );
         let res = index
             .search(&rtxn)
@@ -30,9 +30,10 @@ use crate::update::del_add::{
 use crate::update::index_documents::GrenadParameters;
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
 use crate::update::{AvailableIds, UpdateIndexingStep};
+use crate::vector::chunking::ChunkStore;
 use crate::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
 use crate::vector::settings::WriteBackToDocuments;
-use crate::vector::ArroyWrapper;
+use crate::vector::{ArroyWrapper, SparseVectorStore};
 use crate::{FieldDistribution, FieldId, FieldIdMapMissingEntry, Index, Result};
 
 pub struct TransformOutput {
@@ -841,11 +842,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                 if let Some(WriteBackToDocuments { embedder_id, user_provided }) =
                     action.write_back()
                 {
-                    let reader = ArroyWrapper::new(
-                        self.index.vector_arroy,
-                        *embedder_id,
-                        action.was_quantized,
-                    );
+                    let reader = ArroyWrapper::new(self.index, *embedder_id, action.was_quantized);
                     Some((name.as_str(), (reader, user_provided)))
                 } else {
                     None
@@ -932,9 +929,45 @@ impl<'a, 'i> Transform<'a, 'i> {
         }
 
         // delete all vectors from the embedders that need removal
-        for (_, (reader, _)) in readers {
-            let dimensions = reader.dimensions(wtxn)?;
-            reader.clear(wtxn, dimensions)?;
+        for (name, (reader, _)) in readers {
+            ChunkStore::new(self.index, reader.embedder_index()).clear(wtxn)?;
+            let was_sparse = settings_diff
+                .old
+                .embedding_configs
+                .get(name)
+                .is_some_and(|(embedder, _, _)| embedder.is_sparse());
+            if was_sparse {
+                SparseVectorStore::new(self.index, reader.embedder_index()).clear(wtxn)?;
+            } else {
+                let dimensions = reader.dimensions(wtxn)?;
+                reader.clear(wtxn, dimensions)?;
+            }
+        }
+
+        // embedders switching between dense and sparse embeddings are fully reindexed,
+        // but their embeddings must also be removed from the store they no longer use
+        for (name, action) in settings_diff.embedding_config_updates.iter() {
+            if action.write_back().is_some() {
+                continue;
+            }
+            let Some((old_embedder, _, _)) = settings_diff.old.embedding_configs.get(name) else {
+                continue;
+            };
+            let Some((new_embedder, _, _)) = settings_diff.new.embedding_configs.get(name) else {
+                continue;
+            };
+            if old_embedder.is_sparse() == new_embedder.is_sparse() {
+                continue;
+            }
+            let Some(embedder_id) = self.index.embedder_category_id.get(wtxn, name)? else {
+                continue;
+            };
+            if old_embedder.is_sparse() {
+                SparseVectorStore::new(self.index, embedder_id).clear(wtxn)?;
+            } else {
+                let reader = ArroyWrapper::new(self.index, embedder_id, action.was_quantized);
+                reader.clear(wtxn, old_embedder.dimensions())?;
+            }
         }
 
         let grenad_params = GrenadParameters {
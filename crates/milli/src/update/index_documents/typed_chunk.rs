@@ -26,7 +26,8 @@ use crate::update::index_documents::helpers::{
     as_cloneable_grenad, try_split_array_at, KeepLatestObkv,
 };
 use crate::update::settings::InnerIndexSettingsDiff;
-use crate::vector::ArroyWrapper;
+use crate::vector::chunking::ChunkStore;
+use crate::vector::{ArroyWrapper, SparseEmbedding, SparseVectorStore};
 use crate::{
     lat_lng_to_xyz, CboRoaringBitmapCodec, DocumentId, FieldId, GeoPoint, Index, InternalError,
     Result, SerializationError, U8StrStrCodec,
@@ -87,6 +88,7 @@ pub(crate) enum TypedChunk {
     VectorPoints {
         remove_vectors: grenad::Reader<BufReader<File>>,
         embeddings: Option<grenad::Reader<BufReader<File>>>,
+        chunks: Option<grenad::Reader<BufReader<File>>>,
         expected_dimension: usize,
         manual_vectors: grenad::Reader<BufReader<File>>,
         embedder_name: String,
@@ -600,13 +602,15 @@ pub(crate) fn write_typed_chunk_into_index(
 
                 let deladd_obkv = KvReaderDelAdd::from_slice(value);
                 if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.remove(&geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.remove(&geopoint);
+                    }
                     geo_faceted_docids.remove(docid);
                 }
                 if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.insert(geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.insert(geopoint);
+                    }
                     geo_faceted_docids.insert(docid);
                 }
             }
@@ -620,6 +624,7 @@ pub(crate) fn write_typed_chunk_into_index(
             let mut remove_vectors_builder = MergerBuilder::new(KeepFirst);
             let mut manual_vectors_builder = MergerBuilder::new(KeepFirst);
             let mut embeddings_builder = MergerBuilder::new(KeepFirst);
+            let mut chunks_builder = MergerBuilder::new(KeepFirst);
             let mut add_to_user_provided = RoaringBitmap::new();
             let mut remove_from_user_provided = RoaringBitmap::new();
             let mut params = None;
@@ -628,6 +633,7 @@ pub(crate) fn write_typed_chunk_into_index(
                     remove_vectors,
                     manual_vectors,
                     embeddings,
+                    chunks,
                     expected_dimension,
                     embedder_name,
                     add_to_user_provided: aud,
@@ -644,6 +650,9 @@ pub(crate) fn write_typed_chunk_into_index(
                 if let Some(embeddings) = embeddings {
                     embeddings_builder.push(embeddings.into_cursor()?);
                 }
+                if let Some(chunks) = chunks {
+                    chunks_builder.push(chunks.into_cursor()?);
+                }
                 add_to_user_provided |= aud;
                 remove_from_user_provided |= rud;
             }
@@ -664,10 +673,44 @@ pub(crate) fn write_typed_chunk_into_index(
             let embedder_index = index.embedder_category_id.get(wtxn, &embedder_name)?.ok_or(
                 InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
             )?;
-            let binary_quantized =
-                settings_diff.old.embedding_configs.get(&embedder_name).is_some_and(|conf| conf.2);
+            let quantization = settings_diff
+                .old
+                .embedding_configs
+                .get(&embedder_name)
+                .map(|conf| conf.2)
+                .unwrap_or_default();
+            let is_sparse = settings_diff
+                .new
+                .embedding_configs
+                .get(&embedder_name)
+                .is_some_and(|(embedder, _, _)| embedder.is_sparse());
+            if is_sparse {
+                let store = SparseVectorStore::new(index, embedder_index);
+
+                let merger = remove_vectors_builder.build();
+                let mut iter = merger.into_stream_merger_iter()?;
+                while let Some((key, _)) = iter.next()? {
+                    let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+                    store.del_item(wtxn, docid)?;
+                }
+
+                let merger = embeddings_builder.build();
+                let mut iter = merger.into_stream_merger_iter()?;
+                while let Some((key, value)) = iter.next()? {
+                    let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+                    let embedding: SparseEmbedding =
+                        serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                    store.add_item(wtxn, docid, &embedding)?;
+                }
+
+                // user-provided vectors are not supported by sparse embedders, so there is no manual diff to apply
+                tracing::debug!("Finished sparse vector chunk for {}", embedder_name);
+                return Ok((RoaringBitmap::new(), is_merged_database));
+            }
+
             // FIXME: allow customizing distance
-            let writer = ArroyWrapper::new(index.vector_arroy, embedder_index, binary_quantized);
+            let writer = ArroyWrapper::new(index, embedder_index, quantization);
+            let chunk_store = ChunkStore::new(index, embedder_index);
 
             // remove vectors for docids we want them removed
             let merger = remove_vectors_builder.build();
@@ -675,6 +718,16 @@ pub(crate) fn write_typed_chunk_into_index(
             while let Some((key, _)) = iter.next()? {
                 let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
                 writer.del_items(wtxn, expected_dimension, docid)?;
+                chunk_store.del_chunks(wtxn, docid)?;
+            }
+
+            // store the chunks embedded as the generated embeddings
+            let merger = chunks_builder.build();
+            let mut iter = merger.into_stream_merger_iter()?;
+            while let Some((key, value)) = iter.next()? {
+                let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+                let chunks = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                chunk_store.put_chunks(wtxn, docid, chunks)?;
             }
 
             // add generated embeddings
@@ -735,13 +788,15 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
-/// Converts the latitude and longitude back to an xyz GeoPoint.
-pub fn extract_geo_point(value: &[u8], docid: DocumentId) -> GeoPoint {
-    let (lat, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
-    let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
-    let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
-    let xyz_point = lat_lng_to_xyz(&point);
-    GeoPoint::new(xyz_point, (docid, point))
+/// Converts the concatenated latitudes and longitudes of a document back to xyz GeoPoints.
+pub fn extract_geo_points(value: &[u8], docid: DocumentId) -> impl Iterator<Item = GeoPoint> + '_ {
+    value.chunks_exact(16).map(move |bytes| {
+        let (lat, tail) = helpers::try_split_array_at::<u8, 8>(bytes).unwrap();
+        let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+        let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
+        let xyz_point = lat_lng_to_xyz(&point);
+        GeoPoint::new(xyz_point, (docid, point))
+    })
 }
 
 fn merge_word_docids_reader_into_fst<MF>(
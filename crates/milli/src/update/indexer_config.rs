@@ -2,8 +2,9 @@ use grenad::CompressionType;
 
 use super::GrenadParameters;
 use crate::thread_pool_no_abort::ThreadPoolNoAbort;
+use crate::vector::PersistentEmbeddingCache;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct IndexerConfig {
     pub log_every_n: Option<usize>,
     pub max_nb_chunks: Option<usize>,
@@ -14,6 +15,8 @@ pub struct IndexerConfig {
     pub thread_pool: Option<ThreadPoolNoAbort>,
     pub max_positions_per_attributes: Option<u32>,
     pub skip_index_budget: bool,
+    /// The on-disk cache of the embeddings, shared between indexing and search.
+    pub embedding_cache: Option<PersistentEmbeddingCache>,
 }
 
 impl IndexerConfig {
@@ -39,6 +42,7 @@ impl Default for IndexerConfig {
             thread_pool: None,
             max_positions_per_attributes: None,
             skip_index_budget: false,
+            embedding_cache: None,
         }
     }
 }
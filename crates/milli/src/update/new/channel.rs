@@ -24,9 +24,9 @@ use super::thread_local::{FullySend, ThreadLocal};
 use super::StdResult;
 use crate::heed_codec::facet::{FieldDocIdFacetF64Codec, FieldDocIdFacetStringCodec};
 use crate::index::db_name;
-use crate::index::main_key::{GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
+use crate::index::main_key::{GEOJSON_RTREE_KEY, GEO_FACETED_DOCUMENTS_IDS_KEY, GEO_RTREE_KEY};
 use crate::update::new::KvReaderFieldId;
-use crate::vector::Embedding;
+use crate::vector::{Embedding, SparseEmbedding};
 use crate::{CboRoaringBitmapCodec, DocumentId, Error, Index, InternalError};
 
 /// Note that the FrameProducer requires up to 9 bytes to
@@ -398,6 +398,8 @@ pub enum Database {
     FacetIdStringDocids,
     FieldIdDocidFacetStrings,
     FieldIdDocidFacetF64s,
+    VectorSparseDocuments,
+    VectorChunks,
 }
 
 impl Database {
@@ -419,6 +421,8 @@ impl Database {
             Database::FacetIdStringDocids => index.facet_id_string_docids.remap_types(),
             Database::FieldIdDocidFacetStrings => index.field_id_docid_facet_strings.remap_types(),
             Database::FieldIdDocidFacetF64s => index.field_id_docid_facet_f64s.remap_types(),
+            Database::VectorSparseDocuments => index.vector_sparse_documents.remap_types(),
+            Database::VectorChunks => index.vector_chunks.remap_types(),
         }
     }
 
@@ -440,6 +444,8 @@ impl Database {
             Database::FacetIdStringDocids => db_name::FACET_ID_STRING_DOCIDS,
             Database::FieldIdDocidFacetStrings => db_name::FIELD_ID_DOCID_FACET_STRINGS,
             Database::FieldIdDocidFacetF64s => db_name::FIELD_ID_DOCID_FACET_F64S,
+            Database::VectorSparseDocuments => db_name::VECTOR_SPARSE_DOCUMENTS,
+            Database::VectorChunks => db_name::VECTOR_CHUNKS,
         }
     }
 }
@@ -938,16 +944,49 @@ impl EmbeddingSender<'_, '_> {
         self.0.set_vectors(docid, embedder_id, &embeddings[..])
     }
 
-    pub fn set_vector(
+    /// Sends the sparse embedding of a document.
+    ///
+    /// The writer stores it in the [`crate::vector::SparseVectorStore`] of the embedder,
+    /// replacing the previous sparse embedding of the document.
+    pub fn set_sparse_vector(
         &self,
         docid: DocumentId,
         embedder_id: u8,
-        embedding: Embedding,
+        embedding: &SparseEmbedding,
     ) -> crate::Result<()> {
-        self.0.set_vectors(docid, embedder_id, &[embedding])
+        let key = embedder_document_key(docid, embedder_id);
+        let value = serde_json::to_vec(embedding).map_err(InternalError::SerdeJson)?;
+        self.0.write_key_value(Database::VectorSparseDocuments, &key, &value)
+    }
+
+    /// Removes the sparse embedding of a document, if any.
+    pub fn delete_sparse_vector(&self, docid: DocumentId, embedder_id: u8) -> crate::Result<()> {
+        let key = embedder_document_key(docid, embedder_id);
+        self.0.delete_entry(Database::VectorSparseDocuments, &key)
+    }
+
+    /// Sends the byte ranges of the chunks embedded as the vectors of a document.
+    ///
+    /// The writer stores them in the [`crate::vector::chunking::ChunkStore`] of the embedder.
+    pub fn set_chunks(
+        &self,
+        docid: DocumentId,
+        embedder_id: u8,
+        chunks: &[Range<usize>],
+    ) -> crate::Result<()> {
+        let key = embedder_document_key(docid, embedder_id);
+        let value = serde_json::to_vec(chunks).map_err(InternalError::SerdeJson)?;
+        self.0.write_key_value(Database::VectorChunks, &key, &value)
     }
 }
 
+fn embedder_document_key(docid: DocumentId, embedder_id: u8) -> [u8; 5] {
+    let mut key = [0u8; 5];
+    key[0] = embedder_id;
+    key[1..].copy_from_slice(&docid.to_be_bytes());
+    key
+}
+
 #[derive(Clone, Copy)]
 pub struct GeoSender<'a, 'b>(&'a ExtractorBbqueueSender<'b>);
 
@@ -963,6 +1002,17 @@ impl GeoSender<'_, '_> {
             .map_err(|_| SendError(()))
     }
 
+    pub fn set_geojson_rtree(&self, value: Mmap) -> StdResult<(), SendError<()>> {
+        self.0
+            .sender
+            .send(ReceiverAction::LargeEntry(LargeEntry {
+                database: Database::Main,
+                key: GEOJSON_RTREE_KEY.to_string().into_bytes().into_boxed_slice(),
+                value,
+            }))
+            .map_err(|_| SendError(()))
+    }
+
     pub fn set_geo_faceted(&self, bitmap: &RoaringBitmap) -> crate::Result<()> {
         let database = Database::Main;
         let value_length = bitmap.serialized_size();
@@ -7,7 +7,9 @@ use serde_json::value::RawValue;
 
 use super::vector_document::VectorDocument;
 use super::{KvReaderFieldId, KvWriterFieldId};
-use crate::constants::{RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME};
+use crate::constants::{
+    RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME, RESERVED_VECTORS_FIELD_NAME,
+};
 use crate::documents::FieldIdMapper;
 use crate::{DocumentId, GlobalFieldsIdsMap, Index, InternalError, Result, UserError};
 
@@ -19,15 +21,15 @@ pub trait Document<'doc> {
     /// Iterate over all **top-level** fields of the document, returning their name and raw JSON value.
     ///
     /// - The returned values *may* contain nested fields.
-    /// - The `_vectors` and `_geo` fields are **ignored** by this method, meaning  they are **not returned** by this method.
+    /// - The `_vectors`, `_geo` and `_geojson` fields are **ignored** by this method, meaning  they are **not returned** by this method.
     fn iter_top_level_fields(&self) -> impl Iterator<Item = Result<(&'doc str, &'doc RawValue)>>;
 
-    /// Number of top level fields, **excluding** `_vectors` and `_geo`
+    /// Number of top level fields, **excluding** `_vectors`, `_geo` and `_geojson`
     fn top_level_fields_count(&self) -> usize;
 
     /// Get the **top-level** with the specified name, if exists.
     ///
-    /// - The `_vectors` and `_geo` fields are **ignored** by this method, meaning e.g. `top_level_field("_vectors")` will return `Ok(None)`
+    /// - The `_vectors`, `_geo` and `_geojson` fields are **ignored** by this method, meaning e.g. `top_level_field("_vectors")` will return `Ok(None)`
     fn top_level_field(&self, k: &str) -> Result<Option<&'doc RawValue>>;
 
     /// Returns the unparsed value of the `_vectors` field from the document data.
@@ -45,6 +47,18 @@ pub trait Document<'doc> {
     ///
     /// This method is meant as a convenience for implementors of [`super::geo_document::GeoDocument`].
     fn geo_field(&self) -> Result<Option<&'doc RawValue>>;
+
+    /// Returns the unparsed value of the `_geojson` field from the document data.
+    ///
+    /// The shape is also stored in the geojson rtree when the `_geojson` field is filterable.
+    fn geojson_field(&self) -> Result<Option<&'doc RawValue>>;
+}
+
+/// Returns `true` if the field is handled separately from the other top-level fields of the documents.
+fn is_reserved_field(name: &str) -> bool {
+    name == RESERVED_VECTORS_FIELD_NAME
+        || name == RESERVED_GEO_FIELD_NAME
+        || name == RESERVED_GEOJSON_FIELD_NAME
 }
 
 #[derive(Debug)]
@@ -80,7 +94,7 @@ impl<'t, Mapper: FieldIdMapper> Document<'t> for DocumentFromDb<'t, Mapper> {
                 Err(error) => return Some(Err(error.into())),
             };
 
-            if name == RESERVED_VECTORS_FIELD_NAME || name == RESERVED_GEO_FIELD_NAME {
+            if is_reserved_field(name) {
                 continue;
             }
 
@@ -103,19 +117,16 @@ impl<'t, Mapper: FieldIdMapper> Document<'t> for DocumentFromDb<'t, Mapper> {
         self.field(RESERVED_GEO_FIELD_NAME)
     }
 
+    fn geojson_field(&self) -> Result<Option<&'t RawValue>> {
+        self.field(RESERVED_GEOJSON_FIELD_NAME)
+    }
+
     fn top_level_fields_count(&self) -> usize {
-        let has_vectors_field = self.vectors_field().unwrap_or(None).is_some();
-        let has_geo_field = self.geo_field().unwrap_or(None).is_some();
-        let count = self.content.iter().count();
-        match (has_vectors_field, has_geo_field) {
-            (true, true) => count - 2,
-            (true, false) | (false, true) => count - 1,
-            (false, false) => count,
-        }
+        self.content.iter().count() - reserved_fields_count(self)
     }
 
     fn top_level_field(&self, k: &str) -> Result<Option<&'t RawValue>> {
-        if k == RESERVED_VECTORS_FIELD_NAME || k == RESERVED_GEO_FIELD_NAME {
+        if is_reserved_field(k) {
             return Ok(None);
         }
         self.field(k)
@@ -167,15 +178,12 @@ impl<'doc> Document<'doc> for DocumentFromVersions<'_, 'doc> {
         Ok(self.versions.geo_field())
     }
 
+    fn geojson_field(&self) -> Result<Option<&'doc RawValue>> {
+        Ok(self.versions.geojson_field())
+    }
+
     fn top_level_fields_count(&self) -> usize {
-        let has_vectors_field = self.vectors_field().unwrap_or(None).is_some();
-        let has_geo_field = self.geo_field().unwrap_or(None).is_some();
-        let count = self.versions.len();
-        match (has_vectors_field, has_geo_field) {
-            (true, true) => count - 2,
-            (true, false) | (false, true) => count - 1,
-            (false, false) => count,
-        }
+        self.versions.len() - reserved_fields_count(self)
     }
 
     fn top_level_field(&self, k: &str) -> Result<Option<&'doc RawValue>> {
@@ -255,6 +263,16 @@ impl<'d, 'doc: 'd, 't: 'd, Mapper: FieldIdMapper> Document<'d>
         db.geo_field()
     }
 
+    fn geojson_field(&self) -> Result<Option<&'d RawValue>> {
+        if let Some(geojson) = self.new_doc.geojson_field()? {
+            return Ok(Some(geojson));
+        }
+
+        let Some(db) = self.db else { return Ok(None) };
+
+        db.geojson_field()
+    }
+
     fn top_level_fields_count(&self) -> usize {
         self.iter_top_level_fields().count()
     }
@@ -270,6 +288,14 @@ impl<'d, 'doc: 'd, 't: 'd, Mapper: FieldIdMapper> Document<'d>
     }
 }
 
+/// Returns the number of `_vectors`, `_geo` and `_geojson` fields present in the document.
+fn reserved_fields_count<'doc>(document: &impl Document<'doc>) -> usize {
+    [document.vectors_field(), document.geo_field(), document.geojson_field()]
+        .into_iter()
+        .filter(|field| matches!(field, Ok(Some(_))))
+        .count()
+}
+
 impl<'doc, D> Document<'doc> for &D
 where
     D: Document<'doc>,
@@ -286,6 +312,10 @@ where
         D::geo_field(self)
     }
 
+    fn geojson_field(&self) -> Result<Option<&'doc RawValue>> {
+        D::geojson_field(self)
+    }
+
     fn top_level_fields_count(&self) -> usize {
         D::top_level_fields_count(self)
     }
@@ -375,6 +405,13 @@ where
         unordered_field_buffer.push((fid, geo_value));
     }
 
+    if let Some(geojson_value) = document.geojson_field()? {
+        let fid = fields_ids_map
+            .id_or_insert(RESERVED_GEOJSON_FIELD_NAME)
+            .ok_or(UserError::AttributeLimitReached)?;
+        unordered_field_buffer.push((fid, geojson_value));
+    }
+
     unordered_field_buffer.sort_by_key(|(fid, _)| *fid);
     for (fid, value) in unordered_field_buffer.iter() {
         writer.insert(*fid, value.get().as_bytes()).unwrap();
@@ -411,9 +448,7 @@ impl<'doc> Versions<'doc> {
     }
 
     pub fn iter_top_level_fields(&self) -> impl Iterator<Item = (&'doc str, &'doc RawValue)> + '_ {
-        self.data
-            .iter()
-            .filter(|(k, _)| *k != RESERVED_VECTORS_FIELD_NAME && *k != RESERVED_GEO_FIELD_NAME)
+        self.data.iter().filter(|(k, _)| !is_reserved_field(k))
     }
 
     pub fn vectors_field(&self) -> Option<&'doc RawValue> {
@@ -424,6 +459,10 @@ impl<'doc> Versions<'doc> {
         self.data.get(RESERVED_GEO_FIELD_NAME)
     }
 
+    pub fn geojson_field(&self) -> Option<&'doc RawValue> {
+        self.data.get(RESERVED_GEOJSON_FIELD_NAME)
+    }
+
     pub fn len(&self) -> usize {
         self.data.len()
     }
@@ -433,7 +472,7 @@ impl<'doc> Versions<'doc> {
     }
 
     pub fn top_level_field(&self, k: &str) -> Option<&'doc RawValue> {
-        if k == RESERVED_VECTORS_FIELD_NAME || k == RESERVED_GEO_FIELD_NAME {
+        if is_reserved_field(k) {
             return None;
         }
         self.data.get(k)
@@ -4,7 +4,7 @@ use bumpalo::Bump;
 use hashbrown::HashMap;
 
 use super::DelAddRoaringBitmap;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::update::new::channel::DocumentsSender;
 use crate::update::new::document::{write_to_obkv, Document as _};
 use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
@@ -67,7 +67,11 @@ impl<'extractor> Extractor<'extractor> for DocumentsExtractor<'_, '_> {
                         .geo_field()
                         .transpose()
                         .map(|res| res.map(|rv| (RESERVED_GEO_FIELD_NAME, rv)));
-                    for res in content.iter_top_level_fields().chain(geo_iter) {
+                    let geojson_iter = content
+                        .geojson_field()
+                        .transpose()
+                        .map(|res| res.map(|rv| (RESERVED_GEOJSON_FIELD_NAME, rv)));
+                    for res in content.iter_top_level_fields().chain(geo_iter).chain(geojson_iter) {
                         let (f, _) = res?;
                         let entry = document_extractor_data
                             .field_distribution_delta
@@ -86,7 +90,11 @@ impl<'extractor> Extractor<'extractor> for DocumentsExtractor<'_, '_> {
                         .geo_field()
                         .transpose()
                         .map(|res| res.map(|rv| (RESERVED_GEO_FIELD_NAME, rv)));
-                    for res in content.iter_top_level_fields().chain(geo_iter) {
+                    let geojson_iter = content
+                        .geojson_field()
+                        .transpose()
+                        .map(|res| res.map(|rv| (RESERVED_GEOJSON_FIELD_NAME, rv)));
+                    for res in content.iter_top_level_fields().chain(geo_iter).chain(geojson_iter) {
                         let (f, _) = res?;
                         let entry = document_extractor_data
                             .field_distribution_delta
@@ -100,7 +108,11 @@ impl<'extractor> Extractor<'extractor> for DocumentsExtractor<'_, '_> {
                         .geo_field()
                         .transpose()
                         .map(|res| res.map(|rv| (RESERVED_GEO_FIELD_NAME, rv)));
-                    for res in content.iter_top_level_fields().chain(geo_iter) {
+                    let geojson_iter = content
+                        .geojson_field()
+                        .transpose()
+                        .map(|res| res.map(|rv| (RESERVED_GEOJSON_FIELD_NAME, rv)));
+                    for res in content.iter_top_level_fields().chain(geo_iter).chain(geojson_iter) {
                         let (f, _) = res?;
                         let entry = document_extractor_data
                             .field_distribution_delta
@@ -133,7 +145,11 @@ impl<'extractor> Extractor<'extractor> for DocumentsExtractor<'_, '_> {
                         .geo_field()
                         .transpose()
                         .map(|res| res.map(|rv| (RESERVED_GEO_FIELD_NAME, rv)));
-                    for res in content.iter_top_level_fields().chain(geo_iter) {
+                    let geojson_iter = content
+                        .geojson_field()
+                        .transpose()
+                        .map(|res| res.map(|rv| (RESERVED_GEOJSON_FIELD_NAME, rv)));
+                    for res in content.iter_top_level_fields().chain(geo_iter).chain(geojson_iter) {
                         let (f, _) = res?;
                         let entry = document_extractor_data
                             .field_distribution_delta
@@ -248,6 +248,22 @@ impl FacetedDocidsExtractor {
         buffer.extend_from_slice(&fid.to_be_bytes());
         cache_fn(cached_sorter, &buffer, docid)?;
 
+        // Datetimes are indexed as numbers of seconds since the Unix epoch,
+        // the strings that are not valid datetimes are kept as strings.
+        let timestamp;
+        let value = match value {
+            Value::String(s) if features.is_datetime() => {
+                match crate::datetime::parse_datetime(s) {
+                    Some(seconds) => {
+                        timestamp = Value::from(seconds);
+                        &timestamp
+                    }
+                    None => value,
+                }
+            }
+            value => value,
+        };
+
         match value {
             // Number
             // key: fid - level - orderedf64 - originalf64
@@ -0,0 +1,239 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Seek as _, Write as _};
+use std::{iter, mem};
+
+use bumpalo::Bump;
+use heed::RoTxn;
+
+use crate::update::new::document::Document;
+use crate::update::new::indexer::document_changes::{DocumentChangeContext, Extractor};
+use crate::update::new::ref_cell_ext::RefCellExt as _;
+use crate::update::new::thread_local::MostlySend;
+use crate::update::new::DocumentChange;
+use crate::update::GrenadParameters;
+use crate::{DocumentId, GeoJsonEnvelope, GeoJsonShape, Index, InternalError, Result};
+
+pub struct GeoJsonExtractor {
+    grenad_parameters: GrenadParameters,
+}
+
+impl GeoJsonExtractor {
+    pub fn new(
+        rtxn: &RoTxn,
+        index: &Index,
+        grenad_parameters: GrenadParameters,
+    ) -> Result<Option<Self>> {
+        if index.is_geojson_filtering_enabled(rtxn)? {
+            Ok(Some(GeoJsonExtractor { grenad_parameters }))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// The shapes extracted by the [`GeoJsonExtractor`].
+///
+/// The entries are `(DocumentId, GeoJsonShape)` tuples serialized with bincode,
+/// the shapes are stored in the geojson rtree to avoid reading the documents at search time.
+pub struct GeoJsonExtractorData<'extractor> {
+    /// The entries of the rtree that must be removed. If a document sees its
+    /// shape being updated, we first put it in the removed and then in the inserted.
+    removed: bumpalo::collections::Vec<'extractor, u8>,
+    inserted: bumpalo::collections::Vec<'extractor, u8>,
+    /// Contains the removed entries if we have spilled to disk.
+    spilled_removed: Option<BufWriter<File>>,
+    /// Contains the inserted entries if we have spilled to disk.
+    spilled_inserted: Option<BufWriter<File>>,
+}
+
+impl<'extractor> GeoJsonExtractorData<'extractor> {
+    fn new_in(extractor_alloc: &'extractor Bump) -> Self {
+        GeoJsonExtractorData {
+            removed: bumpalo::collections::Vec::new_in(extractor_alloc),
+            inserted: bumpalo::collections::Vec::new_in(extractor_alloc),
+            spilled_removed: None,
+            spilled_inserted: None,
+        }
+    }
+
+    /// Spills the next entries to disk if the extractor allocated too much memory.
+    fn spill_if_needed(&mut self, allocated_bytes: usize, max_memory: Option<usize>) -> Result<()> {
+        if self.spilled_removed.is_none() && max_memory.is_some_and(|mm| allocated_bytes >= mm) {
+            // We must spill as we allocated too much memory
+            self.spilled_removed = tempfile::tempfile().map(BufWriter::new).map(Some)?;
+            self.spilled_inserted = tempfile::tempfile().map(BufWriter::new).map(Some)?;
+        }
+        Ok(())
+    }
+
+    fn push_removed(&mut self, docid: DocumentId, shape: &GeoJsonShape) -> Result<()> {
+        push_entry(&mut self.removed, &mut self.spilled_removed, docid, shape)
+    }
+
+    fn push_inserted(&mut self, docid: DocumentId, shape: &GeoJsonShape) -> Result<()> {
+        push_entry(&mut self.inserted, &mut self.spilled_inserted, docid, shape)
+    }
+
+    pub fn freeze(self) -> Result<FrozenGeoJsonExtractorData<'extractor>> {
+        let GeoJsonExtractorData { removed, inserted, spilled_removed, spilled_inserted } = self;
+
+        Ok(FrozenGeoJsonExtractorData {
+            removed: removed.into_bump_slice(),
+            inserted: inserted.into_bump_slice(),
+            spilled_removed: spilled_removed
+                .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
+                .transpose()?,
+            spilled_inserted: spilled_inserted
+                .map(|bw| bw.into_inner().map(BufReader::new).map_err(|iie| iie.into_error()))
+                .transpose()?,
+        })
+    }
+}
+
+unsafe impl MostlySend for GeoJsonExtractorData<'_> {}
+
+fn push_entry(
+    entries: &mut bumpalo::collections::Vec<'_, u8>,
+    spilled: &mut Option<BufWriter<File>>,
+    docid: DocumentId,
+    shape: &GeoJsonShape,
+) -> Result<()> {
+    let entry = bincode::serialize(&(docid, shape)).map_err(InternalError::BincodeError)?;
+    match spilled {
+        Some(file) => file.write_all(&entry)?,
+        None => entries.extend_from_slice(&entry),
+    }
+    Ok(())
+}
+
+pub struct FrozenGeoJsonExtractorData<'extractor> {
+    pub removed: &'extractor [u8],
+    pub inserted: &'extractor [u8],
+    pub spilled_removed: Option<BufReader<File>>,
+    pub spilled_inserted: Option<BufReader<File>>,
+}
+
+impl FrozenGeoJsonExtractorData<'_> {
+    pub fn iter_and_clear_removed(
+        &mut self,
+    ) -> Result<impl Iterator<Item = Result<GeoJsonEnvelope>> + '_> {
+        let spilled = rewind_spilled_entries(&mut self.spilled_removed)?;
+        Ok(read_entries(mem::take(&mut self.removed))
+            .chain(spilled.into_iter().flat_map(read_entries)))
+    }
+
+    pub fn iter_and_clear_inserted(
+        &mut self,
+    ) -> Result<impl Iterator<Item = Result<GeoJsonEnvelope>> + '_> {
+        let spilled = rewind_spilled_entries(&mut self.spilled_inserted)?;
+        Ok(read_entries(mem::take(&mut self.inserted))
+            .chain(spilled.into_iter().flat_map(read_entries)))
+    }
+}
+
+fn rewind_spilled_entries(
+    spilled: &mut Option<BufReader<File>>,
+) -> Result<Option<BufReader<File>>> {
+    let mut spilled = spilled.take();
+    if let Some(spilled) = &mut spilled {
+        spilled.rewind()?;
+    }
+    Ok(spilled)
+}
+
+/// Reads the bincode serialized entries until the end of the reader.
+fn read_entries(mut reader: impl BufRead) -> impl Iterator<Item = Result<GeoJsonEnvelope>> {
+    iter::from_fn(move || {
+        match reader.fill_buf() {
+            Ok(buffer) if buffer.is_empty() => return None,
+            Ok(_) => (),
+            Err(e) => return Some(Err(e.into())),
+        }
+        let entry = bincode::deserialize_from::<_, (DocumentId, GeoJsonShape)>(&mut reader);
+        Some(match entry {
+            Ok((docid, shape)) => Ok(shape.into_envelope(docid)),
+            Err(e) => Err(InternalError::BincodeError(e).into()),
+        })
+    })
+}
+
+impl<'extractor> Extractor<'extractor> for GeoJsonExtractor {
+    type Data = RefCell<GeoJsonExtractorData<'extractor>>;
+
+    fn init_data<'doc>(&'doc self, extractor_alloc: &'extractor Bump) -> Result<Self::Data> {
+        Ok(RefCell::new(GeoJsonExtractorData::new_in(extractor_alloc)))
+    }
+
+    fn process<'doc>(
+        &'doc self,
+        changes: impl Iterator<Item = Result<DocumentChange<'doc>>>,
+        context: &'doc DocumentChangeContext<Self::Data>,
+    ) -> Result<()> {
+        let rtxn = &context.rtxn;
+        let index = context.index;
+        let max_memory = self.grenad_parameters.max_memory_by_thread();
+        let db_fields_ids_map = context.db_fields_ids_map;
+        let mut data_ref = context.data.borrow_mut_or_yield();
+
+        for change in changes {
+            data_ref.spill_if_needed(context.extractor_alloc.allocated_bytes(), max_memory)?;
+
+            match change? {
+                DocumentChange::Deletion(deletion) => {
+                    let docid = deletion.docid();
+                    let external_id = deletion.external_document_id();
+                    let current = deletion.current(rtxn, index, db_fields_ids_map)?;
+
+                    if let Some(shape) = extract_geojson_shape(external_id, current)? {
+                        data_ref.push_removed(docid, &shape)?;
+                    }
+                }
+                DocumentChange::Update(update) => {
+                    let current = update.current(rtxn, index, db_fields_ids_map)?;
+                    let external_id = update.external_document_id();
+                    let docid = update.docid();
+
+                    let current_shape = extract_geojson_shape(external_id, current)?;
+                    let updated_shape = extract_geojson_shape(
+                        external_id,
+                        update.merged(rtxn, index, db_fields_ids_map)?,
+                    )?;
+
+                    if current_shape != updated_shape {
+                        if let Some(shape) = &current_shape {
+                            data_ref.push_removed(docid, shape)?;
+                        }
+
+                        if let Some(shape) = &updated_shape {
+                            data_ref.push_inserted(docid, shape)?;
+                        }
+                    }
+                }
+                DocumentChange::Insertion(insertion) => {
+                    let external_id = insertion.external_document_id();
+                    let docid = insertion.docid();
+
+                    let inserted = insertion.inserted();
+                    if let Some(shape) = extract_geojson_shape(external_id, inserted)? {
+                        data_ref.push_inserted(docid, &shape)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Extracts and validates the shape of the `_geojson` field of a document.
+fn extract_geojson_shape<'doc>(
+    external_id: &str,
+    document: impl Document<'doc>,
+) -> Result<Option<GeoJsonShape>> {
+    let Some(raw_value) = document.geojson_field()? else {
+        return Ok(None);
+    };
+    let value = serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)?;
+    GeoJsonShape::from_value(external_id, value)
+}
@@ -4,7 +4,7 @@ use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Seek as _, Write as _
 use std::{iter, mem, result};
 
 use bumpalo::Bump;
-use bytemuck::{bytes_of, pod_read_unaligned, Pod, Zeroable};
+use bytemuck::{bytes_of, bytes_of_mut, Pod, Zeroable};
 use heed::RoTxn;
 use serde_json::value::RawValue;
 use serde_json::Value;
@@ -18,6 +18,10 @@ use crate::update::new::DocumentChange;
 use crate::update::GrenadParameters;
 use crate::{lat_lng_to_xyz, DocumentId, GeoPoint, Index, InternalError, Result};
 
+mod geojson;
+
+pub use geojson::{FrozenGeoJsonExtractorData, GeoJsonExtractor, GeoJsonExtractorData};
+
 pub struct GeoExtractor {
     grenad_parameters: GrenadParameters,
 }
@@ -53,21 +57,58 @@ impl From<ExtractedGeoPoint> for GeoPoint {
     }
 }
 
-pub struct GeoExtractorData<'extractor> {
+/// The data extracted by the geo extractors, `T` is the packed representation of
+/// the entries of the rtree, the geo points by default.
+pub struct GeoExtractorData<'extractor, T = ExtractedGeoPoint> {
     /// The set of documents ids that were removed. If a document sees its geo
     /// point being updated, we first put it in the deleted and then in the inserted.
-    removed: bumpalo::collections::Vec<'extractor, ExtractedGeoPoint>,
-    inserted: bumpalo::collections::Vec<'extractor, ExtractedGeoPoint>,
-    /// Contains a packed list of `ExtractedGeoPoint` of the inserted geo points
-    /// data structures if we have spilled to disk.
+    removed: bumpalo::collections::Vec<'extractor, T>,
+    inserted: bumpalo::collections::Vec<'extractor, T>,
+    /// Contains a packed list of `T` of the removed rtree entries
+    /// if we have spilled to disk.
     spilled_removed: Option<BufWriter<File>>,
-    /// Contains a packed list of `ExtractedGeoPoint` of the inserted geo points
-    /// data structures if we have spilled to disk.
+    /// Contains a packed list of `T` of the inserted rtree entries
+    /// if we have spilled to disk.
     spilled_inserted: Option<BufWriter<File>>,
 }
 
-impl<'extractor> GeoExtractorData<'extractor> {
-    pub fn freeze(self) -> Result<FrozenGeoExtractorData<'extractor>> {
+impl<'extractor, T: Pod> GeoExtractorData<'extractor, T> {
+    fn new_in(extractor_alloc: &'extractor Bump) -> Self {
+        GeoExtractorData {
+            removed: bumpalo::collections::Vec::new_in(extractor_alloc),
+            inserted: bumpalo::collections::Vec::new_in(extractor_alloc),
+            spilled_inserted: None,
+            spilled_removed: None,
+        }
+    }
+
+    /// Spills the next entries to disk if the extractor allocated too much memory.
+    fn spill_if_needed(&mut self, allocated_bytes: usize, max_memory: Option<usize>) -> Result<()> {
+        if self.spilled_removed.is_none() && max_memory.is_some_and(|mm| allocated_bytes >= mm) {
+            // We must spill as we allocated too much memory
+            self.spilled_removed = tempfile::tempfile().map(BufWriter::new).map(Some)?;
+            self.spilled_inserted = tempfile::tempfile().map(BufWriter::new).map(Some)?;
+        }
+        Ok(())
+    }
+
+    fn push_removed(&mut self, entry: T) -> Result<()> {
+        match &mut self.spilled_removed {
+            Some(file) => file.write_all(bytes_of(&entry))?,
+            None => self.removed.push(entry),
+        }
+        Ok(())
+    }
+
+    fn push_inserted(&mut self, entry: T) -> Result<()> {
+        match &mut self.spilled_inserted {
+            Some(file) => file.write_all(bytes_of(&entry))?,
+            None => self.inserted.push(entry),
+        }
+        Ok(())
+    }
+
+    pub fn freeze(self) -> Result<FrozenGeoExtractorData<'extractor, T>> {
         let GeoExtractorData { removed, inserted, spilled_removed, spilled_inserted } = self;
 
         Ok(FrozenGeoExtractorData {
@@ -83,40 +124,40 @@ impl<'extractor> GeoExtractorData<'extractor> {
     }
 }
 
-unsafe impl MostlySend for GeoExtractorData<'_> {}
+unsafe impl<T> MostlySend for GeoExtractorData<'_, T> {}
 
-pub struct FrozenGeoExtractorData<'extractor> {
-    pub removed: &'extractor [ExtractedGeoPoint],
-    pub inserted: &'extractor [ExtractedGeoPoint],
+pub struct FrozenGeoExtractorData<'extractor, T = ExtractedGeoPoint> {
+    pub removed: &'extractor [T],
+    pub inserted: &'extractor [T],
     pub spilled_removed: Option<BufReader<File>>,
     pub spilled_inserted: Option<BufReader<File>>,
 }
 
-impl FrozenGeoExtractorData<'_> {
+impl<T: Pod> FrozenGeoExtractorData<'_, T> {
     pub fn iter_and_clear_removed(
         &mut self,
-    ) -> io::Result<impl IntoIterator<Item = io::Result<ExtractedGeoPoint>> + '_> {
+    ) -> io::Result<impl IntoIterator<Item = io::Result<T>> + '_> {
         Ok(mem::take(&mut self.removed)
             .iter()
             .copied()
             .map(Ok)
-            .chain(iterator_over_spilled_geopoints(&mut self.spilled_removed)?))
+            .chain(iterator_over_spilled_entries(&mut self.spilled_removed)?))
     }
 
     pub fn iter_and_clear_inserted(
         &mut self,
-    ) -> io::Result<impl IntoIterator<Item = io::Result<ExtractedGeoPoint>> + '_> {
+    ) -> io::Result<impl IntoIterator<Item = io::Result<T>> + '_> {
         Ok(mem::take(&mut self.inserted)
             .iter()
             .copied()
             .map(Ok)
-            .chain(iterator_over_spilled_geopoints(&mut self.spilled_inserted)?))
+            .chain(iterator_over_spilled_entries(&mut self.spilled_inserted)?))
     }
 }
 
-fn iterator_over_spilled_geopoints(
+fn iterator_over_spilled_entries<T: Pod>(
     spilled: &mut Option<BufReader<File>>,
-) -> io::Result<impl IntoIterator<Item = io::Result<ExtractedGeoPoint>> + '_> {
+) -> io::Result<impl IntoIterator<Item = io::Result<T>> + '_> {
     let mut spilled = spilled.take();
     if let Some(spilled) = &mut spilled {
         spilled.rewind()?;
@@ -124,9 +165,9 @@ fn iterator_over_spilled_geopoints(
 
     Ok(iter::from_fn(move || match &mut spilled {
         Some(file) => {
-            let geopoint_bytes = &mut [0u8; mem::size_of::<ExtractedGeoPoint>()];
-            match file.read_exact(geopoint_bytes) {
-                Ok(()) => Some(Ok(pod_read_unaligned(geopoint_bytes))),
+            let mut entry = T::zeroed();
+            match file.read_exact(bytes_of_mut(&mut entry)) {
+                Ok(()) => Some(Ok(entry)),
                 Err(e) if e.kind() == ErrorKind::UnexpectedEof => None,
                 Err(e) => Some(Err(e)),
             }
@@ -139,12 +180,7 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
     type Data = RefCell<GeoExtractorData<'extractor>>;
 
     fn init_data<'doc>(&'doc self, extractor_alloc: &'extractor Bump) -> Result<Self::Data> {
-        Ok(RefCell::new(GeoExtractorData {
-            removed: bumpalo::collections::Vec::new_in(extractor_alloc),
-            inserted: bumpalo::collections::Vec::new_in(extractor_alloc),
-            spilled_inserted: None,
-            spilled_removed: None,
-        }))
+        Ok(RefCell::new(GeoExtractorData::new_in(extractor_alloc)))
     }
 
     fn process<'doc>(
@@ -159,13 +195,7 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
         let mut data_ref = context.data.borrow_mut_or_yield();
 
         for change in changes {
-            if data_ref.spilled_removed.is_none()
-                && max_memory.is_some_and(|mm| context.extractor_alloc.allocated_bytes() >= mm)
-            {
-                // We must spill as we allocated too much memory
-                data_ref.spilled_removed = tempfile::tempfile().map(BufWriter::new).map(Some)?;
-                data_ref.spilled_inserted = tempfile::tempfile().map(BufWriter::new).map(Some)?;
-            }
+            data_ref.spill_if_needed(context.extractor_alloc.allocated_bytes(), max_memory)?;
 
             match change? {
                 DocumentChange::Deletion(deletion) => {
@@ -175,14 +205,12 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let current_geo = current
                         .geo_field()?
                         .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
+                        .transpose()?
+                        .unwrap_or_default();
 
-                    if let Some(lat_lng) = current_geo.flatten() {
+                    for lat_lng in current_geo {
                         let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                        match &mut data_ref.spilled_removed {
-                            Some(file) => file.write_all(bytes_of(&geopoint))?,
-                            None => data_ref.removed.push(geopoint),
-                        }
+                        data_ref.push_removed(geopoint)?;
                     }
                 }
                 DocumentChange::Update(update) => {
@@ -193,32 +221,28 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                     let current_geo = current
                         .geo_field()?
                         .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
+                        .transpose()?
+                        .unwrap_or_default();
 
                     let updated_geo = update
                         .merged(rtxn, index, db_fields_ids_map)?
                         .geo_field()?
                         .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
+                        .transpose()?
+                        .unwrap_or_default();
 
                     if current_geo != updated_geo {
                         // If the current and new geo points are different it means that
-                        // we need to replace the current by the new point and therefore
-                        // delete the current point from the RTree.
-                        if let Some(lat_lng) = current_geo.flatten() {
+                        // we need to replace the current by the new points and therefore
+                        // delete all the current points from the RTree.
+                        for lat_lng in current_geo {
                             let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                            match &mut data_ref.spilled_removed {
-                                Some(file) => file.write_all(bytes_of(&geopoint))?,
-                                None => data_ref.removed.push(geopoint),
-                            }
+                            data_ref.push_removed(geopoint)?;
                         }
 
-                        if let Some(lat_lng) = updated_geo.flatten() {
+                        for lat_lng in updated_geo {
                             let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                            match &mut data_ref.spilled_inserted {
-                                Some(file) => file.write_all(bytes_of(&geopoint))?,
-                                None => data_ref.inserted.push(geopoint),
-                            }
+                            data_ref.push_inserted(geopoint)?;
                         }
                     }
                 }
@@ -230,14 +254,12 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
                         .inserted()
                         .geo_field()?
                         .map(|geo| extract_geo_coordinates(external_id, geo))
-                        .transpose()?;
+                        .transpose()?
+                        .unwrap_or_default();
 
-                    if let Some(lat_lng) = inserted_geo.flatten() {
+                    for lat_lng in inserted_geo {
                         let geopoint = ExtractedGeoPoint { docid, lat_lng };
-                        match &mut data_ref.spilled_inserted {
-                            Some(file) => file.write_all(bytes_of(&geopoint))?,
-                            None => data_ref.inserted.push(geopoint),
-                        }
+                        data_ref.push_inserted(geopoint)?;
                     }
                 }
             }
@@ -247,15 +269,23 @@ impl<'extractor> Extractor<'extractor> for GeoExtractor {
     }
 }
 
-/// Extracts and validates the latitude and latitude from a document geo field.
+/// Extracts and validates the latitudes and longitudes from a document geo field.
 ///
-/// It can be of the form `{ "lat": 0.0, "lng": "1.0" }`.
-pub fn extract_geo_coordinates(
-    external_id: &str,
-    raw_value: &RawValue,
-) -> Result<Option<[f64; 2]>> {
-    let mut geo = match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
-        Value::Null => return Ok(None),
+/// It can be of the form `{ "lat": 0.0, "lng": "1.0" }` or an array of such
+/// objects when the document has multiple locations.
+pub fn extract_geo_coordinates(external_id: &str, raw_value: &RawValue) -> Result<Vec<[f64; 2]>> {
+    match serde_json::from_str(raw_value.get()).map_err(InternalError::SerdeJson)? {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(values) => {
+            values.into_iter().map(|value| extract_geo_point(external_id, value)).collect()
+        }
+        value => extract_geo_point(external_id, value).map(|point| vec![point]),
+    }
+}
+
+/// Extracts and validates the latitude and longitude of a single geo point.
+fn extract_geo_point(external_id: &str, value: Value) -> Result<[f64; 2]> {
+    let mut geo = match value {
         Value::Object(map) => map,
         value => {
             return Err(Box::new(GeoError::NotAnObject {
@@ -299,7 +329,7 @@ pub fn extract_geo_coordinates(
     };
 
     match (extract_finite_float_from_value(lat), extract_finite_float_from_value(lng)) {
-        (Ok(lat), Ok(lng)) => Ok(Some([lat, lng])),
+        (Ok(lat), Ok(lng)) => Ok([lat, lng]),
         (Ok(_), Err(value)) => {
             Err(Box::new(GeoError::BadLongitude { document_id: Value::from(external_id), value })
                 .into())
@@ -3,6 +3,7 @@ use std::cell::RefCell;
 use bumpalo::collections::Vec as BVec;
 use bumpalo::Bump;
 use hashbrown::{DefaultHashBuilder, HashMap};
+use itertools::Itertools;
 
 use super::cache::DelAddRoaringBitmap;
 use crate::error::FaultSource;
@@ -15,6 +16,7 @@ use crate::update::new::DocumentChange;
 use crate::vector::error::{
     EmbedErrorKind, PossibleEmbeddingMistakes, UnusedVectorsDistributionBump,
 };
+use crate::vector::sparse::merge_max;
 use crate::vector::{Embedder, Embedding, EmbeddingConfigs};
 use crate::{DocumentId, FieldDistribution, InternalError, Result, ThreadPoolNoAbort, UserError};
 
@@ -271,6 +273,7 @@ impl<'extractor> Extractor<'extractor> for EmbeddingExtractor<'_, '_> {
 struct Chunks<'a, 'b, 'extractor> {
     texts: BVec<'a, &'a str>,
     ids: BVec<'a, DocumentId>,
+    capacity: usize,
 
     embedder: &'a Embedder,
     embedder_id: u8,
@@ -304,6 +307,7 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         Self {
             texts,
             ids,
+            capacity,
             embedder,
             prompt,
             possible_embedding_mistakes,
@@ -329,24 +333,38 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             self.has_manual_generation.get_or_insert(external_docid);
         }
 
-        if self.texts.len() < self.texts.capacity() {
+        // all the chunks of a document must be embedded together, as they are sent at once
+        let chunks = self.prompt.chunk_ranges(rendered);
+        let chunk_count = chunks.as_ref().map_or(1, Vec::len);
+        if !self.texts.is_empty() && self.texts.len() + chunk_count > self.capacity {
+            Self::embed_chunks(
+                &mut self.texts,
+                &mut self.ids,
+                self.embedder,
+                self.embedder_id,
+                self.embedder_name,
+                self.possible_embedding_mistakes,
+                unused_vectors_distribution,
+                self.threads,
+                self.sender,
+                self.has_manual_generation.take(),
+            )?;
+        }
+
+        let Some(chunks) = chunks else {
             self.texts.push(rendered);
             self.ids.push(docid);
             return Ok(());
+        };
+        // each chunk becomes a vector of the document, unless the embeddings are merged or flattened
+        if !self.embedder.is_sparse() && !self.embedder.is_multi_vector() {
+            self.sender.set_chunks(docid, self.embedder_id, &chunks)?;
         }
-
-        Self::embed_chunks(
-            &mut self.texts,
-            &mut self.ids,
-            self.embedder,
-            self.embedder_id,
-            self.embedder_name,
-            self.possible_embedding_mistakes,
-            unused_vectors_distribution,
-            self.threads,
-            self.sender,
-            self.has_manual_generation.take(),
-        )
+        for range in chunks {
+            self.texts.push(&rendered[range]);
+            self.ids.push(docid);
+        }
+        Ok(())
     }
 
     pub fn drain(
@@ -421,13 +439,37 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
             return Err(crate::Error::UserError(crate::UserError::DocumentEmbeddingError(msg)));
         }
 
-        let res = match embedder.embed_index_ref(texts.as_slice(), threads) {
-            Ok(embeddings) => {
-                for (docid, embedding) in ids.into_iter().zip(embeddings) {
-                    sender.set_vector(*docid, embedder_id, embedding).unwrap();
+        let embedded = if embedder.is_sparse() {
+            embedder.embed_index_ref_sparse(texts.as_slice(), threads).map(|embeddings| {
+                // the chunks of a document are contiguous and are merged into a single sparse embedding
+                let embeddings = ids.iter().zip(embeddings).chunk_by(|(docid, _)| **docid);
+                for (docid, embeddings) in &embeddings {
+                    let embedding = merge_max(embeddings.map(|(_, embedding)| embedding));
+                    sender.set_sparse_vector(docid, embedder_id, &embedding).unwrap();
                 }
-                Ok(())
-            }
+            })
+        } else if embedder.is_multi_vector() {
+            embedder.embed_index_ref_multi(texts.as_slice(), threads).map(|embeddings| {
+                // the embeddings of all the chunks of a document become the vectors of the document
+                let embeddings = ids.iter().zip(embeddings).chunk_by(|(docid, _)| **docid);
+                for (docid, embeddings) in &embeddings {
+                    let embeddings = embeddings.flat_map(|(_, embeddings)| embeddings).collect();
+                    sender.set_vectors(docid, embedder_id, embeddings).unwrap();
+                }
+            })
+        } else {
+            embedder.embed_index_ref(texts.as_slice(), threads).map(|embeddings| {
+                // the chunks of a document are contiguous and become the vectors of the document
+                let embeddings = ids.iter().zip(embeddings).chunk_by(|(docid, _)| **docid);
+                for (docid, embeddings) in &embeddings {
+                    let embeddings = embeddings.map(|(_, embedding)| embedding).collect();
+                    sender.set_vectors(docid, embedder_id, embeddings).unwrap();
+                }
+            })
+        };
+
+        let res = match embedded {
+            Ok(()) => Ok(()),
             Err(error) => {
                 if let FaultSource::Bug = error.fault {
                     Err(crate::Error::InternalError(crate::InternalError::VectorEmbeddingError(
@@ -501,6 +543,20 @@ impl<'a, 'b, 'extractor> Chunks<'a, 'b, 'extractor> {
         docid: DocumentId,
         embeddings: Vec<Embedding>,
     ) -> Result<()> {
+        if self.embedder.is_sparse() {
+            if embeddings.is_empty() {
+                self.sender.delete_sparse_vector(docid, self.embedder_id).unwrap();
+                return Ok(());
+            }
+            return Err(UserError::InvalidVectorsEmbedderConf {
+                document_id: external_docid.to_string(),
+                error: format!(
+                    "`.embedders.{}` produces sparse embeddings, which cannot be provided in `_vectors`.\n  - Hint: opt-out for this document with `_vectors.{}: null`, or let the embedder generate the embedding with `regenerate: true`",
+                    self.embedder_name, self.embedder_name
+                ),
+            }
+            .into());
+        }
         for (embedding_index, embedding) in embeddings.iter().enumerate() {
             if embedding.len() != self.dimensions {
                 return Err(UserError::InvalidIndexingVectorDimensions {
@@ -16,7 +16,7 @@ use crate::index::IndexEmbeddingConfig;
 use crate::progress::MergingWordCache;
 use crate::proximity::ProximityPrecision;
 use crate::update::new::extract::EmbeddingExtractor;
-use crate::update::new::merger::merge_and_send_rtree;
+use crate::update::new::merger::{merge_and_send_geojson_rtree, merge_and_send_rtree};
 use crate::update::new::{merge_and_send_docids, merge_and_send_facet_docids, FacetDatabases};
 use crate::vector::EmbeddingConfigs;
 use crate::{Result, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
@@ -109,7 +109,10 @@ where
         }
     }
 
-    {
+    // the words of the documents are not indexed when the keyword search is disabled
+    let keyword_search = index.keyword_search(&rtxn)?;
+
+    if keyword_search {
         let WordDocidsCaches {
             word_docids,
             word_fid_docids,
@@ -207,7 +210,7 @@ where
     // run the proximity extraction only if the precision is by word
     // this works only if the settings didn't change during this transaction.
     let proximity_precision = index.proximity_precision(&rtxn)?.unwrap_or_default();
-    if proximity_precision == ProximityPrecision::ByWord {
+    if keyword_search && proximity_precision == ProximityPrecision::ByWord {
         let caches = {
             let span = tracing::trace_span!(target: "indexing::documents::extract", "word_pair_proximity_docids");
             let _entered = span.enter();
@@ -306,6 +309,37 @@ where
             &indexing_context.must_stop_processing,
         )?;
     }
+
+    'geojson: {
+        let Some(extractor) =
+            GeoJsonExtractor::new(&rtxn, index, *indexing_context.grenad_parameters)?
+        else {
+            break 'geojson;
+        };
+        let datastore = ThreadLocal::with_capacity(rayon::current_num_threads());
+
+        {
+            let span = tracing::trace_span!(target: "indexing::documents::extract", "geojson");
+            let _entered = span.enter();
+
+            extract(
+                document_changes,
+                &extractor,
+                indexing_context,
+                extractor_allocs,
+                &datastore,
+                IndexingStep::WritingGeoPoints,
+            )?;
+        }
+
+        merge_and_send_geojson_rtree(
+            datastore,
+            &rtxn,
+            index,
+            extractor_sender.geo(),
+            &indexing_context.must_stop_processing,
+        )?;
+    }
     indexing_context.progress.update_progress(IndexingStep::WaitingForDatabaseWrites);
     finished_extraction.store(true, std::sync::atomic::Ordering::Relaxed);
 
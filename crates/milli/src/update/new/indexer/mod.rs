@@ -164,7 +164,6 @@ where
 
         let global_fields_ids_map = GlobalFieldsIdsMap::new(&new_fields_ids_map);
 
-        let vector_arroy = index.vector_arroy;
         let arroy_writers: Result<HashMap<_, _>> = embedders
             .inner_as_ref()
             .iter()
@@ -177,7 +176,7 @@ where
                 )?;
 
                 let dimensions = embedder.dimensions();
-                let writer = ArroyWrapper::new(vector_arroy, embedder_index, *was_quantized);
+                let writer = ArroyWrapper::new(index, embedder_index, *was_quantized);
 
                 Ok((
                     embedder_index,
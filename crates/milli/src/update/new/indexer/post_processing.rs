@@ -42,6 +42,10 @@ where
         indexing_context.progress,
     )?;
     compute_facet_search_database(index, wtxn, global_fields_ids_map, indexing_context.progress)?;
+    // there are no words to post-process when the keyword search is disabled
+    if !index.keyword_search(wtxn)? {
+        return Ok(());
+    }
     indexing_context.progress.update_progress(IndexingStep::PostProcessingWords);
     if let Some(prefix_delta) = compute_word_fst(index, wtxn, indexing_context.progress)? {
         compute_prefix_database(
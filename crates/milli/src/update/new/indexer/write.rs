@@ -2,7 +2,7 @@ use std::sync::atomic::AtomicBool;
 
 use bstr::ByteSlice as _;
 use hashbrown::HashMap;
-use heed::RwTxn;
+use heed::{BytesDecode as _, RwTxn};
 use rand::SeedableRng as _;
 use time::OffsetDateTime;
 
@@ -10,10 +10,14 @@ use super::super::channel::*;
 use crate::database_stats::DatabaseStats;
 use crate::documents::PrimaryKey;
 use crate::fields_ids_map::metadata::FieldIdMapWithMetadata;
+use crate::heed_codec::U8BEU32Codec;
 use crate::index::IndexEmbeddingConfig;
 use crate::progress::Progress;
 use crate::update::settings::InnerIndexSettings;
-use crate::vector::{ArroyWrapper, Embedder, EmbeddingConfigs, Embeddings};
+use crate::vector::chunking::ChunkStore;
+use crate::vector::{
+    ArroyWrapper, Embedder, EmbeddingConfigs, Embeddings, SparseEmbedding, SparseVectorStore,
+};
 use crate::{Error, Index, InternalError, Result, UserError};
 
 pub fn write_to_db(
@@ -39,6 +43,11 @@ pub fn write_to_db(
 
         match action {
             ReceiverAction::WakeUp => (),
+            ReceiverAction::LargeEntry(LargeEntry {
+                database: Database::VectorSparseDocuments,
+                key,
+                value,
+            }) => write_sparse_embedding(index, wtxn, &key, Some(&value[..]))?,
             ReceiverAction::LargeEntry(LargeEntry { database, key, value }) => {
                 let database_name = database.database_name();
                 let database = database.database(index);
@@ -117,14 +126,18 @@ where
 
     let seed = rand::random();
     let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
-    for (_index, (_embedder_name, _embedder, writer, dimensions)) in arroy_writers {
+    for (_index, (_embedder_name, embedder, writer, dimensions)) in arroy_writers {
+        // sparse embeddings are not stored in arroy
+        if embedder.is_sparse() {
+            continue;
+        }
         let dimensions = *dimensions;
         writer.build_and_quantize(
             wtxn,
             progress,
             &mut rng,
             dimensions,
-            false,
+            None,
             arroy_memory,
             must_stop_processing,
         )?;
@@ -171,6 +184,13 @@ pub fn write_from_bbqueue(
 ) -> crate::Result<()> {
     while let Some(frame_with_header) = writer_receiver.recv_frame() {
         match frame_with_header.header() {
+            EntryHeader::DbOperation(operation)
+                if matches!(operation.database, Database::VectorSparseDocuments) =>
+            {
+                let frame = frame_with_header.frame();
+                let (key, value) = operation.key_value(frame);
+                write_sparse_embedding(index, wtxn, key, value)?;
+            }
             EntryHeader::DbOperation(operation) => {
                 let database_name = operation.database.database_name();
                 let database = operation.database.database(index);
@@ -207,7 +227,12 @@ pub fn write_from_bbqueue(
                 }
             }
             EntryHeader::ArroyDeleteVector(ArroyDeleteVector { docid }) => {
-                for (_index, (_name, _embedder, writer, dimensions)) in arroy_writers {
+                for (index_id, (_name, embedder, writer, dimensions)) in arroy_writers {
+                    ChunkStore::new(index, *index_id).del_chunks(wtxn, docid)?;
+                    if embedder.is_sparse() {
+                        SparseVectorStore::new(index, *index_id).del_item(wtxn, docid)?;
+                        continue;
+                    }
                     let dimensions = *dimensions;
                     writer.del_items(wtxn, dimensions, docid)?;
                 }
@@ -233,3 +258,26 @@ pub fn write_from_bbqueue(
 
     Ok(())
 }
+
+/// Sparse embeddings are sent as entries of the sparse documents database,
+/// but must go through the [`SparseVectorStore`] to keep the posting lists up to date.
+fn write_sparse_embedding(
+    index: &Index,
+    wtxn: &mut RwTxn<'_>,
+    key: &[u8],
+    value: Option<&[u8]>,
+) -> Result<()> {
+    let (embedder_id, docid) = U8BEU32Codec::bytes_decode(key).map_err(heed::Error::Decoding)?;
+    let store = SparseVectorStore::new(index, embedder_id);
+    match value {
+        Some(value) => {
+            let embedding: SparseEmbedding =
+                serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+            store.add_item(wtxn, docid, &embedding)?;
+        }
+        None => {
+            store.del_item(wtxn, docid)?;
+        }
+    }
+    Ok(())
+}
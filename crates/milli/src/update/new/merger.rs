@@ -10,7 +10,7 @@ use roaring::RoaringBitmap;
 use super::channel::*;
 use super::extract::{
     merge_caches_sorted, transpose_and_freeze_caches, BalancedCaches, DelAddRoaringBitmap,
-    FacetKind, GeoExtractorData,
+    FacetKind, GeoExtractorData, GeoJsonExtractorData,
 };
 use crate::update::facet::new_incremental::FacetFieldIdChange;
 use crate::{CboRoaringBitmapCodec, FieldId, GeoPoint, Index, InternalError, Result};
@@ -39,15 +39,15 @@ where
             let extracted_geo_point = result?;
             let removed = rtree.remove(&GeoPoint::from(extracted_geo_point));
             debug_assert!(removed.is_some());
-            let removed = faceted.remove(extracted_geo_point.docid);
-            debug_assert!(removed);
+            // a document can have multiple geo points, it is
+            // removed from the faceted documents with its first one.
+            faceted.remove(extracted_geo_point.docid);
         }
 
         for result in frozen.iter_and_clear_inserted()? {
             let extracted_geo_point = result?;
             rtree.insert(GeoPoint::from(extracted_geo_point));
-            let inserted = faceted.insert(extracted_geo_point.docid);
-            debug_assert!(inserted);
+            faceted.insert(extracted_geo_point.docid);
         }
     }
 
@@ -62,6 +62,45 @@ where
     Ok(())
 }
 
+#[tracing::instrument(level = "trace", skip_all, target = "indexing::merge")]
+pub fn merge_and_send_geojson_rtree<'extractor, MSP>(
+    datastore: impl IntoIterator<Item = RefCell<GeoJsonExtractorData<'extractor>>>,
+    rtxn: &RoTxn,
+    index: &Index,
+    geo_sender: GeoSender<'_, '_>,
+    must_stop_processing: &MSP,
+) -> Result<()>
+where
+    MSP: Fn() -> bool + Sync,
+{
+    let mut rtree = index.geojson_rtree(rtxn)?.unwrap_or_default();
+
+    for data in datastore {
+        if must_stop_processing() {
+            return Err(InternalError::AbortedIndexation.into());
+        }
+
+        let mut frozen = data.into_inner().freeze()?;
+        for result in frozen.iter_and_clear_removed()? {
+            let removed = rtree.remove(&result?);
+            debug_assert!(removed.is_some());
+        }
+
+        for result in frozen.iter_and_clear_inserted()? {
+            rtree.insert(result?);
+        }
+    }
+
+    let mut file = tempfile::tempfile()?;
+    bincode::serialize_into(&mut file, &rtree).map_err(InternalError::BincodeError)?;
+    file.sync_all()?;
+
+    let rtree_mmap = unsafe { Mmap::map(&file)? };
+    geo_sender.set_geojson_rtree(rtree_mmap).unwrap();
+
+    Ok(())
+}
+
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::merge")]
 pub fn merge_and_send_docids<'extractor, MSP, D>(
     mut caches: Vec<BalancedCaches<'extractor>>,
@@ -23,7 +23,9 @@ pub fn field_distribution(index: &Index, wtxn: &mut RwTxn<'_>, progress: &Progre
             continue;
         };
         let geo_iter = document.geo_field().transpose().map(|res| res.map(|rv| ("_geo", rv)));
-        for res in document.iter_top_level_fields().chain(geo_iter) {
+        let geojson_iter =
+            document.geojson_field().transpose().map(|res| res.map(|rv| ("_geojson", rv)));
+        for res in document.iter_top_level_fields().chain(geo_iter).chain(geojson_iter) {
             let (field_name, _) = res?;
             if let Some(count) = distribution.get_mut(field_name) {
                 *count += 1;
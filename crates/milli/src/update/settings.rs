@@ -8,6 +8,7 @@ use charabia::{Normalize, Tokenizer, TokenizerBuilder};
 use deserr::{DeserializeError, Deserr};
 use itertools::{merge_join_by, EitherOrBoth, Itertools};
 use roaring::RoaringBitmap;
+use rstar::RTree;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use time::OffsetDateTime;
 
@@ -15,9 +16,10 @@ use super::del_add::{DelAdd, DelAddOperation};
 use super::index_documents::{IndexDocumentsConfig, Transform};
 use super::IndexerConfig;
 use crate::attribute_patterns::PatternMatch;
-use crate::constants::RESERVED_GEO_FIELD_NAME;
+use crate::constants::{RESERVED_GEOJSON_FIELD_NAME, RESERVED_GEO_FIELD_NAME};
 use crate::criterion::Criterion;
-use crate::error::UserError;
+use crate::database_stats::DatabaseStats;
+use crate::error::{InternalError, UserError};
 use crate::fields_ids_map::metadata::{FieldIdMapWithMetadata, MetadataBuilder};
 use crate::filterable_attributes_rules::match_faceted_field;
 use crate::index::{
@@ -25,16 +27,22 @@ use crate::index::{
     DEFAULT_MIN_WORD_LEN_TWO_TYPOS,
 };
 use crate::order_by_map::OrderByMap;
-use crate::prompt::default_max_bytes;
+use crate::prompt::{default_max_bytes, Fragment};
 use crate::proximity::ProximityPrecision;
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
+use crate::vector::rerank::RerankerSettings;
 use crate::vector::settings::{
-    EmbedderAction, EmbedderSource, EmbeddingSettings, NestingContext, ReindexAction,
-    SubEmbeddingSettings, WriteBackToDocuments,
+    vector_quantization, EmbedderAction, EmbedderSource, EmbeddingSettings, NestingContext,
+    ReindexAction, SubEmbeddingSettings, WriteBackToDocuments,
+};
+use crate::vector::{
+    Embedder, EmbeddingConfig, EmbeddingConfigs, PersistentEmbeddingCache, VectorQuantization,
+};
+use crate::{
+    Error, FieldId, Filter, FilterableAttributesRule, GeoJsonShape, Index, LocalizedAttributesRule,
+    QueryRule, Result,
 };
-use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
-use crate::{FieldId, FilterableAttributesRule, Index, LocalizedAttributesRule, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -180,9 +188,12 @@ pub struct Settings<'a, 't, 'i> {
     proximity_precision: Setting<ProximityPrecision>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
     search_cutoff: Setting<u64>,
+    query_rules: Setting<Vec<QueryRule>>,
+    rerankers: Setting<BTreeMap<String, RerankerSettings>>,
     localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
     prefix_search: Setting<PrefixSearch>,
     facet_search: Setting<bool>,
+    keyword_search: Setting<bool>,
 }
 
 impl<'a, 't, 'i> Settings<'a, 't, 'i> {
@@ -217,9 +228,12 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             proximity_precision: Setting::NotSet,
             embedder_settings: Setting::NotSet,
             search_cutoff: Setting::NotSet,
+            query_rules: Setting::NotSet,
+            rerankers: Setting::NotSet,
             localized_attributes_rules: Setting::NotSet,
             prefix_search: Setting::NotSet,
             facet_search: Setting::NotSet,
+            keyword_search: Setting::NotSet,
             indexer_config,
         }
     }
@@ -418,6 +432,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.search_cutoff = Setting::Reset;
     }
 
+    pub fn set_query_rules(&mut self, value: Vec<QueryRule>) {
+        self.query_rules = Setting::Set(value);
+    }
+
+    pub fn reset_query_rules(&mut self) {
+        self.query_rules = Setting::Reset;
+    }
+
+    pub fn set_rerankers(&mut self, value: BTreeMap<String, RerankerSettings>) {
+        self.rerankers = Setting::Set(value);
+    }
+
+    pub fn reset_rerankers(&mut self) {
+        self.rerankers = Setting::Reset;
+    }
+
     pub fn set_localized_attributes_rules(&mut self, value: Vec<LocalizedAttributesRule>) {
         self.localized_attributes_rules = Setting::Set(value);
     }
@@ -442,6 +472,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.facet_search = Setting::Reset;
     }
 
+    pub fn set_keyword_search(&mut self, value: bool) {
+        self.keyword_search = Setting::Set(value);
+    }
+
+    pub fn reset_keyword_search(&mut self) {
+        self.keyword_search = Setting::Reset;
+    }
+
     #[tracing::instrument(
         level = "trace"
         skip(self, progress_callback, should_abort, settings_diff),
@@ -490,6 +528,38 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    /// Rebuilds the geojson rtree from the documents when the `_geojson` field becomes filterable
+    /// and deletes it when it is not filterable anymore.
+    fn reindex_geojson(&mut self) -> Result<()> {
+        self.index.delete_geojson_rtree(self.wtxn)?;
+        if !self.index.is_geojson_filtering_enabled(self.wtxn)? {
+            return Ok(());
+        }
+
+        let fields_ids_map = self.index.fields_ids_map(self.wtxn)?;
+        let Some(geojson_fid) = fields_ids_map.id(RESERVED_GEOJSON_FIELD_NAME) else {
+            return Ok(());
+        };
+
+        let mut envelopes = Vec::new();
+        for result in self.index.all_documents(self.wtxn)? {
+            let (docid, document) = result?;
+            let Some(value) = document.get(geojson_fid) else {
+                continue;
+            };
+            let value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+            let external_id = self.index.external_id_of(self.wtxn, [docid])?.into_iter().next();
+            let external_id = external_id.transpose()?.unwrap_or_default();
+            if let Some(shape) = GeoJsonShape::from_value(&external_id, value)? {
+                envelopes.push(shape.into_envelope(docid));
+            }
+        }
+
+        self.index.put_geojson_rtree(self.wtxn, &RTree::bulk_load(envelopes))?;
+
+        Ok(())
+    }
+
     fn update_displayed(&mut self) -> Result<bool> {
         match self.displayed_fields {
             Setting::Set(ref fields) => {
@@ -994,6 +1064,38 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changed)
     }
 
+    fn update_keyword_search(&mut self) -> Result<bool> {
+        let old = self.index.keyword_search(self.wtxn)?;
+        match self.keyword_search {
+            Setting::Set(new) => self.index.put_keyword_search(self.wtxn, new)?,
+            Setting::Reset => {
+                self.index.delete_keyword_search(self.wtxn)?;
+            }
+            Setting::NotSet => return Ok(false),
+        }
+        let new = self.index.keyword_search(self.wtxn)?;
+        if old == new {
+            return Ok(false);
+        }
+
+        if new {
+            // the words are extracted again by the reindexing
+            self.index.delete_keyword_search_saved_stats(self.wtxn)?;
+        } else {
+            // the words will never be used, we drop them right away instead of reindexing the documents
+            let databases = self.index.keyword_search_databases();
+            let stats = DatabaseStats::from_databases(databases, self.wtxn)?;
+            self.index.put_keyword_search_saved_stats(self.wtxn, stats)?;
+            for database in databases {
+                database.clear(self.wtxn)?;
+            }
+            self.index.put_words_fst(self.wtxn, &fst::Set::default())?;
+            self.index.put_words_prefixes_fst(self.wtxn, &fst::Set::default())?;
+        }
+
+        Ok(true)
+    }
+
     fn update_embedding_configs(&mut self) -> Result<BTreeMap<String, EmbedderAction>> {
         match std::mem::take(&mut self.embedder_settings) {
             Setting::Set(configs) => self.update_embedding_configs_set(configs),
@@ -1014,7 +1116,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                             name,
                             EmbedderAction::with_write_back(
                                 WriteBackToDocuments { embedder_id, user_provided },
-                                config.quantized(),
+                                config.quantization(),
                             ),
                         ))
                     })
@@ -1052,7 +1154,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             match joined {
                 // updated config
                 EitherOrBoth::Both((name, (old, user_provided)), (_, new)) => {
-                    let was_quantized = old.binary_quantized.set().unwrap_or_default();
+                    let was_quantized = vector_quantization(
+                        &old.binary_quantized,
+                        &old.quantization,
+                        &old.rescoring,
+                    );
                     let settings_diff = SettingsDiff::from_settings(&name, old, new)?;
                     match settings_diff {
                         SettingsDiff::Remove => {
@@ -1105,7 +1211,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                             if quantize {
                                 embedder_actions.insert(
                                     name.clone(),
-                                    EmbedderAction::default().with_is_being_quantized(true),
+                                    EmbedderAction { was_quantized, ..Default::default() }
+                                        .with_is_being_quantized(true),
                                 );
                             }
                             updated_configs.insert(name, (new, user_provided));
@@ -1128,7 +1235,10 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                     let setting = validate_embedding_settings(setting, &name)?;
                     embedder_actions.insert(
                         name.clone(),
-                        EmbedderAction::with_reindex(ReindexAction::FullReindex, false),
+                        EmbedderAction::with_reindex(
+                            ReindexAction::FullReindex,
+                            VectorQuantization::default(),
+                        ),
                     );
                     updated_configs.insert(name, (setting, RoaringBitmap::new()));
                 }
@@ -1193,6 +1303,59 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(changed)
     }
 
+    fn update_query_rules(&mut self) -> Result<()> {
+        match &self.query_rules {
+            Setting::Set(new) => {
+                for (index, rule) in new.iter().enumerate() {
+                    let Some(filter) = &rule.filter else { continue };
+                    match Filter::from_str(filter) {
+                        Ok(_) => (),
+                        Err(Error::UserError(UserError::InvalidFilter(error))) => {
+                            return Err(UserError::InvalidQueryRuleFilter { index, error }.into())
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                let old = self.index.query_rules(self.wtxn)?;
+                if old.as_ref() != Some(new) {
+                    self.index.put_query_rules(self.wtxn, new.clone())?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_query_rules(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_rerankers(&mut self) -> Result<()> {
+        match &self.rerankers {
+            Setting::Set(new) => {
+                for (reranker_name, settings) in new {
+                    if let Err(message) = settings.to_config() {
+                        return Err(UserError::InvalidSettingsReranker {
+                            reranker_name: reranker_name.clone(),
+                            message,
+                        }
+                        .into());
+                    }
+                }
+                let old = self.index.rerankers(self.wtxn)?;
+                if old.as_ref() != Some(new) {
+                    self.index.put_rerankers(self.wtxn, new.clone())?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_rerankers(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     fn update_localized_attributes_rules(&mut self) -> Result<()> {
         match &self.localized_attributes_rules {
             Setting::Set(new) => {
@@ -1231,6 +1394,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_query_rules()?;
+        self.update_rerankers()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
@@ -1245,11 +1410,18 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_proximity_precision()?;
         self.update_prefix_search()?;
         self.update_facet_search()?;
+        self.update_keyword_search()?;
         self.update_localized_attributes_rules()?;
 
         let embedding_config_updates = self.update_embedding_configs()?;
 
-        let mut new_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn, None)?;
+        // the reindexing embeds the documents with the new embedders, using the persistent cache
+        let embedders = embedders(
+            self.index.embedding_configs(self.wtxn)?,
+            self.indexer_config.embedding_cache.as_ref(),
+        )?;
+        let mut new_inner_settings =
+            InnerIndexSettings::from_index(self.index, self.wtxn, Some(embedders))?;
         new_inner_settings.recompute_searchables(self.wtxn, self.index)?;
 
         let primary_key_id = self
@@ -1265,10 +1437,15 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             settings_update_only,
         );
 
+        let run_geojson_indexing = inner_settings_diff.run_geojson_indexing();
         if inner_settings_diff.any_reindexing_needed() {
             self.reindex(&progress_callback, &should_abort, inner_settings_diff)?;
         }
 
+        if run_geojson_indexing {
+            self.reindex_geojson()?;
+        }
+
         Ok(())
     }
 }
@@ -1325,6 +1502,7 @@ impl InnerIndexSettingsDiff {
                 || old_settings.dictionary != new_settings.dictionary
                 || old_settings.proximity_precision != new_settings.proximity_precision
                 || old_settings.prefix_search != new_settings.prefix_search
+                || old_settings.keyword_search != new_settings.keyword_search
                 || old_settings.localized_attributes_rules
                     != new_settings.localized_attributes_rules
         };
@@ -1349,11 +1527,14 @@ impl InnerIndexSettingsDiff {
 
         // if the user-defined searchables changed, then we need to reindex prompts.
         if cache_user_defined_searchables {
-            for (embedder_name, (config, _, _quantized)) in
+            for (embedder_name, (config, _, _quantization)) in
                 new_settings.embedding_configs.inner_as_ref()
             {
-                let was_quantized =
-                    old_settings.embedding_configs.get(embedder_name).is_some_and(|conf| conf.2);
+                let was_quantized = old_settings
+                    .embedding_configs
+                    .get(embedder_name)
+                    .map(|conf| conf.2)
+                    .unwrap_or_default();
                 // skip embedders that don't use document templates
                 if !config.uses_document_template() {
                     continue;
@@ -1398,12 +1579,23 @@ impl InnerIndexSettingsDiff {
     }
 
     pub fn reindex_searchable(&self) -> bool {
+        // the words are not extracted when the keyword search is disabled,
+        // the keyword search databases are cleared by the settings update instead
+        if !self.new.keyword_search {
+            return false;
+        }
+
         self.cache_reindex_searchable_without_user_defined
             || self.cache_exact_attributes
             || self.cache_user_defined_searchables
     }
 
     pub fn reindex_proximities(&self) -> bool {
+        // the proximities must be computed from scratch when the keyword search is enabled again
+        if !self.old.keyword_search {
+            return self.new.keyword_search;
+        }
+
         // if any searchable settings force the reindexing
         (self.cache_reindex_searchable_without_user_defined || self.cache_user_defined_searchables)
         // and if any settings needs the proximity database created
@@ -1412,7 +1604,10 @@ impl InnerIndexSettingsDiff {
     }
 
     pub fn reindex_searchable_id(&self, id: FieldId) -> Option<DelAddOperation> {
-        if self.cache_reindex_searchable_without_user_defined || self.cache_exact_attributes {
+        if !self.new.keyword_search {
+            None
+        } else if self.cache_reindex_searchable_without_user_defined || self.cache_exact_attributes
+        {
             Some(DelAddOperation::DeletionAndAddition)
         } else if let Some(only_additional_fields) = &self.only_additional_fields {
             let additional_field = self.new.fields_ids_map.name(id).unwrap();
@@ -1483,6 +1678,14 @@ impl InnerIndexSettingsDiff {
                     if old_facet_level_database != new_facet_level_database {
                         return true;
                     }
+
+                    // Check if the field is a datetime in the old and new settings.
+                    // If there is a difference, its values must be reindexed as numbers or strings.
+                    if old_filterable_features.is_datetime()
+                        != new_filterable_features.is_datetime()
+                    {
+                        return true;
+                    }
                 }
             }
         }
@@ -1495,6 +1698,11 @@ impl InnerIndexSettingsDiff {
             || self.old.facet_search != self.new.facet_search
     }
 
+    /// Returns `true` if the field is a datetime in only one of the old and new settings.
+    pub fn datetime_changed(&self, field_id: FieldId) -> bool {
+        self.old.is_datetime(field_id) != self.new.is_datetime(field_id)
+    }
+
     pub fn reindex_facets(&self) -> bool {
         self.facet_fids_changed() || self.global_facet_settings_changed()
     }
@@ -1511,6 +1719,10 @@ impl InnerIndexSettingsDiff {
         self.old.geo_fields_ids != self.new.geo_fields_ids
             || (!self.settings_update_only && self.new.geo_fields_ids.is_some())
     }
+
+    pub fn run_geojson_indexing(&self) -> bool {
+        self.old.geojson_filtering_enabled != self.new.geojson_filtering_enabled
+    }
 }
 
 #[derive(Clone)]
@@ -1529,8 +1741,10 @@ pub(crate) struct InnerIndexSettings {
     pub proximity_precision: ProximityPrecision,
     pub embedding_configs: EmbeddingConfigs,
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
+    pub geojson_filtering_enabled: bool,
     pub prefix_search: PrefixSearch,
     pub facet_search: bool,
+    pub keyword_search: bool,
 }
 
 impl InnerIndexSettings {
@@ -1548,10 +1762,11 @@ impl InnerIndexSettings {
         let proximity_precision = index.proximity_precision(rtxn)?.unwrap_or_default();
         let embedding_configs = match embedding_configs {
             Some(embedding_configs) => embedding_configs,
-            None => embedders(index.embedding_configs(rtxn)?)?,
+            None => embedders(index.embedding_configs(rtxn)?, None)?,
         };
         let prefix_search = index.prefix_search(rtxn)?.unwrap_or_default();
         let facet_search = index.facet_search(rtxn)?;
+        let keyword_search = index.keyword_search(rtxn)?;
         let geo_fields_ids = match fields_ids_map.id(RESERVED_GEO_FIELD_NAME) {
             Some(_) if index.is_geo_enabled(rtxn)? => {
                 // if `_geo` is faceted then we get the `lat` and `lng`
@@ -1563,6 +1778,7 @@ impl InnerIndexSettings {
             }
             _ => None,
         };
+        let geojson_filtering_enabled = index.is_geojson_filtering_enabled(rtxn)?;
         let localized_attributes_rules =
             index.localized_attributes_rules(rtxn)?.unwrap_or_default();
         let filterable_attributes_rules = index.filterable_attributes_rules(rtxn)?;
@@ -1590,8 +1806,10 @@ impl InnerIndexSettings {
             proximity_precision,
             embedding_configs,
             geo_fields_ids,
+            geojson_filtering_enabled,
             prefix_search,
             facet_search,
+            keyword_search,
         })
     }
 
@@ -1605,6 +1823,13 @@ impl InnerIndexSettings {
         )
     }
 
+    /// Returns `true` if the string values of the field must be indexed as datetimes.
+    pub fn is_datetime(&self, field_id: FieldId) -> bool {
+        self.fields_ids_map.metadata(field_id).is_some_and(|metadata| {
+            metadata.filterable_attributes_features(&self.filterable_attributes_rules).is_datetime()
+        })
+    }
+
     // find and insert the new field ids
     pub fn recompute_searchables(
         &mut self,
@@ -1629,26 +1854,25 @@ impl InnerIndexSettings {
     }
 }
 
-fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingConfigs> {
+fn embedders(
+    embedding_configs: Vec<IndexEmbeddingConfig>,
+    persistent_cache: Option<&PersistentEmbeddingCache>,
+) -> Result<EmbeddingConfigs> {
     let res: Result<_> = embedding_configs
         .into_iter()
-        .map(
-            |IndexEmbeddingConfig {
-                 name,
-                 config: EmbeddingConfig { embedder_options, prompt, quantized },
-                 ..
-             }| {
-                let prompt = Arc::new(prompt.try_into().map_err(crate::Error::from)?);
-
-                let embedder = Arc::new(
-                    // cache_cap: no cache needed for indexing purposes
-                    Embedder::new(embedder_options.clone(), 0)
-                        .map_err(crate::vector::Error::from)
-                        .map_err(crate::Error::from)?,
-                );
-                Ok((name, (embedder, prompt, quantized.unwrap_or_default())))
-            },
-        )
+        .map(|IndexEmbeddingConfig { name, config, .. }| {
+            let quantization = config.quantization();
+            let EmbeddingConfig { embedder_options, prompt, .. } = config;
+            let prompt = Arc::new(prompt.try_into().map_err(crate::Error::from)?);
+
+            let embedder = Arc::new(
+                // cache_cap: no in-memory cache needed for indexing purposes
+                Embedder::new(embedder_options.clone(), 0, persistent_cache)
+                    .map_err(crate::vector::Error::from)
+                    .map_err(crate::Error::from)?,
+            );
+            Ok((name, (embedder, prompt, quantization)))
+        })
         .collect();
     res.map(EmbeddingConfigs::new)
 }
@@ -1694,6 +1918,7 @@ pub fn validate_embedding_settings(
         model,
         revision,
         pooling,
+        normalize,
         api_key,
         dimensions,
         document_template,
@@ -1706,10 +1931,36 @@ pub fn validate_embedding_settings(
         distribution,
         headers,
         binary_quantized: binary_quantize,
+        quantization,
+        rescoring,
+        chunking,
+        sparse,
+        multi_vector,
+        indexing_fragment,
+        search_fragment,
     } = settings;
 
     let document_template = validate_prompt(name, document_template, document_template_max_bytes)?;
 
+    if let Some(fragment) = indexing_fragment.as_ref().set() {
+        Fragment::for_documents(fragment.to_owned()).map_err(|inner| {
+            UserError::InvalidFragmentForEmbeddings {
+                embedder_name: name.to_owned(),
+                field: "indexingFragment",
+                inner,
+            }
+        })?;
+    }
+    if let Some(fragment) = search_fragment.as_ref().set() {
+        Fragment::for_query(fragment.to_owned()).map_err(|inner| {
+            UserError::InvalidFragmentForEmbeddings {
+                embedder_name: name.to_owned(),
+                field: "searchFragment",
+                inner,
+            }
+        })?;
+    }
+
     if let Some(0) = dimensions.set() {
         return Err(crate::error::UserError::InvalidSettingsDimensions {
             embedder_name: name.to_owned(),
@@ -1741,6 +1992,7 @@ pub fn validate_embedding_settings(
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             document_template,
@@ -1753,6 +2005,13 @@ pub fn validate_embedding_settings(
             distribution,
             headers,
             binary_quantized: binary_quantize,
+            quantization,
+            rescoring,
+            chunking,
+            sparse,
+            multi_vector,
+            indexing_fragment,
+            search_fragment,
         }));
     };
     EmbeddingSettings::check_settings(
@@ -1762,6 +2021,7 @@ pub fn validate_embedding_settings(
         &model,
         &revision,
         &pooling,
+        &normalize,
         &dimensions,
         &api_key,
         &url,
@@ -1773,7 +2033,14 @@ pub fn validate_embedding_settings(
         &search_embedder,
         &indexing_embedder,
         &binary_quantize,
+        &quantization,
+        &rescoring,
         &distribution,
+        &chunking,
+        &sparse,
+        &multi_vector,
+        &indexing_fragment,
+        &search_fragment,
     )?;
     match inferred_source {
         EmbedderSource::OpenAi => {
@@ -1810,7 +2077,8 @@ pub fn validate_embedding_settings(
         EmbedderSource::Ollama
         | EmbedderSource::HuggingFace
         | EmbedderSource::UserProvided
-        | EmbedderSource::Rest => {}
+        | EmbedderSource::Rest
+        | EmbedderSource::Onnx => {}
         EmbedderSource::Composite => {
             if let Setting::Set(embedder) = &search_embedder {
                 if let Some(source) = embedder.source.set() {
@@ -1840,6 +2108,7 @@ pub fn validate_embedding_settings(
                         &embedder.model,
                         &embedder.revision,
                         &embedder.pooling,
+                        &embedder.normalize,
                         &embedder.dimensions,
                         &embedder.api_key,
                         &embedder.url,
@@ -1851,7 +2120,14 @@ pub fn validate_embedding_settings(
                         &search_embedder,
                         &indexing_embedder,
                         &embedder.binary_quantized,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
                         &embedder.distribution,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
                     )?;
                 } else {
                     return Err(UserError::MissingSourceForNested {
@@ -1895,6 +2171,7 @@ pub fn validate_embedding_settings(
                         &embedder.model,
                         &embedder.revision,
                         &embedder.pooling,
+                        &embedder.normalize,
                         &embedder.dimensions,
                         &embedder.api_key,
                         &embedder.url,
@@ -1906,7 +2183,14 @@ pub fn validate_embedding_settings(
                         &search_embedder,
                         &indexing_embedder,
                         &embedder.binary_quantized,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
                         &embedder.distribution,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
+                        &Setting::NotSet,
                     )?;
                 } else {
                     return Err(UserError::MissingSourceForNested {
@@ -1925,6 +2209,7 @@ pub fn validate_embedding_settings(
         model,
         revision,
         pooling,
+        normalize,
         api_key,
         dimensions,
         document_template,
@@ -1937,6 +2222,13 @@ pub fn validate_embedding_settings(
         distribution,
         headers,
         binary_quantized: binary_quantize,
+        quantization,
+        rescoring,
+        chunking,
+        sparse,
+        multi_vector,
+        indexing_fragment,
+        search_fragment,
     }))
 }
 
@@ -521,6 +521,65 @@ fn set_and_reset_stop_words() {
     assert_eq!(result.documents_ids.len(), 1); // there is one benoit in our data
 }
 
+#[test]
+fn disable_and_enable_keyword_search() {
+    let index = TempIndex::new();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "name": "kevin", "maxim": "I love dogs" },
+            { "id": 1, "name": "kevina", "maxim": "Doggos are the best" },
+        ]))
+        .unwrap();
+
+    // disabling the keyword search drops the words of the documents
+    index
+        .update_settings(|settings| {
+            settings.set_keyword_search(false);
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    assert!(!index.keyword_search(&rtxn).unwrap());
+    assert!(index.word_docids.is_empty(&rtxn).unwrap());
+    assert!(index.word_pair_proximity_docids.is_empty(&rtxn).unwrap());
+    assert!(index.words_fst(&rtxn).unwrap().is_empty());
+    let saved_stats = index.keyword_search_saved_stats(&rtxn).unwrap().unwrap();
+    assert!(saved_stats.number_of_entries() > 0);
+    assert!(saved_stats.total_size() > 0);
+    drop(rtxn);
+
+    // the new documents are not tokenized either
+    index
+        .add_documents(documents!([
+            { "id": 2, "name": "benoit", "maxim": "The crepes are really good, not the dogs" },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    assert!(index.word_docids.is_empty(&rtxn).unwrap());
+    assert!(index.word_fid_docids.is_empty(&rtxn).unwrap());
+    let result = index.search(&rtxn).query("dog").execute().unwrap();
+    assert!(result.documents_ids.is_empty());
+    drop(rtxn);
+
+    // enabling it again extracts the words of all the documents
+    index
+        .update_settings(|settings| {
+            settings.reset_keyword_search();
+        })
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+    assert!(index.keyword_search(&rtxn).unwrap());
+    assert!(index.keyword_search_saved_stats(&rtxn).unwrap().is_none());
+    assert!(!index.word_pair_proximity_docids.is_empty(&rtxn).unwrap());
+    let result = index.search(&rtxn).query("dog").execute().unwrap();
+    assert_eq!(result.documents_ids.len(), 3);
+    let result = index.search(&rtxn).query("crepes").execute().unwrap();
+    assert_eq!(result.documents_ids, vec![2]);
+}
+
 #[test]
 fn set_and_reset_synonyms() {
     let index = TempIndex::new();
@@ -893,9 +952,12 @@ fn test_correct_settings_init() {
                 proximity_precision,
                 embedder_settings,
                 search_cutoff,
+                query_rules,
+                rerankers,
                 localized_attributes_rules,
                 prefix_search,
                 facet_search,
+                keyword_search,
             } = settings;
             assert!(matches!(searchable_fields, Setting::NotSet));
             assert!(matches!(displayed_fields, Setting::NotSet));
@@ -920,9 +982,12 @@ fn test_correct_settings_init() {
             assert!(matches!(proximity_precision, Setting::NotSet));
             assert!(matches!(embedder_settings, Setting::NotSet));
             assert!(matches!(search_cutoff, Setting::NotSet));
+            assert!(matches!(query_rules, Setting::NotSet));
+            assert!(matches!(rerankers, Setting::NotSet));
             assert!(matches!(localized_attributes_rules, Setting::NotSet));
             assert!(matches!(prefix_search, Setting::NotSet));
             assert!(matches!(facet_search, Setting::NotSet));
+            assert!(matches!(keyword_search, Setting::NotSet));
         })
         .unwrap();
 }
@@ -0,0 +1,208 @@
+use std::ops::Range;
+
+use deserr::Deserr;
+use heed::types::{Bytes, SerdeJson};
+use heed::{Database, RoTxn, RwTxn};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::heed_codec::U8BEU32Codec;
+use crate::{DocumentId, Index};
+
+/// The maximum number of chunks embedded for a single document.
+///
+/// Each chunk is stored as a separate vector of the document, and a document cannot have more than
+/// [`u8::MAX`] vectors per embedder. The text after the last chunk is not embedded.
+pub const MAX_CHUNKS: usize = u8::MAX as usize;
+
+/// Describes how the text of a document is split into several overlapping chunks,
+/// each of them being embedded as a separate vector of the document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct Chunking {
+    /// Maximum size of a chunk, in bytes.
+    pub size: usize,
+    /// Number of bytes shared by two consecutive chunks.
+    #[serde(default)]
+    #[deserr(default)]
+    pub overlap: usize,
+    /// Field whose value is chunked instead of the rendered document template.
+    #[serde(default)]
+    #[deserr(default)]
+    pub field: Option<String>,
+}
+
+impl Chunking {
+    /// Returns a description of the problem if the chunking cannot be applied.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.size == 0 {
+            return Err("`chunking.size` must be greater than 0".to_string());
+        }
+        if self.overlap >= self.size {
+            return Err(format!(
+                "`chunking.overlap` must be smaller than `chunking.size`, got overlap {} for size {}",
+                self.overlap, self.size
+            ));
+        }
+        Ok(())
+    }
+
+    /// Splits the text into the byte ranges of its chunks.
+    ///
+    /// Chunks are cut on char boundaries, preferably right after a whitespace when there is one
+    /// in the second half of the chunk. At least one chunk is always returned, and at most [`MAX_CHUNKS`].
+    pub fn chunks(&self, text: &str) -> Vec<Range<usize>> {
+        let size = self.size.max(1);
+        let mut chunks = Vec::new();
+        let mut start: usize = 0;
+
+        loop {
+            let mut end = floor_char_boundary(text, start.saturating_add(size));
+            if end <= start {
+                // the chunk is smaller than the next char, take the char entirely
+                end = ceil_char_boundary(text, start + 1);
+            }
+            if end < text.len() {
+                let half = start + (end - start) / 2;
+                if let Some((position, c)) =
+                    text[start..end].char_indices().rev().find(|(_, c)| c.is_whitespace())
+                {
+                    let cut = start + position + c.len_utf8();
+                    if cut > half {
+                        end = cut;
+                    }
+                }
+            }
+
+            chunks.push(start..end);
+            if end >= text.len() || chunks.len() == MAX_CHUNKS {
+                break;
+            }
+
+            let mut next = floor_char_boundary(text, end.saturating_sub(self.overlap));
+            if !text[..next].ends_with(char::is_whitespace) {
+                // avoid starting the chunk in the middle of a word
+                if let Some((position, c)) =
+                    text[next..end].char_indices().find(|(_, c)| c.is_whitespace())
+                {
+                    next += position + c.len_utf8();
+                }
+            }
+            start = if next > start && next < end { next } else { end };
+        }
+
+        chunks
+    }
+}
+
+/// Stores the byte ranges of the chunks of the documents of an embedder, as they were embedded.
+///
+/// The range at a given position is the chunk that was embedded as the vector of the same index,
+/// which avoids rendering the documents again to find the chunk a search matched.
+pub struct ChunkStore {
+    embedder_index: u8,
+    database: Database<U8BEU32Codec, SerdeJson<Vec<Range<usize>>>>,
+}
+
+impl ChunkStore {
+    pub fn new(index: &Index, embedder_index: u8) -> Self {
+        Self { embedder_index, database: index.vector_chunks }
+    }
+
+    pub fn chunks(
+        &self,
+        rtxn: &RoTxn,
+        docid: DocumentId,
+    ) -> heed::Result<Option<Vec<Range<usize>>>> {
+        self.database.get(rtxn, &(self.embedder_index, docid))
+    }
+
+    /// Overwrite the chunks of a document.
+    pub fn put_chunks(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        chunks: Vec<Range<usize>>,
+    ) -> heed::Result<()> {
+        self.database.put(wtxn, &(self.embedder_index, docid), &chunks)
+    }
+
+    /// Delete the chunks of a document, returning whether it had some.
+    pub fn del_chunks(&self, wtxn: &mut RwTxn, docid: DocumentId) -> heed::Result<bool> {
+        self.database.delete(wtxn, &(self.embedder_index, docid))
+    }
+
+    /// Delete the chunks of all the documents of the embedder.
+    pub fn clear(&self, wtxn: &mut RwTxn) -> heed::Result<()> {
+        let prefix = [self.embedder_index];
+        let mut iter = self.database.remap_key_type::<Bytes>().prefix_iter_mut(wtxn, &prefix)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference from the database.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn chunking(size: usize, overlap: usize) -> Chunking {
+        Chunking { size, overlap, field: None }
+    }
+
+    fn split<'t>(chunking: &Chunking, text: &'t str) -> Vec<&'t str> {
+        chunking.chunks(text).into_iter().map(|range| &text[range]).collect()
+    }
+
+    #[test]
+    fn chunks() {
+        let text = "the quick brown fox jumps over the lazy dog";
+        assert_eq!(split(&chunking(100, 0), text), vec![text]);
+        assert_eq!(
+            split(&chunking(16, 0), text),
+            vec!["the quick brown ", "fox jumps over ", "the lazy dog"]
+        );
+        assert_eq!(
+            split(&chunking(16, 6), text),
+            vec!["the quick brown ", "brown fox jumps ", "jumps over the ", "the lazy dog"]
+        );
+        // no whitespace in the second half of the chunk
+        assert_eq!(split(&chunking(4, 0), "abcdefghij"), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn chunks_edge_cases() {
+        assert_eq!(chunking(10, 0).chunks(""), vec![0..0]);
+        // chunks are cut on char boundaries, even when a char is larger than the chunk
+        assert_eq!(split(&chunking(3, 0), "éééé"), vec!["é", "é", "é", "é"]);
+        assert_eq!(split(&chunking(1, 0), "🐶🐱"), vec!["🐶", "🐱"]);
+        let long = "a".repeat(MAX_CHUNKS * 2);
+        assert_eq!(chunking(1, 0).chunks(&long).len(), MAX_CHUNKS);
+    }
+
+    #[test]
+    fn validate() {
+        assert!(chunking(10, 2).validate().is_ok());
+        assert!(chunking(0, 0).validate().is_err());
+        assert!(chunking(10, 10).validate().is_err());
+    }
+}
@@ -4,8 +4,8 @@ use arroy::Distance;
 
 use super::error::CompositeEmbedderContainsHuggingFace;
 use super::{
-    hf, manual, ollama, openai, rest, DistributionShift, EmbedError, Embedding, EmbeddingCache,
-    NewEmbedderError,
+    hf, manual, ollama, onnx, openai, rest, DistributionShift, EmbedError, Embedding,
+    EmbeddingCache, NewEmbedderError, PersistentEmbeddingCache,
 };
 use crate::ThreadPoolNoAbort;
 
@@ -21,6 +21,8 @@ pub enum SubEmbedder {
     Ollama(ollama::Embedder),
     /// An embedder based on making embedding queries against a generic JSON/REST embedding server.
     Rest(rest::Embedder),
+    /// An embedder based on running local ONNX models with ONNX Runtime.
+    Onnx(onnx::Embedder),
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
@@ -30,6 +32,7 @@ pub enum SubEmbedderOptions {
     Ollama(ollama::EmbedderOptions),
     UserProvided(manual::EmbedderOptions),
     Rest(rest::EmbedderOptions),
+    Onnx(onnx::EmbedderOptions),
 }
 
 impl SubEmbedderOptions {
@@ -40,6 +43,7 @@ impl SubEmbedderOptions {
             SubEmbedderOptions::Ollama(embedder_options) => embedder_options.distribution,
             SubEmbedderOptions::UserProvided(embedder_options) => embedder_options.distribution,
             SubEmbedderOptions::Rest(embedder_options) => embedder_options.distribution,
+            SubEmbedderOptions::Onnx(embedder_options) => embedder_options.distribution,
         }
     }
 }
@@ -60,10 +64,11 @@ impl Embedder {
     pub fn new(
         EmbedderOptions { search, index }: EmbedderOptions,
         cache_cap: usize,
+        persistent_cache: Option<&PersistentEmbeddingCache>,
     ) -> Result<Self, NewEmbedderError> {
-        let search = SubEmbedder::new(search, cache_cap)?;
-        // cache is only used at search
-        let index = SubEmbedder::new(index, 0)?;
+        let search = SubEmbedder::new(search, cache_cap, persistent_cache)?;
+        // the in-memory cache is only used at search
+        let index = SubEmbedder::new(index, 0, persistent_cache)?;
 
         // check dimensions
         if search.dimensions() != index.dimensions() {
@@ -124,25 +129,32 @@ impl SubEmbedder {
     pub fn new(
         options: SubEmbedderOptions,
         cache_cap: usize,
+        persistent_cache: Option<&PersistentEmbeddingCache>,
     ) -> std::result::Result<Self, NewEmbedderError> {
+        let cache = EmbeddingCache::new(
+            cache_cap,
+            persistent_cache
+                .map(|cache| cache.for_embedder(&options))
+                .transpose()
+                .map_err(NewEmbedderError::serialize_cache_key)?,
+        );
         Ok(match options {
             SubEmbedderOptions::HuggingFace(options) => {
-                Self::HuggingFace(hf::Embedder::new(options, cache_cap)?)
+                Self::HuggingFace(hf::Embedder::new(options, cache)?)
             }
             SubEmbedderOptions::OpenAi(options) => {
-                Self::OpenAi(openai::Embedder::new(options, cache_cap)?)
+                Self::OpenAi(openai::Embedder::new(options, cache)?)
             }
             SubEmbedderOptions::Ollama(options) => {
-                Self::Ollama(ollama::Embedder::new(options, cache_cap)?)
+                Self::Ollama(ollama::Embedder::new(options, cache)?)
             }
             SubEmbedderOptions::UserProvided(options) => {
                 Self::UserProvided(manual::Embedder::new(options))
             }
-            SubEmbedderOptions::Rest(options) => Self::Rest(rest::Embedder::new(
-                options,
-                cache_cap,
-                rest::ConfigurationSource::User,
-            )?),
+            SubEmbedderOptions::Rest(options) => {
+                Self::Rest(rest::Embedder::new(options, cache, rest::ConfigurationSource::User)?)
+            }
+            SubEmbedderOptions::Onnx(options) => Self::Onnx(onnx::Embedder::new(options, cache)?),
         })
     }
 
@@ -157,6 +169,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.embed(&texts, deadline),
             SubEmbedder::UserProvided(embedder) => embedder.embed(&texts),
             SubEmbedder::Rest(embedder) => embedder.embed(texts, deadline),
+            SubEmbedder::Onnx(embedder) => embedder.embed(texts),
         }
     }
 
@@ -178,6 +191,7 @@ impl SubEmbedder {
                 .embed_ref(&[text], deadline)?
                 .pop()
                 .ok_or_else(EmbedError::missing_embedding),
+            SubEmbedder::Onnx(embedder) => embedder.embed_one(text),
         }
     }
 
@@ -195,6 +209,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.embed_index(text_chunks, threads),
             SubEmbedder::UserProvided(embedder) => embedder.embed_index(text_chunks),
             SubEmbedder::Rest(embedder) => embedder.embed_index(text_chunks, threads),
+            SubEmbedder::Onnx(embedder) => embedder.embed_index(text_chunks),
         }
     }
 
@@ -210,6 +225,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.embed_index_ref(texts, threads),
             SubEmbedder::UserProvided(embedder) => embedder.embed_index_ref(texts),
             SubEmbedder::Rest(embedder) => embedder.embed_index_ref(texts, threads),
+            SubEmbedder::Onnx(embedder) => embedder.embed_index_ref(texts),
         }
     }
 
@@ -221,6 +237,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.chunk_count_hint(),
             SubEmbedder::UserProvided(_) => 100,
             SubEmbedder::Rest(embedder) => embedder.chunk_count_hint(),
+            SubEmbedder::Onnx(embedder) => embedder.chunk_count_hint(),
         }
     }
 
@@ -232,6 +249,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.prompt_count_in_chunk_hint(),
             SubEmbedder::UserProvided(_) => 1,
             SubEmbedder::Rest(embedder) => embedder.prompt_count_in_chunk_hint(),
+            SubEmbedder::Onnx(embedder) => embedder.prompt_count_in_chunk_hint(),
         }
     }
 
@@ -240,7 +258,8 @@ impl SubEmbedder {
             SubEmbedder::HuggingFace(_)
             | SubEmbedder::OpenAi(_)
             | SubEmbedder::Ollama(_)
-            | SubEmbedder::Rest(_) => true,
+            | SubEmbedder::Rest(_)
+            | SubEmbedder::Onnx(_) => true,
             SubEmbedder::UserProvided(_) => false,
         }
     }
@@ -253,6 +272,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.dimensions(),
             SubEmbedder::UserProvided(embedder) => embedder.dimensions(),
             SubEmbedder::Rest(embedder) => embedder.dimensions(),
+            SubEmbedder::Onnx(embedder) => embedder.dimensions(),
         }
     }
 
@@ -264,6 +284,7 @@ impl SubEmbedder {
             SubEmbedder::Ollama(embedder) => embedder.distribution(),
             SubEmbedder::UserProvided(embedder) => embedder.distribution(),
             SubEmbedder::Rest(embedder) => embedder.distribution(),
+            SubEmbedder::Onnx(embedder) => embedder.distribution(),
         }
     }
 
@@ -274,6 +295,7 @@ impl SubEmbedder {
             SubEmbedder::UserProvided(_) => None,
             SubEmbedder::Ollama(embedder) => Some(embedder.cache()),
             SubEmbedder::Rest(embedder) => Some(embedder.cache()),
+            SubEmbedder::Onnx(embedder) => Some(embedder.cache()),
         }
     }
 }
@@ -57,6 +57,11 @@ pub enum EmbedErrorKind {
     TensorValue(candle_core::Error),
     #[error("could not run model:\n  - {0}")]
     ModelForward(candle_core::Error),
+    #[cfg(feature = "onnx")]
+    #[error("could not run ONNX model:\n  - {0}")]
+    OnnxRun(ort::Error),
+    #[error("unexpected shape `{0:?}` for the output of the ONNX model\n  - Note: the first output of the model must contain the embeddings of the texts, or the embeddings of their tokens")]
+    OnnxOutputShape(Vec<i64>),
     #[error("attempt to embed the following text in a configuration where embeddings must be user provided:\n  - `{0}`")]
     ManualEmbed(String),
     #[error("model not found. Meilisearch will not automatically download models from the Ollama library, please pull the model manually{}", option_info(.0.as_deref(), "server replied with "))]
@@ -95,10 +100,28 @@ pub enum EmbedErrorKind {
     RestNetwork(ureq::Transport),
     #[error("error extracting embeddings from the response:\n  - {0}")]
     RestExtractionError(String),
+    #[error("expected a response containing {0} scores, got only {1}")]
+    RerankResponseScoreCount(usize, usize),
+    #[error("error extracting scores from the response:\n  - {0}")]
+    RerankExtractionError(String),
+    #[error("reached the deadline after scoring {0} of the {1} documents to rerank")]
+    RerankDeadline(usize, usize),
     #[error("was expecting embeddings of dimension `{0}`, got embeddings of dimensions `{1}`")]
     UnexpectedDimension(usize, usize),
+    #[error("received an invalid sparse embedding:\n  - {0}")]
+    InvalidSparseEmbedding(String),
+    #[error("the embedder does not produce sparse embeddings")]
+    NotSparse,
+    #[error("received an empty array of embeddings for a multi-vector embedder")]
+    EmptyMultiVectorEmbedding,
+    #[error("the embedder does not produce multiple embeddings per text")]
+    NotMultiVector,
     #[error("no embedding was produced")]
     MissingEmbedding,
+    #[error("could not render the search fragment:\n  - {0}")]
+    RenderSearchFragment(liquid::Error),
+    #[error("could not parse the document rendered with the indexing fragment:\n  - {0}")]
+    InvalidRenderedFragment(serde_json::Error),
     #[error(transparent)]
     PanicInThreadPool(#[from] PanicCatched),
 }
@@ -127,6 +150,15 @@ impl EmbedError {
         Self { kind: EmbedErrorKind::ModelForward(inner), fault: FaultSource::Runtime }
     }
 
+    #[cfg(feature = "onnx")]
+    pub fn onnx_run(inner: ort::Error) -> Self {
+        Self { kind: EmbedErrorKind::OnnxRun(inner), fault: FaultSource::Runtime }
+    }
+
+    pub fn onnx_output_shape(shape: Vec<i64>) -> Self {
+        Self { kind: EmbedErrorKind::OnnxOutputShape(shape), fault: FaultSource::User }
+    }
+
     pub(crate) fn embed_on_manual_embedder(texts: String) -> EmbedError {
         Self { kind: EmbedErrorKind::ManualEmbed(texts), fault: FaultSource::User }
     }
@@ -197,19 +229,61 @@ impl EmbedError {
         Self { kind: EmbedErrorKind::RestNetwork(transport), fault: FaultSource::Runtime }
     }
 
-    pub(crate) fn rest_unexpected_dimension(expected: usize, got: usize) -> EmbedError {
+    pub(crate) fn unexpected_dimension(expected: usize, got: usize) -> EmbedError {
         Self {
             kind: EmbedErrorKind::UnexpectedDimension(expected, got),
             fault: FaultSource::Runtime,
         }
     }
+    pub(crate) fn rerank_deadline(scored: usize, documents: usize) -> EmbedError {
+        Self {
+            kind: EmbedErrorKind::RerankDeadline(scored, documents),
+            fault: FaultSource::Runtime,
+        }
+    }
+
     pub(crate) fn missing_embedding() -> EmbedError {
         Self { kind: EmbedErrorKind::MissingEmbedding, fault: FaultSource::Undecided }
     }
 
+    pub(crate) fn render_search_fragment(error: liquid::Error) -> EmbedError {
+        Self { kind: EmbedErrorKind::RenderSearchFragment(error), fault: FaultSource::User }
+    }
+
+    pub(crate) fn invalid_rendered_fragment(error: serde_json::Error) -> EmbedError {
+        Self { kind: EmbedErrorKind::InvalidRenderedFragment(error), fault: FaultSource::Bug }
+    }
+
     pub(crate) fn rest_extraction_error(error: String) -> EmbedError {
         Self { kind: EmbedErrorKind::RestExtractionError(error), fault: FaultSource::Runtime }
     }
+
+    pub(crate) fn rerank_response_score_count(expected: usize, got: usize) -> EmbedError {
+        Self {
+            kind: EmbedErrorKind::RerankResponseScoreCount(expected, got),
+            fault: FaultSource::Runtime,
+        }
+    }
+
+    pub(crate) fn rerank_extraction_error(error: String) -> EmbedError {
+        Self { kind: EmbedErrorKind::RerankExtractionError(error), fault: FaultSource::Runtime }
+    }
+
+    pub(crate) fn invalid_sparse_embedding(error: String) -> EmbedError {
+        Self { kind: EmbedErrorKind::InvalidSparseEmbedding(error), fault: FaultSource::Runtime }
+    }
+
+    pub(crate) fn not_sparse() -> EmbedError {
+        Self { kind: EmbedErrorKind::NotSparse, fault: FaultSource::Bug }
+    }
+
+    pub(crate) fn empty_multi_vector_embedding() -> EmbedError {
+        Self { kind: EmbedErrorKind::EmptyMultiVectorEmbedding, fault: FaultSource::Runtime }
+    }
+
+    pub(crate) fn not_multi_vector() -> EmbedError {
+        Self { kind: EmbedErrorKind::NotMultiVector, fault: FaultSource::Bug }
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -319,6 +393,30 @@ impl NewEmbedderError {
         Self { kind: NewEmbedderErrorKind::LoadModel(inner), fault: FaultSource::Runtime }
     }
 
+    pub fn cross_encoder_label_count(model: String, labels: usize) -> NewEmbedderError {
+        Self {
+            kind: NewEmbedderErrorKind::CrossEncoderLabelCount { model, labels },
+            fault: FaultSource::User,
+        }
+    }
+
+    pub fn onnx_model_not_found(directory: PathBuf) -> NewEmbedderError {
+        Self { kind: NewEmbedderErrorKind::OnnxModelNotFound(directory), fault: FaultSource::User }
+    }
+
+    #[cfg(feature = "onnx")]
+    pub fn onnx_load_model(inner: ort::Error) -> NewEmbedderError {
+        Self { kind: NewEmbedderErrorKind::OnnxLoadModel(inner), fault: FaultSource::Runtime }
+    }
+
+    pub fn onnx_disabled() -> NewEmbedderError {
+        Self { kind: NewEmbedderErrorKind::OnnxDisabled, fault: FaultSource::User }
+    }
+
+    pub fn serialize_cache_key(inner: serde_json::Error) -> NewEmbedderError {
+        Self { kind: NewEmbedderErrorKind::SerializeCacheKey(inner), fault: FaultSource::Bug }
+    }
+
     pub fn could_not_determine_dimension(inner: EmbedError) -> NewEmbedderError {
         Self {
             kind: NewEmbedderErrorKind::CouldNotDetermineDimension(inner),
@@ -487,6 +585,20 @@ pub enum NewEmbedderErrorKind {
     CouldNotDetermineDimension(EmbedError),
     #[error("loading model failed:\n  - {0}")]
     LoadModel(candle_core::Error),
+    #[error("model `{model}` cannot be used as a reranker: its classifier has {labels} labels.\n  - Note: a reranker must be a cross-encoder producing a single relevance score")]
+    CrossEncoderLabelCount { model: String, labels: usize },
+    // onnx
+    #[error("could not find an ONNX model in `{}`\n  - Note: the directory must contain a `model.onnx` file, either directly or in an `onnx` subdirectory", .0.display())]
+    OnnxModelNotFound(PathBuf),
+    #[cfg(feature = "onnx")]
+    #[error("loading ONNX model failed:\n  - {0}")]
+    OnnxLoadModel(ort::Error),
+    #[error("`onnx` embedders are not available in this build of Meilisearch\n  - Note: Meilisearch must be compiled with the `onnx` feature to run local ONNX models")]
+    OnnxDisabled,
+    #[error(
+        "could not serialize the options of the embedder to key its embedding cache:\n  - {0}"
+    )]
+    SerializeCacheKey(serde_json::Error),
     #[error("{0}")]
     CouldNotParseTemplate(String),
     #[error("unsupported Ollama URL.\n  - For `ollama` sources, the URL must end with `/api/embed` or `/api/embeddings`\n  - Got `{0}`")]
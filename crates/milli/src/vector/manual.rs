@@ -6,17 +6,25 @@ use crate::vector::Embedding;
 pub struct Embedder {
     dimensions: usize,
     distribution: Option<DistributionShift>,
+    multi_vector: bool,
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
 pub struct EmbedderOptions {
     pub dimensions: usize,
     pub distribution: Option<DistributionShift>,
+    /// Whether documents and queries are represented by several embeddings, compared with MaxSim.
+    #[serde(default)]
+    pub multi_vector: bool,
 }
 
 impl Embedder {
     pub fn new(options: EmbedderOptions) -> Self {
-        Self { dimensions: options.dimensions, distribution: options.distribution }
+        Self {
+            dimensions: options.dimensions,
+            distribution: options.distribution,
+            multi_vector: options.multi_vector,
+        }
     }
 
     pub fn embed<S: AsRef<str>>(&self, texts: &[S]) -> Result<Vec<Embedding>, EmbedError> {
@@ -41,6 +49,10 @@ impl Embedder {
         self.distribution
     }
 
+    pub fn is_multi_vector(&self) -> bool {
+        self.multi_vector
+    }
+
     pub(crate) fn embed_index_ref(&self, texts: &[&str]) -> Result<Vec<Embedding>, EmbedError> {
         texts.iter().map(|text| self.embed_one(text)).collect()
     }
@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
@@ -6,6 +7,7 @@ use std::time::Instant;
 use arroy::distances::{BinaryQuantizedCosine, Cosine};
 use arroy::ItemId;
 use deserr::{DeserializeError, Deserr};
+use heed::types::Bytes;
 use heed::{RoTxn, RwTxn, Unspecified};
 use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
@@ -13,23 +15,37 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 use self::error::{EmbedError, NewEmbedderError};
+use self::persistent_cache::EmbedderPersistentCache;
+use self::quantization::{RescoringPrecision, RESCORING_OVERSAMPLING};
+use crate::heed_codec::U8BEU32Codec;
 use crate::progress::Progress;
 use crate::prompt::{Prompt, PromptData};
-use crate::ThreadPoolNoAbort;
+use crate::{Index, ThreadPoolNoAbort};
 
+pub mod chunking;
 pub mod composite;
 pub mod error;
 pub mod hf;
 pub mod json_template;
 pub mod manual;
+pub mod multi_vector;
 pub mod openai;
 pub mod parsed_vectors;
+pub mod persistent_cache;
+pub mod quantization;
+pub mod recall;
+pub mod rerank;
 pub mod settings;
+pub mod sparse;
 
 pub mod ollama;
+pub mod onnx;
 pub mod rest;
 
 pub use self::error::Error;
+pub use self::persistent_cache::PersistentEmbeddingCache;
+pub use self::quantization::{Quantization, RescoringStore, VectorQuantization};
+pub use self::sparse::{SparseEmbedding, SparseVectorStore};
 
 pub type Embedding = Vec<f32>;
 
@@ -40,15 +56,26 @@ pub struct ArroyWrapper {
     quantized: bool,
     embedder_index: u8,
     database: arroy::Database<Unspecified>,
+    rescoring_database: heed::Database<U8BEU32Codec, Bytes>,
+    /// The precision of the embeddings kept to rescore the candidates, if any.
+    rescoring: Option<RescoringPrecision>,
 }
 
 impl ArroyWrapper {
-    pub fn new(
-        database: arroy::Database<Unspecified>,
-        embedder_index: u8,
-        quantized: bool,
-    ) -> Self {
-        Self { database, embedder_index, quantized }
+    pub fn new(index: &Index, embedder_index: u8, quantization: VectorQuantization) -> Self {
+        Self {
+            database: index.vector_arroy,
+            rescoring_database: index.vector_rescoring,
+            embedder_index,
+            quantized: quantization.is_binary_quantized(),
+            rescoring: quantization.rescoring_precision(),
+        }
+    }
+
+    fn rescoring_store(&self) -> Option<RescoringStore> {
+        self.rescoring.map(|precision| {
+            RescoringStore::new(self.rescoring_database, self.embedder_index, precision)
+        })
     }
 
     pub fn embedder_index(&self) -> u8 {
@@ -89,10 +116,23 @@ impl ArroyWrapper {
         progress: &Progress,
         rng: &mut R,
         dimension: usize,
-        quantizing: bool,
+        quantizing: Option<VectorQuantization>,
         arroy_memory: Option<usize>,
         cancel: &(impl Fn() -> bool + Sync + Send),
     ) -> Result<(), arroy::Error> {
+        if let Some(quantization) = quantizing {
+            self.update_rescoring(
+                wtxn,
+                progress,
+                rng,
+                dimension,
+                quantization,
+                arroy_memory,
+                cancel,
+            )?;
+        }
+        let quantizing = quantizing.is_some_and(|quantization| quantization.is_binary_quantized());
+
         for index in arroy_db_range_for_embedder(self.embedder_index) {
             if self.quantized {
                 let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
@@ -131,6 +171,65 @@ impl ArroyWrapper {
         Ok(())
     }
 
+    /// Fills, converts or clears the rescoring embeddings according to the new quantization of the embedder.
+    ///
+    /// Must be called before the arroy trees are quantized, as the embeddings are read from them.
+    #[allow(clippy::too_many_arguments)]
+    fn update_rescoring<R: rand::Rng + rand::SeedableRng>(
+        &mut self,
+        wtxn: &mut RwTxn,
+        progress: &Progress,
+        rng: &mut R,
+        dimension: usize,
+        quantization: VectorQuantization,
+        arroy_memory: Option<usize>,
+        cancel: &(impl Fn() -> bool + Sync + Send),
+    ) -> Result<(), arroy::Error> {
+        let precision = quantization.rescoring_precision();
+        if precision == self.rescoring {
+            return Ok(());
+        }
+
+        match (self.rescoring_store(), precision) {
+            (Some(store), None) => store.clear(wtxn)?,
+            (Some(store), Some(precision)) => {
+                store.convert(wtxn, precision)?;
+            }
+            (None, Some(precision)) if !self.quantized => {
+                let store =
+                    RescoringStore::new(self.rescoring_database, self.embedder_index, precision);
+                store.clear(wtxn)?;
+                for index in arroy_db_range_for_embedder(self.embedder_index) {
+                    let writer = arroy::Writer::new(self.angular_db(), index, dimension);
+                    if writer.is_empty(wtxn)? {
+                        break;
+                    }
+                    // the items of a tree can only be listed once it is built
+                    if writer.need_build(wtxn)? {
+                        writer
+                            .builder(rng)
+                            .available_memory(arroy_memory.unwrap_or(usize::MAX))
+                            .progress(|step| progress.update_progress_from_arroy(step))
+                            .cancel(cancel)
+                            .build(wtxn)?;
+                    }
+                    let items =
+                        arroy::Reader::open(wtxn, index, self.angular_db())?.item_ids().clone();
+                    for item in items {
+                        if let Some(vector) = writer.item_vector(wtxn, item)? {
+                            store.push_embedding(wtxn, item, &vector)?;
+                        }
+                    }
+                }
+            }
+            // the embeddings of binary quantized trees are too imprecise to rescore,
+            // which the validation of the settings prevents.
+            (None, _) => {}
+        }
+        self.rescoring = precision;
+        Ok(())
+    }
+
     /// Overwrite all the embeddings associated with the index and item ID.
     /// /!\ It won't remove embeddings after the last passed embedding, which can leave stale embeddings.
     ///     You should call `del_items` on the `item_id` before calling this method.
@@ -153,6 +252,9 @@ impl ArroyWrapper {
                     .add_item(wtxn, item_id, vector)?
             }
         }
+        if let Some(store) = self.rescoring_store() {
+            store.put_embeddings(wtxn, item_id, embeddings)?;
+        }
         Ok(())
     }
 
@@ -163,6 +265,9 @@ impl ArroyWrapper {
         item_id: arroy::ItemId,
         vector: &[f32],
     ) -> Result<(), arroy::Error> {
+        if let Some(store) = self.rescoring_store() {
+            store.push_embedding(wtxn, item_id, vector)?;
+        }
         if self.quantized {
             self._add_item(wtxn, self.quantized_db(), item_id, vector)
         } else {
@@ -196,6 +301,9 @@ impl ArroyWrapper {
         dimension: usize,
         item_id: arroy::ItemId,
     ) -> Result<(), arroy::Error> {
+        if let Some(store) = self.rescoring_store() {
+            store.del_embeddings(wtxn, item_id)?;
+        }
         for index in arroy_db_range_for_embedder(self.embedder_index) {
             if self.quantized {
                 let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
@@ -221,7 +329,11 @@ impl ArroyWrapper {
         vector: &[f32],
     ) -> Result<bool, arroy::Error> {
         if self.quantized {
-            self._del_item(wtxn, self.quantized_db(), item_id, vector)
+            let deleted = self._del_item(wtxn, self.quantized_db(), item_id, vector)?;
+            if let Some(store) = self.rescoring_store().filter(|_| deleted) {
+                store.del_embedding(wtxn, item_id, vector)?;
+            }
+            Ok(deleted)
         } else {
             self._del_item(wtxn, self.angular_db(), item_id, vector)
         }
@@ -272,6 +384,9 @@ impl ArroyWrapper {
     }
 
     pub fn clear(&self, wtxn: &mut RwTxn, dimension: usize) -> Result<(), arroy::Error> {
+        if let Some(store) = self.rescoring_store() {
+            store.clear(wtxn)?;
+        }
         for index in arroy_db_range_for_embedder(self.embedder_index) {
             if self.quantized {
                 let writer = arroy::Writer::new(self.quantized_db(), index, dimension);
@@ -324,6 +439,17 @@ impl ArroyWrapper {
         limit: usize,
         filter: Option<&RoaringBitmap>,
     ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+        // rescoring embeddings are only kept when the arroy trees are quantized
+        if let Some(store) = self.rescoring_store() {
+            if let Some(query) = store.embeddings(rtxn, item)? {
+                let oversampled_limit = limit.saturating_mul(RESCORING_OVERSAMPLING);
+                let candidates =
+                    self._nns_by_item(rtxn, self.quantized_db(), item, oversampled_limit, filter)?;
+                let mut results = store.rescore_by_document(rtxn, &query, candidates)?;
+                results.truncate(limit);
+                return Ok(results);
+            }
+        }
         if self.quantized {
             self._nns_by_item(rtxn, self.quantized_db(), item, limit, filter)
         } else {
@@ -358,13 +484,25 @@ impl ArroyWrapper {
         Ok(results)
     }
 
+    /// Returns the nearest items along with the index of their vector that matched and its distance.
+    ///
+    /// The same item may appear several times when it has several vectors.
     pub fn nns_by_vector(
         &self,
         rtxn: &RoTxn,
         vector: &[f32],
         limit: usize,
         filter: Option<&RoaringBitmap>,
-    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+    ) -> Result<Vec<(ItemId, u8, f32)>, arroy::Error> {
+        // rescoring embeddings are only kept when the arroy trees are quantized
+        if let Some(store) = self.rescoring_store() {
+            let oversampled_limit = limit.saturating_mul(RESCORING_OVERSAMPLING);
+            let candidates =
+                self._nns_by_vector(rtxn, self.quantized_db(), vector, oversampled_limit, filter)?;
+            let mut results = store.rescore(rtxn, vector, candidates)?;
+            results.truncate(limit);
+            return Ok(results);
+        }
         if self.quantized {
             self._nns_by_vector(rtxn, self.quantized_db(), vector, limit, filter)
         } else {
@@ -379,25 +517,164 @@ impl ArroyWrapper {
         vector: &[f32],
         limit: usize,
         filter: Option<&RoaringBitmap>,
-    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+    ) -> Result<Vec<(ItemId, u8, f32)>, arroy::Error> {
         let mut results = Vec::new();
 
-        for reader in self.readers(rtxn, db) {
+        // readers are opened in the order of the vectors of the items
+        for (vector_index, reader) in (0..=u8::MAX).zip(self.readers(rtxn, db)) {
             let reader = reader?;
             let mut searcher = reader.nns(limit);
             if let Some(filter) = filter {
                 searcher.candidates(filter);
             }
 
-            results.append(&mut searcher.by_vector(rtxn, vector)?);
+            results.extend(
+                searcher
+                    .by_vector(rtxn, vector)?
+                    .into_iter()
+                    .map(|(item, distance)| (item, vector_index, distance)),
+            );
         }
 
-        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        results.sort_unstable_by_key(|(_, _, distance)| OrderedFloat(*distance));
 
         Ok(results)
     }
 
+    /// Returns the nearest candidates along with the index of their closest vector and its distance,
+    /// computed against each of their vectors rather than by searching the arroy trees.
+    ///
+    /// Unlike [`Self::nns_by_vector`], the results are exact and an item appears at most once,
+    /// but the cost grows with the number of candidates.
+    pub fn exact_nns_by_vector(
+        &self,
+        rtxn: &RoTxn,
+        vector: &[f32],
+        limit: usize,
+        candidates: &RoaringBitmap,
+    ) -> Result<Vec<(ItemId, u8, f32)>, arroy::Error> {
+        let mut results = Vec::new();
+        self.for_each_item_vectors(rtxn, candidates, |item, vectors| {
+            let closest = (0..=u8::MAX)
+                .zip(vectors)
+                .map(|(vector_index, item_vector)| {
+                    (vector_index, multi_vector::cosine_distance(vector, item_vector))
+                })
+                .min_by(|(_, left), (_, right)| left.total_cmp(right));
+            if let Some((vector_index, distance)) = closest {
+                results.push((item, vector_index, distance));
+            }
+        })?;
+
+        results.sort_unstable_by(|(left_item, _, left), (right_item, _, right)| {
+            left.total_cmp(right).then(left_item.cmp(right_item))
+        });
+        results.truncate(limit);
+
+        Ok(results)
+    }
+
+    /// Returns the `limit` items with the best [`multi_vector::max_sim`] score for the query, best first.
+    ///
+    /// The nearest neighbours of each embedding of the query are the candidates,
+    /// which are then scored exactly against all their embeddings.
+    pub fn nns_by_vectors(
+        &self,
+        rtxn: &RoTxn,
+        query: &[Embedding],
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+        let mut candidates = RoaringBitmap::new();
+        for vector in query {
+            for (item, _, _) in self.nns_by_vector(rtxn, vector, limit, filter)? {
+                candidates.insert(item);
+            }
+        }
+
+        self.exact_nns_by_vectors(rtxn, query, limit, &candidates)
+    }
+
+    /// Returns the candidates with the best MaxSim score for the embeddings of the query,
+    /// computed for each of the candidates rather than for the ones found in the arroy trees.
+    pub fn exact_nns_by_vectors(
+        &self,
+        rtxn: &RoTxn,
+        query: &[Embedding],
+        limit: usize,
+        candidates: &RoaringBitmap,
+    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+        let mut results = Vec::with_capacity(candidates.len() as usize);
+        self.for_each_item_vectors(rtxn, candidates, |item, vectors| {
+            results.push((item, multi_vector::max_sim(query, vectors)));
+        })?;
+
+        results.sort_unstable_by(|(left_item, left), (right_item, right)| {
+            right.total_cmp(left).then(left_item.cmp(right_item))
+        });
+        results.truncate(limit);
+
+        Ok(results)
+    }
+
+    /// Calls `f` with the vectors of each of the candidates that have some.
+    ///
+    /// Unlike calling [`Self::item_vectors`] for each candidate, the arroy trees are only opened once.
+    fn for_each_item_vectors(
+        &self,
+        rtxn: &RoTxn,
+        candidates: &RoaringBitmap,
+        mut f: impl FnMut(ItemId, &[Vec<f32>]),
+    ) -> Result<(), arroy::Error> {
+        // the rescoring embeddings are more precise than the ones of the quantized trees
+        if let Some(store) = self.rescoring_store() {
+            for item in candidates {
+                if let Some(vectors) = store.embeddings(rtxn, item)? {
+                    f(item, &vectors);
+                }
+            }
+            return Ok(());
+        }
+
+        if self.quantized {
+            self._for_each_item_vectors(rtxn, self.quantized_db(), candidates, f)
+        } else {
+            self._for_each_item_vectors(rtxn, self.angular_db(), candidates, f)
+        }
+    }
+
+    fn _for_each_item_vectors<D: arroy::Distance>(
+        &self,
+        rtxn: &RoTxn,
+        db: arroy::Database<D>,
+        candidates: &RoaringBitmap,
+        mut f: impl FnMut(ItemId, &[Vec<f32>]),
+    ) -> Result<(), arroy::Error> {
+        let readers: Vec<_> = self.readers(rtxn, db).collect::<Result<_, _>>()?;
+        let mut vectors = Vec::new();
+        for item in candidates {
+            vectors.clear();
+            for reader in &readers {
+                match reader.item_vector(rtxn, item)? {
+                    Some(vector) => vectors.push(vector),
+                    None => break,
+                }
+            }
+            if !vectors.is_empty() {
+                f(item, &vectors);
+            }
+        }
+        Ok(())
+    }
+
     pub fn item_vectors(&self, rtxn: &RoTxn, item_id: u32) -> Result<Vec<Vec<f32>>, arroy::Error> {
+        // the rescoring embeddings are more precise than the ones of the quantized trees
+        if let Some(store) = self.rescoring_store() {
+            if let Some(vectors) = store.embeddings(rtxn, item_id)? {
+                return Ok(vectors);
+            }
+        }
+
         let mut vectors = Vec::new();
 
         if self.quantized {
@@ -555,23 +832,44 @@ pub enum Embedder {
     Rest(rest::Embedder),
     /// An embedder composed of an embedder at search time and an embedder at indexing time.
     Composite(composite::Embedder),
+    /// An embedder based on running local ONNX models with ONNX Runtime.
+    Onnx(onnx::Embedder),
 }
 
 #[derive(Debug)]
 struct EmbeddingCache {
     data: Option<Mutex<lru::LruCache<String, Embedding>>>,
+    /// The on-disk cache, consulted when an embedding is missing from the in-memory cache.
+    persistent: Option<EmbedderPersistentCache>,
 }
 
 impl EmbeddingCache {
     const MAX_TEXT_LEN: usize = 2000;
 
-    pub fn new(cap: usize) -> Self {
+    pub fn new(cap: usize, persistent: Option<EmbedderPersistentCache>) -> Self {
         let data = NonZeroUsize::new(cap).map(lru::LruCache::new).map(Mutex::new);
-        Self { data }
+        Self { data, persistent }
     }
 
     /// Get the embedding corresponding to `text`, if any is present in the cache.
     pub fn get(&self, text: &str) -> Option<Embedding> {
+        if let Some(embedding) = self.get_in_memory(text) {
+            return Some(embedding);
+        }
+        let embedding = self.persistent.as_ref()?.get(text)?;
+        self.put_in_memory(text.to_owned(), embedding.clone());
+        Some(embedding)
+    }
+
+    /// Puts a new embedding for the specified `text`
+    pub fn put(&self, text: String, embedding: Embedding) {
+        if let Some(persistent) = &self.persistent {
+            persistent.put_many([(text.as_str(), &embedding)]);
+        }
+        self.put_in_memory(text, embedding);
+    }
+
+    fn get_in_memory(&self, text: &str) -> Option<Embedding> {
         let data = self.data.as_ref()?;
         if text.len() > Self::MAX_TEXT_LEN {
             return None;
@@ -581,8 +879,7 @@ impl EmbeddingCache {
         cache.get(text).cloned()
     }
 
-    /// Puts a new embedding for the specified `text`
-    pub fn put(&self, text: String, embedding: Embedding) {
+    fn put_in_memory(&self, text: String, embedding: Embedding) {
         let Some(data) = self.data.as_ref() else {
             return;
         };
@@ -595,6 +892,10 @@ impl EmbeddingCache {
 
         cache.put(text, embedding);
     }
+
+    fn persistent(&self) -> Option<&EmbedderPersistentCache> {
+        self.persistent.as_ref()
+    }
 }
 
 /// Configuration for an embedder.
@@ -606,12 +907,21 @@ pub struct EmbeddingConfig {
     pub prompt: PromptData,
     /// If this embedder is binary quantized
     pub quantized: Option<bool>,
+    /// How the embeddings of this embedder are quantized, takes precedence over `quantized`
+    pub quantization: Option<Quantization>,
+    /// If the candidates of this quantized embedder are rescored against full-precision embeddings
+    pub rescoring: Option<bool>,
     // TODO: add metrics and anything needed
 }
 
 impl EmbeddingConfig {
-    pub fn quantized(&self) -> bool {
-        self.quantized.unwrap_or_default()
+    pub fn quantization(&self) -> VectorQuantization {
+        let quantization = match (self.quantization, self.quantized) {
+            (Some(quantization), _) => quantization,
+            (None, Some(true)) => Quantization::Binary,
+            (None, _) => Quantization::None,
+        };
+        VectorQuantization { quantization, rescoring: self.rescoring.unwrap_or_default() }
     }
 }
 
@@ -619,11 +929,11 @@ impl EmbeddingConfig {
 ///
 /// Each configuration is mapped to a name.
 #[derive(Clone, Default)]
-pub struct EmbeddingConfigs(HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)>);
+pub struct EmbeddingConfigs(HashMap<String, (Arc<Embedder>, Arc<Prompt>, VectorQuantization)>);
 
 impl EmbeddingConfigs {
     /// Create the map from its internal component.s
-    pub fn new(data: HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)>) -> Self {
+    pub fn new(data: HashMap<String, (Arc<Embedder>, Arc<Prompt>, VectorQuantization)>) -> Self {
         Self(data)
     }
 
@@ -632,24 +942,28 @@ impl EmbeddingConfigs {
     }
 
     /// Get an embedder configuration and template from its name.
-    pub fn get(&self, name: &str) -> Option<(Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn get(&self, name: &str) -> Option<(Arc<Embedder>, Arc<Prompt>, VectorQuantization)> {
         self.0.get(name).cloned()
     }
 
-    pub fn inner_as_ref(&self) -> &HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn inner_as_ref(
+        &self,
+    ) -> &HashMap<String, (Arc<Embedder>, Arc<Prompt>, VectorQuantization)> {
         &self.0
     }
 
-    pub fn into_inner(self) -> HashMap<String, (Arc<Embedder>, Arc<Prompt>, bool)> {
+    pub fn into_inner(self) -> HashMap<String, (Arc<Embedder>, Arc<Prompt>, VectorQuantization)> {
         self.0
     }
 }
 
 impl IntoIterator for EmbeddingConfigs {
-    type Item = (String, (Arc<Embedder>, Arc<Prompt>, bool));
+    type Item = (String, (Arc<Embedder>, Arc<Prompt>, VectorQuantization));
 
-    type IntoIter =
-        std::collections::hash_map::IntoIter<String, (Arc<Embedder>, Arc<Prompt>, bool)>;
+    type IntoIter = std::collections::hash_map::IntoIter<
+        String,
+        (Arc<Embedder>, Arc<Prompt>, VectorQuantization),
+    >;
 
     fn into_iter(self) -> Self::IntoIter {
         self.0.into_iter()
@@ -665,6 +979,7 @@ pub enum EmbedderOptions {
     UserProvided(manual::EmbedderOptions),
     Rest(rest::EmbedderOptions),
     Composite(composite::EmbedderOptions),
+    Onnx(onnx::EmbedderOptions),
 }
 
 impl Default for EmbedderOptions {
@@ -675,31 +990,41 @@ impl Default for EmbedderOptions {
 
 impl Embedder {
     /// Spawns a new embedder built from its options.
+    ///
+    /// The embeddings it computes are kept in the `persistent_cache`, when there is one,
+    /// keyed by the options of the embedder.
     pub fn new(
         options: EmbedderOptions,
         cache_cap: usize,
+        persistent_cache: Option<&PersistentEmbeddingCache>,
     ) -> std::result::Result<Self, NewEmbedderError> {
+        let cache = EmbeddingCache::new(
+            cache_cap,
+            persistent_cache
+                .map(|cache| cache.for_embedder(&options))
+                .transpose()
+                .map_err(NewEmbedderError::serialize_cache_key)?,
+        );
         Ok(match options {
             EmbedderOptions::HuggingFace(options) => {
-                Self::HuggingFace(hf::Embedder::new(options, cache_cap)?)
+                Self::HuggingFace(hf::Embedder::new(options, cache)?)
             }
             EmbedderOptions::OpenAi(options) => {
-                Self::OpenAi(openai::Embedder::new(options, cache_cap)?)
+                Self::OpenAi(openai::Embedder::new(options, cache)?)
             }
             EmbedderOptions::Ollama(options) => {
-                Self::Ollama(ollama::Embedder::new(options, cache_cap)?)
+                Self::Ollama(ollama::Embedder::new(options, cache)?)
             }
             EmbedderOptions::UserProvided(options) => {
                 Self::UserProvided(manual::Embedder::new(options))
             }
-            EmbedderOptions::Rest(options) => Self::Rest(rest::Embedder::new(
-                options,
-                cache_cap,
-                rest::ConfigurationSource::User,
-            )?),
+            EmbedderOptions::Rest(options) => {
+                Self::Rest(rest::Embedder::new(options, cache, rest::ConfigurationSource::User)?)
+            }
             EmbedderOptions::Composite(options) => {
-                Self::Composite(composite::Embedder::new(options, cache_cap)?)
+                Self::Composite(composite::Embedder::new(options, cache_cap, persistent_cache)?)
             }
+            EmbedderOptions::Onnx(options) => Self::Onnx(onnx::Embedder::new(options, cache)?),
         })
     }
 
@@ -711,8 +1036,12 @@ impl Embedder {
         text: &str,
         deadline: Option<Instant>,
     ) -> std::result::Result<Embedding, EmbedError> {
+        let cache_key = match self {
+            Embedder::Rest(embedder) => embedder.search_cache_key(text)?,
+            _ => Cow::Borrowed(text),
+        };
         if let Some(cache) = self.cache() {
-            if let Some(embedding) = cache.get(text) {
+            if let Some(embedding) = cache.get(&cache_key) {
                 tracing::trace!(text, "embedding found in cache");
                 return Ok(embedding);
             }
@@ -726,15 +1055,13 @@ impl Embedder {
                 embedder.embed(&[text], deadline)?.pop().ok_or_else(EmbedError::missing_embedding)
             }
             Embedder::UserProvided(embedder) => embedder.embed_one(text),
-            Embedder::Rest(embedder) => embedder
-                .embed_ref(&[text], deadline)?
-                .pop()
-                .ok_or_else(EmbedError::missing_embedding),
+            Embedder::Rest(embedder) => embedder.embed_search(text, deadline),
             Embedder::Composite(embedder) => embedder.search.embed_one(text, deadline),
+            Embedder::Onnx(embedder) => embedder.embed_one(text),
         }?;
 
         if let Some(cache) = self.cache() {
-            cache.put(text.to_owned(), embedding.clone());
+            cache.put(cache_key.into_owned(), embedding.clone());
         }
 
         Ok(embedding)
@@ -743,10 +1070,61 @@ impl Embedder {
     /// Embed multiple chunks of texts.
     ///
     /// Each chunk is composed of one or multiple texts.
+    ///
+    /// Only the texts missing from the persistent cache are sent to the embedder.
     pub fn embed_index(
         &self,
         text_chunks: Vec<Vec<String>>,
         threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        let Some(cache) = self.persistent_cache() else {
+            return self.embed_index_uncached(text_chunks, threads);
+        };
+
+        let mut embeddings: Vec<Vec<Option<Embedding>>> = text_chunks
+            .iter()
+            .map(|chunk| cache.get_many(chunk.iter().map(String::as_str)))
+            .collect();
+        let missing_chunks: Vec<Vec<String>> = text_chunks
+            .into_iter()
+            .zip(&embeddings)
+            .map(|(chunk, embeddings)| {
+                chunk
+                    .into_iter()
+                    .zip(embeddings)
+                    .filter_map(|(text, embedding)| embedding.is_none().then_some(text))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|chunk| !chunk.is_empty())
+            .collect();
+
+        if !missing_chunks.is_empty() {
+            let missing_texts = missing_chunks.concat();
+            let computed: Vec<Embedding> =
+                self.embed_index_uncached(missing_chunks, threads)?.into_iter().flatten().collect();
+            cache.put_many(missing_texts.iter().map(String::as_str).zip(&computed));
+            let mut computed = computed.into_iter();
+            for embedding in embeddings.iter_mut().flatten().filter(|embedding| embedding.is_none())
+            {
+                *embedding = computed.next();
+            }
+        }
+
+        embeddings
+            .into_iter()
+            .map(|chunk| {
+                chunk
+                    .into_iter()
+                    .map(|embedding| embedding.ok_or_else(EmbedError::missing_embedding))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            })
+            .collect()
+    }
+
+    fn embed_index_uncached(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
     ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
         match self {
             Embedder::HuggingFace(embedder) => embedder.embed_index(text_chunks),
@@ -755,6 +1133,7 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.embed_index(text_chunks),
             Embedder::Rest(embedder) => embedder.embed_index(text_chunks, threads),
             Embedder::Composite(embedder) => embedder.index.embed_index(text_chunks, threads),
+            Embedder::Onnx(embedder) => embedder.embed_index(text_chunks),
         }
     }
 
@@ -763,6 +1142,37 @@ impl Embedder {
         &self,
         texts: &[&str],
         threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        let Some(cache) = self.persistent_cache() else {
+            return self.embed_index_ref_uncached(texts, threads);
+        };
+
+        let mut embeddings = cache.get_many(texts.iter().copied());
+        let missing_texts: Vec<&str> = texts
+            .iter()
+            .zip(&embeddings)
+            .filter_map(|(text, embedding)| embedding.is_none().then_some(*text))
+            .collect();
+
+        if !missing_texts.is_empty() {
+            let computed = self.embed_index_ref_uncached(&missing_texts, threads)?;
+            cache.put_many(missing_texts.iter().copied().zip(&computed));
+            let mut computed = computed.into_iter();
+            for embedding in embeddings.iter_mut().filter(|embedding| embedding.is_none()) {
+                *embedding = computed.next();
+            }
+        }
+
+        embeddings
+            .into_iter()
+            .map(|embedding| embedding.ok_or_else(EmbedError::missing_embedding))
+            .collect()
+    }
+
+    fn embed_index_ref_uncached(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
     ) -> std::result::Result<Vec<Embedding>, EmbedError> {
         match self {
             Embedder::HuggingFace(embedder) => embedder.embed_index_ref(texts),
@@ -771,6 +1181,107 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.embed_index_ref(texts),
             Embedder::Rest(embedder) => embedder.embed_index_ref(texts, threads),
             Embedder::Composite(embedder) => embedder.index.embed_index_ref(texts, threads),
+            Embedder::Onnx(embedder) => embedder.embed_index_ref(texts),
+        }
+    }
+
+    /// Embed in search context with an embedder producing sparse embeddings.
+    #[tracing::instrument(level = "debug", skip_all, target = "search")]
+    pub fn embed_search_sparse(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> std::result::Result<SparseEmbedding, EmbedError> {
+        match self {
+            Embedder::Rest(embedder) if embedder.is_sparse() => {
+                embedder.embed_search_sparse(text, deadline)
+            }
+            _ => Err(EmbedError::not_sparse()),
+        }
+    }
+
+    /// Sparse variant of [`Self::embed_index`].
+    pub fn embed_index_sparse(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Vec<SparseEmbedding>>, EmbedError> {
+        match self {
+            Embedder::Rest(embedder) if embedder.is_sparse() => {
+                embedder.embed_index_sparse(text_chunks, threads)
+            }
+            _ => Err(EmbedError::not_sparse()),
+        }
+    }
+
+    /// Sparse variant of [`Self::embed_index_ref`].
+    pub fn embed_index_ref_sparse(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<SparseEmbedding>, EmbedError> {
+        match self {
+            Embedder::Rest(embedder) if embedder.is_sparse() => {
+                embedder.embed_index_ref_sparse(texts, threads)
+            }
+            _ => Err(EmbedError::not_sparse()),
+        }
+    }
+
+    /// Embed in search context with a multi-vector embedder, producing several embeddings for the text.
+    #[tracing::instrument(level = "debug", skip_all, target = "search")]
+    pub fn embed_search_multi(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        match self {
+            Embedder::Rest(embedder) if embedder.is_multi_vector() => {
+                embedder.embed_search_multi(text, deadline)
+            }
+            Embedder::UserProvided(embedder) if embedder.is_multi_vector() => {
+                embedder.embed_one(text).map(|embedding| vec![embedding])
+            }
+            _ => Err(EmbedError::not_multi_vector()),
+        }
+    }
+
+    /// Multi-vector variant of [`Self::embed_index`].
+    pub fn embed_index_multi(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Vec<Vec<Embedding>>>, EmbedError> {
+        match self {
+            Embedder::Rest(embedder) if embedder.is_multi_vector() => {
+                embedder.embed_index_multi(text_chunks, threads)
+            }
+            Embedder::UserProvided(embedder) if embedder.is_multi_vector() => {
+                let chunks = embedder.embed_index(text_chunks)?;
+                Ok(chunks
+                    .into_iter()
+                    .map(|chunk| chunk.into_iter().map(|embedding| vec![embedding]).collect())
+                    .collect())
+            }
+            _ => Err(EmbedError::not_multi_vector()),
+        }
+    }
+
+    /// Multi-vector variant of [`Self::embed_index_ref`].
+    pub fn embed_index_ref_multi(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        match self {
+            Embedder::Rest(embedder) if embedder.is_multi_vector() => {
+                embedder.embed_index_ref_multi(texts, threads)
+            }
+            Embedder::UserProvided(embedder) if embedder.is_multi_vector() => {
+                let embeddings = embedder.embed_index_ref(texts)?;
+                Ok(embeddings.into_iter().map(|embedding| vec![embedding]).collect())
+            }
+            _ => Err(EmbedError::not_multi_vector()),
         }
     }
 
@@ -783,6 +1294,7 @@ impl Embedder {
             Embedder::UserProvided(_) => 100,
             Embedder::Rest(embedder) => embedder.chunk_count_hint(),
             Embedder::Composite(embedder) => embedder.index.chunk_count_hint(),
+            Embedder::Onnx(embedder) => embedder.chunk_count_hint(),
         }
     }
 
@@ -795,6 +1307,7 @@ impl Embedder {
             Embedder::UserProvided(_) => 1,
             Embedder::Rest(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::Composite(embedder) => embedder.index.prompt_count_in_chunk_hint(),
+            Embedder::Onnx(embedder) => embedder.prompt_count_in_chunk_hint(),
         }
     }
 
@@ -807,6 +1320,7 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.dimensions(),
             Embedder::Rest(embedder) => embedder.dimensions(),
             Embedder::Composite(embedder) => embedder.dimensions(),
+            Embedder::Onnx(embedder) => embedder.dimensions(),
         }
     }
 
@@ -819,6 +1333,37 @@ impl Embedder {
             Embedder::UserProvided(embedder) => embedder.distribution(),
             Embedder::Rest(embedder) => embedder.distribution(),
             Embedder::Composite(embedder) => embedder.distribution(),
+            Embedder::Onnx(embedder) => embedder.distribution(),
+        }
+    }
+
+    /// Whether the embedder produces sparse embeddings, stored in a [`SparseVectorStore`] rather than in arroy.
+    ///
+    /// The `embed_*_sparse` methods must be used to embed texts with such an embedder.
+    pub fn is_sparse(&self) -> bool {
+        match self {
+            Embedder::Rest(embedder) => embedder.is_sparse(),
+            Embedder::HuggingFace(_)
+            | Embedder::OpenAi(_)
+            | Embedder::Ollama(_)
+            | Embedder::UserProvided(_)
+            | Embedder::Composite(_)
+            | Embedder::Onnx(_) => false,
+        }
+    }
+
+    /// Whether documents and queries are represented by several embeddings, and compared with MaxSim.
+    ///
+    /// The `embed_*_multi` methods must be used to embed texts with such an embedder.
+    pub fn is_multi_vector(&self) -> bool {
+        match self {
+            Embedder::Rest(embedder) => embedder.is_multi_vector(),
+            Embedder::UserProvided(embedder) => embedder.is_multi_vector(),
+            Embedder::HuggingFace(_)
+            | Embedder::OpenAi(_)
+            | Embedder::Ollama(_)
+            | Embedder::Composite(_)
+            | Embedder::Onnx(_) => false,
         }
     }
 
@@ -827,7 +1372,8 @@ impl Embedder {
             Embedder::HuggingFace(_)
             | Embedder::OpenAi(_)
             | Embedder::Ollama(_)
-            | Embedder::Rest(_) => true,
+            | Embedder::Rest(_)
+            | Embedder::Onnx(_) => true,
             Embedder::UserProvided(_) => false,
             Embedder::Composite(embedder) => embedder.index.uses_document_template(),
         }
@@ -841,6 +1387,22 @@ impl Embedder {
             Embedder::Ollama(embedder) => Some(embedder.cache()),
             Embedder::Rest(embedder) => Some(embedder.cache()),
             Embedder::Composite(embedder) => embedder.search.cache(),
+            Embedder::Onnx(embedder) => Some(embedder.cache()),
+        }
+    }
+
+    /// The persistent cache of the embeddings computed at indexing time, if any.
+    fn persistent_cache(&self) -> Option<&EmbedderPersistentCache> {
+        match self {
+            Embedder::HuggingFace(embedder) => embedder.cache().persistent(),
+            Embedder::OpenAi(embedder) => embedder.cache().persistent(),
+            Embedder::UserProvided(_) => None,
+            Embedder::Ollama(embedder) => embedder.cache().persistent(),
+            Embedder::Rest(embedder) => embedder.cache().persistent(),
+            Embedder::Composite(embedder) => {
+                embedder.index.cache().and_then(EmbeddingCache::persistent)
+            }
+            Embedder::Onnx(embedder) => embedder.cache().persistent(),
         }
     }
 }
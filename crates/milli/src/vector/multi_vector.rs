@@ -0,0 +1,77 @@
+use super::Embedding;
+
+/// Computes the cosine similarity of two embeddings, or `0.0` when one of them is null.
+pub fn cosine_similarity(left: &[f32], right: &[f32]) -> f32 {
+    let (mut dot, mut left_norm, mut right_norm) = (0.0, 0.0, 0.0);
+    for (left, right) in left.iter().zip(right) {
+        dot += left * right;
+        left_norm += left * left;
+        right_norm += right * right;
+    }
+    if left_norm == 0.0 || right_norm == 0.0 {
+        return 0.0;
+    }
+    dot / (left_norm.sqrt() * right_norm.sqrt())
+}
+
+/// Computes the cosine distance of two embeddings, in the same range as the distances returned by arroy.
+pub fn cosine_distance(left: &[f32], right: &[f32]) -> f32 {
+    (1.0 - cosine_similarity(left, right)) / 2.0
+}
+
+/// Late interaction score of a document for a query, both represented by several embeddings.
+///
+/// Each embedding of the query is matched with its most similar embedding in the document,
+/// and the score is the mean of these similarities, so that it stays comparable between queries
+/// of different lengths.
+pub fn max_sim(query: &[Embedding], document: &[Embedding]) -> f32 {
+    if query.is_empty() {
+        return 0.0;
+    }
+    let total: f32 = query
+        .iter()
+        .map(|query| {
+            document
+                .iter()
+                .map(|document| cosine_similarity(query, document))
+                .fold(None, |max: Option<f32>, similarity| {
+                    Some(max.map_or(similarity, |max| max.max(similarity)))
+                })
+                .unwrap_or(0.0)
+        })
+        .sum();
+    total / query.len() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cosine() {
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]), 1.0);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 3.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[-1.0, 0.0]), -1.0);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn max_sim_averages_the_best_match_of_each_query_vector() {
+        let query = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+
+        // one document vector per query vector
+        let document = vec![vec![0.0, 1.0], vec![1.0, 0.0]];
+        assert_eq!(max_sim(&query, &document), 1.0);
+
+        // a single document vector matching only the first query vector
+        let document = vec![vec![1.0, 0.0]];
+        assert_eq!(max_sim(&query, &document), 0.5);
+
+        // adding a document vector never lowers the score
+        let document = vec![vec![1.0, 0.0], vec![-1.0, 0.0]];
+        assert_eq!(max_sim(&query, &document), 0.5);
+
+        assert_eq!(max_sim(&query, &[]), 0.0);
+        assert_eq!(max_sim(&[], &document), 0.0);
+    }
+}
@@ -70,15 +70,19 @@ impl EmbedderOptions {
             request,
             response,
             headers: Default::default(),
+            sparse: false,
+            multi_vector: false,
+            indexing_fragment: false,
+            search_fragment: None,
         })
     }
 }
 
 impl Embedder {
-    pub fn new(options: EmbedderOptions, cache_cap: usize) -> Result<Self, NewEmbedderError> {
+    pub fn new(options: EmbedderOptions, cache: EmbeddingCache) -> Result<Self, NewEmbedderError> {
         let rest_embedder = match RestEmbedder::new(
             options.into_rest_embedder_config()?,
-            cache_cap,
+            cache,
             super::rest::ConfigurationSource::Ollama,
         ) {
             Ok(embedder) => embedder,
@@ -0,0 +1,399 @@
+//! Local ONNX models, available when compiled with the `onnx` feature.
+//!
+//! Without the feature, the options of the embedders are still known so that the settings and the dumps
+//! keep them, but creating an embedder fails.
+
+#[cfg(feature = "onnx")]
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "onnx")]
+use ort::session::builder::GraphOptimizationLevel;
+#[cfg(feature = "onnx")]
+use ort::session::Session;
+#[cfg(feature = "onnx")]
+use ort::value::Tensor;
+#[cfg(feature = "onnx")]
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+use super::error::{EmbedError, NewEmbedderError};
+use super::hf::OverridePooling;
+#[cfg(feature = "onnx")]
+use super::hf::{Pooling, PoolingConfig};
+use super::{DistributionShift, Embedding, EmbeddingCache};
+
+/// Maximum number of texts passed to the model in a single inference.
+#[cfg(feature = "onnx")]
+const BATCH_SIZE: usize = 16;
+
+/// Number of tokens texts are truncated to when the tokenizer does not define a truncation.
+#[cfg(feature = "onnx")]
+const DEFAULT_MAX_TOKENS: usize = 512;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    /// Path to a local directory containing the `.onnx` model and its `tokenizer.json`.
+    pub model: String,
+    pub pooling: OverridePooling,
+    pub normalize: bool,
+    pub dimensions: Option<usize>,
+    pub distribution: Option<DistributionShift>,
+}
+
+impl EmbedderOptions {
+    pub fn new(model: String) -> Self {
+        Self {
+            model,
+            pooling: OverridePooling::UseModel,
+            normalize: false,
+            dimensions: None,
+            distribution: None,
+        }
+    }
+}
+
+/// Perform embedding of documents and queries with a local ONNX model, on the CPU
+#[cfg(feature = "onnx")]
+pub struct Embedder {
+    session: Session,
+    tokenizer: Tokenizer,
+    options: EmbedderOptions,
+    dimensions: usize,
+    pooling: Pooling,
+    token_type_ids: bool,
+    cache: EmbeddingCache,
+}
+
+#[cfg(feature = "onnx")]
+impl std::fmt::Debug for Embedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Embedder")
+            .field("model", &self.options.model)
+            .field("tokenizer", &self.tokenizer)
+            .field("options", &self.options)
+            .field("pooling", &self.pooling)
+            .finish()
+    }
+}
+
+#[cfg(feature = "onnx")]
+impl Embedder {
+    pub fn new(
+        options: EmbedderOptions,
+        cache: EmbeddingCache,
+    ) -> std::result::Result<Self, NewEmbedderError> {
+        let directory = Path::new(&options.model);
+        let model_filename = ["model.onnx", "onnx/model.onnx"]
+            .into_iter()
+            .map(|filename| directory.join(filename))
+            .find(|filename| filename.is_file())
+            .ok_or_else(|| NewEmbedderError::onnx_model_not_found(directory.to_owned()))?;
+
+        let tokenizer_filename = directory.join("tokenizer.json");
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_filename)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename.clone(), inner))?;
+
+        let mut pooling = read_pooling(&options.model, directory.join("1_Pooling/config.json"))?;
+        pooling.override_with(options.pooling);
+
+        let session = Session::builder()
+            .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Level3))
+            .and_then(|builder| builder.commit_from_file(&model_filename))
+            .map_err(NewEmbedderError::onnx_load_model)?;
+        let token_type_ids = session.inputs.iter().any(|input| input.name == "token_type_ids");
+
+        tracing::debug!(model = options.model, filename = ?model_filename, pooling = ?pooling, "model config");
+
+        if let Some(pp) = tokenizer.get_padding_mut() {
+            pp.strategy = tokenizers::PaddingStrategy::BatchLongest
+        } else {
+            let pp = PaddingParams {
+                strategy: tokenizers::PaddingStrategy::BatchLongest,
+                ..Default::default()
+            };
+            tokenizer.with_padding(Some(pp));
+        }
+        if tokenizer.get_truncation().is_none() {
+            let truncation =
+                TruncationParams { max_length: DEFAULT_MAX_TOKENS, ..Default::default() };
+            tokenizer
+                .with_truncation(Some(truncation))
+                .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename, inner))?;
+        }
+
+        let mut this =
+            Self { session, tokenizer, options, dimensions: 0, pooling, token_type_ids, cache };
+
+        let embedding =
+            this.embed_one("test").map_err(NewEmbedderError::could_not_determine_dimension)?;
+        if let Some(dimensions) = this.options.dimensions {
+            if dimensions != embedding.len() {
+                return Err(NewEmbedderError::could_not_determine_dimension(
+                    EmbedError::unexpected_dimension(dimensions, embedding.len()),
+                ));
+            }
+        }
+        this.dimensions = embedding.len();
+
+        Ok(this)
+    }
+
+    pub fn embed(&self, texts: Vec<String>) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        self.embed_index_ref(&texts)
+    }
+
+    pub fn embed_one(&self, text: &str) -> std::result::Result<Embedding, EmbedError> {
+        self.embed_batch(&[text])?.pop().ok_or_else(EmbedError::missing_embedding)
+    }
+
+    /// Runs a single inference for all the texts, that are padded to the longest one.
+    fn embed_batch(&self, texts: &[&str]) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let encodings =
+            self.tokenizer.encode_batch(texts.to_vec(), true).map_err(EmbedError::tokenize)?;
+        let batch_size = encodings.len();
+        let token_count = encodings.first().map_or(0, |encoding| encoding.len());
+        let shape = vec![batch_size as i64, token_count as i64];
+
+        let mut token_ids = Vec::with_capacity(batch_size * token_count);
+        let mut attention_mask = Vec::with_capacity(batch_size * token_count);
+        for encoding in &encodings {
+            token_ids.extend(encoding.get_ids().iter().map(|&id| id as i64));
+            attention_mask.extend(encoding.get_attention_mask().iter().map(|&mask| mask as i64));
+        }
+
+        let mut inputs = vec![
+            ("input_ids", Tensor::from_array((shape.clone(), token_ids))),
+            ("attention_mask", Tensor::from_array((shape.clone(), attention_mask.clone()))),
+        ];
+        if self.token_type_ids {
+            inputs.push((
+                "token_type_ids",
+                Tensor::from_array((shape, vec![0i64; batch_size * token_count])),
+            ));
+        }
+        let inputs = inputs
+            .into_iter()
+            .map(|(name, tensor)| tensor.map(|tensor| (name, tensor)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(EmbedError::onnx_run)?;
+
+        let outputs = self.session.run(inputs).map_err(EmbedError::onnx_run)?;
+        let (output_shape, output) =
+            outputs[0].try_extract_raw_tensor::<f32>().map_err(EmbedError::onnx_run)?;
+
+        let mut embeddings: Vec<Embedding> = match *output_shape {
+            // the model already pools the embeddings
+            [n_sentences, hidden_size] if n_sentences as usize == batch_size => {
+                output.chunks_exact(hidden_size as usize).map(<[f32]>::to_vec).collect()
+            }
+            [n_sentences, n_tokens, hidden_size]
+                if n_sentences as usize == batch_size && n_tokens as usize == token_count =>
+            {
+                let hidden_size = hidden_size as usize;
+                output
+                    .chunks_exact(token_count * hidden_size)
+                    .zip(attention_mask.chunks_exact(token_count))
+                    .map(|(tokens, mask)| pool(self.pooling, tokens, mask, hidden_size))
+                    .collect()
+            }
+            _ => return Err(EmbedError::onnx_output_shape(output_shape.to_vec())),
+        };
+
+        if self.options.normalize {
+            embeddings.iter_mut().for_each(|embedding| normalize(embedding));
+        }
+
+        Ok(embeddings)
+    }
+
+    pub fn embed_index(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        text_chunks.into_iter().map(|prompts| self.embed(prompts)).collect()
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        1
+    }
+
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        BATCH_SIZE
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.options.distribution
+    }
+
+    pub(crate) fn embed_index_ref(&self, texts: &[&str]) -> Result<Vec<Embedding>, EmbedError> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(BATCH_SIZE) {
+            embeddings.extend(self.embed_batch(batch)?);
+        }
+        Ok(embeddings)
+    }
+
+    pub(super) fn cache(&self) -> &EmbeddingCache {
+        &self.cache
+    }
+}
+
+/// Stands for an ONNX embedder when compiled without the `onnx` feature, it can never be created.
+#[cfg(not(feature = "onnx"))]
+#[derive(Debug)]
+pub enum Embedder {}
+
+#[cfg(not(feature = "onnx"))]
+impl Embedder {
+    pub fn new(
+        _options: EmbedderOptions,
+        _cache: EmbeddingCache,
+    ) -> std::result::Result<Self, NewEmbedderError> {
+        Err(NewEmbedderError::onnx_disabled())
+    }
+
+    pub fn embed(&self, _texts: Vec<String>) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        match *self {}
+    }
+
+    pub fn embed_one(&self, _text: &str) -> std::result::Result<Embedding, EmbedError> {
+        match *self {}
+    }
+
+    pub fn embed_index(
+        &self,
+        _text_chunks: Vec<Vec<String>>,
+    ) -> std::result::Result<Vec<Vec<Embedding>>, EmbedError> {
+        match *self {}
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        match *self {}
+    }
+
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        match *self {}
+    }
+
+    pub fn dimensions(&self) -> usize {
+        match *self {}
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        match *self {}
+    }
+
+    pub(crate) fn embed_index_ref(&self, _texts: &[&str]) -> Result<Vec<Embedding>, EmbedError> {
+        match *self {}
+    }
+
+    pub(super) fn cache(&self) -> &EmbeddingCache {
+        match *self {}
+    }
+}
+
+#[cfg(feature = "onnx")]
+fn read_pooling(model: &str, pooling_filename: PathBuf) -> Result<Pooling, NewEmbedderError> {
+    if !pooling_filename.is_file() {
+        return Ok(Pooling::default());
+    }
+    let pooling = std::fs::read_to_string(&pooling_filename)
+        .map_err(|inner| NewEmbedderError::open_pooling_config(pooling_filename.clone(), inner))?;
+    let pooling: PoolingConfig = serde_json::from_str(&pooling).map_err(|inner| {
+        NewEmbedderError::deserialize_pooling_config(model.to_owned(), pooling_filename, inner)
+    })?;
+    Ok(pooling.into())
+}
+
+/// Pools the embeddings of the tokens of a single text, ignoring the padding tokens.
+#[cfg(feature = "onnx")]
+fn pool(pooling: Pooling, tokens: &[f32], mask: &[i64], hidden_size: usize) -> Embedding {
+    let mut tokens = tokens
+        .chunks_exact(hidden_size)
+        .zip(mask)
+        .filter_map(|(token, &mask)| (mask != 0).then_some(token));
+    let token_count = mask.iter().filter(|&&mask| mask != 0).count().max(1) as f32;
+
+    match pooling {
+        Pooling::Cls => {
+            tokens.next().map(<[f32]>::to_vec).unwrap_or_else(|| vec![0.0; hidden_size])
+        }
+        Pooling::LastToken => {
+            tokens.next_back().map(<[f32]>::to_vec).unwrap_or_else(|| vec![0.0; hidden_size])
+        }
+        Pooling::Max => tokens.fold(vec![f32::MIN; hidden_size], |mut pooled, token| {
+            pooled.iter_mut().zip(token).for_each(|(pooled, value)| *pooled = pooled.max(*value));
+            pooled
+        }),
+        Pooling::Mean | Pooling::MeanSqrtLen => {
+            let mut pooled = tokens.fold(vec![0.0; hidden_size], |mut pooled, token| {
+                pooled.iter_mut().zip(token).for_each(|(pooled, value)| *pooled += value);
+                pooled
+            });
+            let divisor =
+                if matches!(pooling, Pooling::Mean) { token_count } else { token_count.sqrt() };
+            pooled.iter_mut().for_each(|value| *value /= divisor);
+            pooled
+        }
+    }
+}
+
+#[cfg(feature = "onnx")]
+fn normalize(embedding: &mut [f32]) {
+    let norm = embedding.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        embedding.iter_mut().for_each(|value| *value /= norm);
+    }
+}
+
+#[cfg(all(test, feature = "onnx"))]
+mod tests {
+    use super::*;
+
+    /// A model whose token embeddings are `[id, -id]`, with a word level tokenizer.
+    fn tiny_model() -> String {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/tiny-onnx").to_string()
+    }
+
+    #[test]
+    fn inference_and_pooling() {
+        let embedder =
+            Embedder::new(EmbedderOptions::new(tiny_model()), EmbeddingCache::new(0, None))
+                .unwrap();
+        assert_eq!(embedder.dimensions(), 2);
+
+        // `world` is padded, the padding token is ignored by the mean
+        let embeddings =
+            embedder.embed(vec!["hello world".to_string(), "world".to_string()]).unwrap();
+        assert_eq!(embeddings, vec![vec![1.5, -1.5], vec![2.0, -2.0]]);
+
+        let mut options = EmbedderOptions::new(tiny_model());
+        options.pooling = OverridePooling::ForceCls;
+        options.normalize = true;
+        let embedder = Embedder::new(options, EmbeddingCache::new(0, None)).unwrap();
+        let embedding = embedder.embed_one("test hello").unwrap();
+        let expected = 3.0 / 18f32.sqrt();
+        assert_eq!(embedding, vec![expected, -expected]);
+    }
+
+    #[test]
+    fn pool_ignores_padding() {
+        let tokens = [1.0, -1.0, 3.0, -3.0, 0.0, 0.0];
+        let mask = [1, 1, 0];
+        assert_eq!(pool(Pooling::Mean, &tokens, &mask, 2), vec![2.0, -2.0]);
+        assert_eq!(pool(Pooling::Cls, &tokens, &mask, 2), vec![1.0, -1.0]);
+        assert_eq!(pool(Pooling::LastToken, &tokens, &mask, 2), vec![3.0, -3.0]);
+        assert_eq!(pool(Pooling::Max, &tokens, &mask, 2), vec![3.0, -1.0]);
+        assert_eq!(
+            pool(Pooling::MeanSqrtLen, &tokens, &mask, 2),
+            vec![4.0 / 2f32.sqrt(), -4.0 / 2f32.sqrt()]
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt;
+use std::path::Path;
+
+use heed::types::Bytes;
+use heed::{Database, DatabaseStat, Env, EnvFlags, EnvOpenOptions, RoTxn, RwTxn, WithoutTls};
+use serde::Serialize;
+
+use super::Embedding;
+use crate::BEU64;
+
+/// The size of the memory map of the cache, it must be accounted for in the virtual memory budget.
+const MAP_SIZE: usize = 64 * 1024 * 1024 * 1024; // 64 GiB
+const EMBEDDINGS: &str = "embeddings";
+const INSERTIONS: &str = "insertions";
+
+/// The share of the entries evicted when the cache grows larger than half of its map.
+const EVICTED_RATIO: u64 = 4;
+
+/// An on-disk cache of the embeddings computed by the embedders, kept across restarts.
+///
+/// The embeddings are keyed by a hash of the options of the embedder that computed them and by the text
+/// they represent, so that the cache is shared between the indexes, between indexing and search,
+/// and survives the deletion of the indexes, e.g. to import a dump.
+///
+/// The cache is best effort: a failure to read or write it is logged and the embedders are called instead.
+/// For the same reason, the writes are not synced to the disk, so that computing an embedding at search time
+/// does not wait for the disk, at the cost of losing the last embeddings on a crash.
+///
+/// Once the cache grows larger than half of its map, the oldest quarter of its entries is evicted.
+#[derive(Clone)]
+pub struct PersistentEmbeddingCache {
+    env: Env<WithoutTls>,
+    /// Maps a key made of the hash of the embedder options and the hash of the text to
+    /// the length of the text, the text, and the embedding.
+    embeddings: Database<Bytes, Bytes>,
+    /// Maps an increasing insertion number to the key of the embedding inserted, to evict the oldest ones.
+    insertions: Database<BEU64, Bytes>,
+}
+
+impl fmt::Debug for PersistentEmbeddingCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentEmbeddingCache").field("path", &self.env.path()).finish()
+    }
+}
+
+impl PersistentEmbeddingCache {
+    /// Opens the cache stored in the `path` directory, creating it if needed.
+    pub fn open(path: &Path) -> heed::Result<Self> {
+        Self::open_with_map_size(path, MAP_SIZE)
+    }
+
+    fn open_with_map_size(path: &Path, map_size: usize) -> heed::Result<Self> {
+        std::fs::create_dir_all(path)?;
+        let env = unsafe {
+            let env_options = EnvOpenOptions::new();
+            let mut env_options = env_options.read_txn_without_tls();
+            env_options.flags(EnvFlags::NO_SYNC | EnvFlags::NO_META_SYNC);
+            env_options.max_dbs(2).map_size(map_size).open(path)
+        }?;
+        let mut wtxn = env.write_txn()?;
+        let embeddings = env.create_database(&mut wtxn, Some(EMBEDDINGS))?;
+        let insertions = env.create_database(&mut wtxn, Some(INSERTIONS))?;
+        wtxn.commit()?;
+        Ok(Self { env, embeddings, insertions })
+    }
+
+    /// The size of the memory map of the cache.
+    pub fn map_size(&self) -> usize {
+        self.env.info().map_size
+    }
+
+    /// The part of the cache holding the embeddings computed by an embedder with the given options.
+    pub(crate) fn for_embedder<O: Serialize>(
+        &self,
+        options: &O,
+    ) -> serde_json::Result<EmbedderPersistentCache> {
+        // the `Hash` implementations of the options skip some of them, like the templates of the REST embedder,
+        // so the whole serialized options are hashed instead
+        let options = serde_json::to_vec(options)?;
+        Ok(EmbedderPersistentCache { cache: self.clone(), config_hash: fxhash::hash64(&options) })
+    }
+
+    /// Evicts the oldest entries once the cache is larger than half of its map,
+    /// so that it never fills its map.
+    fn evict_if_needed(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<()> {
+        let size =
+            compute_size(self.embeddings.stat(wtxn)?) + compute_size(self.insertions.stat(wtxn)?);
+        if size <= self.map_size() / 2 {
+            return Ok(());
+        }
+
+        let evicted_count = (self.insertions.len(wtxn)? / EVICTED_RATIO).max(1);
+        let mut last_evicted = None;
+        let mut evicted_keys = Vec::new();
+        for result in self.insertions.iter(wtxn)?.take(evicted_count as usize) {
+            let (insertion, key) = result?;
+            last_evicted = Some(insertion);
+            evicted_keys.push(key.to_vec());
+        }
+        for key in &evicted_keys {
+            self.embeddings.delete(wtxn, key)?;
+        }
+        if let Some(last_evicted) = last_evicted {
+            self.insertions.delete_range(wtxn, &(..=last_evicted))?;
+        }
+        tracing::debug!(
+            evicted = evicted_keys.len(),
+            "evicted embeddings from the persistent cache"
+        );
+        Ok(())
+    }
+}
+
+fn compute_size(stat: DatabaseStat) -> usize {
+    let DatabaseStat { page_size, depth: _, branch_pages, leaf_pages, overflow_pages, entries: _ } =
+        stat;
+    (branch_pages + leaf_pages + overflow_pages) * page_size as usize
+}
+
+/// The embeddings of the persistent cache computed by a single embedder configuration.
+#[derive(Debug, Clone)]
+pub(crate) struct EmbedderPersistentCache {
+    cache: PersistentEmbeddingCache,
+    config_hash: u64,
+}
+
+impl EmbedderPersistentCache {
+    /// Get the embedding corresponding to `text`, if any is present in the cache.
+    pub fn get(&self, text: &str) -> Option<Embedding> {
+        self.get_many(std::iter::once(text)).pop().flatten()
+    }
+
+    /// Get the embeddings corresponding to the `texts`, in the same order.
+    pub fn get_many<'t>(&self, texts: impl IntoIterator<Item = &'t str>) -> Vec<Option<Embedding>> {
+        let texts = texts.into_iter();
+        let rtxn = match self.cache.env.read_txn() {
+            Ok(rtxn) => rtxn,
+            Err(error) => {
+                tracing::warn!(%error, "could not read the persistent embedding cache");
+                return texts.map(|_| None).collect();
+            }
+        };
+        texts
+            .map(|text| match self.get_with_txn(&rtxn, text) {
+                Ok(embedding) => embedding,
+                Err(error) => {
+                    tracing::warn!(%error, "could not read the persistent embedding cache");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn get_with_txn(&self, rtxn: &RoTxn<'_>, text: &str) -> heed::Result<Option<Embedding>> {
+        let Some(value) = self.cache.embeddings.get(rtxn, &self.key(text))? else {
+            return Ok(None);
+        };
+        // the text is stored along the embedding to rule out hash collisions
+        let Some((cached_text, embedding)) = decode_value(value) else { return Ok(None) };
+        if cached_text != text.as_bytes() {
+            return Ok(None);
+        }
+        tracing::trace!(text, "embedding found in persistent cache");
+        Ok(Some(embedding))
+    }
+
+    /// Puts the embeddings of the specified texts in a single transaction.
+    ///
+    /// The embeddings of texts that are already in the cache are not written again.
+    pub fn put_many<'t>(&self, entries: impl IntoIterator<Item = (&'t str, &'t Embedding)>) {
+        let result = (|| -> heed::Result<()> {
+            let mut wtxn = self.cache.env.write_txn()?;
+            let mut insertion = match self.cache.insertions.last(&wtxn)? {
+                Some((last, _)) => last + 1,
+                None => 0,
+            };
+            let mut value = Vec::new();
+            let mut inserted = false;
+            for (text, embedding) in entries {
+                let key = self.key(text);
+                let cached = self.cache.embeddings.get(&wtxn, &key)?.and_then(decode_value);
+                if cached.is_some_and(|(cached_text, _)| cached_text == text.as_bytes()) {
+                    continue;
+                }
+                encode_value(&mut value, text, embedding);
+                self.cache.embeddings.put(&mut wtxn, &key, &value)?;
+                self.cache.insertions.put(&mut wtxn, &insertion, &key)?;
+                insertion += 1;
+                inserted = true;
+            }
+            if !inserted {
+                // no need to commit an empty transaction
+                return Ok(());
+            }
+            self.cache.evict_if_needed(&mut wtxn)?;
+            wtxn.commit()
+        })();
+        if let Err(error) = result {
+            tracing::warn!(%error, "could not write to the persistent embedding cache");
+        }
+    }
+
+    fn key(&self, text: &str) -> [u8; 16] {
+        let mut key = [0; 16];
+        key[..8].copy_from_slice(&self.config_hash.to_be_bytes());
+        key[8..].copy_from_slice(&fxhash::hash64(text).to_be_bytes());
+        key
+    }
+}
+
+fn encode_value(buffer: &mut Vec<u8>, text: &str, embedding: &Embedding) {
+    buffer.clear();
+    buffer.extend_from_slice(&(text.len() as u32).to_be_bytes());
+    buffer.extend_from_slice(text.as_bytes());
+    buffer.extend(embedding.iter().flat_map(|value| value.to_le_bytes()));
+}
+
+fn decode_value(value: &[u8]) -> Option<(&[u8], Embedding)> {
+    let (len, value) = value.split_first_chunk::<4>()?;
+    let len = u32::from_be_bytes(*len) as usize;
+    if value.len() < len {
+        return None;
+    }
+    let (text, embedding) = value.split_at(len);
+    let embedding = embedding
+        .chunks_exact(std::mem::size_of::<f32>())
+        .map(|bytes| f32::from_le_bytes(bytes.try_into().unwrap()))
+        .collect();
+    Some((text, embedding))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn shared_between_instances_with_the_same_options() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = PersistentEmbeddingCache::open(dir.path()).unwrap();
+
+        let embedder = cache.for_embedder(&"options").unwrap();
+        assert_eq!(embedder.get("hello"), None);
+        embedder.put_many([("hello", &vec![0.5, -1.0, 2.0]), ("world", &vec![1.0, 1.0, 1.0])]);
+
+        let same_embedder = cache.for_embedder(&"options").unwrap();
+        assert_eq!(
+            same_embedder.get_many(["world", "hello", "other"]),
+            vec![Some(vec![1.0, 1.0, 1.0]), Some(vec![0.5, -1.0, 2.0]), None]
+        );
+
+        let other_embedder = cache.for_embedder(&"other options").unwrap();
+        assert_eq!(other_embedder.get("hello"), None);
+    }
+
+    #[test]
+    fn evicts_the_oldest_embeddings() {
+        let dir = tempfile::tempdir().unwrap();
+        let map_size = 16 * 1024 * 1024;
+        let cache = PersistentEmbeddingCache::open_with_map_size(dir.path(), map_size).unwrap();
+        let embedder = cache.for_embedder(&"options").unwrap();
+
+        // about 4 KiB per embedding, the cache can't keep them all in half of its map
+        let embedding = vec![1.0; 1024];
+        let texts: Vec<_> = (0..4000).map(|i| i.to_string()).collect();
+        for text in &texts {
+            embedder.put_many([(text.as_str(), &embedding)]);
+        }
+
+        assert_eq!(embedder.get("0"), None);
+        assert_eq!(embedder.get("3999"), Some(embedding));
+
+        let rtxn = cache.env.read_txn().unwrap();
+        let size = compute_size(cache.embeddings.stat(&rtxn).unwrap())
+            + compute_size(cache.insertions.stat(&rtxn).unwrap());
+        assert!(size <= map_size / 2, "{size}");
+        assert_eq!(cache.embeddings.len(&rtxn).unwrap(), cache.insertions.len(&rtxn).unwrap());
+    }
+}
@@ -0,0 +1,424 @@
+use deserr::Deserr;
+use heed::types::Bytes;
+use heed::{Database, RoTxn, RwTxn};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::multi_vector::cosine_distance;
+use super::Embeddings;
+use crate::heed_codec::U8BEU32Codec;
+use crate::DocumentId;
+
+/// How many more candidates than requested are fetched from the quantized arroy trees before being rescored.
+pub const RESCORING_OVERSAMPLING: usize = 4;
+
+/// The quantization of the embeddings of an embedder.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum Quantization {
+    /// Embeddings are indexed with 32-bit floats.
+    #[default]
+    None,
+    /// Embeddings are indexed as bits, and the candidates are rescored against 8-bit integers.
+    Int8,
+    /// Embeddings are indexed as bits.
+    Binary,
+}
+
+impl std::fmt::Display for Quantization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Quantization::None => "none",
+            Quantization::Int8 => "int8",
+            Quantization::Binary => "binary",
+        };
+        f.write_str(s)
+    }
+}
+
+/// How the embeddings of an embedder are stored, as determined by its quantization settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VectorQuantization {
+    pub quantization: Quantization,
+    /// Whether full-precision embeddings are kept to rescore the candidates.
+    pub rescoring: bool,
+}
+
+impl VectorQuantization {
+    /// Whether the arroy trees of the embedder are binary quantized.
+    pub fn is_binary_quantized(&self) -> bool {
+        self.quantization != Quantization::None
+    }
+
+    /// The precision of the embeddings kept to rescore the candidates found in the arroy trees, if any.
+    pub fn rescoring_precision(&self) -> Option<RescoringPrecision> {
+        match (self.quantization, self.rescoring) {
+            (Quantization::None, _) => None,
+            (_, true) => Some(RescoringPrecision::Full),
+            (Quantization::Int8, false) => Some(RescoringPrecision::Int8),
+            (Quantization::Binary, false) => None,
+        }
+    }
+
+    /// Whether the embeddings stored for `self` can be computed from the embeddings stored for `old`.
+    ///
+    /// Quantization is lossy, so the precision of the stored embeddings can only decrease.
+    pub fn can_be_converted_from(&self, old: &VectorQuantization) -> bool {
+        if self == old || !old.is_binary_quantized() {
+            return true;
+        }
+        self.is_binary_quantized() && self.rescoring_precision() <= old.rescoring_precision()
+    }
+}
+
+impl std::fmt::Display for VectorQuantization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.quantization)?;
+        if self.rescoring {
+            f.write_str(" with rescoring")?;
+        }
+        Ok(())
+    }
+}
+
+/// The precision of the embeddings kept to rescore the candidates of a quantized embedder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RescoringPrecision {
+    /// Each component is scaled to an 8-bit integer, with a scale factor per embedding.
+    Int8,
+    /// Each component is kept as a 32-bit float.
+    Full,
+}
+
+impl RescoringPrecision {
+    fn embedding_len(&self, dimension: usize) -> usize {
+        match self {
+            RescoringPrecision::Int8 => size_of::<f32>() + dimension,
+            RescoringPrecision::Full => size_of::<f32>() * dimension,
+        }
+    }
+
+    fn encode(&self, embedding: &[f32], output: &mut Vec<u8>) {
+        match self {
+            RescoringPrecision::Int8 => {
+                let (scale, codes) = int8_quantize(embedding);
+                output.extend_from_slice(&scale.to_le_bytes());
+                output.extend(codes.into_iter().map(|code| code as u8));
+            }
+            RescoringPrecision::Full => {
+                output.extend(embedding.iter().flat_map(|component| component.to_le_bytes()))
+            }
+        }
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Vec<f32> {
+        match self {
+            RescoringPrecision::Int8 => {
+                let (scale, codes) = bytes.split_at(size_of::<f32>());
+                let scale = f32::from_le_bytes(scale.try_into().unwrap());
+                codes.iter().map(|code| *code as i8 as f32 * scale).collect()
+            }
+            RescoringPrecision::Full => bytes
+                .chunks_exact(size_of::<f32>())
+                .map(|component| f32::from_le_bytes(component.try_into().unwrap()))
+                .collect(),
+        }
+    }
+}
+
+/// Scalar quantization of an embedding to 8-bit integers, returning the scale and the codes.
+///
+/// The scale is chosen so that the component with the largest magnitude maps to ±127.
+pub fn int8_quantize(embedding: &[f32]) -> (f32, Vec<i8>) {
+    let max = embedding.iter().fold(0.0f32, |max, component| max.max(component.abs()));
+    if max == 0.0 || !max.is_finite() {
+        return (0.0, vec![0; embedding.len()]);
+    }
+    let scale = max / i8::MAX as f32;
+    let codes = embedding
+        .iter()
+        .map(|component| (component / scale).round().clamp(-127.0, 127.0) as i8)
+        .collect();
+    (scale, codes)
+}
+
+/// Keeps the embeddings of a quantized embedder at a higher precision than its arroy trees,
+/// so that the nearest neighbors found in the trees can be rescored.
+///
+/// All the embeddings of a document are stored under a single key, after their dimension,
+/// in the same order as in the arroy trees.
+pub struct RescoringStore {
+    embedder_index: u8,
+    precision: RescoringPrecision,
+    database: Database<U8BEU32Codec, Bytes>,
+}
+
+impl RescoringStore {
+    pub fn new(
+        database: Database<U8BEU32Codec, Bytes>,
+        embedder_index: u8,
+        precision: RescoringPrecision,
+    ) -> Self {
+        Self { embedder_index, precision, database }
+    }
+
+    pub fn precision(&self) -> RescoringPrecision {
+        self.precision
+    }
+
+    /// Overwrite the embeddings of a document.
+    ///
+    /// Like the arroy trees, at most 256 embeddings are kept.
+    pub fn put_embeddings(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        embeddings: &Embeddings<f32>,
+    ) -> heed::Result<()> {
+        let dimension = embeddings.dimension();
+        let mut value = (dimension as u32).to_le_bytes().to_vec();
+        for embedding in embeddings.iter().take(u8::MAX as usize + 1) {
+            self.precision.encode(embedding, &mut value);
+        }
+        self.database.put(wtxn, &(self.embedder_index, docid), &value)
+    }
+
+    /// Append an embedding to the embeddings of a document.
+    pub fn push_embedding(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        embedding: &[f32],
+    ) -> heed::Result<()> {
+        let mut value = match self.database.get(wtxn, &(self.embedder_index, docid))? {
+            Some(value) => value.to_vec(),
+            None => (embedding.len() as u32).to_le_bytes().to_vec(),
+        };
+        if self.embedding_count(&value) > u8::MAX as usize {
+            return Ok(());
+        }
+        self.precision.encode(embedding, &mut value);
+        self.database.put(wtxn, &(self.embedder_index, docid), &value)
+    }
+
+    /// Delete one embedding of a document, replacing it by the last one as the arroy trees do.
+    pub fn del_embedding(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        embedding: &[f32],
+    ) -> heed::Result<()> {
+        let Some(value) = self.database.get(wtxn, &(self.embedder_index, docid))? else {
+            return Ok(());
+        };
+        let (header, embeddings) = value.split_at(size_of::<u32>());
+        let embedding_len = self.precision.embedding_len(embedding.len());
+
+        let mut encoded = Vec::with_capacity(embedding_len);
+        self.precision.encode(embedding, &mut encoded);
+
+        let mut embeddings: Vec<&[u8]> = embeddings.chunks_exact(embedding_len).collect();
+        let Some(position) = embeddings.iter().position(|candidate| *candidate == encoded) else {
+            return Ok(());
+        };
+        embeddings.swap_remove(position);
+
+        if embeddings.is_empty() {
+            self.database.delete(wtxn, &(self.embedder_index, docid))?;
+        } else {
+            let value =
+                [header].into_iter().chain(embeddings).flatten().copied().collect::<Vec<_>>();
+            self.database.put(wtxn, &(self.embedder_index, docid), &value)?;
+        }
+        Ok(())
+    }
+
+    /// Delete all the embeddings of a document.
+    pub fn del_embeddings(&self, wtxn: &mut RwTxn, docid: DocumentId) -> heed::Result<bool> {
+        self.database.delete(wtxn, &(self.embedder_index, docid))
+    }
+
+    /// Delete the embeddings of all the documents of the embedder.
+    pub fn clear(&self, wtxn: &mut RwTxn) -> heed::Result<()> {
+        let prefix = [self.embedder_index];
+        let mut iter = self.database.remap_key_type::<Bytes>().prefix_iter_mut(wtxn, &prefix)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference from the database.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+
+    /// Re-encode the embeddings of all the documents to another precision.
+    ///
+    /// As the embeddings are decoded first, the new precision should not be higher than the current one.
+    pub fn convert(&self, wtxn: &mut RwTxn, precision: RescoringPrecision) -> heed::Result<Self> {
+        let converted = Self { precision, ..*self };
+        if precision == self.precision {
+            return Ok(converted);
+        }
+
+        let prefix = [self.embedder_index];
+        let mut iter = self.database.remap_key_type::<Bytes>().prefix_iter_mut(wtxn, &prefix)?;
+        while let Some((key, value)) = iter.next().transpose()? {
+            let mut converted_value = value[..size_of::<u32>()].to_vec();
+            for embedding in self.decode(value) {
+                precision.encode(&embedding, &mut converted_value);
+            }
+            let key = key.to_vec();
+            // safety: we copied the key and do not keep a reference to the value.
+            unsafe { iter.put_current(&key, &converted_value)? };
+        }
+        Ok(converted)
+    }
+
+    /// Returns the embeddings of a document, in the order of the arroy trees.
+    pub fn embeddings(
+        &self,
+        rtxn: &RoTxn,
+        docid: DocumentId,
+    ) -> heed::Result<Option<Vec<Vec<f32>>>> {
+        let value = self.database.get(rtxn, &(self.embedder_index, docid))?;
+        Ok(value.map(|value| self.decode(value)))
+    }
+
+    /// Replaces the distances of the candidates found in the arroy trees by the cosine distance
+    /// between the query and the stored embeddings, returning the candidates sorted by distance.
+    ///
+    /// Candidates whose embedding is missing keep their distance.
+    pub fn rescore(
+        &self,
+        rtxn: &RoTxn,
+        query: &[f32],
+        mut candidates: Vec<(DocumentId, u8, f32)>,
+    ) -> heed::Result<Vec<(DocumentId, u8, f32)>> {
+        let mut embeddings = std::collections::HashMap::new();
+        for (docid, vector_index, distance) in &mut candidates {
+            if !embeddings.contains_key(docid) {
+                embeddings.insert(*docid, self.embeddings(rtxn, *docid)?.unwrap_or_default());
+            }
+            if let Some(embedding) = embeddings[docid].get(*vector_index as usize) {
+                *distance = cosine_distance(query, embedding);
+            }
+        }
+        candidates.sort_unstable_by(|(left_docid, _, left), (right_docid, _, right)| {
+            left.total_cmp(right).then(left_docid.cmp(right_docid))
+        });
+        Ok(candidates)
+    }
+
+    /// Replaces the distances of the candidates found in the arroy trees for a document by the
+    /// smallest cosine distance between the embeddings of the document and of the candidate
+    /// stored at the same position, returning the candidates sorted by distance.
+    ///
+    /// Candidates whose embeddings are missing keep their distance.
+    pub fn rescore_by_document(
+        &self,
+        rtxn: &RoTxn,
+        query: &[Vec<f32>],
+        mut candidates: Vec<(DocumentId, f32)>,
+    ) -> heed::Result<Vec<(DocumentId, f32)>> {
+        for (docid, distance) in &mut candidates {
+            let Some(embeddings) = self.embeddings(rtxn, *docid)? else { continue };
+            let rescored = query
+                .iter()
+                .zip(&embeddings)
+                .map(|(query, embedding)| cosine_distance(query, embedding))
+                .reduce(f32::min);
+            if let Some(rescored) = rescored {
+                *distance = rescored;
+            }
+        }
+        candidates.sort_unstable_by(|(left_docid, left), (right_docid, right)| {
+            left.total_cmp(right).then(left_docid.cmp(right_docid))
+        });
+        Ok(candidates)
+    }
+
+    fn embedding_count(&self, value: &[u8]) -> usize {
+        let (header, embeddings) = value.split_at(size_of::<u32>());
+        let dimension = u32::from_le_bytes(header.try_into().unwrap()) as usize;
+        embeddings.len() / self.precision.embedding_len(dimension)
+    }
+
+    fn decode(&self, value: &[u8]) -> Vec<Vec<f32>> {
+        let (header, embeddings) = value.split_at(size_of::<u32>());
+        let dimension = u32::from_le_bytes(header.try_into().unwrap()) as usize;
+        embeddings
+            .chunks_exact(self.precision.embedding_len(dimension))
+            .map(|embedding| self.precision.decode(embedding))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::tests::TempIndex;
+    use crate::vector::multi_vector::cosine_similarity;
+
+    #[test]
+    fn int8_quantization_keeps_the_direction() {
+        let embedding = [0.5, -1.0, 0.25, 0.0];
+        let (scale, codes) = int8_quantize(&embedding);
+        assert_eq!(codes, vec![64, -127, 32, 0]);
+        let decoded: Vec<f32> = codes.iter().map(|code| *code as f32 * scale).collect();
+        assert!(cosine_similarity(&embedding, &decoded) > 0.9999);
+
+        assert_eq!(int8_quantize(&[0.0, 0.0]), (0.0, vec![0, 0]));
+    }
+
+    #[test]
+    fn precision_can_only_decrease() {
+        let none = VectorQuantization::default();
+        let int8 = VectorQuantization { quantization: Quantization::Int8, rescoring: false };
+        let binary = VectorQuantization { quantization: Quantization::Binary, rescoring: false };
+        let rescored = VectorQuantization { quantization: Quantization::Binary, rescoring: true };
+
+        for quantization in [none, int8, binary, rescored] {
+            assert!(quantization.can_be_converted_from(&none));
+            assert!(quantization.can_be_converted_from(&quantization));
+        }
+        assert!(binary.can_be_converted_from(&int8));
+        assert!(int8.can_be_converted_from(&rescored));
+        assert!(!none.can_be_converted_from(&binary));
+        assert!(!int8.can_be_converted_from(&binary));
+        assert!(!rescored.can_be_converted_from(&int8));
+    }
+
+    #[test]
+    fn store_rescores_candidates() {
+        let index = TempIndex::new();
+        let mut wtxn = index.write_txn().unwrap();
+        let store = RescoringStore::new(index.vector_rescoring, 0, RescoringPrecision::Full);
+        let other_store = RescoringStore::new(index.vector_rescoring, 1, RescoringPrecision::Full);
+
+        let embeddings = Embeddings::from_inner(vec![1.0, 0.0, 0.0, 1.0], 2).unwrap();
+        store.put_embeddings(&mut wtxn, 0, &embeddings).unwrap();
+        store.push_embedding(&mut wtxn, 1, &[1.0, 1.0]).unwrap();
+        other_store.push_embedding(&mut wtxn, 0, &[-1.0, 0.0]).unwrap();
+
+        assert_eq!(store.embeddings(&wtxn, 0).unwrap(), Some(vec![vec![1.0, 0.0], vec![0.0, 1.0]]));
+
+        // the distances found in the arroy trees are all the same
+        let candidates = vec![(0, 0, 0.25), (0, 1, 0.25), (1, 0, 0.25), (2, 0, 0.25)];
+        let rescored = store.rescore(&wtxn, &[0.0, 1.0], candidates).unwrap();
+        let docids: Vec<_> = rescored.iter().map(|(docid, index, _)| (*docid, *index)).collect();
+        assert_eq!(docids, vec![(0, 1), (1, 0), (2, 0), (0, 0)]);
+
+        // the last embedding replaces the deleted one
+        store.push_embedding(&mut wtxn, 0, &[1.0, 1.0]).unwrap();
+        store.del_embedding(&mut wtxn, 0, &[1.0, 0.0]).unwrap();
+        assert_eq!(store.embeddings(&wtxn, 0).unwrap(), Some(vec![vec![1.0, 1.0], vec![0.0, 1.0]]));
+
+        let store = store.convert(&mut wtxn, RescoringPrecision::Int8).unwrap();
+        let embeddings = store.embeddings(&wtxn, 0).unwrap().unwrap();
+        assert_eq!(embeddings.len(), 2);
+        assert!(cosine_similarity(&embeddings[0], &[1.0, 1.0]) > 0.9999);
+
+        store.clear(&mut wtxn).unwrap();
+        assert_eq!(store.embeddings(&wtxn, 0).unwrap(), None);
+        assert_eq!(other_store.embeddings(&wtxn, 0).unwrap(), Some(vec![vec![-1.0, 0.0]]));
+    }
+}
@@ -0,0 +1,111 @@
+use heed::RoTxn;
+use rand::rngs::StdRng;
+use rand::SeedableRng as _;
+use roaring::RoaringBitmap;
+
+use super::{ArroyStats, ArroyWrapper, Embedder, VectorQuantization};
+use crate::{DocumentId, Index, Result, UserError};
+
+/// The seed used to sample the queries, so that successive measures use the same queries.
+const RECALL_SEED: u64 = 42;
+
+/// How many of the exact nearest neighbors the approximate search of an embedder finds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Recall {
+    /// The number of neighbors searched for each query.
+    pub k: usize,
+    /// The number of queries that were run.
+    pub sampled_queries: usize,
+    /// The ratio of the exact nearest neighbors found by the approximate search, between `0.0` and `1.0`.
+    ///
+    /// `None` when no neighbor could be searched for, because less than two documents have embeddings.
+    pub recall: Option<f64>,
+}
+
+/// Measures the recall@k of the approximate nearest neighbors search of an embedder,
+/// against an exact search over all the documents that have embeddings.
+///
+/// The queries are the embeddings of documents sampled at random, and the document
+/// of a query is never counted as one of its neighbors.
+///
+/// Sparse embedders are always searched exactly, so no query is sampled for them.
+pub fn measure_recall(
+    index: &Index,
+    rtxn: &RoTxn<'_>,
+    embedder_name: &str,
+    embedder: &Embedder,
+    quantization: VectorQuantization,
+    sample_size: usize,
+    k: usize,
+) -> Result<Recall> {
+    let embedder_index = index
+        .embedder_category_id
+        .get(rtxn, embedder_name)?
+        .ok_or_else(|| UserError::InvalidSearchEmbedder(embedder_name.to_owned()))?;
+    if embedder.is_sparse() {
+        return Ok(Recall { k, sampled_queries: 0, recall: None });
+    }
+
+    let reader = ArroyWrapper::new(index, embedder_index, quantization);
+    let mut stats = ArroyStats::default();
+    reader.aggregate_stats(rtxn, &mut stats)?;
+    let documents = stats.documents;
+
+    let mut rng = StdRng::seed_from_u64(RECALL_SEED);
+    let sample_size = sample_size.min(documents.len() as usize);
+    let queries: RoaringBitmap =
+        rand::seq::index::sample(&mut rng, documents.len() as usize, sample_size)
+            .into_iter()
+            .filter_map(|rank| documents.select(rank as u32))
+            .collect();
+
+    let mut sampled_queries = 0;
+    let mut found = 0;
+    let mut expected = 0;
+    for docid in queries {
+        let vectors = reader.item_vectors(rtxn, docid)?;
+        let Some(vector) = vectors.first() else { continue };
+
+        // one more neighbor is searched for, as the document of the query is skipped
+        let (approximate, exact) = if embedder.is_multi_vector() {
+            let approximate = reader.nns_by_vectors(rtxn, &vectors, k.saturating_add(1), None)?;
+            let exact =
+                reader.exact_nns_by_vectors(rtxn, &vectors, k.saturating_add(1), &documents)?;
+            (
+                neighbors(approximate.into_iter().map(|(docid, _)| docid), docid, k),
+                neighbors(exact.into_iter().map(|(docid, _)| docid), docid, k),
+            )
+        } else {
+            let approximate = reader.nns_by_vector(rtxn, vector, k.saturating_add(1), None)?;
+            let exact =
+                reader.exact_nns_by_vector(rtxn, vector, k.saturating_add(1), &documents)?;
+            (
+                neighbors(approximate.into_iter().map(|(docid, _, _)| docid), docid, k),
+                neighbors(exact.into_iter().map(|(docid, _, _)| docid), docid, k),
+            )
+        };
+
+        sampled_queries += 1;
+        found += approximate.intersection_len(&exact);
+        expected += exact.len();
+    }
+
+    let recall = (expected != 0).then(|| found as f64 / expected as f64);
+    Ok(Recall { k, sampled_queries, recall })
+}
+
+/// The first `k` distinct documents of the results, except for the document of the query.
+fn neighbors(
+    results: impl Iterator<Item = DocumentId>,
+    query: DocumentId,
+    k: usize,
+) -> RoaringBitmap {
+    let mut neighbors = RoaringBitmap::new();
+    for docid in results.filter(|docid| *docid != query) {
+        if neighbors.len() as usize == k {
+            break;
+        }
+        neighbors.insert(docid);
+    }
+    neighbors
+}
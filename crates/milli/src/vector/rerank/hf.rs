@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use candle_core::Tensor;
+use candle_nn::{Linear, Module as _, VarBuilder};
+use candle_transformers::models::bert::{BertModel, Config, DTYPE};
+use hf_hub::api::sync::Api;
+use hf_hub::{Repo, RepoType};
+use tokenizers::{Tokenizer, TruncationParams, TruncationStrategy};
+
+use crate::vector::error::{EmbedError, NewEmbedderError};
+use crate::vector::hf::WeightSource;
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct RerankerOptions {
+    pub model: String,
+    pub revision: Option<String>,
+}
+
+impl RerankerOptions {
+    pub fn new() -> Self {
+        Self { model: "cross-encoder/ms-marco-MiniLM-L-6-v2".to_string(), revision: None }
+    }
+}
+
+impl Default for RerankerOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The part of the configuration of a BERT cross-encoder that is not read by [`BertModel`].
+#[derive(serde::Deserialize)]
+struct ClassifierConfig {
+    hidden_size: usize,
+    #[serde(default)]
+    id2label: Option<BTreeMap<String, String>>,
+}
+
+/// Reranks documents with a BERT cross-encoder, which scores the query and a document read together.
+pub struct Reranker {
+    model: BertModel,
+    pooler: Linear,
+    classifier: Linear,
+    tokenizer: Tokenizer,
+    options: RerankerOptions,
+}
+
+impl std::fmt::Debug for Reranker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reranker")
+            .field("model", &self.options.model)
+            .field("tokenizer", &self.tokenizer)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl Reranker {
+    pub fn new(options: RerankerOptions) -> Result<Self, NewEmbedderError> {
+        let device = match candle_core::Device::cuda_if_available(0) {
+            Ok(device) => device,
+            Err(error) => {
+                tracing::warn!("could not initialize CUDA device for Hugging Face reranker, defaulting to CPU: {}", error);
+                candle_core::Device::Cpu
+            }
+        };
+        let repo = match options.revision.clone() {
+            Some(revision) => Repo::with_revision(options.model.clone(), RepoType::Model, revision),
+            None => Repo::model(options.model.clone()),
+        };
+        let (config_filename, tokenizer_filename, weights_filename, weight_source) = {
+            let api = Api::new().map_err(NewEmbedderError::new_api_fail)?;
+            let api = api.repo(repo);
+            let config = api.get("config.json").map_err(NewEmbedderError::api_get)?;
+            let tokenizer = api.get("tokenizer.json").map_err(NewEmbedderError::api_get)?;
+            let (weights, source) = {
+                api.get("model.safetensors")
+                    .map(|filename| (filename, WeightSource::Safetensors))
+                    .or_else(|_| {
+                        api.get("pytorch_model.bin")
+                            .map(|filename| (filename, WeightSource::Pytorch))
+                    })
+                    .map_err(NewEmbedderError::api_get)?
+            };
+            (config, tokenizer, weights, source)
+        };
+
+        let config = std::fs::read_to_string(&config_filename)
+            .map_err(|inner| NewEmbedderError::open_config(config_filename.clone(), inner))?;
+        let (bert_config, classifier_config) = serde_json::from_str::<Config>(&config)
+            .and_then(|bert_config| {
+                Ok((bert_config, serde_json::from_str::<ClassifierConfig>(&config)?))
+            })
+            .map_err(|inner| {
+                NewEmbedderError::deserialize_config(
+                    options.model.clone(),
+                    config,
+                    config_filename,
+                    inner,
+                )
+            })?;
+        let labels = classifier_config.id2label.map_or(1, |id2label| id2label.len());
+        if labels != 1 {
+            return Err(NewEmbedderError::cross_encoder_label_count(options.model, labels));
+        }
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_filename)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename.clone(), inner))?;
+        // only the document is truncated, so that the query is always read entirely
+        tokenizer
+            .with_truncation(Some(TruncationParams {
+                max_length: 512,
+                strategy: TruncationStrategy::OnlySecond,
+                ..Default::default()
+            }))
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename, inner))?;
+
+        let vb = match weight_source {
+            WeightSource::Pytorch => VarBuilder::from_pth(&weights_filename, DTYPE, &device)
+                .map_err(NewEmbedderError::pytorch_weight)?,
+            WeightSource::Safetensors => unsafe {
+                VarBuilder::from_mmaped_safetensors(&[weights_filename], DTYPE, &device)
+                    .map_err(NewEmbedderError::safetensor_weight)?
+            },
+        };
+
+        tracing::debug!(model = options.model, weight=?weight_source, "reranker config");
+
+        let hidden_size = classifier_config.hidden_size;
+        let model =
+            BertModel::load(vb.pp("bert"), &bert_config).map_err(NewEmbedderError::load_model)?;
+        let pooler = candle_nn::linear(hidden_size, hidden_size, vb.pp("bert.pooler.dense"))
+            .map_err(NewEmbedderError::load_model)?;
+        let classifier = candle_nn::linear(hidden_size, 1, vb.pp("classifier"))
+            .map_err(NewEmbedderError::load_model)?;
+
+        Ok(Self { model, pooler, classifier, tokenizer, options })
+    }
+
+    /// Returns the relevance score of each document for the query, in the order of the documents.
+    ///
+    /// Fails if the deadline is reached before all the documents are scored.
+    pub fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        let mut scores = Vec::with_capacity(documents.len());
+        for document in documents {
+            if deadline.is_some_and(|deadline| Instant::now() > deadline) {
+                tracing::warn!("Could not rerank due to deadline");
+                return Err(EmbedError::rerank_deadline(scores.len(), documents.len()));
+            }
+            scores.push(self.rerank_one(query, document)?);
+        }
+        Ok(scores)
+    }
+
+    fn rerank_one(&self, query: &str, document: &str) -> Result<f32, EmbedError> {
+        let tokens =
+            self.tokenizer.encode((query, document), true).map_err(EmbedError::tokenize)?;
+        let token_ids = Tensor::new(tokens.get_ids(), &self.model.device)
+            .and_then(|token_ids| token_ids.unsqueeze(0))
+            .map_err(EmbedError::tensor_shape)?;
+        let token_type_ids = Tensor::new(tokens.get_type_ids(), &self.model.device)
+            .and_then(|token_type_ids| token_type_ids.unsqueeze(0))
+            .map_err(EmbedError::tensor_shape)?;
+        let embeddings = self
+            .model
+            .forward(&token_ids, &token_type_ids, None)
+            .map_err(EmbedError::model_forward)?;
+
+        // the classifier reads the pooled embedding of the CLS token
+        let cls = embeddings.get_on_dim(1, 0).map_err(EmbedError::tensor_value)?;
+        let pooled = self
+            .pooler
+            .forward(&cls)
+            .and_then(|pooled| pooled.tanh())
+            .map_err(EmbedError::model_forward)?;
+        let logits = self.classifier.forward(&pooled).map_err(EmbedError::model_forward)?;
+        let logits: Vec<f32> = logits
+            .flatten_all()
+            .and_then(|logits| logits.to_vec1())
+            .map_err(EmbedError::tensor_shape)?;
+        let logit = logits.first().copied().ok_or_else(EmbedError::missing_embedding)?;
+
+        Ok(1.0 / (1.0 + (-logit).exp()))
+    }
+}
@@ -0,0 +1,214 @@
+//! Rerankers score the best documents of a search against its query, to refine their order.
+//!
+//! Unlike embedders, rerankers read the query and the document together, which makes them more precise
+//! but too slow to be applied to more than the first documents of a search.
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::time::Instant;
+
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::error::{EmbedError, NewEmbedderError};
+use crate::prompt::{Prompt, PromptData};
+
+pub mod hf;
+pub mod rest;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum RerankerSource {
+    Rest,
+    HuggingFace,
+}
+
+impl std::fmt::Display for RerankerSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RerankerSource::Rest => "rest",
+            RerankerSource::HuggingFace => "huggingFace",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Configuration of a reranker, as sent by the user and stored in the index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Deserr, ToSchema)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct RerankerSettings {
+    /// The source used to score the documents.
+    pub source: RerankerSource,
+    /// The name of the cross-encoder model downloaded from the Hugging Face Hub.
+    ///
+    /// Available for source `huggingFace`, defaults to `cross-encoder/ms-marco-MiniLM-L-6-v2`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub model: Option<String>,
+    /// The revision of the model, available for source `huggingFace`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub revision: Option<String>,
+    /// The URL of the reranking server, mandatory for source `rest`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub url: Option<String>,
+    /// The API key sent as a bearer token to the reranking server, available for source `rest`.
+    ///
+    /// This setting is partially hidden when returned by the settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub api_key: Option<String>,
+    /// Template request to send to the reranking server, mandatory for source `rest`.
+    ///
+    /// It must contain the `{{query}}` placeholder, and the `{{text}}` placeholder for the documents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<serde_json::Value>)]
+    pub request: Option<serde_json::Value>,
+    /// Template response indicating how to find the scores in the response of the reranking server,
+    /// mandatory for source `rest`.
+    ///
+    /// It must contain the `{{score}}` placeholder, and optionally the `{{index}}` placeholder
+    /// when the scores are not in the order of the documents of the request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    #[schema(value_type = Option<serde_json::Value>)]
+    pub response: Option<serde_json::Value>,
+    /// Additional headers to send to the reranking server, available for source `rest`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub headers: Option<BTreeMap<String, String>>,
+    /// A liquid template used to render a document to a text that is scored against the query.
+    ///
+    /// Defaults to the default document template of the embedders.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub document_template: Option<String>,
+    /// Rendered texts are truncated to this size, defaults to 400.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub document_template_max_bytes: Option<usize>,
+}
+
+impl RerankerSettings {
+    /// Checks the settings and returns the options of the reranker, along with its document template.
+    ///
+    /// The error is a message describing the invalid parameter.
+    pub fn to_config(&self) -> Result<(RerankerOptions, PromptData), String> {
+        let Self {
+            source,
+            model,
+            revision,
+            url,
+            api_key,
+            request,
+            response,
+            headers,
+            document_template,
+            document_template_max_bytes,
+        } = self;
+
+        let unavailable = |field: &str| format!("`{field}` is unavailable for source `{source}`");
+        let mandatory = |field: &str| format!("`{field}` is mandatory for source `{source}`");
+
+        let options = match source {
+            RerankerSource::Rest => {
+                if model.is_some() {
+                    return Err(unavailable("model"));
+                }
+                if revision.is_some() {
+                    return Err(unavailable("revision"));
+                }
+                let options = rest::RerankerOptions {
+                    url: url.clone().ok_or_else(|| mandatory("url"))?,
+                    api_key: api_key.clone(),
+                    request: request.clone().ok_or_else(|| mandatory("request"))?,
+                    response: response.clone().ok_or_else(|| mandatory("response"))?,
+                    headers: headers.clone().unwrap_or_default(),
+                };
+                // building a rest reranker does not send any request, but checks the templates
+                rest::Reranker::new(options.clone()).map_err(|error| error.kind.to_string())?;
+                RerankerOptions::Rest(options)
+            }
+            RerankerSource::HuggingFace => {
+                for (field, is_some) in [
+                    ("url", url.is_some()),
+                    ("apiKey", api_key.is_some()),
+                    ("request", request.is_some()),
+                    ("response", response.is_some()),
+                    ("headers", headers.is_some()),
+                ] {
+                    if is_some {
+                        return Err(unavailable(field));
+                    }
+                }
+                let mut options = hf::RerankerOptions::default();
+                if let Some(model) = model {
+                    options.model = model.clone();
+                }
+                options.revision = revision.clone();
+                RerankerOptions::HuggingFace(options)
+            }
+        };
+
+        let mut prompt = PromptData::default();
+        if let Some(template) = document_template {
+            prompt.template = template.clone();
+        }
+        if let Some(max_bytes) = document_template_max_bytes {
+            prompt.max_bytes = Some(
+                NonZeroUsize::new(*max_bytes)
+                    .ok_or_else(|| "`documentTemplateMaxBytes` cannot be zero".to_string())?,
+            );
+        }
+        Prompt::try_from(prompt.clone())
+            .map_err(|error| format!("`documentTemplate`: {}", error.kind))?;
+
+        Ok((options, prompt))
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RerankerOptions {
+    Rest(rest::RerankerOptions),
+    HuggingFace(hf::RerankerOptions),
+}
+
+/// A reranker, scoring documents for a query.
+#[derive(Debug)]
+pub enum Reranker {
+    Rest(rest::Reranker),
+    HuggingFace(hf::Reranker),
+}
+
+impl Reranker {
+    pub fn new(options: RerankerOptions) -> Result<Self, NewEmbedderError> {
+        Ok(match options {
+            RerankerOptions::Rest(options) => Self::Rest(rest::Reranker::new(options)?),
+            RerankerOptions::HuggingFace(options) => Self::HuggingFace(hf::Reranker::new(options)?),
+        })
+    }
+
+    /// Returns the relevance score of each document for the query, in the order of the documents.
+    ///
+    /// The higher the score, the more relevant the document. Scores are expected between 0 and 1,
+    /// as they become the ranking score of the reranked documents.
+    pub fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        if documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        match self {
+            Reranker::Rest(reranker) => reranker.rerank(query, documents, deadline),
+            Reranker::HuggingFace(reranker) => reranker.rerank(query, documents, deadline),
+        }
+    }
+}
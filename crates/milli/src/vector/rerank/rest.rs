@@ -0,0 +1,272 @@
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::vector::error::{EmbedError, NewEmbedderError};
+use crate::vector::json_template::ValueTemplate;
+use crate::vector::rest::{
+    check_response, ConfigurationSource, Retry, REPEAT_PLACEHOLDER, REQUEST_PLACEHOLDER,
+};
+use crate::vector::REQUEST_PARALLELISM;
+
+pub(super) const QUERY_PLACEHOLDER: &str = "{{query}}";
+pub(super) const SCORE_PLACEHOLDER: &str = "{{score}}";
+pub(super) const INDEX_PLACEHOLDER: &str = "{{index}}";
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RerankerOptions {
+    pub url: String,
+    pub api_key: Option<String>,
+    pub request: serde_json::Value,
+    pub response: serde_json::Value,
+    pub headers: BTreeMap<String, String>,
+}
+
+impl std::hash::Hash for RerankerOptions {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.api_key.hash(state);
+        self.url.hash(state);
+        // skip hashing the request and response, like for the rest embedder
+    }
+}
+
+/// Reranks documents by sending them along with the query to a remote server.
+///
+/// The request template contains the `{{query}}` placeholder, replaced by the query,
+/// and the `{{text}}` placeholder, replaced by the documents.
+/// The response template contains the `{{score}}` placeholder, and optionally the `{{index}}` placeholder
+/// for servers that return the scores sorted by relevance along with the index of their document.
+#[derive(Debug)]
+pub struct Reranker {
+    client: ureq::Agent,
+    bearer: Option<String>,
+    headers: BTreeMap<String, String>,
+    url: String,
+    request: ValueTemplate,
+    scores: ValueTemplate,
+    indexes: Option<ValueTemplate>,
+}
+
+impl Reranker {
+    pub fn new(options: RerankerOptions) -> Result<Self, NewEmbedderError> {
+        let bearer = options.api_key.as_deref().map(|api_key| format!("Bearer {api_key}"));
+
+        let client = ureq::AgentBuilder::new()
+            .max_idle_connections(REQUEST_PARALLELISM * 2)
+            .max_idle_connections_per_host(REQUEST_PARALLELISM * 2)
+            .timeout(std::time::Duration::from_secs(30))
+            .build();
+
+        if !contains_string(&options.request, QUERY_PLACEHOLDER) {
+            return Err(NewEmbedderError::rest_could_not_parse_template(format!(
+                "in `request`: the template must contain the `{QUERY_PLACEHOLDER}` placeholder"
+            )));
+        }
+        let request = parse_template("request", options.request, REQUEST_PLACEHOLDER)?;
+        let indexes = if contains_string(&options.response, INDEX_PLACEHOLDER) {
+            Some(parse_template("response", options.response.clone(), INDEX_PLACEHOLDER)?)
+        } else {
+            None
+        };
+        let scores = parse_template("response", options.response, SCORE_PLACEHOLDER)?;
+
+        match (scores.has_array_value(), request.has_array_value()) {
+            (true, true) | (false, false) => (),
+            (true, false) => return Err(NewEmbedderError::rest_could_not_parse_template("in `response`: `response` has multiple scores, but `request` has only one document to rerank".to_string())),
+            (false, true) => return Err(NewEmbedderError::rest_could_not_parse_template("in `response`: `response` has a single score, but `request` has multiple documents to rerank".to_string())),
+        }
+
+        Ok(Self {
+            client,
+            bearer,
+            headers: options.headers,
+            url: options.url,
+            request,
+            scores,
+            indexes,
+        })
+    }
+
+    /// Returns the relevance score of each document for the query, in the order of the documents.
+    pub fn rerank(
+        &self,
+        query: &str,
+        documents: &[&str],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        if self.request.has_array_value() {
+            self.send(query, documents, deadline)
+        } else {
+            let mut scores = Vec::with_capacity(documents.len());
+            for document in documents {
+                scores.extend(self.send(query, std::slice::from_ref(document), deadline)?);
+            }
+            Ok(scores)
+        }
+    }
+
+    fn send(
+        &self,
+        query: &str,
+        documents: &[&str],
+        deadline: Option<Instant>,
+    ) -> Result<Vec<f32>, EmbedError> {
+        let request = self.client.post(&self.url);
+        let request = if let Some(bearer) = &self.bearer {
+            request.set("Authorization", bearer)
+        } else {
+            request
+        };
+        let mut request = request.set("Content-Type", "application/json");
+        for (header, value) in &self.headers {
+            request = request.set(header.as_str(), value.as_str());
+        }
+
+        let mut body =
+            self.request.inject(documents.iter().map(|document| (*document).into())).unwrap();
+        // documents are rendered from templates and are not expected to be exactly the placeholder
+        replace_string(&mut body, QUERY_PLACEHOLDER, query);
+
+        for attempt in 0..10 {
+            let response = request.clone().send_json(&body);
+            let result = check_response(response, ConfigurationSource::User)
+                .and_then(|response| self.response_to_scores(response, documents.len()));
+
+            let retry_duration = match result {
+                Ok(scores) => return Ok(scores),
+                Err(retry) => {
+                    tracing::warn!("Failed: {}", retry.error);
+                    if let Some(deadline) = deadline {
+                        let now = std::time::Instant::now();
+                        if now > deadline {
+                            tracing::warn!("Could not rerank due to deadline");
+                            return Err(retry.into_error());
+                        }
+
+                        let duration_to_deadline = deadline - now;
+                        retry
+                            .into_duration(attempt)
+                            .map(|duration| duration.min(duration_to_deadline))
+                    } else {
+                        retry.into_duration(attempt)
+                    }
+                }
+            }?;
+
+            let retry_duration = retry_duration.min(std::time::Duration::from_secs(60)); // don't wait more than a minute
+
+            // randomly up to double the retry duration
+            let retry_duration = retry_duration
+                + rand::thread_rng().gen_range(std::time::Duration::ZERO..retry_duration);
+
+            tracing::warn!(
+                "Attempt #{}, retrying after {}ms.",
+                attempt,
+                retry_duration.as_millis()
+            );
+            std::thread::sleep(retry_duration);
+        }
+
+        let response = request.send_json(&body);
+        check_response(response, ConfigurationSource::User)
+            .and_then(|response| self.response_to_scores(response, documents.len()))
+            .map_err(Retry::into_error)
+    }
+
+    fn response_to_scores(
+        &self,
+        response: ureq::Response,
+        expected_count: usize,
+    ) -> Result<Vec<f32>, Retry> {
+        let response: serde_json::Value = response
+            .into_json()
+            .map_err(EmbedError::rest_response_deserialization)
+            .map_err(Retry::retry_later)?;
+
+        let scores: Vec<f32> =
+            extract(&self.scores, response.clone(), SCORE_PLACEHOLDER, "a number")
+                .map_err(Retry::give_up)?;
+        if scores.len() != expected_count {
+            return Err(Retry::give_up(EmbedError::rerank_response_score_count(
+                expected_count,
+                scores.len(),
+            )));
+        }
+
+        let Some(indexes) = &self.indexes else {
+            return Ok(scores);
+        };
+        let indexes: Vec<usize> =
+            extract(indexes, response, INDEX_PLACEHOLDER, "a positive integer")
+                .map_err(Retry::give_up)?;
+        // every document must have been given exactly one score
+        let mut ordered_scores = vec![None; expected_count];
+        for (index, score) in indexes.into_iter().zip(scores) {
+            match ordered_scores.get_mut(index) {
+                Some(slot @ None) => *slot = Some(score),
+                _ => {
+                    return Err(Retry::give_up(EmbedError::rerank_extraction_error(format!(
+                        "`{INDEX_PLACEHOLDER}` is out of bounds or repeated: `{index}`"
+                    ))))
+                }
+            }
+        }
+        // unwrap: as many distinct indexes as documents were extracted, all in bounds
+        Ok(ordered_scores.into_iter().map(Option::unwrap).collect())
+    }
+}
+
+fn parse_template(
+    root: &str,
+    template: serde_json::Value,
+    placeholder: &str,
+) -> Result<ValueTemplate, NewEmbedderError> {
+    ValueTemplate::new(template, placeholder, REPEAT_PLACEHOLDER).map_err(|error| {
+        NewEmbedderError::rest_could_not_parse_template(error.error_message(
+            root,
+            placeholder,
+            REPEAT_PLACEHOLDER,
+        ))
+    })
+}
+
+fn extract<T: for<'de> Deserialize<'de>>(
+    template: &ValueTemplate,
+    response: serde_json::Value,
+    placeholder: &str,
+    description: &str,
+) -> Result<Vec<T>, EmbedError> {
+    template.extract(response).map_err(|error| {
+        EmbedError::rerank_extraction_error(error.error_message(
+            "response",
+            placeholder,
+            description,
+        ))
+    })
+}
+
+fn contains_string(value: &serde_json::Value, needle: &str) -> bool {
+    match value {
+        serde_json::Value::String(s) => s == needle,
+        serde_json::Value::Array(values) => {
+            values.iter().any(|value| contains_string(value, needle))
+        }
+        serde_json::Value::Object(map) => map.values().any(|value| contains_string(value, needle)),
+        _ => false,
+    }
+}
+
+fn replace_string(value: &mut serde_json::Value, needle: &str, replacement: &str) {
+    match value {
+        serde_json::Value::String(s) if s == needle => *s = replacement.to_owned(),
+        serde_json::Value::Array(values) => {
+            values.iter_mut().for_each(|value| replace_string(value, needle, replacement))
+        }
+        serde_json::Value::Object(map) => {
+            map.values_mut().for_each(|value| replace_string(value, needle, replacement))
+        }
+        _ => (),
+    }
+}
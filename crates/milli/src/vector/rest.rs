@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::time::Instant;
 
@@ -9,10 +10,12 @@ use serde::{Deserialize, Serialize};
 
 use super::error::EmbedErrorKind;
 use super::json_template::ValueTemplate;
+use super::sparse::SparseEmbedding;
 use super::{
     DistributionShift, EmbedError, Embedding, EmbeddingCache, NewEmbedderError, REQUEST_PARALLELISM,
 };
 use crate::error::FaultSource;
+use crate::prompt::Fragment;
 use crate::ThreadPoolNoAbort;
 
 // retrying in case of failure
@@ -78,6 +81,8 @@ pub struct Embedder {
     dimensions: usize,
     distribution: Option<DistributionShift>,
     cache: EmbeddingCache,
+    sparse: bool,
+    multi_vector: bool,
 }
 
 /// All data needed to perform requests and parse responses
@@ -90,6 +95,9 @@ struct EmbedderData {
     request: Request,
     response: Response,
     configuration_source: ConfigurationSource,
+    /// Whether the texts to embed at indexing time are documents rendered with an indexing fragment.
+    indexing_fragment: bool,
+    search_fragment: Option<Fragment>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
@@ -101,6 +109,20 @@ pub struct EmbedderOptions {
     pub request: serde_json::Value,
     pub response: serde_json::Value,
     pub headers: BTreeMap<String, String>,
+    /// Whether the `{{embedding}}` placeholder of the response contains sparse embeddings.
+    #[serde(default)]
+    pub sparse: bool,
+    /// Whether the `{{embedding}}` placeholder of the response contains an array of embeddings for each text.
+    #[serde(default)]
+    pub multi_vector: bool,
+    /// Whether the documents are rendered with an indexing fragment,
+    /// so that their rendered JSON values are sent in place of `{{text}}`.
+    #[serde(default)]
+    pub indexing_fragment: bool,
+    /// A JSON value whose strings are liquid templates over the query `q`, rendered and sent in place of
+    /// `{{text}}` when searching, instead of the text of the query.
+    #[serde(default)]
+    pub search_fragment: Option<serde_json::Value>,
 }
 
 impl std::hash::Hash for EmbedderOptions {
@@ -109,7 +131,10 @@ impl std::hash::Hash for EmbedderOptions {
         self.distribution.hash(state);
         self.dimensions.hash(state);
         self.url.hash(state);
-        // skip hashing the request and response
+        self.sparse.hash(state);
+        self.multi_vector.hash(state);
+        self.indexing_fragment.hash(state);
+        // skip hashing the request, response and search fragment
         // collisions in regular usage should be minimal,
         // and the list is limited to 256 values anyway
     }
@@ -126,7 +151,7 @@ enum InputType {
 impl Embedder {
     pub fn new(
         options: EmbedderOptions,
-        cache_cap: usize,
+        cache: EmbeddingCache,
         configuration_source: ConfigurationSource,
     ) -> Result<Self, NewEmbedderError> {
         let bearer = options.api_key.as_deref().map(|api_key| format!("Bearer {api_key}"));
@@ -139,6 +164,14 @@ impl Embedder {
 
         let request = Request::new(options.request)?;
         let response = Response::new(options.response, &request)?;
+        let search_fragment = match options.search_fragment {
+            Some(fragment) => Some(Fragment::for_query(fragment).map_err(|error| {
+                NewEmbedderError::rest_could_not_parse_template(format!(
+                    "in `searchFragment`: {error}"
+                ))
+            })?),
+            None => None,
+        };
 
         let data = EmbedderData {
             client,
@@ -148,19 +181,26 @@ impl Embedder {
             response,
             configuration_source,
             headers: options.headers,
+            indexing_fragment: options.indexing_fragment,
+            search_fragment,
         };
 
-        let dimensions = if let Some(dimensions) = options.dimensions {
+        let dimensions = if options.sparse {
+            // sparse embeddings have as many dimensions as there are tokens in the vocabulary of the model
+            0
+        } else if let Some(dimensions) = options.dimensions {
             dimensions
         } else {
-            infer_dimensions(&data)?
+            infer_dimensions(&data, options.multi_vector)?
         };
 
         Ok(Self {
             data,
             dimensions,
             distribution: options.distribution,
-            cache: EmbeddingCache::new(cache_cap),
+            cache,
+            sparse: options.sparse,
+            multi_vector: options.multi_vector,
         })
     }
 
@@ -193,6 +233,77 @@ impl Embedder {
         Ok(embeddings.pop().unwrap())
     }
 
+    /// Embeds a search query, rendered with the search fragment when there is one.
+    pub fn embed_search(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Embedding, EmbedError> {
+        self.embed_query(text, deadline)
+    }
+
+    pub fn embed_search_sparse(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> Result<SparseEmbedding, EmbedError> {
+        self.embed_query(text, deadline)
+    }
+
+    pub fn embed_search_multi(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> Result<Vec<Embedding>, EmbedError> {
+        self.embed_query(text, deadline)
+    }
+
+    /// The key of the embedding of a search query in the cache.
+    ///
+    /// A query rendered with the search fragment is not embedded like a document rendered to the same text,
+    /// so it is cached under its rendered value, like the documents rendered with an indexing fragment.
+    pub fn search_cache_key<'t>(&self, text: &'t str) -> Result<Cow<'t, str>, EmbedError> {
+        match &self.data.search_fragment {
+            Some(_) => Ok(Cow::Owned(query_input(&self.data, text)?.to_string())),
+            None => Ok(Cow::Borrowed(text)),
+        }
+    }
+
+    fn embed_query<E: RestEmbedding>(
+        &self,
+        text: &str,
+        deadline: Option<Instant>,
+    ) -> Result<E, EmbedError> {
+        let input = query_input(&self.data, text)?;
+        let mut embeddings = embed(&self.data, &[input], 1, self.expected_dimensions(), deadline)?;
+        // unwrap: guaranteed that embeddings.len() == 1, otherwise the previous line terminated in error
+        Ok(embeddings.pop().unwrap())
+    }
+
+    /// Embeds the texts rendered from the documents.
+    ///
+    /// When the documents are rendered with an indexing fragment, the texts are JSON values that are sent as is.
+    fn embed_documents<S, E>(&self, texts: &[S]) -> Result<Vec<E>, EmbedError>
+    where
+        S: AsRef<str> + Serialize,
+        E: RestEmbedding,
+    {
+        if !self.data.indexing_fragment {
+            return embed(&self.data, texts, texts.len(), self.expected_dimensions(), None);
+        }
+        let inputs = texts
+            .iter()
+            .map(|text| serde_json::from_str(text.as_ref()))
+            .collect::<Result<Vec<serde_json::Value>, _>>()
+            .map_err(EmbedError::invalid_rendered_fragment)?;
+        embed(&self.data, &inputs, inputs.len(), self.expected_dimensions(), None)
+    }
+
+    fn expected_dimensions(&self) -> Option<usize> {
+        // sparse embeddings have no fixed dimensions
+        (!self.sparse).then_some(self.dimensions)
+    }
+
     pub fn embed_index(
         &self,
         text_chunks: Vec<Vec<String>>,
@@ -201,11 +312,14 @@ impl Embedder {
         // This condition helps reduce the number of active rayon jobs
         // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
         if threads.active_operations() >= REQUEST_PARALLELISM {
-            text_chunks.into_iter().map(move |chunk| self.embed(chunk, None)).collect()
+            text_chunks.into_iter().map(move |chunk| self.embed_documents(&chunk)).collect()
         } else {
             threads
                 .install(move || {
-                    text_chunks.into_par_iter().map(move |chunk| self.embed(chunk, None)).collect()
+                    text_chunks
+                        .into_par_iter()
+                        .map(move |chunk| self.embed_documents(&chunk))
+                        .collect()
                 })
                 .map_err(|error| EmbedError {
                     kind: EmbedErrorKind::PanicInThreadPool(error),
@@ -224,7 +338,7 @@ impl Embedder {
         if threads.active_operations() >= REQUEST_PARALLELISM {
             let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
                 .chunks(self.prompt_count_in_chunk_hint())
-                .map(move |chunk| self.embed_ref(chunk, None))
+                .map(move |chunk| self.embed_documents(chunk))
                 .collect();
 
             let embeddings = embeddings?;
@@ -234,7 +348,121 @@ impl Embedder {
                 .install(move || {
                     let embeddings: Result<Vec<Vec<Embedding>>, _> = texts
                         .par_chunks(self.prompt_count_in_chunk_hint())
-                        .map(move |chunk| self.embed_ref(chunk, None))
+                        .map(move |chunk| self.embed_documents(chunk))
+                        .collect();
+
+                    let embeddings = embeddings?;
+                    Ok(embeddings.into_iter().flatten().collect())
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub fn embed_index_sparse(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<SparseEmbedding>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            text_chunks.into_iter().map(move |chunk| self.embed_documents(&chunk)).collect()
+        } else {
+            threads
+                .install(move || {
+                    text_chunks
+                        .into_par_iter()
+                        .map(move |chunk| self.embed_documents(&chunk))
+                        .collect()
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub(crate) fn embed_index_ref_sparse(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<SparseEmbedding>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            let embeddings: Result<Vec<Vec<SparseEmbedding>>, _> = texts
+                .chunks(self.prompt_count_in_chunk_hint())
+                .map(move |chunk| self.embed_documents(chunk))
+                .collect();
+
+            let embeddings = embeddings?;
+            Ok(embeddings.into_iter().flatten().collect())
+        } else {
+            threads
+                .install(move || {
+                    let embeddings: Result<Vec<Vec<SparseEmbedding>>, _> = texts
+                        .par_chunks(self.prompt_count_in_chunk_hint())
+                        .map(move |chunk| self.embed_documents(chunk))
+                        .collect();
+
+                    let embeddings = embeddings?;
+                    Ok(embeddings.into_iter().flatten().collect())
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub fn embed_index_multi(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<Vec<Embedding>>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            text_chunks.into_iter().map(move |chunk| self.embed_documents(&chunk)).collect()
+        } else {
+            threads
+                .install(move || {
+                    text_chunks
+                        .into_par_iter()
+                        .map(move |chunk| self.embed_documents(&chunk))
+                        .collect()
+                })
+                .map_err(|error| EmbedError {
+                    kind: EmbedErrorKind::PanicInThreadPool(error),
+                    fault: FaultSource::Bug,
+                })?
+        }
+    }
+
+    pub(crate) fn embed_index_ref_multi(
+        &self,
+        texts: &[&str],
+        threads: &ThreadPoolNoAbort,
+    ) -> Result<Vec<Vec<Embedding>>, EmbedError> {
+        // This condition helps reduce the number of active rayon jobs
+        // so that we avoid consuming all the LMDB rtxns and avoid stack overflows.
+        if threads.active_operations() >= REQUEST_PARALLELISM {
+            let embeddings: Result<Vec<Vec<Vec<Embedding>>>, _> = texts
+                .chunks(self.prompt_count_in_chunk_hint())
+                .map(move |chunk| self.embed_documents(chunk))
+                .collect();
+
+            let embeddings = embeddings?;
+            Ok(embeddings.into_iter().flatten().collect())
+        } else {
+            threads
+                .install(move || {
+                    let embeddings: Result<Vec<Vec<Vec<Embedding>>>, _> = texts
+                        .par_chunks(self.prompt_count_in_chunk_hint())
+                        .map(move |chunk| self.embed_documents(chunk))
                         .collect();
 
                     let embeddings = embeddings?;
@@ -266,27 +494,53 @@ impl Embedder {
         self.distribution
     }
 
+    pub fn is_sparse(&self) -> bool {
+        self.sparse
+    }
+
+    pub fn is_multi_vector(&self) -> bool {
+        self.multi_vector
+    }
+
     pub(super) fn cache(&self) -> &EmbeddingCache {
         &self.cache
     }
 }
 
-fn infer_dimensions(data: &EmbedderData) -> Result<usize, NewEmbedderError> {
-    let v = embed(data, ["test"].as_slice(), 1, None, None)
+fn infer_dimensions(data: &EmbedderData, multi_vector: bool) -> Result<usize, NewEmbedderError> {
+    let input =
+        [query_input(data, "test").map_err(NewEmbedderError::could_not_determine_dimension)?];
+    if multi_vector {
+        let v: Vec<Vec<Embedding>> = embed(data, input.as_slice(), 1, None, None)
+            .map_err(NewEmbedderError::could_not_determine_dimension)?;
+        // unwrap: guaranteed that v.len() == 1 and that its embeddings are not empty,
+        // otherwise the previous line terminated in error
+        return Ok(v.first().unwrap().first().unwrap().len());
+    }
+    let v: Vec<Embedding> = embed(data, input.as_slice(), 1, None, None)
         .map_err(NewEmbedderError::could_not_determine_dimension)?;
     // unwrap: guaranteed that v.len() == 1, otherwise the previous line terminated in error
     Ok(v.first().unwrap().len())
 }
 
-fn embed<S>(
+/// The value sent in place of `{{text}}` to embed a search query.
+fn query_input(data: &EmbedderData, text: &str) -> Result<serde_json::Value, EmbedError> {
+    match &data.search_fragment {
+        Some(fragment) => fragment.render_query(text).map_err(EmbedError::render_search_fragment),
+        None => Ok(serde_json::Value::String(text.to_owned())),
+    }
+}
+
+fn embed<S, E>(
     data: &EmbedderData,
     inputs: &[S],
     expected_count: usize,
     expected_dimension: Option<usize>,
     deadline: Option<Instant>,
-) -> Result<Vec<Embedding>, EmbedError>
+) -> Result<Vec<E>, EmbedError>
 where
     S: Serialize,
+    E: RestEmbedding,
 {
     let request = data.client.post(&data.url);
     let request = if let Some(bearer) = &data.bearer {
@@ -344,7 +598,7 @@ where
     })
 }
 
-fn check_response(
+pub(super) fn check_response(
     response: Result<ureq::Response, ureq::Error>,
     configuration_source: ConfigurationSource,
 ) -> Result<ureq::Response, Retry> {
@@ -377,18 +631,18 @@ fn check_response(
     }
 }
 
-fn response_to_embedding(
+fn response_to_embedding<E: RestEmbedding>(
     response: ureq::Response,
     data: &EmbedderData,
     expected_count: usize,
     expected_dimensions: Option<usize>,
-) -> Result<Vec<Embedding>, Retry> {
+) -> Result<Vec<E>, Retry> {
     let response: serde_json::Value = response
         .into_json()
         .map_err(EmbedError::rest_response_deserialization)
         .map_err(Retry::retry_later)?;
 
-    let embeddings = data.response.extract_embeddings(response).map_err(Retry::give_up)?;
+    let embeddings = data.response.extract(response).map_err(Retry::give_up)?;
 
     if embeddings.len() != expected_count {
         return Err(Retry::give_up(EmbedError::rest_response_embedding_count(
@@ -397,18 +651,53 @@ fn response_to_embedding(
         )));
     }
 
-    if let Some(dimensions) = expected_dimensions {
-        for embedding in &embeddings {
-            if embedding.len() != dimensions {
-                return Err(Retry::give_up(EmbedError::rest_unexpected_dimension(
-                    dimensions,
-                    embedding.len(),
-                )));
+    for embedding in &embeddings {
+        embedding.check(expected_dimensions).map_err(Retry::give_up)?;
+    }
+
+    Ok(embeddings)
+}
+
+/// An embedding that can be extracted from the response of the embedding server.
+trait RestEmbedding: for<'de> Deserialize<'de> {
+    /// Description of the value expected in place of the `{{embedding}}` placeholder.
+    const DESCRIPTION: &'static str;
+
+    fn check(&self, expected_dimensions: Option<usize>) -> Result<(), EmbedError>;
+}
+
+impl RestEmbedding for Embedding {
+    const DESCRIPTION: &'static str = "an array of numbers";
+
+    fn check(&self, expected_dimensions: Option<usize>) -> Result<(), EmbedError> {
+        match expected_dimensions {
+            Some(dimensions) if self.len() != dimensions => {
+                Err(EmbedError::unexpected_dimension(dimensions, self.len()))
             }
+            _ => Ok(()),
         }
     }
+}
 
-    Ok(embeddings)
+impl RestEmbedding for Vec<Embedding> {
+    const DESCRIPTION: &'static str = "an array of arrays of numbers";
+
+    fn check(&self, expected_dimensions: Option<usize>) -> Result<(), EmbedError> {
+        if self.is_empty() {
+            return Err(EmbedError::empty_multi_vector_embedding());
+        }
+        // embeddings of the same text must all have the same dimensions, even when they are being inferred
+        let expected_dimensions = expected_dimensions.or_else(|| self.first().map(Vec::len));
+        self.iter().try_for_each(|embedding| embedding.check(expected_dimensions))
+    }
+}
+
+impl RestEmbedding for SparseEmbedding {
+    const DESCRIPTION: &'static str = "an object mapping tokens to numbers";
+
+    fn check(&self, _expected_dimensions: Option<usize>) -> Result<(), EmbedError> {
+        super::sparse::validate(self).map_err(EmbedError::invalid_sparse_embedding)
+    }
 }
 
 pub(super) const REQUEST_PLACEHOLDER: &str = "{{text}}";
@@ -478,16 +767,17 @@ impl Response {
         &self,
         response: serde_json::Value,
     ) -> Result<Vec<Embedding>, EmbedError> {
-        let extracted_values: Vec<Embedding> = match self.template.extract(response) {
-            Ok(extracted_values) => extracted_values,
+        self.extract(response)
+    }
+
+    fn extract<E: RestEmbedding>(&self, response: serde_json::Value) -> Result<Vec<E>, EmbedError> {
+        match self.template.extract(response) {
+            Ok(extracted_values) => Ok(extracted_values),
             Err(error) => {
                 let error_message =
-                    error.error_message("response", "{{embedding}}", "an array of numbers");
-                return Err(EmbedError::rest_extraction_error(error_message));
+                    error.error_message("response", "{{embedding}}", E::DESCRIPTION);
+                Err(EmbedError::rest_extraction_error(error_message))
             }
-        };
-        let embeddings: Vec<Embedding> = extracted_values.into_iter().collect();
-
-        Ok(embeddings)
+        }
     }
 }
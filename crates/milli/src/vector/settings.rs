@@ -6,12 +6,13 @@ use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
+use super::chunking::Chunking;
 use super::composite::SubEmbedderOptions;
 use super::hf::OverridePooling;
 use super::{ollama, openai, DistributionShift, EmbedderOptions};
 use crate::prompt::{default_max_bytes, PromptData};
 use crate::update::Setting;
-use crate::vector::EmbeddingConfig;
+use crate::vector::{EmbeddingConfig, Quantization, VectorQuantization};
 use crate::UserError;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
@@ -38,13 +39,15 @@ pub struct EmbeddingSettings {
     #[schema(value_type = Option<String>)]
     /// The name of the model to use.
     ///
+    /// For source `onnx`, the path to a local directory containing the `model.onnx` file and its `tokenizer.json`.
+    ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `ollama`
+    /// - This parameter is mandatory for sources `ollama` and `onnx`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`
+    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -82,7 +85,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -98,6 +101,23 @@ pub struct EmbeddingSettings {
     pub pooling: Setting<OverridePooling>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether to normalize the embeddings produced by the model to a unit length.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `onnx`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    pub normalize: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<String>)]
     /// The API key to pass to the remote embedder while making requests.
     ///
@@ -129,7 +149,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`
+    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -139,7 +159,7 @@ pub struct EmbeddingSettings {
     /// # Defaults
     ///
     /// - For source `openAi`, the dimensions is the maximum allowed by the model.
-    /// - For sources `ollama` and `rest`, the dimensions are inferred by embedding a sample text.
+    /// - For sources `ollama`, `rest` and `onnx`, the dimensions are inferred by embedding a sample text.
     pub dimensions: Setting<usize>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -166,9 +186,62 @@ pub struct EmbeddingSettings {
     /// As binary quantization is a destructive operation, it is not possible to disable again this setting after
     /// first enabling it. If you are unsure of whether the performance-relevancy tradeoff is right for you,
     /// we recommend to use this parameter on a test index first.
+    ///
+    /// Setting this parameter to `true` is equivalent to setting `quantization` to `binary`.
     pub binary_quantized: Setting<bool>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<Quantization>)]
+    /// How the embeddings of this embedder are quantized.
+    ///
+    /// - `none`: embeddings are indexed with 32-bit floats.
+    /// - `binary`: embeddings are indexed as bits, which divides their size by 32 at the cost of relevancy.
+    /// - `int8`: embeddings are indexed as bits, and the best candidates are rescored against embeddings
+    ///   quantized to 8-bit integers, which are kept separately and take a quarter of the size of the
+    ///   original embeddings.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for all embedders, except for sparse embedders
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ When changed, embeddings are not regenerated, but they are quantized again, which takes time.
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `binary` when `binaryQuantized` is `true`, and to `none` otherwise
+    ///
+    /// # Note
+    ///
+    /// As quantization is a destructive operation, the quantization of an embedder cannot be changed to
+    /// a more precise one, such as from `binary` to `int8` or from `int8` to `none`.
+    pub quantization: Setting<Quantization>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether to keep the full-precision embeddings of a `binary` quantized embedder,
+    /// to rescore the best candidates of a search against them.
+    ///
+    /// Rescoring recovers most of the relevancy lost to quantization, while the full-precision
+    /// embeddings are only read for the candidates of a search.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available when `quantization` is `binary`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Can only be enabled along with the quantization of the embedder, as the full-precision embeddings
+    ///   are lost afterwards. Embeddings are not regenerated, but they are quantized, which takes time.
+    /// - 🌱 When disabled, embeddings are never regenerated
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    pub rescoring: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<bool>)]
     /// A liquid template used to render documents to a text that can be embedded.
     ///
@@ -177,7 +250,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `rest` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -190,7 +263,7 @@ pub struct EmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `rest` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -290,6 +363,116 @@ pub struct EmbeddingSettings {
     ///
     /// - 🌱 Changing the value of this parameter never regenerates embeddings
     pub distribution: Setting<DistributionShift>,
+
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<Chunking>)]
+    /// Splits the rendered document template, or the value of a field, into overlapping chunks.
+    ///
+    /// Each chunk is embedded as a separate vector of the document, and the rendered text is no longer
+    /// truncated to `documentTemplateMaxBytes`. Search results report the position of the best matching chunk.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for all embedders, except for source `userProvided`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Documents are not chunked by default
+    pub chunking: Setting<Chunking>,
+
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether the embedder produces sparse embeddings, as objects mapping tokens to their weights.
+    ///
+    /// Sparse embeddings are stored in an inverted index and compared to the query by dot product.
+    /// They cannot be provided by the user in the `_vectors` field of documents.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    pub sparse: Setting<bool>,
+
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether documents and queries are represented by several embeddings, as with late interaction models.
+    ///
+    /// Documents are scored by MaxSim: each embedding of the query is matched with the most similar embedding
+    /// of the document, and the similarities are averaged. A `rest` embedder must return an array of embeddings
+    /// for each text, and a `vector` search parameter is the concatenation of the embeddings of the query.
+    /// At most 256 embeddings are stored for each document.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for sources `rest` and `userProvided`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ When the source is `rest`, changing the value of this parameter always regenerates embeddings
+    /// - 🌱 When the source is `userProvided`, changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    pub multi_vector: Setting<bool>,
+
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<serde_json::Value>)]
+    /// A JSON value rendered for each document and sent to the embedder in place of `{{text}}`.
+    ///
+    /// Each string of the value is a liquid template over the document, as in `documentTemplate`, which makes it
+    /// possible to send several fields of the document to a multimodal embedder, such as the URL or the base64
+    /// content of an image along with its description. Rendered values are neither truncated nor chunked.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `rest`, and cannot be set along with `documentTemplate` or `chunking`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ When modified, embeddings are regenerated for documents whose rendering through the fragment produces a different value.
+    ///
+    /// # Defaults
+    ///
+    /// - Documents are rendered with `documentTemplate` by default
+    pub indexing_fragment: Setting<serde_json::Value>,
+
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    #[schema(value_type = Option<serde_json::Value>)]
+    /// A JSON value rendered for each search query and sent to the embedder in place of `{{text}}`.
+    ///
+    /// Each string of the value is a liquid template where `{{q}}` is the text of the query, so that queries
+    /// can be embedded differently from the documents, e.g. as the text input of a CLIP-style model
+    /// whose documents are embedded from images.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `rest`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🌱 Changing the value of this parameter never regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - The text of the query is sent in place of `{{text}}` by default
+    pub search_fragment: Setting<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr, ToSchema)]
@@ -316,13 +499,15 @@ pub struct SubEmbeddingSettings {
     #[schema(value_type = Option<String>)]
     /// The name of the model to use.
     ///
+    /// For source `onnx`, the path to a local directory containing the `model.onnx` file and its `tokenizer.json`.
+    ///
     /// # Mandatory
     ///
-    /// - This parameter is mandatory for source `ollama`
+    /// - This parameter is mandatory for sources `ollama` and `onnx`
     ///
     /// # Availability
     ///
-    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`
+    /// - This parameter is available for sources `openAi`, `huggingFace`, `ollama`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -360,7 +545,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `huggingFace`
+    /// - This parameter is available for sources `huggingFace`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -376,6 +561,23 @@ pub struct SubEmbeddingSettings {
     pub pooling: Setting<OverridePooling>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
+    #[schema(value_type = Option<bool>)]
+    /// Whether to normalize the embeddings produced by the model to a unit length.
+    ///
+    /// # Availability
+    ///
+    /// - This parameter is available for source `onnx`
+    ///
+    /// # 🔄 Reindexing
+    ///
+    /// - 🏗️ Changing the value of this parameter always regenerates embeddings
+    ///
+    /// # Defaults
+    ///
+    /// - Defaults to `false`
+    pub normalize: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
     #[schema(value_type = Option<String>)]
     /// The API key to pass to the remote embedder while making requests.
     ///
@@ -407,7 +609,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`
+    /// - This parameter is available for source `openAi`, `ollama`, `rest`, `userProvided`, `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -417,7 +619,7 @@ pub struct SubEmbeddingSettings {
     /// # Defaults
     ///
     /// - For source `openAi`, the dimensions is the maximum allowed by the model.
-    /// - For sources `ollama` and `rest`, the dimensions are inferred by embedding a sample text.
+    /// - For sources `ollama`, `rest` and `onnx`, the dimensions are inferred by embedding a sample text.
     pub dimensions: Setting<usize>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
@@ -429,7 +631,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `rest` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -442,7 +644,7 @@ pub struct SubEmbeddingSettings {
     ///
     /// # Availability
     ///
-    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama` and `rest`
+    /// - This parameter is available for source `openAi`, `huggingFace`, `ollama`, `rest` and `onnx`
     ///
     /// # 🔄 Reindexing
     ///
@@ -561,7 +763,7 @@ pub enum SettingsDiff {
 
 #[derive(Default, Debug)]
 pub struct EmbedderAction {
-    pub was_quantized: bool,
+    pub was_quantized: VectorQuantization,
     pub is_being_quantized: bool,
     pub write_back: Option<WriteBackToDocuments>,
     pub reindex: Option<ReindexAction>,
@@ -585,7 +787,10 @@ impl EmbedderAction {
         self
     }
 
-    pub fn with_write_back(write_back: WriteBackToDocuments, was_quantized: bool) -> Self {
+    pub fn with_write_back(
+        write_back: WriteBackToDocuments,
+        was_quantized: VectorQuantization,
+    ) -> Self {
         Self {
             was_quantized,
             is_being_quantized: false,
@@ -594,7 +799,7 @@ impl EmbedderAction {
         }
     }
 
-    pub fn with_reindex(reindex: ReindexAction, was_quantized: bool) -> Self {
+    pub fn with_reindex(reindex: ReindexAction, was_quantized: VectorQuantization) -> Self {
         Self { was_quantized, is_being_quantized: false, write_back: None, reindex: Some(reindex) }
     }
 }
@@ -618,6 +823,7 @@ impl SettingsDiff {
                     mut model,
                     mut revision,
                     mut pooling,
+                    mut normalize,
                     mut api_key,
                     mut dimensions,
                     mut document_template,
@@ -630,6 +836,13 @@ impl SettingsDiff {
                     mut headers,
                     mut document_template_max_bytes,
                     binary_quantized: mut binary_quantize,
+                    mut quantization,
+                    mut rescoring,
+                    mut chunking,
+                    mut sparse,
+                    mut multi_vector,
+                    mut indexing_fragment,
+                    mut search_fragment,
                 } = old;
 
                 let EmbeddingSettings {
@@ -637,6 +850,7 @@ impl SettingsDiff {
                     model: new_model,
                     revision: new_revision,
                     pooling: new_pooling,
+                    normalize: new_normalize,
                     api_key: new_api_key,
                     dimensions: new_dimensions,
                     document_template: new_document_template,
@@ -649,6 +863,13 @@ impl SettingsDiff {
                     headers: new_headers,
                     document_template_max_bytes: new_document_template_max_bytes,
                     binary_quantized: new_binary_quantize,
+                    quantization: new_quantization,
+                    rescoring: new_rescoring,
+                    chunking: new_chunking,
+                    sparse: new_sparse,
+                    multi_vector: new_multi_vector,
+                    indexing_fragment: new_indexing_fragment,
+                    search_fragment: new_search_fragment,
                 } = new;
 
                 if matches!(binary_quantize, Setting::Set(true))
@@ -667,6 +888,7 @@ impl SettingsDiff {
                     &mut model,
                     &mut revision,
                     &mut pooling,
+                    &mut normalize,
                     &mut api_key,
                     &mut dimensions,
                     &mut document_template,
@@ -679,6 +901,7 @@ impl SettingsDiff {
                     new_model,
                     new_revision,
                     new_pooling,
+                    new_normalize,
                     new_api_key,
                     new_dimensions,
                     new_document_template,
@@ -689,7 +912,73 @@ impl SettingsDiff {
                     new_headers,
                 );
 
-                let binary_quantize_changed = binary_quantize.apply(new_binary_quantize);
+                let old_quantization =
+                    vector_quantization(&binary_quantize, &quantization, &rescoring);
+                binary_quantize.apply(new_binary_quantize);
+                quantization.apply(new_quantization);
+                rescoring.apply(new_rescoring);
+                let new_quantization =
+                    vector_quantization(&binary_quantize, &quantization, &rescoring);
+                if !new_quantization.can_be_converted_from(&old_quantization) {
+                    return Err(UserError::InvalidQuantizationChange {
+                        embedder_name: embedder_name.to_string(),
+                        old: old_quantization,
+                        new: new_quantization,
+                    });
+                }
+                let quantization_changed = new_quantization != old_quantization;
+
+                // the chunks are the vectors of the documents
+                if chunking.apply(new_chunking) {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
+
+                // only the `rest` source can produce sparse embeddings, so leaving it resets the setting
+                if !matches!(source, Setting::Set(EmbedderSource::Rest)) && new_sparse.is_not_set()
+                {
+                    sparse = Setting::NotSet;
+                }
+                // sparse embeddings live in a different store than dense ones
+                let was_sparse = matches!(sparse, Setting::Set(true));
+                sparse.apply(new_sparse);
+                if was_sparse != matches!(sparse, Setting::Set(true)) {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
+
+                if !matches!(
+                    source,
+                    Setting::Set(EmbedderSource::Rest | EmbedderSource::UserProvided)
+                ) && new_multi_vector.is_not_set()
+                {
+                    multi_vector = Setting::NotSet;
+                }
+                // a `rest` embedder must then be called again to get all the embeddings of the documents
+                let was_multi_vector = matches!(multi_vector, Setting::Set(true));
+                multi_vector.apply(new_multi_vector);
+                if was_multi_vector != matches!(multi_vector, Setting::Set(true))
+                    && matches!(source, Setting::Set(EmbedderSource::Rest))
+                {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
+
+                // only the `rest` source can render fragments, so leaving it resets them
+                if !matches!(source, Setting::Set(EmbedderSource::Rest)) {
+                    if new_indexing_fragment.is_not_set() {
+                        indexing_fragment = Setting::NotSet;
+                    }
+                    if new_search_fragment.is_not_set() {
+                        search_fragment = Setting::NotSet;
+                    }
+                }
+                // the documents rendered with the old and new fragments are compared, like with the template
+                if indexing_fragment.apply(new_indexing_fragment) {
+                    ReindexAction::push_action(
+                        &mut reindex_action,
+                        ReindexAction::RegeneratePrompts,
+                    );
+                }
+                // changes to the search fragment never trigger any reindexing
+                search_fragment.apply(new_search_fragment);
 
                 // changes to the *search* embedder never triggers any reindexing
                 search_embedder.apply(new_search_embedder);
@@ -706,6 +995,7 @@ impl SettingsDiff {
                     model,
                     revision,
                     pooling,
+                    normalize,
                     api_key,
                     dimensions,
                     document_template,
@@ -718,17 +1008,22 @@ impl SettingsDiff {
                     headers,
                     document_template_max_bytes,
                     binary_quantized: binary_quantize,
+                    quantization,
+                    rescoring,
+                    chunking,
+                    sparse,
+                    multi_vector,
+                    indexing_fragment,
+                    search_fragment,
                 };
 
                 match reindex_action {
-                    Some(action) => Self::Reindex {
-                        action,
-                        updated_settings,
-                        quantize: binary_quantize_changed,
-                    },
+                    Some(action) => {
+                        Self::Reindex { action, updated_settings, quantize: quantization_changed }
+                    }
                     None => Self::UpdateWithoutReindex {
                         updated_settings,
-                        quantize: binary_quantize_changed,
+                        quantize: quantization_changed,
                     },
                 }
             }
@@ -752,6 +1047,7 @@ impl SettingsDiff {
                     mut model,
                     mut revision,
                     mut pooling,
+                    mut normalize,
                     mut api_key,
                     mut dimensions,
                     mut document_template,
@@ -777,6 +1073,7 @@ impl SettingsDiff {
                     model: new_model,
                     revision: new_revision,
                     pooling: new_pooling,
+                    normalize: new_normalize,
                     api_key: new_api_key,
                     dimensions: new_dimensions,
                     document_template: new_document_template,
@@ -797,6 +1094,7 @@ impl SettingsDiff {
                     &mut model,
                     &mut revision,
                     &mut pooling,
+                    &mut normalize,
                     &mut api_key,
                     &mut dimensions,
                     &mut document_template,
@@ -809,6 +1107,7 @@ impl SettingsDiff {
                     new_model,
                     new_revision,
                     new_pooling,
+                    new_normalize,
                     new_api_key,
                     new_dimensions,
                     new_document_template,
@@ -830,6 +1129,7 @@ impl SettingsDiff {
                     model,
                     revision,
                     pooling,
+                    normalize,
                     api_key,
                     dimensions,
                     document_template,
@@ -858,6 +1158,7 @@ impl SettingsDiff {
         model: &mut Setting<String>,
         revision: &mut Setting<String>,
         pooling: &mut Setting<OverridePooling>,
+        normalize: &mut Setting<bool>,
         api_key: &mut Setting<String>,
         dimensions: &mut Setting<usize>,
         document_template: &mut Setting<String>,
@@ -870,6 +1171,7 @@ impl SettingsDiff {
         new_model: Setting<String>,
         new_revision: Setting<String>,
         new_pooling: Setting<OverridePooling>,
+        new_normalize: Setting<bool>,
         new_api_key: Setting<String>,
         new_dimensions: Setting<usize>,
         new_document_template: Setting<String>,
@@ -888,6 +1190,7 @@ impl SettingsDiff {
                 model,
                 revision,
                 pooling,
+                normalize,
                 dimensions,
                 url,
                 request,
@@ -909,6 +1212,9 @@ impl SettingsDiff {
         if pooling.apply(new_pooling) {
             ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
         }
+        if normalize.apply(new_normalize) {
+            ReindexAction::push_action(reindex_action, ReindexAction::FullReindex);
+        }
         if dimensions.apply(new_dimensions) {
             match *source {
                 // regenerate on dimensions change in OpenAI since truncation is supported
@@ -958,6 +1264,22 @@ impl SettingsDiff {
     }
 }
 
+/// The quantization described by the settings of an embedder.
+///
+/// `binaryQuantized` is a shorthand for the `binary` quantization.
+pub(crate) fn vector_quantization(
+    binary_quantized: &Setting<bool>,
+    quantization: &Setting<Quantization>,
+    rescoring: &Setting<bool>,
+) -> VectorQuantization {
+    let quantization = match (quantization, binary_quantized) {
+        (Setting::Set(quantization), _) => *quantization,
+        (_, Setting::Set(true)) => Quantization::Binary,
+        (_, _) => Quantization::None,
+    };
+    VectorQuantization { quantization, rescoring: matches!(rescoring, Setting::Set(true)) }
+}
+
 impl ReindexAction {
     fn push_action(this: &mut Option<Self>, other: Self) {
         *this = match (*this, other) {
@@ -974,6 +1296,7 @@ fn apply_default_for_source(
     model: &mut Setting<String>,
     revision: &mut Setting<String>,
     pooling: &mut Setting<OverridePooling>,
+    normalize: &mut Setting<bool>,
     dimensions: &mut Setting<usize>,
     url: &mut Setting<String>,
     request: &mut Setting<serde_json::Value>,
@@ -989,6 +1312,7 @@ fn apply_default_for_source(
             *model = Setting::Reset;
             *revision = Setting::Reset;
             *pooling = Setting::Reset;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::NotSet;
             *url = Setting::NotSet;
             *request = Setting::NotSet;
@@ -1001,6 +1325,7 @@ fn apply_default_for_source(
             *model = Setting::Reset;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::Reset;
             *url = Setting::NotSet;
             *request = Setting::NotSet;
@@ -1013,6 +1338,7 @@ fn apply_default_for_source(
             *model = Setting::Reset;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::NotSet;
             *url = Setting::Reset;
             *request = Setting::NotSet;
@@ -1025,6 +1351,7 @@ fn apply_default_for_source(
             *model = Setting::NotSet;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::Reset;
             *url = Setting::Reset;
             *request = Setting::Reset;
@@ -1037,6 +1364,7 @@ fn apply_default_for_source(
             *model = Setting::NotSet;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::Reset;
             *url = Setting::NotSet;
             *request = Setting::NotSet;
@@ -1047,10 +1375,24 @@ fn apply_default_for_source(
             *search_embedder = Setting::NotSet;
             *indexing_embedder = Setting::NotSet;
         }
+        Setting::Set(EmbedderSource::Onnx) => {
+            *model = Setting::Reset;
+            *revision = Setting::NotSet;
+            *pooling = Setting::Reset;
+            *normalize = Setting::Reset;
+            *dimensions = Setting::Reset;
+            *url = Setting::NotSet;
+            *request = Setting::NotSet;
+            *response = Setting::NotSet;
+            *headers = Setting::NotSet;
+            *search_embedder = Setting::NotSet;
+            *indexing_embedder = Setting::NotSet;
+        }
         Setting::Set(EmbedderSource::Composite) => {
             *model = Setting::NotSet;
             *revision = Setting::NotSet;
             *pooling = Setting::NotSet;
+            *normalize = Setting::NotSet;
             *dimensions = Setting::NotSet;
             *url = Setting::NotSet;
             *request = Setting::NotSet;
@@ -1114,6 +1456,7 @@ pub enum MetaEmbeddingSetting {
     Model,
     Revision,
     Pooling,
+    Normalize,
     ApiKey,
     Dimensions,
     DocumentTemplate,
@@ -1126,6 +1469,13 @@ pub enum MetaEmbeddingSetting {
     IndexingEmbedder,
     Distribution,
     BinaryQuantized,
+    Quantization,
+    Rescoring,
+    Chunking,
+    Sparse,
+    MultiVector,
+    IndexingFragment,
+    SearchFragment,
 }
 
 impl MetaEmbeddingSetting {
@@ -1136,6 +1486,7 @@ impl MetaEmbeddingSetting {
             Model => "model",
             Revision => "revision",
             Pooling => "pooling",
+            Normalize => "normalize",
             ApiKey => "apiKey",
             Dimensions => "dimensions",
             DocumentTemplate => "documentTemplate",
@@ -1148,6 +1499,13 @@ impl MetaEmbeddingSetting {
             IndexingEmbedder => "indexingEmbedder",
             Distribution => "distribution",
             BinaryQuantized => "binaryQuantized",
+            Quantization => "quantization",
+            Rescoring => "rescoring",
+            Chunking => "chunking",
+            Sparse => "sparse",
+            MultiVector => "multiVector",
+            IndexingFragment => "indexingFragment",
+            SearchFragment => "searchFragment",
         }
     }
 }
@@ -1161,6 +1519,7 @@ impl EmbeddingSettings {
         model: &Setting<String>,
         revision: &Setting<String>,
         pooling: &Setting<OverridePooling>,
+        normalize: &Setting<bool>,
         dimensions: &Setting<usize>,
         api_key: &Setting<String>,
         url: &Setting<String>,
@@ -1172,7 +1531,14 @@ impl EmbeddingSettings {
         search_embedder: &Setting<SubEmbeddingSettings>,
         indexing_embedder: &Setting<SubEmbeddingSettings>,
         binary_quantized: &Setting<bool>,
+        quantization: &Setting<Quantization>,
+        rescoring: &Setting<bool>,
         distribution: &Setting<DistributionShift>,
+        chunking: &Setting<Chunking>,
+        sparse: &Setting<bool>,
+        multi_vector: &Setting<bool>,
+        indexing_fragment: &Setting<serde_json::Value>,
+        search_fragment: &Setting<serde_json::Value>,
     ) -> Result<(), UserError> {
         Self::check_setting(embedder_name, source, MetaEmbeddingSetting::Model, context, model)?;
         Self::check_setting(
@@ -1189,6 +1555,13 @@ impl EmbeddingSettings {
             context,
             pooling,
         )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Normalize,
+            context,
+            normalize,
+        )?;
         Self::check_setting(
             embedder_name,
             source,
@@ -1254,13 +1627,123 @@ impl EmbeddingSettings {
             context,
             binary_quantized,
         )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Quantization,
+            context,
+            quantization,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Rescoring,
+            context,
+            rescoring,
+        )?;
+        match (binary_quantized, quantization) {
+            (Setting::Set(true), Setting::Set(Quantization::None | Quantization::Int8))
+            | (Setting::Set(false), Setting::Set(Quantization::Int8 | Quantization::Binary)) => {
+                return Err(UserError::InvalidSettingsEmbedder {
+                    embedder_name: embedder_name.to_owned(),
+                    message: "`binaryQuantized` conflicts with `quantization`.\n  - Hint: remove `binaryQuantized` and only use `quantization`.".to_owned(),
+                });
+            }
+            _ => {}
+        }
+        if let Setting::Set(true) = rescoring {
+            let quantization = vector_quantization(binary_quantized, quantization, rescoring);
+            if quantization.quantization != Quantization::Binary {
+                return Err(UserError::InvalidSettingsEmbedder {
+                    embedder_name: embedder_name.to_owned(),
+                    message: format!("`rescoring` is only available with the `binary` quantization, not with `{}`.", quantization.quantization),
+                });
+            }
+        }
         Self::check_setting(
             embedder_name,
             source,
             MetaEmbeddingSetting::Distribution,
             context,
             distribution,
-        )
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::Chunking,
+            context,
+            chunking,
+        )?;
+        if let Setting::Set(chunking) = chunking {
+            chunking.validate().map_err(|message| UserError::InvalidSettingsEmbedder {
+                embedder_name: embedder_name.to_owned(),
+                message,
+            })?;
+        }
+        Self::check_setting(embedder_name, source, MetaEmbeddingSetting::Sparse, context, sparse)?;
+        if let Setting::Set(true) = sparse {
+            if let Setting::Set(true) = binary_quantized {
+                return Err(UserError::InvalidSettingsEmbedder {
+                    embedder_name: embedder_name.to_owned(),
+                    message: "`binaryQuantized` cannot be enabled for a sparse embedder."
+                        .to_owned(),
+                });
+            }
+            if let Setting::Set(Quantization::Int8 | Quantization::Binary) = quantization {
+                return Err(UserError::InvalidSettingsEmbedder {
+                    embedder_name: embedder_name.to_owned(),
+                    message: "`quantization` cannot be enabled for a sparse embedder.".to_owned(),
+                });
+            }
+            if matches!(dimensions, Setting::Set(_)) {
+                return Err(UserError::InvalidSettingsEmbedder {
+                    embedder_name: embedder_name.to_owned(),
+                    message: "`dimensions` cannot be set for a sparse embedder, as sparse embeddings have no fixed dimensions.".to_owned(),
+                });
+            }
+        }
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::MultiVector,
+            context,
+            multi_vector,
+        )?;
+        if let (Setting::Set(true), Setting::Set(true)) = (sparse, multi_vector) {
+            return Err(UserError::InvalidSettingsEmbedder {
+                embedder_name: embedder_name.to_owned(),
+                message: "`sparse` and `multiVector` cannot be both enabled.".to_owned(),
+            });
+        }
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::IndexingFragment,
+            context,
+            indexing_fragment,
+        )?;
+        Self::check_setting(
+            embedder_name,
+            source,
+            MetaEmbeddingSetting::SearchFragment,
+            context,
+            search_fragment,
+        )?;
+        if let Setting::Set(_) = indexing_fragment {
+            if let Setting::Set(_) = document_template {
+                return Err(UserError::InvalidSettingsEmbedder {
+                    embedder_name: embedder_name.to_owned(),
+                    message: "`documentTemplate` cannot be set along with `indexingFragment`, as documents are rendered with the fragment.\n  - Hint: reset `indexingFragment` to `null` to render documents with `documentTemplate` again.".to_owned(),
+                });
+            }
+            if let Setting::Set(_) = chunking {
+                return Err(UserError::InvalidSettingsEmbedder {
+                    embedder_name: embedder_name.to_owned(),
+                    message: "`chunking` cannot be set along with `indexingFragment`, as documents rendered with a fragment are not chunked.".to_owned(),
+                });
+            }
+        }
+        Ok(())
     }
 
     pub(crate) fn allowed_sources_for_field(
@@ -1320,8 +1803,21 @@ impl EmbeddingSettings {
         use MetaEmbeddingSetting::*;
         use NestingContext::*;
         match (source, field, context) {
-            (_, Distribution | BinaryQuantized, NotNested) => FieldStatus::Allowed,
-            (_, Distribution | BinaryQuantized, _) => FieldStatus::Disallowed,
+            (UserProvided, Chunking, _) => FieldStatus::Disallowed,
+            (_, Chunking, NotNested) => FieldStatus::Allowed,
+            (_, Chunking, _) => FieldStatus::Disallowed,
+            (Rest, Sparse, NotNested) => FieldStatus::Allowed,
+            (_, Sparse, _) => FieldStatus::Disallowed,
+            (Rest | UserProvided, MultiVector, NotNested) => FieldStatus::Allowed,
+            (_, MultiVector, _) => FieldStatus::Disallowed,
+            (Rest, IndexingFragment | SearchFragment, NotNested) => FieldStatus::Allowed,
+            (_, IndexingFragment | SearchFragment, _) => FieldStatus::Disallowed,
+            (_, Distribution | BinaryQuantized | Quantization | Rescoring, NotNested) => {
+                FieldStatus::Allowed
+            }
+            (_, Distribution | BinaryQuantized | Quantization | Rescoring, _) => {
+                FieldStatus::Disallowed
+            }
             (_, DocumentTemplate | DocumentTemplateMaxBytes, Search) => FieldStatus::Disallowed,
             (
                 OpenAi,
@@ -1336,7 +1832,7 @@ impl EmbeddingSettings {
             ) => FieldStatus::Allowed,
             (
                 OpenAi,
-                Revision | Pooling | Request | Response | Headers | SearchEmbedder
+                Revision | Pooling | Normalize | Request | Response | Headers | SearchEmbedder
                 | IndexingEmbedder,
                 _,
             ) => FieldStatus::Disallowed,
@@ -1347,8 +1843,8 @@ impl EmbeddingSettings {
             ) => FieldStatus::Allowed,
             (
                 HuggingFace,
-                ApiKey | Dimensions | Url | Request | Response | Headers | SearchEmbedder
-                | IndexingEmbedder,
+                Normalize | ApiKey | Dimensions | Url | Request | Response | Headers
+                | SearchEmbedder | IndexingEmbedder,
                 _,
             ) => FieldStatus::Disallowed,
             (Ollama, Model, _) => FieldStatus::Mandatory,
@@ -1359,7 +1855,7 @@ impl EmbeddingSettings {
             ) => FieldStatus::Allowed,
             (
                 Ollama,
-                Revision | Pooling | Request | Response | Headers | SearchEmbedder
+                Revision | Pooling | Normalize | Request | Response | Headers | SearchEmbedder
                 | IndexingEmbedder,
                 _,
             ) => FieldStatus::Disallowed,
@@ -1370,6 +1866,7 @@ impl EmbeddingSettings {
                 Model
                 | Revision
                 | Pooling
+                | Normalize
                 | ApiKey
                 | DocumentTemplate
                 | DocumentTemplateMaxBytes
@@ -1392,9 +1889,11 @@ impl EmbeddingSettings {
                 | Headers,
                 _,
             ) => FieldStatus::Allowed,
-            (Rest, Model | Revision | Pooling | SearchEmbedder | IndexingEmbedder, _) => {
-                FieldStatus::Disallowed
-            }
+            (
+                Rest,
+                Model | Revision | Pooling | Normalize | SearchEmbedder | IndexingEmbedder,
+                _,
+            ) => FieldStatus::Disallowed,
             (Composite, SearchEmbedder | IndexingEmbedder, _) => FieldStatus::Mandatory,
             (Composite, Source, _) => FieldStatus::Allowed,
             (
@@ -1402,6 +1901,7 @@ impl EmbeddingSettings {
                 Model
                 | Revision
                 | Pooling
+                | Normalize
                 | ApiKey
                 | Dimensions
                 | DocumentTemplate
@@ -1412,6 +1912,23 @@ impl EmbeddingSettings {
                 | Headers,
                 _,
             ) => FieldStatus::Disallowed,
+            (Onnx, Model, _) => FieldStatus::Mandatory,
+            (
+                Onnx,
+                Source
+                | Pooling
+                | Normalize
+                | Dimensions
+                | DocumentTemplate
+                | DocumentTemplateMaxBytes,
+                _,
+            ) => FieldStatus::Allowed,
+            (
+                Onnx,
+                Revision | ApiKey | Url | Request | Response | Headers | SearchEmbedder
+                | IndexingEmbedder,
+                _,
+            ) => FieldStatus::Disallowed,
         }
     }
 
@@ -1455,7 +1972,8 @@ impl EmbeddingSettings {
                 EmbedderSource::OpenAi
                 | EmbedderSource::HuggingFace
                 | EmbedderSource::Ollama
-                | EmbedderSource::Rest,
+                | EmbedderSource::Rest
+                | EmbedderSource::Onnx,
             ) => Ok(()),
         }
     }
@@ -1484,6 +2002,7 @@ pub enum EmbedderSource {
     UserProvided,
     Rest,
     Composite,
+    Onnx,
 }
 
 impl std::fmt::Display for EmbedderSource {
@@ -1495,6 +2014,7 @@ impl std::fmt::Display for EmbedderSource {
             EmbedderSource::Ollama => "ollama",
             EmbedderSource::Rest => "rest",
             EmbedderSource::Composite => "composite",
+            EmbedderSource::Onnx => "onnx",
         };
         f.write_str(s)
     }
@@ -1517,6 +2037,7 @@ impl EmbeddingSettings {
             model: Setting::Set(model),
             revision: Setting::some_or_not_set(revision),
             pooling: Setting::Set(pooling),
+            normalize: Setting::NotSet,
             api_key: Setting::NotSet,
             dimensions: Setting::NotSet,
             document_template,
@@ -1529,6 +2050,13 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            rescoring: Setting::NotSet,
+            chunking: Setting::NotSet,
+            sparse: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            indexing_fragment: Setting::NotSet,
+            search_fragment: Setting::NotSet,
         }
     }
 
@@ -1549,6 +2077,7 @@ impl EmbeddingSettings {
             model: Setting::Set(embedding_model.name().to_owned()),
             revision: Setting::NotSet,
             pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
             api_key: Setting::some_or_not_set(api_key),
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
@@ -1561,6 +2090,13 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            rescoring: Setting::NotSet,
+            chunking: Setting::NotSet,
+            sparse: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            indexing_fragment: Setting::NotSet,
+            search_fragment: Setting::NotSet,
         }
     }
 
@@ -1581,6 +2117,7 @@ impl EmbeddingSettings {
             model: Setting::Set(embedding_model),
             revision: Setting::NotSet,
             pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
             api_key: Setting::some_or_not_set(api_key),
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
@@ -1593,11 +2130,22 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            rescoring: Setting::NotSet,
+            chunking: Setting::NotSet,
+            sparse: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            indexing_fragment: Setting::NotSet,
+            search_fragment: Setting::NotSet,
         }
     }
 
     fn from_user_provided(
-        super::manual::EmbedderOptions { dimensions, distribution }: super::manual::EmbedderOptions,
+        super::manual::EmbedderOptions {
+            dimensions,
+            distribution,
+            multi_vector,
+        }: super::manual::EmbedderOptions,
         quantized: Option<bool>,
     ) -> Self {
         Self {
@@ -1605,6 +2153,7 @@ impl EmbeddingSettings {
             model: Setting::NotSet,
             revision: Setting::NotSet,
             pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
             api_key: Setting::NotSet,
             dimensions: Setting::Set(dimensions),
             document_template: Setting::NotSet,
@@ -1617,6 +2166,13 @@ impl EmbeddingSettings {
             indexing_embedder: Setting::NotSet,
             distribution: Setting::some_or_not_set(distribution),
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            rescoring: Setting::NotSet,
+            chunking: Setting::NotSet,
+            sparse: Setting::NotSet,
+            multi_vector: if multi_vector { Setting::Set(true) } else { Setting::NotSet },
+            indexing_fragment: Setting::NotSet,
+            search_fragment: Setting::NotSet,
         }
     }
 
@@ -1629,6 +2185,11 @@ impl EmbeddingSettings {
             response,
             distribution,
             headers,
+            sparse,
+            multi_vector,
+            // retrieved from the prompt
+            indexing_fragment: _,
+            search_fragment,
         }: super::rest::EmbedderOptions,
         document_template: Setting<String>,
         document_template_max_bytes: Setting<usize>,
@@ -1639,6 +2200,7 @@ impl EmbeddingSettings {
             model: Setting::NotSet,
             revision: Setting::NotSet,
             pooling: Setting::NotSet,
+            normalize: Setting::NotSet,
             api_key: Setting::some_or_not_set(api_key),
             dimensions: Setting::some_or_not_set(dimensions),
             document_template,
@@ -1651,16 +2213,66 @@ impl EmbeddingSettings {
             search_embedder: Setting::NotSet,
             indexing_embedder: Setting::NotSet,
             binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            rescoring: Setting::NotSet,
+            chunking: Setting::NotSet,
+            sparse: if sparse { Setting::Set(true) } else { Setting::NotSet },
+            multi_vector: if multi_vector { Setting::Set(true) } else { Setting::NotSet },
+            indexing_fragment: Setting::NotSet,
+            search_fragment: Setting::some_or_not_set(search_fragment),
+        }
+    }
+
+    fn from_onnx(
+        super::onnx::EmbedderOptions {
+            model,
+            pooling,
+            normalize,
+            dimensions,
+            distribution,
+        }: super::onnx::EmbedderOptions,
+        document_template: Setting<String>,
+        document_template_max_bytes: Setting<usize>,
+        quantized: Option<bool>,
+    ) -> Self {
+        Self {
+            source: Setting::Set(EmbedderSource::Onnx),
+            model: Setting::Set(model),
+            revision: Setting::NotSet,
+            pooling: Setting::Set(pooling),
+            normalize: Setting::Set(normalize),
+            api_key: Setting::NotSet,
+            dimensions: Setting::some_or_not_set(dimensions),
+            document_template,
+            document_template_max_bytes,
+            url: Setting::NotSet,
+            request: Setting::NotSet,
+            response: Setting::NotSet,
+            headers: Setting::NotSet,
+            search_embedder: Setting::NotSet,
+            indexing_embedder: Setting::NotSet,
+            distribution: Setting::some_or_not_set(distribution),
+            binary_quantized: Setting::some_or_not_set(quantized),
+            quantization: Setting::NotSet,
+            rescoring: Setting::NotSet,
+            chunking: Setting::NotSet,
+            sparse: Setting::NotSet,
+            multi_vector: Setting::NotSet,
+            indexing_fragment: Setting::NotSet,
+            search_fragment: Setting::NotSet,
         }
     }
 }
 
 impl From<EmbeddingConfig> for EmbeddingSettings {
     fn from(value: EmbeddingConfig) -> Self {
-        let EmbeddingConfig { embedder_options, prompt, quantized } = value;
+        let EmbeddingConfig { embedder_options, prompt, quantized, quantization, rescoring } =
+            value;
         let document_template_max_bytes =
             Setting::Set(prompt.max_bytes.unwrap_or(default_max_bytes()).get());
-        match embedder_options {
+        let chunking = Setting::some_or_not_set(prompt.chunking);
+        let indexing_fragment = prompt.fragment;
+        let mut settings = match embedder_options {
             super::EmbedderOptions::HuggingFace(options) => Self::from_hugging_face(
                 options,
                 Setting::Set(prompt.template),
@@ -1688,6 +2300,12 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 document_template_max_bytes,
                 quantized,
             ),
+            super::EmbedderOptions::Onnx(options) => Self::from_onnx(
+                options,
+                Setting::Set(prompt.template),
+                document_template_max_bytes,
+                quantized,
+            ),
             super::EmbedderOptions::Composite(super::composite::EmbedderOptions {
                 search,
                 index,
@@ -1696,9 +2314,12 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 model: Setting::NotSet,
                 revision: Setting::NotSet,
                 pooling: Setting::NotSet,
+                normalize: Setting::NotSet,
                 api_key: Setting::NotSet,
                 dimensions: Setting::NotSet,
                 binary_quantized: Setting::some_or_not_set(quantized),
+                quantization: Setting::NotSet,
+                rescoring: Setting::NotSet,
                 document_template: Setting::NotSet,
                 document_template_max_bytes: Setting::NotSet,
                 url: Setting::NotSet,
@@ -1716,8 +2337,23 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                     Setting::Set(prompt.template),
                     document_template_max_bytes,
                 )),
+                chunking: Setting::NotSet,
+                sparse: Setting::NotSet,
+                multi_vector: Setting::NotSet,
+                indexing_fragment: Setting::NotSet,
+                search_fragment: Setting::NotSet,
             },
+        };
+        settings.chunking = chunking;
+        if let Some(fragment) = indexing_fragment {
+            // the documents are rendered with the fragment instead of the template
+            settings.document_template = Setting::NotSet;
+            settings.document_template_max_bytes = Setting::NotSet;
+            settings.indexing_fragment = Setting::Set(fragment);
         }
+        settings.quantization = Setting::some_or_not_set(quantization);
+        settings.rescoring = Setting::some_or_not_set(rescoring);
+        settings
     }
 }
 
@@ -1757,6 +2393,12 @@ impl SubEmbeddingSettings {
                 document_template_max_bytes,
                 None,
             ),
+            SubEmbedderOptions::Onnx(embedder_options) => EmbeddingSettings::from_onnx(
+                embedder_options,
+                document_template,
+                document_template_max_bytes,
+                None,
+            ),
         };
         settings.into()
     }
@@ -1769,6 +2411,7 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             document_template,
@@ -1778,15 +2421,23 @@ impl From<EmbeddingSettings> for SubEmbeddingSettings {
             response,
             headers,
             binary_quantized: _,
+            quantization: _,
+            rescoring: _,
             search_embedder: _,
             indexing_embedder: _,
             distribution: _,
+            chunking: _,
+            sparse: _,
+            multi_vector: _,
+            indexing_fragment: _,
+            search_fragment: _,
         } = value;
         Self {
             source,
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             document_template,
@@ -1811,6 +2462,7 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             document_template,
@@ -1821,11 +2473,20 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             distribution,
             headers,
             binary_quantized,
+            quantization,
+            rescoring,
             search_embedder,
             mut indexing_embedder,
+            chunking,
+            sparse,
+            multi_vector,
+            indexing_fragment,
+            search_fragment,
         } = value;
 
         this.quantized = binary_quantized.set();
+        this.quantization = quantization.set();
+        this.rescoring = rescoring.set();
         if let Some((template, document_template_max_bytes)) =
             match (document_template, &mut indexing_embedder) {
                 (Setting::Set(template), _) => Some((template, document_template_max_bytes)),
@@ -1846,8 +2507,16 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                 .and_then(NonZeroUsize::new)
                 .unwrap_or(default_max_bytes());
 
-            this.prompt = PromptData { template, max_bytes: Some(max_bytes) }
+            this.prompt = PromptData {
+                template,
+                max_bytes: Some(max_bytes),
+                chunking: chunking.set(),
+                fragment: None,
+            }
         }
+        let indexing_fragment = indexing_fragment.set();
+        let has_indexing_fragment = indexing_fragment.is_some();
+        this.prompt.fragment = indexing_fragment;
 
         if let Some(source) = source.set() {
             this.embedder_options = match source {
@@ -1860,10 +2529,12 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                 EmbedderSource::HuggingFace => {
                     SubEmbedderOptions::hugging_face(model, revision, pooling, distribution).into()
                 }
-                EmbedderSource::UserProvided => {
-                    SubEmbedderOptions::user_provided(dimensions.set().unwrap(), distribution)
-                        .into()
-                }
+                EmbedderSource::UserProvided => SubEmbedderOptions::user_provided(
+                    dimensions.set().unwrap(),
+                    distribution,
+                    multi_vector.set().unwrap_or_default(),
+                )
+                .into(),
                 EmbedderSource::Rest => SubEmbedderOptions::rest(
                     url.set().unwrap(),
                     api_key,
@@ -1872,6 +2543,18 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                     headers,
                     dimensions,
                     distribution,
+                    sparse.set().unwrap_or_default(),
+                    multi_vector.set().unwrap_or_default(),
+                    has_indexing_fragment,
+                    search_fragment.set(),
+                )
+                .into(),
+                EmbedderSource::Onnx => SubEmbedderOptions::onnx(
+                    model.set().unwrap(),
+                    pooling,
+                    normalize,
+                    dimensions,
+                    distribution,
                 )
                 .into(),
                 EmbedderSource::Composite => {
@@ -1904,6 +2587,7 @@ impl SubEmbedderOptions {
             model,
             revision,
             pooling,
+            normalize,
             api_key,
             dimensions,
             // retrieved by the EmbeddingConfig
@@ -1926,8 +2610,9 @@ impl SubEmbedderOptions {
                 Self::hugging_face(model, revision, pooling, distribution)
             }
             EmbedderSource::Ollama => Self::ollama(model, url, api_key, dimensions, distribution),
+            // multi-vector embedders cannot be nested in a composite embedder
             EmbedderSource::UserProvided => {
-                Self::user_provided(dimensions.set().unwrap(), distribution)
+                Self::user_provided(dimensions.set().unwrap(), distribution, false)
             }
             EmbedderSource::Rest => Self::rest(
                 url.set().unwrap(),
@@ -1937,7 +2622,15 @@ impl SubEmbedderOptions {
                 headers,
                 dimensions,
                 distribution,
+                // sparse, multi-vector and multimodal embedders cannot be nested in a composite embedder
+                false,
+                false,
+                false,
+                None,
             ),
+            EmbedderSource::Onnx => {
+                Self::onnx(model.set().unwrap(), pooling, normalize, dimensions, distribution)
+            }
             EmbedderSource::Composite => panic!("nested composite embedders"),
         }
     }
@@ -1992,12 +2685,18 @@ impl SubEmbedderOptions {
         options.distribution = distribution.set();
         SubEmbedderOptions::HuggingFace(options)
     }
-    fn user_provided(dimensions: usize, distribution: Setting<DistributionShift>) -> Self {
+    fn user_provided(
+        dimensions: usize,
+        distribution: Setting<DistributionShift>,
+        multi_vector: bool,
+    ) -> Self {
         Self::UserProvided(super::manual::EmbedderOptions {
             dimensions,
             distribution: distribution.set(),
+            multi_vector,
         })
     }
+    #[allow(clippy::too_many_arguments)]
     fn rest(
         url: String,
         api_key: Setting<String>,
@@ -2006,6 +2705,10 @@ impl SubEmbedderOptions {
         headers: Setting<BTreeMap<String, String>>,
         dimensions: Setting<usize>,
         distribution: Setting<DistributionShift>,
+        sparse: bool,
+        multi_vector: bool,
+        indexing_fragment: bool,
+        search_fragment: Option<serde_json::Value>,
     ) -> Self {
         Self::Rest(super::rest::EmbedderOptions {
             api_key: api_key.set(),
@@ -2015,6 +2718,10 @@ impl SubEmbedderOptions {
             response,
             distribution: distribution.set(),
             headers: headers.set().unwrap_or_default(),
+            sparse,
+            multi_vector,
+            indexing_fragment,
+            search_fragment,
         })
     }
     fn ollama(
@@ -2037,6 +2744,24 @@ impl SubEmbedderOptions {
         options.distribution = distribution.set();
         SubEmbedderOptions::Ollama(options)
     }
+    fn onnx(
+        model: String,
+        pooling: Setting<OverridePooling>,
+        normalize: Setting<bool>,
+        dimensions: Setting<usize>,
+        distribution: Setting<DistributionShift>,
+    ) -> Self {
+        let mut options = super::onnx::EmbedderOptions::new(model);
+        if let Some(pooling) = pooling.set() {
+            options.pooling = pooling;
+        }
+        if let Some(normalize) = normalize.set() {
+            options.normalize = normalize;
+        }
+        options.dimensions = dimensions.set();
+        options.distribution = distribution.set();
+        SubEmbedderOptions::Onnx(options)
+    }
 }
 
 impl From<SubEmbedderOptions> for EmbedderOptions {
@@ -2051,6 +2776,7 @@ impl From<SubEmbedderOptions> for EmbedderOptions {
                 Self::UserProvided(embedder_options)
             }
             SubEmbedderOptions::Rest(embedder_options) => Self::Rest(embedder_options),
+            SubEmbedderOptions::Onnx(embedder_options) => Self::Onnx(embedder_options),
         }
     }
 }
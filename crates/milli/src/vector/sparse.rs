@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, HashMap};
+
+use heed::types::{Bytes, SerdeJson};
+use heed::{Database, RoTxn, RwTxn};
+use roaring::RoaringBitmap;
+
+use crate::heed_codec::{U8BEU32Codec, U8StrBEU32Codec};
+use crate::{DocumentId, Index, BEU32};
+
+/// A sparse embedding, mapping the tokens of a vocabulary to their weights.
+///
+/// Tokens that are absent from the map have a weight of zero.
+pub type SparseEmbedding = BTreeMap<String, f32>;
+
+/// Maximum length in bytes of a token of a sparse embedding.
+///
+/// Tokens are part of the keys of the posting lists, so they must fit in an LMDB key.
+pub const MAX_TOKEN_LEN: usize = 256;
+
+/// Checks that the tokens and weights of a sparse embedding can be stored.
+pub fn validate(embedding: &SparseEmbedding) -> Result<(), String> {
+    for (token, weight) in embedding {
+        if token.is_empty() {
+            return Err("tokens must not be empty".to_string());
+        }
+        if token.len() > MAX_TOKEN_LEN {
+            return Err(format!(
+                "token `{token}` is longer than the maximum of {MAX_TOKEN_LEN} bytes"
+            ));
+        }
+        if token.contains('\0') {
+            return Err(format!("token `{}` contains a NUL byte", token.escape_debug()));
+        }
+        if !weight.is_finite() {
+            return Err(format!("token `{token}` has a non-finite weight `{weight}`"));
+        }
+    }
+    Ok(())
+}
+
+/// Merges the sparse embeddings of the chunks of a document into a single embedding,
+/// keeping the maximum weight of each token.
+pub fn merge_max(embeddings: impl IntoIterator<Item = SparseEmbedding>) -> SparseEmbedding {
+    let mut merged = SparseEmbedding::new();
+    for embedding in embeddings {
+        for (token, weight) in embedding {
+            merged
+                .entry(token)
+                .and_modify(|merged_weight| *merged_weight = merged_weight.max(weight))
+                .or_insert(weight);
+        }
+    }
+    merged
+}
+
+/// Computes the dot product of two sparse embeddings.
+pub fn dot_product(left: &SparseEmbedding, right: &SparseEmbedding) -> f32 {
+    let (small, large) = if left.len() <= right.len() { (left, right) } else { (right, left) };
+    small.iter().filter_map(|(token, weight)| large.get(token).map(|other| weight * other)).sum()
+}
+
+/// Maps a dot product between sparse embeddings, which is unbounded, to a similarity in `[0, 1)`.
+pub fn similarity(dot_product: f32) -> f32 {
+    let dot_product = dot_product.max(0.0);
+    dot_product / (1.0 + dot_product)
+}
+
+/// Inverted index of the sparse embeddings of an embedder.
+///
+/// The posting lists map each token to the documents whose embedding has a non-zero weight for it,
+/// while the documents database keeps the embedding of each document so that it can be removed.
+pub struct SparseVectorStore {
+    embedder_index: u8,
+    postings: Database<U8StrBEU32Codec, BEU32>,
+    documents: Database<U8BEU32Codec, SerdeJson<SparseEmbedding>>,
+}
+
+impl SparseVectorStore {
+    pub fn new(index: &Index, embedder_index: u8) -> Self {
+        Self {
+            embedder_index,
+            postings: index.vector_sparse_postings,
+            documents: index.vector_sparse_documents,
+        }
+    }
+
+    pub fn embedder_index(&self) -> u8 {
+        self.embedder_index
+    }
+
+    /// Overwrite the sparse embedding of a document.
+    ///
+    /// Tokens with a weight of zero are not stored.
+    pub fn add_item(
+        &self,
+        wtxn: &mut RwTxn,
+        docid: DocumentId,
+        embedding: &SparseEmbedding,
+    ) -> heed::Result<()> {
+        self.del_item(wtxn, docid)?;
+
+        let embedding: SparseEmbedding = embedding
+            .iter()
+            .filter(|(_, weight)| **weight != 0.0)
+            .map(|(token, weight)| (token.clone(), *weight))
+            .collect();
+        if embedding.is_empty() {
+            return Ok(());
+        }
+
+        for (token, weight) in &embedding {
+            // the weights are stored as the bits of the float, as heed has no codec for them.
+            self.postings.put(
+                wtxn,
+                &(self.embedder_index, token.as_str(), docid),
+                &weight.to_bits(),
+            )?;
+        }
+        self.documents.put(wtxn, &(self.embedder_index, docid), &embedding)
+    }
+
+    /// Delete the sparse embedding of a document, returning whether it had one.
+    pub fn del_item(&self, wtxn: &mut RwTxn, docid: DocumentId) -> heed::Result<bool> {
+        let Some(embedding) = self.documents.get(wtxn, &(self.embedder_index, docid))? else {
+            return Ok(false);
+        };
+        for token in embedding.keys() {
+            self.postings.delete(wtxn, &(self.embedder_index, token.as_str(), docid))?;
+        }
+        self.documents.delete(wtxn, &(self.embedder_index, docid))
+    }
+
+    /// Delete all the sparse embeddings of the embedder.
+    pub fn clear(&self, wtxn: &mut RwTxn) -> heed::Result<()> {
+        let prefix = [self.embedder_index];
+
+        let mut iter = self.postings.remap_key_type::<Bytes>().prefix_iter_mut(wtxn, &prefix)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference from the database.
+            unsafe { iter.del_current()? };
+        }
+        drop(iter);
+
+        let mut iter = self.documents.remap_key_type::<Bytes>().prefix_iter_mut(wtxn, &prefix)?;
+        while iter.next().transpose()?.is_some() {
+            // safety: we do not keep a reference from the database.
+            unsafe { iter.del_current()? };
+        }
+        Ok(())
+    }
+
+    pub fn item(&self, rtxn: &RoTxn, docid: DocumentId) -> heed::Result<Option<SparseEmbedding>> {
+        self.documents.get(rtxn, &(self.embedder_index, docid))
+    }
+
+    /// Returns the `limit` documents whose embedding has the highest dot product with the query,
+    /// in decreasing order of dot product.
+    ///
+    /// Documents that share no token with the query are never returned.
+    pub fn nns_by_vector(
+        &self,
+        rtxn: &RoTxn,
+        query: &SparseEmbedding,
+        limit: usize,
+        filter: Option<&RoaringBitmap>,
+    ) -> heed::Result<Vec<(DocumentId, f32)>> {
+        let mut scores: HashMap<DocumentId, f32> = HashMap::new();
+        let mut prefix = Vec::new();
+        for (token, query_weight) in query {
+            if *query_weight == 0.0 || token.contains('\0') {
+                continue;
+            }
+            prefix.clear();
+            prefix.push(self.embedder_index);
+            prefix.extend_from_slice(token.as_bytes());
+            prefix.push(0);
+
+            let postings = self
+                .postings
+                .remap_key_type::<Bytes>()
+                .prefix_iter(rtxn, &prefix)?
+                .remap_key_type::<U8StrBEU32Codec>();
+            for result in postings {
+                let ((_, _, docid), weight) = result?;
+                if filter.is_some_and(|filter| !filter.contains(docid)) {
+                    continue;
+                }
+                *scores.entry(docid).or_default() += query_weight * f32::from_bits(weight);
+            }
+        }
+
+        let mut scores: Vec<_> = scores.into_iter().collect();
+        scores.sort_unstable_by(|(left_docid, left), (right_docid, right)| {
+            right.total_cmp(left).then(left_docid.cmp(right_docid))
+        });
+        scores.truncate(limit);
+        Ok(scores)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    fn embedding(entries: &[(&str, f32)]) -> SparseEmbedding {
+        entries.iter().map(|(token, weight)| (token.to_string(), *weight)).collect()
+    }
+
+    #[test]
+    fn merge_keeps_the_maximum_weight() {
+        let merged = merge_max([
+            embedding(&[("kefir", 0.5), ("dog", 1.0)]),
+            embedding(&[("kefir", 2.0), ("cat", 0.25)]),
+        ]);
+        assert_eq!(merged, embedding(&[("cat", 0.25), ("dog", 1.0), ("kefir", 2.0)]));
+    }
+
+    #[test]
+    fn dot_product_only_counts_shared_tokens() {
+        let left = embedding(&[("kefir", 0.5), ("dog", 1.0)]);
+        let right = embedding(&[("kefir", 2.0), ("cat", 3.0)]);
+        assert_eq!(dot_product(&left, &right), 1.0);
+        assert_eq!(dot_product(&right, &left), 1.0);
+        assert_eq!(dot_product(&left, &embedding(&[("cat", 1.0)])), 0.0);
+    }
+
+    #[test]
+    fn similarity_is_bounded() {
+        assert_eq!(similarity(0.0), 0.0);
+        assert_eq!(similarity(-3.0), 0.0);
+        assert_eq!(similarity(1.0), 0.5);
+        assert!(similarity(1e30) <= 1.0);
+    }
+
+    #[test]
+    fn validation_rejects_unstorable_tokens() {
+        assert!(validate(&embedding(&[("kefir", 0.5)])).is_ok());
+        assert!(validate(&embedding(&[("", 0.5)])).is_err());
+        assert!(validate(&embedding(&[("ke\0fir", 0.5)])).is_err());
+        assert!(validate(&embedding(&[("kefir", f32::NAN)])).is_err());
+        assert!(validate(&embedding(&[(&"k".repeat(MAX_TOKEN_LEN + 1), 0.5)])).is_err());
+    }
+
+    #[test]
+    fn store_ranks_documents_by_dot_product() {
+        let index = TempIndex::new();
+        let mut wtxn = index.write_txn().unwrap();
+        let store = SparseVectorStore::new(&index, 0);
+        let other_store = SparseVectorStore::new(&index, 1);
+
+        store.add_item(&mut wtxn, 0, &embedding(&[("kefir", 1.0), ("dog", 1.0)])).unwrap();
+        store.add_item(&mut wtxn, 1, &embedding(&[("kefir", 3.0)])).unwrap();
+        store.add_item(&mut wtxn, 2, &embedding(&[("cat", 2.0), ("kefirs", 5.0)])).unwrap();
+        other_store.add_item(&mut wtxn, 3, &embedding(&[("kefir", 10.0)])).unwrap();
+
+        let query = embedding(&[("kefir", 1.0), ("dog", 0.5)]);
+        let results = store.nns_by_vector(&wtxn, &query, 10, None).unwrap();
+        assert_eq!(results, vec![(1, 3.0), (0, 1.5)]);
+
+        let filter = RoaringBitmap::from_iter([0]);
+        let results = store.nns_by_vector(&wtxn, &query, 10, Some(&filter)).unwrap();
+        assert_eq!(results, vec![(0, 1.5)]);
+
+        // overwriting a document removes its previous tokens
+        store.add_item(&mut wtxn, 1, &embedding(&[("dog", 1.0), ("kefir", 0.0)])).unwrap();
+        let results = store.nns_by_vector(&wtxn, &query, 10, None).unwrap();
+        assert_eq!(results, vec![(0, 1.5), (1, 0.5)]);
+
+        assert!(store.del_item(&mut wtxn, 0).unwrap());
+        assert!(!store.del_item(&mut wtxn, 0).unwrap());
+        let results = store.nns_by_vector(&wtxn, &query, 10, None).unwrap();
+        assert_eq!(results, vec![(1, 0.5)]);
+
+        store.clear(&mut wtxn).unwrap();
+        assert!(store.nns_by_vector(&wtxn, &query, 10, None).unwrap().is_empty());
+        assert_eq!(other_store.nns_by_vector(&wtxn, &query, 10, None).unwrap(), vec![(3, 10.0)]);
+    }
+}